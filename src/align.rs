@@ -1,16 +1,19 @@
 //! Alignment module for ensuring consistent package versions across a repository.
 //!
 //! This module provides functionality to find the highest version of each package
-//! used across multiple dependency files and update all occurrences to that version.
+//! used across multiple dependency files and update all occurrences to that version,
+//! to the lowest one (`align --lowest`), or to a version chosen with `align --to`.
 
 use crate::updater::{
-    CargoTomlUpdater, CsprojUpdater, FileType, GemfileUpdater, GithubActionsUpdater, GoModUpdater,
-    Lang, MiseUpdater, PackageJsonUpdater, ParsedDependency, PreCommitUpdater, PyProjectUpdater,
-    RequirementsUpdater, TerraformUpdater, Updater,
+    CargoTomlUpdater, ComposerUpdater, CondaEnvUpdater, CsprojUpdater, DockerfileUpdater, FileType,
+    GemfileUpdater, GithubActionsUpdater, GoModUpdater, GradleCatalogUpdater, Lang, MiseUpdater,
+    PackageJsonUpdater, ParsedDependency, PipfileUpdater, PnpmWorkspaceUpdater, PreCommitUpdater,
+    PubspecUpdater, PyProjectUpdater, RequirementsUpdater, RuntimeVersionUpdater, SetupPyUpdater,
+    TerraformUpdater, UnconstrainedDependency, Updater,
 };
 use crate::version::{TagVersion, is_stable_pep440};
 use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
@@ -39,6 +42,34 @@ pub struct PackageOccurrence {
     /// are still included so the audit path can inspect them, but they must not
     /// participate in alignment comparisons or update attempts.
     pub is_bumpable: bool,
+    /// Whether the version is inherited from the workspace root's
+    /// `[workspace.dependencies]`. Such occurrences follow the root
+    /// definition and are never misaligned on their own.
+    pub inherits_workspace: bool,
+}
+
+/// The version `align` moves every occurrence of a package to.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum AlignTarget {
+    /// The highest stable version found across occurrences (default).
+    #[default]
+    Highest,
+    /// The lowest stable version found across occurrences (`--lowest`),
+    /// downgrading every higher occurrence.
+    Lowest,
+    /// A version chosen with `--to`, applied even where it is a downgrade.
+    Exact(String),
+}
+
+impl AlignTarget {
+    /// How the target is labelled next to its version in text output.
+    pub fn label(&self) -> &'static str {
+        match self {
+            AlignTarget::Highest => "highest",
+            AlignTarget::Lowest => "lowest",
+            AlignTarget::Exact(_) => "requested",
+        }
+    }
 }
 
 /// Result of alignment analysis for a single package
@@ -48,6 +79,9 @@ pub struct PackageAlignment {
     pub package_name: String,
     /// The highest version found across all occurrences
     pub highest_version: String,
+    /// The version occurrences are aligned to: `highest_version`, unless
+    /// another [`AlignTarget`] chose it
+    pub target_version: String,
     /// All occurrences of this package
     pub occurrences: Vec<PackageOccurrence>,
     /// Language/ecosystem of this package
@@ -55,18 +89,42 @@ pub struct PackageAlignment {
 }
 
 impl PackageAlignment {
-    /// Returns true if any occurrence is misaligned (not at highest version)
+    /// Returns true if any occurrence is misaligned (not at the target version)
     pub fn has_misalignment(&self) -> bool {
+        self.occurrences.iter().any(|o| self.is_misaligned(o))
+    }
+
+    /// Returns only the misaligned occurrences (excluding those at the target version, with constraints, or not bumpable)
+    pub fn misaligned_occurrences(&self) -> Vec<&PackageOccurrence> {
         self.occurrences
             .iter()
-            .any(|o| o.is_bumpable && !o.has_upper_bound && o.version != self.highest_version)
+            .filter(|o| self.is_misaligned(o))
+            .collect()
     }
 
-    /// Returns only the misaligned occurrences (excluding those at highest version, with constraints, or not bumpable)
-    pub fn misaligned_occurrences(&self) -> Vec<&PackageOccurrence> {
+    /// Whether aligning `occurrence` moves it to an older version.
+    pub fn is_downgrade(&self, occurrence: &PackageOccurrence) -> bool {
+        compare_versions(&occurrence.version, &self.target_version, self.lang)
+            == std::cmp::Ordering::Greater
+    }
+
+    /// Whether `occurrence` would be rewritten to the target version.
+    pub fn is_misaligned(&self, occurrence: &PackageOccurrence) -> bool {
+        occurrence.is_bumpable
+            && !occurrence.has_upper_bound
+            && occurrence.version != self.target_version
+    }
+
+    /// Constrained occurrences the target version falls below: they are
+    /// skipped, and the target would violate their lower bound.
+    pub fn constraint_violations(&self) -> Vec<&PackageOccurrence> {
         self.occurrences
             .iter()
-            .filter(|o| o.is_bumpable && !o.has_upper_bound && o.version != self.highest_version)
+            .filter(|o| o.has_upper_bound)
+            .filter(|o| {
+                compare_versions(&self.target_version, &o.version, self.lang)
+                    == std::cmp::Ordering::Less
+            })
             .collect()
     }
 }
@@ -87,7 +145,11 @@ fn get_updater(file_type: FileType) -> Box<dyn Updater> {
     match file_type {
         FileType::Requirements => Box::new(RequirementsUpdater::new()),
         FileType::PyProject => Box::new(PyProjectUpdater::new()),
+        FileType::Pipfile => Box::new(PipfileUpdater::new()),
+        FileType::SetupPy => Box::new(SetupPyUpdater::new()),
+        FileType::CondaEnv => Box::new(CondaEnvUpdater::new()),
         FileType::PackageJson => Box::new(PackageJsonUpdater::new()),
+        FileType::PnpmWorkspace => Box::new(PnpmWorkspaceUpdater::new()),
         FileType::CargoToml => Box::new(CargoTomlUpdater::new()),
         FileType::GoMod => Box::new(GoModUpdater::new()),
         FileType::Gemfile => Box::new(GemfileUpdater::new()),
@@ -96,6 +158,11 @@ fn get_updater(file_type: FileType) -> Box<dyn Updater> {
         FileType::PreCommitConfig => Box::new(PreCommitUpdater::new()),
         FileType::MiseToml | FileType::ToolVersions => Box::new(MiseUpdater::new()),
         FileType::TerraformTf => Box::new(TerraformUpdater::new()),
+        FileType::Dockerfile => Box::new(DockerfileUpdater::new()),
+        FileType::ComposerJson => Box::new(ComposerUpdater::new()),
+        FileType::GradleCatalog => Box::new(GradleCatalogUpdater::new()),
+        FileType::Pubspec => Box::new(PubspecUpdater::new()),
+        FileType::Nvmrc | FileType::PythonVersion => Box::new(RuntimeVersionUpdater::new()),
     }
 }
 
@@ -109,9 +176,23 @@ fn to_occurrence(dep: &ParsedDependency, path: &Path, file_type: FileType) -> Pa
         has_upper_bound: dep.has_upper_bound,
         original_name: dep.name.clone(),
         is_bumpable: dep.is_bumpable,
+        inherits_workspace: dep.inherits_workspace,
     }
 }
 
+/// Parse the dependencies declared in a single file with the updater for its type.
+pub fn parse_file_dependencies(path: &Path, file_type: FileType) -> Result<Vec<ParsedDependency>> {
+    get_updater(file_type).parse_dependencies(path)
+}
+
+/// Dependencies a single file declares without a lower bound.
+pub fn find_unconstrained_dependencies(
+    path: &Path,
+    file_type: FileType,
+) -> Result<Vec<UnconstrainedDependency>> {
+    get_updater(file_type).unconstrained_dependencies(path)
+}
+
 /// Scan all dependency files and collect package versions grouped by package name and language
 pub fn scan_packages(
     files: &[(PathBuf, FileType)],
@@ -137,11 +218,22 @@ pub fn scan_packages(
 
 /// Find the highest version for each package and identify misalignments
 pub fn find_alignments(packages: HashMap<(String, Lang), Vec<PackageOccurrence>>) -> AlignResult {
+    find_alignments_to(packages, &AlignTarget::Highest)
+}
+
+/// Like [`find_alignments`], aligning each package to `target`.
+///
+/// With [`AlignTarget::Exact`] a package declared only once is still
+/// reported, since its one occurrence may differ from the chosen version.
+pub fn find_alignments_to(
+    packages: HashMap<(String, Lang), Vec<PackageOccurrence>>,
+    target: &AlignTarget,
+) -> AlignResult {
     let mut result = AlignResult::default();
 
     for ((package_name, lang), occurrences) in packages {
         // Skip packages that only appear once (already "aligned")
-        if occurrences.len() <= 1 {
+        if occurrences.len() <= 1 && *target == AlignTarget::Highest {
             continue;
         }
 
@@ -149,9 +241,17 @@ pub fn find_alignments(packages: HashMap<(String, Lang), Vec<PackageOccurrence>>
         let highest = find_highest_version(&occurrences, lang);
 
         if let Some(highest_version) = highest {
+            let target_version = match target {
+                AlignTarget::Highest => highest_version.clone(),
+                // Exists whenever a highest version does: same candidates.
+                AlignTarget::Lowest => find_lowest_version(&occurrences, lang)
+                    .unwrap_or_else(|| highest_version.clone()),
+                AlignTarget::Exact(version) => version.clone(),
+            };
             let alignment = PackageAlignment {
                 package_name: package_name.clone(),
-                highest_version: highest_version.clone(),
+                highest_version,
+                target_version,
                 occurrences,
                 lang,
             };
@@ -172,22 +272,155 @@ pub fn find_alignments(packages: HashMap<(String, Lang), Vec<PackageOccurrence>>
     result
 }
 
-/// Find the highest stable version among occurrences
-fn find_highest_version(occurrences: &[PackageOccurrence], lang: Lang) -> Option<String> {
+/// A package declared at diverging versions by different kinds of manifest
+/// in one directory, e.g. `requirements.txt` and `pyproject.toml` mid-migration.
+#[derive(Debug, Clone)]
+pub struct DuplicateDeclaration {
+    /// Name of the package (lowercased, as keyed by [`scan_packages`])
+    pub package_name: String,
+    /// Directory holding the conflicting manifests
+    pub directory: PathBuf,
+    /// One occurrence per declaration, sorted by file path
+    pub occurrences: Vec<PackageOccurrence>,
+}
+
+/// Find packages that two or more manifest types in the same directory
+/// declare at different versions.
+///
+/// Several files of one type (`requirements.txt` and `requirements-dev.txt`)
+/// are left to `align`; this only flags the same project describing its
+/// dependencies twice. Reporting-only: nothing is rewritten.
+pub fn find_duplicate_declarations(
+    packages: &HashMap<(String, Lang), Vec<PackageOccurrence>>,
+) -> Vec<DuplicateDeclaration> {
+    let mut duplicates = Vec::new();
+
+    for ((package_name, _), occurrences) in packages {
+        let mut by_dir: HashMap<&Path, Vec<&PackageOccurrence>> = HashMap::new();
+        for occurrence in occurrences {
+            let dir = occurrence.file_path.parent().unwrap_or(Path::new(""));
+            by_dir.entry(dir).or_default().push(occurrence);
+        }
+
+        for (dir, group) in by_dir {
+            let first = group[0];
+            let mixed_types = group.iter().any(|o| o.file_type != first.file_type);
+            let diverging = group.iter().any(|o| o.version != first.version);
+            if !(mixed_types && diverging) {
+                continue;
+            }
+            let mut occurrences: Vec<PackageOccurrence> = group.into_iter().cloned().collect();
+            occurrences.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+            duplicates.push(DuplicateDeclaration {
+                package_name: package_name.clone(),
+                directory: dir.to_path_buf(),
+                occurrences,
+            });
+        }
+    }
+
+    duplicates
+        .sort_by(|a, b| (&a.directory, &a.package_name).cmp(&(&b.directory, &b.package_name)));
+    duplicates
+}
+
+/// A package that requirements files joined by `-r` includes declare at
+/// different versions. pip installs the whole include tree at once, so the
+/// declarations conflict rather than describing separate environments.
+#[derive(Debug, Clone)]
+pub struct IncludeConflict {
+    pub package_name: String,
+    /// The requirements file whose includes pull the declarations together.
+    pub root: PathBuf,
+    /// One occurrence per declaration, in include order.
+    pub occurrences: Vec<PackageOccurrence>,
+}
+
+/// Find packages declared at different versions within one `-r` include
+/// tree. Each tree is checked from its outermost requirements file; a file
+/// that another listed file includes is only checked as part of that tree.
+/// Reporting-only: nothing is rewritten.
+pub fn find_include_conflicts(files: &[(PathBuf, FileType)]) -> Vec<IncludeConflict> {
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let trees: Vec<(&PathBuf, Vec<PathBuf>)> = files
+        .iter()
+        .filter(|(_, file_type)| *file_type == FileType::Requirements)
+        .map(|(path, _)| (path, RequirementsUpdater::included_requirement_files(path)))
+        .collect();
+    let included_elsewhere: HashSet<PathBuf> = trees
+        .iter()
+        .flat_map(|(_, included)| included.iter().map(|path| canonical(path)))
+        .collect();
+
+    let mut conflicts = Vec::new();
+    for (root, included) in trees {
+        if included.is_empty() || included_elsewhere.contains(&canonical(root)) {
+            continue;
+        }
+        let mut by_package: Vec<(String, Vec<PackageOccurrence>)> = Vec::new();
+        for path in std::iter::once(root).chain(&included) {
+            let Ok(deps) = parse_file_dependencies(path, FileType::Requirements) else {
+                continue;
+            };
+            for dep in deps.iter().filter(|dep| dep.is_bumpable) {
+                let key = dep.name.to_lowercase();
+                let occurrence = to_occurrence(dep, path, FileType::Requirements);
+                match by_package.iter_mut().find(|(name, _)| *name == key) {
+                    Some((_, occurrences)) => occurrences.push(occurrence),
+                    None => by_package.push((key, vec![occurrence])),
+                }
+            }
+        }
+        for (package_name, occurrences) in by_package {
+            let diverging = occurrences
+                .iter()
+                .any(|o| o.version != occurrences[0].version);
+            let across_files = occurrences
+                .iter()
+                .any(|o| o.file_path != occurrences[0].file_path);
+            if diverging && across_files {
+                conflicts.push(IncludeConflict {
+                    package_name,
+                    root: root.clone(),
+                    occurrences,
+                });
+            }
+        }
+    }
+    conflicts
+}
+
+/// Occurrences whose version can be an alignment target.
+fn target_candidates(
+    occurrences: &[PackageOccurrence],
+    lang: Lang,
+) -> impl Iterator<Item = &PackageOccurrence> {
     occurrences
         .iter()
         .filter(|o| o.is_bumpable) // Skip commit-pinned refs (e.g. Go pseudo-versions)
         .filter(|o| !o.has_upper_bound) // Skip constrained versions
-        .filter(|o| is_stable_version(&o.version, lang)) // Skip pre-releases
+        .filter(move |o| is_stable_version(&o.version, lang)) // Skip pre-releases
+}
+
+/// Find the highest stable version among occurrences
+fn find_highest_version(occurrences: &[PackageOccurrence], lang: Lang) -> Option<String> {
+    target_candidates(occurrences, lang)
         .max_by(|a, b| compare_versions(&a.version, &b.version, lang))
         .map(|o| o.version.clone())
 }
 
+/// Find the lowest stable version among occurrences
+fn find_lowest_version(occurrences: &[PackageOccurrence], lang: Lang) -> Option<String> {
+    target_candidates(occurrences, lang)
+        .min_by(|a, b| compare_versions(&a.version, &b.version, lang))
+        .map(|o| o.version.clone())
+}
+
 /// Check if a version is stable (not a pre-release)
 fn is_stable_version(version: &str, lang: Lang) -> bool {
     match lang {
         Lang::Python => is_stable_pep440(version),
-        Lang::Node | Lang::Rust | Lang::Go | Lang::DotNet => {
+        Lang::Node | Lang::Rust | Lang::Go | Lang::DotNet | Lang::Dart => {
             // Semver pre-release indicator: hyphen followed by identifier
             !version.contains('-')
         }
@@ -198,10 +431,15 @@ fn is_stable_version(version: &str, lang: Lang) -> bool {
                 && !v.contains(".beta")
                 && !v.contains(".alpha")
         }
-        Lang::Actions | Lang::PreCommit | Lang::Mise | Lang::Terraform => {
+        Lang::Actions | Lang::PreCommit | Lang::Mise | Lang::Terraform | Lang::Runtime => {
             let v = version.strip_prefix('v').unwrap_or(version);
             !v.contains('-')
         }
+        // A `-slim` or `-alpine` suffix is an image variant, not a pre-release.
+        Lang::Docker => crate::registry::tag_shape(version).is_some(),
+        // `RC1`, `beta2` and friends; `-p1` patch releases are stable.
+        Lang::Php => crate::registry::composer_version(version).is_some_and(|v| v.pre.is_empty()),
+        Lang::Jvm => !crate::registry::is_maven_prerelease(version),
     }
 }
 
@@ -213,13 +451,32 @@ fn is_stable_version(version: &str, lang: Lang) -> bool {
 pub(crate) fn compare_versions(a: &str, b: &str, lang: Lang) -> std::cmp::Ordering {
     match lang {
         Lang::Python => compare_pep440(a, b),
-        Lang::Node | Lang::Rust | Lang::Ruby | Lang::DotNet => compare_semver(a, b),
+        Lang::Node | Lang::Rust | Lang::Ruby | Lang::DotNet | Lang::Dart => compare_semver(a, b),
         Lang::Go => compare_go_version(a, b),
-        Lang::Actions | Lang::PreCommit | Lang::Mise | Lang::Terraform => {
+        Lang::Actions | Lang::PreCommit | Lang::Mise | Lang::Terraform | Lang::Runtime => {
             let clean_a = a.trim_start_matches('v');
             let clean_b = b.trim_start_matches('v');
             compare_semver(clean_a, clean_b)
         }
+        Lang::Docker => compare_image_tags(a, b),
+        Lang::Php => match (
+            crate::registry::composer_version(a),
+            crate::registry::composer_version(b),
+        ) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => compare_semver(a, b),
+        },
+        Lang::Jvm => crate::registry::maven_version_cmp(a, b),
+    }
+}
+
+/// Compare image tags by their release segments (`3.12-slim` > `3.9-slim`),
+/// falling back to string order for tags that are not versions.
+fn compare_image_tags(a: &str, b: &str) -> std::cmp::Ordering {
+    use crate::registry::tag_shape;
+    match (tag_shape(a), tag_shape(b)) {
+        (Some(sa), Some(sb)) => sa.release.cmp(&sb.release),
+        _ => a.cmp(b),
     }
 }
 
@@ -306,6 +563,7 @@ mod tests {
         let alignment = PackageAlignment {
             package_name: "requests".to_string(),
             highest_version: "2.31.0".to_string(),
+            target_version: "2.31.0".to_string(),
             lang: Lang::Python,
             occurrences: vec![
                 PackageOccurrence {
@@ -316,6 +574,7 @@ mod tests {
                     has_upper_bound: false,
                     original_name: "requests".to_string(),
                     is_bumpable: true,
+                    inherits_workspace: false,
                 },
                 PackageOccurrence {
                     file_path: PathBuf::from("requirements-dev.txt"),
@@ -325,6 +584,7 @@ mod tests {
                     has_upper_bound: false,
                     original_name: "requests".to_string(),
                     is_bumpable: true,
+                    inherits_workspace: false,
                 },
             ],
         };
@@ -338,6 +598,7 @@ mod tests {
         let alignment = PackageAlignment {
             package_name: "django".to_string(),
             highest_version: "4.2.0".to_string(),
+            target_version: "4.2.0".to_string(),
             lang: Lang::Python,
             occurrences: vec![
                 PackageOccurrence {
@@ -348,6 +609,7 @@ mod tests {
                     has_upper_bound: true, // Has constraint, should be skipped
                     original_name: "django".to_string(),
                     is_bumpable: true,
+                    inherits_workspace: false,
                 },
                 PackageOccurrence {
                     file_path: PathBuf::from("requirements-dev.txt"),
@@ -357,6 +619,7 @@ mod tests {
                     has_upper_bound: false,
                     original_name: "django".to_string(),
                     is_bumpable: true,
+                    inherits_workspace: false,
                 },
             ],
         };
@@ -365,6 +628,122 @@ mod tests {
         assert!(!alignment.has_misalignment());
     }
 
+    #[test]
+    fn test_find_alignments_to_exact_version() {
+        let occurrence = |file: &str, version: &str| PackageOccurrence {
+            file_path: PathBuf::from(file),
+            file_type: FileType::Requirements,
+            version: version.to_string(),
+            line_number: Some(1),
+            has_upper_bound: false,
+            original_name: "requests".to_string(),
+            is_bumpable: true,
+            inherits_workspace: false,
+        };
+        let packages =
+            |occurrences| HashMap::from([(("requests".to_string(), Lang::Python), occurrences)]);
+        let target = AlignTarget::Exact("2.28.0".to_string());
+
+        // Both the lower and the higher occurrence move to the chosen version.
+        let result = find_alignments_to(
+            packages(vec![
+                occurrence("a.txt", "2.25.0"),
+                occurrence("b.txt", "2.31.0"),
+            ]),
+            &target,
+        );
+        let alignment = &result.packages[0];
+        assert_eq!(alignment.highest_version, "2.31.0");
+        assert_eq!(alignment.target_version, "2.28.0");
+        assert_eq!(result.misaligned_count, 2);
+
+        // A single occurrence is still aligned to an explicit target.
+        let result = find_alignments_to(packages(vec![occurrence("a.txt", "2.25.0")]), &target);
+        assert_eq!(result.misaligned_count, 1);
+        let result = find_alignments(packages(vec![occurrence("a.txt", "2.25.0")]));
+        assert!(result.packages.is_empty());
+    }
+
+    #[test]
+    fn test_find_alignments_to_lowest_version() {
+        let occurrence = |file: &str, version: &str, has_upper_bound: bool| PackageOccurrence {
+            file_path: PathBuf::from(file),
+            file_type: FileType::Requirements,
+            version: version.to_string(),
+            line_number: Some(1),
+            has_upper_bound,
+            original_name: "requests".to_string(),
+            is_bumpable: true,
+            inherits_workspace: false,
+        };
+        let packages = HashMap::from([(
+            ("requests".to_string(), Lang::Python),
+            vec![
+                occurrence("a.txt", "2.31.0", false),
+                occurrence("b.txt", "2.28.0", false),
+                occurrence("c.txt", "2.30.0", false),
+                // Constrained and pre-release versions never become the target.
+                occurrence("d.txt", "2.0.0", true),
+                occurrence("e.txt", "2.27.0rc1", false),
+            ],
+        )]);
+
+        let result = find_alignments_to(packages, &AlignTarget::Lowest);
+        let alignment = &result.packages[0];
+        assert_eq!(alignment.highest_version, "2.31.0");
+        assert_eq!(alignment.target_version, "2.28.0");
+        let misaligned = alignment.misaligned_occurrences();
+        let downgrades: Vec<_> = misaligned
+            .iter()
+            .filter(|o| alignment.is_downgrade(o))
+            .map(|o| o.file_path.to_str().unwrap())
+            .collect();
+        assert_eq!(downgrades, vec!["a.txt", "c.txt"]);
+        assert_eq!(
+            misaligned.len(),
+            3,
+            "the pre-release moves up to the target"
+        );
+    }
+
+    /// Members inheriting a `[workspace.dependencies]` entry share the root's
+    /// version and follow it: only the root and independent declarations can
+    /// be misaligned.
+    #[test]
+    fn test_find_alignments_groups_workspace_inherited_with_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &root,
+            "[workspace]\nmembers = [\"a\", \"b\"]\n\n[workspace.dependencies]\nserde = \"1.0.200\"\n",
+        )
+        .unwrap();
+        let mut files = vec![(root, FileType::CargoToml)];
+        for (member, spec) in [("a", "{ workspace = true }"), ("b", "\"1.0.100\"")] {
+            std::fs::create_dir_all(dir.path().join(member)).unwrap();
+            let path = dir.path().join(member).join("Cargo.toml");
+            std::fs::write(&path, format!("[dependencies]\nserde = {spec}\n")).unwrap();
+            files.push((path, FileType::CargoToml));
+        }
+
+        let result = find_alignments(scan_packages(&files).unwrap());
+        let serde = &result.packages[0];
+        assert_eq!(serde.highest_version, "1.0.200");
+        assert_eq!(serde.occurrences.len(), 3);
+        let inherited: Vec<_> = serde
+            .occurrences
+            .iter()
+            .filter(|o| o.inherits_workspace)
+            .collect();
+        assert_eq!(inherited.len(), 1);
+        assert_eq!(inherited[0].version, "1.0.200");
+
+        let misaligned = serde.misaligned_occurrences();
+        assert_eq!(misaligned.len(), 1);
+        assert!(misaligned[0].file_path.ends_with("b/Cargo.toml"));
+        assert_eq!(result.misaligned_count, 1);
+    }
+
     /// scan_packages must preserve the original package-name casing in each
     /// PackageOccurrence so that OSV audit queries use the correct name.
     ///
@@ -536,4 +915,105 @@ mod tests {
         );
         assert_eq!(compare_semver("1.0.0-beta", "1.0.0"), Ordering::Less);
     }
+
+    fn occurrence(path: &str, file_type: FileType, version: &str) -> PackageOccurrence {
+        PackageOccurrence {
+            file_path: PathBuf::from(path),
+            file_type,
+            version: version.to_string(),
+            line_number: Some(1),
+            has_upper_bound: false,
+            original_name: "requests".to_string(),
+            is_bumpable: true,
+            inherits_workspace: false,
+        }
+    }
+
+    #[test]
+    fn test_find_duplicate_declarations_flags_diverging_manifest_types() {
+        let mut packages = HashMap::new();
+        packages.insert(
+            ("requests".to_string(), Lang::Python),
+            vec![
+                occurrence("app/requirements.txt", FileType::Requirements, "2.28.0"),
+                occurrence("app/pyproject.toml", FileType::PyProject, "2.31.0"),
+                occurrence("lib/pyproject.toml", FileType::PyProject, "2.20.0"),
+            ],
+        );
+
+        let duplicates = find_duplicate_declarations(&packages);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].package_name, "requests");
+        assert_eq!(duplicates[0].directory, PathBuf::from("app"));
+        assert_eq!(
+            duplicates[0].occurrences[0].file_path,
+            PathBuf::from("app/pyproject.toml")
+        );
+    }
+
+    #[test]
+    fn test_find_duplicate_declarations_ignores_agreeing_or_same_type_files() {
+        let mut packages = HashMap::new();
+        packages.insert(
+            ("requests".to_string(), Lang::Python),
+            vec![
+                occurrence("requirements.txt", FileType::Requirements, "2.31.0"),
+                occurrence("pyproject.toml", FileType::PyProject, "2.31.0"),
+            ],
+        );
+        packages.insert(
+            ("flask".to_string(), Lang::Python),
+            vec![
+                occurrence("requirements.txt", FileType::Requirements, "2.0.0"),
+                occurrence("requirements-dev.txt", FileType::Requirements, "3.0.0"),
+            ],
+        );
+
+        assert!(find_duplicate_declarations(&packages).is_empty());
+    }
+
+    #[test]
+    fn test_find_include_conflicts_checks_each_tree_from_its_outermost_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, content).unwrap();
+            path
+        };
+        let dev = write(
+            "requirements-dev.txt",
+            "-r requirements.txt
+flask==3.0.0
+",
+        );
+        let main = write(
+            "requirements.txt",
+            "-r base.txt
+requests==2.31.0
+",
+        );
+        write(
+            "base.txt",
+            "flask==2.0.0
+requests==2.31.0
+",
+        );
+
+        let conflicts = find_include_conflicts(&[
+            (main, FileType::Requirements),
+            (dev.clone(), FileType::Requirements),
+        ]);
+
+        // requirements.txt is part of the requirements-dev.txt tree, so the
+        // flask conflict is reported once, against the outermost file.
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].package_name, "flask");
+        assert_eq!(conflicts[0].root, dev);
+        let versions: Vec<_> = conflicts[0]
+            .occurrences
+            .iter()
+            .map(|o| o.version.as_str())
+            .collect();
+        assert_eq!(versions, ["3.0.0", "2.0.0"]);
+    }
 }
@@ -154,6 +154,7 @@ mod tests {
             id: id.to_string(),
             summary: None,
             severity: None,
+            cvss_score: None,
             url: None,
             fixed_version: None,
         }
@@ -268,6 +269,7 @@ mod tests {
                 id: "GHSA-test".to_string(),
                 summary: Some("Test".to_string()),
                 severity: Some("High".to_string()),
+                cvss_score: None,
                 url: Some("https://example.com".to_string()),
                 fixed_version: Some("3.2.1".to_string()),
             }],
@@ -316,15 +316,18 @@ fn roundup(x: f64) -> f64 {
 /// Determine the severity label for a raw string from the OSV API.
 ///
 /// Resolution order:
-/// 1. If `db_severity` is `Some`, normalise it via [`SeverityLabel::from_str_label`].
+/// 1. If `db_severity` is a recognised label, normalise it via
+///    [`SeverityLabel::from_str_label`].
 /// 2. If a `cvss_vector` is provided, parse it and map the score to a label.
 /// 3. If the vector cannot be parsed, return the raw vector as a fallback
 ///    (preserves the current behaviour for unrecognised inputs).
-/// 4. If neither is present, return `Unknown`.
+/// 4. Otherwise return `Unknown`.
 pub fn resolve_severity(db_severity: Option<&str>, cvss_vector: Option<&str>) -> ResolvedSeverity {
     if let Some(label) = db_severity {
         let normalized = SeverityLabel::from_str_label(label);
-        return ResolvedSeverity::Label(normalized);
+        if normalized != SeverityLabel::Unknown || cvss_vector.is_none() {
+            return ResolvedSeverity::Label(normalized);
+        }
     }
 
     if let Some(vector) = cvss_vector {
@@ -378,6 +381,15 @@ mod tests {
         assert_eq!(SeverityLabel::from_score(score), SeverityLabel::Low);
     }
 
+    /// CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:N → 8.1 → High
+    #[test]
+    fn cvss_31_high_low_privileges() {
+        let score =
+            parse_cvss_score("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:N").expect("should parse");
+        assert!((score - 8.1).abs() < 0.05, "expected ~8.1, got {score}");
+        assert_eq!(SeverityLabel::from_score(score), SeverityLabel::High);
+    }
+
     /// CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H → scope-changed → Critical (≥9.0)
     #[test]
     fn cvss_31_scope_changed_critical() {
@@ -467,6 +479,19 @@ mod tests {
         assert_eq!(r.as_severity_string(), "Critical");
     }
 
+    #[test]
+    fn resolve_severity_unrecognised_db_label_falls_back_to_cvss_vector() {
+        let r = resolve_severity(
+            Some("bogus"),
+            Some("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:N"),
+        );
+        assert_eq!(r.as_severity_string(), "High");
+        assert_eq!(
+            resolve_severity(Some("bogus"), None).as_severity_string(),
+            "Unknown"
+        );
+    }
+
     #[test]
     fn resolve_severity_falls_back_to_cvss_vector() {
         let r = resolve_severity(None, Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"));
@@ -2,6 +2,7 @@
 
 pub mod cache;
 pub mod cvss;
+pub mod sbom;
 
 use anyhow::Result;
 use futures::stream::{self, StreamExt};
@@ -9,7 +10,6 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
 
 /// Default OSV API base URL (override via `OsvClient::with_base_url`).
 const DEFAULT_OSV_API_URL: &str = "https://api.osv.dev/v1";
@@ -91,6 +91,9 @@ pub struct Vulnerability {
     pub summary: Option<String>,
     /// Severity level if available
     pub severity: Option<String>,
+    /// CVSS base score, when the advisory carries a vector that could be scored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cvss_score: Option<f64>,
     /// URL for more information
     pub url: Option<String>,
     /// Fixed version if available
@@ -126,6 +129,54 @@ impl AuditResult {
     pub fn vulnerable_packages(&self) -> usize {
         self.vulnerable.len()
     }
+
+    /// Drop vulnerabilities below `threshold`, and packages left with none.
+    ///
+    /// Returns how many vulnerabilities were dropped.
+    pub fn retain_min_severity(&mut self, threshold: SeverityThreshold) -> usize {
+        let before = self.total_vulnerabilities();
+        for pkg_result in &mut self.vulnerable {
+            pkg_result
+                .vulnerabilities
+                .retain(|v| threshold.admits(v.severity.as_deref()));
+        }
+        self.vulnerable.retain(|p| !p.vulnerabilities.is_empty());
+        before - self.total_vulnerabilities()
+    }
+}
+
+/// Minimum severity reported by `upd audit --severity`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "lower")]
+pub enum SeverityThreshold {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl SeverityThreshold {
+    /// Flag value, e.g. `high`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SeverityThreshold::Low => "low",
+            SeverityThreshold::Medium => "medium",
+            SeverityThreshold::High => "high",
+            SeverityThreshold::Critical => "critical",
+        }
+    }
+
+    /// Whether a `Vulnerability::severity` value is at or above this
+    /// threshold. Missing, `Unknown`, and unparsed severities never are.
+    pub fn admits(self, severity: Option<&str>) -> bool {
+        let max_key = match self {
+            SeverityThreshold::Critical => 0,
+            SeverityThreshold::High => 1,
+            SeverityThreshold::Medium => 2,
+            SeverityThreshold::Low => 3,
+        };
+        severity_sort_key(severity) <= max_key
+    }
 }
 
 /// Compute which packages can be auto-fixed and which cannot.
@@ -204,7 +255,7 @@ impl OsvClient {
     pub fn with_base_url(base_url: String) -> Self {
         Self {
             client: crate::http::apply(
-                Client::builder().timeout(Duration::from_secs(30))
+                Client::builder()
             )
             .build()
             .expect("Failed to create HTTP client. This usually indicates a TLS/SSL configuration issue on your system."),
@@ -335,6 +386,7 @@ impl OsvClient {
         let request = OsvBatchRequest { queries };
 
         let url = format!("{}/querybatch", self.base_url);
+        crate::http::ensure_host_allowed(&url)?;
         let mut response = self
             .client
             .post(&url)
@@ -385,6 +437,7 @@ impl OsvClient {
                             id: vuln_ref.id.clone(),
                             summary: None,
                             severity: None,
+                            cvss_score: None,
                             url: Some(format!("https://osv.dev/vulnerability/{}", vuln_ref.id)),
                             fixed_version: None,
                         }
@@ -401,6 +454,7 @@ impl OsvClient {
     /// Fetch vulnerability details by ID
     async fn fetch_vuln_by_id(&self, id: &str) -> Result<Vulnerability> {
         let url = format!("{}/vulns/{}", self.base_url, id);
+        crate::http::ensure_host_allowed(&url)?;
         let response = self
             .client
             .get(&url)
@@ -418,25 +472,7 @@ impl OsvClient {
 
         let vuln: OsvVulnerability = response.json().await?;
 
-        // Resolve severity: prefer database_specific label, then CVSS vector.
-        // database_specific.severity takes priority because it is curated by
-        // the advisory database and is often more accurate than a computed score.
-        let db_severity = vuln
-            .database_specific
-            .as_ref()
-            .and_then(|db| db.get("severity"))
-            .and_then(|v| v.as_str());
-        let cvss_vector = vuln
-            .severity
-            .as_ref()
-            .and_then(|s| s.first())
-            .map(|s| s.score.as_str());
-        let resolved = cvss::resolve_severity(db_severity, cvss_vector);
-        // All resolved labels, including "Unknown", are serialised so that
-        // JSON consumers see a consistent schema regardless of whether
-        // severity data was available. "Unknown" sorts last in the sort key,
-        // matching the same position as None would have occupied.
-        let severity = Some(resolved.as_severity_string());
+        let (severity, cvss_score) = resolve_osv_severity(&vuln);
 
         // Extract the fixed version from affected ranges. Only version-typed
         // ranges (ECOSYSTEM/SEMVER, or untyped) qualify: a GIT range's `fixed`
@@ -471,6 +507,7 @@ impl OsvClient {
             id: vuln.id,
             summary: vuln.summary,
             severity,
+            cvss_score,
             url: Some(url),
             fixed_version,
         })
@@ -530,9 +567,48 @@ struct OsvVulnerability {
 
 #[derive(Debug, Deserialize)]
 struct OsvSeverity {
+    /// `CVSS_V2`, `CVSS_V3`, `CVSS_V4`, or an ecosystem-specific scheme.
+    #[serde(rename = "type")]
+    severity_type: Option<String>,
     score: String,
 }
 
+/// Resolve an advisory's severity label and CVSS base score.
+///
+/// The score comes from the first vector that can be scored, trying CVSS v3
+/// before the approximated v4. The label prefers `database_specific.severity`
+/// because it is curated by the advisory database and is often more accurate
+/// than a computed score; without one it is the score's band. Every label,
+/// including "Unknown", is returned so that JSON consumers see a consistent
+/// schema regardless of whether severity data was available.
+fn resolve_osv_severity(vuln: &OsvVulnerability) -> (Option<String>, Option<f64>) {
+    let entries = vuln.severity.as_deref().unwrap_or_default();
+    let rank = |entry: &&OsvSeverity| match entry.severity_type.as_deref() {
+        Some("CVSS_V3") => 0,
+        Some("CVSS_V4") => 1,
+        _ => 2,
+    };
+    let mut ranked: Vec<&OsvSeverity> = entries.iter().collect();
+    ranked.sort_by_key(rank);
+    let scored = ranked.iter().find_map(|entry| {
+        cvss::parse_cvss_score(&entry.score).map(|score| (entry.score.as_str(), score))
+    });
+
+    let db_severity = vuln
+        .database_specific
+        .as_ref()
+        .and_then(|db| db.get("severity"))
+        .and_then(|v| v.as_str());
+    let cvss_vector = scored
+        .map(|(vector, _)| vector)
+        .or_else(|| entries.first().map(|s| s.score.as_str()));
+    let resolved = cvss::resolve_severity(db_severity, cvss_vector);
+    (
+        Some(resolved.as_severity_string()),
+        scored.map(|(_, score)| score),
+    )
+}
+
 #[derive(Debug, Deserialize)]
 struct OsvReference {
     url: String,
@@ -601,6 +677,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolve_osv_severity_scores_vector_and_keeps_curated_label() {
+        let vuln: OsvVulnerability = serde_json::from_value(serde_json::json!({
+            "id": "GHSA-cvss",
+            "severity": [
+                { "type": "CVSS_V2", "score": "AV:N/AC:L/Au:N/C:P/I:P/A:P" },
+                { "type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:N" }
+            ]
+        }))
+        .unwrap();
+        let (severity, score) = resolve_osv_severity(&vuln);
+        assert_eq!(severity.as_deref(), Some("High"));
+        assert!((score.unwrap() - 8.1).abs() < 0.05, "got {score:?}");
+
+        let curated: OsvVulnerability = serde_json::from_value(serde_json::json!({
+            "id": "GHSA-curated",
+            "severity": [
+                { "type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:N" }
+            ],
+            "database_specific": { "severity": "MODERATE" }
+        }))
+        .unwrap();
+        let (severity, score) = resolve_osv_severity(&curated);
+        assert_eq!(severity.as_deref(), Some("Medium"));
+        assert!(score.is_some());
+
+        let label_only: OsvVulnerability = serde_json::from_value(serde_json::json!({
+            "id": "GHSA-label",
+            "database_specific": { "severity": "CRITICAL" }
+        }))
+        .unwrap();
+        assert_eq!(
+            resolve_osv_severity(&label_only),
+            (Some("Critical".to_string()), None)
+        );
+    }
+
+    #[test]
+    fn test_severity_threshold_admits_at_or_above() {
+        assert!(SeverityThreshold::High.admits(Some("Critical")));
+        assert!(SeverityThreshold::High.admits(Some("High")));
+        assert!(!SeverityThreshold::High.admits(Some("Medium")));
+        assert!(SeverityThreshold::Low.admits(Some("Low")));
+        assert!(!SeverityThreshold::Low.admits(Some("None")));
+        assert!(!SeverityThreshold::Low.admits(Some("Unknown")));
+        assert!(!SeverityThreshold::Low.admits(None));
+    }
+
+    #[test]
+    fn test_retain_min_severity_drops_emptied_packages() {
+        let vuln = |id: &str, severity: &str| Vulnerability {
+            id: id.to_string(),
+            summary: None,
+            severity: Some(severity.to_string()),
+            cvss_score: None,
+            url: None,
+            fixed_version: None,
+        };
+        let package = |name: &str, vulnerabilities| PackageAuditResult {
+            package: Package {
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                ecosystem: Ecosystem::PyPI,
+            },
+            vulnerabilities,
+        };
+        let mut result = AuditResult {
+            vulnerable: vec![
+                package("a", vec![vuln("A-1", "Critical"), vuln("A-2", "Low")]),
+                package("b", vec![vuln("B-1", "Medium")]),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(result.retain_min_severity(SeverityThreshold::High), 2);
+        assert_eq!(result.vulnerable_packages(), 1);
+        assert_eq!(result.vulnerable[0].vulnerabilities[0].id, "A-1");
+    }
+
     #[test]
     fn test_audit_result_counts() {
         let mut result = AuditResult {
@@ -618,6 +773,7 @@ mod tests {
                     id: "CVE-2024-001".to_string(),
                     summary: Some("Test vuln".to_string()),
                     severity: Some("HIGH".to_string()),
+                    cvss_score: None,
                     url: None,
                     fixed_version: Some("1.0.1".to_string()),
                 },
@@ -625,6 +781,7 @@ mod tests {
                     id: "CVE-2024-002".to_string(),
                     summary: None,
                     severity: None,
+                    cvss_score: None,
                     url: None,
                     fixed_version: None,
                 },
@@ -848,6 +1005,7 @@ mod tests {
             id: id.to_string(),
             summary: None,
             severity: None,
+            cvss_score: None,
             url: None,
             fixed_version: fixed.map(str::to_string),
         }
@@ -1017,6 +1175,7 @@ mod tests {
                     id: "CVE-A".to_string(),
                     summary: None,
                     severity: None,
+                    cvss_score: None,
                     url: None,
                     fixed_version: Some("1.0.0.10".to_string()),
                 },
@@ -1024,6 +1183,7 @@ mod tests {
                     id: "CVE-B".to_string(),
                     summary: None,
                     severity: None,
+                    cvss_score: None,
                     url: None,
                     fixed_version: Some("1.0.0.9".to_string()),
                 },
@@ -1087,6 +1247,7 @@ mod tests {
                     id: "CVE-CACHED".to_string(),
                     summary: None,
                     severity: None,
+                    cvss_score: None,
                     url: None,
                     fixed_version: None,
                 }],
@@ -0,0 +1,301 @@
+//! SBOM input for `upd audit --sbom`.
+//!
+//! Reads the component list of a CycloneDX or SPDX JSON document and turns
+//! every component carrying a package URL (purl) of an OSV-covered ecosystem
+//! into an audit [`Package`]. The format is detected from the document itself
+//! (`bomFormat` for CycloneDX, `spdxVersion` for SPDX).
+
+use super::{Ecosystem, Package};
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Packages read from an SBOM.
+#[derive(Debug, Default)]
+pub struct SbomPackages {
+    /// Deduplicated packages to audit.
+    pub packages: Vec<Package>,
+    /// Components that could not be audited (no purl, no version, or an
+    /// ecosystem OSV lookups are not wired up for), as `"<name>: <reason>"`.
+    pub skipped: Vec<String>,
+}
+
+/// Read and parse an SBOM file.
+pub fn load_sbom(path: &Path) -> Result<SbomPackages> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read SBOM {}", path.display()))?;
+    parse_sbom(&content)
+        .map_err(|e| anyhow::anyhow!("failed to parse SBOM {}: {:#}", path.display(), e))
+}
+
+/// Parse a CycloneDX or SPDX JSON document.
+pub fn parse_sbom(content: &str) -> Result<SbomPackages> {
+    let doc: Value = serde_json::from_str(content).context("SBOM is not valid JSON")?;
+
+    // (display name, purl, fallback version)
+    let mut components: Vec<(String, Option<String>, Option<String>)> = Vec::new();
+    if doc.get("bomFormat").and_then(Value::as_str) == Some("CycloneDX") {
+        collect_cyclonedx(doc.get("components"), &mut components);
+    } else if doc.get("spdxVersion").is_some() {
+        for pkg in doc
+            .get("packages")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let purl = pkg
+                .get("externalRefs")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .find(|r| r.get("referenceType").and_then(Value::as_str) == Some("purl"))
+                .and_then(|r| r.get("referenceLocator"))
+                .and_then(Value::as_str);
+            components.push((
+                str_field(pkg, "name").unwrap_or_default(),
+                purl.map(str::to_string),
+                str_field(pkg, "versionInfo"),
+            ));
+        }
+    } else {
+        bail!("unrecognized SBOM format (expected CycloneDX or SPDX JSON)");
+    }
+
+    let mut result = SbomPackages::default();
+    let mut seen = HashSet::new();
+    for (name, purl, fallback_version) in components {
+        let Some(purl) = purl else {
+            result.skipped.push(format!("{name}: no package URL"));
+            continue;
+        };
+        let Some(parsed) = parse_purl(&purl) else {
+            result
+                .skipped
+                .push(format!("{name}: invalid package URL {purl}"));
+            continue;
+        };
+        let Some(ecosystem) = ecosystem_for_purl_type(&parsed.purl_type) else {
+            result.skipped.push(format!(
+                "{}: unsupported ecosystem '{}'",
+                parsed.name, parsed.purl_type
+            ));
+            continue;
+        };
+        let Some(version) = parsed.version.or(fallback_version) else {
+            result.skipped.push(format!("{}: no version", parsed.name));
+            continue;
+        };
+        if seen.insert((parsed.name.clone(), version.clone(), ecosystem.as_str())) {
+            result.packages.push(Package {
+                name: parsed.name,
+                version,
+                ecosystem,
+            });
+        }
+    }
+    Ok(result)
+}
+
+/// Collect CycloneDX components, including nested ones.
+fn collect_cyclonedx(
+    components: Option<&Value>,
+    out: &mut Vec<(String, Option<String>, Option<String>)>,
+) {
+    for component in components.and_then(Value::as_array).into_iter().flatten() {
+        out.push((
+            str_field(component, "name").unwrap_or_default(),
+            str_field(component, "purl"),
+            str_field(component, "version"),
+        ));
+        collect_cyclonedx(component.get("components"), out);
+    }
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// The parts of a package URL needed for an OSV query.
+#[derive(Debug, PartialEq, Eq)]
+struct Purl {
+    purl_type: String,
+    /// Name as OSV expects it: npm scopes and Go module paths include the
+    /// namespace.
+    name: String,
+    version: Option<String>,
+}
+
+/// Parse `pkg:<type>/<namespace>/<name>@<version>?<qualifiers>#<subpath>`.
+fn parse_purl(purl: &str) -> Option<Purl> {
+    let rest = purl.strip_prefix("pkg:")?;
+    let rest = rest.split(['?', '#']).next().unwrap_or(rest);
+    // The version separator is the `@` in the last path segment; an unencoded
+    // npm scope (`@angular/core`) must not be mistaken for it.
+    let name_start = rest.rfind('/').map_or(0, |i| i + 1);
+    let (path, version) = match rest[name_start..].find('@') {
+        Some(at) => (
+            &rest[..name_start + at],
+            Some(percent_decode(&rest[name_start + at + 1..])),
+        ),
+        None => (rest, None),
+    };
+    let (purl_type, name_path) = path.split_once('/')?;
+    let purl_type = purl_type.to_ascii_lowercase();
+    let segments: Vec<String> = name_path
+        .trim_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(percent_decode)
+        .collect();
+    if segments.is_empty() {
+        return None;
+    }
+    // Only npm scopes and Go module paths carry meaning in the namespace; for
+    // the other supported types the last segment is the registry name.
+    let name = match purl_type.as_str() {
+        "npm" | "golang" => segments.join("/"),
+        _ => segments.last()?.clone(),
+    };
+    Some(Purl {
+        purl_type,
+        name,
+        version: version.filter(|v| !v.is_empty()),
+    })
+}
+
+/// Map a purl type to the OSV ecosystem `upd` audits.
+fn ecosystem_for_purl_type(purl_type: &str) -> Option<Ecosystem> {
+    match purl_type {
+        "pypi" => Some(Ecosystem::PyPI),
+        "npm" => Some(Ecosystem::Npm),
+        "cargo" => Some(Ecosystem::CratesIo),
+        "golang" => Some(Ecosystem::Go),
+        "gem" => Some(Ecosystem::RubyGems),
+        "nuget" => Some(Ecosystem::NuGet),
+        _ => None,
+    }
+}
+
+/// Decode `%XX` escapes; malformed escapes are kept verbatim.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(hex) = s.get(i + 1..i + 3)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CYCLONEDX: &str = r#"{
+      "bomFormat": "CycloneDX",
+      "specVersion": "1.5",
+      "components": [
+        { "type": "library", "name": "requests", "version": "2.19.0",
+          "purl": "pkg:pypi/requests@2.19.0" },
+        { "type": "library", "name": "core", "version": "7.0.0",
+          "purl": "pkg:npm/%40angular/core@7.0.0" },
+        { "type": "library", "name": "serde", "purl": "pkg:cargo/serde@1.0.100",
+          "components": [
+            { "type": "library", "name": "net",
+              "purl": "pkg:golang/golang.org/x/net@v0.0.1?type=module" }
+          ] },
+        { "type": "library", "name": "rails", "purl": "pkg:gem/rails@5.0.0" },
+        { "type": "library", "name": "Newtonsoft.Json", "purl": "pkg:nuget/Newtonsoft.Json@12.0.1" },
+        { "type": "library", "name": "guava", "purl": "pkg:maven/com.google.guava/guava@30.0" },
+        { "type": "library", "name": "vendored", "version": "1.0" },
+        { "type": "library", "name": "requests", "version": "2.19.0",
+          "purl": "pkg:pypi/requests@2.19.0" }
+      ]
+    }"#;
+
+    #[test]
+    fn test_cyclonedx_components_map_to_osv_ecosystems() {
+        let sbom = parse_sbom(CYCLONEDX).unwrap();
+        let got: Vec<(&str, &str, &str)> = sbom
+            .packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str(), p.ecosystem.as_str()))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("requests", "2.19.0", "PyPI"),
+                ("@angular/core", "7.0.0", "npm"),
+                ("serde", "1.0.100", "crates.io"),
+                ("golang.org/x/net", "v0.0.1", "Go"),
+                ("rails", "5.0.0", "RubyGems"),
+                ("Newtonsoft.Json", "12.0.1", "NuGet"),
+            ]
+        );
+        assert_eq!(sbom.skipped.len(), 2, "{:?}", sbom.skipped);
+        assert!(sbom.skipped[0].contains("unsupported ecosystem 'maven'"));
+        assert!(sbom.skipped[1].contains("vendored: no package URL"));
+    }
+
+    #[test]
+    fn test_spdx_packages_use_purl_external_refs() {
+        let spdx = r#"{
+          "spdxVersion": "SPDX-2.3",
+          "packages": [
+            { "name": "lodash", "versionInfo": "4.17.20",
+              "externalRefs": [
+                { "referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl",
+                  "referenceLocator": "pkg:npm/lodash" }
+              ] },
+            { "name": "app", "versionInfo": "1.0.0" }
+          ]
+        }"#;
+        let sbom = parse_sbom(spdx).unwrap();
+        assert_eq!(sbom.packages.len(), 1);
+        let lodash = &sbom.packages[0];
+        assert_eq!(lodash.name, "lodash");
+        // The purl has no version, so SPDX `versionInfo` is used.
+        assert_eq!(lodash.version, "4.17.20");
+        assert_eq!(lodash.ecosystem, Ecosystem::Npm);
+        assert_eq!(sbom.skipped, vec!["app: no package URL".to_string()]);
+    }
+
+    #[test]
+    fn test_unrecognized_document_is_an_error() {
+        let err = parse_sbom(r#"{"components": []}"#).unwrap_err();
+        assert!(err.to_string().contains("unrecognized SBOM format"));
+        assert!(parse_sbom("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_purl_strips_qualifiers_and_decodes() {
+        assert_eq!(
+            parse_purl("pkg:pypi/Django@4.2%2Blocal?os=linux#sub"),
+            Some(Purl {
+                purl_type: "pypi".to_string(),
+                name: "Django".to_string(),
+                version: Some("4.2+local".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_purl("pkg:npm/@angular/core").map(|p| (p.name, p.version)),
+            Some(("@angular/core".to_string(), None))
+        );
+        assert_eq!(parse_purl("npm/lodash@1.0.0"), None);
+        assert_eq!(parse_purl("pkg:npm"), None);
+    }
+}
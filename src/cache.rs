@@ -1,4 +1,6 @@
-use crate::registry::{Registry, VersionMeta};
+#[cfg(feature = "registry-fixture")]
+use crate::registry::FixtureRegistry;
+use crate::registry::{PackageLinks, Registry, Revalidated, VersionMeta};
 use anyhow::Result;
 use async_trait::async_trait;
 use directories::ProjectDirs;
@@ -9,7 +11,41 @@ use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-const CACHE_TTL_HOURS: u64 = 24;
+/// How long a cached version is trusted before the next lookup refetches it,
+/// unless `--cache-ttl` says otherwise.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(3600);
+
+/// How long a "not found" answer is trusted, unless `--negative-cache-ttl`
+/// says otherwise. Short, so a package published after a typo is fixed is
+/// picked up soon.
+pub const DEFAULT_NEGATIVE_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Returns `true` when a lookup failed because the registry definitively
+/// reported the package missing (HTTP 404), as opposed to a transient
+/// failure such as a 5xx or a timeout.
+pub fn is_not_found(err: &anyhow::Error) -> bool {
+    err.chain()
+        .any(|cause| cause.to_string().contains("not found (HTTP 404)"))
+}
+
+/// Prefix of the error reported when `--offline` finds no cached answer.
+pub const OFFLINE_MISS: &str = "offline: no cached version for";
+
+/// Returns `true` when an error message came from an `--offline` cache miss,
+/// so callers can report it apart from genuine failures.
+pub fn is_offline_miss(message: &str) -> bool {
+    message.contains(OFFLINE_MISS)
+}
+
+/// Prefix of the error reported for a lookup skipped or cut off because the
+/// `--max-runtime` deadline passed.
+pub const DEADLINE_MISS: &str = "max runtime reached: no version lookup for";
+
+/// Returns `true` when an error message came from the `--max-runtime`
+/// deadline rather than from the registry.
+pub fn is_deadline_miss(message: &str) -> bool {
+    message.contains(DEADLINE_MISS)
+}
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Cache {
@@ -29,6 +65,77 @@ pub struct Cache {
     terraform: HashMap<String, CacheEntry>,
     #[serde(default)]
     nuget: HashMap<String, CacheEntry>,
+    #[serde(default, rename = "docker-hub")]
+    docker_hub: HashMap<String, CacheEntry>,
+    #[serde(default)]
+    packagist: HashMap<String, CacheEntry>,
+    #[serde(default)]
+    maven: HashMap<String, CacheEntry>,
+    #[serde(default, rename = "pub")]
+    pub_dev: HashMap<String, CacheEntry>,
+    #[serde(default)]
+    runtime: HashMap<String, CacheEntry>,
+    /// Lookup counters across runs, for the `cache-stats` hit rate.
+    #[serde(default)]
+    counters: CacheCounters,
+    /// Age after which an entry is expired; [`DEFAULT_CACHE_TTL`] when unset.
+    /// A per-run setting, never written to the cache file.
+    #[serde(skip)]
+    ttl: Option<Duration>,
+    /// Age after which a not-found entry is expired;
+    /// [`DEFAULT_NEGATIVE_CACHE_TTL`] when unset. Per run, like `ttl`.
+    #[serde(skip)]
+    negative_ttl: Option<Duration>,
+}
+
+/// Registry keys of the cache file, in display order.
+const REGISTRIES: [&str; 13] = [
+    "pypi",
+    "npm",
+    "crates.io",
+    "go-proxy",
+    "github-releases",
+    "rubygems",
+    "terraform",
+    "nuget",
+    "docker-hub",
+    "packagist",
+    "maven",
+    "pub",
+    "runtime",
+];
+
+/// Cache lookups answered from the cache (`hits`) or sent to the registry
+/// because no fresh entry existed (`misses`).
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheCounters {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// What `upd cache-stats` reports about the cache contents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheSummary {
+    /// Entry count per registry, in [`REGISTRIES`] order, empty ones included.
+    pub entries: Vec<(&'static str, usize)>,
+    /// Entries past the TTL, which the next lookup refetches.
+    pub expired: usize,
+    /// Unix timestamps of the oldest and newest entry.
+    pub oldest: Option<u64>,
+    pub newest: Option<u64>,
+    pub counters: CacheCounters,
+}
+
+impl CacheSummary {
+    pub fn total_entries(&self) -> usize {
+        self.entries.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Share of lookups answered from the cache, or `None` before any lookup.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.counters.hits + self.counters.misses;
+        (total > 0).then(|| self.counters.hits as f64 / total as f64)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -39,6 +146,23 @@ pub struct CacheEntry {
     /// Older cache files predate this field and deserialize with `None`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub versions: Option<Vec<CachedVersionMeta>>,
+    /// Set when the registry reported the package missing: the error
+    /// message of that lookup, replayed on later lookups. `version` is
+    /// empty for these entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_found: Option<String>,
+    /// `ETag` the registry sent with this answer. Once the entry expires it
+    /// is revalidated with `If-None-Match` rather than refetched in full.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+}
+
+/// A fresh cached answer: the version found, or the message of a lookup
+/// the registry answered with "not found".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CachedLookup {
+    Version(String),
+    NotFound(String),
 }
 
 /// Cache-friendly mirror of [`crate::registry::VersionMeta`]. `published_at`
@@ -83,6 +207,19 @@ impl Cache {
         Arc::new(Mutex::new(Self::load().unwrap_or_default()))
     }
 
+    /// Treat entries older than `ttl` as expired (default [`DEFAULT_CACHE_TTL`]).
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Treat not-found entries older than `ttl` as expired (default
+    /// [`DEFAULT_NEGATIVE_CACHE_TTL`]).
+    pub fn with_negative_ttl(mut self, ttl: Duration) -> Self {
+        self.negative_ttl = Some(ttl);
+        self
+    }
+
     /// Save a shared cache to disk
     pub fn save_shared(cache: &Arc<Mutex<Cache>>) -> Result<()> {
         cache
@@ -91,8 +228,8 @@ impl Cache {
             .save()
     }
 
-    pub fn get(&self, registry: &str, package: &str) -> Option<String> {
-        let entries = match registry {
+    fn entries(&self, registry: &str) -> Option<&HashMap<String, CacheEntry>> {
+        Some(match registry {
             "pypi" => &self.pypi,
             "npm" => &self.npm,
             "crates.io" => &self.crates_io,
@@ -101,19 +238,82 @@ impl Cache {
             "rubygems" => &self.rubygems,
             "terraform" => &self.terraform,
             "nuget" => &self.nuget,
+            "docker-hub" => &self.docker_hub,
+            "packagist" => &self.packagist,
+            "maven" => &self.maven,
+            "pub" => &self.pub_dev,
+            "runtime" => &self.runtime,
             _ => return None,
-        };
+        })
+    }
 
-        entries.get(package).and_then(|entry| {
-            if Self::is_expired(entry.fetched_at) {
-                None
-            } else {
-                Some(entry.version.clone())
-            }
+    pub fn get(&self, registry: &str, package: &str) -> Option<String> {
+        match self.lookup(registry, package)? {
+            CachedLookup::Version(version) => Some(version),
+            CachedLookup::NotFound(_) => None,
+        }
+    }
+
+    /// The fresh cached answer for `package`, a cached not-found included.
+    pub fn lookup(&self, registry: &str, package: &str) -> Option<CachedLookup> {
+        let entry = self.entries(registry)?.get(package)?;
+        if Self::is_expired(entry.fetched_at, self.entry_ttl(entry)) {
+            return None;
+        }
+        Some(match &entry.not_found {
+            Some(message) => CachedLookup::NotFound(message.clone()),
+            None => CachedLookup::Version(entry.version.clone()),
         })
     }
 
+    /// Like [`Cache::get`], but also returns entries past their TTL. Used by
+    /// `--offline`, where a stale answer beats no answer.
+    pub fn get_stale(&self, registry: &str, package: &str) -> Option<String> {
+        self.entries(registry)?
+            .get(package)
+            .filter(|entry| entry.not_found.is_none())
+            .map(|entry| entry.version.clone())
+    }
+
+    /// The cached version of `package` and the `ETag` it was served with,
+    /// however old the entry, for revalidating it once it has expired.
+    pub fn get_etag(&self, registry: &str, package: &str) -> Option<(String, String)> {
+        let entry = self.entries(registry)?.get(package)?;
+        if entry.not_found.is_some() {
+            return None;
+        }
+        Some((entry.version.clone(), entry.etag.clone()?))
+    }
+
     pub fn set(&mut self, registry: &str, package: &str, version: String) {
+        self.insert(registry, package, version, None, None);
+    }
+
+    /// Like [`Cache::set`], remembering the `ETag` the answer came with.
+    pub fn set_with_etag(
+        &mut self,
+        registry: &str,
+        package: &str,
+        version: String,
+        etag: Option<String>,
+    ) {
+        self.insert(registry, package, version, None, etag);
+    }
+
+    /// Record that the registry reported `package` missing, with the
+    /// lookup's error `message`.
+    pub fn set_not_found(&mut self, registry: &str, package: &str, message: String) {
+        self.insert(registry, package, String::new(), Some(message), None);
+    }
+
+    fn insert(
+        &mut self,
+        registry: &str,
+        package: &str,
+        version: String,
+        not_found: Option<String>,
+        etag: Option<String>,
+    ) {
         let entries = match registry {
             "pypi" => &mut self.pypi,
             "npm" => &mut self.npm,
@@ -123,6 +323,11 @@ impl Cache {
             "rubygems" => &mut self.rubygems,
             "terraform" => &mut self.terraform,
             "nuget" => &mut self.nuget,
+            "docker-hub" => &mut self.docker_hub,
+            "packagist" => &mut self.packagist,
+            "maven" => &mut self.maven,
+            "pub" => &mut self.pub_dev,
+            "runtime" => &mut self.runtime,
             _ => return,
         };
 
@@ -137,10 +342,62 @@ impl Cache {
                 version,
                 fetched_at,
                 versions: None,
+                not_found,
+                etag,
             },
         );
     }
 
+    /// Record whether a lookup was answered from the cache.
+    pub fn record_lookup(&mut self, hit: bool) {
+        if hit {
+            self.counters.hits += 1;
+        } else {
+            self.counters.misses += 1;
+        }
+    }
+
+    /// Summarize entry counts, entry ages and lookup counters.
+    pub fn summary(&self) -> CacheSummary {
+        let mut summary = CacheSummary {
+            entries: Vec::with_capacity(REGISTRIES.len()),
+            expired: 0,
+            oldest: None,
+            newest: None,
+            counters: self.counters,
+        };
+        for registry in REGISTRIES {
+            let entries = self.entries(registry).expect("known registry");
+            summary.entries.push((registry, entries.len()));
+            for entry in entries.values() {
+                if Self::is_expired(entry.fetched_at, self.entry_ttl(entry)) {
+                    summary.expired += 1;
+                }
+                summary.oldest = Some(
+                    summary
+                        .oldest
+                        .map_or(entry.fetched_at, |t| t.min(entry.fetched_at)),
+                );
+                summary.newest = Some(
+                    summary
+                        .newest
+                        .map_or(entry.fetched_at, |t| t.max(entry.fetched_at)),
+                );
+            }
+        }
+        summary
+    }
+
+    /// Size of the cache file in bytes, or `None` when there is no cache file.
+    pub fn disk_size() -> Result<Option<u64>> {
+        let path = Self::cache_path()?;
+        match fs::metadata(&path) {
+            Ok(metadata) => Ok(Some(metadata.len())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     pub fn clean() -> Result<()> {
         let path = Self::cache_path()?;
         if path.exists() {
@@ -161,34 +418,56 @@ impl Cache {
         Ok(proj_dirs.cache_dir().join("versions.json"))
     }
 
-    fn is_expired(fetched_at: u64) -> bool {
+    fn ttl(&self) -> Duration {
+        self.ttl.unwrap_or(DEFAULT_CACHE_TTL)
+    }
+
+    fn negative_ttl(&self) -> Duration {
+        self.negative_ttl.unwrap_or(DEFAULT_NEGATIVE_CACHE_TTL)
+    }
+
+    /// The TTL that applies to `entry`: not-found answers expire sooner.
+    fn entry_ttl(&self, entry: &CacheEntry) -> Duration {
+        if entry.not_found.is_some() {
+            self.negative_ttl()
+        } else {
+            self.ttl()
+        }
+    }
+
+    fn is_expired(fetched_at: u64, ttl: Duration) -> bool {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs();
 
-        let ttl = Duration::from_secs(CACHE_TTL_HOURS * 3600).as_secs();
-        now.saturating_sub(fetched_at) > ttl
+        now.saturating_sub(fetched_at) > ttl.as_secs()
     }
 
     /// Prune expired entries from the cache
     pub fn prune(&mut self) {
-        self.pypi
-            .retain(|_, entry| !Self::is_expired(entry.fetched_at));
-        self.npm
-            .retain(|_, entry| !Self::is_expired(entry.fetched_at));
-        self.crates_io
-            .retain(|_, entry| !Self::is_expired(entry.fetched_at));
-        self.go_proxy
-            .retain(|_, entry| !Self::is_expired(entry.fetched_at));
-        self.github_releases
-            .retain(|_, entry| !Self::is_expired(entry.fetched_at));
-        self.rubygems
-            .retain(|_, entry| !Self::is_expired(entry.fetched_at));
-        self.terraform
-            .retain(|_, entry| !Self::is_expired(entry.fetched_at));
-        self.nuget
-            .retain(|_, entry| !Self::is_expired(entry.fetched_at));
+        let (ttl, negative_ttl) = (self.ttl(), self.negative_ttl());
+        let expired = |entry: &CacheEntry| {
+            let ttl = if entry.not_found.is_some() {
+                negative_ttl
+            } else {
+                ttl
+            };
+            Self::is_expired(entry.fetched_at, ttl)
+        };
+        self.pypi.retain(|_, entry| !expired(entry));
+        self.npm.retain(|_, entry| !expired(entry));
+        self.crates_io.retain(|_, entry| !expired(entry));
+        self.go_proxy.retain(|_, entry| !expired(entry));
+        self.github_releases.retain(|_, entry| !expired(entry));
+        self.rubygems.retain(|_, entry| !expired(entry));
+        self.terraform.retain(|_, entry| !expired(entry));
+        self.nuget.retain(|_, entry| !expired(entry));
+        self.docker_hub.retain(|_, entry| !expired(entry));
+        self.packagist.retain(|_, entry| !expired(entry));
+        self.maven.retain(|_, entry| !expired(entry));
+        self.pub_dev.retain(|_, entry| !expired(entry));
+        self.runtime.retain(|_, entry| !expired(entry));
     }
 }
 
@@ -198,32 +477,268 @@ pub struct CachedRegistry<R> {
     inner: R,
     cache: Arc<Mutex<Cache>>,
     enabled: bool,
+    /// When set, every lookup is answered by the fixture; neither the cache
+    /// nor the inner registry is consulted.
+    #[cfg(feature = "registry-fixture")]
+    fixture: Option<FixtureRegistry>,
+    /// Answer only from the cache (stale entries included); a miss is an
+    /// error rather than a network request.
+    offline: bool,
+    /// `--max-runtime` deadline: no request starts after it, and requests in
+    /// flight when it passes are abandoned. Cached answers are still served.
+    deadline: Option<tokio::time::Instant>,
+    /// Prepended to every cache key, so a second index behind the same
+    /// registry name (e.g. a Pipfile `[[source]]`) keeps its own entries.
+    key_prefix: Option<String>,
+    /// Lookups currently being fetched, keyed by cache key. Concurrent
+    /// lookups of the same key await the first one's answer instead of
+    /// sending their own request.
+    in_flight: Mutex<HashMap<String, Arc<InFlight>>>,
+    /// Version lists fetched this run, keyed by package. They are not
+    /// written to the cache file, but the cooldown, MSRV, engines and
+    /// deprecation checks of one package share a single request.
+    version_lists: Mutex<HashMap<String, Arc<VersionList>>>,
 }
 
+/// The shared answer of one in-flight lookup; errors are kept as their
+/// message so every waiter can receive a copy.
+type InFlight = tokio::sync::OnceCell<std::result::Result<String, String>>;
+
+/// The shared version list of one package, errors kept as their message.
+type VersionList = tokio::sync::OnceCell<std::result::Result<Vec<VersionMeta>, String>>;
+
 impl<R: Registry> CachedRegistry<R> {
     pub fn new(inner: R, cache: Arc<Mutex<Cache>>, enabled: bool) -> Self {
         Self {
             inner,
             cache,
             enabled,
+            #[cfg(feature = "registry-fixture")]
+            fixture: None,
+            offline: false,
+            deadline: None,
+            key_prefix: None,
+            in_flight: Mutex::new(HashMap::new()),
+            version_lists: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Stop contacting the wrapped registry at `deadline`; see [`DEADLINE_MISS`].
+    pub fn with_deadline(mut self, deadline: Option<tokio::time::Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Cache this registry's answers under `prefix`, apart from the other
+    /// registries sharing its name.
+    pub fn with_key_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.key_prefix = Some(prefix.into());
+        self
+    }
+
+    /// The key `key` is cached under.
+    fn stored_key<'a>(&self, key: &'a str) -> std::borrow::Cow<'a, str> {
+        match &self.key_prefix {
+            Some(prefix) => format!("{prefix}|{key}").into(),
+            None => key.into(),
         }
     }
 
-    /// Get from cache (returns None if disabled, expired, or missing)
-    fn cache_get(&self, package: &str) -> Option<String> {
+    /// Never contact the wrapped registry; see [`OFFLINE_MISS`].
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Resolve every lookup from `fixture` instead of the cache and network.
+    #[cfg(feature = "registry-fixture")]
+    pub fn with_fixture(mut self, fixture: FixtureRegistry) -> Self {
+        self.fixture = Some(fixture);
+        self
+    }
+
+    /// Get from cache (returns None if disabled, expired, or missing). A
+    /// cached not-found comes back as the original lookup's error.
+    fn cache_get(&self, package: &str) -> Option<Result<String>> {
         if !self.enabled {
             return None;
         }
-        self.cache.lock().ok()?.get(self.inner.name(), package)
+        let mut cache = self.cache.lock().ok()?;
+        let lookup = cache.lookup(self.inner.name(), &self.stored_key(package));
+        cache.record_lookup(lookup.is_some());
+        if lookup.is_some() {
+            tracing::debug!(registry = self.inner.name(), key = package, "cache hit");
+        } else {
+            tracing::debug!(registry = self.inner.name(), key = package, "cache miss");
+        }
+        Some(match lookup? {
+            CachedLookup::Version(version) => Ok(version),
+            CachedLookup::NotFound(message) => Err(anyhow::anyhow!(message)),
+        })
+    }
+
+    /// Offline lookup: any cached entry, however old, or an [`OFFLINE_MISS`] error.
+    fn offline_get(&self, cache_key: &str, package: &str) -> Result<String> {
+        self.cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get_stale(self.inner.name(), &self.stored_key(cache_key)))
+            .ok_or_else(|| anyhow::anyhow!("{} {}", OFFLINE_MISS, package))
+    }
+
+    /// Await `request` unless the deadline has passed, abandoning it if the
+    /// deadline passes while it is in flight.
+    async fn before_deadline<T>(
+        &self,
+        package: &str,
+        request: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let Some(deadline) = self.deadline else {
+            return request.await;
+        };
+        // Checked up front: `timeout_at` polls the request once before it
+        // looks at the deadline, which would still send it.
+        if tokio::time::Instant::now() >= deadline {
+            anyhow::bail!("{} {}", DEADLINE_MISS, package);
+        }
+        tokio::time::timeout_at(deadline, request)
+            .await
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("{} {}", DEADLINE_MISS, package)))
+    }
+
+    /// Fetch `cache_key` with `request` and cache the answer, unless a lookup
+    /// of the same key is already in flight, in which case its answer is
+    /// shared. This keeps a package listed in many files from being fetched
+    /// once per file on a cold cache.
+    async fn single_flight(
+        &self,
+        cache_key: &str,
+        request: impl std::future::Future<Output = Result<String>>,
+    ) -> Result<String> {
+        self.single_flight_with_etag(cache_key, async { Ok((request.await?, None)) })
+            .await
+    }
+
+    /// [`Self::single_flight`] for requests that also return the answer's
+    /// `ETag`, which is cached alongside it.
+    async fn single_flight_with_etag(
+        &self,
+        cache_key: &str,
+        request: impl std::future::Future<Output = Result<(String, Option<String>)>>,
+    ) -> Result<String> {
+        let cell = match self.in_flight.lock() {
+            Ok(mut in_flight) => Arc::clone(in_flight.entry(cache_key.to_string()).or_default()),
+            Err(_) => Arc::new(InFlight::new()),
+        };
+
+        // The lookup that runs the request keeps its own error, context and
+        // all; waiters get an error carrying the same message.
+        let mut own_error = None;
+        let shared = cell
+            .get_or_init(|| async {
+                // A lookup that finished between our cache miss and claiming
+                // the slot has already cached its answer.
+                let cached = self
+                    .enabled
+                    .then(|| {
+                        let key = self.stored_key(cache_key);
+                        self.cache.lock().ok()?.lookup(self.inner.name(), &key)
+                    })
+                    .flatten();
+                match cached {
+                    Some(CachedLookup::Version(version)) => return Ok(version),
+                    Some(CachedLookup::NotFound(message)) => return Err(message),
+                    None => {}
+                }
+                match request.await {
+                    Ok((version, etag)) => {
+                        self.cache_set(cache_key, &version, etag);
+                        Ok(version)
+                    }
+                    Err(err) => {
+                        let message = err.to_string();
+                        // Only a definitive 404 is worth remembering; a 5xx
+                        // or timeout may succeed on the next run.
+                        if is_not_found(&err) {
+                            self.cache_set_not_found(cache_key, &message);
+                        }
+                        own_error = Some(err);
+                        Err(message)
+                    }
+                }
+            })
+            .await
+            .clone();
+
+        if let Ok(mut in_flight) = self.in_flight.lock()
+            && in_flight
+                .get(cache_key)
+                .is_some_and(|current| Arc::ptr_eq(current, &cell))
+        {
+            in_flight.remove(cache_key);
+        }
+
+        match (shared, own_error) {
+            (Ok(version), _) => Ok(version),
+            (Err(_), Some(err)) => Err(err),
+            (Err(message), None) => Err(anyhow::anyhow!(message)),
+        }
+    }
+
+    /// Remember that the registry reported `package` missing (no-op if disabled).
+    fn cache_set_not_found(&self, package: &str, message: &str) {
+        if !self.enabled {
+            return;
+        }
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.set_not_found(
+                self.inner.name(),
+                &self.stored_key(package),
+                message.to_string(),
+            );
+        }
     }
 
     /// Set in cache (no-op if disabled). Does NOT save to disk - caller saves once at end.
-    fn cache_set(&self, package: &str, version: &str) {
+    fn cache_set(&self, package: &str, version: &str, etag: Option<String>) {
         if !self.enabled {
             return;
         }
         if let Ok(mut cache) = self.cache.lock() {
-            cache.set(self.inner.name(), package, version.to_string());
+            cache.set_with_etag(
+                self.inner.name(),
+                &self.stored_key(package),
+                version.to_string(),
+                etag,
+            );
+        }
+    }
+
+    /// Latest version of `package`, revalidating an expired cache entry with
+    /// its `ETag` when it has one. A 304 keeps the cached version; either way
+    /// the answer comes back with the `ETag` to cache beside it.
+    async fn fetch_latest_revalidated(&self, package: &str) -> Result<(String, Option<String>)> {
+        let cached = self
+            .enabled
+            .then(|| {
+                let key = self.stored_key(package);
+                self.cache.lock().ok()?.get_etag(self.inner.name(), &key)
+            })
+            .flatten();
+        let etag = cached.as_ref().map(|(_, etag)| etag.as_str());
+        match self
+            .inner
+            .get_latest_version_revalidated(package, etag)
+            .await?
+        {
+            Revalidated::Modified { version, etag } => Ok((version, etag)),
+            Revalidated::NotModified => match cached {
+                Some((version, etag)) => {
+                    tracing::debug!(registry = self.inner.name(), key = package, "revalidated");
+                    Ok((version, Some(etag)))
+                }
+                None => anyhow::bail!("{}: unexpected HTTP 304 for {}", self.inner.name(), package),
+            },
         }
     }
 }
@@ -231,26 +746,46 @@ impl<R: Registry> CachedRegistry<R> {
 #[async_trait]
 impl<R: Registry> Registry for CachedRegistry<R> {
     async fn get_latest_version(&self, package: &str) -> Result<String> {
-        if let Some(v) = self.cache_get(package) {
-            return Ok(v);
+        #[cfg(feature = "registry-fixture")]
+        if let Some(fixture) = &self.fixture {
+            return fixture.get_latest_version(package).await;
+        }
+        if self.offline {
+            return self.offline_get(package, package);
         }
-        let version = self.inner.get_latest_version(package).await?;
-        self.cache_set(package, &version);
-        Ok(version)
+        if let Some(cached) = self.cache_get(package) {
+            return cached;
+        }
+        self.single_flight_with_etag(
+            package,
+            self.before_deadline(package, self.fetch_latest_revalidated(package)),
+        )
+        .await
     }
 
     async fn get_latest_version_including_prereleases(&self, package: &str) -> Result<String> {
+        #[cfg(feature = "registry-fixture")]
+        if let Some(fixture) = &self.fixture {
+            return fixture
+                .get_latest_version_including_prereleases(package)
+                .await;
+        }
         // Pre-releases use separate cache key to avoid returning stable when pre-release needed
         let cache_key = format!("{}:prerelease", package);
-        if let Some(v) = self.cache_get(&cache_key) {
-            return Ok(v);
+        if self.offline {
+            return self.offline_get(&cache_key, package);
         }
-        let version = self
-            .inner
-            .get_latest_version_including_prereleases(package)
-            .await?;
-        self.cache_set(&cache_key, &version);
-        Ok(version)
+        if let Some(cached) = self.cache_get(&cache_key) {
+            return cached;
+        }
+        self.single_flight(
+            &cache_key,
+            self.before_deadline(
+                package,
+                self.inner.get_latest_version_including_prereleases(package),
+            ),
+        )
+        .await
     }
 
     async fn get_latest_version_matching(
@@ -258,21 +793,105 @@ impl<R: Registry> Registry for CachedRegistry<R> {
         package: &str,
         constraints: &str,
     ) -> Result<String> {
+        #[cfg(feature = "registry-fixture")]
+        if let Some(fixture) = &self.fixture {
+            return fixture
+                .get_latest_version_matching(package, constraints)
+                .await;
+        }
         // Constraint-matching uses composite key to cache per-constraint results
         let cache_key = format!("{}:match:{}", package, constraints);
-        if let Some(v) = self.cache_get(&cache_key) {
-            return Ok(v);
+        if self.offline {
+            return self.offline_get(&cache_key, package);
         }
-        let version = self
-            .inner
-            .get_latest_version_matching(package, constraints)
-            .await?;
-        self.cache_set(&cache_key, &version);
-        Ok(version)
+        if let Some(cached) = self.cache_get(&cache_key) {
+            return cached;
+        }
+        self.single_flight(
+            &cache_key,
+            self.before_deadline(
+                package,
+                self.inner.get_latest_version_matching(package, constraints),
+            ),
+        )
+        .await
     }
 
     async fn list_versions(&self, package: &str) -> Result<Vec<VersionMeta>> {
-        self.inner.list_versions(package).await
+        #[cfg(feature = "registry-fixture")]
+        if let Some(fixture) = &self.fixture {
+            return fixture.list_versions(package).await;
+        }
+        // Version lists are not kept in the cache file, so offline there is
+        // nothing to return.
+        if self.offline {
+            anyhow::bail!("{} {}", OFFLINE_MISS, package);
+        }
+        let cell = match self.version_lists.lock() {
+            Ok(mut lists) => Arc::clone(lists.entry(package.to_string()).or_default()),
+            Err(_) => Arc::new(VersionList::new()),
+        };
+        let mut own_error = None;
+        let shared = cell
+            .get_or_init(|| async {
+                self.before_deadline(package, self.inner.list_versions(package))
+                    .await
+                    .map_err(|err| {
+                        let message = err.to_string();
+                        own_error = Some(err);
+                        message
+                    })
+            })
+            .await
+            .clone();
+
+        match (shared, own_error) {
+            (Ok(versions), _) => Ok(versions),
+            (Err(message), own_error) => {
+                // A failed list is not kept: the next check asks again.
+                if let Ok(mut lists) = self.version_lists.lock()
+                    && lists
+                        .get(package)
+                        .is_some_and(|current| Arc::ptr_eq(current, &cell))
+                {
+                    lists.remove(package);
+                }
+                Err(own_error.unwrap_or_else(|| anyhow::anyhow!(message)))
+            }
+        }
+    }
+
+    async fn resolve_tag_commit(&self, package: &str, tag: &str) -> Result<String> {
+        #[cfg(feature = "registry-fixture")]
+        if let Some(fixture) = &self.fixture {
+            return fixture.resolve_tag_commit(package, tag).await;
+        }
+        // Cached beside the package's versions under `package@tag`.
+        let cache_key = format!("{}@{}", package, tag);
+        if self.offline {
+            return self.offline_get(&cache_key, package);
+        }
+        if let Some(cached) = self.cache_get(&cache_key) {
+            return cached;
+        }
+        self.single_flight(
+            &cache_key,
+            self.before_deadline(package, self.inner.resolve_tag_commit(package, tag)),
+        )
+        .await
+    }
+
+    async fn package_links(&self, package: &str) -> Result<PackageLinks> {
+        #[cfg(feature = "registry-fixture")]
+        if let Some(fixture) = &self.fixture {
+            return fixture.package_links(package).await;
+        }
+        // Links are best-effort: offline, fall back to the registry page.
+        if self.offline {
+            return Ok(PackageLinks::default());
+        }
+        self.before_deadline(package, self.inner.package_links(package))
+            .await
     }
 
     fn name(&self) -> &'static str {
@@ -342,6 +961,8 @@ mod tests {
                 version: "0.1.0".to_string(),
                 fetched_at: expired_time,
                 versions: None,
+                not_found: None,
+                etag: None,
             },
         );
 
@@ -349,6 +970,34 @@ mod tests {
         assert!(cache.get("pypi", "old").is_none());
     }
 
+    #[test]
+    fn test_cache_ttl_is_configurable() {
+        let two_hours_ago = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - (2 * 3600);
+        let entry = CacheEntry {
+            version: "1.0.0".to_string(),
+            fetched_at: two_hours_ago,
+            versions: None,
+            not_found: None,
+            etag: None,
+        };
+
+        // Past the one-hour default...
+        let mut cache = Cache::default();
+        cache.pypi.insert("flask".to_string(), entry.clone());
+        assert!(cache.get("pypi", "flask").is_none());
+        assert_eq!(cache.summary().expired, 1);
+
+        // ...but fresh under a day-long TTL.
+        let mut cache = Cache::default().with_ttl(Duration::from_secs(24 * 3600));
+        cache.pypi.insert("flask".to_string(), entry);
+        assert_eq!(cache.get("pypi", "flask"), Some("1.0.0".to_string()));
+        assert_eq!(cache.summary().expired, 0);
+    }
+
     #[test]
     fn test_cache_prune() {
         let mut cache = Cache::default();
@@ -369,6 +1018,8 @@ mod tests {
                 version: "0.1.0".to_string(),
                 fetched_at: expired_time,
                 versions: None,
+                not_found: None,
+                etag: None,
             },
         );
 
@@ -383,6 +1034,46 @@ mod tests {
         assert!(!cache.pypi.contains_key("old"));
     }
 
+    #[test]
+    fn test_cache_summary() {
+        let mut cache = Cache::default();
+        assert_eq!(cache.summary().total_entries(), 0);
+        assert_eq!(cache.summary().oldest, None);
+        assert_eq!(cache.summary().hit_rate(), None);
+
+        cache.set("pypi", "requests", "2.31.0".to_string());
+        cache.set("npm", "lodash", "4.17.21".to_string());
+        let expired_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - (25 * 3600);
+        cache.pypi.insert(
+            "old".to_string(),
+            CacheEntry {
+                version: "0.1.0".to_string(),
+                fetched_at: expired_time,
+                versions: None,
+                not_found: None,
+                etag: None,
+            },
+        );
+        cache.record_lookup(true);
+        cache.record_lookup(true);
+        cache.record_lookup(true);
+        cache.record_lookup(false);
+
+        let summary = cache.summary();
+        assert_eq!(summary.total_entries(), 3);
+        assert_eq!(summary.entries[0], ("pypi", 2));
+        assert_eq!(summary.entries[1], ("npm", 1));
+        assert_eq!(summary.entries.len(), REGISTRIES.len());
+        assert_eq!(summary.expired, 1);
+        assert_eq!(summary.oldest, Some(expired_time));
+        assert!(summary.newest.unwrap() > expired_time);
+        assert_eq!(summary.hit_rate(), Some(0.75));
+    }
+
     #[test]
     fn test_cache_unknown_registry() {
         let mut cache = Cache::default();
@@ -458,6 +1149,8 @@ mod tests {
                     prerelease: false,
                 },
             ]),
+            not_found: None,
+            etag: None,
         };
         let json = serde_json::to_string(&entry).unwrap();
         let back: CacheEntry = serde_json::from_str(&json).unwrap();
@@ -480,6 +1173,8 @@ mod tests {
             version: "1.0.0".to_string(),
             fetched_at: 1_700_000_000,
             versions: None,
+            not_found: None,
+            etag: None,
         };
         let json = serde_json::to_string(&entry).unwrap();
         assert!(
@@ -578,6 +1273,78 @@ mod tests {
         assert_eq!(version, "2.31.0");
     }
 
+    #[tokio::test]
+    async fn test_cached_registry_key_prefix_keeps_entries_apart() {
+        use crate::registry::MockRegistry;
+
+        let cache = Cache::new_shared();
+        cache
+            .lock()
+            .unwrap()
+            .set("pypi", "internal-lib", "9.9.9".to_string());
+
+        let mock = MockRegistry::new("pypi").with_version("internal-lib", "1.2.0");
+        let cached =
+            CachedRegistry::new(mock, cache.clone(), true).with_key_prefix("https://nexus.corp");
+
+        let version = cached.get_latest_version("internal-lib").await.unwrap();
+        assert_eq!(version, "1.2.0");
+        let c = cache.lock().unwrap();
+        assert_eq!(
+            c.get("pypi", "https://nexus.corp|internal-lib"),
+            Some("1.2.0".to_string())
+        );
+        assert_eq!(c.get("pypi", "internal-lib"), Some("9.9.9".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_cached_registry_refetches_expired_entry() {
+        use crate::registry::MockRegistry;
+
+        let stale_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - (2 * 3600);
+        let mut stale = Cache::default().with_ttl(Duration::from_secs(3600));
+        stale.pypi.insert(
+            "flask".to_string(),
+            CacheEntry {
+                version: "2.0.0".to_string(),
+                fetched_at: stale_time,
+                versions: None,
+                not_found: None,
+                etag: None,
+            },
+        );
+        let cache = Arc::new(Mutex::new(stale));
+        let mock = MockRegistry::new("pypi").with_version("flask", "3.0.0");
+        let cached = CachedRegistry::new(mock, cache.clone(), true);
+
+        let version = cached.get_latest_version("flask").await.unwrap();
+        assert_eq!(version, "3.0.0");
+
+        // The entry is replaced with the fresh answer and a new timestamp.
+        let c = cache.lock().unwrap();
+        assert_eq!(c.get("pypi", "flask"), Some("3.0.0".to_string()));
+        assert!(c.pypi["flask"].fetched_at > stale_time);
+    }
+
+    #[tokio::test]
+    async fn test_cached_registry_counts_hits_and_misses() {
+        use crate::registry::MockRegistry;
+
+        let mock = MockRegistry::new("pypi").with_version("flask", "3.0.0");
+        let cache: Arc<Mutex<Cache>> = Arc::new(Mutex::new(Cache::default()));
+        let cached = CachedRegistry::new(mock, cache.clone(), true);
+
+        cached.get_latest_version("flask").await.unwrap();
+        cached.get_latest_version("flask").await.unwrap();
+
+        let counters = cache.lock().unwrap().summary().counters;
+        assert_eq!(counters, CacheCounters { hits: 1, misses: 1 });
+    }
+
     #[tokio::test]
     async fn test_cached_registry_disabled() {
         use crate::registry::MockRegistry;
@@ -664,4 +1431,342 @@ mod tests {
 
         assert_eq!(cached.name(), "npm");
     }
+
+    #[tokio::test]
+    async fn test_cached_registry_offline_uses_stale_cache_and_never_fetches() {
+        use crate::registry::MockRegistry;
+
+        let mock = MockRegistry::new("pypi")
+            .with_version("flask", "3.1.0")
+            .with_version("requests", "2.32.3");
+        let mut cache = Cache::default();
+        cache.pypi.insert(
+            "flask".to_string(),
+            CacheEntry {
+                version: "3.0.0".to_string(),
+                fetched_at: 0,
+                versions: None,
+                not_found: None,
+                etag: None,
+            },
+        );
+        let cached =
+            CachedRegistry::new(mock, Arc::new(Mutex::new(cache)), true).with_offline(true);
+
+        // Expired entries still answer offline.
+        assert_eq!(cached.get_latest_version("flask").await.unwrap(), "3.0.0");
+
+        // The mock knows `requests`, but offline must not ask it.
+        let err = cached.get_latest_version("requests").await.unwrap_err();
+        assert_eq!(err.to_string(), "offline: no cached version for requests");
+        assert!(is_offline_miss(&format!("requests: {err}")));
+        assert!(cached.list_versions("flask").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cached_registry_past_deadline_serves_cache_and_never_fetches() {
+        use crate::registry::MockRegistry;
+
+        let mock = MockRegistry::new("pypi")
+            .with_version("flask", "3.1.0")
+            .with_version("requests", "2.32.3");
+        let cache = Arc::new(Mutex::new(Cache::default()));
+        cache
+            .lock()
+            .unwrap()
+            .set("pypi", "flask", "3.0.0".to_string());
+        let cached =
+            CachedRegistry::new(mock, cache, true).with_deadline(Some(tokio::time::Instant::now()));
+
+        assert_eq!(cached.get_latest_version("flask").await.unwrap(), "3.0.0");
+
+        let err = cached.get_latest_version("requests").await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "max runtime reached: no version lookup for requests"
+        );
+        assert!(is_deadline_miss(&format!("requests: {err}")));
+        assert!(!is_offline_miss(&format!("requests: {err}")));
+        assert!(cached.list_versions("requests").await.is_err());
+    }
+
+    /// Answers 304 when revalidated with `ETag` `"v1"`, otherwise `3.0.0`
+    /// under `"v2"`, counting the requests.
+    struct EtagRegistry {
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Registry for EtagRegistry {
+        async fn get_latest_version(&self, _package: &str) -> Result<String> {
+            unreachable!("lookups go through get_latest_version_revalidated")
+        }
+
+        async fn get_latest_version_revalidated(
+            &self,
+            _package: &str,
+            etag: Option<&str>,
+        ) -> Result<Revalidated> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(match etag {
+                Some("\"v1\"") => Revalidated::NotModified,
+                _ => Revalidated::Modified {
+                    version: "3.0.0".to_string(),
+                    etag: Some("\"v2\"".to_string()),
+                },
+            })
+        }
+
+        fn name(&self) -> &'static str {
+            "npm"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_registry_revalidates_expired_entry_with_etag() {
+        let stale_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - (2 * 3600);
+        let mut stale = Cache::default();
+        for (package, etag) in [("lodash", "\"v1\""), ("react", "\"old\"")] {
+            stale.npm.insert(
+                package.to_string(),
+                CacheEntry {
+                    version: "2.0.0".to_string(),
+                    fetched_at: stale_time,
+                    versions: None,
+                    not_found: None,
+                    etag: Some(etag.to_string()),
+                },
+            );
+        }
+        let cache = Arc::new(Mutex::new(stale));
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let registry = EtagRegistry {
+            calls: Arc::clone(&calls),
+        };
+        let cached = CachedRegistry::new(registry, cache.clone(), true);
+
+        // 304: the cached version stands and its entry is fresh again.
+        assert_eq!(cached.get_latest_version("lodash").await.unwrap(), "2.0.0");
+        // Changed upstream: the new version and ETag replace the entry.
+        assert_eq!(cached.get_latest_version("react").await.unwrap(), "3.0.0");
+        // Both are now answered from the cache without a request.
+        cached.get_latest_version("lodash").await.unwrap();
+        cached.get_latest_version("react").await.unwrap();
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        let c = cache.lock().unwrap();
+        assert!(c.npm["lodash"].fetched_at > stale_time);
+        assert_eq!(c.npm["lodash"].etag.as_deref(), Some("\"v1\""));
+        assert_eq!(c.npm["react"].version, "3.0.0");
+        assert_eq!(c.npm["react"].etag.as_deref(), Some("\"v2\""));
+    }
+
+    /// Answers every lookup after a short delay, counting the requests.
+    struct SlowRegistry {
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl Registry for SlowRegistry {
+        async fn get_latest_version(&self, package: &str) -> Result<String> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            if self.fail {
+                anyhow::bail!("{package} not found");
+            }
+            Ok("1.0.0".to_string())
+        }
+
+        async fn list_versions(&self, package: &str) -> Result<Vec<VersionMeta>> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            if self.fail {
+                anyhow::bail!("{package} not found");
+            }
+            Ok(vec![VersionMeta {
+                version: "1.0.0".to_string(),
+                published_at: None,
+                yanked: false,
+                prerelease: false,
+                rust_version: None,
+                node_engines: None,
+                deprecated: None,
+            }])
+        }
+
+        fn name(&self) -> &'static str {
+            "pypi"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_registry_concurrent_lookups_share_one_request() {
+        for enabled in [true, false] {
+            let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let slow = SlowRegistry {
+                calls: Arc::clone(&calls),
+                fail: false,
+            };
+            let cached = CachedRegistry::new(slow, Arc::new(Mutex::new(Cache::default())), enabled);
+
+            let versions =
+                futures::future::join_all((0..8).map(|_| cached.get_latest_version("flask"))).await;
+
+            assert!(versions.iter().all(|v| v.as_deref().unwrap() == "1.0.0"));
+            assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+            assert!(cached.in_flight.lock().unwrap().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_registry_concurrent_lookups_share_errors() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let slow = SlowRegistry {
+            calls: Arc::clone(&calls),
+            fail: true,
+        };
+        let cached = CachedRegistry::new(slow, Arc::new(Mutex::new(Cache::default())), true);
+
+        let (first, second) = tokio::join!(
+            cached.get_latest_version("flask"),
+            cached.get_latest_version("flask")
+        );
+
+        assert_eq!(first.unwrap_err().to_string(), "flask not found");
+        assert_eq!(second.unwrap_err().to_string(), "flask not found");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // A failed lookup is not remembered: the next one asks again.
+        assert!(cached.get_latest_version("flask").await.is_err());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cached_registry_lists_versions_once_per_run() {
+        for enabled in [true, false] {
+            let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let slow = SlowRegistry {
+                calls: Arc::clone(&calls),
+                fail: false,
+            };
+            let cached = CachedRegistry::new(slow, Arc::new(Mutex::new(Cache::default())), enabled);
+
+            let (first, second) =
+                tokio::join!(cached.list_versions("flask"), cached.list_versions("flask"));
+            let third = cached.list_versions("flask").await;
+
+            for list in [first, second, third] {
+                assert_eq!(list.unwrap()[0].version, "1.0.0");
+            }
+            assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_registry_does_not_keep_failed_version_lists() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let slow = SlowRegistry {
+            calls: Arc::clone(&calls),
+            fail: true,
+        };
+        let cached = CachedRegistry::new(slow, Arc::new(Mutex::new(Cache::default())), true);
+
+        let (first, second) =
+            tokio::join!(cached.list_versions("flask"), cached.list_versions("flask"));
+
+        assert_eq!(first.unwrap_err().to_string(), "flask not found");
+        assert_eq!(second.unwrap_err().to_string(), "flask not found");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(cached.list_versions("flask").await.is_err());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(cached.version_lists.lock().unwrap().is_empty());
+    }
+
+    /// Fails every lookup with an HTTP error, counting the requests.
+    struct FailingRegistry {
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+        status: reqwest::StatusCode,
+    }
+
+    #[async_trait]
+    impl Registry for FailingRegistry {
+        async fn get_latest_version(&self, package: &str) -> Result<String> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(anyhow::anyhow!(crate::registry::http_error_message(
+                self.status,
+                "Package",
+                package,
+                None
+            )))
+        }
+
+        fn name(&self) -> &'static str {
+            "pypi"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_registry_caches_not_found_with_its_message() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let failing = FailingRegistry {
+            calls: Arc::clone(&calls),
+            status: reqwest::StatusCode::NOT_FOUND,
+        };
+        let cache = Arc::new(Mutex::new(Cache::default()));
+        let cached = CachedRegistry::new(failing, Arc::clone(&cache), true);
+
+        let first = cached.get_latest_version("reqeusts").await.unwrap_err();
+        let second = cached.get_latest_version("reqeusts").await.unwrap_err();
+
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(second.to_string(), first.to_string());
+        assert!(second.to_string().contains("not found (HTTP 404)"));
+        // Never served as a version, offline or otherwise.
+        let cache = cache.lock().unwrap();
+        assert_eq!(cache.get("pypi", "reqeusts"), None);
+        assert_eq!(cache.get_stale("pypi", "reqeusts"), None);
+    }
+
+    #[tokio::test]
+    async fn test_cached_registry_does_not_cache_server_errors() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let failing = FailingRegistry {
+            calls: Arc::clone(&calls),
+            status: reqwest::StatusCode::SERVICE_UNAVAILABLE,
+        };
+        let cached = CachedRegistry::new(failing, Arc::new(Mutex::new(Cache::default())), true);
+
+        assert!(cached.get_latest_version("flask").await.is_err());
+        assert!(cached.get_latest_version("flask").await.is_err());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_not_found_entries_use_the_negative_ttl() {
+        let mut cache = Cache::default().with_negative_ttl(Duration::from_secs(60));
+        cache.set_not_found("npm", "leftpad", "gone".to_string());
+        cache.set("npm", "left-pad", "1.3.0".to_string());
+        assert_eq!(
+            cache.lookup("npm", "leftpad"),
+            Some(CachedLookup::NotFound("gone".to_string()))
+        );
+
+        // Two minutes old: past the negative TTL, within the default one.
+        for package in ["leftpad", "left-pad"] {
+            cache.npm.get_mut(package).unwrap().fetched_at -= 120;
+        }
+        assert_eq!(cache.lookup("npm", "leftpad"), None);
+        assert_eq!(
+            cache.lookup("npm", "left-pad"),
+            Some(CachedLookup::Version("1.3.0".to_string()))
+        );
+        cache.prune();
+        assert!(!cache.npm.contains_key("leftpad"));
+        assert!(cache.npm.contains_key("left-pad"));
+    }
 }
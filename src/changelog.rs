@@ -0,0 +1,258 @@
+//! Release-notes links for `--changelog`.
+//!
+//! For each updated package `upd` prints a best-effort URL: the package's own
+//! changelog link when the registry publishes one (PyPI `project_urls`), else
+//! the releases page of its GitHub / GitLab / Codeberg repository, else the
+//! registry's package page. Only crates.io, npm and PyPI are asked for
+//! repository metadata; other ecosystems go straight to the package page.
+
+use crate::registry::{PackageLinks, Registry};
+use crate::updater::{FileType, Lang};
+use futures::stream::{self, StreamExt};
+use std::collections::HashSet;
+
+/// Registry lookups in flight at once.
+const LOOKUP_CONCURRENCY: usize = 8;
+
+/// Where to read about one updated package's new release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogLink {
+    pub package: String,
+    pub url: String,
+}
+
+/// The registry's web page for a package, if the ecosystem has one.
+pub fn package_page(file_type: FileType, package: &str) -> Option<String> {
+    match file_type {
+        FileType::Requirements
+        | FileType::PyProject
+        | FileType::Pipfile
+        | FileType::SetupPy
+        | FileType::CondaEnv => Some(format!(
+            "https://pypi.org/project/{}/",
+            package.to_lowercase().replace('_', "-")
+        )),
+        FileType::PackageJson | FileType::PnpmWorkspace => {
+            Some(format!("https://www.npmjs.com/package/{package}"))
+        }
+        FileType::CargoToml => Some(format!("https://crates.io/crates/{package}")),
+        FileType::GoMod => Some(format!("https://pkg.go.dev/{package}")),
+        FileType::Gemfile => Some(format!("https://rubygems.org/gems/{package}")),
+        FileType::Csproj => Some(format!("https://www.nuget.org/packages/{package}")),
+        // `owner/repo[/path]` is itself a GitHub repository.
+        FileType::GithubActions => {
+            let repo: Vec<&str> = package.split('/').take(2).collect();
+            (repo.len() == 2).then(|| format!("https://github.com/{}/releases", repo.join("/")))
+        }
+        // Hook repos are declared by URL.
+        FileType::PreCommitConfig => releases_url(package),
+        // Official images live under `_/`, the rest under `r/`.
+        FileType::Dockerfile => Some(if package.contains('/') {
+            format!("https://hub.docker.com/r/{package}/tags")
+        } else {
+            format!("https://hub.docker.com/_/{package}/tags")
+        }),
+        FileType::ComposerJson => Some(format!("https://packagist.org/packages/{package}")),
+        FileType::GradleCatalog => package.split_once(':').map(|(group, artifact)| {
+            format!("https://central.sonatype.com/artifact/{group}/{artifact}")
+        }),
+        FileType::Pubspec => Some(format!("https://pub.dev/packages/{package}/changelog")),
+        FileType::Nvmrc => Some("https://nodejs.org/en/about/previous-releases".to_string()),
+        FileType::PythonVersion => Some("https://www.python.org/downloads/".to_string()),
+        FileType::MiseToml | FileType::ToolVersions | FileType::TerraformTf => None,
+    }
+}
+
+/// The releases page of a GitHub, GitLab or Codeberg repository URL.
+///
+/// Accepts the shapes registries publish: `git+https://…`, `git://…`,
+/// `git@host:owner/repo.git`, `github:owner/repo`, trailing `.git`, `/` or
+/// `#readme`, and deeper paths such as `/tree/main/crates/foo`.
+pub fn releases_url(repository: &str) -> Option<String> {
+    let url = repository.trim();
+    let url = url.split(['#', '?']).next().unwrap_or(url);
+    let url = url.strip_prefix("git+").unwrap_or(url);
+    let rest = if let Some(shorthand) = url.strip_prefix("github:") {
+        format!("github.com/{shorthand}")
+    } else if let Some(shorthand) = url.strip_prefix("gitlab:") {
+        format!("gitlab.com/{shorthand}")
+    } else if let Some(scp) = url.strip_prefix("git@") {
+        scp.replacen(':', "/", 1)
+    } else {
+        let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+        // `ssh://git@github.com/owner/repo`
+        let without_user = without_scheme
+            .split_once('@')
+            .filter(|(user, _)| !user.contains('/'))
+            .map_or(without_scheme, |(_, host)| host);
+        without_user.to_string()
+    };
+
+    let mut segments = rest.split('/').filter(|s| !s.is_empty());
+    let host = segments.next()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host).to_string();
+    let owner = segments.next()?;
+    let repo = segments.next()?.trim_end_matches(".git");
+    if repo.is_empty() {
+        return None;
+    }
+    match host.as_str() {
+        "github.com" | "codeberg.org" => Some(format!("https://{host}/{owner}/{repo}/releases")),
+        "gitlab.com" => Some(format!("https://{host}/{owner}/{repo}/-/releases")),
+        _ => None,
+    }
+}
+
+/// Pick the most specific URL for a package: explicit changelog, repository
+/// releases (from the repository, then the homepage), then the package page.
+pub fn changelog_url(file_type: FileType, package: &str, links: &PackageLinks) -> Option<String> {
+    links
+        .changelog
+        .clone()
+        .or_else(|| links.repository.as_deref().and_then(releases_url))
+        .or_else(|| links.homepage.as_deref().and_then(releases_url))
+        .or_else(|| package_page(file_type, package))
+}
+
+/// Resolve a link for each distinct updated package, in input order.
+///
+/// `registry_for` supplies the registry to ask for repository metadata; a
+/// `None`, a failed lookup or missing metadata falls back to the package page.
+/// Packages without any known page are left out.
+pub async fn collect_links<'a>(
+    updates: impl IntoIterator<Item = (FileType, String)>,
+    registry_for: impl Fn(Lang) -> Option<&'a dyn Registry>,
+) -> Vec<ChangelogLink> {
+    let mut seen: HashSet<(Lang, String)> = HashSet::new();
+    let lookups: Vec<_> = updates
+        .into_iter()
+        .filter(|(file_type, package)| seen.insert((file_type.lang(), package.clone())))
+        .map(|(file_type, package)| (file_type, package, registry_for(file_type.lang())))
+        .collect();
+
+    stream::iter(lookups)
+        .map(|(file_type, package, registry)| async move {
+            let links = match registry {
+                Some(registry) => registry.package_links(&package).await.unwrap_or_default(),
+                None => PackageLinks::default(),
+            };
+            changelog_url(file_type, &package, &links).map(|url| ChangelogLink { package, url })
+        })
+        .buffered(LOOKUP_CONCURRENCY)
+        .filter_map(std::future::ready)
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MockRegistry;
+
+    #[test]
+    fn test_releases_url_normalizes_repository_shapes() {
+        for repository in [
+            "https://github.com/serde-rs/serde",
+            "https://github.com/serde-rs/serde/",
+            "https://github.com/serde-rs/serde.git",
+            "git+https://github.com/serde-rs/serde.git",
+            "git://github.com/serde-rs/serde.git",
+            "git@github.com:serde-rs/serde.git",
+            "ssh://git@github.com/serde-rs/serde",
+            "github:serde-rs/serde",
+            "https://www.github.com/serde-rs/serde#readme",
+            "https://github.com/serde-rs/serde/tree/master/serde_derive",
+        ] {
+            assert_eq!(
+                releases_url(repository).as_deref(),
+                Some("https://github.com/serde-rs/serde/releases"),
+                "{repository}"
+            );
+        }
+        assert_eq!(
+            releases_url("https://gitlab.com/group/project").as_deref(),
+            Some("https://gitlab.com/group/project/-/releases")
+        );
+        assert_eq!(releases_url("https://serde.rs"), None);
+        assert_eq!(releases_url("https://github.com/serde-rs"), None);
+    }
+
+    #[test]
+    fn test_package_page_per_ecosystem() {
+        assert_eq!(
+            package_page(FileType::Requirements, "Django_Rest").as_deref(),
+            Some("https://pypi.org/project/django-rest/")
+        );
+        assert_eq!(
+            package_page(FileType::PackageJson, "@angular/core").as_deref(),
+            Some("https://www.npmjs.com/package/@angular/core")
+        );
+        assert_eq!(
+            package_page(FileType::CargoToml, "serde").as_deref(),
+            Some("https://crates.io/crates/serde")
+        );
+        assert_eq!(
+            package_page(FileType::GithubActions, "actions/cache/restore").as_deref(),
+            Some("https://github.com/actions/cache/releases")
+        );
+        assert_eq!(package_page(FileType::MiseToml, "node"), None);
+    }
+
+    #[test]
+    fn test_changelog_url_prefers_explicit_then_repository_then_page() {
+        let mut links = PackageLinks {
+            repository: Some("https://github.com/psf/requests".to_string()),
+            homepage: Some("https://requests.readthedocs.io".to_string()),
+            changelog: Some("https://requests.readthedocs.io/en/latest/history/".to_string()),
+        };
+        assert_eq!(
+            changelog_url(FileType::Requirements, "requests", &links).as_deref(),
+            Some("https://requests.readthedocs.io/en/latest/history/")
+        );
+        links.changelog = None;
+        assert_eq!(
+            changelog_url(FileType::Requirements, "requests", &links).as_deref(),
+            Some("https://github.com/psf/requests/releases")
+        );
+        links.repository = None;
+        assert_eq!(
+            changelog_url(FileType::Requirements, "requests", &links).as_deref(),
+            Some("https://pypi.org/project/requests/")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collect_links_dedupes_and_falls_back_to_package_page() {
+        let registry = MockRegistry::new("crates.io").with_links(
+            "serde",
+            PackageLinks {
+                repository: Some("https://github.com/serde-rs/serde".to_string()),
+                ..PackageLinks::default()
+            },
+        );
+        let links = collect_links(
+            [
+                (FileType::CargoToml, "serde".to_string()),
+                (FileType::CargoToml, "anyhow".to_string()),
+                (FileType::CargoToml, "serde".to_string()),
+                (FileType::MiseToml, "node".to_string()),
+            ],
+            |lang| (lang == Lang::Rust).then_some(&registry as &dyn Registry),
+        )
+        .await;
+
+        assert_eq!(
+            links,
+            vec![
+                ChangelogLink {
+                    package: "serde".to_string(),
+                    url: "https://github.com/serde-rs/serde/releases".to_string(),
+                },
+                ChangelogLink {
+                    package: "anyhow".to_string(),
+                    url: "https://crates.io/crates/anyhow".to_string(),
+                },
+            ]
+        );
+    }
+}
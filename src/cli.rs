@@ -1,7 +1,12 @@
-use crate::updater::Lang;
+use crate::audit::SeverityThreshold;
+use crate::registry::PypiApiOrder;
+use crate::updater::{FileType, Lang, Strategy};
 use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
+/// Files processed at once unless `--max-concurrency` says otherwise.
+pub const DEFAULT_MAX_CONCURRENCY: usize = 8;
+
 pub const REVERT_TIP: &str = "Tip: changes are applied in-place \u{2014} use git to revert.";
 
 /// Three-valued output mode for clispec P1 compliance.
@@ -20,15 +25,114 @@ pub enum OutputMode {
     Json,
 }
 
+/// When to colour text output (`--color`).
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[value(rename_all = "lower")]
+pub enum ColorChoice {
+    /// Colour when stdout is a terminal, honouring `NO_COLOR` and `FORCE_COLOR` (default).
+    #[default]
+    Auto,
+    /// Always colour, even when piped.
+    Always,
+    /// Never colour; same as `--no-color`.
+    Never,
+}
+
+impl ColorChoice {
+    /// The `colored` override this choice implies, reading env vars through
+    /// `env`. `None` leaves `colored`'s own TTY and `CLICOLOR` detection in charge.
+    ///
+    /// Under `auto`, a non-empty `NO_COLOR` disables colour and a non-empty
+    /// `FORCE_COLOR` other than `0` enables it; `NO_COLOR` wins if both are set.
+    pub fn color_override(self, env: impl Fn(&str) -> Option<String>) -> Option<bool> {
+        let set = |name: &str| env(name).filter(|value| !value.is_empty());
+        match self {
+            ColorChoice::Always => Some(true),
+            ColorChoice::Never => Some(false),
+            ColorChoice::Auto if set("NO_COLOR").is_some() => Some(false),
+            ColorChoice::Auto => set("FORCE_COLOR")
+                .filter(|value| value != "0" && !value.eq_ignore_ascii_case("false"))
+                .map(|_| true),
+        }
+    }
+
+    /// Install this choice as the process-wide `colored` override.
+    pub fn apply(self) {
+        self.apply_with_env(|name| std::env::var(name).ok());
+    }
+
+    fn apply_with_env(self, env: impl Fn(&str) -> Option<String>) {
+        match self.color_override(env) {
+            Some(enabled) => colored::control::set_override(enabled),
+            None => colored::control::unset_override(),
+        }
+    }
+}
+
+/// Minimum severity of the diagnostic logs written to stderr (`--log-level`).
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[value(rename_all = "lower")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    fn as_tracing(self) -> tracing::Level {
+        match self {
+            LogLevel::Error => tracing::Level::ERROR,
+            LogLevel::Warn => tracing::Level::WARN,
+            LogLevel::Info => tracing::Level::INFO,
+            LogLevel::Debug => tracing::Level::DEBUG,
+            LogLevel::Trace => tracing::Level::TRACE,
+        }
+    }
+
+    /// Install the process-wide `tracing` subscriber, writing to stderr so
+    /// logs never interleave with the report on stdout. `ansi` colours the
+    /// level names.
+    pub fn init(self, ansi: bool) {
+        // try_init fails only when a subscriber is already installed (e.g. by
+        // an embedding library); the existing one keeps working.
+        let _ = tracing_subscriber::fmt()
+            .with_max_level(self.as_tracing())
+            .with_writer(std::io::stderr)
+            .with_ansi(ansi)
+            .try_init();
+    }
+}
+
 /// Kind of version bump to include when filtering updates.
-#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize)]
 #[value(rename_all = "lower")]
+#[serde(rename_all = "lowercase")]
 pub enum BumpLevel {
     Major,
     Minor,
     Patch,
 }
 
+/// Which findings fail the run (`--error-on`).
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[value(rename_all = "lower")]
+pub enum ErrorOn {
+    /// Exit 1 when a major update is available.
+    Major,
+    /// Exit 1 when a minor or major update is available.
+    Minor,
+    /// Exit 1 when a patch, minor or major update is available.
+    Patch,
+    /// Exit 1 on any pending change, including pins and held-back versions.
+    Any,
+    /// Exit non-zero only when the run hit errors.
+    Error,
+    /// Always exit 0.
+    None,
+}
+
 /// Output format for command results.
 #[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[value(rename_all = "lower")]
@@ -44,6 +148,14 @@ pub enum OutputFormat {
     /// a result and per-file pin locations. Only meaningful for `upd audit`;
     /// other subcommands fall back to JSON-equivalent output when this is set.
     Sarif,
+    /// CycloneDX 1.5 JSON SBOM. Only meaningful for `upd sbom` (its default).
+    Cyclonedx,
+    /// SPDX 2.3 JSON SBOM. Only meaningful for `upd sbom`.
+    Spdx,
+    /// Markdown tables of the updates, grouped by file, for pasting into a
+    /// pull request description. Only meaningful for `upd update`; other
+    /// subcommands print text.
+    Markdown,
 }
 
 #[derive(Parser)]
@@ -51,7 +163,7 @@ pub enum OutputFormat {
 #[command(
     author,
     version,
-    about = "A fast dependency updater for Python, Node.js, Rust, Go, Ruby, .NET, Terraform, GitHub Actions, pre-commit, and Mise/asdf projects",
+    about = "A fast dependency updater for Python, Node.js, Rust, Go, Ruby, .NET, PHP, Terraform, Docker, GitHub Actions, pre-commit, and Mise/asdf projects",
     after_help = "Run 'upd schema' for machine-readable interface description (clispec v0.2).\n\nTip: changes are applied in-place \u{2014} use git to revert."
 )]
 pub struct Cli {
@@ -73,26 +185,58 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub no_cache: bool,
 
-    /// Disable colored output
-    #[arg(long, global = true)]
+    /// Disable colored output (same as `--color never`)
+    #[arg(long, global = true, conflicts_with = "color")]
     pub no_color: bool,
 
+    /// When to color output: auto (default), always, or never.
+    ///
+    /// `auto` colors when stdout is a terminal and honours the `NO_COLOR` and
+    /// `FORCE_COLOR` environment variables; `always` forces color through
+    /// pagers and CI logs; `never` is `--no-color`.
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
     /// Verbose output
     #[arg(short, long, global = true)]
     pub verbose: bool,
 
-    /// Suppress all output except errors and warnings.
+    /// Write diagnostic logs to stderr at this level and above.
     ///
-    /// Useful in scripts where you only care about the exit code.
+    /// Logs cover file processing and registry requests (URL, status, retry
+    /// attempts, cache hits and misses) and are kept apart from the normal
+    /// output on stdout. Off unless given.
+    #[arg(long, global = true, value_enum, value_name = "LEVEL")]
+    pub log_level: Option<LogLevel>,
+
+    /// Print only errors (on stderr) and a one-line summary.
+    ///
+    /// Per-file update lines, notes and lockfile chatter are suppressed;
+    /// exit codes are unchanged, so `--check --quiet` suits cron jobs.
     #[arg(long, short = 'q', global = true, conflicts_with = "verbose")]
     pub quiet: bool,
 
+    /// Print human-readable text on stderr, keeping stdout for structured output.
+    ///
+    /// With `--format json` the progress and summary lines that JSON output
+    /// normally suppresses are printed on stderr alongside the JSON on
+    /// stdout, so a script can capture one and still show the other.
+    #[arg(long, global = true)]
+    pub progress_stderr: bool,
+
     /// Prompt before applying each update.
     ///
     /// Presents each available update one at a time so you can accept or skip it.
     #[arg(short, long, global = true)]
     pub interactive: bool,
 
+    /// Offer an `[e]dit` choice at interactive prompts to type a custom version.
+    ///
+    /// The typed version (e.g. `2.5.0` instead of the proposed `3.0.0`) is
+    /// checked against the ecosystem's version syntax and written as entered.
+    #[arg(long, global = true, requires = "interactive")]
+    pub edit: bool,
+
     /// Include only updates whose bump level exactly matches one of the given levels.
     ///
     /// Repeatable or comma-separated. Use when you want to restrict to an exact set
@@ -126,6 +270,18 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub full_precision: bool,
 
+    /// How far updates may move: `latest` (default), `compatible` or `n-1`.
+    ///
+    /// `compatible` keeps each dependency within the range its current version
+    /// is compatible with: `^1.2.0` stays below 2.0.0, `~1.2.0` below 1.3.0,
+    /// and exact or open-ended versions keep their major (minor, for 0.x).
+    /// Applies to Python, npm, Cargo and Go dependencies.
+    ///
+    /// `n-1` moves to the highest release below the newest one, so new
+    /// releases are never adopted first.
+    #[arg(long, global = true, value_enum, value_name = "STRATEGY", default_value_t = Strategy::Latest)]
+    pub strategy: Strategy,
+
     /// Limit to one or more ecosystems (repeatable, or comma-separated).
     ///
     /// Examples: --lang python  |  --lang python,rust  |  -l go -l node
@@ -144,14 +300,43 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub check: bool,
 
+    /// Decide the exit code from the kind of updates found: `major`, `minor`,
+    /// `patch`, `any`, `error` or `none`.
+    ///
+    /// Applies to every run, not just `--check`: `--apply --error-on major`
+    /// writes updates and still exits 1 if any of them was a major bump.
+    /// A level fails on updates of that bump or bigger; `error` fails only on
+    /// errors (exit 2) and `none` always exits 0. Bump filters such as
+    /// `--only-bump` narrow what is counted.
+    #[arg(long = "error-on", global = true, value_enum, value_name = "LEVEL")]
+    pub error_on: Option<ErrorOn>,
+
     /// Regenerate lockfiles after updating.
     ///
     /// Runs the narrowest per-ecosystem refresh command that updates only the
     /// packages `upd` just rewrote (e.g. `cargo update -p <pkg>`,
-    /// `bundle lock --update <pkg>`, `npm install --package-lock-only`).
+    /// `bundle lock --update <pkg>`, `npm install --package-lock-only`). A
+    /// dry run lists the lockfiles and commands instead of running them.
     #[arg(long, global = true)]
     pub lock: bool,
 
+    /// Regenerate lockfiles without updating any manifest.
+    ///
+    /// Skips the updaters and runs each discovered manifest's manifest-wide
+    /// lock refresh (e.g. `cargo update --workspace`, `npm install
+    /// --package-lock-only`), for when the manifests are already right but a
+    /// lockfile is stale. Dry-run lists the lockfiles; `--apply` regenerates.
+    #[arg(long = "update-lockfile-only", global = true)]
+    pub update_lockfile_only: bool,
+
+    /// Let `--lock` run package install scripts and builds.
+    ///
+    /// By default lockfile refreshes pass `--ignore-scripts` (npm, pnpm, bun)
+    /// or `--no-build` (uv) so locking an untrusted project does not execute
+    /// its code. Use this when a lock only resolves with those steps.
+    #[arg(long, global = true, requires = "lock")]
+    pub allow_scripts: bool,
+
     /// Apply updates to files. Without --apply, runs in dry-run mode.
     ///
     /// When a positional path or no path (VCS root) is used, --apply is required
@@ -164,20 +349,47 @@ pub struct Cli {
     #[arg(short = 'c', long, global = true, value_name = "FILE")]
     pub config: Option<PathBuf>,
 
-    /// Set output format: text (default), json, or sarif.
+    /// Ignore config files entirely (no discovery, no ignore/pin rules)
+    ///
+    /// Every file is updated as if no `.updrc.toml`, `upd.toml`, or `.updrc`
+    /// existed. Unlike --no-cache, which bypasses the version cache, this
+    /// bypasses ignore, pin, exclude, and cooldown settings from config.
+    #[arg(long, global = true, conflicts_with = "config")]
+    pub no_config: bool,
+
+    /// Set output format: text (default), json, sarif, cyclonedx, or spdx.
     ///
     /// Use --format json for machine-readable output in scripts or CI.
     /// Use --format sarif with `upd audit` to emit a SARIF 2.1.0 document
-    /// suitable for upload to GitHub Code Scanning.
+    /// suitable for upload to GitHub Code Scanning. `upd sbom` takes
+    /// --format cyclonedx (default) or --format spdx.
     #[arg(long, global = true, value_enum, value_name = "FORMAT")]
     pub format: Option<OutputFormat>,
 
+    /// Print the package URL (purl) of every declared dependency and exit.
+    ///
+    /// One purl per line (`pkg:pypi/requests@2.28.0`, `pkg:npm/%40scope/name@1.0.0`),
+    /// ready for Grype, Trivy and other purl-aware tools. Versions are the
+    /// declared ones; no registry is contacted. Use --format json for a JSON
+    /// array instead.
+    #[arg(long, global = true)]
+    pub emit_purls: bool,
+
     /// Print the effective configuration and exit.
     ///
     /// Shows which config file was loaded and the resolved ignore/pin settings.
     #[arg(long, global = true)]
     pub show_config: bool,
 
+    /// Print the resolved ignore list and pin map, and the config file(s)
+    /// they came from, then exit.
+    ///
+    /// Uses the same discovery as a run (`--config`, `--no-config`, or the
+    /// nearest config above the first path), for diagnosing why a package
+    /// is or isn't updated.
+    #[arg(long, global = true)]
+    pub print_config: bool,
+
     /// Minimum release age before a version is eligible for update.
     ///
     /// Overrides the `[cooldown]` config for this run. Setting `--min-age 0`
@@ -191,24 +403,330 @@ pub struct Cli {
 
     /// Update only the named package(s), skipping all others.
     ///
-    /// Comma-separated or repeatable. Exact case-sensitive match.
+    /// Comma-separated or repeatable; the names union. Exact case-sensitive
+    /// match. `--only` is accepted as an alias.
     #[arg(
         long = "package",
+        visible_alias = "only",
         value_name = "NAME",
         global = true,
         value_delimiter = ','
     )]
     pub packages: Vec<String>,
 
+    /// Let the named package(s) update into pre-releases.
+    ///
+    /// Listed packages are resolved against alphas, betas and release
+    /// candidates even when their current version is stable; every other
+    /// package stays on stable releases. Comma-separated or repeatable; adds
+    /// to the config's `allow_prerelease` list.
+    #[arg(long, value_name = "NAME", global = true, value_delimiter = ',')]
+    pub allow_prerelease: Vec<String>,
+
+    /// Update only the named dependency group(s), leaving production
+    /// dependencies untouched.
+    ///
+    /// Matches `[dependency-groups.<name>]` and
+    /// `[project.optional-dependencies.<name>]` in pyproject.toml (Poetry's
+    /// `dev-dependencies` is the `dev` group), and `dev`, `peer` or `optional`
+    /// for the matching package.json sections. Comma-separated or repeatable.
+    /// Other manifest types have no groups and are updated as usual.
+    #[arg(
+        long = "group",
+        value_name = "NAME",
+        global = true,
+        value_delimiter = ','
+    )]
+    pub groups: Vec<String>,
+
+    /// Skip the run entirely when fewer than N updates are available.
+    ///
+    /// The update plan is computed first (as in dry-run) and only applied when
+    /// it contains at least N updates matching the bump filters. Below the
+    /// threshold nothing is written and `upd` exits 0. Useful for scheduled
+    /// bots that should not open a PR for a single trivial patch bump.
+    #[arg(
+        long = "min-update-count",
+        value_name = "N",
+        global = true,
+        conflicts_with = "interactive"
+    )]
+    pub min_update_count: Option<usize>,
+
+    /// Apply at most N updates this run, most impactful first.
+    ///
+    /// The update plan is computed first (as in dry-run) and ranked by bump
+    /// level, major before minor before patch, keeping file order within a
+    /// level. Only the top N are written; the rest are reported as deferred
+    /// and are picked up by a later run.
+    #[arg(
+        long = "newest-only",
+        value_name = "N",
+        global = true,
+        conflicts_with = "interactive",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub newest_only: Option<usize>,
+
+    /// Keep a copy of each file's previous content before writing it.
+    ///
+    /// The copy is written beside the file as `.<name>.upd.bak`; `upd
+    /// rollback` moves it back. Each `--backup` run replaces the backups of
+    /// the previous one, so only the most recent run can be rolled back.
+    /// Lockfiles regenerated by `--lock` are not backed up.
+    #[arg(long, global = true)]
+    pub backup: bool,
+
+    /// Apply updates, then run COMMAND in each changed directory and revert on failure.
+    ///
+    /// Every manifest (and its lockfiles) is backed up before writing. After
+    /// updates and any `--lock` regeneration, COMMAND runs through the shell
+    /// with the changed file's directory as the working directory. If it exits
+    /// non-zero, that directory's files are restored from the backup and the
+    /// reverted updates are reported as errors. Only takes effect with --apply.
+    ///
+    /// Example: `upd --apply --apply-only-if-tests-pass "cargo test"`
+    #[arg(
+        long = "apply-only-if-tests-pass",
+        value_name = "COMMAND",
+        global = true,
+        conflicts_with = "interactive"
+    )]
+    pub apply_only_if_tests_pass: Option<String>,
+
+    /// Write a JSON record of every dependency's final decision to PATH.
+    ///
+    /// Each dependency discovered in each scanned file appears exactly once,
+    /// with its decision (`updated`, `pinned`, `held_back`,
+    /// `skipped_by_cooldown`, `ignored`, `filtered`, `not_bumpable`,
+    /// `constrained`, `unchanged` or `error`) and a human-readable reason.
+    /// Written in both dry-run and apply mode.
+    #[arg(
+        long = "decisions-file",
+        value_name = "PATH",
+        global = true,
+        conflicts_with = "interactive"
+    )]
+    pub decisions_file: Option<PathBuf>,
+
+    /// Also write a compact JSON summary of the run's counts to PATH.
+    ///
+    /// Holds only the top-level counts (updated, major, minor, patch, pinned,
+    /// ignored, errors), overall and per ecosystem, while stdout keeps its
+    /// normal output. Written in both dry-run and apply mode.
+    #[arg(
+        long = "summary-json",
+        value_name = "PATH",
+        global = true,
+        conflicts_with = "interactive"
+    )]
+    pub summary_json: Option<PathBuf>,
+
+    /// Fail the run when a dependency is declared without a lower bound.
+    ///
+    /// Flags npm `"*"`/`"latest"`, bare PyPI names, Cargo `"*"`, Pipfile
+    /// `"*"` and ceiling-only specs such as `<3`. Each offender is reported as
+    /// an error with its file and line, and the run exits 2.
+    #[arg(
+        long = "treat-unconstrained-as-error",
+        global = true,
+        conflicts_with = "interactive"
+    )]
+    pub treat_unconstrained_as_error: bool,
+
+    /// Skip Cargo versions that need a newer Rust than the project supports.
+    ///
+    /// The project MSRV is `rust-version` from `[package]` (or the workspace
+    /// root's `[workspace.package]`), else the installed `rustc`. When the
+    /// latest crate version declares a higher `rust-version`, the newest
+    /// compatible version is chosen instead and a note explains the skip.
+    #[arg(long = "respect-msrv", global = true)]
+    pub respect_msrv: bool,
+
+    /// Update npm packages even when their `engines.node` excludes the project's Node.
+    ///
+    /// By default, when package.json declares `engines.node`, an update whose
+    /// new version requires a newer Node than the lowest one the project
+    /// supports is held back by engines and reported as a warning.
+    #[arg(long = "ignore-engines", global = true)]
+    pub ignore_engines: bool,
+
+    /// Update npm packages to versions the registry marks as deprecated.
+    ///
+    /// By default, when the version an npm dependency would move to is
+    /// deprecated, the newest non-deprecated version above the current one is
+    /// written instead; with none available the dependency is held back. The
+    /// deprecation message is reported as a warning either way.
+    #[arg(long = "allow-deprecated", global = true)]
+    pub allow_deprecated: bool,
+
+    /// Report pyproject requirements whose markers can never match `requires-python`.
+    ///
+    /// A requirement like `foo>=1.0; python_version >= "3.11"` is dead when
+    /// `[project] requires-python` excludes 3.11 entirely. Such lines are
+    /// reported as warnings so they can be cleaned up by hand; they are never
+    /// deleted.
+    #[arg(long = "prune-dead-markers", global = true)]
+    pub prune_dead_markers: bool,
+
+    /// Show each file's changes as a colored unified diff.
+    ///
+    /// Replaces the per-package `old → new` lines in text output with a diff
+    /// of the rewritten file, e.g. `upd --diff --dry-run` for code review.
+    /// Applies to every file type.
+    #[arg(long, global = true, conflicts_with = "interactive")]
+    pub diff: bool,
+
+    /// Group `@types/*` updates under their runtime package.
+    ///
+    /// In text output, an update of `@types/foo` is printed right below the
+    /// update of `foo` from the same file, so the typings can be checked
+    /// against the new runtime version at a glance.
+    #[arg(long = "link-types", global = true)]
+    pub link_types: bool,
+
+    /// Update a manifest of this type read from stdin; pass `-` as the path.
+    ///
+    /// The updated manifest is written to stdout and no file is touched, so
+    /// `cat pyproject.toml | upd update - --stdin-format pyproject` works as a
+    /// filter. Errors and warnings go to stderr. Accepts the `file_type`
+    /// names of the JSON report, e.g. `requirements`, `package_json`,
+    /// `cargo_toml`.
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "FILE_TYPE",
+        visible_alias = "file-type",
+        conflicts_with_all = ["interactive", "diff", "lock", "apply_only_if_tests_pass", "min_update_count"]
+    )]
+    pub stdin_format: Option<FileType>,
+
+    /// Answer from the local cache only; never contact a registry.
+    ///
+    /// Version lookups use the cached answer from earlier runs, however old.
+    /// Packages with no cached answer are reported as offline-skipped rather
+    /// than as errors. For `audit`, only cached OSV results are used: any
+    /// package whose cached result is missing or older than 24 hours is
+    /// reported as an error and the command exits with status 2.
+    #[arg(long, global = true, conflicts_with = "no_cache")]
+    pub offline: bool,
+
+    /// How long a cached version stays fresh, e.g. `30m` or `24h` (units: s,
+    /// m, h, d, w).
+    ///
+    /// A lookup that finds an older entry refetches it from the registry and
+    /// updates the cache. `--offline` still uses entries of any age.
+    #[arg(long = "cache-ttl", global = true, value_name = "DURATION", default_value = "1h", value_parser = parse_positive_duration)]
+    pub cache_ttl: std::time::Duration,
+
+    /// How long a "package not found" answer is cached, e.g. `5m` (units: s,
+    /// m, h, d, w).
+    ///
+    /// Only definitive HTTP 404s are cached, so a mistyped name fails fast on
+    /// repeated runs; server errors and timeouts are always retried.
+    #[arg(long = "negative-cache-ttl", global = true, value_name = "DURATION", default_value = "5m", value_parser = parse_positive_duration)]
+    pub negative_cache_ttl: std::time::Duration,
+
+    /// Overall time budget, e.g. `90s` or `5m` (units: s, m, h, d, w).
+    ///
+    /// Once it passes, no new registry request is started and requests in
+    /// flight are abandoned. Whatever resolved in time is reported, and
+    /// written in apply mode; the skipped packages are listed and the run
+    /// exits with status 7 (timed out, partial).
+    #[arg(long = "max-runtime", global = true, value_name = "DURATION", value_parser = parse_positive_duration)]
+    pub max_runtime: Option<std::time::Duration>,
+
+    /// Order in which PyPI's Simple and JSON APIs are tried.
+    ///
+    /// The default `simple,json` tries the PEP 691 Simple API and falls back
+    /// to the legacy JSON API. Use `json,simple` when a mirror serves the JSON
+    /// API faster, or `simple-only` / `json-only` for private indexes that
+    /// mishandle one of them. With `simple-only`, release dates are
+    /// unavailable, so cooldown cannot hold packages back.
+    #[arg(
+        long = "pypi-api",
+        global = true,
+        value_enum,
+        default_value = "simple,json"
+    )]
+    pub pypi_api: PypiApiOrder,
+
+    /// Point ecosystems at other registries, e.g.
+    /// `python=https://art/api/pypi/simple,node=https://art/api/npm`.
+    ///
+    /// Overrides the URL each ecosystem would otherwise detect from its own
+    /// environment variables and config files (`PIP_INDEX_URL`, `.npmrc`,
+    /// `GOPROXY`, ...); credentials are still looked up for the mapped host.
+    /// Supported: python, node, rust, go, ruby, dotnet, php, jvm, dart.
+    /// Repeatable.
+    #[arg(
+        long = "registry-map",
+        global = true,
+        value_name = "LANG=URL",
+        value_delimiter = ',',
+        value_parser = parse_registry_mapping
+    )]
+    pub registry_map: Vec<(Lang, String)>,
+
     /// Disable .gitignore filtering and walk every dependency file in the tree.
     ///
     /// By default, `upd` honors `.gitignore`, `.git/info/exclude`, and the
     /// global gitignore when discovering dependency files. Pass `--no-ignore`
     /// to scan files git would ignore. Equivalent to `rg --no-ignore`.
+    /// `--no-gitignore` is accepted as an alias.
     /// Explicit file paths are always processed regardless of this flag.
-    #[arg(long = "no-ignore", global = true)]
+    #[arg(long = "no-ignore", visible_alias = "no-gitignore", global = true)]
     pub no_ignore: bool,
 
+    /// Descend into hidden directories and pick up hidden dependency files.
+    ///
+    /// By default, discovery prunes every entry starting with `.` except the
+    /// dotfiles `upd` updates (`.github`, `.pre-commit-config.yaml`, ...).
+    /// `.git` is never walked. Equivalent to `rg --hidden`.
+    #[arg(long = "include-hidden", global = true)]
+    pub include_hidden: bool,
+
+    /// Only consider declared (direct) dependencies.
+    ///
+    /// This is the default; the flag makes it explicit in scripts and rejects
+    /// a conflicting --include-transitive.
+    #[arg(
+        long = "only-direct",
+        global = true,
+        conflicts_with = "include_transitive"
+    )]
+    pub only_direct: bool,
+
+    /// Also report transitive dependencies pinned in lockfiles.
+    ///
+    /// Locked packages the manifest does not declare are checked for newer
+    /// versions (update) or known vulnerabilities (audit) and reported; they
+    /// are never rewritten and do not change the exit code of `update`. Reads
+    /// Cargo.lock, package-lock.json, poetry.lock, uv.lock and Pipfile.lock.
+    #[arg(
+        long = "include-transitive",
+        global = true,
+        conflicts_with = "interactive"
+    )]
+    pub include_transitive: bool,
+
+    /// Print a release-notes link for each updated package.
+    ///
+    /// Best effort: the changelog URL a package publishes (PyPI), else the
+    /// releases page of its GitHub / GitLab repository as reported by
+    /// crates.io, npm or PyPI, else the registry's package page.
+    #[arg(long, global = true, conflicts_with = "interactive")]
+    pub changelog: bool,
+
+    /// Skip dependency files whose path matches GLOB (repeatable).
+    ///
+    /// Patterns match against the path relative to the scanned directory,
+    /// e.g. `--exclude '**/tests/fixtures/**'`. Adds to the config `exclude`
+    /// list; explicit file arguments are always processed.
+    #[arg(long = "exclude", value_name = "GLOB", global = true)]
+    pub exclude: Vec<String>,
+
     /// Disable TLS certificate verification.
     ///
     /// Skips verification of server certificates for all HTTPS requests this run.
@@ -219,6 +737,64 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub insecure: bool,
 
+    /// Per-request timeout for registry and advisory lookups, in seconds.
+    #[arg(long, global = true, value_name = "SECS", default_value_t = crate::http::DEFAULT_TIMEOUT.as_secs(),
+          value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: u64,
+
+    /// Retries after a network error or 5xx response (default 2).
+    ///
+    /// Backoff starts at 100ms and doubles per retry, capped at 5s; each wait
+    /// is a random fraction of that ceiling so parallel retries spread out.
+    /// `--retries 0` disables retrying entirely: each request is tried once.
+    #[arg(long, global = true, value_name = "N", default_value_t = crate::http::DEFAULT_RETRIES)]
+    pub retries: u32,
+
+    /// Maximum registry requests in flight to any one host (default unlimited).
+    ///
+    /// Keyed on the host of the registry URL, so one mirror serving several
+    /// ecosystems (e.g. a Nexus proxying PyPI, npm and crates.io) gets a
+    /// single shared cap.
+    #[arg(long, global = true, value_name = "N",
+          value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub resolve_concurrency_per_host: Option<usize>,
+
+    /// Maximum registry requests per second, across every host and
+    /// ecosystem (default unlimited).
+    ///
+    /// Up to one second's worth of requests go out at once; the rest wait
+    /// for their turn. Retries count against the limit too. Fractions such as
+    /// `0.5` are accepted.
+    #[arg(long = "rate-limit", global = true, value_name = "REQ/S", value_parser = parse_rate_limit)]
+    pub rate_limit: Option<f64>,
+
+    /// Maximum files processed at once (default 8).
+    ///
+    /// Each file resolves its own dependencies concurrently, so this bounds
+    /// how many of those batches run together. Combine with
+    /// `--resolve-concurrency-per-host` to cap requests to a rate-limited
+    /// registry; `--no-parallel` overrides both.
+    #[arg(long, global = true, value_name = "N", default_value_t = DEFAULT_MAX_CONCURRENCY,
+          value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub max_concurrency: usize,
+
+    /// Process files and registry lookups strictly one at a time.
+    ///
+    /// Output follows file discovery order and requests are sent one after
+    /// another, so failures are reproducible and verbose logs read linearly.
+    /// Much slower than the default; overrides `--resolve-concurrency-per-host`.
+    #[arg(long, global = true)]
+    pub no_parallel: bool,
+
+    /// Stop at the first file whose lookups fail instead of collecting every error.
+    ///
+    /// Lookups still in flight are cancelled and the remaining files are not
+    /// checked. Files finished before the failure are reported as usual; a
+    /// preview that stopped early (e.g. under `--min-update-count`) is never
+    /// applied.
+    #[arg(long, global = true)]
+    pub fail_fast: bool,
+
     /// Output format for structured consumers (auto/text/json).
     ///
     /// `auto` emits JSON when stdout is not a TTY and human-readable text
@@ -268,6 +844,36 @@ pub enum Command {
         /// Paths to scan and align
         #[arg()]
         paths: Vec<PathBuf>,
+
+        /// Align to this version instead of the highest one found.
+        ///
+        /// Applies to the packages selected with `--package` and rewrites every
+        /// occurrence, including ones currently higher. Constrained
+        /// occurrences are still skipped, with a warning when the version
+        /// falls below their lower bound.
+        #[arg(long, value_name = "VERSION", conflicts_with = "lowest")]
+        to: Option<String>,
+
+        /// Align down to the lowest version in use instead of the highest.
+        ///
+        /// For maximum compatibility across services: every higher occurrence
+        /// is downgraded. Constrained occurrences are still skipped.
+        #[arg(long)]
+        lowest: bool,
+    },
+
+    /// List dependencies with a newer version, never writing.
+    ///
+    /// Each entry shows the current and latest version; when the latest is a
+    /// new major, also how many majors behind the dependency is, counted from
+    /// the released versions (e.g. "2 majors behind: 3.x → 5.x").
+    Outdated {
+        /// Paths to scan
+        paths: Vec<PathBuf>,
+
+        /// Only list dependencies that are behind by at least one major version
+        #[arg(long)]
+        major: bool,
     },
 
     /// Check dependencies for known security vulnerabilities
@@ -288,21 +894,71 @@ pub enum Command {
         ///
         /// Requires `--apply` to write changes; without it this is a dry-run preview that
         /// exits 1 when fixable packages are found (or 0 with `--no-fail`).
-        #[arg(long = "fix-audit")]
+        /// `--fix` is accepted as a shorter spelling.
+        #[arg(long = "fix-audit", visible_alias = "fix")]
         fix_audit: bool,
 
-        /// Use the local audit cache only; do not contact OSV.
+        /// Only report vulnerabilities at or above this severity.
         ///
-        /// Any package whose cached result is missing or older than 24 hours is
-        /// reported as an error and causes the command to exit with status 2.
-        /// Combine with a prior online run to ensure the cache is populated.
-        #[arg(long)]
-        offline: bool,
+        /// Applied before display and before the exit-code decision, so
+        /// `upd audit --severity high` passes when only low/medium issues
+        /// exist. Vulnerabilities with no known severity are dropped when a
+        /// threshold is set.
+        #[arg(long, value_enum, value_name = "LEVEL")]
+        severity: Option<SeverityThreshold>,
+
+        /// Audit the components of a CycloneDX or SPDX JSON SBOM instead of scanning manifests.
+        ///
+        /// The format is detected from the document. Components are matched to
+        /// OSV ecosystems through their package URL (purl); components without a
+        /// purl or from unsupported ecosystems are reported and skipped.
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["paths", "fix_audit"])]
+        sbom: Option<PathBuf>,
+    },
+
+    /// Write an SBOM of the declared dependencies.
+    ///
+    /// Emits CycloneDX 1.5 JSON (default, `--format cyclonedx`) or SPDX 2.3
+    /// JSON (`--format spdx`) with a package URL (purl) per component. Versions
+    /// are the ones declared in the manifests, not lockfile-resolved versions.
+    Sbom {
+        /// Output file (`-` for stdout)
+        #[arg(value_name = "OUT")]
+        out: PathBuf,
+
+        /// Paths to scan
+        #[arg()]
+        paths: Vec<PathBuf>,
+    },
+
+    /// Restore the files changed by the last `--backup` run.
+    ///
+    /// Moves each `.<name>.upd.bak` backup under the scanned paths back over
+    /// its file, which also removes the backup.
+    Rollback {
+        /// Paths to scan for backups
+        #[arg()]
+        paths: Vec<PathBuf>,
     },
 
     /// Clear the version cache
     CleanCache,
 
+    /// Summarize the version cache: entries per registry, size on disk,
+    /// entry ages and the lookup hit rate.
+    #[command(visible_alias = "stats")]
+    CacheStats,
+
+    /// Merge `--format json` update reports from separate runs into one view.
+    ///
+    /// Sums the summaries and lists, per package, which report (repo) would
+    /// update it from which version. Each report is named by its path.
+    MergeReports {
+        /// `upd update --format json` report files
+        #[arg(value_name = "REPORT", required = true)]
+        paths: Vec<PathBuf>,
+    },
+
     /// Update upd itself
     SelfUpdate,
 
@@ -311,7 +967,68 @@ pub enum Command {
     /// Works offline with no authentication or configuration required.
     /// Consumers can use this to discover commands, arguments, output fields,
     /// and error kinds without parsing --help text.
-    Schema,
+    Schema {
+        /// Print the JSON Schema of the `--format json` reports instead
+        #[arg(long)]
+        report: bool,
+    },
+}
+
+/// Parse a `--max-runtime` budget or `--cache-ttl`: `<integer><unit>` with a
+/// positive value.
+fn parse_positive_duration(input: &str) -> Result<std::time::Duration, String> {
+    crate::cooldown::parse_duration(input)
+        .ok()
+        .and_then(|duration| duration.to_std().ok())
+        .filter(|duration| !duration.is_zero())
+        .ok_or_else(|| {
+            format!("invalid duration '{input}': expected a positive <number><s|m|h|d|w>, e.g. 90s")
+        })
+}
+
+/// Parse `--rate-limit`: a positive number of requests per second.
+fn parse_rate_limit(input: &str) -> Result<f64, String> {
+    input
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|rate| rate.is_finite() && *rate > 0.0)
+        .ok_or_else(|| {
+            format!(
+                "invalid rate limit '{input}': expected a positive number of requests per second"
+            )
+        })
+}
+
+/// Parse one `--registry-map` entry: `<lang>=<http(s) URL>`.
+fn parse_registry_mapping(input: &str) -> Result<(Lang, String), String> {
+    let (lang, url) = input
+        .split_once('=')
+        .ok_or_else(|| format!("invalid registry mapping '{input}': expected LANG=URL"))?;
+    let lang = Lang::from_str(lang.trim(), true)
+        .ok()
+        .filter(|lang| crate::registry::MAPPABLE_LANGS.contains(lang))
+        .ok_or_else(|| {
+            let supported: Vec<&str> = crate::registry::MAPPABLE_LANGS
+                .iter()
+                .map(Lang::as_str)
+                .collect();
+            format!(
+                "invalid registry mapping '{input}': unknown ecosystem '{}' (expected one of: {})",
+                lang.trim(),
+                supported.join(", ")
+            )
+        })?;
+    let url = url.trim();
+    // Cargo's sparse indexes carry a `sparse+` scheme prefix.
+    let is_http = url::Url::parse(url.strip_prefix("sparse+").unwrap_or(url))
+        .is_ok_and(|parsed| matches!(parsed.scheme(), "http" | "https"));
+    if !is_http {
+        return Err(format!(
+            "invalid registry mapping '{input}': '{url}' is not an http(s) URL"
+        ));
+    }
+    Ok((lang, url.to_string()))
 }
 
 impl Cli {
@@ -320,7 +1037,10 @@ impl Cli {
     /// Dry-run is implied by --check, --dry-run, or the absence of --apply/--yes
     /// when --interactive is not set.
     pub fn is_effective_dry_run(&self) -> bool {
-        self.check || self.dry_run || (!self.apply && !self.yes && !self.interactive)
+        matches!(self.command, Some(Command::Outdated { .. }))
+            || self.check
+            || self.dry_run
+            || (!self.apply && !self.yes && !self.interactive)
     }
 
     /// Returns true when JSON output should be emitted to stdout.
@@ -338,6 +1058,15 @@ impl Cli {
         }
     }
 
+    /// The effective `--color` choice, folding in `--no-color`.
+    pub fn color_choice(&self) -> ColorChoice {
+        if self.no_color {
+            ColorChoice::Never
+        } else {
+            self.color
+        }
+    }
+
     /// Returns explicitly provided paths, or an empty vec when none were given.
     ///
     /// Callers that need a default path (e.g. the VCS root) must resolve it
@@ -345,8 +1074,11 @@ impl Cli {
     pub fn get_paths(&self) -> Vec<PathBuf> {
         match &self.command {
             Some(Command::Update { paths }) if !paths.is_empty() => paths.clone(),
-            Some(Command::Align { paths }) if !paths.is_empty() => paths.clone(),
+            Some(Command::Align { paths, .. }) if !paths.is_empty() => paths.clone(),
+            Some(Command::Outdated { paths, .. }) if !paths.is_empty() => paths.clone(),
             Some(Command::Audit { paths, .. }) if !paths.is_empty() => paths.clone(),
+            Some(Command::Sbom { paths, .. }) if !paths.is_empty() => paths.clone(),
+            Some(Command::Rollback { paths }) if !paths.is_empty() => paths.clone(),
             _ if !self.paths.is_empty() => self.paths.clone(),
             _ => vec![],
         }
@@ -405,6 +1137,18 @@ mod tests {
         assert!(cli.lock);
     }
 
+    #[test]
+    fn test_cli_allow_scripts_requires_lock() {
+        let cli = Cli::try_parse_from(["upd", "--lock", "--allow-scripts"]).unwrap();
+        assert!(cli.allow_scripts);
+        assert!(
+            !Cli::try_parse_from(["upd", "--lock"])
+                .unwrap()
+                .allow_scripts
+        );
+        assert!(Cli::try_parse_from(["upd", "--allow-scripts"]).is_err());
+    }
+
     #[test]
     fn test_cli_parses_dry_run() {
         let cli = Cli::try_parse_from(["upd", "-n"]).unwrap();
@@ -436,6 +1180,15 @@ mod tests {
 
         let cli = Cli::try_parse_from(["upd", "--interactive"]).unwrap();
         assert!(cli.interactive);
+        assert!(!cli.edit);
+    }
+
+    #[test]
+    fn test_cli_edit_requires_interactive() {
+        let cli = Cli::try_parse_from(["upd", "--interactive", "--edit"]).unwrap();
+        assert!(cli.edit);
+
+        assert!(Cli::try_parse_from(["upd", "--edit"]).is_err());
     }
 
     #[test]
@@ -518,6 +1271,37 @@ mod tests {
         assert!(cli.full_precision);
     }
 
+    #[test]
+    fn test_cli_parses_strategy() {
+        let cli = Cli::try_parse_from(["upd"]).unwrap();
+        assert_eq!(cli.strategy, Strategy::Latest);
+
+        let cli = Cli::try_parse_from(["upd", "--strategy", "compatible"]).unwrap();
+        assert_eq!(cli.strategy, Strategy::Compatible);
+
+        let cli = Cli::try_parse_from(["upd", "--strategy", "n-1"]).unwrap();
+        assert_eq!(cli.strategy, Strategy::NMinusOne);
+
+        assert!(Cli::try_parse_from(["upd", "--strategy", "newest"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_stdin_format() {
+        let cli =
+            Cli::try_parse_from(["upd", "update", "-", "--stdin-format", "pyproject"]).unwrap();
+        assert_eq!(cli.stdin_format, Some(FileType::PyProject));
+        assert_eq!(cli.get_paths(), vec![PathBuf::from("-")]);
+
+        let cli = Cli::try_parse_from(["upd", "-", "--file-type", "package_json"]).unwrap();
+        assert_eq!(cli.stdin_format, Some(FileType::PackageJson));
+
+        assert!(Cli::try_parse_from(["upd", "-", "--stdin-format", "pyproject.toml"]).is_err());
+        assert!(
+            Cli::try_parse_from(["upd", "-", "--stdin-format", "pyproject", "--interactive"])
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_cli_parses_paths() {
         let cli = Cli::try_parse_from(["upd", "path1", "path2"]).unwrap();
@@ -560,6 +1344,100 @@ mod tests {
         assert!(matches!(cli.command, Some(Command::CleanCache)));
     }
 
+    #[test]
+    fn test_cli_parses_backup_and_rollback() {
+        let cli = Cli::try_parse_from(["upd", "--apply", "--backup"]).unwrap();
+        assert!(cli.backup);
+
+        let cli = Cli::try_parse_from(["upd", "rollback", "services"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Rollback { .. })));
+        assert_eq!(cli.get_paths(), vec![PathBuf::from("services")]);
+    }
+
+    #[test]
+    fn test_cli_parses_error_on() {
+        let cli = Cli::try_parse_from(["upd", "--error-on", "major"]).unwrap();
+        assert_eq!(cli.error_on, Some(ErrorOn::Major));
+        let cli = Cli::try_parse_from(["upd", "update", "--error-on", "none"]).unwrap();
+        assert_eq!(cli.error_on, Some(ErrorOn::None));
+        assert!(Cli::try_parse_from(["upd"]).unwrap().error_on.is_none());
+        assert!(Cli::try_parse_from(["upd", "--error-on", "critical"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_registry_map() {
+        let cli = Cli::try_parse_from([
+            "upd",
+            "--registry-map",
+            "python=https://art/api/pypi/simple,node=https://art/api/npm",
+            "--registry-map",
+            "rust=sparse+https://art/api/cargo/index/",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.registry_map,
+            vec![
+                (Lang::Python, "https://art/api/pypi/simple".to_string()),
+                (Lang::Node, "https://art/api/npm".to_string()),
+                (
+                    Lang::Rust,
+                    "sparse+https://art/api/cargo/index/".to_string()
+                ),
+            ]
+        );
+        assert!(
+            Cli::try_parse_from(["upd"])
+                .unwrap()
+                .registry_map
+                .is_empty()
+        );
+        assert!(Cli::try_parse_from(["upd", "--registry-map", "python"]).is_err());
+        assert!(Cli::try_parse_from(["upd", "--registry-map", "docker=https://art"]).is_err());
+        assert!(Cli::try_parse_from(["upd", "--registry-map", "node=art/npm"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_update_lockfile_only() {
+        let cli = Cli::try_parse_from(["upd", "--update-lockfile-only", "--apply"]).unwrap();
+        assert!(cli.update_lockfile_only);
+        assert!(!cli.lock);
+        assert!(!Cli::try_parse_from(["upd"]).unwrap().update_lockfile_only);
+    }
+
+    #[test]
+    fn test_cli_parses_schema_report() {
+        let cli = Cli::try_parse_from(["upd", "schema"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Schema { report: false })
+        ));
+        let cli = Cli::try_parse_from(["upd", "schema", "--report"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Schema { report: true })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parses_cache_stats_command() {
+        let cli = Cli::try_parse_from(["upd", "cache-stats"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::CacheStats)));
+        let cli = Cli::try_parse_from(["upd", "stats"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::CacheStats)));
+    }
+
+    #[test]
+    fn test_cli_parses_merge_reports_command() {
+        let cli = Cli::try_parse_from(["upd", "merge-reports", "a.json", "b.json"]).unwrap();
+        match cli.command {
+            Some(Command::MergeReports { paths }) => {
+                assert_eq!(paths, [PathBuf::from("a.json"), PathBuf::from("b.json")]);
+            }
+            _ => panic!("expected merge-reports"),
+        }
+        assert!(Cli::try_parse_from(["upd", "merge-reports"]).is_err());
+    }
+
     #[test]
     fn test_cli_parses_self_update_command() {
         let cli = Cli::try_parse_from(["upd", "self-update"]).unwrap();
@@ -713,7 +1591,7 @@ mod tests {
     fn test_cli_parses_align_command_with_paths() {
         let cli = Cli::try_parse_from(["upd", "align", "path1", "path2"]).unwrap();
         match cli.command {
-            Some(Command::Align { paths }) => {
+            Some(Command::Align { paths, .. }) => {
                 assert_eq!(paths.len(), 2);
                 assert_eq!(paths[0], PathBuf::from("path1"));
                 assert_eq!(paths[1], PathBuf::from("path2"));
@@ -722,6 +1600,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parses_align_to() {
+        let cli = Cli::try_parse_from(["upd", "align", "--to", "2.28.0", "--package", "requests"])
+            .unwrap();
+        match cli.command {
+            Some(Command::Align { to, .. }) => assert_eq!(to.as_deref(), Some("2.28.0")),
+            _ => panic!("Expected Align command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parses_align_lowest() {
+        let cli = Cli::try_parse_from(["upd", "align", "--lowest"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Align { lowest: true, .. })
+        ));
+        assert!(
+            Cli::try_parse_from(["upd", "align", "--lowest", "--to", "1.0.0"]).is_err(),
+            "--lowest and --to must conflict"
+        );
+    }
+
+    #[test]
+    fn test_cli_parses_outdated_major() {
+        let cli = Cli::try_parse_from(["upd", "outdated", "--major", "--apply", "app"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Outdated { major: true, .. })
+        ));
+        assert_eq!(cli.get_paths(), vec![PathBuf::from("app")]);
+        // `outdated` never writes, even with --apply.
+        assert!(cli.is_effective_dry_run());
+        assert!(Cli::try_parse_from(["upd", "--major"]).is_err());
+    }
+
     #[test]
     fn test_get_paths_uses_align_command_paths() {
         let cli = Cli::try_parse_from(["upd", "align", "cmd_path"]).unwrap();
@@ -762,6 +1676,24 @@ mod tests {
         assert!(cli.check);
     }
 
+    #[test]
+    fn test_cli_parses_audit_severity_threshold() {
+        let cli = Cli::try_parse_from(["upd", "audit", "--severity", "high"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Audit {
+                severity: Some(SeverityThreshold::High),
+                ..
+            })
+        ));
+        let cli = Cli::try_parse_from(["upd", "audit"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Audit { severity: None, .. })
+        ));
+        assert!(Cli::try_parse_from(["upd", "audit", "--severity", "moderate"]).is_err());
+    }
+
     #[test]
     fn test_cli_parses_audit_with_lang_filter() {
         let cli = Cli::try_parse_from(["upd", "audit", "--lang", "python"]).unwrap();
@@ -837,6 +1769,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parses_emit_purls() {
+        let cli = Cli::try_parse_from(["upd", "--emit-purls", "src"]).unwrap();
+        assert!(cli.emit_purls);
+        assert!(!Cli::try_parse_from(["upd"]).unwrap().emit_purls);
+    }
+
+    #[test]
+    fn test_cli_parses_changelog() {
+        assert!(
+            Cli::try_parse_from(["upd", "--changelog"])
+                .unwrap()
+                .changelog
+        );
+        assert!(!Cli::try_parse_from(["upd"]).unwrap().changelog);
+        assert!(Cli::try_parse_from(["upd", "--changelog", "-i"]).is_err());
+    }
+
     #[test]
     fn test_cli_parses_show_config() {
         let cli = Cli::try_parse_from(["upd", "--show-config"]).unwrap();
@@ -855,6 +1805,16 @@ mod tests {
         assert!(cli.show_config);
     }
 
+    #[test]
+    fn test_cli_parses_print_config() {
+        assert!(
+            Cli::try_parse_from(["upd", "--print-config"])
+                .unwrap()
+                .print_config
+        );
+        assert!(!Cli::try_parse_from(["upd"]).unwrap().print_config);
+    }
+
     // P6: --help should produce longer output than -h because field doc comments
     // have both a short first line (used by -h) and extended paragraphs (--help only).
     #[test]
@@ -932,6 +1892,15 @@ mod tests {
         assert!(matches!(cli.command, Some(Command::Update { .. })));
     }
 
+    #[test]
+    fn test_cli_fail_fast_parses() {
+        let cli = Cli::try_parse_from(["upd"]).unwrap();
+        assert!(!cli.fail_fast);
+
+        let cli = Cli::try_parse_from(["upd", "outdated", "--fail-fast"]).unwrap();
+        assert!(cli.fail_fast);
+    }
+
     #[test]
     fn test_cli_no_ignore_default_false() {
         let cli = Cli::try_parse_from(["upd"]).unwrap();
@@ -955,6 +1924,21 @@ mod tests {
         assert!(matches!(cli.command, Some(Command::Align { .. })));
     }
 
+    #[test]
+    fn test_cli_no_gitignore_is_alias_for_no_ignore() {
+        let cli = Cli::try_parse_from(["upd", "--no-gitignore"]).unwrap();
+        assert!(cli.no_ignore);
+    }
+
+    #[test]
+    fn test_cli_include_hidden_parses() {
+        let cli = Cli::try_parse_from(["upd"]).unwrap();
+        assert!(!cli.include_hidden);
+
+        let cli = Cli::try_parse_from(["upd", "audit", "--include-hidden"]).unwrap();
+        assert!(cli.include_hidden);
+    }
+
     #[test]
     fn test_cli_parses_insecure() {
         let cli = Cli::try_parse_from(["upd", "--insecure"]).unwrap();
@@ -974,4 +1958,421 @@ mod tests {
             assert!(cli.insecure, "--insecure must be accepted under `{sub}`");
         }
     }
+
+    #[test]
+    fn test_cli_parses_min_update_count() {
+        let cli = Cli::try_parse_from(["upd", "--min-update-count", "3"]).unwrap();
+        assert_eq!(cli.min_update_count, Some(3));
+    }
+
+    #[test]
+    fn test_cli_min_update_count_default_none() {
+        let cli = Cli::try_parse_from(["upd"]).unwrap();
+        assert!(cli.min_update_count.is_none());
+    }
+
+    #[test]
+    fn test_cli_min_update_count_conflicts_with_interactive() {
+        let result = Cli::try_parse_from(["upd", "--min-update-count", "2", "-i"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_newest_only() {
+        let cli = Cli::try_parse_from(["upd", "--newest-only", "5"]).unwrap();
+        assert_eq!(cli.newest_only, Some(5));
+        assert!(Cli::try_parse_from(["upd"]).unwrap().newest_only.is_none());
+        assert!(Cli::try_parse_from(["upd", "--newest-only", "0"]).is_err());
+        assert!(Cli::try_parse_from(["upd", "--newest-only", "2", "-i"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_apply_only_if_tests_pass() {
+        let cli =
+            Cli::try_parse_from(["upd", "--apply", "--apply-only-if-tests-pass", "cargo test"])
+                .unwrap();
+        assert_eq!(cli.apply_only_if_tests_pass.as_deref(), Some("cargo test"));
+    }
+
+    #[test]
+    fn test_cli_apply_only_if_tests_pass_conflicts_with_interactive() {
+        let result = Cli::try_parse_from(["upd", "-i", "--apply-only-if-tests-pass", "true"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_group_repeatable_and_comma_separated() {
+        let cli = Cli::try_parse_from(["upd", "--group", "dev,test", "--group", "docs"]).unwrap();
+        assert_eq!(cli.groups, vec!["dev", "test", "docs"]);
+        assert!(Cli::try_parse_from(["upd"]).unwrap().groups.is_empty());
+    }
+
+    #[test]
+    fn test_cli_parses_decisions_file() {
+        let cli = Cli::try_parse_from(["upd", "--decisions-file", "decisions.json"]).unwrap();
+        assert_eq!(cli.decisions_file, Some(PathBuf::from("decisions.json")));
+    }
+
+    #[test]
+    fn test_cli_decisions_file_conflicts_with_interactive() {
+        let result = Cli::try_parse_from(["upd", "-i", "--decisions-file", "d.json"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_audit_sbom() {
+        let cli = Cli::try_parse_from(["upd", "audit", "--sbom", "bom.json"]).unwrap();
+        match cli.command {
+            Some(Command::Audit { sbom, .. }) => {
+                assert_eq!(sbom, Some(PathBuf::from("bom.json")));
+            }
+            _ => panic!("Expected Audit command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_audit_fix_is_alias_for_fix_audit() {
+        let cli = Cli::try_parse_from(["upd", "audit", "--fix", "--apply"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Audit {
+                fix_audit: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_audit_sbom_conflicts_with_paths_and_fix_audit() {
+        assert!(Cli::try_parse_from(["upd", "audit", "--sbom", "bom.json", "src"]).is_err());
+        assert!(
+            Cli::try_parse_from(["upd", "audit", "--sbom", "bom.json", "--fix-audit"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_sbom_subcommand() {
+        let cli =
+            Cli::try_parse_from(["upd", "sbom", "--format", "spdx", "bom.json", "a", "b"]).unwrap();
+        assert_eq!(cli.format, Some(OutputFormat::Spdx));
+        match &cli.command {
+            Some(Command::Sbom { out, paths }) => {
+                assert_eq!(out, &PathBuf::from("bom.json"));
+                assert_eq!(paths.len(), 2);
+            }
+            _ => panic!("Expected Sbom command"),
+        }
+        assert_eq!(
+            cli.get_paths(),
+            vec![PathBuf::from("a"), PathBuf::from("b")]
+        );
+    }
+
+    #[test]
+    fn test_cli_sbom_requires_output() {
+        assert!(Cli::try_parse_from(["upd", "sbom"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_treat_unconstrained_as_error() {
+        let cli = Cli::try_parse_from(["upd", "--treat-unconstrained-as-error"]).unwrap();
+        assert!(cli.treat_unconstrained_as_error);
+        assert!(
+            !Cli::try_parse_from(["upd"])
+                .unwrap()
+                .treat_unconstrained_as_error
+        );
+    }
+
+    #[test]
+    fn test_cli_treat_unconstrained_as_error_conflicts_with_interactive() {
+        let result = Cli::try_parse_from(["upd", "-i", "--treat-unconstrained-as-error"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_ignore_engines() {
+        let cli = Cli::try_parse_from(["upd", "--ignore-engines", "--lang", "node"]).unwrap();
+        assert!(cli.ignore_engines);
+        assert!(!Cli::try_parse_from(["upd"]).unwrap().ignore_engines);
+    }
+
+    #[test]
+    fn test_cli_parses_no_config() {
+        let cli = Cli::try_parse_from(["upd", "--no-config"]).unwrap();
+        assert!(cli.no_config);
+        assert!(Cli::try_parse_from(["upd", "--no-config", "--config", "upd.toml"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_allow_deprecated() {
+        let cli = Cli::try_parse_from(["upd", "--allow-deprecated"]).unwrap();
+        assert!(cli.allow_deprecated);
+        assert!(!Cli::try_parse_from(["upd"]).unwrap().allow_deprecated);
+    }
+
+    #[test]
+    fn test_cli_parses_respect_msrv() {
+        let cli = Cli::try_parse_from(["upd", "--respect-msrv", "--lang", "rust"]).unwrap();
+        assert!(cli.respect_msrv);
+        assert!(!Cli::try_parse_from(["upd"]).unwrap().respect_msrv);
+        assert!(
+            Cli::try_parse_from(["upd", "-i", "--respect-msrv"])
+                .unwrap()
+                .respect_msrv
+        );
+    }
+
+    #[test]
+    fn test_cli_parses_summary_json() {
+        let cli = Cli::try_parse_from(["upd", "--summary-json", "summary.json"]).unwrap();
+        assert_eq!(cli.summary_json, Some(PathBuf::from("summary.json")));
+        assert!(Cli::try_parse_from(["upd", "-i", "--summary-json", "s.json"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_diff() {
+        assert!(
+            Cli::try_parse_from(["upd", "--diff", "--dry-run"])
+                .unwrap()
+                .diff
+        );
+        assert!(!Cli::try_parse_from(["upd"]).unwrap().diff);
+        assert!(Cli::try_parse_from(["upd", "-i", "--diff"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_link_types() {
+        assert!(
+            Cli::try_parse_from(["upd", "--link-types"])
+                .unwrap()
+                .link_types
+        );
+        assert!(!Cli::try_parse_from(["upd"]).unwrap().link_types);
+    }
+
+    #[test]
+    fn test_cli_parses_offline_for_update_and_audit() {
+        assert!(Cli::try_parse_from(["upd", "--offline"]).unwrap().offline);
+        let cli = Cli::try_parse_from(["upd", "audit", "--offline"]).unwrap();
+        assert!(cli.offline);
+        assert!(matches!(cli.command, Some(Command::Audit { .. })));
+        assert!(Cli::try_parse_from(["upd", "--offline", "--no-cache"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_log_level() {
+        assert_eq!(Cli::try_parse_from(["upd"]).unwrap().log_level, None);
+        let cli = Cli::try_parse_from(["upd", "outdated", "--log-level", "debug"]).unwrap();
+        assert_eq!(cli.log_level, Some(LogLevel::Debug));
+        assert!(Cli::try_parse_from(["upd", "--log-level", "loud"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_color_choice() {
+        assert_eq!(
+            Cli::try_parse_from(["upd"]).unwrap().color_choice(),
+            ColorChoice::Auto
+        );
+        let cli = Cli::try_parse_from(["upd", "audit", "--color", "always"]).unwrap();
+        assert_eq!(cli.color_choice(), ColorChoice::Always);
+        let cli = Cli::try_parse_from(["upd", "--no-color"]).unwrap();
+        assert_eq!(cli.color_choice(), ColorChoice::Never);
+        assert!(Cli::try_parse_from(["upd", "--color", "always", "--no-color"]).is_err());
+    }
+
+    #[test]
+    fn test_color_override_per_mode_and_env() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        let no_env: &[(&str, &str)] = &[];
+        assert_eq!(ColorChoice::Auto.color_override(env(no_env)), None);
+        assert_eq!(ColorChoice::Always.color_override(env(no_env)), Some(true));
+        assert_eq!(ColorChoice::Never.color_override(env(no_env)), Some(false));
+
+        assert_eq!(
+            ColorChoice::Auto.color_override(env(&[("NO_COLOR", "1")])),
+            Some(false)
+        );
+        assert_eq!(
+            ColorChoice::Auto.color_override(env(&[("NO_COLOR", "")])),
+            None
+        );
+        assert_eq!(
+            ColorChoice::Auto.color_override(env(&[("FORCE_COLOR", "1")])),
+            Some(true)
+        );
+        assert_eq!(
+            ColorChoice::Auto.color_override(env(&[("FORCE_COLOR", "0")])),
+            None
+        );
+        assert_eq!(
+            ColorChoice::Auto.color_override(env(&[("NO_COLOR", "1"), ("FORCE_COLOR", "1")])),
+            Some(false)
+        );
+        // Explicit choices win over the environment.
+        assert_eq!(
+            ColorChoice::Always.color_override(env(&[("NO_COLOR", "1")])),
+            Some(true)
+        );
+        assert_eq!(
+            ColorChoice::Never.color_override(env(&[("FORCE_COLOR", "1")])),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_color_choice_sets_colored_override() {
+        let should_colorize = || colored::control::SHOULD_COLORIZE.should_colorize();
+        let no_env = |_: &str| None;
+
+        ColorChoice::Always.apply_with_env(no_env);
+        assert!(should_colorize());
+        ColorChoice::Never.apply_with_env(no_env);
+        assert!(!should_colorize());
+        ColorChoice::Auto
+            .apply_with_env(|name: &str| (name == "FORCE_COLOR").then(|| "1".to_string()));
+        assert!(should_colorize());
+        ColorChoice::Auto
+            .apply_with_env(|name: &str| (name == "NO_COLOR").then(|| "1".to_string()));
+        assert!(!should_colorize());
+        colored::control::unset_override();
+    }
+
+    #[test]
+    fn test_cli_parses_timeout_and_retries() {
+        let cli = Cli::try_parse_from(["upd"]).unwrap();
+        assert_eq!((cli.timeout, cli.retries), (30, 2));
+        let cli =
+            Cli::try_parse_from(["upd", "audit", "--timeout", "120", "--retries", "0"]).unwrap();
+        assert_eq!((cli.timeout, cli.retries), (120, 0));
+        assert!(Cli::try_parse_from(["upd", "--timeout", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_resolve_concurrency_per_host() {
+        assert_eq!(
+            Cli::try_parse_from(["upd"])
+                .unwrap()
+                .resolve_concurrency_per_host,
+            None
+        );
+        let cli =
+            Cli::try_parse_from(["upd", "audit", "--resolve-concurrency-per-host", "4"]).unwrap();
+        assert_eq!(cli.resolve_concurrency_per_host, Some(4));
+        assert!(Cli::try_parse_from(["upd", "--resolve-concurrency-per-host", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_rate_limit() {
+        assert_eq!(Cli::try_parse_from(["upd"]).unwrap().rate_limit, None);
+        let cli = Cli::try_parse_from(["upd", "audit", "--rate-limit", "0.5"]).unwrap();
+        assert_eq!(cli.rate_limit, Some(0.5));
+        for invalid in ["0", "-2", "fast", "inf"] {
+            assert!(
+                Cli::try_parse_from(["upd", "--rate-limit", invalid]).is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cli_parses_max_concurrency() {
+        assert_eq!(
+            Cli::try_parse_from(["upd"]).unwrap().max_concurrency,
+            DEFAULT_MAX_CONCURRENCY
+        );
+        let cli = Cli::try_parse_from(["upd", "--max-concurrency", "2", "."]).unwrap();
+        assert_eq!(cli.max_concurrency, 2);
+        assert!(Cli::try_parse_from(["upd", "--max-concurrency", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_max_runtime() {
+        let cli = Cli::try_parse_from(["upd", "--max-runtime", "90s"]).unwrap();
+        assert_eq!(cli.max_runtime, Some(std::time::Duration::from_secs(90)));
+        let cli = Cli::try_parse_from(["upd", "audit", "--max-runtime", "5m"]).unwrap();
+        assert_eq!(cli.max_runtime, Some(std::time::Duration::from_secs(300)));
+        assert_eq!(Cli::try_parse_from(["upd"]).unwrap().max_runtime, None);
+        assert!(Cli::try_parse_from(["upd", "--max-runtime", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_cache_ttl() {
+        let cli = Cli::try_parse_from(["upd"]).unwrap();
+        assert_eq!(cli.cache_ttl, crate::cache::DEFAULT_CACHE_TTL);
+        let cli = Cli::try_parse_from(["upd", "cache-stats", "--cache-ttl", "24h"]).unwrap();
+        assert_eq!(cli.cache_ttl, std::time::Duration::from_secs(24 * 3600));
+        assert!(Cli::try_parse_from(["upd", "--cache-ttl", "0s"]).is_err());
+        assert!(Cli::try_parse_from(["upd", "--cache-ttl", "soon"]).is_err());
+        let cli = Cli::try_parse_from(["upd"]).unwrap();
+        assert_eq!(
+            cli.negative_cache_ttl,
+            crate::cache::DEFAULT_NEGATIVE_CACHE_TTL
+        );
+        let cli = Cli::try_parse_from(["upd", "--negative-cache-ttl", "30s"]).unwrap();
+        assert_eq!(cli.negative_cache_ttl, std::time::Duration::from_secs(30));
+        assert!(Cli::try_parse_from(["upd", "--max-runtime", "90"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_pypi_api_order() {
+        let cli = Cli::try_parse_from(["upd"]).unwrap();
+        assert_eq!(cli.pypi_api, PypiApiOrder::SimpleThenJson);
+        let cli = Cli::try_parse_from(["upd", "--pypi-api", "json,simple"]).unwrap();
+        assert_eq!(cli.pypi_api, PypiApiOrder::JsonThenSimple);
+        let cli = Cli::try_parse_from(["upd", "--pypi-api", "simple-only"]).unwrap();
+        assert_eq!(cli.pypi_api, PypiApiOrder::SimpleOnly);
+        let cli = Cli::try_parse_from(["upd", "--pypi-api", "json-only"]).unwrap();
+        assert_eq!(cli.pypi_api, PypiApiOrder::JsonOnly);
+        assert!(Cli::try_parse_from(["upd", "--pypi-api", "json"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_prune_dead_markers() {
+        let cli = Cli::try_parse_from(["upd", "--prune-dead-markers"]).unwrap();
+        assert!(cli.prune_dead_markers);
+        assert!(!Cli::try_parse_from(["upd"]).unwrap().prune_dead_markers);
+    }
+
+    #[test]
+    fn test_cli_parses_repeatable_exclude() {
+        let cli = Cli::try_parse_from([
+            "upd",
+            "--exclude",
+            "**/tests/fixtures/**",
+            "--exclude",
+            "vendor/{a,b}/*.toml",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.exclude,
+            vec!["**/tests/fixtures/**", "vendor/{a,b}/*.toml"]
+        );
+        assert!(Cli::try_parse_from(["upd"]).unwrap().exclude.is_empty());
+    }
+
+    #[test]
+    fn test_cli_parses_include_transitive() {
+        let cli = Cli::try_parse_from(["upd", "audit", "--include-transitive"]).unwrap();
+        assert!(cli.include_transitive);
+        assert!(!Cli::try_parse_from(["upd"]).unwrap().include_transitive);
+        assert!(
+            Cli::try_parse_from(["upd", "--only-direct"])
+                .unwrap()
+                .only_direct
+        );
+    }
+
+    #[test]
+    fn test_cli_only_direct_conflicts_with_include_transitive() {
+        assert!(Cli::try_parse_from(["upd", "--only-direct", "--include-transitive"]).is_err());
+        assert!(Cli::try_parse_from(["upd", "-i", "--include-transitive"]).is_err());
+    }
 }
@@ -1,6 +1,7 @@
 //! Configuration file support for upd
 //!
-//! Supports `.updrc.toml` and `upd.toml` configuration files.
+//! Supports `.updrc.toml` and `upd.toml` configuration files, and a
+//! `[tool.upd]` table in `pyproject.toml` with the same keys.
 //!
 //! # Schema
 //!
@@ -19,12 +20,26 @@
 //!     "**/vendored/requirements.txt",
 //! ]
 //!
+//! # Registry hosts upd may contact (empty = unrestricted) - top-level array
+//! allowed_registries = ["nexus.corp"]
+//!
+//! # Bump levels `--apply` writes; majors are then only reported - top-level array
+//! auto_apply = ["minor", "patch"]
+//!
+//! # Packages that may update into pre-releases - top-level array
+//! allow_prerelease = ["torch"]
+//!
 //! # Pin packages to specific versions or constraints - top-level table
 //! [pin]
 //! requests = "2.28.0"  # Pin to exact version
 //! django = ">=3.2,<4"  # Pin to version range
 //! ```
 //!
+//! A range pin resolves to the newest registry version inside it for the
+//! Python formats (pyproject.toml, requirements, setup.py, Pipfile and the
+//! pip list of environment.yml), Cargo.toml and go.mod. Other files take only
+//! exact pins and skip a range pin with a warning.
+//!
 //! Unknown top-level keys produce a warning on stderr but do not stop execution.
 //! A common mistake is writing `[ignore]` (table) instead of `ignore = [...]` (array).
 //!
@@ -44,6 +59,7 @@
 //!
 //! Valid duration units: `s`, `m`, `h`, `d`, `w`. Use `"0"` to disable.
 
+use crate::cli::BumpLevel;
 use colored::Colorize;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -53,7 +69,15 @@ use std::path::{Path, PathBuf};
 const MAX_CONFIG_FILE_SIZE: u64 = 1024 * 1024;
 
 /// All valid top-level keys in the config schema.
-const KNOWN_KEYS: &[&str] = &["ignore", "exclude", "pin", "cooldown"];
+const KNOWN_KEYS: &[&str] = &[
+    "ignore",
+    "exclude",
+    "pin",
+    "cooldown",
+    "allowed_registries",
+    "auto_apply",
+    "allow_prerelease",
+];
 
 /// Raw cooldown config as written in the TOML file. Parsed into a
 /// `crate::cooldown::CooldownPolicy` at runtime via `UpdConfig::to_cooldown_policy`.
@@ -90,16 +114,111 @@ pub struct UpdConfig {
     /// Optional cooldown (minimum release age) policy.
     #[serde(default)]
     pub cooldown: Option<CooldownConfig>,
+
+    /// Hosts that `upd` may send requests to (e.g. `nexus.corp` or
+    /// `nexus.corp:8443`).
+    ///
+    /// When non-empty, any request to a host outside this list is refused
+    /// before it is sent, so private package names cannot leak to a public
+    /// index. Empty (the default) leaves requests unrestricted.
+    #[serde(default)]
+    pub allowed_registries: Vec<String>,
+
+    /// Bump levels a non-interactive `--apply` run may write (e.g.
+    /// `["minor", "patch"]`). Updates of other levels are reported as
+    /// available but not applied. Empty (the default) applies every level.
+    #[serde(default)]
+    pub auto_apply: Vec<BumpLevel>,
+
+    /// Packages looked up including pre-releases even when the current
+    /// version is stable, like `--allow-prerelease`.
+    #[serde(default)]
+    pub allow_prerelease: Vec<String>,
 }
 
 impl UpdConfig {
     /// Discover the nearest config file, walking up from `start_dir`.
     ///
+    /// A dedicated config file (`.updrc.toml`, `upd.toml`, `.updrc`) and a
+    /// `[tool.upd]` table in `pyproject.toml` are each looked up from the
+    /// nearest directory. When both exist they are merged, with the dedicated
+    /// file winning on conflicting pins; the returned path is the dedicated
+    /// file's.
+    ///
     /// Returns `Ok(None)` when no config file exists. A config file that is
     /// present but fails to parse is a hard error (`Err`): silently falling back
     /// to defaults would drop the user's `ignore`/`pin` rules and let unwanted
     /// updates through.
     pub fn discover(start_dir: &Path) -> Result<Option<(Self, PathBuf)>, String> {
+        Ok(Self::discover_with_sources(start_dir)?
+            .and_then(|(config, mut sources)| sources.pop().map(|path| (config, path))))
+    }
+
+    /// Like [`UpdConfig::discover`], but returns every file that contributed
+    /// to the merged config, in merge order (a `pyproject.toml` before the
+    /// dedicated file that overrides it).
+    pub fn discover_with_sources(start_dir: &Path) -> Result<Option<(Self, Vec<PathBuf>)>, String> {
+        let dedicated = Self::discover_dedicated(start_dir)?;
+        let pyproject = Self::discover_pyproject_table(start_dir)?;
+
+        Ok(match (dedicated, pyproject) {
+            (Some((config, path)), Some((mut base, base_path))) => {
+                base.merge(config);
+                Some((base, vec![base_path, path]))
+            }
+            (dedicated, pyproject) => dedicated
+                .or(pyproject)
+                .map(|(config, path)| (config, vec![path])),
+        })
+    }
+
+    /// Every config between `start_dir` and `root`, merged so nearer configs
+    /// win: ignore lists are combined and nearer pins override farther ones.
+    ///
+    /// The nearest config is found as by [`UpdConfig::discover`]; discovery
+    /// then resumes above it while still inside `root` (typically the
+    /// repository root). Without a `root` only the nearest config is used.
+    /// Sources are returned in merge order, farthest first.
+    pub fn discover_layered(
+        start_dir: &Path,
+        root: Option<&Path>,
+    ) -> Result<Option<(Self, Vec<PathBuf>)>, String> {
+        let mut layers = Vec::new();
+        let mut dir = Some(start_dir.to_path_buf());
+        while let Some(current) = dir.take() {
+            let Some((config, sources)) = Self::discover_with_sources(&current)? else {
+                break;
+            };
+            // Only the nearest config may come from above the root.
+            let outside_root = |root: &Path| sources.iter().any(|s| !s.starts_with(root));
+            if !layers.is_empty() && root.is_some_and(outside_root) {
+                break;
+            }
+            // Resume above the farthest file this layer was read from.
+            dir = root.and_then(|root| {
+                let above = sources
+                    .iter()
+                    .filter_map(|source| source.parent())
+                    .min_by_key(|parent| parent.components().count())?
+                    .parent()?;
+                above.starts_with(root).then(|| above.to_path_buf())
+            });
+            layers.push((config, sources));
+        }
+
+        let mut layers = layers.into_iter().rev();
+        let Some((mut merged, mut all_sources)) = layers.next() else {
+            return Ok(None);
+        };
+        for (config, sources) in layers {
+            merged.merge(config);
+            all_sources.extend(sources);
+        }
+        Ok(Some((merged, all_sources)))
+    }
+
+    /// Nearest dedicated config file, walking up from `start_dir`.
+    fn discover_dedicated(start_dir: &Path) -> Result<Option<(Self, PathBuf)>, String> {
         let config_names = [".updrc.toml", "upd.toml", ".updrc"];
 
         let mut current = Some(start_dir);
@@ -120,6 +239,36 @@ impl UpdConfig {
         Ok(None)
     }
 
+    /// Nearest `pyproject.toml` with a `[tool.upd]` table, walking up from
+    /// `start_dir`.
+    ///
+    /// A `pyproject.toml` that is unreadable or not valid TOML is skipped here:
+    /// it is not an upd config file, and the Python updater reports the
+    /// problem when it processes the file.
+    fn discover_pyproject_table(start_dir: &Path) -> Result<Option<(Self, PathBuf)>, String> {
+        let mut current = Some(start_dir);
+        while let Some(dir) = current {
+            let pyproject_path = dir.join("pyproject.toml");
+            let table = std::fs::metadata(&pyproject_path)
+                .ok()
+                .filter(|metadata| metadata.len() <= MAX_CONFIG_FILE_SIZE)
+                .and_then(|_| std::fs::read_to_string(&pyproject_path).ok())
+                .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+                .and_then(|raw| raw.get("tool")?.get("upd").cloned());
+            if let Some(table) = table {
+                let label = format!("{} [tool.upd]", pyproject_path.display());
+                let (config, warnings) = Self::parse_value_with_warnings(table, &label)?;
+                for w in &warnings {
+                    eprintln!("warning: {w}");
+                }
+                return Ok(Some((config, pyproject_path)));
+            }
+            current = dir.parent();
+        }
+
+        Ok(None)
+    }
+
     /// Load configuration from a specific file path.
     ///
     /// Parse errors are printed to stderr so the user can see that their config
@@ -209,7 +358,15 @@ impl UpdConfig {
         // into the typed struct. This lets us warn without hard-rejecting.
         let raw: toml::Value = toml::from_str(content)
             .map_err(|e| format!("Invalid TOML in config file {}:\n  {}", source_label, e))?;
+        Self::parse_value_with_warnings(raw, source_label)
+    }
 
+    /// Validate and deserialize an already-parsed config table, collecting
+    /// warnings for unknown keys.
+    fn parse_value_with_warnings(
+        raw: toml::Value,
+        source_label: &str,
+    ) -> Result<(Self, Vec<String>), String> {
         let mut warnings = Vec::new();
         if let toml::Value::Table(table) = &raw {
             for key in table.keys() {
@@ -235,6 +392,11 @@ impl UpdConfig {
             "rubygems",
             "terraform",
             "nuget",
+            "docker-hub",
+            "packagist",
+            "maven",
+            "pub",
+            "runtime",
         ];
         if let toml::Value::Table(table) = &raw
             && let Some(toml::Value::Table(cooldown)) = table.get("cooldown")
@@ -284,6 +446,28 @@ exclude = [
     # "**/vendored/requirements.txt",
 ]
 
+# allowed_registries: hosts upd may send requests to (top-level array of
+# strings, `host` or `host:port`). When set, requests to any other host are
+# refused before they are sent, including redirects and `upd audit` lookups
+# (add `api.osv.dev` to keep auditing). Empty means unrestricted.
+allowed_registries = [
+    # "nexus.corp",
+]
+
+# auto_apply: bump levels a non-interactive --apply run writes (top-level
+# array of "major", "minor", "patch"). Other updates are reported as available
+# but not applied. Empty means every level is applied.
+auto_apply = [
+    # "minor", "patch",
+]
+
+# allow_prerelease: packages that may update into pre-releases (alphas, betas,
+# release candidates) even from a stable version (top-level array of strings).
+# Names are matched like `ignore`. Everything else stays on stable releases.
+allow_prerelease = [
+    # "torch",
+]
+
 # pin: packages pinned to a specific version or constraint (top-level table)
 [pin]
 # example-package = "1.2.3"
@@ -295,7 +479,8 @@ exclude = [
 # default = "7d"         # applied to every ecosystem unless overridden below
 
 # Per-ecosystem overrides. Valid keys: pypi, npm, crates.io, go-proxy,
-# github-releases, rubygems, terraform, nuget.
+# github-releases, rubygems, terraform, nuget, docker-hub, packagist, maven,
+# pub, runtime.
 [cooldown.ecosystem]
 # npm = "14d"
 # pypi = "14d"
@@ -374,12 +559,24 @@ exclude = [
             .map(|(_, v)| v.as_str())
     }
 
+    /// Check if `package` may update into pre-releases. Matched like
+    /// `should_ignore`.
+    pub fn allows_prerelease(&self, package: &str) -> bool {
+        let target = normalize_package_name(package);
+        self.allow_prerelease
+            .iter()
+            .any(|p| normalize_package_name(p) == target)
+    }
+
     /// Check if any configuration is present
     pub fn has_config(&self) -> bool {
         !self.ignore.is_empty()
             || !self.exclude.is_empty()
             || !self.pin.is_empty()
             || self.cooldown.is_some()
+            || !self.allowed_registries.is_empty()
+            || !self.auto_apply.is_empty()
+            || !self.allow_prerelease.is_empty()
     }
 
     /// Merge another configuration into this one (other takes precedence)
@@ -396,6 +593,28 @@ exclude = [
                 self.exclude.push(pattern);
             }
         }
+        // Extend pre-release allow list
+        for pkg in other.allow_prerelease {
+            if !self.allow_prerelease.contains(&pkg) {
+                self.allow_prerelease.push(pkg);
+            }
+        }
+        // Allowed registry hosts only narrow: a nested config keeps the hosts
+        // both lists allow and can never add one. A list sharing no host with
+        // the outer one is ignored rather than lifting the restriction.
+        if self.allowed_registries.is_empty() {
+            self.allowed_registries = other.allowed_registries;
+        } else if !other.allowed_registries.is_empty() {
+            let narrowed: Vec<String> = self
+                .allowed_registries
+                .iter()
+                .filter(|host| other.allowed_registries.contains(host))
+                .cloned()
+                .collect();
+            if !narrowed.is_empty() {
+                self.allowed_registries = narrowed;
+            }
+        }
         // Override pinned versions
         for (pkg, version) in other.pin {
             self.pin.insert(pkg, version);
@@ -404,6 +623,10 @@ exclude = [
         if other.cooldown.is_some() {
             self.cooldown = other.cooldown;
         }
+        // So does the auto-apply policy
+        if !other.auto_apply.is_empty() {
+            self.auto_apply = other.auto_apply;
+        }
     }
 }
 
@@ -412,7 +635,7 @@ exclude = [
 /// Lowercases the name and collapses any run of `-`, `_`, or `.` into a single
 /// `-`. Applied to both sides of an ignore-list comparison so spellings that
 /// differ only in case or separator are treated as the same package.
-fn normalize_package_name(name: &str) -> String {
+pub(crate) fn normalize_package_name(name: &str) -> String {
     let mut out = String::with_capacity(name.len());
     let mut prev_separator = false;
     for ch in name.chars() {
@@ -429,6 +652,12 @@ fn normalize_package_name(name: &str) -> String {
     out
 }
 
+/// Returns `true` when a `[pin]` value is a range (`>=4.2,<5`) to update
+/// within rather than an exact version to write.
+pub fn is_range_pin(pin: &str) -> bool {
+    pin.contains(['<', '>', '=', '~', '^', '!', ',', '*'])
+}
+
 /// Render the resolved cooldown for human display in `--show-config`.
 pub fn render_cooldown_for_show_config(policy: &crate::cooldown::CooldownPolicy) -> String {
     fn fmt_dur(d: chrono::Duration) -> String {
@@ -475,6 +704,32 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_load_auto_apply_levels() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".updrc.toml");
+
+        fs::write(&config_path, "auto_apply = [\"minor\", \"patch\"]\n").unwrap();
+        let config = UpdConfig::load_from_path_with_error(&config_path).unwrap();
+        assert_eq!(config.auto_apply, vec![BumpLevel::Minor, BumpLevel::Patch]);
+        assert!(config.has_config());
+
+        fs::write(&config_path, "auto_apply = [\"minors\"]\n").unwrap();
+        assert!(UpdConfig::load_from_path_with_error(&config_path).is_err());
+    }
+
+    #[test]
+    fn test_load_allow_prerelease() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".updrc.toml");
+
+        fs::write(&config_path, "allow_prerelease = [\"Torch\"]\n").unwrap();
+        let config = UpdConfig::load_from_path_with_error(&config_path).unwrap();
+        assert!(config.has_config());
+        assert!(config.allows_prerelease("torch"));
+        assert!(!config.allows_prerelease("numpy"));
+    }
+
     #[test]
     fn test_load_config_from_toml() {
         let temp_dir = TempDir::new().unwrap();
@@ -507,6 +762,9 @@ django = ">=3.2,<4"
             ignore: vec!["pkg-a".to_string(), "pkg-b".to_string()],
             pin: HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         assert!(config.should_ignore("pkg-a"));
@@ -589,6 +847,23 @@ exclude = ["**/archive/**", "**/vendored/requirements.txt"]
         assert!(config.has_config());
     }
 
+    #[test]
+    fn test_allowed_registries_parses_and_is_a_known_key() {
+        let content = r#"
+allowed_registries = ["nexus.corp", "nexus.corp:8443"]
+"#;
+        let (config, warnings) = UpdConfig::parse_with_warnings(content, "test.toml").unwrap();
+        assert!(
+            warnings.is_empty(),
+            "allowed_registries must be a known top-level key (no warning); got: {warnings:?}"
+        );
+        assert_eq!(
+            config.allowed_registries,
+            vec!["nexus.corp".to_string(), "nexus.corp:8443".to_string()]
+        );
+        assert!(config.has_config());
+    }
+
     #[test]
     fn test_merge_extends_exclude_without_duplicates() {
         let mut base = UpdConfig {
@@ -606,6 +881,36 @@ exclude = ["**/archive/**", "**/vendored/requirements.txt"]
         );
     }
 
+    #[test]
+    fn test_merge_allowed_registries_only_narrows() {
+        let outer = || UpdConfig {
+            allowed_registries: vec!["nexus.corp".to_string(), "mirror.corp".to_string()],
+            ..Default::default()
+        };
+
+        let mut widened = outer();
+        widened.merge(UpdConfig {
+            allowed_registries: vec!["nexus.corp".to_string(), "pypi.org".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(widened.allowed_registries, vec!["nexus.corp".to_string()]);
+
+        let mut disjoint = outer();
+        disjoint.merge(UpdConfig {
+            allowed_registries: vec!["pypi.org".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(disjoint.allowed_registries, outer().allowed_registries);
+
+        let mut unset = outer();
+        unset.merge(UpdConfig::default());
+        assert_eq!(unset.allowed_registries, outer().allowed_registries);
+
+        let mut first = UpdConfig::default();
+        first.merge(outer());
+        assert_eq!(first.allowed_registries, outer().allowed_registries);
+    }
+
     #[test]
     fn test_schema_toml_documents_exclude() {
         let schema = UpdConfig::schema_toml();
@@ -626,6 +931,9 @@ exclude = ["**/archive/**", "**/vendored/requirements.txt"]
             ignore: vec![],
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         assert_eq!(config.get_pinned_version("requests"), Some("2.28.0"));
@@ -633,6 +941,16 @@ exclude = ["**/archive/**", "**/vendored/requirements.txt"]
         assert_eq!(config.get_pinned_version("flask"), None);
     }
 
+    #[test]
+    fn test_is_range_pin() {
+        assert!(!is_range_pin("2.28.0"));
+        assert!(!is_range_pin("v1.2.3"));
+        assert!(is_range_pin(">=4.2,<5"));
+        assert!(is_range_pin("^1.0"));
+        assert!(is_range_pin("~=3.1"));
+        assert!(is_range_pin("1.*"));
+    }
+
     #[test]
     fn test_discover_config_in_current_dir() {
         let temp_dir = TempDir::new().unwrap();
@@ -700,6 +1018,128 @@ ignore = ["parent-pkg"]
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_discover_reads_pyproject_tool_upd() {
+        let temp_dir = TempDir::new().unwrap();
+        let pyproject = temp_dir.path().join("pyproject.toml");
+        fs::write(
+            &pyproject,
+            r#"[project]
+name = "demo"
+dependencies = ["requests>=2.28.0"]
+
+[tool.upd]
+ignore = ["django"]
+
+[tool.upd.pin]
+requests = "2.31.0"
+"#,
+        )
+        .unwrap();
+
+        let (config, path) = UpdConfig::discover(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(path, pyproject);
+        assert!(config.should_ignore("django"));
+        assert_eq!(config.get_pinned_version("requests"), Some("2.31.0"));
+    }
+
+    #[test]
+    fn test_discover_dedicated_config_wins_over_tool_upd() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("pyproject.toml"),
+            "[tool.upd]\nignore = [\"from-pyproject\"]\n[tool.upd.pin]\nrequests = \"2.0.0\"\nflask = \"2.3.0\"\n",
+        )
+        .unwrap();
+        let dedicated = temp_dir.path().join(".updrc.toml");
+        fs::write(
+            &dedicated,
+            "ignore = [\"from-updrc\"]\n[pin]\nrequests = \"2.31.0\"\n",
+        )
+        .unwrap();
+
+        let (config, path) = UpdConfig::discover(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(path, dedicated);
+        assert!(config.should_ignore("from-pyproject"));
+        assert!(config.should_ignore("from-updrc"));
+        assert_eq!(config.get_pinned_version("requests"), Some("2.31.0"));
+        assert_eq!(config.get_pinned_version("flask"), Some("2.3.0"));
+
+        let (_, sources) = UpdConfig::discover_with_sources(temp_dir.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(sources, [temp_dir.path().join("pyproject.toml"), dedicated]);
+    }
+
+    #[test]
+    fn test_discover_layered_merges_configs_up_to_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let service = root.join("services").join("api");
+        fs::create_dir_all(&service).unwrap();
+        fs::write(
+            root.join(".updrc.toml"),
+            "ignore = [\"boto3\"]\n[pin]\nrequests = \"2.28.0\"\nflask = \"2.3.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            service.join(".updrc.toml"),
+            "ignore = [\"django\"]\n[pin]\nrequests = \"2.31.0\"\n",
+        )
+        .unwrap();
+
+        let (config, sources) = UpdConfig::discover_layered(&service, Some(root))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sources,
+            [root.join(".updrc.toml"), service.join(".updrc.toml")]
+        );
+        assert!(config.should_ignore("boto3"));
+        assert!(config.should_ignore("django"));
+        assert_eq!(config.get_pinned_version("requests"), Some("2.31.0"));
+        assert_eq!(config.get_pinned_version("flask"), Some("2.3.0"));
+
+        // Without a root, only the nearest config applies.
+        let (config, sources) = UpdConfig::discover_layered(&service, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sources, [service.join(".updrc.toml")]);
+        assert!(!config.should_ignore("boto3"));
+
+        // Configs above the root are left out.
+        let (_, sources) = UpdConfig::discover_layered(&service, Some(&root.join("services")))
+            .unwrap()
+            .unwrap();
+        assert_eq!(sources, [service.join(".updrc.toml")]);
+    }
+
+    #[test]
+    fn test_discover_skips_pyproject_without_tool_upd() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("pyproject.toml"),
+            "[project]\nname = \"demo\"\n\n[tool.ruff]\nline-length = 100\n",
+        )
+        .unwrap();
+        assert!(UpdConfig::discover(temp_dir.path()).unwrap().is_none());
+
+        fs::write(temp_dir.path().join("pyproject.toml"), "not [valid toml").unwrap();
+        assert!(UpdConfig::discover(temp_dir.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_discover_rejects_malformed_tool_upd() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("pyproject.toml"),
+            "[tool.upd]\nignore = \"not-a-list\"\n",
+        )
+        .unwrap();
+        let err = UpdConfig::discover(temp_dir.path()).unwrap_err();
+        assert!(err.contains("[tool.upd]"), "{err}");
+    }
+
     #[test]
     fn test_empty_config() {
         let temp_dir = TempDir::new().unwrap();
@@ -724,6 +1164,9 @@ ignore = ["parent-pkg"]
             ignore: vec!["pkg".to_string()],
             pin: HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
         assert!(with_ignore.has_config());
 
@@ -734,6 +1177,9 @@ ignore = ["parent-pkg"]
             ignore: vec![],
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
         assert!(with_pin.has_config());
     }
@@ -749,6 +1195,9 @@ ignore = ["parent-pkg"]
                 m
             },
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let other = UpdConfig {
@@ -761,6 +1210,9 @@ ignore = ["parent-pkg"]
                 m
             },
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         base.merge(other);
@@ -911,6 +1363,9 @@ parent-pkg = "1.0.0"
                 m
             },
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         // Create mock registry
@@ -973,6 +1428,9 @@ parent-pkg = "1.0.0"
                 m
             },
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         });
 
         // Test Requirements
@@ -64,7 +64,8 @@ pub struct CooldownPolicy {
     pub default: Duration,
     /// Per-ecosystem overrides keyed by registry name (see `src/cache.rs` for
     /// the canonical names: "pypi", "npm", "crates.io", "go-proxy",
-    /// "github-releases", "rubygems", "terraform", "nuget").
+    /// "github-releases", "rubygems", "terraform", "nuget", "docker-hub",
+    /// "packagist", "maven", "pub", "runtime").
     pub per_ecosystem: HashMap<String, Duration>,
     /// CLI `--min-age` override. Wins over everything else when set.
     pub force_override: Option<Duration>,
@@ -474,6 +475,9 @@ mod tests {
             published_at: Some(now - Duration::days(days_ago)),
             yanked,
             prerelease,
+            rust_version: None,
+            node_engines: None,
+            deprecated: None,
         }
     }
 
@@ -0,0 +1,488 @@
+//! Per-dependency decision record for `--decisions-file`.
+//!
+//! Where the summary only counts outcomes, the decisions record lists every
+//! dependency discovered in every scanned file exactly once, together with what
+//! `upd` decided to do with it and why. Teams commit or archive the file to
+//! track over time why a dependency did not move.
+
+use crate::updater::{ParsedDependency, UpdateResult};
+use serde::Serialize;
+use std::path::Path;
+
+/// Version of the decisions-file format. Bumped on breaking changes only.
+pub const DECISIONS_SCHEMA_VERSION: u32 = 1;
+
+/// Final outcome for a single dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionKind {
+    /// Moved (or, in dry-run, would move) to a newer registry version.
+    Updated,
+    /// Rewritten to the version pinned in the config.
+    Pinned,
+    /// Updated to an older-than-latest version because of the cooldown.
+    HeldBack,
+    /// Kept because every newer version is inside the cooldown window.
+    SkippedByCooldown,
+    /// Listed in the config `ignore` array.
+    Ignored,
+    /// Excluded by a `--package` filter.
+    Filtered,
+    /// References a commit rather than a release (e.g. a Go pseudo-version).
+    NotBumpable,
+    /// Kept because its constraint has an upper bound.
+    Constrained,
+    /// Already at the newest eligible version.
+    Unchanged,
+    /// The lookup or the file failed.
+    Error,
+}
+
+/// One dependency's decision.
+#[derive(Debug, Clone, Serialize)]
+pub struct Decision {
+    pub file: String,
+    pub package: String,
+    pub current: String,
+    pub decision: DecisionKind,
+    /// Target version for `updated`, `pinned` and `held_back`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<String>,
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+/// Top-level document written to the decisions file.
+#[derive(Debug, Serialize)]
+pub struct DecisionsRecord {
+    pub schema_version: u32,
+    pub command: &'static str,
+    /// `"dry-run"` or `"apply"`.
+    pub mode: &'static str,
+    pub decisions: Vec<Decision>,
+}
+
+/// Context that shapes the reasons given for unchanged dependencies.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DecisionContext<'a> {
+    /// Names passed via `--package`; empty when no filter is active.
+    pub packages: &'a [String],
+    /// Whether `--only-bump` / `--max-bump` narrowed the permitted bump levels.
+    pub bump_filter_active: bool,
+}
+
+/// Tracks which entries of an [`UpdateResult`] list were claimed by a parsed
+/// dependency, so leftovers can be reported on their own.
+struct Claims<'a, T> {
+    entries: &'a [T],
+    used: Vec<bool>,
+}
+
+impl<'a, T> Claims<'a, T> {
+    fn new(entries: &'a [T]) -> Self {
+        Self {
+            entries,
+            used: vec![false; entries.len()],
+        }
+    }
+
+    /// Claim the first unused entry for `dep`, preferring an exact line match.
+    fn claim(
+        &mut self,
+        dep: &ParsedDependency,
+        key: impl Fn(&T) -> (&str, Option<usize>),
+    ) -> Option<&'a T> {
+        let name_matches = |e: &T| key(e).0.eq_ignore_ascii_case(&dep.name);
+        let idx = (0..self.entries.len())
+            .find(|&i| {
+                !self.used[i]
+                    && name_matches(&self.entries[i])
+                    && dep.line_number.is_some()
+                    && key(&self.entries[i]).1 == dep.line_number
+            })
+            .or_else(|| {
+                (0..self.entries.len()).find(|&i| !self.used[i] && name_matches(&self.entries[i]))
+            })?;
+        self.used[idx] = true;
+        Some(&self.entries[idx])
+    }
+
+    fn unclaimed(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.entries
+            .iter()
+            .zip(&self.used)
+            .filter(|(_, used)| !**used)
+            .map(|(e, _)| e)
+    }
+}
+
+/// Build the decisions for one file.
+///
+/// `deps` are the dependencies parsed from the file; `result` is what the
+/// updater reported for it. Every parsed dependency yields exactly one
+/// decision, and any result entry that no parsed dependency claimed is
+/// reported as well, so nothing the updater touched goes missing. Errors are
+/// attributed to a dependency when they carry its `"<name>: "` prefix; other
+/// errors are file-level (the file failed or was reverted) and apply to every
+/// dependency without a more specific decision.
+pub fn build_file_decisions(
+    path: &Path,
+    result: &UpdateResult,
+    deps: &[ParsedDependency],
+    ctx: DecisionContext<'_>,
+) -> Vec<Decision> {
+    let file = path.display().to_string();
+    let mut updated = Claims::new(&result.updated);
+    let mut pinned = Claims::new(&result.pinned);
+    let mut ignored = Claims::new(&result.ignored);
+    let mut held_back = Claims::new(&result.held_back);
+    let mut skipped = Claims::new(&result.skipped_by_cooldown);
+
+    let package_error = |name: &str| {
+        result
+            .errors
+            .iter()
+            .find(|e| e.starts_with(&format!("{name}: ")))
+    };
+    let file_errors: Vec<&String> = result
+        .errors
+        .iter()
+        .filter(|e| !deps.iter().any(|d| e.starts_with(&format!("{}: ", d.name))))
+        .collect();
+
+    let mut decisions = Vec::with_capacity(deps.len());
+    // Inherited entries are decided where the workspace root defines them.
+    for dep in deps.iter().filter(|d| !d.inherits_workspace) {
+        let decision = |kind, current: &str, new: Option<&str>, reason: String| Decision {
+            file: file.clone(),
+            package: dep.name.clone(),
+            current: current.to_string(),
+            decision: kind,
+            new: new.map(str::to_string),
+            reason,
+            line: dep.line_number,
+        };
+
+        if let Some(e) = package_error(&dep.name) {
+            decisions.push(decision(DecisionKind::Error, &dep.version, None, e.clone()));
+        } else if let Some((_, old, new, _, _)) = held_back.claim(dep, |e| (&e.0, None)) {
+            // A held-back package is also recorded in `updated`; claim both so
+            // the dependency is reported once.
+            updated.claim(dep, |e| (&e.0, e.3));
+            decisions.push(decision(
+                DecisionKind::HeldBack,
+                old,
+                Some(new),
+                "newest version is inside the cooldown window; updated to the newest eligible version"
+                    .to_string(),
+            ));
+        } else if let Some((_, old, new, _)) = updated.claim(dep, |e| (&e.0, e.3)) {
+            decisions.push(decision(
+                DecisionKind::Updated,
+                old,
+                Some(new),
+                "newer version available".to_string(),
+            ));
+        } else if let Some((_, old, new, _)) = pinned.claim(dep, |e| (&e.0, e.3)) {
+            decisions.push(decision(
+                DecisionKind::Pinned,
+                old,
+                Some(new),
+                "pinned in config".to_string(),
+            ));
+        } else if let Some((_, current, latest, _)) = skipped.claim(dep, |e| (&e.0, None)) {
+            decisions.push(decision(
+                DecisionKind::SkippedByCooldown,
+                current,
+                None,
+                format!("every newer version (latest {latest}) is inside the cooldown window"),
+            ));
+        } else if let Some((_, current, _)) = ignored.claim(dep, |e| (&e.0, e.2)) {
+            decisions.push(decision(
+                DecisionKind::Ignored,
+                current,
+                None,
+                "listed in config `ignore`".to_string(),
+            ));
+        } else if let Some(e) = file_errors.first() {
+            decisions.push(decision(
+                DecisionKind::Error,
+                &dep.version,
+                None,
+                (*e).clone(),
+            ));
+        } else if !ctx.packages.is_empty() && !ctx.packages.iter().any(|p| p == &dep.name) {
+            decisions.push(decision(
+                DecisionKind::Filtered,
+                &dep.version,
+                None,
+                "not selected by --package".to_string(),
+            ));
+        } else if !dep.is_bumpable {
+            decisions.push(decision(
+                DecisionKind::NotBumpable,
+                &dep.version,
+                None,
+                "references a commit rather than a release".to_string(),
+            ));
+        } else if dep.has_upper_bound {
+            decisions.push(decision(
+                DecisionKind::Constrained,
+                &dep.version,
+                None,
+                "no newer version satisfies the upper-bound constraint".to_string(),
+            ));
+        } else {
+            let reason = if ctx.bump_filter_active {
+                "no newer version within the permitted bump levels"
+            } else {
+                "already at the newest eligible version"
+            };
+            decisions.push(decision(
+                DecisionKind::Unchanged,
+                &dep.version,
+                None,
+                reason.to_string(),
+            ));
+        }
+    }
+
+    // Entries the updater reported but parsing did not surface (e.g. names the
+    // parser spells differently) are still part of the record.
+    let leftover =
+        |package: &str, current: &str, kind, new: Option<&str>, reason: &str, line| Decision {
+            file: file.clone(),
+            package: package.to_string(),
+            current: current.to_string(),
+            decision: kind,
+            new: new.map(str::to_string),
+            reason: reason.to_string(),
+            line,
+        };
+    let held_back_names: Vec<&str> = result.held_back.iter().map(|e| e.0.as_str()).collect();
+    for (name, old, new, line) in updated.unclaimed() {
+        if held_back_names.contains(&name.as_str()) {
+            continue;
+        }
+        decisions.push(leftover(
+            name,
+            old,
+            DecisionKind::Updated,
+            Some(new),
+            "newer version available",
+            *line,
+        ));
+    }
+    for (name, old, new, _, _) in held_back.unclaimed() {
+        decisions.push(leftover(
+            name,
+            old,
+            DecisionKind::HeldBack,
+            Some(new),
+            "newest version is inside the cooldown window; updated to the newest eligible version",
+            None,
+        ));
+    }
+    for (name, old, new, line) in pinned.unclaimed() {
+        decisions.push(leftover(
+            name,
+            old,
+            DecisionKind::Pinned,
+            Some(new),
+            "pinned in config",
+            *line,
+        ));
+    }
+    for (name, current, _, _) in skipped.unclaimed() {
+        decisions.push(leftover(
+            name,
+            current,
+            DecisionKind::SkippedByCooldown,
+            None,
+            "every newer version is inside the cooldown window",
+            None,
+        ));
+    }
+    for (name, current, line) in ignored.unclaimed() {
+        decisions.push(leftover(
+            name,
+            current,
+            DecisionKind::Ignored,
+            None,
+            "listed in config `ignore`",
+            *line,
+        ));
+    }
+
+    decisions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn dep(name: &str, version: &str, line: usize) -> ParsedDependency {
+        ParsedDependency {
+            name: name.to_string(),
+            version: version.to_string(),
+            line_number: Some(line),
+            has_upper_bound: false,
+            is_bumpable: true,
+            inherits_workspace: false,
+        }
+    }
+
+    fn kinds(decisions: &[Decision]) -> Vec<(&str, DecisionKind)> {
+        decisions
+            .iter()
+            .map(|d| (d.package.as_str(), d.decision))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_dependency_gets_exactly_one_decision() {
+        let mut constrained = dep("django", "3.2", 5);
+        constrained.has_upper_bound = true;
+        let deps = vec![
+            dep("requests", "1.0.0", 1),
+            dep("flask", "2.0.0", 2),
+            dep("numpy", "1.0.0", 3),
+            dep("broken", "0.1.0", 4),
+            constrained,
+            dep("attrs", "23.1.0", 6),
+        ];
+        let result = UpdateResult {
+            updated: vec![(
+                "requests".to_string(),
+                "1.0.0".to_string(),
+                "1.0.1".to_string(),
+                Some(1),
+            )],
+            pinned: vec![(
+                "flask".to_string(),
+                "2.0.0".to_string(),
+                "2.3.0".to_string(),
+                Some(2),
+            )],
+            ignored: vec![("numpy".to_string(), "1.0.0".to_string(), Some(3))],
+            errors: vec!["broken: Package not found".to_string()],
+            unchanged: 2,
+            ..Default::default()
+        };
+
+        let decisions = build_file_decisions(
+            &PathBuf::from("requirements.txt"),
+            &result,
+            &deps,
+            DecisionContext::default(),
+        );
+
+        assert_eq!(
+            kinds(&decisions),
+            vec![
+                ("requests", DecisionKind::Updated),
+                ("flask", DecisionKind::Pinned),
+                ("numpy", DecisionKind::Ignored),
+                ("broken", DecisionKind::Error),
+                ("django", DecisionKind::Constrained),
+                ("attrs", DecisionKind::Unchanged),
+            ]
+        );
+        assert_eq!(decisions[0].new.as_deref(), Some("1.0.1"));
+        assert_eq!(decisions[3].reason, "broken: Package not found");
+        assert!(decisions.iter().all(|d| !d.reason.is_empty()));
+    }
+
+    #[test]
+    fn test_held_back_is_reported_once() {
+        let deps = vec![dep("lodash", "4.17.20", 1)];
+        let result = UpdateResult {
+            updated: vec![(
+                "lodash".to_string(),
+                "4.17.20".to_string(),
+                "4.17.21".to_string(),
+                Some(1),
+            )],
+            held_back: vec![(
+                "lodash".to_string(),
+                "4.17.20".to_string(),
+                "4.17.21".to_string(),
+                "4.17.22".to_string(),
+                Utc::now(),
+            )],
+            ..Default::default()
+        };
+
+        let decisions = build_file_decisions(
+            &PathBuf::from("package.json"),
+            &result,
+            &deps,
+            DecisionContext::default(),
+        );
+        assert_eq!(kinds(&decisions), vec![("lodash", DecisionKind::HeldBack)]);
+    }
+
+    #[test]
+    fn test_file_level_error_applies_to_undecided_dependencies() {
+        let deps = vec![dep("requests", "1.0.0", 1)];
+        let result = UpdateResult {
+            errors: vec!["connection refused".to_string()],
+            ..Default::default()
+        };
+        let decisions = build_file_decisions(
+            &PathBuf::from("requirements.txt"),
+            &result,
+            &deps,
+            DecisionContext::default(),
+        );
+        assert_eq!(kinds(&decisions), vec![("requests", DecisionKind::Error)]);
+        assert_eq!(decisions[0].reason, "connection refused");
+    }
+
+    #[test]
+    fn test_package_filter_and_bump_filter_reasons() {
+        let deps = vec![dep("requests", "1.0.0", 1), dep("flask", "2.0.0", 2)];
+        let packages = vec!["flask".to_string()];
+        let decisions = build_file_decisions(
+            &PathBuf::from("requirements.txt"),
+            &UpdateResult::default(),
+            &deps,
+            DecisionContext {
+                packages: &packages,
+                bump_filter_active: true,
+            },
+        );
+        assert_eq!(
+            kinds(&decisions),
+            vec![
+                ("requests", DecisionKind::Filtered),
+                ("flask", DecisionKind::Unchanged),
+            ]
+        );
+        assert!(decisions[1].reason.contains("bump levels"));
+    }
+
+    #[test]
+    fn test_unclaimed_result_entries_are_still_reported() {
+        let result = UpdateResult {
+            updated: vec![(
+                "serde".to_string(),
+                "1.0.0".to_string(),
+                "1.0.1".to_string(),
+                Some(7),
+            )],
+            ..Default::default()
+        };
+        let decisions = build_file_decisions(
+            &PathBuf::from("Cargo.toml"),
+            &result,
+            &[],
+            DecisionContext::default(),
+        );
+        assert_eq!(kinds(&decisions), vec![("serde", DecisionKind::Updated)]);
+        assert_eq!(decisions[0].line, Some(7));
+    }
+}
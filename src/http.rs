@@ -1,16 +1,26 @@
-//! TLS configuration for HTTP clients.
+//! TLS and host-allowlist configuration for HTTP clients.
 //!
 //! This module owns a process-global `HttpOptions` (initialized once per networked
-//! subcommand) describing extra CA certificates and an `--insecure` flag. Each
-//! `Client::builder()` chain in the codebase calls [`apply`] to inherit those options.
+//! subcommand) describing extra CA certificates, an `--insecure` flag, the
+//! `allowed_registries` host allowlist, the `--timeout` / `--retries` budget, the
+//! per-host concurrency cap (`--resolve-concurrency-per-host`, or one slot under
+//! `--no-parallel`) and the `--rate-limit` token bucket. Each `Client::builder()` chain in the
+//! codebase calls [`apply`] to inherit those options, and every request path calls
+//! [`ensure_host_allowed`] before sending. Registry lookups also hold an
+//! [`acquire_host_slot`] permit while their request is in flight and take an
+//! [`acquire_rate_token`] before each attempt.
 //!
-//! Pure helpers ([`resolve_ca_path`], [`parse_pem_bundle`], [`chain_indicates_tls_failure`])
-//! contain the testable logic; [`init`] is a thin shell over them.
+//! Pure helpers ([`resolve_ca_path`], [`parse_pem_bundle`], [`chain_indicates_tls_failure`],
+//! [`check_host`]) contain the testable logic; [`init`] is a thin shell over them.
 
 use anyhow::{Context, Result};
 use reqwest::{Certificate, ClientBuilder};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::OnceLock;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
 
 const CA_BUNDLE_ENV_VARS: &[&str] = &[
     "UPD_CA_BUNDLE",
@@ -19,11 +29,155 @@ const CA_BUNDLE_ENV_VARS: &[&str] = &[
     "SSL_CERT_FILE",
 ];
 
-#[derive(Debug, Default)]
+/// Default per-request timeout (`--timeout`).
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default number of retries after a failed request (`--retries`).
+pub const DEFAULT_RETRIES: u32 = 2;
+
+#[derive(Debug)]
 pub struct HttpOptions {
     pub insecure: bool,
     /// PEM-parsed certificates loaded once at startup from env-var-resolved paths.
     pub extra_certs: Vec<Certificate>,
+    /// Hosts that requests may be sent to (`allowed_registries` in the config).
+    /// Empty means unrestricted.
+    pub allowed_hosts: Vec<String>,
+    /// Whole-request timeout applied to every client.
+    pub timeout: Duration,
+    /// Retries after a network error or 5xx response; 0 disables retrying.
+    pub retries: u32,
+    /// Request slots per host (`--resolve-concurrency-per-host`), or one slot
+    /// shared by every host (`--no-parallel`).
+    pub host_limiter: HostLimiter,
+    /// Requests per second across every registry (`--rate-limit`).
+    pub rate_limiter: RateLimiter,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self {
+            insecure: false,
+            extra_certs: Vec::new(),
+            allowed_hosts: Vec::new(),
+            timeout: DEFAULT_TIMEOUT,
+            retries: DEFAULT_RETRIES,
+            host_limiter: HostLimiter::default(),
+            rate_limiter: RateLimiter::default(),
+        }
+    }
+}
+
+/// Caps the requests in flight to any one host.
+///
+/// Slots are keyed on the URL's `host:port`, not on the ecosystem, so a single
+/// mirror serving PyPI, npm and crates.io is protected across all of them.
+/// Without a limit (the default) every request proceeds immediately.
+#[derive(Debug, Default)]
+pub struct HostLimiter {
+    limit: Option<usize>,
+    /// Every host shares one set of slots.
+    shared: bool,
+    hosts: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl HostLimiter {
+    pub fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            shared: false,
+            hosts: Mutex::default(),
+        }
+    }
+
+    /// A single slot shared by all hosts: requests go out strictly one at a
+    /// time (`--no-parallel`).
+    pub fn serial() -> Self {
+        Self {
+            limit: Some(1),
+            shared: true,
+            hosts: Mutex::default(),
+        }
+    }
+
+    /// Wait for a free slot on `url`'s host. The slot is released when the
+    /// returned permit is dropped; `None` means the request is not limited.
+    pub async fn acquire(&self, url: &str) -> Option<OwnedSemaphorePermit> {
+        let limit = self.limit?;
+        let key = if self.shared {
+            String::new()
+        } else {
+            let parsed = url::Url::parse(url).ok()?;
+            let host = parsed.host_str()?.to_ascii_lowercase();
+            match parsed.port_or_known_default() {
+                Some(port) => format!("{host}:{port}"),
+                None => host,
+            }
+        };
+        let semaphore = {
+            let mut hosts = self.hosts.lock().ok()?;
+            Arc::clone(
+                hosts
+                    .entry(key)
+                    .or_insert_with(|| Arc::new(Semaphore::new(limit))),
+            )
+        };
+        semaphore.acquire_owned().await.ok()
+    }
+}
+
+/// Caps registry requests per second across every host and ecosystem.
+///
+/// A token bucket holding up to one second's worth of requests (at least
+/// one): a burst goes out immediately, after which requests are spaced
+/// `1 / rate` apart. Waiters are served in arrival order. Without a rate (the
+/// default) every request proceeds immediately.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    rate: Option<f64>,
+    bucket: tokio::sync::Mutex<TokenBucket>,
+}
+
+#[derive(Debug, Default)]
+struct TokenBucket {
+    tokens: f64,
+    /// When `tokens` was last brought up to date; `None` until the first
+    /// request, so the bucket starts full.
+    refilled_at: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// `rate` requests per second; `None` disables the limit.
+    pub fn new(rate: Option<f64>) -> Self {
+        Self {
+            rate: rate.filter(|r| r.is_finite() && *r > 0.0),
+            bucket: tokio::sync::Mutex::default(),
+        }
+    }
+
+    /// Wait until a request may be sent under the configured rate.
+    pub async fn acquire(&self) {
+        let Some(rate) = self.rate else {
+            return;
+        };
+        let burst = rate.floor().max(1.0);
+        // Held across the wait so later requests queue behind this one.
+        let mut bucket = self.bucket.lock().await;
+        let now = Instant::now();
+        let tokens = match bucket.refilled_at {
+            Some(at) => (bucket.tokens + now.duration_since(at).as_secs_f64() * rate).min(burst),
+            None => burst,
+        };
+        if tokens >= 1.0 {
+            bucket.tokens = tokens - 1.0;
+            bucket.refilled_at = Some(now);
+            return;
+        }
+        let wait = Duration::from_secs_f64((1.0 - tokens) / rate);
+        tokio::time::sleep(wait).await;
+        bucket.tokens = 0.0;
+        bucket.refilled_at = Some(now + wait);
+    }
 }
 
 static HTTP_OPTIONS: OnceLock<HttpOptions> = OnceLock::new();
@@ -133,6 +287,36 @@ where
 /// [`HttpOptions::default`] forever — only clients constructed after `init`
 /// pick up the configured CAs and `--insecure` flag.
 pub fn init(insecure: bool) -> Result<()> {
+    init_with_allowed_hosts(insecure, Vec::new())
+}
+
+/// Like [`init`], additionally restricting every request to `allowed_hosts`.
+///
+/// Entries are host names (`nexus.corp`) or `host:port` pairs, compared
+/// case-insensitively. An empty list leaves requests unrestricted.
+pub fn init_with_allowed_hosts(insecure: bool, allowed_hosts: Vec<String>) -> Result<()> {
+    init_with_limits(
+        insecure,
+        allowed_hosts,
+        DEFAULT_TIMEOUT,
+        DEFAULT_RETRIES,
+        HostLimiter::default(),
+        RateLimiter::default(),
+    )
+}
+
+/// Like [`init_with_allowed_hosts`], additionally setting the per-request
+/// `timeout`, the number of `retries` (0 disables retrying), the
+/// `host_limiter` that caps requests in flight and the `rate_limiter` that
+/// caps requests per second.
+pub fn init_with_limits(
+    insecure: bool,
+    allowed_hosts: Vec<String>,
+    timeout: Duration,
+    retries: u32,
+    host_limiter: HostLimiter,
+    rate_limiter: RateLimiter,
+) -> Result<()> {
     let extra_certs =
         compute_extra_certs(insecure, |k| std::env::var(k).ok(), |p| std::fs::read(p))?;
     // OnceLock::set is fallible if already set; that's fine — first init wins, later
@@ -140,6 +324,11 @@ pub fn init(insecure: bool) -> Result<()> {
     let _ = HTTP_OPTIONS.set(HttpOptions {
         insecure,
         extra_certs,
+        allowed_hosts,
+        timeout,
+        retries,
+        host_limiter,
+        rate_limiter,
     });
     Ok(())
 }
@@ -152,9 +341,17 @@ pub fn options() -> &'static HttpOptions {
         .unwrap_or_else(|| DEFAULT_OPTIONS.get_or_init(HttpOptions::default))
 }
 
-/// Apply the configured TLS options to a [`ClientBuilder`].
+/// Maximum redirect chain length when the allowlist redirect policy is active
+/// (matches reqwest's default policy).
+const MAX_REDIRECTS: usize = 10;
+
+/// Apply the configured TLS options and request timeout to a [`ClientBuilder`].
+///
+/// When a host allowlist is configured, redirects are also checked against it
+/// so an allowlisted registry cannot bounce a request to an arbitrary host.
 pub fn apply(mut builder: ClientBuilder) -> ClientBuilder {
     let opts = options();
+    builder = builder.timeout(opts.timeout);
     for cert in &opts.extra_certs {
         builder = builder.add_root_certificate(cert.clone());
     }
@@ -163,9 +360,77 @@ pub fn apply(mut builder: ClientBuilder) -> ClientBuilder {
             .danger_accept_invalid_certs(true)
             .danger_accept_invalid_hostnames(true);
     }
+    if !opts.allowed_hosts.is_empty() {
+        builder = builder.redirect(reqwest::redirect::Policy::custom(|attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                return attempt.error("too many redirects");
+            }
+            match ensure_host_allowed(attempt.url().as_str()) {
+                Ok(()) => attempt.follow(),
+                Err(e) => attempt.error(e.to_string()),
+            }
+        }));
+    }
     builder
 }
 
+/// Check `url` against a host allowlist.
+///
+/// An empty allowlist permits every host. Otherwise the URL's host (or
+/// `host:port`) must match an entry case-insensitively; anything else —
+/// including URLs that do not parse or have no host — is rejected with an
+/// error naming the host and the configured list.
+pub(crate) fn check_host(url: &str, allowed: &[String]) -> Result<()> {
+    if allowed.is_empty() {
+        return Ok(());
+    }
+    let parsed = url::Url::parse(url).ok();
+    let host = parsed
+        .as_ref()
+        .and_then(|u| u.host_str())
+        .map(str::to_ascii_lowercase);
+    if let Some(host) = &host {
+        let host_port = parsed
+            .as_ref()
+            .and_then(|u| u.port_or_known_default())
+            .map(|port| format!("{host}:{port}"));
+        let matches = allowed.iter().any(|entry| {
+            entry.eq_ignore_ascii_case(host)
+                || host_port
+                    .as_deref()
+                    .is_some_and(|hp| entry.eq_ignore_ascii_case(hp))
+        });
+        if matches {
+            return Ok(());
+        }
+    }
+    anyhow::bail!(
+        "refusing to send request to {}: host is not in allowed_registries ({})",
+        host.as_deref().unwrap_or(url),
+        allowed.join(", ")
+    )
+}
+
+/// Refuse `url` unless its host is permitted by the configured allowlist.
+///
+/// Called by every request path before `.send()`, so private package names
+/// never reach a host outside `allowed_registries`.
+pub fn ensure_host_allowed(url: &str) -> Result<()> {
+    check_host(url, &options().allowed_hosts)
+}
+
+/// Wait for a request slot on `url`'s host under the configured per-host
+/// cap. Hold the permit until the response arrives.
+pub async fn acquire_host_slot(url: &str) -> Option<OwnedSemaphorePermit> {
+    options().host_limiter.acquire(url).await
+}
+
+/// Wait for a token under the configured `--rate-limit`. Called before every
+/// registry request is sent, retries included.
+pub async fn acquire_rate_token() {
+    options().rate_limiter.acquire().await
+}
+
 /// Build the user-facing TLS hint for a given URL.
 fn tls_hint(url: &str) -> String {
     let host = url::Url::parse(url)
@@ -513,4 +778,148 @@ uMhJbUlN9AYtL2pAGNPK
             "apply(builder).build() must succeed: {client:?}"
         );
     }
+
+    #[test]
+    fn test_check_host_empty_allowlist_permits_everything() {
+        assert!(check_host("https://pypi.org/simple/requests/", &[]).is_ok());
+    }
+
+    #[test]
+    fn test_check_host_allowlisted_host_proceeds() {
+        let allowed = vec!["Nexus.Corp".to_string()];
+        assert!(check_host("https://nexus.corp/repository/pypi/simple/", &allowed).is_ok());
+    }
+
+    #[test]
+    fn test_check_host_matches_host_port_entries() {
+        let allowed = vec!["127.0.0.1:8080".to_string()];
+        assert!(check_host("http://127.0.0.1:8080/simple/", &allowed).is_ok());
+        assert!(check_host("http://127.0.0.1:9090/simple/", &allowed).is_err());
+    }
+
+    #[test]
+    fn test_check_host_blocks_non_allowlisted_host() {
+        let allowed = vec!["nexus.corp".to_string()];
+        let err = check_host("https://pypi.org/simple/internal-pkg/", &allowed)
+            .expect_err("pypi.org is not allowlisted");
+        let msg = err.to_string();
+        assert!(
+            msg.contains("pypi.org"),
+            "error should name the host: {msg}"
+        );
+        assert!(
+            msg.contains("allowed_registries"),
+            "error should name the config key: {msg}"
+        );
+        // The package path must not leak into the error either.
+        assert!(!msg.contains("internal-pkg"), "got: {msg}");
+    }
+
+    #[test]
+    fn test_check_host_does_not_match_suffix() {
+        let allowed = vec!["nexus.corp".to_string()];
+        assert!(check_host("https://evil-nexus.corp/", &allowed).is_err());
+        assert!(check_host("https://nexus.corp.evil.com/", &allowed).is_err());
+    }
+
+    #[test]
+    fn test_check_host_rejects_unparseable_url_when_restricted() {
+        let allowed = vec!["nexus.corp".to_string()];
+        assert!(check_host("not a url", &allowed).is_err());
+    }
+
+    /// Run `tasks` concurrent fake requests through `limiter`, alternating
+    /// between `urls`, and return the most that were ever in flight at once.
+    async fn max_in_flight(limiter: Arc<HostLimiter>, urls: &[&str], tasks: usize) -> usize {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let handles: Vec<_> = (0..tasks)
+            .map(|i| {
+                let limiter = Arc::clone(&limiter);
+                let in_flight = Arc::clone(&in_flight);
+                let peak = Arc::clone(&peak);
+                let url = urls[i % urls.len()].to_string();
+                tokio::spawn(async move {
+                    let _slot = limiter.acquire(&url).await;
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        peak.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_host_limiter_caps_one_host_across_ecosystems() {
+        let limiter = Arc::new(HostLimiter::new(Some(2)));
+        let urls = [
+            "http://mirror.corp:8081/simple/requests/",
+            "http://MIRROR.corp:8081/npm/left-pad",
+            "http://mirror.corp:8081/crates/api/v1/crates/serde",
+        ];
+        assert_eq!(max_in_flight(limiter, &urls, 12).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_host_limiter_keeps_hosts_independent() {
+        let limiter = Arc::new(HostLimiter::new(Some(1)));
+        let urls = ["https://pypi.org/simple/a/", "https://registry.npmjs.org/b"];
+        assert_eq!(max_in_flight(limiter, &urls, 8).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_serial_host_limiter_shares_one_slot_across_hosts() {
+        let limiter = Arc::new(HostLimiter::serial());
+        let urls = ["https://pypi.org/simple/a/", "https://registry.npmjs.org/b"];
+        assert_eq!(max_in_flight(limiter, &urls, 8).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_host_limiter_without_limit_is_unbounded() {
+        let limiter = HostLimiter::new(None);
+        assert!(
+            limiter
+                .acquire("https://pypi.org/simple/a/")
+                .await
+                .is_none()
+        );
+        let urls = ["https://pypi.org/simple/a/"];
+        assert_eq!(max_in_flight(Arc::new(limiter), &urls, 5).await, 5);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_spaces_requests_after_burst() {
+        let limiter = Arc::new(RateLimiter::new(Some(20.0)));
+        let start = std::time::Instant::now();
+        let handles: Vec<_> = (0..26)
+            .map(|_| {
+                let limiter = Arc::clone(&limiter);
+                tokio::spawn(async move { limiter.acquire().await })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        // 20 go out in the initial burst, the other 6 are 50ms apart.
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(280), "{elapsed:?}");
+        assert!(elapsed < Duration::from_secs(2), "{elapsed:?}");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_without_rate_is_unbounded() {
+        let limiter = RateLimiter::new(None);
+        let start = std::time::Instant::now();
+        for _ in 0..100 {
+            limiter.acquire().await;
+        }
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
 }
@@ -10,8 +10,15 @@ pub enum Decision {
     No,
     /// Apply all remaining updates
     All,
+    /// Apply this and every remaining update of the same type (major or
+    /// minor/patch), still prompting for the others
+    AllOfType,
+    /// Skip every remaining update not already approved by [`Decision::AllOfType`]
+    SkipRest,
     /// Skip all remaining updates and finish
     Quit,
+    /// Apply a version typed by the user instead of the proposed one
+    Edit,
 }
 
 /// Response from prompting user about updates
@@ -25,62 +32,110 @@ pub enum PromptResult {
     Quit,
 }
 
+/// Checks a version typed at the `[e]dit` prompt, returning why it was rejected.
+pub type VersionValidator<'a> = &'a dyn Fn(&PendingUpdate, &str) -> Result<(), String>;
+
 /// Prompt the user for a single update decision
-pub fn prompt_single(
-    file: &str,
-    line_num: Option<usize>,
-    package: &str,
-    old_version: &str,
-    new_version: &str,
-    is_major: bool,
+///
+/// `allow_edit` adds the `[e]dit` choice offered by `--interactive --edit`.
+pub fn prompt_single<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    update: &PendingUpdate,
+    allow_edit: bool,
 ) -> io::Result<Decision> {
     // Format location
-    let location = match line_num {
-        Some(n) => format!("{}:{}:", file, n),
-        None => format!("{}:", file),
+    let location = match update.line_num {
+        Some(n) => format!("{}:{}:", update.file, n),
+        None => format!("{}:", update.file),
     };
 
     // Build the prompt line
-    let type_indicator = if is_major {
+    let type_indicator = if update.is_major {
         " (MAJOR)".yellow().bold().to_string()
     } else {
         String::new()
     };
+    let update_type = if update.is_major { "major" } else { "minor" };
+    let edit_choice = if allow_edit {
+        format!(" / [{}]dit", "e".magenta().bold())
+    } else {
+        String::new()
+    };
 
-    print!(
-        "{} {} {} → {}{}\n  Apply? [{}]es / [{}]o / [{}]ll / [{}]uit: ",
+    write!(
+        output,
+        "{} {} {} → {}{}\n  Apply? [{}]es / [{}]o / [{}]ll / [{}]ll {} / [{}]kip rest / [{}]uit{}: ",
         location.blue().underline(),
-        package.bold(),
-        old_version.dimmed(),
-        new_version.green(),
+        update.package.bold(),
+        update.old_version.dimmed(),
+        update.new_version.green(),
         type_indicator,
         "y".green().bold(),
         "n".red().bold(),
         "a".cyan().bold(),
+        "A".cyan().bold(),
+        update_type,
+        "s".red().bold(),
         "q".yellow().bold(),
-    );
-    io::stdout().flush()?;
+        edit_choice,
+    )?;
+    output.flush()?;
 
     // Read user input
-    let stdin = io::stdin();
-    let mut input = String::new();
-    stdin.lock().read_line(&mut input)?;
+    let mut line = String::new();
+    input.read_line(&mut line)?;
 
-    let input = input.trim().to_lowercase();
+    // `A` is the only case-sensitive answer.
+    let line = line.trim();
+    if line == "A" {
+        return Ok(Decision::AllOfType);
+    }
+    let line = line.to_lowercase();
 
-    match input.as_str() {
+    match line.as_str() {
         "y" | "yes" | "" => Ok(Decision::Yes), // default to yes on empty input
         "n" | "no" => Ok(Decision::No),
         "a" | "all" => Ok(Decision::All),
+        "s" | "skip" => Ok(Decision::SkipRest),
         "q" | "quit" => Ok(Decision::Quit),
+        "e" | "edit" if allow_edit => Ok(Decision::Edit),
         _ => {
             // Invalid input, default to no
-            println!("{}", "Invalid input, skipping...".yellow());
+            writeln!(output, "{}", "Invalid input, skipping...".yellow())?;
             Ok(Decision::No)
         }
     }
 }
 
+/// Ask for the version to apply instead of the proposed one.
+///
+/// Returns `None` (skip the update) when the input is empty or rejected by
+/// `validate`.
+fn prompt_version<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    update: &PendingUpdate,
+    validate: VersionValidator<'_>,
+) -> io::Result<Option<String>> {
+    write!(output, "  Version for {}: ", update.package.bold())?;
+    output.flush()?;
+
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    let version = line.trim();
+
+    if version.is_empty() {
+        writeln!(output, "{}", "No version entered, skipping...".yellow())?;
+        return Ok(None);
+    }
+    if let Err(reason) = validate(update, version) {
+        writeln!(output, "{}", format!("{reason}, skipping...").yellow())?;
+        return Ok(None);
+    }
+    Ok(Some(version.to_string()))
+}
+
 /// Represents a pending update that can be approved or rejected
 #[derive(Debug, Clone)]
 pub struct PendingUpdate {
@@ -91,6 +146,8 @@ pub struct PendingUpdate {
     pub new_version: String,
     pub is_major: bool,
     pub approved: bool,
+    /// Version typed at the `[e]dit` prompt, applied instead of `new_version`
+    pub edited_version: Option<String>,
 }
 
 impl PendingUpdate {
@@ -110,53 +167,134 @@ impl PendingUpdate {
             new_version,
             is_major,
             approved: false,
+            edited_version: None,
+        }
+    }
+
+    /// The version to write if approved: the edited one, else the proposed one.
+    pub fn target_version(&self) -> &str {
+        self.edited_version.as_deref().unwrap_or(&self.new_version)
+    }
+}
+
+/// Group answers that carry over to the updates not yet prompted.
+#[derive(Debug, Default)]
+struct StickyDecisions {
+    approve_major: bool,
+    approve_minor: bool,
+    skip_rest: bool,
+}
+
+impl StickyDecisions {
+    /// The carried-over answer for `update`, or `None` to prompt for it.
+    fn decide(&self, update: &PendingUpdate) -> Option<bool> {
+        let approved = if update.is_major {
+            self.approve_major
+        } else {
+            self.approve_minor
+        };
+        if approved {
+            Some(true)
+        } else if self.skip_rest {
+            Some(false)
+        } else {
+            None
         }
     }
 }
 
 /// Run interactive prompts for all pending updates
 /// Returns the updates with their approval status set
-pub fn prompt_all(mut updates: Vec<PendingUpdate>) -> io::Result<Vec<PendingUpdate>> {
+///
+/// With `edit` set, the user may also type a custom version, which must pass
+/// the validator before it is recorded in `edited_version`.
+pub fn prompt_all(
+    updates: Vec<PendingUpdate>,
+    edit: Option<VersionValidator<'_>>,
+) -> io::Result<Vec<PendingUpdate>> {
+    let stdin = io::stdin();
+    prompt_all_with(updates, edit, &mut stdin.lock(), &mut io::stdout())
+}
+
+/// [`prompt_all`] over arbitrary input and output streams.
+pub fn prompt_all_with<R: BufRead, W: Write>(
+    mut updates: Vec<PendingUpdate>,
+    edit: Option<VersionValidator<'_>>,
+    input: &mut R,
+    output: &mut W,
+) -> io::Result<Vec<PendingUpdate>> {
     if updates.is_empty() {
         return Ok(updates);
     }
 
     let total = updates.len();
-    println!("\n{} {} update(s) available\n", "?".cyan().bold(), total);
+    writeln!(
+        output,
+        "\n{} {} update(s) available\n",
+        "?".cyan().bold(),
+        total
+    )?;
+
+    let mut sticky = StickyDecisions::default();
+
+    for i in 0..total {
+        if let Some(approved) = sticky.decide(&updates[i]) {
+            updates[i].approved = approved;
+            continue;
+        }
 
-    for (i, update) in updates.iter_mut().enumerate() {
         // Show progress
-        print!("[{}/{}] ", i + 1, total);
+        write!(output, "[{}/{}] ", i + 1, total)?;
 
-        let decision = prompt_single(
-            &update.file,
-            update.line_num,
-            &update.package,
-            &update.old_version,
-            &update.new_version,
-            update.is_major,
-        )?;
+        let decision = prompt_single(input, output, &updates[i], edit.is_some())?;
 
         match decision {
             Decision::Yes => {
-                update.approved = true;
+                updates[i].approved = true;
             }
             Decision::No => {
-                update.approved = false;
+                updates[i].approved = false;
+            }
+            Decision::Edit => {
+                // `Edit` is only offered when a validator was supplied.
+                if let Some(validate) = edit {
+                    let version = prompt_version(input, output, &updates[i], validate)?;
+                    updates[i].approved = version.is_some();
+                    updates[i].edited_version = version;
+                }
             }
             Decision::All => {
                 // Approve this and all remaining updates
-                update.approved = true;
-                for remaining in updates.iter_mut().skip(i + 1) {
+                for remaining in updates.iter_mut().skip(i) {
                     remaining.approved = true;
                 }
-                println!("{}", "Applying all remaining updates...".cyan());
+                writeln!(output, "{}", "Applying all remaining updates...".cyan())?;
                 break;
             }
+            Decision::AllOfType => {
+                updates[i].approved = true;
+                let update_type = if updates[i].is_major {
+                    sticky.approve_major = true;
+                    "major"
+                } else {
+                    sticky.approve_minor = true;
+                    "minor"
+                };
+                writeln!(
+                    output,
+                    "{}",
+                    format!("Applying all remaining {update_type} updates...").cyan()
+                )?;
+            }
+            Decision::SkipRest => {
+                updates[i].approved = false;
+                sticky.skip_rest = true;
+                writeln!(output, "{}", "Skipping remaining updates...".yellow())?;
+            }
             Decision::Quit => {
                 // Keep current update as not approved, stop prompting
-                update.approved = false;
-                println!("{}", "Skipping remaining updates...".yellow());
+                updates[i].approved = false;
+                writeln!(output, "{}", "Skipping remaining updates...".yellow())?;
                 break;
             }
         }
@@ -208,10 +346,152 @@ mod tests {
     #[test]
     fn test_prompt_all_empty() {
         let updates: Vec<PendingUpdate> = vec![];
-        let result = prompt_all(updates).unwrap();
+        let result = prompt_all(updates, None).unwrap();
         assert!(result.is_empty());
     }
 
+    fn pending(package: &str, old_version: &str, new_version: &str) -> PendingUpdate {
+        PendingUpdate::new(
+            "requirements.txt".to_string(),
+            Some(1),
+            package.to_string(),
+            old_version.to_string(),
+            new_version.to_string(),
+            true,
+        )
+    }
+
+    fn accept_numeric(_: &PendingUpdate, version: &str) -> Result<(), String> {
+        if version.split('.').all(|part| part.parse::<u64>().is_ok()) {
+            Ok(())
+        } else {
+            Err(format!("\"{version}\" is not a valid version"))
+        }
+    }
+
+    #[test]
+    fn test_prompt_all_with_records_edited_version() {
+        let updates = vec![
+            pending("flask", "2.0.0", "3.0.0"),
+            pending("requests", "2.28.0", "2.32.3"),
+        ];
+        let mut input = io::Cursor::new("e\n2.5.0\nn\n");
+        let mut output = Vec::new();
+
+        let result =
+            prompt_all_with(updates, Some(&accept_numeric), &mut input, &mut output).unwrap();
+
+        assert!(result[0].approved);
+        assert_eq!(result[0].edited_version.as_deref(), Some("2.5.0"));
+        assert_eq!(result[0].target_version(), "2.5.0");
+        assert!(!result[1].approved);
+        assert_eq!(result[1].target_version(), "2.32.3");
+        assert!(String::from_utf8(output).unwrap().contains("dit"));
+    }
+
+    #[test]
+    fn test_prompt_all_with_skips_rejected_edit() {
+        let mut input = io::Cursor::new("e\nlatest\n");
+        let mut output = Vec::new();
+
+        let result = prompt_all_with(
+            vec![pending("flask", "2.0.0", "3.0.0")],
+            Some(&accept_numeric),
+            &mut input,
+            &mut output,
+        )
+        .unwrap();
+
+        assert!(!result[0].approved);
+        assert_eq!(result[0].edited_version, None);
+        assert!(
+            String::from_utf8(output)
+                .unwrap()
+                .contains("\"latest\" is not a valid version")
+        );
+    }
+
+    #[test]
+    fn test_prompt_all_with_edit_is_invalid_input_without_validator() {
+        let mut input = io::Cursor::new("e\n");
+        let mut output = Vec::new();
+
+        let result = prompt_all_with(
+            vec![pending("flask", "2.0.0", "3.0.0")],
+            None,
+            &mut input,
+            &mut output,
+        )
+        .unwrap();
+
+        assert!(!result[0].approved);
+        assert!(
+            String::from_utf8(output)
+                .unwrap()
+                .contains("Invalid input, skipping...")
+        );
+    }
+
+    fn pending_minor(package: &str, old_version: &str, new_version: &str) -> PendingUpdate {
+        PendingUpdate {
+            is_major: false,
+            ..pending(package, old_version, new_version)
+        }
+    }
+
+    #[test]
+    fn test_prompt_all_with_approves_remaining_of_same_type() {
+        let updates = vec![
+            pending_minor("flask", "2.0.0", "2.1.0"),
+            pending("django", "4.2.0", "5.1.0"),
+            pending_minor("requests", "2.28.0", "2.32.3"),
+            pending("numpy", "1.26.0", "2.1.0"),
+        ];
+        // `A` on the first minor update, then `n` for the first major; the
+        // second minor is approved without a prompt.
+        let mut input = io::Cursor::new("A\nn\ny\n");
+        let mut output = Vec::new();
+
+        let result = prompt_all_with(updates, None, &mut input, &mut output).unwrap();
+
+        let approved: Vec<bool> = result.iter().map(|u| u.approved).collect();
+        assert_eq!(approved, vec![true, false, true, true]);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Applying all remaining minor updates..."));
+        assert!(!output.contains("[3/4]"));
+        assert!(output.contains("[4/4]"));
+    }
+
+    #[test]
+    fn test_prompt_all_with_skip_rest_keeps_sticky_approvals() {
+        let updates = vec![
+            pending("django", "4.2.0", "5.1.0"),
+            pending_minor("flask", "2.0.0", "2.1.0"),
+            pending("numpy", "1.26.0", "2.1.0"),
+            pending("pandas", "1.5.0", "2.2.0"),
+        ];
+        let mut input = io::Cursor::new("A\ns\n");
+        let mut output = Vec::new();
+
+        let result = prompt_all_with(updates, None, &mut input, &mut output).unwrap();
+
+        let approved: Vec<bool> = result.iter().map(|u| u.approved).collect();
+        assert_eq!(approved, vec![true, false, true, true]);
+
+        let mut input = io::Cursor::new("s\n");
+        let result = prompt_all_with(
+            vec![
+                pending("django", "4.2.0", "5.1.0"),
+                pending_minor("flask", "2.0.0", "2.1.0"),
+            ],
+            None,
+            &mut input,
+            &mut Vec::new(),
+        )
+        .unwrap();
+        assert!(result.iter().all(|u| !u.approved));
+    }
+
     #[test]
     fn test_decision_enum() {
         assert_eq!(Decision::Yes, Decision::Yes);
@@ -1,30 +1,42 @@
 pub mod align;
 pub mod audit;
 pub mod cache;
+pub mod changelog;
 pub mod cli;
 pub mod config;
 pub mod cooldown;
+pub mod decisions;
 pub mod http;
 pub mod interactive;
 pub mod lockfile;
+pub mod merge_reports;
+pub mod outdated;
 pub mod output;
 pub mod registry;
+pub mod sbom;
 pub mod schema;
+pub mod stdin;
+pub mod transitive;
 pub mod updater;
+pub mod verify;
 pub mod version;
 
-pub use align::{AlignResult, PackageAlignment, PackageOccurrence, find_alignments, scan_packages};
+pub use align::{
+    AlignResult, AlignTarget, PackageAlignment, PackageOccurrence, find_alignments,
+    find_alignments_to, scan_packages,
+};
 pub use audit::cache::AuditCache;
 pub use audit::{AuditResult, Ecosystem, OsvClient, Package, PackageAuditResult, Vulnerability};
 pub use cache::Cache;
-pub use cli::{Cli, Command, REVERT_TIP};
+pub use cli::{Cli, Command, ErrorOn, REVERT_TIP};
 pub use config::UpdConfig;
 pub use lockfile::{
     LockfileRegenResult, LockfileType, RegenOutcome, detect_lockfiles, regenerate_lockfiles,
     tool_available,
 };
 pub use registry::{
-    GitHubReleasesRegistry, NpmRegistry, NuGetRegistry, PyPiRegistry, Registry, RubyGemsRegistry,
+    DockerHubRegistry, GitHubReleasesRegistry, MavenRegistry, NpmRegistry, NuGetRegistry,
+    PackagistRegistry, PubRegistry, PyPiRegistry, Registry, RubyGemsRegistry, RuntimeRegistry,
     TerraformRegistry, VersionMeta,
 };
 pub use updater::{
@@ -49,6 +61,51 @@ pub fn decide_exit_code(non_mutating: bool, has_pending_updates: bool, has_error
     }
 }
 
+/// Determine the process exit code under `--error-on`, from the run's
+/// update counts by bump type.
+///
+/// Errors still exit `2` unless the level is [`ErrorOn::None`], which always
+/// exits `0`. Otherwise `1` means updates at or above the level were found,
+/// whether or not they were applied; [`ErrorOn::Any`] also counts pins and
+/// held-back versions through `has_pending_updates`.
+pub fn decide_error_on_exit_code(
+    error_on: ErrorOn,
+    (major, minor, patch): (usize, usize, usize),
+    has_pending_updates: bool,
+    has_errors: bool,
+) -> i32 {
+    let failing = match error_on {
+        ErrorOn::None => return 0,
+        ErrorOn::Error => false,
+        ErrorOn::Major => major > 0,
+        ErrorOn::Minor => major + minor > 0,
+        ErrorOn::Patch => major + minor + patch > 0,
+        ErrorOn::Any => has_pending_updates,
+    };
+    if has_errors {
+        2
+    } else if failing {
+        1
+    } else {
+        0
+    }
+}
+
+/// Exit code for a run that `--max-runtime` cut short: whatever resolved in
+/// time was reported (and applied in apply mode), the rest was skipped.
+/// Declared as the `timed_out_partial` outcome in the schema.
+pub const EXIT_TIMED_OUT: i32 = 7;
+
+/// Replace a clean or pending-updates exit code with [`EXIT_TIMED_OUT`] when
+/// the deadline skipped lookups. Errors (`2`) still take precedence.
+pub fn with_timed_out_exit_code(exit_code: i32, timed_out: bool) -> i32 {
+    if timed_out && exit_code != 2 {
+        EXIT_TIMED_OUT
+    } else {
+        exit_code
+    }
+}
+
 /// Determine the process exit code for the `audit` subcommand.
 ///
 /// - `2` — scan errors occurred; errors take precedence over vulnerability
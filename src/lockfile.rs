@@ -56,6 +56,7 @@ impl RegenOutcome {
 pub enum LockfileType {
     PoetryLock,
     UvLock,
+    PipfileLock,
     PackageLockJson,
     YarnLock,
     PnpmLock,
@@ -73,6 +74,7 @@ impl LockfileType {
         match self {
             LockfileType::PoetryLock => "poetry.lock",
             LockfileType::UvLock => "uv.lock",
+            LockfileType::PipfileLock => "Pipfile.lock",
             LockfileType::PackageLockJson => "package-lock.json",
             LockfileType::YarnLock => "yarn.lock",
             LockfileType::PnpmLock => "pnpm-lock.yaml",
@@ -92,13 +94,48 @@ impl LockfileType {
     /// it (`cargo update -p …`, `bundle lock --update …`). Ecosystems whose CLI
     /// supports a lockfile-only flag prefer that over a full install. Everything
     /// else falls back to the manifest-wide refresh command.
-    pub fn command(&self, changed: &[String]) -> (&'static str, Vec<String>) {
+    ///
+    /// Unless `allow_scripts` is set, tools that can run package code while
+    /// locking get their flag for not doing so: `--ignore-scripts` for npm,
+    /// pnpm and bun, `--no-build` for uv.
+    pub fn command(&self, changed: &[String], allow_scripts: bool) -> (&'static str, Vec<String>) {
+        let (cmd, mut args) = self.base_command(changed);
+        if !allow_scripts {
+            args.extend(self.no_scripts_flags().iter().map(|flag| flag.to_string()));
+        }
+        (cmd, args)
+    }
+
+    /// Flags that stop the tool from running install scripts or building
+    /// packages while it locks. Empty when the command runs no package code or
+    /// the tool has no such switch (Poetry, Pipenv, Bundler and .NET evaluate
+    /// the project itself).
+    pub fn no_scripts_flags(&self) -> &'static [&'static str] {
+        match self {
+            LockfileType::PackageLockJson | LockfileType::PnpmLock | LockfileType::BunLock => {
+                &["--ignore-scripts"]
+            }
+            LockfileType::UvLock => &["--no-build"],
+            // `--mode update-lockfile` already skips the build step.
+            LockfileType::YarnLock
+            | LockfileType::PoetryLock
+            | LockfileType::PipfileLock
+            | LockfileType::CargoLock
+            | LockfileType::GoSum
+            | LockfileType::GemfileLock
+            | LockfileType::PackagesLockJson
+            | LockfileType::TerraformLock => &[],
+        }
+    }
+
+    fn base_command(&self, changed: &[String]) -> (&'static str, Vec<String>) {
         match self {
             LockfileType::PoetryLock => (
                 "poetry",
                 vec!["lock".to_string(), "--no-update".to_string()],
             ),
             LockfileType::UvLock => ("uv", vec!["lock".to_string()]),
+            LockfileType::PipfileLock => ("pipenv", vec!["lock".to_string()]),
             LockfileType::PackageLockJson => (
                 "npm",
                 vec!["install".to_string(), "--package-lock-only".to_string()],
@@ -157,6 +194,7 @@ impl LockfileType {
     pub fn manifest(&self) -> &'static str {
         match self {
             LockfileType::PoetryLock | LockfileType::UvLock => "pyproject.toml",
+            LockfileType::PipfileLock => "Pipfile",
             LockfileType::PackageLockJson
             | LockfileType::YarnLock
             | LockfileType::PnpmLock
@@ -201,6 +239,16 @@ pub fn detect_lockfiles(manifest_path: &Path) -> Vec<LockfileType> {
         }
     }
 
+    // Check for pipenv lockfile (only if manifest is Pipfile)
+    if manifest_path
+        .file_name()
+        .map(|n| n == "Pipfile")
+        .unwrap_or(false)
+        && dir.join("Pipfile.lock").exists()
+    {
+        lockfiles.push(LockfileType::PipfileLock);
+    }
+
     // Check for Node.js lockfiles (only if manifest is package.json)
     if manifest_path
         .file_name()
@@ -221,6 +269,16 @@ pub fn detect_lockfiles(manifest_path: &Path) -> Vec<LockfileType> {
         }
     }
 
+    // A pnpm catalog change is installed through the workspace lockfile
+    if manifest_path
+        .file_name()
+        .map(|n| n == "pnpm-workspace.yaml")
+        .unwrap_or(false)
+        && dir.join("pnpm-lock.yaml").exists()
+    {
+        lockfiles.push(LockfileType::PnpmLock);
+    }
+
     // Check for Rust lockfile (only if manifest is Cargo.toml)
     if manifest_path
         .file_name()
@@ -293,7 +351,8 @@ pub fn tool_available(tool: &str) -> bool {
 /// `changed` is the list of package names that `upd` just rewrote in the
 /// corresponding manifest. This is forwarded to [`LockfileType::command`] so
 /// ecosystems that support targeted commands (e.g. `cargo update -p …`) only
-/// touch the packages that actually changed.
+/// touch the packages that actually changed. `allow_scripts` (`--allow-scripts`)
+/// drops the flags that keep install scripts from running.
 ///
 /// Returns a [`RegenOutcome`] distinguishing success, missing tool, and
 /// command failure.
@@ -301,10 +360,11 @@ pub(crate) fn regenerate_lockfile(
     manifest_path: &Path,
     lockfile_type: LockfileType,
     changed: &[String],
+    allow_scripts: bool,
     verbose: bool,
 ) -> RegenOutcome {
     let dir = manifest_path.parent().unwrap_or(Path::new("."));
-    let (cmd, args) = lockfile_type.command(changed);
+    let (cmd, args) = lockfile_type.command(changed, allow_scripts);
 
     if !tool_available(cmd) {
         return RegenOutcome::ToolMissing {
@@ -314,7 +374,7 @@ pub(crate) fn regenerate_lockfile(
     }
 
     if verbose {
-        println!(
+        crate::textln!(
             "{}",
             format!(
                 "Regenerating {} with `{} {}`...",
@@ -337,7 +397,7 @@ pub(crate) fn regenerate_lockfile(
     };
 
     if output.status.success() {
-        println!(
+        crate::textln!(
             "{} Regenerated {}",
             "✓".green(),
             lockfile_type.filename().bold()
@@ -388,6 +448,7 @@ impl LockfileRegenResult {
 pub fn regenerate_lockfiles(
     manifest_path: &Path,
     changed: &[String],
+    allow_scripts: bool,
     verbose: bool,
 ) -> LockfileRegenResult {
     let lockfiles = detect_lockfiles(manifest_path);
@@ -401,7 +462,7 @@ pub fn regenerate_lockfiles(
 
     let outcomes = lockfiles
         .into_iter()
-        .map(|lf| regenerate_lockfile(manifest_path, lf, changed, verbose))
+        .map(|lf| regenerate_lockfile(manifest_path, lf, changed, allow_scripts, verbose))
         .collect();
 
     LockfileRegenResult {
@@ -420,6 +481,7 @@ mod tests {
     fn test_lockfile_type_filename() {
         assert_eq!(LockfileType::PoetryLock.filename(), "poetry.lock");
         assert_eq!(LockfileType::UvLock.filename(), "uv.lock");
+        assert_eq!(LockfileType::PipfileLock.filename(), "Pipfile.lock");
         assert_eq!(
             LockfileType::PackageLockJson.filename(),
             "package-lock.json"
@@ -433,27 +495,30 @@ mod tests {
 
     #[test]
     fn test_lockfile_type_command() {
-        let (cmd, args) = LockfileType::PoetryLock.command(&[]);
+        let (cmd, args) = LockfileType::PoetryLock.command(&[], false);
         assert_eq!(cmd, "poetry");
         assert_eq!(args, &["lock", "--no-update"]);
 
-        let (cmd, args) = LockfileType::UvLock.command(&[]);
+        let (cmd, args) = LockfileType::UvLock.command(&[], false);
         assert_eq!(cmd, "uv");
-        assert_eq!(args, &["lock"]);
+        assert_eq!(args, &["lock", "--no-build"]);
     }
 
     #[test]
-    fn test_package_lock_json_uses_package_lock_only_flag() {
-        let (cmd, args) = LockfileType::PackageLockJson.command(&["react".to_string()]);
+    fn test_package_lock_json_uses_package_lock_only_and_ignore_scripts() {
+        let (cmd, args) = LockfileType::PackageLockJson.command(&["react".to_string()], false);
         assert_eq!(cmd, "npm");
-        assert_eq!(args, vec!["install", "--package-lock-only"]);
+        assert_eq!(
+            args,
+            vec!["install", "--package-lock-only", "--ignore-scripts"]
+        );
     }
 
     #[test]
-    fn test_pnpm_lock_uses_lockfile_only_flag() {
-        let (cmd, args) = LockfileType::PnpmLock.command(&["react".to_string()]);
+    fn test_pnpm_lock_uses_lockfile_only_and_ignore_scripts() {
+        let (cmd, args) = LockfileType::PnpmLock.command(&["react".to_string()], false);
         assert_eq!(cmd, "pnpm");
-        assert_eq!(args, vec!["install", "--lockfile-only"]);
+        assert_eq!(args, vec!["install", "--lockfile-only", "--ignore-scripts"]);
     }
 
     #[test]
@@ -461,7 +526,7 @@ mod tests {
         // Yarn Berry (2+) supports --mode update-lockfile; it is the only
         // documented flag that refreshes the lockfile without running install
         // scripts.
-        let (cmd, args) = LockfileType::YarnLock.command(&["react".to_string()]);
+        let (cmd, args) = LockfileType::YarnLock.command(&["react".to_string()], false);
         assert_eq!(cmd, "yarn");
         assert_eq!(args, vec!["install", "--mode", "update-lockfile"]);
     }
@@ -469,7 +534,7 @@ mod tests {
     #[test]
     fn test_cargo_lock_passes_each_changed_package_to_update_p() {
         let changed = vec!["serde".to_string(), "tokio".to_string()];
-        let (cmd, args) = LockfileType::CargoLock.command(&changed);
+        let (cmd, args) = LockfileType::CargoLock.command(&changed, false);
         assert_eq!(cmd, "cargo");
         assert_eq!(args, vec!["update", "-p", "serde", "-p", "tokio"]);
     }
@@ -479,7 +544,7 @@ mod tests {
         // Defensive: an empty changed list should never reach command() from the
         // update path, but if it does (e.g. the `upd lock` subcommand) we emit the
         // broad workspace update so nothing silently regresses.
-        let (cmd, args) = LockfileType::CargoLock.command(&[]);
+        let (cmd, args) = LockfileType::CargoLock.command(&[], false);
         assert_eq!(cmd, "cargo");
         assert_eq!(args, vec!["update", "--workspace"]);
     }
@@ -487,7 +552,7 @@ mod tests {
     #[test]
     fn test_gemfile_lock_uses_bundle_lock_update_with_changed_packages() {
         let changed = vec!["rails".to_string(), "pg".to_string()];
-        let (cmd, args) = LockfileType::GemfileLock.command(&changed);
+        let (cmd, args) = LockfileType::GemfileLock.command(&changed, false);
         assert_eq!(cmd, "bundle");
         assert_eq!(args, vec!["lock", "--update", "rails", "pg"]);
     }
@@ -497,46 +562,81 @@ mod tests {
         // Without targeted packages, `bundle lock --update` would bump every gem;
         // we emit plain `bundle lock` (refreshes against the current Gemfile
         // without bumping anything).
-        let (cmd, args) = LockfileType::GemfileLock.command(&[]);
+        let (cmd, args) = LockfileType::GemfileLock.command(&[], false);
         assert_eq!(cmd, "bundle");
         assert_eq!(args, vec!["lock"]);
     }
 
     #[test]
     fn test_go_sum_falls_back_to_mod_tidy_regardless_of_changed_list() {
-        let (cmd, args) = LockfileType::GoSum.command(&["golang.org/x/net".to_string()]);
+        let (cmd, args) = LockfileType::GoSum.command(&["golang.org/x/net".to_string()], false);
         assert_eq!(cmd, "go");
         assert_eq!(args, vec!["mod", "tidy"]);
     }
 
     #[test]
     fn test_packages_lock_json_falls_back_to_dotnet_restore() {
-        let (cmd, args) = LockfileType::PackagesLockJson.command(&["Newtonsoft.Json".to_string()]);
+        let (cmd, args) =
+            LockfileType::PackagesLockJson.command(&["Newtonsoft.Json".to_string()], false);
         assert_eq!(cmd, "dotnet");
         assert_eq!(args, vec!["restore"]);
     }
 
     #[test]
     fn test_terraform_lock_falls_back_to_providers_lock() {
-        let (cmd, args) = LockfileType::TerraformLock.command(&["hashicorp/aws".to_string()]);
+        let (cmd, args) =
+            LockfileType::TerraformLock.command(&["hashicorp/aws".to_string()], false);
         assert_eq!(cmd, "terraform");
         assert_eq!(args, vec!["providers", "lock"]);
     }
 
     #[test]
-    fn test_bun_lock_uses_bun_install() {
+    fn test_bun_lock_uses_bun_install_without_scripts() {
         // Bun does not have a stable lockfile-only mode; plain `install` is the
-        // minimum reliable form. Keeping the test pins the decision so changes
-        // here are intentional.
-        let (cmd, args) = LockfileType::BunLock.command(&["react".to_string()]);
+        // minimum reliable form, with lifecycle scripts off. Keeping the test
+        // pins the decision so changes here are intentional.
+        let (cmd, args) = LockfileType::BunLock.command(&["react".to_string()], false);
         assert_eq!(cmd, "bun");
+        assert_eq!(args, vec!["install", "--ignore-scripts"]);
+    }
+
+    #[test]
+    fn test_allow_scripts_drops_the_safety_flags() {
+        let changed = ["react".to_string()];
+        let (_, args) = LockfileType::PackageLockJson.command(&changed, true);
+        assert_eq!(args, vec!["install", "--package-lock-only"]);
+        let (_, args) = LockfileType::PnpmLock.command(&changed, true);
+        assert_eq!(args, vec!["install", "--lockfile-only"]);
+        let (_, args) = LockfileType::BunLock.command(&changed, true);
         assert_eq!(args, vec!["install"]);
+        let (_, args) = LockfileType::UvLock.command(&[], true);
+        assert_eq!(args, vec!["lock"]);
+    }
+
+    #[test]
+    fn test_safety_flags_only_where_the_tool_has_one() {
+        // These either run no package code while locking or have no switch
+        // to stop it; their commands are the same either way.
+        for lockfile in [
+            LockfileType::PoetryLock,
+            LockfileType::PipfileLock,
+            LockfileType::YarnLock,
+            LockfileType::CargoLock,
+            LockfileType::GoSum,
+            LockfileType::GemfileLock,
+            LockfileType::PackagesLockJson,
+            LockfileType::TerraformLock,
+        ] {
+            assert!(lockfile.no_scripts_flags().is_empty());
+            assert_eq!(lockfile.command(&[], false), lockfile.command(&[], true));
+        }
     }
 
     #[test]
     fn test_lockfile_type_manifest() {
         assert_eq!(LockfileType::PoetryLock.manifest(), "pyproject.toml");
         assert_eq!(LockfileType::UvLock.manifest(), "pyproject.toml");
+        assert_eq!(LockfileType::PipfileLock.manifest(), "Pipfile");
         assert_eq!(LockfileType::PackageLockJson.manifest(), "package.json");
         assert_eq!(LockfileType::YarnLock.manifest(), "package.json");
         assert_eq!(LockfileType::PnpmLock.manifest(), "package.json");
@@ -601,6 +701,17 @@ mod tests {
         assert_eq!(detected[0], LockfileType::YarnLock);
     }
 
+    #[test]
+    fn test_detect_lockfiles_pnpm_workspace() {
+        let dir = tempdir().unwrap();
+        let manifest = dir.path().join("pnpm-workspace.yaml");
+
+        fs::write(&manifest, "catalog:\n  react: ^18.2.0\n").unwrap();
+        fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+
+        assert_eq!(detect_lockfiles(&manifest), vec![LockfileType::PnpmLock]);
+    }
+
     #[test]
     fn test_detect_lockfiles_pnpm() {
         let dir = tempdir().unwrap();
@@ -629,6 +740,22 @@ mod tests {
         assert_eq!(detected[0], LockfileType::BunLock);
     }
 
+    #[test]
+    fn test_detect_lockfiles_pipenv() {
+        let dir = tempdir().unwrap();
+        let manifest = dir.path().join("Pipfile");
+        let lockfile = dir.path().join("Pipfile.lock");
+
+        fs::write(&manifest, "[packages]").unwrap();
+        fs::write(&lockfile, "{}").unwrap();
+
+        let detected = detect_lockfiles(&manifest);
+        assert_eq!(detected, vec![LockfileType::PipfileLock]);
+        let (cmd, args) = LockfileType::PipfileLock.command(&["requests".to_string()], false);
+        assert_eq!(cmd, "pipenv");
+        assert_eq!(args, vec!["lock"]);
+    }
+
     #[test]
     fn test_detect_lockfiles_cargo() {
         let dir = tempdir().unwrap();
@@ -846,7 +973,7 @@ mod tests {
         fs::write(&manifest, "{}").unwrap();
         // Deliberately do NOT create package-lock.json
 
-        let result = regenerate_lockfiles(&manifest, &[], false);
+        let result = regenerate_lockfiles(&manifest, &[], false, false);
         assert!(
             result.no_lockfiles,
             "no_lockfiles should be true when no lockfile exists beside the manifest"
@@ -865,7 +992,7 @@ mod tests {
         fs::write(&manifest, "[package]").unwrap();
         // Deliberately do NOT create Cargo.lock
 
-        let result = regenerate_lockfiles(&manifest, &[], false);
+        let result = regenerate_lockfiles(&manifest, &[], false, false);
         assert!(
             result.no_lockfiles,
             "no_lockfiles should be true when Cargo.lock is absent"
@@ -2,32 +2,53 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
 use clap::Parser;
 use colored::Colorize;
+use futures::future::join_all;
 use futures::stream::{self, StreamExt};
 
-use std::collections::{BTreeSet, HashMap, HashSet};
+use similar::{ChangeTag, TextDiff};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use upd::align::{PackageAlignment, PackageOccurrence, find_alignments, scan_packages};
+use tracing::Instrument;
+use upd::align::{
+    AlignTarget, PackageAlignment, PackageOccurrence, find_alignments_to, scan_packages,
+};
 use upd::audit::cache::AuditCache;
 use upd::audit::{AuditResult, Ecosystem, OsvClient, Package as AuditPackage, compute_fix_plan};
 use upd::cache::{Cache, CachedRegistry};
-use upd::cli::{BumpLevel, Cli, Command, OutputMode, REVERT_TIP};
+use upd::changelog::ChangelogLink;
+use upd::cli::{BumpLevel, Cli, Command, ErrorOn, OutputMode, REVERT_TIP};
 use upd::config::UpdConfig;
 use upd::cooldown::CooldownPolicy;
+use upd::decisions::{
+    DECISIONS_SCHEMA_VERSION, DecisionContext, DecisionsRecord, build_file_decisions,
+};
 use upd::interactive::{PendingUpdate, prompt_all};
-use upd::lockfile::{LockfileRegenResult, regenerate_lockfiles};
+use upd::lockfile::{LockfileRegenResult, detect_lockfiles, regenerate_lockfiles};
+use upd::outdated::{OutdatedPackage, majors_behind};
+use upd::output::{SummaryCounts, SummaryJson};
+#[cfg(feature = "registry-fixture")]
+use upd::registry::RegistryFixture;
 use upd::registry::{
-    CratesIoRegistry, GitHubReleasesRegistry, GoProxyRegistry, MultiPyPiRegistry, NpmRegistry,
-    NuGetRegistry, PyPiRegistry, RubyGemsRegistry, TerraformRegistry,
+    CratesIoRegistry, DockerHubRegistry, GitHubReleasesRegistry, GoProxyRegistry, MavenRegistry,
+    MultiPyPiRegistry, NpmRegistry, NuGetRegistry, PackagistRegistry, PubRegistry, PyPiRegistry,
+    Registry, RubyGemsRegistry, RuntimeRegistry, TerraformRegistry, mapped_registry_url,
 };
+use upd::stdin::StdinManifest;
+use upd::textln;
+use upd::transitive::{OutdatedTransitive, collect_transitive, find_outdated_transitive};
 use upd::updater::{
-    BumpFilter, CargoTomlUpdater, CsprojUpdater, DiscoverOptions, FileType, GemfileUpdater,
-    GithubActionsUpdater, GoModUpdater, Lang, MiseUpdater, PackageJsonUpdater, PreCommitUpdater,
-    PyProjectUpdater, RequirementsUpdater, TerraformUpdater, UpdateOptions, UpdateResult, Updater,
-    discover_files_with, read_file_safe, write_file_atomic,
+    BumpFilter, CargoTomlUpdater, ComposerUpdater, CondaEnvUpdater, ContentChange, CsprojUpdater,
+    DiscoverOptions, DockerfileUpdater, FileType, GemfileUpdater, GithubActionsUpdater,
+    GoModUpdater, GradleCatalogUpdater, Lang, MiseUpdater, PackageJsonUpdater, PipfileUpdater,
+    PnpmWorkspaceUpdater, PreCommitUpdater, PubspecUpdater, PyProjectUpdater, RequirementsUpdater,
+    RuntimeVersionUpdater, SetupPyUpdater, SourceRegistries, TerraformUpdater, UpdateOptions,
+    UpdateResult, Updater, discover_files_with, read_file_safe, with_included_requirements,
+    write_file_atomic,
 };
-use upd::version::match_version_precision;
+use upd::verify::FileBackup;
+use upd::version::{is_valid_version, match_version_precision};
 
 /// Walk up from `start` to find the nearest ancestor directory that contains a
 /// `.git` entry (file or directory). Returns the path to that ancestor.
@@ -131,8 +152,20 @@ fn humanize_cooldown(d: Duration) -> String {
     format!("{}s", d.num_seconds())
 }
 
-fn init_tls(cli: &Cli) -> anyhow::Result<()> {
-    upd::http::init(cli.insecure).context("Failed to initialize TLS options")?;
+fn init_tls(cli: &Cli, allowed_registries: &[String]) -> anyhow::Result<()> {
+    upd::http::init_with_limits(
+        cli.insecure,
+        allowed_registries.to_vec(),
+        std::time::Duration::from_secs(cli.timeout),
+        cli.retries,
+        if cli.no_parallel {
+            upd::http::HostLimiter::serial()
+        } else {
+            upd::http::HostLimiter::new(cli.resolve_concurrency_per_host)
+        },
+        upd::http::RateLimiter::new(cli.rate_limit),
+    )
+    .context("Failed to initialize TLS options")?;
     if cli.insecure {
         eprintln!(
             "{}: TLS certificate verification disabled \u{2014} connections are not authenticated",
@@ -177,8 +210,12 @@ fn format_skipped_by_cooldown_line(
 /// Map a [`FileType`] to the registry ecosystem name used by cooldown policy keys.
 fn ecosystem_for_file_type(file_type: FileType) -> &'static str {
     match file_type {
-        FileType::Requirements | FileType::PyProject => "pypi",
-        FileType::PackageJson => "npm",
+        FileType::Requirements
+        | FileType::PyProject
+        | FileType::Pipfile
+        | FileType::SetupPy
+        | FileType::CondaEnv => "pypi",
+        FileType::PackageJson | FileType::PnpmWorkspace => "npm",
         FileType::CargoToml => "crates.io",
         FileType::GoMod => "go-proxy",
         FileType::Gemfile => "rubygems",
@@ -188,10 +225,16 @@ fn ecosystem_for_file_type(file_type: FileType) -> &'static str {
         | FileType::ToolVersions => "github-releases",
         FileType::Csproj => "nuget",
         FileType::TerraformTf => "terraform",
+        FileType::Dockerfile => "docker-hub",
+        FileType::ComposerJson => "packagist",
+        FileType::GradleCatalog => "maven",
+        FileType::Pubspec => "pub",
+        FileType::Nvmrc | FileType::PythonVersion => "runtime",
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Ordered by impact: `Major` sorts first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum UpdateType {
     Major,
     Minor,
@@ -201,6 +244,8 @@ enum UpdateType {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum ChangeKind {
     RegistryUpdate,
+    /// A registry update whose version was typed at the `[e]dit` prompt
+    EditedUpdate,
     ConfigPin,
 }
 
@@ -266,7 +311,11 @@ enum AuditStatus {
 #[derive(Debug, Clone)]
 struct ResolvedUpdateConfig {
     config: Arc<UpdConfig>,
+    /// The nearest config file (or the `--config` path).
     path: PathBuf,
+    /// Configs farther up, up to the repository root, that `path` was
+    /// merged over. Farthest first.
+    inherited: Vec<PathBuf>,
     explicit: bool,
 }
 
@@ -277,27 +326,30 @@ fn log_update_config_usage(resolved: &ResolvedUpdateConfig) {
         return;
     }
 
-    println!(
+    eprintln!(
         "{}",
         format!("Using config from: {}", resolved.path.display()).cyan()
     );
+    for path in resolved.inherited.iter().rev() {
+        eprintln!("{}", format!("  Merged over {}", path.display()).dimmed());
+    }
 
     if !resolved.config.ignore.is_empty() {
-        println!(
+        eprintln!(
             "{}",
             format!("  Ignoring {} package(s)", resolved.config.ignore.len()).dimmed()
         );
     }
 
     if !resolved.config.pin.is_empty() {
-        println!(
+        eprintln!(
             "{}",
             format!("  Pinning {} package(s)", resolved.config.pin.len()).dimmed()
         );
     }
 
     if !resolved.config.exclude.is_empty() {
-        println!(
+        eprintln!(
             "{}",
             format!(
                 "  Excluding {} path pattern(s)",
@@ -306,15 +358,35 @@ fn log_update_config_usage(resolved: &ResolvedUpdateConfig) {
             .dimmed()
         );
     }
+
+    if !resolved.config.allowed_registries.is_empty() {
+        eprintln!(
+            "{}",
+            format!(
+                "  Restricting requests to {} registry host(s)",
+                resolved.config.allowed_registries.len()
+            )
+            .dimmed()
+        );
+    }
 }
 
+/// The config for files in `start_dir`: the nearest config, merged over any
+/// others between it and the repository root (nearer wins).
 fn discover_update_config(start_dir: &Path) -> Result<Option<ResolvedUpdateConfig>, String> {
+    let root = find_vcs_root(start_dir);
     Ok(
-        UpdConfig::discover(start_dir)?.map(|(config, path)| ResolvedUpdateConfig {
-            config: Arc::new(config),
-            path,
-            explicit: false,
-        }),
+        UpdConfig::discover_layered(start_dir, root.as_deref())?.and_then(
+            |(config, mut sources)| {
+                let path = sources.pop()?;
+                Some(ResolvedUpdateConfig {
+                    config: Arc::new(config),
+                    path,
+                    inherited: sources,
+                    explicit: false,
+                })
+            },
+        ),
     )
 }
 
@@ -324,7 +396,7 @@ fn discover_update_config(start_dir: &Path) -> Result<Option<ResolvedUpdateConfi
 /// for `align`) describe the whole scan rather than an individual file, so they
 /// are resolved once from a single config: an explicit `--config` when given,
 /// otherwise the nearest config discovered upward from the first scan path.
-/// Falls back to an empty config when none is found.
+/// Falls back to an empty config when none is found or `--no-config` is set.
 fn resolve_root_config(cli: &Cli, paths: &[PathBuf]) -> Result<ResolvedUpdateConfig> {
     if let Some(config_path) = &cli.config {
         return Ok(ResolvedUpdateConfig {
@@ -332,11 +404,36 @@ fn resolve_root_config(cli: &Cli, paths: &[PathBuf]) -> Result<ResolvedUpdateCon
                 UpdConfig::load_from_path_with_error(config_path).map_err(anyhow::Error::msg)?,
             ),
             path: config_path.clone(),
+            inherited: Vec::new(),
             explicit: true,
         });
     }
 
-    let start_dir = paths
+    let start_dir = config_start_dir(paths);
+
+    if cli.no_config {
+        return Ok(ResolvedUpdateConfig {
+            config: Arc::new(UpdConfig::default()),
+            path: start_dir,
+            inherited: Vec::new(),
+            explicit: false,
+        });
+    }
+
+    Ok(discover_update_config(&start_dir)
+        .map_err(anyhow::Error::msg)?
+        .unwrap_or_else(|| ResolvedUpdateConfig {
+            config: Arc::new(UpdConfig::default()),
+            path: start_dir,
+            inherited: Vec::new(),
+            explicit: false,
+        }))
+}
+
+/// Directory config discovery starts from: the first scan path (or its parent
+/// directory when it is a file), else the working directory.
+fn config_start_dir(paths: &[PathBuf]) -> PathBuf {
+    paths
         .first()
         .map(|p| {
             if p.is_dir() {
@@ -347,27 +444,111 @@ fn resolve_root_config(cli: &Cli, paths: &[PathBuf]) -> Result<ResolvedUpdateCon
                     .unwrap_or_else(|| PathBuf::from("."))
             }
         })
-        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+}
 
-    Ok(discover_update_config(&start_dir)
-        .map_err(anyhow::Error::msg)?
-        .unwrap_or_else(|| ResolvedUpdateConfig {
-            config: Arc::new(UpdConfig::default()),
-            path: start_dir,
-            explicit: false,
-        }))
+/// `--print-config`: the `ignore` list and `pin` map a run from the first
+/// scan path would use, and the files they were read from.
+fn print_effective_config(cli: &Cli) -> Result<()> {
+    let (config, sources) = if cli.no_config {
+        (UpdConfig::default(), Vec::new())
+    } else if let Some(config_path) = &cli.config {
+        let config =
+            UpdConfig::load_from_path_with_error(config_path).map_err(anyhow::Error::msg)?;
+        (config, vec![config_path.clone()])
+    } else {
+        discover_update_config(&config_start_dir(&cli.paths))
+            .map_err(anyhow::Error::msg)?
+            .map(|resolved| {
+                let mut sources = resolved.inherited;
+                sources.push(resolved.path);
+                (Arc::unwrap_or_clone(resolved.config), sources)
+            })
+            .unwrap_or_default()
+    };
+    let pins: BTreeMap<&String, &String> = config.pin.iter().collect();
+
+    if effective_json_mode(cli) {
+        let output = serde_json::json!({
+            "sources": sources.iter().map(|p| p.display().to_string()).collect::<Vec<_>>(),
+            "ignore": config.ignore,
+            "pin": pins,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    match sources.as_slice() {
+        [] if cli.no_config => textln!("Config: none (--no-config)"),
+        [] => textln!("Config: none found"),
+        [first, rest @ ..] => {
+            textln!("Config: {}", first.display());
+            for path in rest {
+                textln!(
+                    "        {} {}",
+                    path.display(),
+                    "(overrides the above)".dimmed()
+                );
+            }
+        }
+    }
+    textln!("{} ({}):", "ignore".bold(), config.ignore.len());
+    for package in &config.ignore {
+        textln!("  {}", package);
+    }
+    textln!("{} ({}):", "pin".bold(), pins.len());
+    for (package, version) in &pins {
+        textln!("  {} = {}", package, version);
+    }
+    Ok(())
+}
+
+/// Warn when a nested config restricts hosts differently from the config of
+/// the scanned path. The allowlist is enforced for the whole process, so only
+/// the latter applies.
+fn warn_unenforced_allowed_registries(
+    root: &ResolvedUpdateConfig,
+    file_configs: &HashMap<PathBuf, Option<Arc<UpdConfig>>>,
+) {
+    let Some(first) = file_configs
+        .iter()
+        .filter(|(_, config)| {
+            config
+                .as_ref()
+                .is_some_and(|config| config.allowed_registries != root.config.allowed_registries)
+        })
+        .map(|(path, _)| path)
+        .min()
+    else {
+        return;
+    };
+    let dir = first.parent().unwrap_or(first.as_path());
+    eprintln!(
+        "{}: allowed_registries in the config for {} is not enforced: the host allowlist comes only from the config for the scanned path (run `upd {}` to apply it)",
+        "warning".yellow().bold(),
+        first.display(),
+        dir.display()
+    );
 }
 
 fn load_update_configs(
     cli: &Cli,
     files: &[(PathBuf, FileType)],
 ) -> Result<HashMap<PathBuf, Option<Arc<UpdConfig>>>> {
+    if cli.no_config {
+        if cli.verbose {
+            eprintln!("{}", "Config files bypassed (--no-config)".cyan());
+        }
+        return Ok(files.iter().map(|(path, _)| (path.clone(), None)).collect());
+    }
+
     let explicit_config = if let Some(config_path) = &cli.config {
         Some(ResolvedUpdateConfig {
             config: Arc::new(
                 UpdConfig::load_from_path_with_error(config_path).map_err(anyhow::Error::msg)?,
             ),
             path: config_path.clone(),
+            inherited: Vec::new(),
             explicit: true,
         })
     } else {
@@ -434,17 +615,24 @@ fn build_update_options(
     options
 }
 
+/// Approved occurrences of each planned change, each carrying the version
+/// typed at the `[e]dit` prompt, if any.
+type ApprovedChanges = HashMap<PlannedChange, Vec<Option<String>>>;
+
 fn build_approved_change_counts(
     updates_with_decisions: &[PendingUpdate],
     planned_changes: &[PlannedChange],
-) -> HashMap<PlannedChange, usize> {
+) -> ApprovedChanges {
     debug_assert_eq!(updates_with_decisions.len(), planned_changes.len());
 
-    let mut approved_counts = HashMap::new();
+    let mut approved_counts: ApprovedChanges = HashMap::new();
 
     for (update, change) in updates_with_decisions.iter().zip(planned_changes.iter()) {
         if update.approved {
-            *approved_counts.entry(change.clone()).or_insert(0) += 1;
+            approved_counts
+                .entry(change.clone())
+                .or_default()
+                .push(update.edited_version.clone());
         }
     }
 
@@ -455,21 +643,24 @@ fn take_approved_changes_for_file(
     path: &Path,
     file_type: FileType,
     updates: &[(String, String, String, Option<usize>)],
-    approved_change_counts: &mut HashMap<PlannedChange, usize>,
+    approved_change_counts: &mut ApprovedChanges,
 ) -> Vec<PlannedChange> {
     let mut selected = Vec::new();
 
     for update in updates {
-        let candidate = PlannedChange::from_update(path.to_path_buf(), file_type, update);
-        if let Some(count) = approved_change_counts.get_mut(&candidate)
-            && *count > 0
+        let mut candidate = PlannedChange::from_update(path.to_path_buf(), file_type, update);
+        if let Some(approvals) = approved_change_counts.get_mut(&candidate)
+            && !approvals.is_empty()
         {
-            *count -= 1;
+            if let Some(edited_version) = approvals.remove(0) {
+                candidate.kind = ChangeKind::EditedUpdate;
+                candidate.new_version = edited_version;
+            }
             selected.push(candidate);
         }
     }
 
-    approved_change_counts.retain(|_, count| *count > 0);
+    approved_change_counts.retain(|_, approvals| !approvals.is_empty());
     selected
 }
 
@@ -486,7 +677,7 @@ fn take_pinned_changes_for_file(
 
 fn collect_selected_changes_for_file(
     scanned_file: &ScannedFileResult,
-    approved_change_counts: &mut HashMap<PlannedChange, usize>,
+    approved_change_counts: &mut ApprovedChanges,
 ) -> Vec<PlannedChange> {
     let mut selected = take_approved_changes_for_file(
         &scanned_file.path,
@@ -502,10 +693,116 @@ fn collect_selected_changes_for_file(
     selected
 }
 
+/// Rewrite a manifest with the changes selected at the interactive prompt.
+///
+/// Versions typed at the `[e]dit` prompt are written as entered; the rest
+/// follow the usual precision matching.
+fn rewrite_selected_changes(
+    content: &str,
+    changes: &[PlannedChange],
+    file_type: FileType,
+    full_precision: bool,
+) -> Result<String> {
+    let (edited, proposed): (Vec<_>, Vec<_>) = changes
+        .iter()
+        .partition(|change| change.kind == ChangeKind::EditedUpdate);
+
+    let mut rewritten = content.to_string();
+    for (changes, full_precision) in [(proposed, full_precision), (edited, true)] {
+        if changes.is_empty() {
+            continue;
+        }
+        let updates: Vec<_> = changes
+            .iter()
+            .map(|change| VersionEdit {
+                package: change.package.as_str(),
+                old_version: change.old_version.as_str(),
+                new_version: change.new_version.as_str(),
+                line_num: change.line_num,
+            })
+            .collect();
+        rewritten = apply_version_updates(&rewritten, &updates, file_type, full_precision)?.content;
+    }
+    Ok(rewritten)
+}
+
 fn file_has_manifest_changes(result: &UpdateResult) -> bool {
     !result.updated.is_empty() || !result.pinned.is_empty()
 }
 
+/// Changed package names grouped by the directory of their manifest file.
+/// Each directory gets its own targeted lockfile command so we never pull in
+/// transitive churn from sibling subprojects. Both registry updates and config
+/// pins modify the manifest, so both contribute — otherwise pin-only changes
+/// would silently degrade to a broad refresh.
+fn changed_packages_by_dir(scanned: &[ScannedFileResult]) -> HashMap<PathBuf, Vec<String>> {
+    let mut changed_by_dir: HashMap<PathBuf, Vec<String>> = HashMap::new();
+    for scanned_file in scanned {
+        if !file_has_manifest_changes(&scanned_file.result) {
+            continue;
+        }
+        let Some(dir) = scanned_file.path.parent() else {
+            continue;
+        };
+        let entry = changed_by_dir.entry(dir.to_path_buf()).or_default();
+        for (name, _, _, _) in scanned_file
+            .result
+            .updated
+            .iter()
+            .chain(scanned_file.result.pinned.iter())
+        {
+            if !entry.iter().any(|n| n == name) {
+                entry.push(name.clone());
+            }
+        }
+    }
+    changed_by_dir
+}
+
+/// Under `--lock --dry-run`, list the lockfiles a real run would regenerate
+/// and the command it would run for each, without invoking any tool. Like a
+/// real run, the first changed manifest of a directory decides its lockfiles.
+fn print_would_regenerate_lockfiles(scanned: &[ScannedFileResult], allow_scripts: bool) {
+    let changed_by_dir = changed_packages_by_dir(scanned);
+    let empty: Vec<String> = Vec::new();
+    let mut processed_dirs: HashSet<PathBuf> = HashSet::new();
+    let mut lines = Vec::new();
+
+    for scanned_file in scanned {
+        if !file_has_manifest_changes(&scanned_file.result) {
+            continue;
+        }
+        let Some(dir) = scanned_file.path.parent() else {
+            continue;
+        };
+        if !processed_dirs.insert(dir.to_path_buf()) {
+            continue;
+        }
+        let changed = changed_by_dir.get(dir).unwrap_or(&empty);
+        for lockfile in detect_lockfiles(&scanned_file.path) {
+            let (cmd, args) = lockfile.command(changed, allow_scripts);
+            let command = std::iter::once(cmd)
+                .chain(args.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" ");
+            lines.push(format!(
+                "  {} ({})",
+                dir.join(lockfile.filename()).display(),
+                command.dimmed()
+            ));
+        }
+    }
+
+    if lines.is_empty() {
+        return;
+    }
+    textln!();
+    textln!("{}", "Would regenerate lockfiles:".cyan());
+    for line in lines {
+        textln!("{}", line);
+    }
+}
+
 fn has_checkable_manifest_changes(result: &UpdateResult, filter: UpdateFilter) -> bool {
     // A cooldown-only "Skipped" outcome is expected steady state: we
     // deliberately chose to hold the current version. It must NOT trip
@@ -557,11 +854,15 @@ fn levenshtein(a: &str, b: &str) -> usize {
 /// Suggest the closest known subcommand for a mistyped positional argument,
 /// when one is within a small edit distance (a typo, not an arbitrary word).
 fn suggest_subcommand(input: &str) -> Option<&'static str> {
-    const SUBCOMMANDS: [&str; 6] = [
+    const SUBCOMMANDS: [&str; 10] = [
         "update",
         "align",
+        "outdated",
         "audit",
+        "sbom",
         "clean-cache",
+        "cache-stats",
+        "merge-reports",
         "self-update",
         "schema",
     ];
@@ -574,6 +875,15 @@ fn suggest_subcommand(input: &str) -> Option<&'static str> {
         .map(|(_, cmd)| cmd)
 }
 
+/// The first genuine error of one file's update, which stops a
+/// `--fail-fast` run.
+fn file_error(result: &Result<UpdateResult, String>) -> Option<&str> {
+    match result {
+        Ok(result) => result.first_error(),
+        Err(e) => Some(e),
+    }
+}
+
 fn classify_error(e: &anyhow::Error) -> serde_json::Value {
     let msg = e.to_string();
     let (kind, exit_code) = if msg.contains("No such file")
@@ -633,14 +943,28 @@ async fn run() -> Result<()> {
         std::process::exit(4)
     });
 
-    // Handle no-color flag
-    if cli.no_color {
-        colored::control::set_override(false);
+    // --color / --no-color, with NO_COLOR and FORCE_COLOR under auto
+    cli.color_choice().apply();
+
+    if let Some(level) = cli.log_level {
+        let ansi = cli
+            .color_choice()
+            .color_override(|name| std::env::var(name).ok())
+            .unwrap_or_else(|| std::io::stderr().is_terminal());
+        level.init(ansi);
+    }
+
+    if cli.progress_stderr {
+        upd::output::set_text_stream(upd::output::TextStream::Stderr);
     }
 
     // Schema subcommand: works offline with no config or auth required.
-    if matches!(cli.command, Some(Command::Schema)) {
-        upd::schema::print_schema();
+    if let Some(Command::Schema { report }) = cli.command {
+        if report {
+            upd::schema::print_report_schema();
+        } else {
+            upd::schema::print_schema();
+        }
         return Ok(());
     }
 
@@ -661,8 +985,18 @@ async fn run() -> Result<()> {
         return Ok(());
     }
 
+    if cli.stdin_format.is_some() && !matches!(cli.command, Some(Command::Update { .. }) | None) {
+        anyhow::bail!("--stdin-format only applies to updating dependencies");
+    }
+
     // Reject non-existent paths before any I/O; known subcommands are routed by clap before this point.
-    let invalid: Vec<_> = cli.paths.iter().filter(|p| !p.exists()).collect();
+    // `-` names stdin under --stdin-format.
+    let is_stdin = |p: &PathBuf| cli.stdin_format.is_some() && p.as_os_str() == "-";
+    let invalid: Vec<_> = cli
+        .paths
+        .iter()
+        .filter(|p| !p.exists() && !is_stdin(p))
+        .collect();
     if !invalid.is_empty() {
         for path in &invalid {
             let arg = path.display().to_string();
@@ -678,10 +1012,26 @@ async fn run() -> Result<()> {
         std::process::exit(2);
     }
 
+    // --print-config: show the resolved ignore/pin rules and exit
+    if cli.print_config {
+        return print_effective_config(&cli);
+    }
+
+    // --emit-purls: list declared dependencies as package URLs and exit
+    if cli.emit_purls {
+        return run_emit_purls(&cli);
+    }
+
     match &cli.command {
         Some(Command::CleanCache) => {
             clean_cache()?;
         }
+        Some(Command::CacheStats) => {
+            cache_stats(effective_json_mode(&cli), cli.cache_ttl)?;
+        }
+        Some(Command::MergeReports { paths }) => {
+            merge_reports(paths, effective_json_mode(&cli))?;
+        }
         Some(Command::SelfUpdate) => {
             self_update(&cli).await?;
         }
@@ -691,11 +1041,17 @@ async fn run() -> Result<()> {
         Some(Command::Audit { .. }) => {
             run_audit(&cli).await?;
         }
-        Some(Command::Schema) => {
+        Some(Command::Sbom { .. }) => {
+            run_sbom(&cli)?;
+        }
+        Some(Command::Rollback { .. }) => {
+            run_rollback(&cli)?;
+        }
+        Some(Command::Schema { .. }) => {
             // Already handled above before show_config check.
             unreachable!("Schema handled earlier");
         }
-        Some(Command::Update { .. }) | None => {
+        Some(Command::Update { .. } | Command::Outdated { .. }) | None => {
             run_update(&cli).await?;
         }
     }
@@ -713,8 +1069,17 @@ async fn run() -> Result<()> {
 /// --format sarif overrides everything: SARIF is never treated as plain JSON.
 fn effective_json_mode(cli: &Cli) -> bool {
     use upd::cli::OutputFormat;
-    // SARIF is its own mode; never treat it as JSON.
-    if cli.format == Some(OutputFormat::Sarif) {
+    // SARIF, SBOM and markdown formats are their own modes; never treat them
+    // as JSON.
+    if matches!(
+        cli.format,
+        Some(
+            OutputFormat::Sarif
+                | OutputFormat::Cyclonedx
+                | OutputFormat::Spdx
+                | OutputFormat::Markdown
+        )
+    ) {
         return false;
     }
     match cli.output {
@@ -726,7 +1091,12 @@ fn effective_json_mode(cli: &Cli) -> bool {
         OutputMode::Auto => match cli.format {
             Some(OutputFormat::Json) => true,
             Some(OutputFormat::Text) => false,
-            Some(OutputFormat::Sarif) => false,
+            Some(
+                OutputFormat::Sarif
+                | OutputFormat::Cyclonedx
+                | OutputFormat::Spdx
+                | OutputFormat::Markdown,
+            ) => false,
             None => cli.is_json_output(),
         },
     }
@@ -734,6 +1104,9 @@ fn effective_json_mode(cli: &Cli) -> bool {
 
 async fn run_update(cli: &Cli) -> Result<()> {
     let json_mode = effective_json_mode(cli);
+    let markdown_mode = !json_mode
+        && cli.output != OutputMode::Text
+        && cli.format == Some(upd::cli::OutputFormat::Markdown);
 
     // Reject --interactive with an explicit JSON output request. When output is
     // auto-detected as JSON (stdout piped), the TTY check inside
@@ -744,10 +1117,19 @@ async fn run_update(cli: &Cli) -> Result<()> {
         anyhow::bail!("--interactive cannot be combined with --format json or --output json");
     }
 
-    // Resolve paths: explicit > VCS root > error
-    let paths = match resolve_scan_paths(cli) {
-        Ok(p) => p,
-        Err(msg) => {
+    if cli.update_lockfile_only {
+        return run_lockfile_only(cli);
+    }
+
+    // --stdin-format: the manifest is piped in and the rewrite goes to stdout.
+    let stdin_manifest = read_stdin_manifest(cli)?;
+
+    // Resolve paths: explicit > VCS root > error. A piped manifest takes its
+    // config from the working directory.
+    let paths = match (&stdin_manifest, resolve_scan_paths(cli)) {
+        (Some(_), _) => vec![std::env::current_dir()?],
+        (None, Ok(p)) => p,
+        (None, Err(msg)) => {
             eprintln!(
                 "{}",
                 serde_json::json!({"error": {"kind": "io_error", "message": msg, "exit_code": 2}})
@@ -757,53 +1139,79 @@ async fn run_update(cli: &Cli) -> Result<()> {
     };
 
     // Mutations are opt-in. Without --apply/--yes (and not --interactive,
-    // --check, or --dry-run), the run behaves as dry-run.
-    let effective_dry_run = cli.is_effective_dry_run();
+    // --check, or --dry-run), the run behaves as dry-run. A piped manifest is
+    // never written back.
+    let effective_dry_run = cli.is_effective_dry_run() || stdin_manifest.is_some();
 
     // `exclude` is a discovery-level setting resolved once from the root config;
     // per-file `ignore`/`pin` are loaded separately by `load_update_configs`.
     let root_config = resolve_root_config(cli, &paths)?;
 
-    let files = discover_files_with(
-        &paths,
-        &cli.langs,
-        DiscoverOptions {
-            no_ignore: cli.no_ignore,
-            verbose: cli.verbose,
-            exclude: &root_config.config.exclude,
-        },
-    );
+    let files = match &stdin_manifest {
+        Some(manifest) => vec![(manifest.path().to_path_buf(), manifest.file_type())],
+        // Files pulled in with `-r` are updated (and reported) on their own.
+        None => drop_constraint_files(
+            with_included_requirements(discover_files_with(
+                &paths,
+                &cli.langs,
+                DiscoverOptions {
+                    no_ignore: cli.no_ignore,
+                    include_hidden: cli.include_hidden,
+                    verbose: cli.verbose,
+                    exclude: &root_config.config.exclude,
+                    relative_exclude: &cli.exclude,
+                },
+            )),
+            cli.verbose,
+        ),
+    };
+    let inherited_constraints = inherited_constraint_files(&files);
     let file_count = files.len();
+    prepare_backups(
+        cli,
+        files.iter().map(|(path, _)| path.as_path()),
+        effective_dry_run,
+    );
 
-    let text_mode_early = !json_mode;
+    // Human-readable text; also printed beside JSON or markdown under
+    // --progress-stderr.
+    let text_mode_early = (!json_mode && !markdown_mode) || cli.progress_stderr;
 
     if files.is_empty() {
-        if text_mode_early {
-            if !cli.quiet {
-                println!("{}", "No dependency files found.".yellow());
-            }
-        } else {
-            emit_update_json(
-                UpdateReportInput {
-                    scanned: &[],
-                    total_result: &UpdateResult::default(),
-                    file_count: 0,
-                    dry_run: effective_dry_run,
-                    filter: UpdateFilter::from_cli(&cli.only_bump, cli.max_bump),
-                    file_cooldowns: &HashMap::new(),
-                    cooldown_notes: Vec::new(),
-                },
-                &BoundedOutputParams::from_cli(cli),
-            )?;
+        if text_mode_early && !cli.quiet {
+            textln!("{}", "No dependency files found.".yellow());
+        }
+        let input = UpdateReportInput {
+            scanned: &[],
+            total_result: &UpdateResult::default(),
+            file_count: 0,
+            dry_run: effective_dry_run,
+            filter: UpdateFilter::from_cli(&cli.only_bump, cli.max_bump),
+            file_cooldowns: &HashMap::new(),
+            cooldown_notes: Vec::new(),
+            transitive: &[],
+            changelogs: &[],
+        };
+        if json_mode {
+            emit_update_json(input, &BoundedOutputParams::from_cli(cli))?;
+        } else if markdown_mode {
+            emit_update_markdown(input);
         }
         return Ok(());
     }
 
     // Init TLS only after we know we're going to network. The empty-files
     // early return above must not be killed by a malformed CA bundle env var.
-    init_tls(cli)?;
-
-    let file_configs = load_update_configs(cli, &files)?;
+    init_tls(cli, &root_config.config.allowed_registries)?;
+
+    let mut file_configs = load_update_configs(cli, &files)?;
+    warn_unenforced_allowed_registries(&root_config, &file_configs);
+    if let Some(manifest) = &stdin_manifest {
+        file_configs.insert(
+            manifest.path().to_path_buf(),
+            Some(Arc::clone(&root_config.config)),
+        );
+    }
 
     // Resolve a cooldown policy per file so configs attached to one manifest
     // cannot silently apply to another (e.g. a `.updrc.toml` in a subtree).
@@ -833,14 +1241,25 @@ async fn run_update(cli: &Cli) -> Result<()> {
     // Create filter from CLI flags
     let filter = UpdateFilter::from_cli(&cli.only_bump, cli.max_bump);
 
+    // --max-runtime: registries stop issuing requests once this passes.
+    let deadline = cli
+        .max_runtime
+        .map(|budget| tokio::time::Instant::now() + budget);
+
     // Create shared cache and wrap registries with caching layer
-    let cache = Cache::new_shared();
+    let cache = Arc::new(Mutex::new(
+        Cache::load()
+            .unwrap_or_default()
+            .with_ttl(cli.cache_ttl)
+            .with_negative_ttl(cli.negative_cache_ttl),
+    ));
     let cache_enabled = !cli.no_cache;
 
     // Create PyPI registry with optional credentials and extra index URLs
     let pypi_registry = {
-        let index_url =
-            PyPiRegistry::detect_index_url().unwrap_or_else(|| "https://pypi.org".to_string());
+        let index_url = mapped_registry_url(&cli.registry_map, Lang::Python)
+            .or_else(PyPiRegistry::detect_index_url)
+            .unwrap_or_else(|| "https://pypi.org".to_string());
         let credentials = PyPiRegistry::detect_credentials(&index_url);
         if cli.verbose && credentials.is_some() {
             eprintln!("{}", "Using authenticated PyPI access".cyan());
@@ -856,14 +1275,37 @@ async fn run_update(cli: &Cli) -> Result<()> {
             );
         }
 
-        MultiPyPiRegistry::from_primary_and_extras(primary, extra_urls)
+        let registries = std::iter::once(primary)
+            .chain(extra_urls.iter().map(|url| PyPiRegistry::from_url(url)))
+            .map(|registry| Arc::new(registry.with_api_order(cli.pypi_api)))
+            .collect();
+        MultiPyPiRegistry::new(registries)
     };
 
-    let pypi = CachedRegistry::new(pypi_registry, Arc::clone(&cache), cache_enabled);
+    let pypi = CachedRegistry::new(pypi_registry, Arc::clone(&cache), cache_enabled)
+        .with_offline(cli.offline)
+        .with_deadline(deadline);
+
+    // Pipfiles declaring their own `[[source]]` index resolve from it, one
+    // registry per URL with the same cache, offline and deadline wiring.
+    let pipfile_sources: HashMap<String, CachedRegistry<PyPiRegistry>> = files
+        .iter()
+        .filter(|(_, file_type)| *file_type == FileType::Pipfile)
+        .filter_map(|(path, _)| PipfileUpdater::source_index_url(path))
+        .map(|url| {
+            let registry = PipfileUpdater::source_registry(&url).with_api_order(cli.pypi_api);
+            let registry = CachedRegistry::new(registry, Arc::clone(&cache), cache_enabled)
+                .with_key_prefix(url.clone())
+                .with_offline(cli.offline)
+                .with_deadline(deadline);
+            (url, registry)
+        })
+        .collect();
 
     // Create npm registry with optional credentials
     let npm_registry = {
-        let registry_url = NpmRegistry::detect_registry_url()
+        let registry_url = mapped_registry_url(&cli.registry_map, Lang::Node)
+            .or_else(NpmRegistry::detect_registry_url)
             .unwrap_or_else(|| "https://registry.npmjs.org".to_string());
         let credentials = NpmRegistry::detect_credentials(&registry_url);
         if cli.verbose && credentials.is_some() {
@@ -872,11 +1314,14 @@ async fn run_update(cli: &Cli) -> Result<()> {
         NpmRegistry::with_registry_url_and_credentials(registry_url, credentials)
     };
 
-    let npm = CachedRegistry::new(npm_registry, Arc::clone(&cache), cache_enabled);
+    let npm = CachedRegistry::new(npm_registry, Arc::clone(&cache), cache_enabled)
+        .with_offline(cli.offline)
+        .with_deadline(deadline);
 
     // Create Cargo registry with optional credentials
     let crates_io_registry = {
-        let registry_url = CratesIoRegistry::detect_registry_url()
+        let registry_url = mapped_registry_url(&cli.registry_map, Lang::Rust)
+            .or_else(CratesIoRegistry::detect_registry_url)
             .unwrap_or_else(|| "https://crates.io/api/v1/crates".to_string());
         let credentials = CratesIoRegistry::detect_credentials("crates-io");
         let has_cargo_files = files.iter().any(|(_, ft)| *ft == FileType::CargoToml);
@@ -886,11 +1331,14 @@ async fn run_update(cli: &Cli) -> Result<()> {
         CratesIoRegistry::with_registry_url_and_credentials(registry_url, credentials)
     };
 
-    let crates_io = CachedRegistry::new(crates_io_registry, Arc::clone(&cache), cache_enabled);
+    let crates_io = CachedRegistry::new(crates_io_registry, Arc::clone(&cache), cache_enabled)
+        .with_offline(cli.offline)
+        .with_deadline(deadline);
 
     // Create Go proxy registry with optional credentials
     let go_proxy_registry = {
-        let proxy_url = GoProxyRegistry::detect_proxy_url()
+        let proxy_url = mapped_registry_url(&cli.registry_map, Lang::Go)
+            .or_else(GoProxyRegistry::detect_proxy_url)
             .unwrap_or_else(|| "https://proxy.golang.org".to_string());
         let credentials = GoProxyRegistry::detect_credentials(&proxy_url);
         if cli.verbose && credentials.is_some() {
@@ -899,19 +1347,75 @@ async fn run_update(cli: &Cli) -> Result<()> {
         GoProxyRegistry::with_proxy_url_and_credentials(proxy_url, credentials)
     };
 
-    let go_proxy = CachedRegistry::new(go_proxy_registry, Arc::clone(&cache), cache_enabled);
+    let go_proxy = CachedRegistry::new(go_proxy_registry, Arc::clone(&cache), cache_enabled)
+        .with_offline(cli.offline)
+        .with_deadline(deadline);
 
-    // Create RubyGems registry
-    let rubygems_registry = RubyGemsRegistry::new();
-    let rubygems = CachedRegistry::new(rubygems_registry, Arc::clone(&cache), cache_enabled);
+    // Create RubyGems registry with optional credentials
+    let rubygems_registry = {
+        let api_url = mapped_registry_url(&cli.registry_map, Lang::Ruby)
+            .or_else(RubyGemsRegistry::detect_api_url)
+            .unwrap_or_else(|| "https://rubygems.org".to_string());
+        let credentials = RubyGemsRegistry::detect_credentials(&api_url);
+        if cli.verbose && credentials.is_some() {
+            eprintln!("{}", "Using authenticated RubyGems access".cyan());
+        }
+        RubyGemsRegistry::with_api_url_and_credentials(api_url, credentials)
+    };
+    let rubygems = CachedRegistry::new(rubygems_registry, Arc::clone(&cache), cache_enabled)
+        .with_offline(cli.offline)
+        .with_deadline(deadline);
 
     // Create Terraform registry
     let terraform_registry = TerraformRegistry::new();
-    let terraform = CachedRegistry::new(terraform_registry, Arc::clone(&cache), cache_enabled);
+    let terraform = CachedRegistry::new(terraform_registry, Arc::clone(&cache), cache_enabled)
+        .with_offline(cli.offline)
+        .with_deadline(deadline);
 
     // Create NuGet registry
-    let nuget_registry = NuGetRegistry::new();
-    let nuget = CachedRegistry::new(nuget_registry, Arc::clone(&cache), cache_enabled);
+    let nuget_registry = mapped_registry_url(&cli.registry_map, Lang::DotNet)
+        .map(NuGetRegistry::with_api_url)
+        .unwrap_or_default();
+    let nuget = CachedRegistry::new(nuget_registry, Arc::clone(&cache), cache_enabled)
+        .with_offline(cli.offline)
+        .with_deadline(deadline);
+
+    // Create Docker Hub registry
+    let docker_hub_registry = DockerHubRegistry::new();
+    let docker_hub = CachedRegistry::new(docker_hub_registry, Arc::clone(&cache), cache_enabled)
+        .with_offline(cli.offline)
+        .with_deadline(deadline);
+
+    // Create Packagist registry for composer.json
+    let packagist_registry = mapped_registry_url(&cli.registry_map, Lang::Php)
+        .map(PackagistRegistry::with_api_url)
+        .unwrap_or_default();
+    let packagist = CachedRegistry::new(packagist_registry, Arc::clone(&cache), cache_enabled)
+        .with_offline(cli.offline)
+        .with_deadline(deadline);
+
+    // Create Maven registry for Gradle version catalogs
+    let maven_registry = mapped_registry_url(&cli.registry_map, Lang::Jvm)
+        .map(MavenRegistry::with_repo_url)
+        .unwrap_or_default();
+    let maven = CachedRegistry::new(maven_registry, Arc::clone(&cache), cache_enabled)
+        .with_offline(cli.offline)
+        .with_deadline(deadline);
+
+    // Create pub.dev registry for pubspec.yaml, honoring PUB_HOSTED_URL
+    let pub_registry = mapped_registry_url(&cli.registry_map, Lang::Dart)
+        .or_else(PubRegistry::detect_hosted_url)
+        .map(PubRegistry::with_api_url)
+        .unwrap_or_default();
+    let pub_dev = CachedRegistry::new(pub_registry, Arc::clone(&cache), cache_enabled)
+        .with_offline(cli.offline)
+        .with_deadline(deadline);
+
+    // Create runtime release registry (.nvmrc, .python-version)
+    let runtime_registry = RuntimeRegistry::new();
+    let runtime = CachedRegistry::new(runtime_registry, Arc::clone(&cache), cache_enabled)
+        .with_offline(cli.offline)
+        .with_deadline(deadline);
 
     // Create GitHub releases registry with optional token
     let github_releases_registry = GitHubReleasesRegistry::new();
@@ -919,12 +1423,18 @@ async fn run_update(cli: &Cli) -> Result<()> {
         eprintln!("{}", "Using authenticated GitHub access".cyan());
     }
     let github_releases =
-        CachedRegistry::new(github_releases_registry, Arc::clone(&cache), cache_enabled);
+        CachedRegistry::new(github_releases_registry, Arc::clone(&cache), cache_enabled)
+            .with_offline(cli.offline)
+            .with_deadline(deadline);
 
     // Create updaters wrapped in Arc for parallel processing
     let requirements_updater = Arc::new(RequirementsUpdater::new());
     let pyproject_updater = Arc::new(PyProjectUpdater::new());
+    let pipfile_updater = Arc::new(PipfileUpdater::new());
+    let setup_py_updater = Arc::new(SetupPyUpdater::new());
+    let conda_env_updater = Arc::new(CondaEnvUpdater::new());
     let package_json_updater = Arc::new(PackageJsonUpdater::new());
+    let pnpm_workspace_updater = Arc::new(PnpmWorkspaceUpdater::new());
     let cargo_toml_updater = Arc::new(CargoTomlUpdater::new());
     let go_mod_updater = Arc::new(GoModUpdater::new());
     let github_actions_updater = Arc::new(GithubActionsUpdater::new());
@@ -933,6 +1443,53 @@ async fn run_update(cli: &Cli) -> Result<()> {
     let mise_updater = Arc::new(MiseUpdater::new());
     let terraform_updater = Arc::new(TerraformUpdater::new());
     let csproj_updater = Arc::new(CsprojUpdater::new());
+    let dockerfile_updater = Arc::new(DockerfileUpdater::new());
+    let composer_updater = Arc::new(ComposerUpdater::new());
+    let gradle_catalog_updater = Arc::new(GradleCatalogUpdater::new());
+    let pubspec_updater = Arc::new(PubspecUpdater::new());
+    let runtime_version_updater = Arc::new(RuntimeVersionUpdater::new());
+
+    // UPD_REGISTRY_FIXTURE swaps every registry for a pre-seeded in-memory
+    // map so whole runs can be tested and benchmarked without the network.
+    // Only builds with the `registry-fixture` feature honor it.
+    #[cfg(feature = "registry-fixture")]
+    let fixture = RegistryFixture::from_env()?;
+    #[cfg(feature = "registry-fixture")]
+    let (
+        pypi,
+        npm,
+        crates_io,
+        go_proxy,
+        rubygems,
+        terraform,
+        nuget,
+        docker_hub,
+        packagist,
+        maven,
+        pub_dev,
+        runtime,
+        github_releases,
+    ) = (
+        with_registry_fixture(pypi, fixture.as_ref()),
+        with_registry_fixture(npm, fixture.as_ref()),
+        with_registry_fixture(crates_io, fixture.as_ref()),
+        with_registry_fixture(go_proxy, fixture.as_ref()),
+        with_registry_fixture(rubygems, fixture.as_ref()),
+        with_registry_fixture(terraform, fixture.as_ref()),
+        with_registry_fixture(nuget, fixture.as_ref()),
+        with_registry_fixture(docker_hub, fixture.as_ref()),
+        with_registry_fixture(packagist, fixture.as_ref()),
+        with_registry_fixture(maven, fixture.as_ref()),
+        with_registry_fixture(pub_dev, fixture.as_ref()),
+        with_registry_fixture(runtime, fixture.as_ref()),
+        with_registry_fixture(github_releases, fixture.as_ref()),
+    );
+
+    #[cfg(feature = "registry-fixture")]
+    let pipfile_sources: HashMap<_, _> = pipfile_sources
+        .into_iter()
+        .map(|(url, registry)| (url, with_registry_fixture(registry, fixture.as_ref())))
+        .collect();
 
     // Wrap registries in Arc for parallel processing
     let pypi = Arc::new(pypi);
@@ -942,7 +1499,18 @@ async fn run_update(cli: &Cli) -> Result<()> {
     let rubygems = Arc::new(rubygems);
     let terraform = Arc::new(terraform);
     let nuget = Arc::new(nuget);
+    let docker_hub = Arc::new(docker_hub);
+    let packagist = Arc::new(packagist);
+    let maven = Arc::new(maven);
+    let pub_dev = Arc::new(pub_dev);
+    let runtime = Arc::new(runtime);
     let github_releases = Arc::new(github_releases);
+    let source_registries = SourceRegistries::new(
+        pipfile_sources
+            .into_iter()
+            .map(|(url, registry)| (url, Arc::new(registry) as Arc<dyn Registry>))
+            .collect(),
+    );
 
     // Interactive mode: first discover updates, then prompt, then apply approved ones
     if cli.interactive {
@@ -958,10 +1526,19 @@ async fn run_update(cli: &Cli) -> Result<()> {
             &rubygems,
             &terraform,
             &nuget,
+            &docker_hub,
+            &packagist,
+            &maven,
+            &pub_dev,
+            &runtime,
             &github_releases,
             &requirements_updater,
             &pyproject_updater,
+            &pipfile_updater,
+            &setup_py_updater,
+            &conda_env_updater,
             &package_json_updater,
+            &pnpm_workspace_updater,
             &cargo_toml_updater,
             &go_mod_updater,
             &gemfile_updater,
@@ -970,6 +1547,12 @@ async fn run_update(cli: &Cli) -> Result<()> {
             &mise_updater,
             &terraform_updater,
             &csproj_updater,
+            &dockerfile_updater,
+            &composer_updater,
+            &gradle_catalog_updater,
+            &pubspec_updater,
+            &runtime_version_updater,
+            &source_registries,
             &cache,
             cache_enabled,
             &file_cooldowns,
@@ -979,137 +1562,348 @@ async fn run_update(cli: &Cli) -> Result<()> {
     }
 
     // Non-interactive mode: process files in parallel
-    let dry_run = effective_dry_run;
-    let file_jobs: Vec<_> = files
-        .into_iter()
-        .map(|(path, file_type)| {
-            let config = file_configs.get(&path).cloned().flatten();
-            let cooldown_policy = file_cooldowns.get(&path).and_then(|p| p.as_ref());
-            (
-                path,
-                file_type,
-                build_update_options(
-                    dry_run,
-                    cli.full_precision,
-                    config,
-                    &cli.packages,
-                    cooldown_policy,
-                    Arc::clone(&cooldown_notes),
-                    filter.to_bump_filter(),
-                ),
-            )
-        })
-        .collect();
-
     let verbose = cli.verbose;
 
-    // Process files in parallel with a concurrency limit
-    let concurrency_limit = 8; // Process up to 8 files concurrently
-
-    let results: Vec<(PathBuf, FileType, Result<UpdateResult, String>)> = stream::iter(file_jobs)
-        .map(|(path, file_type, update_options)| {
-            let pypi = Arc::clone(&pypi);
-            let npm = Arc::clone(&npm);
-            let crates_io = Arc::clone(&crates_io);
-            let go_proxy = Arc::clone(&go_proxy);
-            let rubygems = Arc::clone(&rubygems);
-            let terraform = Arc::clone(&terraform);
-            let nuget = Arc::clone(&nuget);
-            let github_releases = Arc::clone(&github_releases);
-            let requirements_updater = Arc::clone(&requirements_updater);
-            let pyproject_updater = Arc::clone(&pyproject_updater);
-            let package_json_updater = Arc::clone(&package_json_updater);
-            let cargo_toml_updater = Arc::clone(&cargo_toml_updater);
-            let go_mod_updater = Arc::clone(&go_mod_updater);
-            let gemfile_updater = Arc::clone(&gemfile_updater);
-            let github_actions_updater = Arc::clone(&github_actions_updater);
-            let pre_commit_updater = Arc::clone(&pre_commit_updater);
-            let mise_updater = Arc::clone(&mise_updater);
-            let csproj_updater = Arc::clone(&csproj_updater);
-            let terraform_updater = Arc::clone(&terraform_updater);
-
-            async move {
-                let result = match file_type {
-                    FileType::Requirements => {
-                        requirements_updater
-                            .update(&path, pypi.as_ref(), update_options.clone())
-                            .await
-                    }
-                    FileType::PyProject => {
-                        pyproject_updater
-                            .update(&path, pypi.as_ref(), update_options.clone())
-                            .await
-                    }
-                    FileType::PackageJson => {
-                        package_json_updater
-                            .update(&path, npm.as_ref(), update_options.clone())
-                            .await
-                    }
-                    FileType::CargoToml => {
-                        cargo_toml_updater
-                            .update(&path, crates_io.as_ref(), update_options.clone())
-                            .await
-                    }
-                    FileType::GoMod => {
-                        go_mod_updater
-                            .update(&path, go_proxy.as_ref(), update_options.clone())
-                            .await
-                    }
-                    FileType::Gemfile => {
-                        gemfile_updater
-                            .update(&path, rubygems.as_ref(), update_options.clone())
-                            .await
-                    }
-                    FileType::GithubActions => {
-                        github_actions_updater
-                            .update(&path, github_releases.as_ref(), update_options.clone())
-                            .await
-                    }
-                    FileType::PreCommitConfig => {
-                        pre_commit_updater
-                            .update(&path, github_releases.as_ref(), update_options.clone())
-                            .await
-                    }
-                    FileType::MiseToml | FileType::ToolVersions => {
-                        mise_updater
-                            .update(&path, github_releases.as_ref(), update_options.clone())
-                            .await
-                    }
-                    FileType::Csproj => {
-                        csproj_updater
-                            .update(&path, nuget.as_ref(), update_options.clone())
-                            .await
-                    }
-                    FileType::TerraformTf => {
-                        terraform_updater
-                            .update(&path, terraform.as_ref(), update_options.clone())
-                            .await
+    // Process up to --max-concurrency files at once; one at a time under
+    // --no-parallel, so results arrive in discovery order.
+    let concurrency_limit = if cli.no_parallel {
+        1
+    } else {
+        cli.max_concurrency
+    };
+
+    let process_files =
+        async |dry_run: bool| -> Vec<(PathBuf, FileType, Result<UpdateResult, String>)> {
+            let file_jobs: Vec<_> = files
+                .iter()
+                .map(|(path, file_type)| {
+                    let config = file_configs.get(path).cloned().flatten();
+                    let cooldown_policy = file_cooldowns.get(path).and_then(|p| p.as_ref());
+                    let bump_filter = filter.to_bump_filter();
+                    (
+                        path.clone(),
+                        *file_type,
+                        build_update_options(
+                            dry_run,
+                            cli.full_precision,
+                            config,
+                            &cli.packages,
+                            cooldown_policy,
+                            Arc::clone(&cooldown_notes),
+                            bump_filter,
+                        )
+                        .with_groups(cli.groups.clone())
+                        .with_strategy(cli.strategy)
+                        .with_allow_prerelease(cli.allow_prerelease.clone())
+                        .with_respect_msrv(cli.respect_msrv)
+                        .with_ignore_engines(cli.ignore_engines)
+                        .with_allow_deprecated(cli.allow_deprecated)
+                        .with_prune_dead_markers(cli.prune_dead_markers)
+                        .with_source_registries(source_registries.clone())
+                        .with_constraint_files(constraint_files_for(&inherited_constraints, path)),
+                    )
+                })
+                .collect();
+
+            let jobs = stream::iter(file_jobs)
+                .map(|(path, file_type, update_options)| {
+                    let pypi = Arc::clone(&pypi);
+                    let npm = Arc::clone(&npm);
+                    let crates_io = Arc::clone(&crates_io);
+                    let go_proxy = Arc::clone(&go_proxy);
+                    let rubygems = Arc::clone(&rubygems);
+                    let terraform = Arc::clone(&terraform);
+                    let nuget = Arc::clone(&nuget);
+                    let docker_hub = Arc::clone(&docker_hub);
+                    let packagist = Arc::clone(&packagist);
+                    let maven = Arc::clone(&maven);
+                    let pub_dev = Arc::clone(&pub_dev);
+                    let runtime = Arc::clone(&runtime);
+                    let github_releases = Arc::clone(&github_releases);
+                    let requirements_updater = Arc::clone(&requirements_updater);
+                    let pyproject_updater = Arc::clone(&pyproject_updater);
+                    let pipfile_updater = Arc::clone(&pipfile_updater);
+                    let setup_py_updater = Arc::clone(&setup_py_updater);
+                    let conda_env_updater = Arc::clone(&conda_env_updater);
+                    let package_json_updater = Arc::clone(&package_json_updater);
+                    let pnpm_workspace_updater = Arc::clone(&pnpm_workspace_updater);
+                    let cargo_toml_updater = Arc::clone(&cargo_toml_updater);
+                    let go_mod_updater = Arc::clone(&go_mod_updater);
+                    let gemfile_updater = Arc::clone(&gemfile_updater);
+                    let github_actions_updater = Arc::clone(&github_actions_updater);
+                    let pre_commit_updater = Arc::clone(&pre_commit_updater);
+                    let mise_updater = Arc::clone(&mise_updater);
+                    let csproj_updater = Arc::clone(&csproj_updater);
+                    let terraform_updater = Arc::clone(&terraform_updater);
+                    let dockerfile_updater = Arc::clone(&dockerfile_updater);
+                    let composer_updater = Arc::clone(&composer_updater);
+                    let gradle_catalog_updater = Arc::clone(&gradle_catalog_updater);
+                    let pubspec_updater = Arc::clone(&pubspec_updater);
+                    let runtime_version_updater = Arc::clone(&runtime_version_updater);
+
+                    let span = tracing::info_span!(
+                        "file",
+                        path = %path.display(),
+                        file_type = ?file_type
+                    );
+
+                    async move {
+                        tracing::debug!("processing");
+                        let result = match file_type {
+                            FileType::Requirements => {
+                                requirements_updater
+                                    .update(&path, pypi.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::PyProject => {
+                                pyproject_updater
+                                    .update(&path, pypi.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::Pipfile => {
+                                pipfile_updater
+                                    .update(&path, pypi.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::SetupPy => {
+                                setup_py_updater
+                                    .update(&path, pypi.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::CondaEnv => {
+                                conda_env_updater
+                                    .update(&path, pypi.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::PackageJson => {
+                                package_json_updater
+                                    .update(&path, npm.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::PnpmWorkspace => {
+                                pnpm_workspace_updater
+                                    .update(&path, npm.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::CargoToml => {
+                                cargo_toml_updater
+                                    .update(&path, crates_io.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::GoMod => {
+                                go_mod_updater
+                                    .update(&path, go_proxy.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::Gemfile => {
+                                gemfile_updater
+                                    .update(&path, rubygems.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::GithubActions => {
+                                github_actions_updater
+                                    .update(&path, github_releases.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::PreCommitConfig => {
+                                pre_commit_updater
+                                    .update(&path, github_releases.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::MiseToml | FileType::ToolVersions => {
+                                mise_updater
+                                    .update(&path, github_releases.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::Csproj => {
+                                csproj_updater
+                                    .update(&path, nuget.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::TerraformTf => {
+                                terraform_updater
+                                    .update(&path, terraform.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::Dockerfile => {
+                                dockerfile_updater
+                                    .update(&path, docker_hub.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::ComposerJson => {
+                                composer_updater
+                                    .update(&path, packagist.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::GradleCatalog => {
+                                gradle_catalog_updater
+                                    .update(&path, maven.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::Pubspec => {
+                                pubspec_updater
+                                    .update(&path, pub_dev.as_ref(), update_options.clone())
+                                    .await
+                            }
+                            FileType::Nvmrc | FileType::PythonVersion => {
+                                runtime_version_updater
+                                    .update(&path, runtime.as_ref(), update_options.clone())
+                                    .await
+                            }
+                        };
+                        match &result {
+                            Ok(r) => tracing::debug!(
+                                updated = r.updated.len(),
+                                unchanged = r.unchanged,
+                                errors = r.errors.len(),
+                                "processed"
+                            ),
+                            Err(e) => tracing::warn!(error = %e, "failed"),
+                        }
+                        (path, file_type, result.map_err(|e| e.to_string()))
                     }
-                };
-                (path, file_type, result.map_err(|e| e.to_string()))
+                    .instrument(span)
+                })
+                .buffer_unordered(concurrency_limit);
+
+            // Under --fail-fast, stop at the first file that failed; dropping
+            // the stream cancels the lookups still in flight.
+            let mut results = Vec::with_capacity(files.len());
+            futures::pin_mut!(jobs);
+            while let Some(job) = jobs.next().await {
+                let failed = cli.fail_fast && file_error(&job.2).is_some();
+                results.push(job);
+                if failed {
+                    break;
+                }
             }
-        })
-        .buffer_unordered(concurrency_limit)
-        .collect()
-        .await;
+            results
+        };
 
-    // Process results, preserving per-file attribution for both text and JSON output.
-    let text_mode = !json_mode;
-    let mut total_result = UpdateResult::default();
-    let mut updated_files: Vec<PathBuf> = Vec::new();
-    let mut scanned: Vec<ScannedFileResult> = Vec::new();
+    // Snapshot manifests and lockfiles before anything is written so a failed
+    // --apply-only-if-tests-pass verification can restore them.
+    let backup = match &cli.apply_only_if_tests_pass {
+        Some(_) if !effective_dry_run => Some(FileBackup::capture(
+            files
+                .iter()
+                .map(|(path, file_type)| (path.as_path(), *file_type)),
+        )),
+        _ => None,
+    };
 
-    for (path, file_type, result) in results {
-        if verbose && text_mode {
-            println!("{}", format!("Processed: {}", path.display()).cyan());
+    // A config `auto_apply` policy also previews first: updates its policy
+    // excludes are reported as available but not applied.
+    let auto_apply_active = !effective_dry_run
+        && file_configs
+            .values()
+            .flatten()
+            .any(|config| !config.auto_apply.is_empty());
+    // --newest-only previews too, then writes only the top N of the plan.
+    let preview = effective_dry_run
+        || cli.min_update_count.is_some()
+        || cli.newest_only.is_some()
+        || auto_apply_active;
+
+    // With --min-update-count the plan is always computed without writing
+    // first; it is only applied when it clears the threshold. Below the
+    // threshold the run degrades to a dry-run report that exits 0.
+    let mut dry_run = effective_dry_run;
+    let mut below_min_update_count = None;
+    let mut results = process_files(preview).await;
+    // A --fail-fast preview that stopped on an error is not applied; it is
+    // reported as the dry run it was.
+    if preview && cli.fail_fast && results.iter().any(|(_, _, r)| file_error(r).is_some()) {
+        dry_run = true;
+    }
+    if let Some(min) = cli.min_update_count {
+        let available: usize = results
+            .iter()
+            .filter_map(|(_, _, r)| r.as_ref().ok())
+            .map(|r| count_updates_by_type(&r.updated, filter).3)
+            .sum();
+        if available < min {
+            dry_run = true;
+            below_min_update_count = Some((available, min));
+        }
+    }
+    if preview {
+        if !dry_run && auto_apply_active {
+            hold_back_by_auto_apply(&mut results, &file_configs, filter);
+        }
+        if let Some(limit) = cli.newest_only {
+            defer_beyond_limit(&mut results, limit);
+        }
+        apply_previewed_updates(&mut results, cli.full_precision, dry_run);
+    }
+    let stopped_at = cli
+        .fail_fast
+        .then(|| {
+            results
+                .iter()
+                .find_map(|(path, _, result)| file_error(result).map(|_| path.clone()))
+        })
+        .flatten();
+
+    if let Some(Command::Outdated { major, .. }) = &cli.command {
+        let exit_code = report_outdated(&results, *major, json_mode, |lang| match lang {
+            Lang::Python => pypi.as_ref() as &dyn Registry,
+            Lang::Node => npm.as_ref(),
+            Lang::Rust => crates_io.as_ref(),
+            Lang::Go => go_proxy.as_ref(),
+            Lang::Ruby => rubygems.as_ref(),
+            Lang::DotNet => nuget.as_ref(),
+            Lang::Terraform => terraform.as_ref(),
+            Lang::Docker => docker_hub.as_ref(),
+            Lang::Php => packagist.as_ref(),
+            Lang::Jvm => maven.as_ref(),
+            Lang::Dart => pub_dev.as_ref(),
+            Lang::Runtime => runtime.as_ref(),
+            Lang::Actions | Lang::PreCommit | Lang::Mise => github_releases.as_ref(),
+        })
+        .await?;
+        if cache_enabled {
+            let _ = Cache::save_shared(&cache);
+        }
+        if exit_code != 0 {
+            std::process::exit(exit_code);
+        }
+        return Ok(());
+    }
+
+    if let Some(manifest) = stdin_manifest {
+        if cache_enabled {
+            let _ = Cache::save_shared(&cache);
+        }
+        let exit_code = finish_stdin_update(&manifest, results, &cooldown_notes)?;
+        // Remove the scratch copy before exiting.
+        drop(manifest);
+        if exit_code != 0 {
+            std::process::exit(exit_code);
+        }
+        return Ok(());
+    }
+
+    // Process results, preserving per-file attribution for both text and JSON output.
+    let text_mode = text_mode_early;
+    let mut total_result = UpdateResult::default();
+    let mut updated_files: Vec<PathBuf> = Vec::new();
+    let mut scanned: Vec<ScannedFileResult> = Vec::new();
+
+    for (path, file_type, result) in results {
+        if verbose && text_mode {
+            textln!("{}", format!("Processed: {}", path.display()).cyan());
         }
 
         match result {
-            Ok(file_result) => {
+            Ok(mut file_result) => {
+                file_result.separate_offline_misses();
+                file_result.separate_deadline_misses();
                 if !dry_run && file_has_manifest_changes(&file_result) {
                     updated_files.push(path.clone());
                 }
-                if text_mode && !cli.quiet {
+                if text_mode && !cli.quiet && cli.diff {
+                    print_file_diff(&path.display().to_string(), &file_result);
+                } else if text_mode && !cli.quiet {
                     let cooldown_policy = file_cooldowns.get(&path).and_then(|p| p.as_ref());
                     print_file_result(
                         &path.display().to_string(),
@@ -1119,7 +1913,10 @@ async fn run_update(cli: &Cli) -> Result<()> {
                         filter,
                         verbose,
                         cooldown_policy,
+                        cli.link_types,
                     );
+                } else if text_mode {
+                    print_file_errors(&path.display().to_string(), &file_result);
                 }
                 scanned.push(ScannedFileResult {
                     path: path.clone(),
@@ -1148,34 +1945,54 @@ async fn run_update(cli: &Cli) -> Result<()> {
         }
     }
 
-    // Regenerate lockfiles if requested and at least one manifest changed.
-    if cli.lock && !dry_run && !updated_files.is_empty() {
-        // Group changed package names by the directory of their manifest file.
-        // Each directory gets its own targeted-command invocation so we never
-        // pull in transitive churn from sibling subprojects. Both registry
-        // updates and config pins modify the manifest, so both contribute —
-        // otherwise pin-only changes would silently degrade to a broad refresh.
-        let mut changed_by_dir: HashMap<PathBuf, Vec<String>> = HashMap::new();
-        for scanned_file in &scanned {
-            if scanned_file.result.updated.is_empty() && scanned_file.result.pinned.is_empty() {
-                continue;
-            }
-            let Some(dir) = scanned_file.path.parent() else {
-                continue;
-            };
-            let entry = changed_by_dir.entry(dir.to_path_buf()).or_default();
-            for (name, _, _, _) in scanned_file
-                .result
+    if cli.treat_unconstrained_as_error {
+        report_unconstrained_dependencies(&mut scanned, &mut total_result, text_mode);
+    }
+    report_duplicate_declarations(&mut scanned, &mut total_result, text_mode);
+    report_include_conflicts(&mut scanned, &mut total_result, text_mode);
+
+    // Transitive dependencies are reported from lockfiles, never rewritten.
+    let transitive = if cli.include_transitive {
+        let manifests: Vec<(PathBuf, FileType)> = scanned
+            .iter()
+            .map(|file| (file.path.clone(), file.file_type))
+            .collect();
+        let report = find_outdated_transitive(&manifests, |lang| match lang {
+            Lang::Python => Some(pypi.as_ref() as &dyn Registry),
+            Lang::Node => Some(npm.as_ref() as &dyn Registry),
+            Lang::Rust => Some(crates_io.as_ref() as &dyn Registry),
+            _ => None,
+        })
+        .await;
+        for failure in &report.failures {
+            eprintln!("warning: transitive check failed for {failure}");
+        }
+        report.outdated
+    } else {
+        Vec::new()
+    };
+
+    let changelogs = if cli.changelog {
+        let updates = scanned.iter().flat_map(|file| {
+            file.result
                 .updated
                 .iter()
-                .chain(scanned_file.result.pinned.iter())
-            {
-                if !entry.iter().any(|n| n == name) {
-                    entry.push(name.clone());
-                }
-            }
-        }
+                .map(|(package, _, _, _)| (file.file_type, package.clone()))
+        });
+        upd::changelog::collect_links(updates, |lang| match lang {
+            Lang::Python => Some(pypi.as_ref() as &dyn Registry),
+            Lang::Node => Some(npm.as_ref() as &dyn Registry),
+            Lang::Rust => Some(crates_io.as_ref() as &dyn Registry),
+            _ => None,
+        })
+        .await
+    } else {
+        Vec::new()
+    };
 
+    // Regenerate lockfiles if requested and at least one manifest changed.
+    if cli.lock && !dry_run && !updated_files.is_empty() {
+        let changed_by_dir = changed_packages_by_dir(&scanned);
         let empty: Vec<String> = Vec::new();
         let mut processed_dirs: HashSet<PathBuf> = HashSet::new();
         let mut regen_results: Vec<(PathBuf, LockfileRegenResult)> = Vec::new();
@@ -1185,7 +2002,12 @@ async fn run_update(cli: &Cli) -> Result<()> {
                 let dir_path = dir.to_path_buf();
                 if processed_dirs.insert(dir_path.clone()) {
                     let changed = changed_by_dir.get(&dir_path).unwrap_or(&empty);
-                    let result = regenerate_lockfiles(path, changed, verbose && text_mode);
+                    let result = regenerate_lockfiles(
+                        path,
+                        changed,
+                        cli.allow_scripts,
+                        verbose && text_mode,
+                    );
                     regen_results.push((path.clone(), result));
                 }
             }
@@ -1196,20 +2018,22 @@ async fn run_update(cli: &Cli) -> Result<()> {
         let has_work = regen_results.iter().any(|(_, r)| !r.no_lockfiles);
 
         if text_mode && has_work && !cli.quiet {
-            println!();
-            println!("{}", "Regenerating lockfiles...".cyan());
+            textln!();
+            textln!("{}", "Regenerating lockfiles...".cyan());
         }
 
         for (path, result) in regen_results {
             if result.no_lockfiles {
-                let manifest_name = path
-                    .file_name()
-                    .map(|n| n.to_string_lossy().into_owned())
-                    .unwrap_or_default();
-                eprintln!(
-                    "note: no lockfile found for {} — skipping (nothing to regenerate)",
-                    manifest_name
-                );
+                if !cli.quiet {
+                    let manifest_name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    eprintln!(
+                        "note: no lockfile found for {} — skipping (nothing to regenerate)",
+                        manifest_name
+                    );
+                }
                 continue;
             }
             for outcome in result.outcomes {
@@ -1221,57 +2045,151 @@ async fn run_update(cli: &Cli) -> Result<()> {
         }
     }
 
+    // A dry run only says which lockfiles a real run would regenerate.
+    if cli.lock && dry_run && text_mode && !cli.quiet {
+        print_would_regenerate_lockfiles(&scanned, cli.allow_scripts);
+    }
+
+    if let (Some(command), Some(backup)) = (&cli.apply_only_if_tests_pass, &backup)
+        && !dry_run
+        && !updated_files.is_empty()
+    {
+        verify_changed_dirs(
+            command,
+            backup,
+            &updated_files,
+            &mut scanned,
+            &mut total_result,
+            text_mode && !cli.quiet,
+        );
+    }
+
+    if let Some(decisions_path) = &cli.decisions_file {
+        write_decisions_file(decisions_path, &scanned, &cli.packages, filter, dry_run)?;
+    }
+
+    if let Some(summary_path) = &cli.summary_json {
+        write_summary_json(summary_path, &scanned, filter, dry_run)?;
+    }
+
     // Save cache to disk
     if cache_enabled {
         let _ = Cache::save_shared(&cache);
     }
 
     // Emit cooldown unavailability notes (deduplicated across all files).
-    if let Ok(notes) = cooldown_notes.lock() {
+    if let Ok(notes) = cooldown_notes.lock()
+        && !cli.quiet
+    {
         for note in notes.iter() {
             eprintln!("note: {}", note);
         }
     }
 
-    if text_mode {
-        if !cli.quiet {
-            println!();
-            let applied = print_summary(&total_result, file_count, dry_run, filter);
-            // Print the revert tip after a mutating run that applied at least one update.
-            if !dry_run && applied > 0 {
-                println!("{}", REVERT_TIP);
-            }
-            let implicit_dry_run = effective_dry_run && !cli.check && !cli.dry_run;
-            if implicit_dry_run && applied > 0 {
-                println!(
-                    "{}",
-                    "Run with --apply to write changes, or --interactive to approve individually."
-                        .yellow()
-                );
-            }
+    if text_mode && cli.quiet {
+        let changed_files = scanned
+            .iter()
+            .filter(|scanned_file| file_has_manifest_changes(&scanned_file.result))
+            .count();
+        print_quiet_summary(&total_result, file_count, changed_files, dry_run, filter);
+    }
+    if text_mode && !cli.quiet {
+        print_outdated_transitive(&transitive);
+        print_changelog_links(&changelogs);
+        textln!();
+        let applied = print_summary(&total_result, file_count, dry_run, filter);
+        if let Some((available, min)) = below_min_update_count {
+            textln!(
+                "{}",
+                format!(
+                    "{} update(s) available, below --min-update-count {}; nothing written.",
+                    available, min
+                )
+                .yellow()
+            );
         }
-    } else {
+        if let Some(path) = &stopped_at {
+            textln!(
+                "{}",
+                format!(
+                    "Stopped at the first error, in {} (--fail-fast); remaining files were not checked.",
+                    path.display()
+                )
+                .yellow()
+            );
+        }
+        // Print the revert tip after a mutating run that applied at least one update.
+        if !dry_run && applied > 0 {
+            textln!("{}", REVERT_TIP);
+        }
+        let implicit_dry_run = effective_dry_run && !cli.check && !cli.dry_run;
+        if implicit_dry_run && applied > 0 && below_min_update_count.is_none() {
+            textln!(
+                "{}",
+                "Run with --apply to write changes, or --interactive to approve individually."
+                    .yellow()
+            );
+        }
+    }
+    if json_mode || markdown_mode {
         let notes_vec: Vec<String> = cooldown_notes
             .lock()
             .map(|g| g.iter().cloned().collect())
             .unwrap_or_default();
-        emit_update_json(
-            UpdateReportInput {
-                scanned: &scanned,
-                total_result: &total_result,
-                file_count,
-                dry_run,
-                filter,
-                file_cooldowns: &file_cooldowns,
-                cooldown_notes: notes_vec,
-            },
-            &BoundedOutputParams::from_cli(cli),
-        )?;
+        let input = UpdateReportInput {
+            scanned: &scanned,
+            total_result: &total_result,
+            file_count,
+            dry_run,
+            filter,
+            file_cooldowns: &file_cooldowns,
+            cooldown_notes: notes_vec,
+            transitive: &transitive,
+            changelogs: &changelogs,
+        };
+        if json_mode {
+            emit_update_json(input, &BoundedOutputParams::from_cli(cli))?;
+        } else {
+            emit_update_markdown(input);
+        }
     }
 
     let has_errors = !total_result.errors.is_empty();
-    let has_pending = has_checkable_manifest_changes(&total_result, filter);
-    let exit_code = upd::decide_exit_code(dry_run, has_pending, has_errors);
+    // Falling short of --min-update-count is a deliberate no-op, not a
+    // pending-updates signal.
+    let has_pending =
+        below_min_update_count.is_none() && has_checkable_manifest_changes(&total_result, filter);
+    let exit_code = match cli.error_on {
+        Some(error_on) => {
+            let (major, minor, patch, _) = count_updates_by_type(&total_result.updated, filter);
+            let exit_code = upd::decide_error_on_exit_code(
+                error_on,
+                (major, minor, patch),
+                has_pending,
+                has_errors,
+            );
+            if exit_code == 1 && text_mode && !cli.quiet {
+                textln!(
+                    "{}",
+                    format!(
+                        "Failing on --error-on {}: {} major, {} minor, {} patch update(s) found.",
+                        format!("{error_on:?}").to_lowercase(),
+                        major,
+                        minor,
+                        patch
+                    )
+                    .yellow()
+                );
+            }
+            exit_code
+        }
+        None => upd::decide_exit_code(dry_run, has_pending, has_errors),
+    };
+    let exit_code = if cli.error_on == Some(ErrorOn::None) {
+        exit_code
+    } else {
+        upd::with_timed_out_exit_code(exit_code, !total_result.timed_out.is_empty())
+    };
     if exit_code != 0 {
         std::process::exit(exit_code);
     }
@@ -1279,6 +2197,700 @@ async fn run_update(cli: &Cli) -> Result<()> {
     Ok(())
 }
 
+/// Narrow `filter` to the bump levels a config's `auto_apply` policy writes.
+fn auto_apply_bump_filter(filter: BumpFilter, config: &UpdConfig) -> BumpFilter {
+    if config.auto_apply.is_empty() {
+        return filter;
+    }
+    BumpFilter {
+        major: filter.major && config.auto_apply.contains(&BumpLevel::Major),
+        minor: filter.minor && config.auto_apply.contains(&BumpLevel::Minor),
+        patch: filter.patch && config.auto_apply.contains(&BumpLevel::Patch),
+    }
+}
+
+/// Move each previewed update the file's `auto_apply` policy excludes into
+/// `not_applied`, leaving the rest to be written.
+fn hold_back_by_auto_apply(
+    results: &mut [(PathBuf, FileType, Result<UpdateResult, String>)],
+    file_configs: &HashMap<PathBuf, Option<Arc<UpdConfig>>>,
+    filter: UpdateFilter,
+) {
+    for (path, _, result) in results.iter_mut() {
+        let (Ok(result), Some(Some(config))) = (result, file_configs.get(path)) else {
+            continue;
+        };
+        let bump_filter = auto_apply_bump_filter(filter.to_bump_filter(), config);
+        let (kept, not_applied) = std::mem::take(&mut result.updated)
+            .into_iter()
+            .partition(|(_, old, new, _)| bump_filter.allows(old, new));
+        result.updated = kept;
+        result.not_applied.extend(not_applied);
+    }
+}
+
+/// Keep the `limit` most impactful previewed updates across all files and
+/// move the rest into `deferred`. Majors rank before minors before patches;
+/// within a level, discovery order is kept.
+fn defer_beyond_limit(
+    results: &mut [(PathBuf, FileType, Result<UpdateResult, String>)],
+    limit: usize,
+) {
+    let mut ranked: Vec<(UpdateType, usize, usize)> = results
+        .iter()
+        .enumerate()
+        .filter_map(|(file_idx, (_, _, result))| Some((file_idx, result.as_ref().ok()?)))
+        .flat_map(|(file_idx, result)| {
+            result
+                .updated
+                .iter()
+                .enumerate()
+                .map(move |(idx, (_, old, new, _))| (classify_update(old, new), file_idx, idx))
+        })
+        .collect();
+    ranked.sort_by_key(|(update_type, _, _)| *update_type);
+    let kept: HashSet<(usize, usize)> = ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, file_idx, idx)| (file_idx, idx))
+        .collect();
+
+    for (file_idx, (_, _, result)) in results.iter_mut().enumerate() {
+        let Ok(result) = result else {
+            continue;
+        };
+        let (updated, deferred): (Vec<_>, Vec<_>) = std::mem::take(&mut result.updated)
+            .into_iter()
+            .enumerate()
+            .partition(|(idx, _)| kept.contains(&(file_idx, *idx)));
+        result.updated = updated.into_iter().map(|(_, update)| update).collect();
+        result.deferred = deferred.into_iter().map(|(_, update)| update).collect();
+    }
+}
+
+/// Write the previewed plan of each file unless `dry_run`.
+///
+/// A plan left whole is written exactly as previewed. One narrowed by
+/// `auto_apply` or `--newest-only` is rewritten with the updates and pins
+/// left in its result, the way the interactive mode writes the updates picked
+/// at its prompt; that rewrite replaces the previewed one for `--diff`.
+fn apply_previewed_updates(
+    results: &mut [(PathBuf, FileType, Result<UpdateResult, String>)],
+    full_precision: bool,
+    dry_run: bool,
+) {
+    for (path, file_type, result) in results.iter_mut() {
+        let Ok(result) = result else {
+            continue;
+        };
+        if result.not_applied.is_empty() && result.deferred.is_empty() {
+            if let Some(change) = result.content_change.as_ref().filter(|_| !dry_run)
+                && change.updated != change.original
+                && let Err(e) = write_file_atomic(path, &change.updated)
+            {
+                result
+                    .errors
+                    .push(format!("Failed to write {}: {}", path.display(), e));
+            }
+            continue;
+        }
+        result.content_change = None;
+        if !file_has_manifest_changes(result) {
+            continue;
+        }
+        let mut changes: Vec<PlannedChange> = result
+            .updated
+            .iter()
+            .map(|update| PlannedChange::from_update(path.clone(), *file_type, update))
+            .collect();
+        changes.extend(take_pinned_changes_for_file(
+            path,
+            *file_type,
+            &result.pinned,
+        ));
+
+        let written = read_file_safe(path).and_then(|original| {
+            let updated =
+                rewrite_selected_changes(&original, &changes, *file_type, full_precision)?;
+            if !dry_run && updated != original {
+                write_file_atomic(path, &updated)?;
+            }
+            result.content_change = Some(ContentChange { original, updated });
+            Ok(())
+        });
+        if let Err(e) = written {
+            result
+                .errors
+                .push(format!("Failed to rewrite {}: {}", path.display(), e));
+        }
+    }
+}
+
+/// Read the `--stdin-format` manifest, or `None` when the run scans files.
+fn read_stdin_manifest(cli: &Cli) -> Result<Option<StdinManifest>> {
+    let Some(file_type) = cli.stdin_format else {
+        return Ok(None);
+    };
+    if cli.get_paths() != [PathBuf::from("-")] {
+        anyhow::bail!("--stdin-format reads the manifest from stdin; pass `-` as the only path");
+    }
+    StdinManifest::read(file_type)
+        .map(Some)
+        .context("failed to read the manifest from stdin")
+}
+
+/// Write the updated `--stdin-format` manifest to stdout (unchanged when
+/// nothing was updated) and its notes to stderr. Returns the exit code: 2 on
+/// errors, 7 when `--max-runtime` cut lookups short, else 0.
+fn finish_stdin_update(
+    manifest: &StdinManifest,
+    results: Vec<(PathBuf, FileType, Result<UpdateResult, String>)>,
+    cooldown_notes: &Mutex<BTreeSet<String>>,
+) -> Result<i32> {
+    let mut result = match results.into_iter().next() {
+        Some((_, _, Ok(result))) => result,
+        Some((_, _, Err(e))) => anyhow::bail!("Error processing <stdin>: {e}"),
+        None => UpdateResult::default(),
+    };
+    result.separate_offline_misses();
+    result.separate_deadline_misses();
+
+    let updated = result
+        .content_change
+        .as_ref()
+        .map_or(manifest.content(), |change| change.updated.as_str());
+    print!("{updated}");
+    std::io::Write::flush(&mut std::io::stdout())?;
+
+    print_file_notes("<stdin>", &result);
+    if let Ok(notes) = cooldown_notes.lock() {
+        for note in notes.iter() {
+            eprintln!("note: {}", note);
+        }
+    }
+
+    let exit_code = upd::decide_exit_code(false, false, !result.errors.is_empty());
+    Ok(upd::with_timed_out_exit_code(
+        exit_code,
+        !result.timed_out.is_empty(),
+    ))
+}
+
+/// Print the `--include-transitive` findings, one line per locked package.
+fn print_outdated_transitive(transitive: &[OutdatedTransitive]) {
+    if transitive.is_empty() {
+        return;
+    }
+    textln!();
+    textln!(
+        "{}",
+        "Outdated transitive dependencies (reported only, not rewritten):".cyan()
+    );
+    for entry in transitive {
+        textln!(
+            "  {}: {} {} → {}",
+            entry.lockfile.display(),
+            entry.package,
+            entry.current.red(),
+            entry.latest.green()
+        );
+    }
+}
+
+/// Print the `--changelog` links, one line per updated package.
+fn print_changelog_links(changelogs: &[ChangelogLink]) {
+    if changelogs.is_empty() {
+        return;
+    }
+    textln!();
+    textln!("{}", "Changelogs:".cyan());
+    for link in changelogs {
+        textln!("  {}: {}", link.package, link.url);
+    }
+}
+
+/// `upd outdated`: list every dependency with a newer version and how many
+/// majors it is behind, looked up from each package's released versions.
+/// Returns the exit code: 2 on errors, 1 when anything is listed, else 0.
+async fn report_outdated<'a>(
+    results: &[(PathBuf, FileType, Result<UpdateResult, String>)],
+    major_only: bool,
+    json_mode: bool,
+    registry_for: impl Fn(Lang) -> &'a dyn Registry,
+) -> Result<i32> {
+    let mut errors = Vec::new();
+    let mut candidates = Vec::new();
+    for (path, file_type, result) in results {
+        match result {
+            Ok(result) => {
+                errors.extend(result.errors.iter().cloned());
+                for (package, current, latest, _) in &result.updated {
+                    candidates.push((path, *file_type, package, current, latest));
+                }
+            }
+            Err(e) => errors.push(format!("Error processing {}: {}", path.display(), e)),
+        }
+    }
+
+    let version_lists = join_all(candidates.iter().map(|(_, file_type, package, _, _)| {
+        registry_for(file_type.lang()).list_versions(package)
+    }))
+    .await;
+    let packages: Vec<OutdatedPackage> = candidates
+        .into_iter()
+        .zip(version_lists)
+        .map(|((path, file_type, package, current, latest), versions)| {
+            let versions = versions.unwrap_or_default();
+            OutdatedPackage {
+                file: path.display().to_string(),
+                package: package.clone(),
+                lang: file_type.lang().as_str().to_string(),
+                current: current.clone(),
+                latest: latest.clone(),
+                bump: match classify_update(current, latest) {
+                    UpdateType::Major => "major",
+                    UpdateType::Minor => "minor",
+                    UpdateType::Patch => "patch",
+                }
+                .to_string(),
+                majors_behind: majors_behind(current, latest, &versions),
+            }
+        })
+        .filter(|package| !major_only || package.majors_behind > 0)
+        .collect();
+    let behind_a_major = packages.iter().filter(|p| p.majors_behind > 0).count();
+
+    for error in &errors {
+        eprintln!("{}", error.red());
+    }
+    if json_mode {
+        let report = serde_json::json!({
+            "command": "outdated",
+            "packages": packages,
+            "summary": {
+                "outdated": packages.len(),
+                "behind_a_major": behind_a_major,
+                "errors": errors.len(),
+            },
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if packages.is_empty() {
+        let msg = if major_only {
+            "no dependencies are behind by a major version"
+        } else {
+            "all dependencies up to date"
+        };
+        textln!("{} {}", "✓".green(), msg);
+    } else {
+        let mut current_file = None;
+        for package in &packages {
+            if current_file != Some(&package.file) {
+                textln!("{}", package.file.blue().underline());
+                current_file = Some(&package.file);
+            }
+            let gap = package
+                .gap_label()
+                .map(|label| format!(" ({})", label.yellow()))
+                .unwrap_or_default();
+            textln!(
+                "  {} {} \u{2192} {}{}",
+                package.package,
+                package.current,
+                package.latest.green(),
+                gap
+            );
+        }
+        textln!();
+        textln!(
+            "{} outdated package(s), {} behind by a major version",
+            packages.len().to_string().bold(),
+            behind_a_major
+        );
+    }
+
+    Ok(if !errors.is_empty() {
+        2
+    } else if packages.is_empty() {
+        0
+    } else {
+        1
+    })
+}
+
+/// Resolve `registry` from the `UPD_REGISTRY_FIXTURE` map when one is loaded.
+#[cfg(feature = "registry-fixture")]
+fn with_registry_fixture<R: Registry>(
+    registry: CachedRegistry<R>,
+    fixture: Option<&RegistryFixture>,
+) -> CachedRegistry<R> {
+    match fixture {
+        Some(fixture) => {
+            let name = registry.name();
+            registry.with_fixture(fixture.for_registry(name))
+        }
+        None => registry,
+    }
+}
+
+/// Drop requirements files that another discovered file references with
+/// `-c`: their entries are ceilings for the referencing file, not
+/// dependencies to bump.
+fn drop_constraint_files(
+    files: Vec<(PathBuf, FileType)>,
+    verbose: bool,
+) -> Vec<(PathBuf, FileType)> {
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let constraint_files: HashSet<PathBuf> = files
+        .iter()
+        .filter(|(_, file_type)| *file_type == FileType::Requirements)
+        .flat_map(|(path, _)| RequirementsUpdater::constraint_files(path))
+        .map(|path| canonical(&path))
+        .collect();
+    files
+        .into_iter()
+        .filter(|(path, _)| {
+            let is_constraints = constraint_files.contains(&canonical(path));
+            if is_constraints && verbose {
+                eprintln!(
+                    "skipping {}: constraints file (-c), read-only",
+                    path.display()
+                );
+            }
+            !is_constraints
+        })
+        .collect()
+}
+
+/// Constraints files each discovered requirements file inherits from the
+/// requirements files that include it with `-r`.
+fn inherited_constraint_files(files: &[(PathBuf, FileType)]) -> HashMap<PathBuf, Vec<PathBuf>> {
+    let roots: Vec<PathBuf> = files
+        .iter()
+        .filter(|(_, file_type)| *file_type == FileType::Requirements)
+        .map(|(path, _)| path.clone())
+        .collect();
+    RequirementsUpdater::inherited_constraint_files(&roots)
+}
+
+fn constraint_files_for(inherited: &HashMap<PathBuf, Vec<PathBuf>>, path: &Path) -> Vec<PathBuf> {
+    let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    inherited.get(&key).cloned().unwrap_or_default()
+}
+
+/// Write the `--decisions-file` record: one entry per dependency discovered in
+/// each scanned file, with its final decision and the reason for it.
+///
+/// Files are re-parsed here so dependencies the updater left untouched are
+/// listed too; a file that no longer parses contributes only the entries its
+/// result reported.
+fn write_decisions_file(
+    path: &Path,
+    scanned: &[ScannedFileResult],
+    packages: &[String],
+    filter: UpdateFilter,
+    dry_run: bool,
+) -> Result<()> {
+    let ctx = DecisionContext {
+        packages,
+        bump_filter_active: !(filter.major && filter.minor && filter.patch),
+    };
+    let decisions = scanned
+        .iter()
+        .flat_map(|file| {
+            let deps =
+                upd::align::parse_file_dependencies(&file.path, file.file_type).unwrap_or_default();
+            build_file_decisions(&file.path, &file.result, &deps, ctx)
+        })
+        .collect();
+    let record = DecisionsRecord {
+        schema_version: DECISIONS_SCHEMA_VERSION,
+        command: "update",
+        mode: if dry_run { "dry-run" } else { "apply" },
+        decisions,
+    };
+    let json = serde_json::to_string_pretty(&record)?;
+    std::fs::write(path, json + "\n")
+        .with_context(|| format!("failed to write decisions file {}", path.display()))
+}
+
+/// Tally one file's result into `--summary-json` counts.
+fn add_summary_counts(counts: &mut SummaryCounts, result: &UpdateResult, filter: UpdateFilter) {
+    let (major, minor, patch, total) = count_updates_by_type(&result.updated, filter);
+    counts.updated += total;
+    counts.major += major;
+    counts.minor += minor;
+    counts.patch += patch;
+    counts.pinned += result.pinned.len();
+    counts.ignored += result.ignored.len();
+    counts.errors += result.errors.len();
+}
+
+/// Build the `--summary-json` record from the per-file results.
+fn build_summary_json(
+    scanned: &[ScannedFileResult],
+    filter: UpdateFilter,
+    dry_run: bool,
+) -> SummaryJson {
+    let mut total = SummaryCounts::default();
+    let mut ecosystems: BTreeMap<&'static str, SummaryCounts> = BTreeMap::new();
+    for file in scanned {
+        add_summary_counts(&mut total, &file.result, filter);
+        add_summary_counts(
+            ecosystems
+                .entry(file.file_type.lang().as_str())
+                .or_default(),
+            &file.result,
+            filter,
+        );
+    }
+    SummaryJson {
+        command: "update",
+        mode: if dry_run { "dry-run" } else { "applied" },
+        total,
+        ecosystems,
+    }
+}
+
+/// Write the `--summary-json` counts file. Written in both dry-run and apply
+/// mode, independent of `--format`.
+fn write_summary_json(
+    path: &Path,
+    scanned: &[ScannedFileResult],
+    filter: UpdateFilter,
+    dry_run: bool,
+) -> Result<()> {
+    let json = serde_json::to_string_pretty(&build_summary_json(scanned, filter, dry_run))?;
+    std::fs::write(path, json + "\n")
+        .with_context(|| format!("failed to write summary file {}", path.display()))
+}
+
+/// Run the `--apply-only-if-tests-pass` command in each changed directory and
+/// restore that directory's verification unit from `backup` when it fails.
+///
+/// Reverted updates are removed from both the per-file and aggregate results
+/// and replaced by an error naming the command failure and the updates that
+/// were rolled back, so the summary and exit code reflect what is on disk.
+fn verify_changed_dirs(
+    command: &str,
+    backup: &FileBackup,
+    updated_files: &[PathBuf],
+    scanned: &mut [ScannedFileResult],
+    total_result: &mut UpdateResult,
+    show_progress: bool,
+) {
+    // A changed `-r` include is verified from every directory that reaches it.
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for path in updated_files {
+        for dir in backup.units_of(path) {
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+
+    if show_progress {
+        textln!();
+        textln!(
+            "{}",
+            format!("Verifying changes with `{command}`...").cyan()
+        );
+    }
+
+    for dir in dirs {
+        let failure = match upd::verify::run_verification(command, &dir) {
+            Ok(()) => {
+                if show_progress {
+                    textln!("{} {}", "✓".green(), dir.display());
+                }
+                continue;
+            }
+            Err(failure) => failure,
+        };
+
+        let (restored, restore_errors) = backup.restore_unit(&dir);
+        for msg in restore_errors {
+            eprintln!("{}", format!("error: {msg}").red());
+            total_result.errors.push(msg);
+        }
+
+        // Restored files may live outside `dir` (a `-r` include elsewhere).
+        let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let restored: HashSet<PathBuf> = restored.iter().map(|path| canonical(path)).collect();
+        for file in scanned
+            .iter_mut()
+            .filter(|f| restored.contains(&canonical(&f.path)))
+        {
+            let reverted: Vec<_> = file
+                .result
+                .updated
+                .drain(..)
+                .chain(file.result.pinned.drain(..))
+                .collect();
+            if reverted.is_empty() {
+                continue;
+            }
+            for entry in &reverted {
+                if let Some(i) = total_result.updated.iter().position(|e| e == entry) {
+                    total_result.updated.remove(i);
+                } else if let Some(i) = total_result.pinned.iter().position(|e| e == entry) {
+                    total_result.pinned.remove(i);
+                }
+            }
+            let changes = reverted
+                .iter()
+                .map(|(name, old, new, _)| format!("{name} {old} → {new}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let msg = format!(
+                "verification failed in {}: {}\nreverted {}: {}",
+                dir.display(),
+                failure,
+                file.path.display(),
+                changes
+            );
+            eprintln!("{}", format!("error: {msg}").red());
+            file.result.errors.push(msg.clone());
+            total_result.errors.push(msg);
+        }
+    }
+}
+
+/// Warn when manifests of different types in one directory declare the same
+/// package at different versions. The warning is recorded on the first file
+/// of each group; nothing is rewritten.
+fn report_duplicate_declarations(
+    scanned: &mut [ScannedFileResult],
+    total_result: &mut UpdateResult,
+    text_mode: bool,
+) {
+    // A file that failed to parse already carries that error.
+    let files: Vec<(PathBuf, FileType)> = scanned
+        .iter()
+        .filter(|file| file.result.errors.is_empty())
+        .map(|file| (file.path.clone(), file.file_type))
+        .collect();
+    let Ok(packages) = scan_packages(&files) else {
+        return;
+    };
+    for duplicate in upd::align::find_duplicate_declarations(&packages) {
+        let declared: Vec<String> = duplicate
+            .occurrences
+            .iter()
+            .map(|o| {
+                let name = o
+                    .file_path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy();
+                format!("{} ({})", name, o.version)
+            })
+            .collect();
+        let msg = format!(
+            "{} is declared at different versions in {}; consider consolidating into one manifest",
+            duplicate.occurrences[0].original_name,
+            declared.join(", ")
+        );
+        let first = &duplicate.occurrences[0].file_path;
+        if text_mode {
+            eprintln!(
+                "{} {} {}",
+                format!("{}:", first.display()).blue().underline(),
+                "Warning:".yellow(),
+                msg
+            );
+        }
+        if let Some(file) = scanned.iter_mut().find(|file| &file.path == first) {
+            file.result.warnings.push(msg.clone());
+        }
+        total_result.warnings.push(msg);
+    }
+}
+
+/// Warn when requirements files joined by `-r` includes declare the same
+/// package at different versions. The warning is recorded on the including
+/// file; nothing is rewritten (`upd align` brings the versions together).
+fn report_include_conflicts(
+    scanned: &mut [ScannedFileResult],
+    total_result: &mut UpdateResult,
+    text_mode: bool,
+) {
+    // A file that failed to parse already carries that error.
+    let files: Vec<(PathBuf, FileType)> = scanned
+        .iter()
+        .filter(|file| file.result.errors.is_empty())
+        .map(|file| (file.path.clone(), file.file_type))
+        .collect();
+    for conflict in upd::align::find_include_conflicts(&files) {
+        let dir = conflict.root.parent().unwrap_or(Path::new(""));
+        let declared: Vec<String> = conflict
+            .occurrences
+            .iter()
+            .map(|o| {
+                let name = o.file_path.strip_prefix(dir).unwrap_or(&o.file_path);
+                format!("{} ({})", name.display(), o.version)
+            })
+            .collect();
+        let msg = format!(
+            "{} is declared at different versions across the -r includes of {}: {}; run `upd align` to align them",
+            conflict.occurrences[0].original_name,
+            conflict
+                .root
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy(),
+            declared.join(", ")
+        );
+        if text_mode {
+            eprintln!(
+                "{} {} {}",
+                format!("{}:", conflict.root.display()).blue().underline(),
+                "Warning:".yellow(),
+                msg
+            );
+        }
+        if let Some(file) = scanned.iter_mut().find(|file| file.path == conflict.root) {
+            file.result.warnings.push(msg.clone());
+        }
+        total_result.warnings.push(msg);
+    }
+}
+
+/// `--treat-unconstrained-as-error`: record every dependency declared without
+/// a lower bound as an error on its file.
+fn report_unconstrained_dependencies(
+    scanned: &mut [ScannedFileResult],
+    total_result: &mut UpdateResult,
+    text_mode: bool,
+) {
+    for file in scanned.iter_mut() {
+        // A file that failed to parse already carries that error.
+        let Ok(offenders) = upd::align::find_unconstrained_dependencies(&file.path, file.file_type)
+        else {
+            continue;
+        };
+        for dep in offenders {
+            let location = match dep.line_number {
+                Some(line) => format!("{}:{}", file.path.display(), line),
+                None => file.path.display().to_string(),
+            };
+            let spec = if dep.spec.is_empty() {
+                "no version specifier".to_string()
+            } else {
+                format!("\"{}\"", dep.spec)
+            };
+            let msg = format!(
+                "{}: declared without a lower bound at {} ({})",
+                dep.name, location, spec
+            );
+            if text_mode {
+                eprintln!("{} {}", "Error:".red(), msg);
+            }
+            file.result.errors.push(msg.clone());
+            total_result.errors.push(msg);
+        }
+    }
+}
+
 /// Parameters controlling bounded JSON output (--limit, --offset, --fields).
 struct BoundedOutputParams<'a> {
     limit: Option<usize>,
@@ -1296,7 +2908,7 @@ impl<'a> BoundedOutputParams<'a> {
     }
 }
 
-/// Inputs needed to build the update JSON report.
+/// Inputs needed to build the update JSON or markdown report.
 struct UpdateReportInput<'a> {
     scanned: &'a [ScannedFileResult],
     total_result: &'a UpdateResult,
@@ -1305,6 +2917,8 @@ struct UpdateReportInput<'a> {
     filter: UpdateFilter,
     file_cooldowns: &'a HashMap<PathBuf, Option<CooldownPolicy>>,
     cooldown_notes: Vec<String>,
+    transitive: &'a [OutdatedTransitive],
+    changelogs: &'a [ChangelogLink],
 }
 
 /// Apply --limit, --offset, and --fields to a JSON document for bounded output.
@@ -1355,7 +2969,26 @@ fn apply_bounded_output(
 }
 
 fn emit_update_json(input: UpdateReportInput<'_>, bounded: &BoundedOutputParams<'_>) -> Result<()> {
-    use upd::output::{UpdateReport, UpdateSummary, build_update_file_report};
+    let report = build_update_report(input);
+    let doc = serde_json::to_value(&report)?;
+    let doc = apply_bounded_output(doc, "files", bounded);
+    println!("{}", serde_json::to_string_pretty(&doc)?);
+    Ok(())
+}
+
+/// Print the update report as a markdown table for `--format markdown`.
+fn emit_update_markdown(input: UpdateReportInput<'_>) {
+    print!(
+        "{}",
+        upd::output::render_update_markdown(&build_update_report(input))
+    );
+}
+
+fn build_update_report(input: UpdateReportInput<'_>) -> upd::output::UpdateReport {
+    use upd::output::{
+        ChangelogEntry, REPORT_SCHEMA_VERSION, TransitiveEntry, UpdateReport, UpdateSummary,
+        build_update_file_report,
+    };
 
     let UpdateReportInput {
         scanned,
@@ -1365,6 +2998,8 @@ fn emit_update_json(input: UpdateReportInput<'_>, bounded: &BoundedOutputParams<
         filter,
         file_cooldowns,
         cooldown_notes,
+        transitive,
+        changelogs,
     } = input;
 
     let files: Vec<_> = scanned
@@ -1407,22 +3042,38 @@ fn emit_update_json(input: UpdateReportInput<'_>, bounded: &BoundedOutputParams<
         ignored: total_result.ignored.len(),
         errors: total_result.errors.len(),
         warnings: total_result.warnings.len(),
+        not_applied: total_result.not_applied.len(),
+        deferred: total_result.deferred.len(),
         held_back: total_result.held_back.len(),
         skipped_by_cooldown: total_result.skipped_by_cooldown.len(),
+        offline_skipped: total_result.offline_skipped.len(),
+        timed_out: total_result.timed_out.len(),
     };
 
-    let report = UpdateReport {
+    UpdateReport {
+        schema_version: REPORT_SCHEMA_VERSION,
         command: "update",
         mode: if dry_run { "dry-run" } else { "applied" },
         files,
         summary,
         cooldown_notes,
-    };
-
-    let doc = serde_json::to_value(&report)?;
-    let doc = apply_bounded_output(doc, "files", bounded);
-    println!("{}", serde_json::to_string_pretty(&doc)?);
-    Ok(())
+        transitive: transitive
+            .iter()
+            .map(|t| TransitiveEntry {
+                lockfile: t.lockfile.display().to_string(),
+                package: t.package.clone(),
+                current: t.current.clone(),
+                latest: t.latest.clone(),
+            })
+            .collect(),
+        changelogs: changelogs
+            .iter()
+            .map(|c| ChangelogEntry {
+                package: c.package.clone(),
+                url: c.url.clone(),
+            })
+            .collect(),
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -1438,10 +3089,19 @@ async fn run_interactive_update(
     rubygems: &Arc<CachedRegistry<RubyGemsRegistry>>,
     terraform: &Arc<CachedRegistry<TerraformRegistry>>,
     nuget: &Arc<CachedRegistry<NuGetRegistry>>,
+    docker_hub: &Arc<CachedRegistry<DockerHubRegistry>>,
+    packagist: &Arc<CachedRegistry<PackagistRegistry>>,
+    maven: &Arc<CachedRegistry<MavenRegistry>>,
+    pub_dev: &Arc<CachedRegistry<PubRegistry>>,
+    runtime: &Arc<CachedRegistry<RuntimeRegistry>>,
     github_releases: &Arc<CachedRegistry<GitHubReleasesRegistry>>,
     requirements_updater: &Arc<RequirementsUpdater>,
     pyproject_updater: &Arc<PyProjectUpdater>,
+    pipfile_updater: &Arc<PipfileUpdater>,
+    setup_py_updater: &Arc<SetupPyUpdater>,
+    conda_env_updater: &Arc<CondaEnvUpdater>,
     package_json_updater: &Arc<PackageJsonUpdater>,
+    pnpm_workspace_updater: &Arc<PnpmWorkspaceUpdater>,
     cargo_toml_updater: &Arc<CargoTomlUpdater>,
     go_mod_updater: &Arc<GoModUpdater>,
     gemfile_updater: &Arc<GemfileUpdater>,
@@ -1450,6 +3110,12 @@ async fn run_interactive_update(
     mise_updater: &Arc<MiseUpdater>,
     terraform_updater: &Arc<TerraformUpdater>,
     csproj_updater: &Arc<CsprojUpdater>,
+    dockerfile_updater: &Arc<DockerfileUpdater>,
+    composer_updater: &Arc<ComposerUpdater>,
+    gradle_catalog_updater: &Arc<GradleCatalogUpdater>,
+    pubspec_updater: &Arc<PubspecUpdater>,
+    runtime_version_updater: &Arc<RuntimeVersionUpdater>,
+    source_registries: &SourceRegistries,
     cache: &Arc<std::sync::Mutex<Cache>>,
     cache_enabled: bool,
     file_cooldowns: &HashMap<PathBuf, Option<CooldownPolicy>>,
@@ -1474,6 +3140,7 @@ async fn run_interactive_update(
     let mut planned_changes: Vec<PlannedChange> = Vec::new();
     let mut scanned_results: Vec<ScannedFileResult> = Vec::new();
 
+    let inherited_constraints = inherited_constraint_files(files);
     for (path, file_type) in files {
         let cooldown_policy = file_cooldowns.get(path).and_then(|p| p.as_ref());
         let dry_run_options = build_update_options(
@@ -1484,7 +3151,16 @@ async fn run_interactive_update(
             cooldown_policy,
             Arc::clone(&cooldown_notes),
             filter.to_bump_filter(),
-        );
+        )
+        .with_groups(cli.groups.clone())
+        .with_strategy(cli.strategy)
+        .with_allow_prerelease(cli.allow_prerelease.clone())
+        .with_respect_msrv(cli.respect_msrv)
+        .with_ignore_engines(cli.ignore_engines)
+        .with_allow_deprecated(cli.allow_deprecated)
+        .with_prune_dead_markers(cli.prune_dead_markers)
+        .with_source_registries(source_registries.clone())
+        .with_constraint_files(constraint_files_for(&inherited_constraints, path));
 
         if cli.verbose {
             eprintln!("{}", format!("Scanning: {}", path.display()).cyan());
@@ -1501,11 +3177,31 @@ async fn run_interactive_update(
                     .update(path, pypi.as_ref(), dry_run_options.clone())
                     .await
             }
+            FileType::Pipfile => {
+                pipfile_updater
+                    .update(path, pypi.as_ref(), dry_run_options.clone())
+                    .await
+            }
+            FileType::SetupPy => {
+                setup_py_updater
+                    .update(path, pypi.as_ref(), dry_run_options.clone())
+                    .await
+            }
+            FileType::CondaEnv => {
+                conda_env_updater
+                    .update(path, pypi.as_ref(), dry_run_options.clone())
+                    .await
+            }
             FileType::PackageJson => {
                 package_json_updater
                     .update(path, npm.as_ref(), dry_run_options.clone())
                     .await
             }
+            FileType::PnpmWorkspace => {
+                pnpm_workspace_updater
+                    .update(path, npm.as_ref(), dry_run_options.clone())
+                    .await
+            }
             FileType::CargoToml => {
                 cargo_toml_updater
                     .update(path, crates_io.as_ref(), dry_run_options.clone())
@@ -1546,6 +3242,31 @@ async fn run_interactive_update(
                     .update(path, terraform.as_ref(), dry_run_options.clone())
                     .await
             }
+            FileType::Dockerfile => {
+                dockerfile_updater
+                    .update(path, docker_hub.as_ref(), dry_run_options.clone())
+                    .await
+            }
+            FileType::ComposerJson => {
+                composer_updater
+                    .update(path, packagist.as_ref(), dry_run_options.clone())
+                    .await
+            }
+            FileType::GradleCatalog => {
+                gradle_catalog_updater
+                    .update(path, maven.as_ref(), dry_run_options.clone())
+                    .await
+            }
+            FileType::Pubspec => {
+                pubspec_updater
+                    .update(path, pub_dev.as_ref(), dry_run_options.clone())
+                    .await
+            }
+            FileType::Nvmrc | FileType::PythonVersion => {
+                runtime_version_updater
+                    .update(path, runtime.as_ref(), dry_run_options.clone())
+                    .await
+            }
         };
 
         match result {
@@ -1591,7 +3312,7 @@ async fn run_interactive_update(
 
     if !has_interactive_changes(&pending_updates, &scanned_results) {
         if !cli.quiet {
-            println!(
+            textln!(
                 "{} Scanned {} file(s), all dependencies up to date",
                 "✓".green(),
                 files.len()
@@ -1609,7 +3330,23 @@ async fn run_interactive_update(
     let updates_with_decisions = if pending_updates.is_empty() {
         Vec::new()
     } else {
-        prompt_all(pending_updates)?
+        let file_types: HashMap<String, FileType> = files
+            .iter()
+            .map(|(path, file_type)| (path.display().to_string(), *file_type))
+            .collect();
+        let validate = |update: &PendingUpdate, version: &str| -> Result<(), String> {
+            let lang = file_types.get(&update.file).map(FileType::lang);
+            if lang.is_none_or(|lang| is_valid_version(version, lang)) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "\"{}\" is not a valid {} version",
+                    version,
+                    lang.map_or("", |lang| lang.as_str())
+                ))
+            }
+        };
+        prompt_all(pending_updates, cli.edit.then_some(&validate as _))?
     };
 
     let mut approved_change_counts =
@@ -1618,7 +3355,7 @@ async fn run_interactive_update(
 
     if approved_count == 0 && configured_pin_count == 0 {
         if !cli.quiet {
-            println!("\n{}", "No updates applied.".yellow());
+            textln!("\n{}", "No updates applied.".yellow());
         }
         return Ok(());
     }
@@ -1631,7 +3368,7 @@ async fn run_interactive_update(
         apply_parts.push(format!("{} configured pin(s)", configured_pin_count));
     }
     if !cli.quiet {
-        println!(
+        textln!(
             "\n{}",
             format!("Applying {}...", apply_parts.join(" and ")).cyan()
         );
@@ -1650,28 +3387,19 @@ async fn run_interactive_update(
         }
 
         let content = read_file_safe(&scanned_file.path)?;
-        let updates: Vec<_> = selected_changes
-            .iter()
-            .map(|change| VersionEdit {
-                package: change.package.as_str(),
-                old_version: change.old_version.as_str(),
-                new_version: change.new_version.as_str(),
-                line_num: change.line_num,
-            })
-            .collect();
-        let rewritten = apply_version_updates(
+        let rewritten = rewrite_selected_changes(
             &content,
-            &updates,
+            &selected_changes,
             scanned_file.file_type,
             cli.full_precision,
         )
         .map_err(|e| anyhow::anyhow!("Failed to rewrite {}: {}", scanned_file.path.display(), e))?;
 
-        if rewritten.content == content {
+        if rewritten == content {
             continue;
         }
 
-        write_file_atomic(&scanned_file.path, &rewritten.content)?;
+        write_file_atomic(&scanned_file.path, &rewritten)?;
         updated_files.push(scanned_file.path.clone());
 
         let file_str = scanned_file.path.display().to_string();
@@ -1691,26 +3419,40 @@ async fn run_interactive_update(
                 if !entry.iter().any(|n| n == &change.package) {
                     entry.push(change.package.clone());
                 }
-            }
-
-            match change.kind {
-                ChangeKind::RegistryUpdate => {
+            }
+
+            match change.kind {
+                ChangeKind::RegistryUpdate => {
+                    applied_updates += 1;
+                    if !cli.quiet {
+                        textln!(
+                            "{} {} {} {} → {}",
+                            location.blue().underline(),
+                            "Updated".green(),
+                            change.package.bold(),
+                            change.old_version.dimmed(),
+                            change.new_version.green(),
+                        );
+                    }
+                }
+                ChangeKind::EditedUpdate => {
                     applied_updates += 1;
                     if !cli.quiet {
-                        println!(
-                            "{} {} {} {} → {}",
+                        textln!(
+                            "{} {} {} {} → {} {}",
                             location.blue().underline(),
                             "Updated".green(),
                             change.package.bold(),
                             change.old_version.dimmed(),
                             change.new_version.green(),
+                            "(edited)".dimmed(),
                         );
                     }
                 }
                 ChangeKind::ConfigPin => {
                     applied_pins += 1;
                     if !cli.quiet {
-                        println!(
+                        textln!(
                             "{} {} {} {} → {} {}",
                             location.blue().underline(),
                             "Pinned".cyan(),
@@ -1736,7 +3478,8 @@ async fn run_interactive_update(
                 let dir_path = dir.to_path_buf();
                 if processed_dirs.insert(dir_path.clone()) {
                     let changed = changed_by_dir.get(&dir_path).unwrap_or(&empty);
-                    let result = regenerate_lockfiles(path, changed, cli.verbose);
+                    let result =
+                        regenerate_lockfiles(path, changed, cli.allow_scripts, cli.verbose);
                     regen_results.push((path.clone(), result));
                 }
             }
@@ -1746,8 +3489,8 @@ async fn run_interactive_update(
         let has_work = regen_results.iter().any(|(_, r)| !r.no_lockfiles);
 
         if has_work && !cli.quiet {
-            println!();
-            println!("{}", "Regenerating lockfiles...".cyan());
+            textln!();
+            textln!("{}", "Regenerating lockfiles...".cyan());
         }
 
         let mut had_error = false;
@@ -1794,16 +3537,16 @@ async fn run_interactive_update(
     }
 
     if !cli.quiet {
-        println!();
+        textln!();
         if applied_updates > 0 {
-            println!(
+            textln!(
                 "{} {} package(s)",
                 "Updated".green(),
                 applied_updates.to_string().green().bold()
             );
         }
         if applied_pins > 0 {
-            println!(
+            textln!(
                 "{} {} package(s) to configured versions",
                 "Pinned".cyan(),
                 applied_pins.to_string().cyan().bold()
@@ -1815,7 +3558,22 @@ async fn run_interactive_update(
 }
 
 async fn run_align(cli: &Cli) -> Result<()> {
-    let text_mode = !effective_json_mode(cli);
+    let json_mode = effective_json_mode(cli);
+    // Human-readable text; also printed beside JSON under --progress-stderr.
+    let text_mode = !json_mode || cli.progress_stderr;
+
+    // `--to` aligns the `--package` selection to a chosen version; `--lowest`
+    // aligns down instead of up.
+    let target = match &cli.command {
+        Some(Command::Align {
+            to: Some(version), ..
+        }) => AlignTarget::Exact(version.clone()),
+        Some(Command::Align { lowest: true, .. }) => AlignTarget::Lowest,
+        _ => AlignTarget::Highest,
+    };
+    if matches!(target, AlignTarget::Exact(_)) && cli.packages.is_empty() {
+        anyhow::bail!("--to requires --package to select the packages to align");
+    }
 
     // Resolve paths: explicit > VCS root > error
     let paths = match resolve_scan_paths(cli) {
@@ -1840,23 +3598,25 @@ async fn run_align(cli: &Cli) -> Result<()> {
         log_update_config_usage(&resolved_config);
     }
 
-    let files = discover_files_with(
+    // Requirements files reached only through `-r` includes are aligned too.
+    let files = with_included_requirements(discover_files_with(
         &paths,
         &cli.langs,
         DiscoverOptions {
             no_ignore: cli.no_ignore,
+            include_hidden: cli.include_hidden,
             verbose: cli.verbose,
             exclude: &config.exclude,
+            relative_exclude: &cli.exclude,
         },
-    );
+    ));
     let file_count = files.len();
 
     if files.is_empty() {
-        if text_mode {
-            if !cli.quiet {
-                println!("{}", "No dependency files found.".yellow());
-            }
-        } else {
+        if text_mode && !cli.quiet {
+            textln!("{}", "No dependency files found.".yellow());
+        }
+        if json_mode {
             emit_align_json(&[], 0, &BoundedOutputParams::from_cli(cli))?;
         }
         return Ok(());
@@ -1864,10 +3624,10 @@ async fn run_align(cli: &Cli) -> Result<()> {
 
     // Init TLS only after we know we're going to network. The empty-files
     // early return above must not be killed by a malformed CA bundle env var.
-    init_tls(cli)?;
+    init_tls(cli, &config.allowed_registries)?;
 
     if cli.verbose && text_mode {
-        println!(
+        textln!(
             "{}",
             format!("Scanning {} dependency file(s) for alignment", file_count).cyan()
         );
@@ -1882,8 +3642,28 @@ async fn run_align(cli: &Cli) -> Result<()> {
         }
     };
 
+    let packages: HashMap<_, _> = packages
+        .into_iter()
+        .filter(|(_, occurrences)| {
+            cli.packages.is_empty()
+                || occurrences
+                    .iter()
+                    .any(|o| cli.packages.contains(&o.original_name))
+        })
+        .collect();
+    if let AlignTarget::Exact(version) = &target
+        && let Some((name, lang)) = packages
+            .keys()
+            .find(|(_, lang)| !is_valid_version(version, *lang))
+    {
+        anyhow::bail!(
+            "--to {version} is not a valid {} version for {name}",
+            lang.as_str()
+        );
+    }
+
     // Find alignments
-    let align_result = find_alignments(packages);
+    let align_result = find_alignments_to(packages, &target);
 
     // Surface packages the config ignores so a green `--check` is explainable.
     // Goes to stderr (like the discovery "skipping <path>" lines) so it never
@@ -1905,7 +3685,7 @@ async fn run_align(cli: &Cli) -> Result<()> {
         .filter(|p| p.has_misalignment())
         .collect();
 
-    if !text_mode {
+    if json_mode {
         let to_report: Vec<PackageAlignment> = align_result
             .packages
             .iter()
@@ -1916,9 +3696,32 @@ async fn run_align(cli: &Cli) -> Result<()> {
         emit_align_json(&to_report, file_count, &BoundedOutputParams::from_cli(cli))?;
     }
 
+    // Constrained occurrences are never rewritten; say so when the target
+    // would break them.
+    if text_mode {
+        let considered = align_result
+            .packages
+            .iter()
+            .filter(|p| !config.should_ignore(&p.package_name));
+        for alignment in considered {
+            for occurrence in alignment.constraint_violations() {
+                eprintln!(
+                    "{} {} {} is below the lower bound of this {} requirement ({}); left unchanged",
+                    format!("{}:", occurrence.file_path.display())
+                        .blue()
+                        .underline(),
+                    "Warning:".yellow(),
+                    alignment.target_version,
+                    occurrence.original_name,
+                    occurrence.version
+                );
+            }
+        }
+    }
+
     if misaligned.is_empty() {
         if text_mode && !cli.quiet {
-            println!(
+            textln!(
                 "{} Scanned {} file(s), all packages are aligned",
                 "✓".green(),
                 file_count
@@ -1933,7 +3736,7 @@ async fn run_align(cli: &Cli) -> Result<()> {
     if text_mode && !cli.quiet {
         let action_prefix = if dry_run { "Would align" } else { "Aligning" };
 
-        println!(
+        textln!(
             "\n{} {} misaligned package(s) across {} file(s):\n",
             action_prefix,
             misaligned.len().to_string().yellow().bold(),
@@ -1941,15 +3744,22 @@ async fn run_align(cli: &Cli) -> Result<()> {
         );
 
         for alignment in &misaligned {
-            print_alignment(alignment, dry_run);
+            print_alignment(alignment, &target);
         }
     }
 
     // Apply alignments if not dry-run
     if !dry_run {
+        prepare_backups(
+            cli,
+            misaligned
+                .iter()
+                .flat_map(|a| a.occurrences.iter().map(|o| o.file_path.as_path())),
+            dry_run,
+        );
         let updated_count = apply_alignments(&misaligned, cli.full_precision)?;
         if text_mode && !cli.quiet {
-            println!(
+            textln!(
                 "\n{} {} package occurrence(s)",
                 "Aligned".green(),
                 updated_count.to_string().green().bold()
@@ -1960,12 +3770,12 @@ async fn run_align(cli: &Cli) -> Result<()> {
             .iter()
             .map(|a| a.misaligned_occurrences().len())
             .sum();
-        println!(
+        textln!(
             "\n{} {} package occurrence(s) to align",
             "Found".yellow(),
             total_misaligned.to_string().yellow().bold()
         );
-        println!("Run with --apply to write changes.");
+        textln!("Run with --apply to write changes.");
     }
 
     // Dry-run (including --check) signals pending misalignments with exit 1,
@@ -1982,7 +3792,7 @@ fn emit_align_json(
     file_count: usize,
     bounded: &BoundedOutputParams<'_>,
 ) -> Result<()> {
-    use upd::output::{AlignReport, AlignSummary, build_align_package};
+    use upd::output::{AlignReport, AlignSummary, REPORT_SCHEMA_VERSION, build_align_package};
 
     let pkgs: Vec<_> = packages.iter().map(build_align_package).collect();
     let misaligned_packages = pkgs.iter().filter(|p| p.is_misaligned).count();
@@ -1993,6 +3803,7 @@ fn emit_align_json(
         .count();
 
     let report = AlignReport {
+        schema_version: REPORT_SCHEMA_VERSION,
         command: "align",
         summary: AlignSummary {
             files_scanned: file_count,
@@ -2015,6 +3826,58 @@ fn emit_align_json(
 /// but OSV's NuGet ecosystem is case-sensitive. Each `PackageOccurrence` carries
 /// `original_name` with the casing from the dependency file; that value is used
 /// as `AuditPackage::name` so OSV queries reach the correct advisory.
+/// Append the lockfile-pinned transitive packages of each manifest
+/// (`--include-transitive`) to the audit set, skipping ones already present.
+fn add_transitive_audit_packages(
+    files: &[(PathBuf, FileType)],
+    audit_packages: &mut Vec<AuditPackage>,
+) {
+    let mut seen: HashSet<(String, String, &'static str)> = audit_packages
+        .iter()
+        .map(|p| {
+            (
+                p.name.to_lowercase(),
+                p.version.clone(),
+                p.ecosystem.as_str(),
+            )
+        })
+        .collect();
+
+    for (path, file_type) in files {
+        let ecosystem = match file_type.lang() {
+            Lang::Python => Ecosystem::PyPI,
+            Lang::Node => Ecosystem::Npm,
+            Lang::Rust => Ecosystem::CratesIo,
+            _ => continue,
+        };
+        let groups = match collect_transitive(path, *file_type) {
+            Ok(groups) => groups,
+            Err(e) => {
+                eprintln!(
+                    "warning: transitive check failed for {}: {}",
+                    path.display(),
+                    e
+                );
+                continue;
+            }
+        };
+        for package in groups.into_iter().flat_map(|group| group.packages) {
+            let key = (
+                package.name.to_lowercase(),
+                package.version.clone(),
+                ecosystem.as_str(),
+            );
+            if seen.insert(key) {
+                audit_packages.push(AuditPackage {
+                    name: package.name,
+                    version: package.version,
+                    ecosystem,
+                });
+            }
+        }
+    }
+}
+
 pub(crate) fn build_audit_packages(
     packages: &HashMap<(String, Lang), Vec<PackageOccurrence>>,
 ) -> Vec<AuditPackage> {
@@ -2022,11 +3885,17 @@ pub(crate) fn build_audit_packages(
     let mut seen: HashSet<(String, String, String)> = HashSet::new();
 
     for ((name, lang), occurrences) in packages {
-        // OSV doesn't cover GitHub Actions, pre-commit hooks, mise tools, or Terraform; skip
+        // OSV doesn't cover GitHub Actions, pre-commit hooks, mise tools, Terraform, or
+        // container images, and Composer, Maven and pub packages aren't audited yet; skip
         if *lang == Lang::Actions
             || *lang == Lang::PreCommit
             || *lang == Lang::Mise
             || *lang == Lang::Terraform
+            || *lang == Lang::Docker
+            || *lang == Lang::Php
+            || *lang == Lang::Jvm
+            || *lang == Lang::Dart
+            || *lang == Lang::Runtime
         {
             continue;
         }
@@ -2038,7 +3907,15 @@ pub(crate) fn build_audit_packages(
             Lang::Go => Ecosystem::Go,
             Lang::Ruby => Ecosystem::RubyGems,
             Lang::DotNet => Ecosystem::NuGet,
-            Lang::Actions | Lang::PreCommit | Lang::Mise | Lang::Terraform => {
+            Lang::Actions
+            | Lang::PreCommit
+            | Lang::Mise
+            | Lang::Terraform
+            | Lang::Docker
+            | Lang::Php
+            | Lang::Jvm
+            | Lang::Dart
+            | Lang::Runtime => {
                 unreachable!("filtered above")
             }
         };
@@ -2067,14 +3944,9 @@ pub(crate) fn build_audit_packages(
 /// Each entry is `(file_type, [(package_name, old_version, new_version, line_num)])`.
 type FileEdits = (FileType, Vec<(String, String, String, Option<usize>)>);
 
-async fn run_audit(cli: &Cli) -> Result<()> {
-    let no_fail = matches!(&cli.command, Some(Command::Audit { no_fail, .. }) if *no_fail);
-    let fix_audit = matches!(&cli.command, Some(Command::Audit { fix_audit, .. }) if *fix_audit);
-    let offline = matches!(&cli.command, Some(Command::Audit { offline, .. }) if *offline);
-    let json_mode = effective_json_mode(cli);
-    let text_mode = !json_mode && cli.format != Some(upd::cli::OutputFormat::Sarif);
-    let sarif_mode = cli.format == Some(upd::cli::OutputFormat::Sarif) && !json_mode;
-    // Audit never mutates files, so no VCS check is needed. Fall back to CWD.
+/// Discover manifests under the CLI paths and collect one purl-identified
+/// component per declared dependency. Also returns the number of files read.
+fn declared_components(cli: &Cli) -> Result<(Vec<upd::sbom::SbomComponent>, usize)> {
     let paths = {
         let explicit = cli.get_paths();
         if explicit.is_empty() {
@@ -2083,64 +3955,171 @@ async fn run_audit(cli: &Cli) -> Result<()> {
             explicit
         }
     };
-    // `exclude` path globs are honored uniformly across subcommands; resolve the
-    // root config so audit drops the same files `update`/`align` would.
     let root_config = resolve_root_config(cli, &paths)?;
     let files = discover_files_with(
         &paths,
         &cli.langs,
         DiscoverOptions {
             no_ignore: cli.no_ignore,
+            include_hidden: cli.include_hidden,
             verbose: cli.verbose,
             exclude: &root_config.config.exclude,
+            relative_exclude: &cli.exclude,
         },
     );
-    let file_count = files.len();
+    let packages = scan_packages(&files)?;
+    Ok((upd::sbom::collect_components(&packages), files.len()))
+}
 
-    if files.is_empty() {
-        if text_mode {
-            if !cli.quiet {
-                println!("{}", "No dependency files found.".yellow());
-            }
-        } else if sarif_mode {
-            emit_audit_sarif(&AuditResult::default(), &HashMap::new())?;
-        } else {
-            emit_audit_json(
-                &AuditResult::default(),
-                "complete",
-                &BoundedOutputParams::from_cli(cli),
-            )?;
+/// `--emit-purls`: print the purl of every declared dependency, one per line
+/// (or a JSON array with an explicit `--format json` / `-o json`).
+fn run_emit_purls(cli: &Cli) -> Result<()> {
+    use upd::cli::OutputFormat;
+
+    let (components, _) = declared_components(cli)?;
+    let json = cli.output == OutputMode::Json
+        || (cli.output == OutputMode::Auto && cli.format == Some(OutputFormat::Json));
+    if json {
+        let purls: Vec<&str> = components.iter().map(|c| c.purl.as_str()).collect();
+        println!("{}", serde_json::to_string_pretty(&purls)?);
+    } else {
+        for component in &components {
+            println!("{}", component.purl);
         }
-        return Ok(());
     }
+    Ok(())
+}
 
-    if cli.verbose && text_mode {
-        println!(
-            "{}",
-            format!(
-                "Scanning {} dependency file(s) for vulnerabilities",
-                file_count
-            )
-            .cyan()
-        );
+fn run_sbom(cli: &Cli) -> Result<()> {
+    use upd::cli::OutputFormat;
+    use upd::sbom::SbomFormat;
+
+    let Some(Command::Sbom { out, .. }) = &cli.command else {
+        unreachable!("run_sbom called without the sbom subcommand");
+    };
+    let format = match cli.format {
+        None | Some(OutputFormat::Cyclonedx) => SbomFormat::CycloneDx,
+        Some(OutputFormat::Spdx) => SbomFormat::Spdx,
+        Some(_) => anyhow::bail!("upd sbom supports --format cyclonedx (default) or spdx"),
+    };
+    let (components, file_count) = declared_components(cli)?;
+    let created = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let document = serde_json::to_string_pretty(&upd::sbom::render(format, &components, &created))?;
+
+    if out.as_os_str() == "-" {
+        println!("{}", document);
+    } else {
+        write_file_atomic(out, &format!("{}\n", document))?;
+        if !cli.quiet {
+            textln!(
+                "{} Wrote SBOM with {} component(s) from {} file(s) to {}",
+                "✓".green(),
+                components.len(),
+                file_count,
+                out.display()
+            );
+        }
     }
+    Ok(())
+}
 
-    // Scan all files for packages
-    let packages = match scan_packages(&files) {
-        Ok(p) => p,
-        Err(e) => {
-            eprintln!("{}", format!("Error scanning files: {}", e).red());
-            return Err(e);
+async fn run_audit(cli: &Cli) -> Result<()> {
+    let no_fail = matches!(&cli.command, Some(Command::Audit { no_fail, .. }) if *no_fail);
+    let fix_audit = matches!(&cli.command, Some(Command::Audit { fix_audit, .. }) if *fix_audit);
+    let offline = cli.offline;
+    let json_mode = effective_json_mode(cli);
+    let text_mode = !json_mode && cli.format != Some(upd::cli::OutputFormat::Sarif);
+    let sarif_mode = cli.format == Some(upd::cli::OutputFormat::Sarif) && !json_mode;
+    // Audit never mutates files, so no VCS check is needed. Fall back to CWD.
+    let paths = {
+        let explicit = cli.get_paths();
+        if explicit.is_empty() {
+            vec![PathBuf::from(".")]
+        } else {
+            explicit
         }
     };
+    // `exclude` path globs are honored uniformly across subcommands; resolve the
+    // root config so audit drops the same files `update`/`align` would.
+    let root_config = resolve_root_config(cli, &paths)?;
+    let sbom = match &cli.command {
+        Some(Command::Audit { sbom, .. }) => sbom.as_deref(),
+        _ => None,
+    };
+
+    let (packages, audit_packages, file_count) = if let Some(sbom_path) = sbom {
+        // An SBOM replaces manifest discovery: there are no files to point
+        // SARIF locations or --fix-audit edits at.
+        let sbom = upd::audit::sbom::load_sbom(sbom_path)?;
+        if !cli.quiet {
+            for skipped in &sbom.skipped {
+                eprintln!("{} skipped SBOM component {}", "⚠".yellow(), skipped);
+            }
+        }
+        (HashMap::new(), sbom.packages, 1)
+    } else {
+        let files = discover_files_with(
+            &paths,
+            &cli.langs,
+            DiscoverOptions {
+                no_ignore: cli.no_ignore,
+                include_hidden: cli.include_hidden,
+                verbose: cli.verbose,
+                exclude: &root_config.config.exclude,
+                relative_exclude: &cli.exclude,
+            },
+        );
+        let file_count = files.len();
+
+        if files.is_empty() {
+            if text_mode {
+                if !cli.quiet {
+                    textln!("{}", "No dependency files found.".yellow());
+                }
+            } else if sarif_mode {
+                emit_audit_sarif(&AuditResult::default(), &HashMap::new())?;
+            } else {
+                emit_audit_json(
+                    &AuditResult::default(),
+                    "complete",
+                    &BoundedOutputParams::from_cli(cli),
+                )?;
+            }
+            return Ok(());
+        }
+
+        if cli.verbose && text_mode {
+            textln!(
+                "{}",
+                format!(
+                    "Scanning {} dependency file(s) for vulnerabilities",
+                    file_count
+                )
+                .cyan()
+            );
+        }
 
-    // Convert to audit packages (deduplicate by name+version+ecosystem)
-    let audit_packages = build_audit_packages(&packages);
+        // Scan all files for packages
+        let packages = match scan_packages(&files) {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("{}", format!("Error scanning files: {}", e).red());
+                return Err(e);
+            }
+        };
+
+        // Convert to audit packages (deduplicate by name+version+ecosystem)
+        let mut audit_packages = build_audit_packages(&packages);
+        if cli.include_transitive {
+            add_transitive_audit_packages(&files, &mut audit_packages);
+        }
+        (packages, audit_packages, file_count)
+    };
 
     if audit_packages.is_empty() {
         if text_mode {
             if !cli.quiet {
-                println!(
+                textln!(
                     "{} Scanned {} file(s), no packages found",
                     "✓".green(),
                     file_count
@@ -2164,11 +4143,11 @@ async fn run_audit(cli: &Cli) -> Result<()> {
     // similarly network-free. Initialized before the user-visible "Checking…"
     // line so the `--insecure` warning lands on stderr first.
     if !offline {
-        init_tls(cli)?;
+        init_tls(cli, &root_config.config.allowed_registries)?;
     }
 
     if text_mode && !cli.quiet {
-        println!(
+        textln!(
             "{}",
             format!(
                 "Checking {} unique package(s) for vulnerabilities...",
@@ -2185,7 +4164,7 @@ async fn run_audit(cli: &Cli) -> Result<()> {
     } else {
         Some(AuditCache::new_shared())
     };
-    let audit_result = osv_client
+    let mut audit_result = osv_client
         .check_packages_cached(&audit_packages, audit_cache.as_ref(), offline)
         .await?;
 
@@ -2195,13 +4174,33 @@ async fn run_audit(cli: &Cli) -> Result<()> {
         let _ = AuditCache::save_shared(c);
     }
 
+    if let Some(Command::Audit {
+        severity: Some(threshold),
+        ..
+    }) = &cli.command
+    {
+        let hidden = audit_result.retain_min_severity(*threshold);
+        if hidden > 0 && text_mode && !cli.quiet {
+            textln!(
+                "{}",
+                format!(
+                    "Hiding {} vulnerabilit{} below --severity {}",
+                    hidden,
+                    if hidden == 1 { "y" } else { "ies" },
+                    threshold.as_str()
+                )
+                .dimmed()
+            );
+        }
+    }
+
     let status = audit_status(&audit_result);
 
     if text_mode {
         if !cli.quiet {
             match status {
                 AuditStatus::Clean => {
-                    println!(
+                    textln!(
                         "\n{} No vulnerabilities found in {} package(s)",
                         "✓".green(),
                         audit_packages.len()
@@ -2212,7 +4211,7 @@ async fn run_audit(cli: &Cli) -> Result<()> {
                 }
                 AuditStatus::Incomplete => {
                     if audit_result.vulnerable.is_empty() {
-                        println!(
+                        textln!(
                             "\n{} Audit incomplete: {} error(s) occurred while checking {} package(s)",
                             "⚠".yellow().bold(),
                             audit_result.errors.len().to_string().yellow().bold(),
@@ -2220,7 +4219,7 @@ async fn run_audit(cli: &Cli) -> Result<()> {
                         );
                     } else {
                         print_audit_vulnerabilities(&audit_result);
-                        println!(
+                        textln!(
                             "\n{} Audit incomplete: {} error(s) occurred while checking dependencies",
                             "⚠".yellow().bold(),
                             audit_result.errors.len().to_string().yellow().bold()
@@ -2288,6 +4287,9 @@ async fn run_audit(cli: &Cli) -> Result<()> {
                     || *lang == Lang::PreCommit
                     || *lang == Lang::Mise
                     || *lang == Lang::Terraform
+                    || *lang == Lang::Docker
+                    || *lang == Lang::Php
+                    || *lang == Lang::Runtime
                 {
                     continue;
                 }
@@ -2319,6 +4321,11 @@ async fn run_audit(cli: &Cli) -> Result<()> {
 
             // Mutations are opt-in; without --apply this is a dry-run preview.
             let effective_dry_run = cli.is_effective_dry_run();
+            prepare_backups(
+                cli,
+                edits_by_file.keys().map(PathBuf::as_path),
+                effective_dry_run,
+            );
 
             let mut total_fixed: usize = 0;
             let mut fix_errors: Vec<String> = Vec::new();
@@ -2331,7 +4338,7 @@ async fn run_audit(cli: &Cli) -> Result<()> {
                             None => format!("{}:", path.display()),
                         };
                         if effective_dry_run {
-                            println!(
+                            textln!(
                                 "{} {} {} {} → {} {}",
                                 location.blue().underline(),
                                 "Would fix".yellow(),
@@ -2341,7 +4348,7 @@ async fn run_audit(cli: &Cli) -> Result<()> {
                                 "(security fix)".dimmed(),
                             );
                         } else {
-                            println!(
+                            textln!(
                                 "{} {} {} {} → {} {}",
                                 location.blue().underline(),
                                 "Fixed".green(),
@@ -2410,14 +4417,14 @@ async fn run_audit(cli: &Cli) -> Result<()> {
             if text_mode && !cli.quiet {
                 if effective_dry_run {
                     if total_fixed > 0 {
-                        println!(
+                        textln!(
                             "\n{} Would fix {} vulnerable package occurrence(s). Run with --apply to write changes.",
                             "→".yellow(),
                             total_fixed.to_string().yellow().bold()
                         );
                     }
                 } else {
-                    println!(
+                    textln!(
                         "\n{} Fixed {} vulnerable package occurrence(s)",
                         "✓".green(),
                         total_fixed.to_string().green().bold()
@@ -2535,6 +4542,11 @@ fn build_sarif_occurrences(
             || *lang == Lang::PreCommit
             || *lang == Lang::Mise
             || *lang == Lang::Terraform
+            || *lang == Lang::Docker
+            || *lang == Lang::Php
+            || *lang == Lang::Jvm
+            || *lang == Lang::Dart
+            || *lang == Lang::Runtime
         {
             continue;
         }
@@ -2546,7 +4558,15 @@ fn build_sarif_occurrences(
             Lang::Go => Ecosystem::Go,
             Lang::Ruby => Ecosystem::RubyGems,
             Lang::DotNet => Ecosystem::NuGet,
-            Lang::Actions | Lang::PreCommit | Lang::Mise | Lang::Terraform => {
+            Lang::Actions
+            | Lang::PreCommit
+            | Lang::Mise
+            | Lang::Terraform
+            | Lang::Docker
+            | Lang::Php
+            | Lang::Jvm
+            | Lang::Dart
+            | Lang::Runtime => {
                 unreachable!("filtered above")
             }
         };
@@ -2569,7 +4589,7 @@ fn build_sarif_occurrences(
 }
 
 fn print_audit_vulnerabilities(audit_result: &AuditResult) {
-    println!(
+    textln!(
         "\n{} Found {} vulnerability/ies in {} package(s):\n",
         "⚠".yellow().bold(),
         audit_result
@@ -2594,7 +4614,7 @@ fn print_audit_vulnerabilities(audit_result: &AuditResult) {
             Ecosystem::NuGet => "(NuGet)",
         };
 
-        println!(
+        textln!(
             "  {} {}@{} {}",
             "●".red(),
             pkg_result.package.name.bold(),
@@ -2606,7 +4626,10 @@ fn print_audit_vulnerabilities(audit_result: &AuditResult) {
             let severity_str = vuln
                 .severity
                 .as_ref()
-                .map(|s| format!("[{}]", s).red().to_string())
+                .map(|s| match vuln.cvss_score {
+                    Some(score) => format!("[{} {:.1}]", s, score).red().to_string(),
+                    None => format!("[{}]", s).red().to_string(),
+                })
                 .unwrap_or_default();
 
             let summary = vuln
@@ -2621,7 +4644,7 @@ fn print_audit_vulnerabilities(audit_result: &AuditResult) {
                 })
                 .unwrap_or_else(|| "No description".to_string());
 
-            println!(
+            textln!(
                 "    {} {} {} {}",
                 "├──".dimmed(),
                 vuln.id.yellow(),
@@ -2630,7 +4653,7 @@ fn print_audit_vulnerabilities(audit_result: &AuditResult) {
             );
 
             if let Some(fixed) = &vuln.fixed_version {
-                println!(
+                textln!(
                     "    {}   {} {}",
                     "│".dimmed(),
                     "Fixed in:".dimmed(),
@@ -2639,13 +4662,13 @@ fn print_audit_vulnerabilities(audit_result: &AuditResult) {
             }
 
             if let Some(url) = &vuln.url {
-                println!("    {}   {}", "│".dimmed(), url.blue().underline());
+                textln!("    {}   {}", "│".dimmed(), url.blue().underline());
             }
         }
-        println!();
+        textln!();
     }
 
-    println!(
+    textln!(
         "{} {} vulnerable package(s), {} total vulnerability/ies",
         "Summary:".bold(),
         audit_result.vulnerable_packages().to_string().yellow(),
@@ -2653,7 +4676,7 @@ fn print_audit_vulnerabilities(audit_result: &AuditResult) {
     );
 }
 
-fn print_alignment(alignment: &PackageAlignment, _dry_run: bool) {
+fn print_alignment(alignment: &PackageAlignment, target: &AlignTarget) {
     let lang_indicator = match alignment.lang {
         Lang::Python => "",
         Lang::Node => " (npm)",
@@ -2665,14 +4688,23 @@ fn print_alignment(alignment: &PackageAlignment, _dry_run: bool) {
         Lang::PreCommit => " (pre-commit)",
         Lang::Mise => " (mise)",
         Lang::Terraform => " (terraform)",
+        Lang::Docker => " (docker)",
+        Lang::Php => " (composer)",
+        Lang::Jvm => " (gradle)",
+        Lang::Dart => " (pub)",
+        Lang::Runtime => " (runtime)",
     };
 
-    println!(
+    textln!(
         "  {}{}",
         alignment.package_name.bold(),
         lang_indicator.dimmed()
     );
-    println!("    → {} (highest)", alignment.highest_version.green());
+    textln!(
+        "    → {} ({})",
+        alignment.target_version.green(),
+        target.label()
+    );
 
     for occurrence in &alignment.occurrences {
         let location = match occurrence.line_number {
@@ -2681,33 +4713,50 @@ fn print_alignment(alignment: &PackageAlignment, _dry_run: bool) {
         };
 
         if occurrence.has_upper_bound {
-            println!(
+            textln!(
                 "    {} {} {} {}",
                 "├──".dimmed(),
                 location.blue(),
                 occurrence.version.dimmed(),
                 "(constrained, skipped)".yellow()
             );
-        } else if occurrence.version == alignment.highest_version {
-            println!(
+        } else if occurrence.inherits_workspace {
+            textln!(
+                "    {} {} {} {}",
+                "├──".dimmed(),
+                location.blue(),
+                occurrence.version.dimmed(),
+                "(inherited from workspace)".dimmed()
+            );
+        } else if occurrence.version == alignment.target_version {
+            textln!(
                 "    {} {} {} {}",
                 "├──".dimmed(),
                 location.blue(),
                 occurrence.version.green(),
                 "(already aligned)".dimmed()
             );
+        } else if alignment.is_downgrade(occurrence) {
+            textln!(
+                "    {} {} {} → {} {}",
+                "├──".dimmed(),
+                location.blue(),
+                occurrence.version.red(),
+                alignment.target_version.green(),
+                "(downgrade)".yellow()
+            );
         } else {
-            println!(
+            textln!(
                 "    {} {} {} → {}",
                 "├──".dimmed(),
                 location.blue(),
                 occurrence.version.red(),
-                alignment.highest_version.green()
+                alignment.target_version.green()
             );
         }
     }
 
-    println!();
+    textln!();
 }
 
 fn apply_alignments(alignments: &[&PackageAlignment], full_precision: bool) -> Result<usize> {
@@ -2727,7 +4776,7 @@ fn apply_alignments(alignments: &[&PackageAlignment], full_precision: bool) -> R
                 .push(VersionEdit {
                     package: &alignment.package_name,
                     old_version: &occurrence.version,
-                    new_version: &alignment.highest_version,
+                    new_version: &alignment.target_version,
                     line_num: occurrence.line_number,
                 });
         }
@@ -2836,9 +4885,17 @@ fn apply_version_updates(
                 apply_requirements_version(&mut document, update, &target_version)
             }
             FileType::PyProject => apply_pyproject_version(&mut document, update, &target_version),
-            FileType::PackageJson => {
+            FileType::Pipfile => apply_pipfile_version(&mut document, update, &target_version),
+            FileType::SetupPy => apply_setup_py_version(&mut document, update, &target_version),
+            FileType::CondaEnv => apply_conda_env_version(&mut document, update, &target_version),
+            // Composer constraints share npm's `"name": "^1.2"` shape.
+            FileType::PackageJson | FileType::ComposerJson => {
                 apply_package_json_version(&mut document, update, &target_version)
             }
+            // pubspec entries share the catalog's `name: ^1.2.0` shape.
+            FileType::PnpmWorkspace | FileType::Pubspec => {
+                apply_pnpm_workspace_version(&mut document, update, &target_version)
+            }
             FileType::CargoToml => apply_cargo_toml_version(&mut document, update, &target_version),
             FileType::GoMod => apply_go_mod_version(&mut document, update, &target_version),
             FileType::Gemfile => apply_gemfile_version(&mut document, update, &target_version),
@@ -2856,6 +4913,13 @@ fn apply_version_updates(
             FileType::TerraformTf => {
                 apply_terraform_version(&mut document, update, &target_version)
             }
+            // A tag is written whole: its precision and variant come from the registry.
+            FileType::Dockerfile => apply_dockerfile_version(&mut document, update),
+            // Maven versions are exact, so the new version is written whole too.
+            FileType::GradleCatalog => apply_gradle_catalog_version(&mut document, update),
+            FileType::Nvmrc | FileType::PythonVersion => {
+                apply_runtime_version(&mut document, update, &target_version)
+            }
         };
     }
 
@@ -2963,19 +5027,92 @@ where
     false
 }
 
-fn apply_requirements_version(
+fn apply_requirements_version(
+    document: &mut TextDocument,
+    update: &VersionEdit<'_>,
+    target_version: &str,
+) -> bool {
+    let pattern = format!(
+        r"^(\s*{}(?:\[[^\]]*\])?\s*(?:===|==|>=|<=|~=|!=|>|<)\s*){}",
+        regex::escape(update.package),
+        regex::escape(update.old_version)
+    );
+    let re = regex::Regex::new(&pattern).unwrap();
+    let replacement = format!("${{1}}{}", target_version);
+
+    apply_line_replacement(document, update.line_num, |line| {
+        replace_first_match(line, &re, &replacement)
+    })
+}
+
+fn apply_pipfile_version(
+    document: &mut TextDocument,
+    update: &VersionEdit<'_>,
+    target_version: &str,
+) -> bool {
+    // `name = "==1.0"`, `name = {version = "==1.0", ...}`, or the `version`
+    // key of a `[packages.name]` sub-table (which the line number points at).
+    let pattern = format!(
+        r#"(^\s*(?:"?{}"?\s*=\s*(?:\{{[^}}]*version\s*=\s*)?|version\s*=\s*)["'](?:===|==|>=|<=|~=|!=|>|<)?\s*){}"#,
+        regex::escape(update.package),
+        regex::escape(update.old_version)
+    );
+    let re = regex::Regex::new(&pattern).unwrap();
+    let replacement = format!("${{1}}{}", target_version);
+    apply_line_replacement(document, update.line_num, |line| {
+        replace_first_match(line, &re, &replacement)
+    })
+}
+
+fn apply_setup_py_version(
+    document: &mut TextDocument,
+    update: &VersionEdit<'_>,
+    target_version: &str,
+) -> bool {
+    // A quoted requirement string such as `"requests[socks]>=2.0,<3"`.
+    let pattern = format!(
+        r#"(["']\s*{}\s*(?:\[[^\]]*\])?\s*(?:===|==|>=|<=|~=|!=|>|<)\s*){}"#,
+        regex::escape(update.package),
+        regex::escape(update.old_version)
+    );
+    let re = regex::Regex::new(&pattern).unwrap();
+    let replacement = format!("${{1}}{}", target_version);
+    apply_line_replacement(document, update.line_num, |line| {
+        replace_first_match(line, &re, &replacement)
+    })
+}
+
+fn apply_conda_env_version(
+    document: &mut TextDocument,
+    update: &VersionEdit<'_>,
+    target_version: &str,
+) -> bool {
+    // A `- pip:` list item such as `- requests>=2.0` or `- "flask[async]==3.0"`.
+    let pattern = format!(
+        r#"(^\s*-\s*["']?{}\s*(?:\[[^\]]*\])?\s*(?:===|==|>=|<=|~=|!=|>|<)\s*){}"#,
+        regex::escape(update.package),
+        regex::escape(update.old_version)
+    );
+    let re = regex::Regex::new(&pattern).unwrap();
+    let replacement = format!("${{1}}{}", target_version);
+    apply_line_replacement(document, update.line_num, |line| {
+        replace_first_match(line, &re, &replacement)
+    })
+}
+
+fn apply_pnpm_workspace_version(
     document: &mut TextDocument,
     update: &VersionEdit<'_>,
     target_version: &str,
 ) -> bool {
+    // A catalog entry such as `react: ^18.2.0` or `"@types/node": '~20.11.0'`.
     let pattern = format!(
-        r"^(\s*{}(?:\[[^\]]*\])?\s*(?:==|>=|<=|~=|!=|>|<)\s*){}",
+        r#"(^\s*["']?{}["']?\s*:\s*["']?(?:\^|~|>=)?){}"#,
         regex::escape(update.package),
         regex::escape(update.old_version)
     );
     let re = regex::Regex::new(&pattern).unwrap();
     let replacement = format!("${{1}}{}", target_version);
-
     apply_line_replacement(document, update.line_num, |line| {
         replace_first_match(line, &re, &replacement)
     })
@@ -2987,7 +5124,7 @@ fn apply_pyproject_version(
     target_version: &str,
 ) -> bool {
     let pep621_pattern = format!(
-        r#"({}(?:\[[^\]]*\])?\s*(?:==|>=|<=|~=|!=|>|<)\s*){}"#,
+        r#"({}(?:\[[^\]]*\])?\s*(?:===|==|>=|<=|~=|!=|>|<)\s*){}"#,
         regex::escape(update.package),
         regex::escape(update.old_version)
     );
@@ -3083,14 +5220,40 @@ fn replace_cargo_table_version_assignment(
     replace_first_match(line, &re, &replacement)
 }
 
-fn is_cargo_dependency_header(line: &str, package: &str) -> bool {
+fn cargo_section_header(line: &str) -> Option<&str> {
     let trimmed = line.trim();
-    if !trimmed.starts_with('[') || !trimmed.ends_with(']') {
-        return false;
-    }
+    (trimmed.starts_with('[') && trimmed.ends_with(']') && !trimmed.starts_with("[["))
+        .then(|| &trimmed[1..trimmed.len() - 1])
+}
+
+fn is_cargo_dependency_header(line: &str, package: &str) -> bool {
+    cargo_section_header(line)
+        .and_then(|section| section.rsplit_once('.'))
+        .is_some_and(|(parent, name)| {
+            name.trim_matches(['"', '\'']) == package
+                && CargoTomlUpdater::is_dependency_table(parent)
+        })
+}
 
-    let section = &trimmed[1..trimmed.len() - 1];
-    section.contains("dependencies") && section.rsplit('.').next() == Some(package)
+/// For each line, whether it sits directly inside a dependency table, so
+/// version-like values under `[package.metadata.*]`, `[features]` or
+/// `[lints]` are never mistaken for dependency versions.
+fn cargo_dependency_lines(document: &TextDocument) -> Vec<bool> {
+    let mut in_dependencies = false;
+    document
+        .lines
+        .iter()
+        .map(|line| {
+            if let Some(section) = cargo_section_header(line) {
+                in_dependencies = CargoTomlUpdater::is_dependency_table(section);
+                return false;
+            }
+            if line.trim_start().starts_with("[[") {
+                in_dependencies = false;
+            }
+            in_dependencies
+        })
+        .collect()
 }
 
 fn replace_cargo_version_in_following_table(
@@ -3116,6 +5279,7 @@ fn replace_cargo_version_in_following_table(
 
 fn cargo_replacement_candidate(
     document: &TextDocument,
+    dependency_lines: &[bool],
     start_idx: usize,
     update: &VersionEdit<'_>,
     target_version: &str,
@@ -3124,12 +5288,14 @@ fn cargo_replacement_candidate(
         return None;
     }
 
-    if let Some(updated) = replace_cargo_inline_dependency_version(
-        &document.lines[start_idx],
-        update.package,
-        update.old_version,
-        target_version,
-    ) {
+    if dependency_lines[start_idx]
+        && let Some(updated) = replace_cargo_inline_dependency_version(
+            &document.lines[start_idx],
+            update.package,
+            update.old_version,
+            target_version,
+        )
+    {
         return Some((start_idx, updated));
     }
 
@@ -3147,6 +5313,7 @@ fn cargo_replacement_candidate(
 
 fn apply_unique_cargo_replacement(
     document: &mut TextDocument,
+    dependency_lines: &[bool],
     skip_idx: Option<usize>,
     update: &VersionEdit<'_>,
     target_version: &str,
@@ -3158,9 +5325,13 @@ fn apply_unique_cargo_replacement(
             continue;
         }
 
-        if let Some(found) =
-            cargo_replacement_candidate(document, start_idx, update, target_version)
-        {
+        if let Some(found) = cargo_replacement_candidate(
+            document,
+            dependency_lines,
+            start_idx,
+            update,
+            target_version,
+        ) {
             if candidate.is_some() {
                 return false;
             }
@@ -3182,9 +5353,11 @@ fn apply_cargo_toml_version(
     update: &VersionEdit<'_>,
     target_version: &str,
 ) -> bool {
+    let dependency_lines = cargo_dependency_lines(document);
+
     if let Some(idx) = line_index(update.line_num) {
         if let Some((line_idx, updated)) =
-            cargo_replacement_candidate(document, idx, update, target_version)
+            cargo_replacement_candidate(document, &dependency_lines, idx, update, target_version)
         {
             document.lines[line_idx] = updated;
             return true;
@@ -3192,6 +5365,7 @@ fn apply_cargo_toml_version(
 
         return apply_unique_cargo_replacement(
             document,
+            &dependency_lines,
             (idx < document.lines.len()).then_some(idx),
             update,
             target_version,
@@ -3200,7 +5374,7 @@ fn apply_cargo_toml_version(
 
     for idx in 0..document.lines.len() {
         if let Some((line_idx, updated)) =
-            cargo_replacement_candidate(document, idx, update, target_version)
+            cargo_replacement_candidate(document, &dependency_lines, idx, update, target_version)
         {
             document.lines[line_idx] = updated;
             return true;
@@ -3413,6 +5587,21 @@ fn apply_tool_versions_version(
     })
 }
 
+fn apply_runtime_version(
+    document: &mut TextDocument,
+    update: &VersionEdit<'_>,
+    target_version: &str,
+) -> bool {
+    // A bare version at the start of the line, optionally `v`-prefixed (`.nvmrc`)
+    let pattern = format!(r"^(\s*v?){}(\s|$)", regex::escape(update.old_version));
+    let re = regex::Regex::new(&pattern).unwrap();
+    let replacement = format!("${{1}}{}${{2}}", target_version);
+
+    apply_line_replacement(document, update.line_num, |line| {
+        replace_first_match(line, &re, &replacement)
+    })
+}
+
 fn apply_terraform_version(
     document: &mut TextDocument,
     update: &VersionEdit<'_>,
@@ -3430,6 +5619,35 @@ fn apply_terraform_version(
     })
 }
 
+fn apply_dockerfile_version(document: &mut TextDocument, update: &VersionEdit<'_>) -> bool {
+    // `FROM [--platform=...] [docker.io/]image:tag [AS stage]`
+    let pattern = format!(
+        r#"(?i)(^\s*FROM\s+(?:--\S+\s+)*(?:(?:index\.)?docker\.io/)?(?:library/)?{}:){}(\s|$)"#,
+        regex::escape(update.package),
+        regex::escape(update.old_version)
+    );
+    let re = regex::Regex::new(&pattern).unwrap();
+    let replacement = format!("${{1}}{}${{2}}", update.new_version);
+
+    apply_line_replacement(document, update.line_num, |line| {
+        replace_first_match(line, &re, &replacement)
+    })
+}
+
+fn apply_gradle_catalog_version(document: &mut TextDocument, update: &VersionEdit<'_>) -> bool {
+    // `key = "1.2.3"` in `[versions]`; the line number points at it.
+    let pattern = format!(
+        r#"(^\s*["']?[\w.-]+["']?\s*=\s*["']){}(["'])"#,
+        regex::escape(update.old_version)
+    );
+    let re = regex::Regex::new(&pattern).unwrap();
+    let replacement = format!("${{1}}{}${{2}}", update.new_version);
+
+    apply_line_replacement(document, update.line_num, |line| {
+        replace_first_match(line, &re, &replacement)
+    })
+}
+
 /// Filter configuration for update types
 #[derive(Clone, Copy)]
 struct UpdateFilter {
@@ -3508,6 +5726,7 @@ fn count_updates_by_type(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn print_file_result(
     path: &str,
     file_type: FileType,
@@ -3516,21 +5735,54 @@ fn print_file_result(
     filter: UpdateFilter,
     verbose: bool,
     cooldown_policy: Option<&CooldownPolicy>,
+    link_types: bool,
 ) {
     if result.updated.is_empty()
+        && result.not_applied.is_empty()
+        && result.deferred.is_empty()
         && result.pinned.is_empty()
         && result.ignored.is_empty()
+        && result.skipped_sources.is_empty()
         && result.errors.is_empty()
         && result.warnings.is_empty()
         && result.held_back.is_empty()
         && result.skipped_by_cooldown.is_empty()
+        && result.offline_skipped.is_empty()
+        && result.timed_out.is_empty()
     {
         return;
     }
 
     let action = if dry_run { "Would update" } else { "Updated" };
 
-    for (package, old, new, line_num) in &result.updated {
+    // An `@types/foo` update whose runtime package `foo` is updated in the
+    // same file is labelled with it, and with --link-types printed below it.
+    let types_of = |package: &str| {
+        if file_type.lang() != Lang::Node {
+            return None;
+        }
+        PackageJsonUpdater::runtime_package_for_types(package)
+            .filter(|runtime| result.updated.iter().any(|(name, ..)| name == runtime))
+    };
+    let mut ordered = Vec::new();
+    for update in &result.updated {
+        if link_types && types_of(&update.0).is_some() {
+            continue;
+        }
+        ordered.push((update, false));
+        if link_types && file_type.lang() == Lang::Node {
+            let types = PackageJsonUpdater::types_package_for(&update.0);
+            ordered.extend(
+                result
+                    .updated
+                    .iter()
+                    .filter(|(name, ..)| Some(name) == types.as_ref())
+                    .map(|types_update| (types_update, true)),
+            );
+        }
+    }
+
+    for ((package, old, new, line_num), linked) in ordered {
         let update_type = classify_update(old, new);
 
         // Skip if filtered out
@@ -3549,15 +5801,57 @@ fn print_file_result(
             UpdateType::Minor => String::new(),
             UpdateType::Patch => String::new(),
         };
+        let types_note = types_of(package)
+            .map(|runtime| format!(" (types for {runtime})").dimmed().to_string())
+            .unwrap_or_default();
+        let marker = if linked { "  ↳ " } else { "" };
 
-        println!(
-            "{} {} {} {} → {}{}",
+        textln!(
+            "{}{} {} {} {} → {}{}{}",
+            marker,
             location.blue().underline(),
             action.green(),
             package.bold(),
             old.dimmed(),
             new.green(),
-            type_indicator
+            type_indicator,
+            types_note
+        );
+    }
+
+    // Updates the config's auto_apply policy left for a manual decision
+    for (package, old, new, line_num) in &result.not_applied {
+        let location = match line_num {
+            Some(n) => format!("{}:{}:", path, n),
+            None => format!("{}:", path),
+        };
+
+        textln!(
+            "{} {} {} {} → {} {}",
+            location.blue().underline(),
+            "Available".yellow(),
+            package.bold(),
+            old.dimmed(),
+            new.yellow(),
+            "(not applied)".dimmed()
+        );
+    }
+
+    // Updates --newest-only left for a later run
+    for (package, old, new, line_num) in &result.deferred {
+        let location = match line_num {
+            Some(n) => format!("{}:{}:", path, n),
+            None => format!("{}:", path),
+        };
+
+        textln!(
+            "{} {} {} {} → {} {}",
+            location.blue().underline(),
+            "Deferred".yellow(),
+            package.bold(),
+            old.dimmed(),
+            new.yellow(),
+            "(--newest-only)".dimmed()
         );
     }
 
@@ -3569,7 +5863,7 @@ fn print_file_result(
             None => format!("{}:", path),
         };
 
-        println!(
+        textln!(
             "{} {} {} {} → {} {}",
             location.blue().underline(),
             pinned_action.cyan(),
@@ -3599,7 +5893,7 @@ fn print_file_result(
                 cooldown,
                 now,
             );
-            println!("{} {}", file_location.blue().underline(), line.yellow());
+            textln!("{} {}", file_location.blue().underline(), line.yellow());
         }
 
         for (package, _current, skipped_latest, skipped_pub_at) in &result.skipped_by_cooldown {
@@ -3610,7 +5904,7 @@ fn print_file_result(
                 cooldown,
                 now,
             );
-            println!("{} {}", file_location.blue().underline(), line.dimmed());
+            textln!("{} {}", file_location.blue().underline(), line.dimmed());
         }
     }
 
@@ -3622,7 +5916,7 @@ fn print_file_result(
                 None => format!("{}:", path),
             };
 
-            println!(
+            textln!(
                 "{} {} {} {} {}",
                 location.blue().underline(),
                 "Skipped".dimmed(),
@@ -3631,29 +5925,126 @@ fn print_file_result(
                 "(ignored)".dimmed()
             );
         }
+
+        for (package, reason, line_num) in &result.skipped_sources {
+            let location = match line_num {
+                Some(n) => format!("{}:{}:", path, n),
+                None => format!("{}:", path),
+            };
+
+            textln!(
+                "{} {} {} {}",
+                location.blue().underline(),
+                "Skipped".dimmed(),
+                package.bold(),
+                format!("({reason})").dimmed()
+            );
+        }
     }
 
-    for error in &result.errors {
+    print_file_notes(path, result);
+}
+
+/// Print a file's errors, warnings, and offline and --max-runtime skips.
+fn print_file_notes(path: &str, result: &UpdateResult) {
+    print_file_errors(path, result);
+
+    for warning in &result.warnings {
         let location = format!("{}:", path);
         eprintln!(
             "{} {} {}",
             location.blue().underline(),
-            "Error:".red(),
-            error
+            "Warning:".yellow(),
+            warning
         );
     }
 
-    for warning in &result.warnings {
+    for skipped in result.offline_skipped.iter().chain(&result.timed_out) {
         let location = format!("{}:", path);
         eprintln!(
             "{} {} {}",
             location.blue().underline(),
-            "Warning:".yellow(),
-            warning
+            "Skipped".dimmed(),
+            skipped.dimmed()
+        );
+    }
+}
+
+/// Print a file's errors on stderr. All `--quiet` shows of a file.
+fn print_file_errors(path: &str, result: &UpdateResult) {
+    for error in &result.errors {
+        let location = format!("{}:", path);
+        eprintln!(
+            "{} {} {}",
+            location.blue().underline(),
+            "Error:".red(),
+            error
         );
     }
 }
 
+/// Print the `--diff` view of a file: a colored unified diff of the rewrite,
+/// followed by the file's errors and warnings.
+fn print_file_diff(path: &str, result: &UpdateResult) {
+    if let Some(change) = &result.content_change {
+        print!("{}", format_file_diff(path, change));
+    }
+    print_file_notes(path, result);
+}
+
+/// Render `change` as a unified diff with three lines of context.
+fn format_file_diff(path: &str, change: &ContentChange) -> String {
+    let diff = TextDiff::from_lines(&change.original, &change.updated);
+    let mut out = format!(
+        "{}\n{}\n",
+        format!("--- a/{path}").bold(),
+        format!("+++ b/{path}").bold()
+    );
+    for hunk in diff.unified_diff().context_radius(3).iter_hunks() {
+        out.push_str(&format!("{}\n", hunk.header().to_string().cyan()));
+        for line in hunk.iter_changes() {
+            let text = line.value().trim_end_matches(['\r', '\n']);
+            let rendered = match line.tag() {
+                ChangeTag::Delete => format!("-{text}").red(),
+                ChangeTag::Insert => format!("+{text}").green(),
+                ChangeTag::Equal => format!(" {text}").normal(),
+            };
+            out.push_str(&format!("{rendered}\n"));
+        }
+    }
+    out
+}
+
+/// The single `--quiet` summary line: updates and errors across the run.
+fn print_quiet_summary(
+    result: &UpdateResult,
+    file_count: usize,
+    changed_files: usize,
+    dry_run: bool,
+    filter: UpdateFilter,
+) {
+    let updates = count_updates_by_type(&result.updated, filter).3;
+    let mut line = if updates == 0 && result.errors.is_empty() {
+        format!(
+            "Scanned {} file(s), all dependencies up to date",
+            file_count
+        )
+    } else if updates == 0 {
+        format!("Scanned {} file(s)", file_count)
+    } else {
+        format!(
+            "{} {} package(s) in {} file(s)",
+            if dry_run { "Would update" } else { "Updated" },
+            updates,
+            changed_files
+        )
+    };
+    if !result.errors.is_empty() {
+        line.push_str(&format!(", {} error(s)", result.errors.len()));
+    }
+    textln!("{}", line);
+}
+
 fn print_summary(
     result: &UpdateResult,
     file_count: usize,
@@ -3675,8 +6066,12 @@ fn print_summary(
         && pinned_count == 0
         && held_back_count == 0
         && skipped_cooldown_count == 0
+        && result.not_applied.is_empty()
+        && result.deferred.is_empty()
+        && result.offline_skipped.is_empty()
+        && result.timed_out.is_empty()
     {
-        println!(
+        textln!(
             "{} Scanned {} file(s), all dependencies up to date",
             "✓".green(),
             file_count
@@ -3704,7 +6099,7 @@ fn print_summary(
         };
 
         if filtered_total > 0 {
-            println!(
+            textln!(
                 "{} {} package(s){} in {} file(s), {} up to date",
                 action,
                 filtered_total.to_string().green().bold(),
@@ -3717,65 +6112,357 @@ fn print_summary(
         // Show pinned count
         if pinned_count > 0 {
             let pinned_action = if dry_run { "Would pin" } else { "Pinned" };
-            println!(
+            textln!(
                 "{} {} package(s) to configured versions",
                 pinned_action,
                 pinned_count.to_string().cyan().bold()
             );
         }
 
+        // Show updates left unwritten by the auto_apply policy
+        if !result.not_applied.is_empty() {
+            textln!(
+                "{} {} update(s) not applied (auto_apply policy)",
+                "Available".yellow(),
+                result.not_applied.len().to_string().yellow().bold()
+            );
+        }
+
+        // Show updates left for a later run by --newest-only
+        if !result.deferred.is_empty() {
+            textln!(
+                "{} {} update(s) deferred to a later run (--newest-only)",
+                "Deferred".yellow(),
+                result.deferred.len().to_string().yellow().bold()
+            );
+        }
+
         // Show held-back count (cooldown caused selection of older safe version)
         if held_back_count > 0 {
-            println!(
+            textln!(
                 "{} {} package(s) held back by cooldown",
                 "Held back".yellow(),
                 held_back_count.to_string().yellow().bold()
             );
         }
-
-        // Show skipped-by-cooldown count (no version old enough)
-        if skipped_cooldown_count > 0 {
-            println!(
-                "{} {} package(s) skipped (cooldown)",
-                "Skipped".dimmed(),
-                skipped_cooldown_count.to_string().dimmed()
-            );
+
+        // Show skipped-by-cooldown count (no version old enough)
+        if skipped_cooldown_count > 0 {
+            textln!(
+                "{} {} package(s) skipped (cooldown)",
+                "Skipped".dimmed(),
+                skipped_cooldown_count.to_string().dimmed()
+            );
+        }
+    }
+
+    // Show ignored count (informational)
+    if ignored_count > 0 {
+        textln!(
+            "{} {} package(s) per config",
+            "Skipped".dimmed(),
+            ignored_count.to_string().dimmed()
+        );
+    }
+
+    if !result.offline_skipped.is_empty() {
+        textln!(
+            "{} {} package(s) with no cached version (offline)",
+            "Skipped".dimmed(),
+            result.offline_skipped.len().to_string().dimmed()
+        );
+    }
+
+    if !result.timed_out.is_empty() {
+        textln!(
+            "{} {} package(s) not checked before --max-runtime ran out",
+            "Skipped".yellow(),
+            result.timed_out.len().to_string().yellow()
+        );
+    }
+
+    if !result.errors.is_empty() {
+        eprintln!(
+            "{} error(s) occurred",
+            result.errors.len().to_string().red().bold()
+        );
+    }
+
+    filtered_total
+}
+
+/// `--backup`: drop the backups an earlier run left for `paths`, then back up
+/// every file this run writes. A dry run writes nothing and keeps them.
+fn prepare_backups<'a>(cli: &Cli, paths: impl IntoIterator<Item = &'a Path>, dry_run: bool) {
+    if cli.backup && !dry_run {
+        upd::updater::discard_backups(paths);
+        upd::updater::enable_backups();
+    }
+}
+
+/// `upd rollback`: move the `--backup` copies under the scanned paths back
+/// over their files.
+fn run_rollback(cli: &Cli) -> Result<()> {
+    let paths = {
+        let explicit = cli.get_paths();
+        if explicit.is_empty() {
+            vec![PathBuf::from(".")]
+        } else {
+            explicit
+        }
+    };
+    let root_config = resolve_root_config(cli, &paths)?;
+    let files = with_included_requirements(discover_files_with(
+        &paths,
+        &cli.langs,
+        DiscoverOptions {
+            no_ignore: cli.no_ignore,
+            include_hidden: cli.include_hidden,
+            verbose: cli.verbose,
+            exclude: &root_config.config.exclude,
+            relative_exclude: &cli.exclude,
+        },
+    ));
+
+    let mut restored = Vec::new();
+    for (path, _) in &files {
+        if upd::updater::restore_backup(path)? {
+            restored.push(path.display().to_string());
+        }
+    }
+
+    if effective_json_mode(cli) {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "command": "rollback",
+                "restored": restored,
+            }))?
+        );
+    } else if restored.is_empty() {
+        textln!("{}", "Nothing to restore.".yellow());
+    } else if !cli.quiet {
+        for path in &restored {
+            textln!("{} Restored {}", "✓".green(), path);
+        }
+    }
+    Ok(())
+}
+
+/// `--update-lockfile-only`: regenerate the lockfile of every discovered
+/// manifest without running the updaters. Dry-run only lists them.
+fn run_lockfile_only(cli: &Cli) -> Result<()> {
+    let paths = resolve_scan_paths(cli).map_err(|msg| anyhow::anyhow!(msg))?;
+    let dry_run = cli.is_effective_dry_run();
+    let json_mode = effective_json_mode(cli);
+    let text_mode = !json_mode || cli.progress_stderr;
+    if json_mode {
+        // The package managers' progress lines must not mix into the JSON.
+        upd::output::set_text_stream(upd::output::TextStream::Stderr);
+    }
+    let root_config = resolve_root_config(cli, &paths)?;
+    let files = discover_files_with(
+        &paths,
+        &cli.langs,
+        DiscoverOptions {
+            no_ignore: cli.no_ignore,
+            include_hidden: cli.include_hidden,
+            verbose: cli.verbose,
+            exclude: &root_config.config.exclude,
+            relative_exclude: &cli.exclude,
+        },
+    );
+
+    // Several manifests can share a lockfile (Terraform `.tf` files); each
+    // lockfile is regenerated once, from the first manifest that owns it.
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    let mut records = Vec::new();
+    let mut errors = Vec::new();
+    for (manifest, _) in &files {
+        let dir = manifest.parent().unwrap_or(Path::new("."));
+        let lockfiles: Vec<PathBuf> = detect_lockfiles(manifest)
+            .into_iter()
+            .map(|lockfile| dir.join(lockfile.filename()))
+            .filter(|lockfile| seen.insert(lockfile.clone()))
+            .collect();
+        if lockfiles.is_empty() {
+            continue;
+        }
+
+        // An empty change list selects each tool's manifest-wide refresh.
+        let failures = if dry_run {
+            Vec::new()
+        } else {
+            regenerate_lockfiles(manifest, &[], cli.allow_scripts, cli.verbose && text_mode)
+                .error_messages()
+        };
+        for lockfile in lockfiles {
+            let lockfile = lockfile.display().to_string();
+            if dry_run && text_mode && !cli.quiet {
+                textln!("Would regenerate {}", lockfile);
+            }
+            records.push(serde_json::json!({
+                "lockfile": lockfile,
+                "manifest": manifest.display().to_string(),
+                "regenerated": !dry_run && failures.is_empty(),
+            }));
+        }
+        for message in failures {
+            eprintln!("{}", format!("error: {message}").red());
+            errors.push(message);
         }
     }
 
-    // Show ignored count (informational)
-    if ignored_count > 0 {
+    if json_mode {
         println!(
-            "{} {} package(s) per config",
-            "Skipped".dimmed(),
-            ignored_count.to_string().dimmed()
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "command": "update",
+                "mode": if dry_run { "dry-run" } else { "applied" },
+                "lockfiles": records,
+                "errors": errors,
+            }))?
         );
     }
-
-    if !result.errors.is_empty() {
-        eprintln!(
-            "{} error(s) occurred",
-            result.errors.len().to_string().red().bold()
-        );
+    if text_mode && !cli.quiet && records.is_empty() {
+        textln!("{}", "No lockfiles found.".yellow());
     }
-
-    filtered_total
+    if !errors.is_empty() {
+        std::process::exit(2);
+    }
+    Ok(())
 }
 
 fn clean_cache() -> Result<()> {
     Cache::clean()?;
-    println!("{}", "Cache cleaned successfully.".green());
+    textln!("{}", "Cache cleaned successfully.".green());
+    Ok(())
+}
+
+fn merge_reports(paths: &[PathBuf], json_mode: bool) -> Result<()> {
+    let reports = paths
+        .iter()
+        .map(|path| Ok((path.display().to_string(), upd::merge_reports::load(path)?)))
+        .collect::<Result<Vec<_>>>()?;
+    let merged = upd::merge_reports::merge(&reports);
+
+    if json_mode {
+        println!("{}", serde_json::to_string_pretty(&merged)?);
+        return Ok(());
+    }
+
+    let counts = &merged.summary.counts;
+    textln!(
+        "Merged {} report(s): {} update(s) ({} major, {} minor, {} patch), {} error(s)",
+        merged.summary.reports,
+        counts.updates_total.to_string().bold(),
+        counts.updates_major,
+        counts.updates_minor,
+        counts.updates_patch,
+        counts.errors
+    );
+    textln!("\n{}", "Repos:".bold());
+    for repo in &merged.repos {
+        textln!(
+            "  {}  {} update(s) ({} major, {} minor, {} patch) in {} file(s), {} error(s)",
+            repo.repo,
+            repo.counts.updates_total,
+            repo.counts.updates_major,
+            repo.counts.updates_minor,
+            repo.counts.updates_patch,
+            repo.counts.files_scanned,
+            repo.counts.errors
+        );
+    }
+    if !merged.packages.is_empty() {
+        textln!("\n{}", "Packages:".bold());
+    }
+    for package in &merged.packages {
+        textln!(
+            "  {} ({}) in {} repo(s)",
+            package.package.bold(),
+            package.lang,
+            package.repos
+        );
+        for update in &package.updates {
+            textln!(
+                "    {}  {}  {} → {} ({})",
+                update.repo,
+                update.file,
+                update.current,
+                update.latest,
+                update.bump
+            );
+        }
+    }
+    Ok(())
+}
+
+fn cache_stats(json_mode: bool, ttl: std::time::Duration) -> Result<()> {
+    let summary = Cache::load()?.with_ttl(ttl).summary();
+    let size = Cache::disk_size()?;
+    let timestamp = |secs: Option<u64>| {
+        secs.and_then(|s| chrono::DateTime::<chrono::Utc>::from_timestamp(s as i64, 0))
+    };
+    let (oldest, newest) = (timestamp(summary.oldest), timestamp(summary.newest));
+
+    if json_mode {
+        let registries: serde_json::Map<String, serde_json::Value> = summary
+            .entries
+            .iter()
+            .map(|(name, count)| (name.to_string(), serde_json::json!(count)))
+            .collect();
+        let output = serde_json::json!({
+            "command": "cache-stats",
+            "entries": summary.total_entries(),
+            "registries": registries,
+            "expired": summary.expired,
+            "size_bytes": size.unwrap_or(0),
+            "oldest": oldest.map(|t| t.to_rfc3339()),
+            "newest": newest.map(|t| t.to_rfc3339()),
+            "hits": summary.counters.hits,
+            "misses": summary.counters.misses,
+            "hit_rate": summary.hit_rate(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    textln!(
+        "{} cached version(s), {} expired, {} bytes on disk",
+        summary.total_entries().to_string().bold(),
+        summary.expired,
+        size.unwrap_or(0)
+    );
+    for (name, count) in summary.entries.iter().filter(|(_, count)| *count > 0) {
+        textln!("  {:<16} {}", name, count);
+    }
+    let format_time = |t: Option<chrono::DateTime<chrono::Utc>>| {
+        t.map_or_else(
+            || "-".to_string(),
+            |t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        )
+    };
+    textln!("Oldest entry: {}", format_time(oldest));
+    textln!("Newest entry: {}", format_time(newest));
+    match summary.hit_rate() {
+        Some(rate) => textln!(
+            "Hit rate: {:.1}% ({} hits, {} misses)",
+            rate * 100.0,
+            summary.counters.hits,
+            summary.counters.misses
+        ),
+        None => textln!("Hit rate: - (no lookups recorded)"),
+    }
     Ok(())
 }
 
 async fn self_update(cli: &Cli) -> Result<()> {
-    init_tls(cli)?;
-    println!("Checking for updates...");
+    init_tls(cli, &[])?;
+    textln!("Checking for updates...");
 
     let url = "https://api.github.com/repos/rvben/upd/releases/latest";
-    let client =
-        upd::http::apply(reqwest::Client::builder().timeout(std::time::Duration::from_secs(30)))
-            .build()?;
+    let client = upd::http::apply(reqwest::Client::builder()).build()?;
     let response = client
         .get(url)
         .header("User-Agent", "upd")
@@ -3796,18 +6483,18 @@ async fn self_update(cli: &Cli) -> Result<()> {
     let latest = release.tag_name.trim_start_matches('v');
 
     if latest == VERSION {
-        println!(
+        textln!(
             "{}",
             format!("Already at latest version ({})", VERSION).green()
         );
         return Ok(());
     }
 
-    println!(
+    textln!(
         "{}",
         format!("New version available: {} → {}", VERSION, latest).yellow()
     );
-    println!("To update, run: cargo install upd");
+    textln!("To update, run: cargo install upd");
 
     Ok(())
 }
@@ -4213,6 +6900,81 @@ mod tests {
         assert!(approved_counts.is_empty());
     }
 
+    #[test]
+    fn test_edited_interactive_version_is_written_instead_of_latest() {
+        let scanned_file = ScannedFileResult {
+            path: PathBuf::from("requirements.txt"),
+            file_type: FileType::Requirements,
+            result: UpdateResult {
+                updated: vec![
+                    ("flask".into(), "2.0".into(), "3.0".into(), Some(1)),
+                    ("requests".into(), "2.28.0".into(), "2.32.3".into(), Some(2)),
+                ],
+                ..Default::default()
+            },
+        };
+
+        let edited = PendingUpdate::new(
+            "requirements.txt".into(),
+            Some(1),
+            "flask".into(),
+            "2.0".into(),
+            "3.0".into(),
+            true,
+        );
+        let mut input = std::io::Cursor::new("e\n2.5.1\ny\n");
+        let validate = |update: &PendingUpdate, version: &str| {
+            assert_eq!(update.package, "flask");
+            if is_valid_version(version, Lang::Python) {
+                Ok(())
+            } else {
+                Err(format!("\"{version}\" is not a valid python version"))
+            }
+        };
+        let accepted = PendingUpdate::new(
+            "requirements.txt".into(),
+            Some(2),
+            "requests".into(),
+            "2.28.0".into(),
+            "2.32.3".into(),
+            false,
+        );
+        let decisions = upd::interactive::prompt_all_with(
+            vec![edited, accepted],
+            Some(&validate),
+            &mut input,
+            &mut Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(decisions[0].target_version(), "2.5.1");
+
+        let planned_changes: Vec<_> = scanned_file
+            .result
+            .updated
+            .iter()
+            .map(|update| {
+                PlannedChange::from_update(
+                    scanned_file.path.clone(),
+                    scanned_file.file_type,
+                    update,
+                )
+            })
+            .collect();
+        let mut approved_counts = build_approved_change_counts(&decisions, &planned_changes);
+        let selected = collect_selected_changes_for_file(&scanned_file, &mut approved_counts);
+        assert_eq!(selected[0].kind, ChangeKind::EditedUpdate);
+
+        // The typed version is written as entered, not truncated to `2.0`'s precision.
+        let rewritten = rewrite_selected_changes(
+            "flask==2.0\nrequests==2.28.0\n",
+            &selected,
+            FileType::Requirements,
+            false,
+        )
+        .unwrap();
+        assert_eq!(rewritten, "flask==2.5.1\nrequests==2.32.3\n");
+    }
+
     #[test]
     fn test_file_has_manifest_changes_for_pin_only_results() {
         let result = UpdateResult {
@@ -4260,6 +7022,7 @@ mod tests {
                 id: "RUSTSEC-0000-0000".into(),
                 summary: Some("Test".into()),
                 severity: None,
+                cvss_score: None,
                 url: None,
                 fixed_version: None,
             }],
@@ -4281,6 +7044,7 @@ mod tests {
                 id: "RUSTSEC-0000-0000".into(),
                 summary: Some("Test".into()),
                 severity: None,
+                cvss_score: None,
                 url: None,
                 fixed_version: None,
             }],
@@ -4317,6 +7081,23 @@ mod tests {
         assert!(config.should_ignore("requests"));
     }
 
+    #[test]
+    fn test_load_update_configs_no_config_skips_discovery() {
+        let temp = tempdir().unwrap();
+        std::fs::write(temp.path().join(".updrc.toml"), "ignore = [\"react\"]").unwrap();
+        let file = temp.path().join("package.json");
+        std::fs::write(&file, "{}").unwrap();
+
+        let cli = Cli::try_parse_from(["upd", "--no-config"]).unwrap();
+        let files = vec![(file.clone(), FileType::PackageJson)];
+
+        let configs = load_update_configs(&cli, &files).unwrap();
+        assert!(configs.get(&file).unwrap().is_none());
+
+        let root = resolve_root_config(&cli, std::slice::from_ref(&file)).unwrap();
+        assert!(!root.config.has_config());
+    }
+
     #[test]
     fn test_load_update_configs_resolves_config_per_file() {
         let temp = tempdir().unwrap();
@@ -4349,6 +7130,49 @@ mod tests {
         assert!(!config_b.should_ignore("react"));
     }
 
+    #[test]
+    fn test_load_update_configs_merges_repo_root_config() {
+        let temp = tempdir().unwrap();
+        std::fs::create_dir(temp.path().join(".git")).unwrap();
+        std::fs::write(
+            temp.path().join(".updrc.toml"),
+            "ignore = [\"react\"]\n[pin]\nlodash = \"4.17.20\"\naxios = \"1.6.0\"\n",
+        )
+        .unwrap();
+        let service = temp.path().join("service");
+        std::fs::create_dir_all(&service).unwrap();
+        std::fs::write(
+            service.join(".updrc.toml"),
+            "ignore = [\"vue\"]\n[pin]\nlodash = \"4.17.21\"\n",
+        )
+        .unwrap();
+
+        let root_file = temp.path().join("package.json");
+        let service_file = service.join("package.json");
+        std::fs::write(&root_file, "{}").unwrap();
+        std::fs::write(&service_file, "{}").unwrap();
+
+        let cli = Cli::try_parse_from(["upd"]).unwrap();
+        let files = vec![
+            (root_file.clone(), FileType::PackageJson),
+            (service_file.clone(), FileType::PackageJson),
+        ];
+
+        let configs = load_update_configs(&cli, &files).unwrap();
+        let root_config = configs.get(&root_file).cloned().flatten().unwrap();
+        let service_config = configs.get(&service_file).cloned().flatten().unwrap();
+
+        assert!(root_config.should_ignore("react"));
+        assert!(!root_config.should_ignore("vue"));
+        assert_eq!(root_config.get_pinned_version("lodash"), Some("4.17.20"));
+
+        // The subproject inherits the root rules, its own pins winning.
+        assert!(service_config.should_ignore("react"));
+        assert!(service_config.should_ignore("vue"));
+        assert_eq!(service_config.get_pinned_version("lodash"), Some("4.17.21"));
+        assert_eq!(service_config.get_pinned_version("axios"), Some("1.6.0"));
+    }
+
     #[test]
     fn test_apply_version_updates_pyproject_preserves_additional_constraints() {
         let content = "[project]\ndependencies = [\"django>=3.2,<4\"]\n";
@@ -4412,6 +7236,40 @@ dev = [
         assert!(error.to_string().contains("Failed to apply 1 version edit"));
     }
 
+    #[test]
+    fn test_apply_version_updates_dockerfile_writes_whole_tag() {
+        let content = "FROM --platform=linux/amd64 python:3.11-slim AS builder\nFROM python:3.11\n";
+        let updates = [VersionEdit {
+            package: "python",
+            old_version: "3.11-slim",
+            new_version: "3.13-slim",
+            line_num: Some(1),
+        }];
+
+        let applied =
+            apply_version_updates(content, &updates, FileType::Dockerfile, false).unwrap();
+
+        assert_eq!(
+            applied.content,
+            "FROM --platform=linux/amd64 python:3.13-slim AS builder\nFROM python:3.11\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_version_updates_nvmrc_keeps_v_prefix() {
+        let updates = [VersionEdit {
+            package: "node",
+            old_version: "20.11.0",
+            new_version: "20.18.1",
+            line_num: Some(1),
+        }];
+
+        let applied =
+            apply_version_updates("v20.11.0\n", &updates, FileType::Nvmrc, false).unwrap();
+
+        assert_eq!(applied.content, "v20.18.1\n");
+    }
+
     #[test]
     fn test_apply_version_updates_csproj_targets_selected_multiline_package_only() {
         let content = r#"<Project Sdk="Microsoft.NET.Sdk">
@@ -4473,6 +7331,47 @@ version = "1.0.0"
         );
     }
 
+    #[test]
+    fn test_apply_version_updates_cargo_skips_metadata_features_and_lints() {
+        let content = r#"[package]
+name = "demo"
+version = "1.0.0"
+
+[package.metadata.release]
+version = "1.0.0"
+serde = "1.0.0"
+
+[package.metadata.dependencies.serde]
+version = "1.0.0"
+
+[features]
+serde = ["dep:serde", "serde-1.0.0"]
+
+[lints.rust]
+serde = "1.0.0"
+
+[dependencies]
+serde = { version = "1.0.0", optional = true }
+"#;
+        let updates = [VersionEdit {
+            package: "serde",
+            old_version: "1.0.0",
+            new_version: "1.0.2",
+            line_num: None,
+        }];
+
+        let applied = apply_version_updates(content, &updates, FileType::CargoToml, false).unwrap();
+
+        assert_eq!(applied.applied_count(), 1);
+        assert_eq!(
+            applied.content,
+            content.replace(
+                r#"serde = { version = "1.0.0""#,
+                r#"serde = { version = "1.0.2""#
+            )
+        );
+    }
+
     #[test]
     fn test_apply_version_updates_package_json_uses_unique_fallback_for_duplicate_targets() {
         let content = r#"{
@@ -4515,6 +7414,134 @@ version = "1.0.0"
         );
     }
 
+    #[test]
+    fn test_apply_version_updates_composer_json_keeps_operator() {
+        let content = r#"{
+    "require": {
+        "monolog/monolog": "~2.9",
+        "symfony/console": ">=6.4.0"
+    }
+}
+"#;
+        let updates = [
+            VersionEdit {
+                package: "monolog/monolog",
+                old_version: "2.9",
+                new_version: "2.10.0",
+                line_num: Some(3),
+            },
+            VersionEdit {
+                package: "symfony/console",
+                old_version: "6.4.0",
+                new_version: "7.1.6",
+                line_num: Some(4),
+            },
+        ];
+
+        let applied =
+            apply_version_updates(content, &updates, FileType::ComposerJson, false).unwrap();
+
+        assert_eq!(applied.applied_count(), 2);
+        assert!(applied.content.contains(r#""monolog/monolog": "~2.10""#));
+        assert!(applied.content.contains(r#""symfony/console": ">=7.1.6""#));
+    }
+
+    #[test]
+    fn test_apply_version_updates_pnpm_workspace_keeps_prefix() {
+        let content = "catalog:\n  react: ^18.2.0\n  'lodash': \"4.17.20\"\n";
+        let updates = [
+            VersionEdit {
+                package: "react",
+                old_version: "18.2.0",
+                new_version: "18.3.1",
+                line_num: Some(2),
+            },
+            VersionEdit {
+                package: "lodash",
+                old_version: "4.17.20",
+                new_version: "4.17.21",
+                line_num: Some(3),
+            },
+        ];
+
+        let applied =
+            apply_version_updates(content, &updates, FileType::PnpmWorkspace, false).unwrap();
+
+        assert_eq!(applied.applied_count(), 2);
+        assert_eq!(
+            applied.content,
+            "catalog:\n  react: ^18.3.1\n  'lodash': \"4.17.21\"\n"
+        );
+    }
+
+    #[test]
+    fn test_defer_beyond_limit_keeps_most_impactful_across_files() {
+        let update = |name: &str, old: &str, new: &str| {
+            (name.to_string(), old.to_string(), new.to_string(), None)
+        };
+        let file = |updated| UpdateResult {
+            updated,
+            ..Default::default()
+        };
+        let mut results = vec![
+            (
+                PathBuf::from("requirements.txt"),
+                FileType::Requirements,
+                Ok(file(vec![
+                    update("flask", "3.0.0", "3.0.1"),
+                    update("django", "4.2.0", "5.0.0"),
+                ])),
+            ),
+            (
+                PathBuf::from("package.json"),
+                FileType::PackageJson,
+                Ok(file(vec![
+                    update("lodash", "4.17.20", "4.17.21"),
+                    update("express", "4.17.0", "4.21.0"),
+                ])),
+            ),
+        ];
+
+        defer_beyond_limit(&mut results, 2);
+
+        let names = |updates: &[(String, String, String, Option<usize>)]| {
+            updates.iter().map(|u| u.0.clone()).collect::<Vec<_>>()
+        };
+        let python = results[0].2.as_ref().unwrap();
+        let node = results[1].2.as_ref().unwrap();
+        assert_eq!(names(&python.updated), vec!["django"]);
+        assert_eq!(names(&python.deferred), vec!["flask"]);
+        assert_eq!(names(&node.updated), vec!["express"]);
+        assert_eq!(names(&node.deferred), vec!["lodash"]);
+    }
+
+    #[test]
+    fn test_apply_version_updates_pubspec_keeps_caret() {
+        let content = "dependencies:\n  http: ^1.1.0\n  path: 1.8.3 # pinned\n";
+        let updates = [
+            VersionEdit {
+                package: "http",
+                old_version: "1.1.0",
+                new_version: "1.2.2",
+                line_num: Some(2),
+            },
+            VersionEdit {
+                package: "path",
+                old_version: "1.8.3",
+                new_version: "1.9.0",
+                line_num: Some(3),
+            },
+        ];
+
+        let applied = apply_version_updates(content, &updates, FileType::Pubspec, false).unwrap();
+
+        assert_eq!(applied.applied_count(), 2);
+        assert_eq!(
+            applied.content,
+            "dependencies:\n  http: ^1.2.2\n  path: 1.9.0 # pinned\n"
+        );
+    }
+
     #[test]
     fn test_apply_version_updates_cargo_uses_unique_fallback_for_duplicate_targets() {
         let content = r#"[package]
@@ -4577,6 +7604,7 @@ serde = "1.0.1"
         let alignment = PackageAlignment {
             package_name: "PackageB".into(),
             highest_version: "2.0.0".into(),
+            target_version: "2.0.0".into(),
             occurrences: vec![PackageOccurrence {
                 file_path: file.clone(),
                 file_type: FileType::Csproj,
@@ -4585,6 +7613,7 @@ serde = "1.0.1"
                 has_upper_bound: false,
                 original_name: "PackageB".into(),
                 is_bumpable: true,
+                inherits_workspace: false,
             }],
             lang: Lang::DotNet,
         };
@@ -4622,6 +7651,7 @@ serde = "1.0.1"
             has_upper_bound: false,
             original_name: "Newtonsoft.Json".to_string(),
             is_bumpable: true,
+            inherits_workspace: false,
         }];
 
         let mut packages = HashMap::new();
@@ -4637,6 +7667,123 @@ serde = "1.0.1"
         assert_eq!(audit_pkgs[0].version, "12.0.1");
     }
 
+    #[test]
+    fn test_format_file_diff_emits_unified_hunks() {
+        let change = ContentChange {
+            original: "flask==2.0.0\nrequests==2.32.3\n".to_string(),
+            updated: "flask==3.0.3\nrequests==2.32.3\n".to_string(),
+        };
+        let diff = format_file_diff("requirements.txt", &change);
+        assert!(diff.contains("--- a/requirements.txt"), "{diff}");
+        assert!(diff.contains("+++ b/requirements.txt"), "{diff}");
+        assert!(diff.contains("@@ -1,2 +1,2 @@"), "{diff}");
+        assert!(diff.contains("-flask==2.0.0"), "{diff}");
+        assert!(diff.contains("+flask==3.0.3"), "{diff}");
+        assert!(diff.contains(" requests==2.32.3"), "{diff}");
+    }
+
+    /// `--summary-json` totals equal the merged `UpdateResult`, and the
+    /// per-ecosystem entries partition them.
+    #[test]
+    fn test_summary_json_counts_match_merged_result() {
+        let filter = UpdateFilter::from_cli(&[], None);
+        let python = UpdateResult {
+            updated: vec![
+                ("flask".into(), "2.0.0".into(), "3.0.0".into(), Some(1)),
+                ("requests".into(), "2.31.0".into(), "2.32.0".into(), Some(2)),
+            ],
+            pinned: vec![("django".into(), "4.0".into(), "4.2".into(), Some(3))],
+            errors: vec!["boom".into()],
+            ..Default::default()
+        };
+        let node = UpdateResult {
+            updated: vec![("react".into(), "18.2.0".into(), "18.2.1".into(), Some(4))],
+            ignored: vec![("lodash".into(), "4.17.0".into(), Some(5))],
+            ..Default::default()
+        };
+        let extra_python = UpdateResult {
+            updated: vec![("rich".into(), "13.0.0".into(), "14.0.0".into(), None)],
+            ..Default::default()
+        };
+        let scanned = vec![
+            ScannedFileResult {
+                path: PathBuf::from("requirements.txt"),
+                file_type: FileType::Requirements,
+                result: python.clone(),
+            },
+            ScannedFileResult {
+                path: PathBuf::from("package.json"),
+                file_type: FileType::PackageJson,
+                result: node.clone(),
+            },
+            ScannedFileResult {
+                path: PathBuf::from("pyproject.toml"),
+                file_type: FileType::PyProject,
+                result: extra_python.clone(),
+            },
+        ];
+        let mut merged = UpdateResult::default();
+        merged.merge(python);
+        merged.merge(node);
+        merged.merge(extra_python);
+
+        let summary = build_summary_json(&scanned, filter, true);
+        let (major, minor, patch, total) = count_updates_by_type(&merged.updated, filter);
+        assert_eq!(
+            summary.total,
+            SummaryCounts {
+                updated: total,
+                major,
+                minor,
+                patch,
+                pinned: merged.pinned.len(),
+                ignored: merged.ignored.len(),
+                errors: merged.errors.len(),
+            }
+        );
+        assert_eq!(summary.total.updated, 4);
+        assert_eq!(summary.ecosystems.len(), 2);
+        assert_eq!(summary.ecosystems["python"].updated, 3);
+        assert_eq!(summary.ecosystems["python"].major, 2);
+        assert_eq!(summary.ecosystems["node"].patch, 1);
+        assert_eq!(summary.ecosystems["node"].ignored, 1);
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["mode"], "dry-run");
+        assert_eq!(json["ecosystems"]["python"]["errors"], 1);
+    }
+
+    /// `--include-transitive` adds lockfile-only packages to the audit set
+    /// without duplicating the declared ones.
+    #[test]
+    fn test_add_transitive_audit_packages_appends_locked_only_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("package.json");
+        std::fs::write(&manifest, r#"{"dependencies": {"express": "4.19.2"}}"#).unwrap();
+        std::fs::write(
+            dir.path().join("package-lock.json"),
+            r#"{"lockfileVersion": 3, "packages": {
+                "": {"name": "app"},
+                "node_modules/express": {"version": "4.19.2"},
+                "node_modules/qs": {"version": "6.5.2"}
+            }}"#,
+        )
+        .unwrap();
+
+        let mut audit_pkgs = vec![AuditPackage {
+            name: "express".to_string(),
+            version: "4.19.2".to_string(),
+            ecosystem: Ecosystem::Npm,
+        }];
+        add_transitive_audit_packages(&[(manifest, FileType::PackageJson)], &mut audit_pkgs);
+
+        let names: Vec<(&str, &str)> = audit_pkgs
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+        assert_eq!(names, vec![("express", "4.19.2"), ("qs", "6.5.2")]);
+    }
+
     /// `build_audit_packages` must include Go pseudo-version entries so the OSV
     /// query can find CVEs for the specific commit snapshot. Pseudo-versions are
     /// valid OSV query inputs for the Go ecosystem.
@@ -4656,6 +7803,7 @@ serde = "1.0.1"
             has_upper_bound: false,
             original_name: "golang.org/x/crypto".to_string(),
             is_bumpable: false,
+            inherits_workspace: false,
         }];
 
         let mut packages = HashMap::new();
@@ -0,0 +1,283 @@
+//! Aggregation for `upd merge-reports`.
+//!
+//! Combines the `--format json` reports of separate `upd update` runs (one per
+//! repository) into a single view: totals across every report, a breakdown per
+//! report, and a per-package list of the reports that would update it. Only
+//! the report files are read; nothing is resolved or written.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// The parts of an `upd update --format json` report that are aggregated.
+#[derive(Debug, Deserialize)]
+pub struct InputReport {
+    command: String,
+    #[serde(default)]
+    mode: String,
+    #[serde(default)]
+    files: Vec<InputFile>,
+    #[serde(default)]
+    summary: Counts,
+}
+
+#[derive(Debug, Deserialize)]
+struct InputFile {
+    path: String,
+    #[serde(default)]
+    lang: String,
+    #[serde(default)]
+    updates: Vec<InputUpdate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InputUpdate {
+    package: String,
+    current: String,
+    latest: String,
+    bump: String,
+}
+
+/// Summary counters, read from each report and summed for the merge.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct Counts {
+    #[serde(default)]
+    pub files_scanned: usize,
+    #[serde(default)]
+    pub files_with_changes: usize,
+    #[serde(default)]
+    pub updates_total: usize,
+    #[serde(default)]
+    pub updates_major: usize,
+    #[serde(default)]
+    pub updates_minor: usize,
+    #[serde(default)]
+    pub updates_patch: usize,
+    #[serde(default)]
+    pub errors: usize,
+    #[serde(default)]
+    pub warnings: usize,
+}
+
+impl Counts {
+    fn add(&mut self, other: &Counts) {
+        self.files_scanned += other.files_scanned;
+        self.files_with_changes += other.files_with_changes;
+        self.updates_total += other.updates_total;
+        self.updates_major += other.updates_major;
+        self.updates_minor += other.updates_minor;
+        self.updates_patch += other.updates_patch;
+        self.errors += other.errors;
+        self.warnings += other.warnings;
+    }
+}
+
+/// The aggregated view written by `upd merge-reports`.
+#[derive(Debug, Serialize)]
+pub struct MergedReport {
+    pub command: &'static str,
+    pub summary: MergedSummary,
+    pub repos: Vec<RepoSummary>,
+    pub packages: Vec<PackageSummary>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MergedSummary {
+    pub reports: usize,
+    #[serde(flatten)]
+    pub counts: Counts,
+}
+
+/// One input report; `repo` is the report path as given on the command line.
+#[derive(Debug, Serialize)]
+pub struct RepoSummary {
+    pub repo: String,
+    pub mode: String,
+    #[serde(flatten)]
+    pub counts: Counts,
+}
+
+/// Every update of one package across the merged reports.
+#[derive(Debug, Serialize)]
+pub struct PackageSummary {
+    pub package: String,
+    pub lang: String,
+    /// Number of distinct reports that update this package.
+    pub repos: usize,
+    pub updates: Vec<PackageUpdate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PackageUpdate {
+    pub repo: String,
+    pub file: String,
+    pub current: String,
+    pub latest: String,
+    pub bump: String,
+}
+
+/// Read one `upd update --format json` report.
+pub fn load(path: &Path) -> Result<InputReport> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read report {}", path.display()))?;
+    let report: InputReport = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not an upd JSON report", path.display()))?;
+    if report.command != "update" {
+        anyhow::bail!(
+            "{} is a `{}` report; only `upd update --format json` reports can be merged",
+            path.display(),
+            report.command
+        );
+    }
+    Ok(report)
+}
+
+/// Merge `(repo, report)` pairs, keeping the order the reports were given in.
+///
+/// Packages are grouped by ecosystem and name, most widely updated first.
+pub fn merge(reports: &[(String, InputReport)]) -> MergedReport {
+    let mut total = Counts::default();
+    let mut repos = Vec::new();
+    let mut packages: BTreeMap<(String, String), Vec<PackageUpdate>> = BTreeMap::new();
+
+    for (repo, report) in reports {
+        total.add(&report.summary);
+        repos.push(RepoSummary {
+            repo: repo.clone(),
+            mode: report.mode.clone(),
+            counts: report.summary.clone(),
+        });
+        for file in &report.files {
+            for update in &file.updates {
+                packages
+                    .entry((file.lang.clone(), update.package.clone()))
+                    .or_default()
+                    .push(PackageUpdate {
+                        repo: repo.clone(),
+                        file: file.path.clone(),
+                        current: update.current.clone(),
+                        latest: update.latest.clone(),
+                        bump: update.bump.clone(),
+                    });
+            }
+        }
+    }
+
+    let mut packages: Vec<PackageSummary> = packages
+        .into_iter()
+        .map(|((lang, package), updates)| {
+            let mut distinct: Vec<&str> = updates.iter().map(|u| u.repo.as_str()).collect();
+            distinct.sort_unstable();
+            distinct.dedup();
+            PackageSummary {
+                package,
+                lang,
+                repos: distinct.len(),
+                updates,
+            }
+        })
+        .collect();
+    // Stable sort: ties keep the (lang, package) order of the map.
+    packages.sort_by_key(|package| std::cmp::Reverse(package.repos));
+
+    MergedReport {
+        command: "merge-reports",
+        summary: MergedSummary {
+            reports: reports.len(),
+            counts: total,
+        },
+        repos,
+        packages,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(json: &str) -> InputReport {
+        serde_json::from_str(json).unwrap()
+    }
+
+    const API: &str = r#"{
+        "command": "update",
+        "mode": "dry-run",
+        "files": [{
+            "path": "requirements.txt",
+            "file_type": "requirements",
+            "lang": "python",
+            "updates": [
+                {"package": "requests", "current": "2.31.0", "latest": "2.32.3", "bump": "minor"},
+                {"package": "django", "current": "4.2.0", "latest": "5.1.0", "bump": "major"}
+            ],
+            "pinned": [], "ignored": [], "errors": [], "warnings": []
+        }],
+        "summary": {
+            "files_scanned": 1, "files_with_changes": 1, "updates_total": 2,
+            "updates_major": 1, "updates_minor": 1, "updates_patch": 0,
+            "pinned": 0, "ignored": 0, "errors": 0, "warnings": 0
+        }
+    }"#;
+
+    const WEB: &str = r#"{
+        "command": "update",
+        "mode": "apply",
+        "files": [{
+            "path": "pyproject.toml",
+            "file_type": "pyproject",
+            "lang": "python",
+            "updates": [
+                {"package": "requests", "current": "2.30.0", "latest": "2.32.3", "bump": "minor"}
+            ],
+            "pinned": [], "ignored": [], "errors": [], "warnings": []
+        }],
+        "summary": {
+            "files_scanned": 2, "files_with_changes": 1, "updates_total": 1,
+            "updates_major": 0, "updates_minor": 1, "updates_patch": 0,
+            "pinned": 0, "ignored": 0, "errors": 1, "warnings": 0
+        }
+    }"#;
+
+    #[test]
+    fn test_merge_sums_totals_and_keeps_repo_attribution() {
+        let merged = merge(&[
+            ("api.json".to_string(), report(API)),
+            ("web.json".to_string(), report(WEB)),
+        ]);
+
+        assert_eq!(merged.summary.reports, 2);
+        assert_eq!(merged.summary.counts.files_scanned, 3);
+        assert_eq!(merged.summary.counts.updates_total, 3);
+        assert_eq!(merged.summary.counts.updates_major, 1);
+        assert_eq!(merged.summary.counts.updates_minor, 2);
+        assert_eq!(merged.summary.counts.errors, 1);
+
+        let repos: Vec<_> = merged.repos.iter().map(|r| r.repo.as_str()).collect();
+        assert_eq!(repos, ["api.json", "web.json"]);
+        assert_eq!(merged.repos[1].mode, "apply");
+        assert_eq!(merged.repos[1].counts.updates_total, 1);
+
+        // requests is updated in both repos, so it sorts first.
+        let requests = &merged.packages[0];
+        assert_eq!((requests.package.as_str(), requests.repos), ("requests", 2));
+        assert_eq!(requests.updates[0].repo, "api.json");
+        assert_eq!(requests.updates[0].current, "2.31.0");
+        assert_eq!(requests.updates[1].repo, "web.json");
+        assert_eq!(requests.updates[1].file, "pyproject.toml");
+        assert_eq!(merged.packages[1].package, "django");
+        assert_eq!(merged.packages[1].repos, 1);
+    }
+
+    #[test]
+    fn test_load_rejects_non_update_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.json");
+        std::fs::write(&path, r#"{"command": "audit", "summary": {}}"#).unwrap();
+        let err = load(&path).unwrap_err().to_string();
+        assert!(err.contains("`audit` report"), "{err}");
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load(&path).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! Major-version gap reporting for `upd outdated`.
+//!
+//! `outdated` is a read-only `update` run: every dependency with a newer
+//! version is listed with its current and latest version and how many major
+//! releases it trails by. The gap is counted from the package's published
+//! versions, so a project that skipped a major (1.x straight to 3.x) is one
+//! major behind, not two.
+
+use crate::registry::VersionMeta;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// One dependency with a newer version available.
+#[derive(Debug, Clone, Serialize)]
+pub struct OutdatedPackage {
+    pub file: String,
+    pub package: String,
+    pub lang: String,
+    pub current: String,
+    pub latest: String,
+    pub bump: String,
+    pub majors_behind: u64,
+}
+
+impl OutdatedPackage {
+    /// "2 majors behind: 3.x → 5.x", or `None` when the major is current.
+    pub fn gap_label(&self) -> Option<String> {
+        if self.majors_behind == 0 {
+            return None;
+        }
+        let plural = if self.majors_behind == 1 { "" } else { "s" };
+        Some(format!(
+            "{} major{} behind: {}.x \u{2192} {}.x",
+            self.majors_behind,
+            plural,
+            major_of(&self.current)?,
+            major_of(&self.latest)?
+        ))
+    }
+}
+
+/// The major version number: the leading numeric segment, ignoring a `v`
+/// prefix (`v3.2.1` and `3` both give 3).
+pub fn major_of(version: &str) -> Option<u64> {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    let digits: String = version.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+/// How many major releases lie between `current` and `latest`.
+///
+/// Counts the distinct majors with a stable, unyanked release in `versions`
+/// above the current one, up to and including the latest; majors that were
+/// never released are not counted. With no version list the numeric
+/// difference is used.
+pub fn majors_behind(current: &str, latest: &str, versions: &[VersionMeta]) -> u64 {
+    let (Some(current_major), Some(latest_major)) = (major_of(current), major_of(latest)) else {
+        return 0;
+    };
+    if latest_major <= current_major {
+        return 0;
+    }
+    if versions.is_empty() {
+        return latest_major - current_major;
+    }
+    let mut majors: BTreeSet<u64> = versions
+        .iter()
+        .filter(|meta| !meta.prerelease && !meta.yanked)
+        .filter_map(|meta| major_of(&meta.version))
+        .filter(|major| (current_major + 1..=latest_major).contains(major))
+        .collect();
+    majors.insert(latest_major);
+    majors.len() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metas(versions: &[&str]) -> Vec<VersionMeta> {
+        versions
+            .iter()
+            .map(|v| VersionMeta {
+                version: v.to_string(),
+                published_at: None,
+                yanked: false,
+                prerelease: v.contains('-'),
+                rust_version: None,
+                node_engines: None,
+                deprecated: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_major_of() {
+        assert_eq!(major_of("3.2.1"), Some(3));
+        assert_eq!(major_of("v12"), Some(12));
+        assert_eq!(major_of("2024.1.0"), Some(2024));
+        assert_eq!(major_of("latest"), None);
+    }
+
+    #[test]
+    fn test_majors_behind_counts_released_majors() {
+        let versions = metas(&["3.0.0", "3.4.0", "4.0.0", "4.2.1", "5.0.0", "5.1.0"]);
+        assert_eq!(majors_behind("3.4.0", "5.1.0", &versions), 2);
+        assert_eq!(majors_behind("4.2.1", "5.1.0", &versions), 1);
+        assert_eq!(majors_behind("5.0.0", "5.1.0", &versions), 0);
+    }
+
+    #[test]
+    fn test_majors_behind_skips_unreleased_majors() {
+        // 2.x was never released and 4.x only as a pre-release.
+        let versions = metas(&["1.0.0", "3.0.0", "4.0.0-rc.1", "5.0.0"]);
+        assert_eq!(majors_behind("1.0.0", "5.0.0", &versions), 2);
+    }
+
+    #[test]
+    fn test_majors_behind_without_a_version_list() {
+        assert_eq!(majors_behind("v3.1", "v5.0", &[]), 2);
+        assert_eq!(majors_behind("5.0", "4.9", &[]), 0);
+    }
+
+    #[test]
+    fn test_gap_label() {
+        let mut package = OutdatedPackage {
+            file: "requirements.txt".to_string(),
+            package: "django".to_string(),
+            lang: "python".to_string(),
+            current: "3.2.0".to_string(),
+            latest: "5.1.0".to_string(),
+            bump: "major".to_string(),
+            majors_behind: 2,
+        };
+        assert_eq!(
+            package.gap_label().as_deref(),
+            Some("2 majors behind: 3.x \u{2192} 5.x")
+        );
+        package.majors_behind = 1;
+        assert_eq!(
+            package.gap_label().as_deref(),
+            Some("1 major behind: 3.x \u{2192} 5.x")
+        );
+        package.majors_behind = 0;
+        assert_eq!(package.gap_label(), None);
+    }
+}
@@ -4,19 +4,93 @@
 //! `upd audit` when `--format json` is passed. The schema is part of the
 //! public CLI contract: additive changes are allowed, field renames are
 //! breaking.
+//!
+//! Human-readable text is printed through [`textln!`], which writes to stdout
+//! unless `--progress-stderr` routes it to stderr so stdout carries only the
+//! structured output.
 
 use crate::align::{PackageAlignment, PackageOccurrence};
 use crate::audit::{AuditResult, Vulnerability};
 use crate::updater::{FileType, UpdateResult};
+use schemars::JsonSchema;
 use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by `--progress-stderr`; read by [`text_stream`].
+static TEXT_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// The stream human-readable text is printed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextStream {
+    Stdout,
+    Stderr,
+}
+
+/// Route human-readable text for the rest of the process.
+pub fn set_text_stream(stream: TextStream) {
+    TEXT_TO_STDERR.store(stream == TextStream::Stderr, Ordering::Relaxed);
+}
+
+/// Where [`textln!`] currently prints.
+pub fn text_stream() -> TextStream {
+    if TEXT_TO_STDERR.load(Ordering::Relaxed) {
+        TextStream::Stderr
+    } else {
+        TextStream::Stdout
+    }
+}
+
+/// `println!` for human-readable text: prints to the stream chosen with
+/// [`set_text_stream`]. Structured output (JSON, SARIF, SBOM) keeps using
+/// `println!` so it always lands on stdout.
+#[macro_export]
+macro_rules! textln {
+    () => {
+        $crate::textln!("")
+    };
+    ($($arg:tt)*) => {
+        match $crate::output::text_stream() {
+            $crate::output::TextStream::Stdout => println!($($arg)*),
+            $crate::output::TextStream::Stderr => eprintln!($($arg)*),
+        }
+    };
+}
+
+/// Version of the `--format json` report structure, emitted as
+/// `schema_version` by `upd update`, `upd align` and `upd audit`. Bumped on
+/// breaking changes only; see `upd schema --report`.
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// JSON Schema (draft 2020-12) of the `--format json` reports, derived from
+/// the report types below so it cannot drift from what is serialized.
+pub fn report_json_schema() -> Value {
+    let mut generator = schemars::generate::SchemaSettings::draft2020_12()
+        .for_serialize()
+        .into_generator();
+    let reports = [
+        generator.subschema_for::<UpdateReport>(),
+        generator.subschema_for::<AlignReport>(),
+        generator.subschema_for::<AuditReport>(),
+    ];
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "upd JSON report",
+        "description": "Output of `upd update`, `upd align` and `upd audit` with --format json; the `command` field names the variant",
+        "x-schema-version": REPORT_SCHEMA_VERSION,
+        "anyOf": reports,
+        "$defs": generator.take_definitions(true),
+    })
+}
 
 /// A structured error entry in the JSON output.
 ///
 /// Replaces the former `errors: Vec<String>` with typed objects so that
 /// consumers can programmatically distinguish network failures from parse
 /// errors without string-matching.
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, JsonSchema, Clone)]
 pub struct ErrorEntry {
     /// Path of the file being processed when the error occurred, if known.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -51,23 +125,38 @@ impl ErrorEntry {
 
 /// Wire-level representation of a dependency file and what `upd update`
 /// would or did do to it.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct UpdateFileReport {
     pub path: String,
     pub file_type: &'static str,
     pub lang: &'static str,
     pub updates: Vec<UpdateEntry>,
+    /// Updates left unwritten by the config's `auto_apply` policy.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not_applied: Vec<UpdateEntry>,
+    /// Updates left for a later run by `--newest-only`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deferred: Vec<UpdateEntry>,
     pub pinned: Vec<PinnedEntry>,
     pub ignored: Vec<IgnoredEntry>,
+    /// Git, path and unconfigured-registry dependencies that were not looked up.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_sources: Vec<SkippedSourceEntry>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub held_back: Vec<HeldBackEntry>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub skipped_by_cooldown: Vec<SkippedByCooldownEntry>,
     pub errors: Vec<ErrorEntry>,
     pub warnings: Vec<String>,
+    /// `--offline` lookups with no cached answer; not counted as errors.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub offline_skipped: Vec<String>,
+    /// Lookups cut short by `--max-runtime`; not counted as errors.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timed_out: Vec<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct UpdateEntry {
     pub package: String,
     pub current: String,
@@ -79,7 +168,7 @@ pub struct UpdateEntry {
 
 /// A package update held back by cooldown — the chosen version is older than
 /// the absolute latest, which was too new.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct HeldBackEntry {
     pub package: String,
     pub current: String,
@@ -95,7 +184,7 @@ pub struct HeldBackEntry {
 
 /// A package skipped by cooldown entirely — every newer version is too new,
 /// so the current version is kept.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct SkippedByCooldownEntry {
     pub package: String,
     pub current: String,
@@ -107,7 +196,7 @@ pub struct SkippedByCooldownEntry {
     pub cooldown_seconds: i64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct PinnedEntry {
     pub package: String,
     pub current: String,
@@ -116,7 +205,7 @@ pub struct PinnedEntry {
     pub line: Option<usize>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct IgnoredEntry {
     pub package: String,
     pub current: String,
@@ -124,7 +213,34 @@ pub struct IgnoredEntry {
     pub line: Option<usize>,
 }
 
-#[derive(Debug, Serialize)]
+/// A dependency that does not come from a registry, so it has no version to
+/// update to.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SkippedSourceEntry {
+    pub package: String,
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+/// A locked transitive dependency with a newer registry version
+/// (`--include-transitive`). Reported only; lockfiles are never rewritten.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TransitiveEntry {
+    pub lockfile: String,
+    pub package: String,
+    pub current: String,
+    pub latest: String,
+}
+
+/// A release-notes link for an updated package (`--changelog`).
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ChangelogEntry {
+    pub package: String,
+    pub url: String,
+}
+
+#[derive(Debug, Default, Serialize, JsonSchema)]
 pub struct UpdateSummary {
     pub files_scanned: usize,
     pub files_with_changes: usize,
@@ -137,26 +253,62 @@ pub struct UpdateSummary {
     pub errors: usize,
     pub warnings: usize,
     #[serde(default, skip_serializing_if = "is_zero")]
+    pub not_applied: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub deferred: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
     pub held_back: usize,
     #[serde(default, skip_serializing_if = "is_zero")]
     pub skipped_by_cooldown: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub offline_skipped: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub timed_out: usize,
 }
 
 fn is_zero(n: &usize) -> bool {
     *n == 0
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct UpdateReport {
+    /// [`REPORT_SCHEMA_VERSION`] of this report's structure.
+    pub schema_version: u32,
     pub command: &'static str,
     pub mode: &'static str,
     pub files: Vec<UpdateFileReport>,
     pub summary: UpdateSummary,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub cooldown_notes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transitive: Vec<TransitiveEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changelogs: Vec<ChangelogEntry>,
 }
 
+/// Update counts for one ecosystem (or the whole run) in `--summary-json`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct SummaryCounts {
+    pub updated: usize,
+    pub major: usize,
+    pub minor: usize,
+    pub patch: usize,
+    pub pinned: usize,
+    pub ignored: usize,
+    pub errors: usize,
+}
+
+/// The compact record written by `--summary-json`: top-level counts only,
+/// overall and keyed by ecosystem (`python`, `node`, `rust`, ...).
 #[derive(Debug, Serialize)]
+pub struct SummaryJson {
+    pub command: &'static str,
+    pub mode: &'static str,
+    pub total: SummaryCounts,
+    pub ecosystems: BTreeMap<&'static str, SummaryCounts>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct AlignOccurrence {
     pub path: String,
     pub file_type: &'static str,
@@ -167,16 +319,19 @@ pub struct AlignOccurrence {
     pub is_misaligned: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct AlignPackage {
     pub package: String,
     pub lang: &'static str,
     pub highest_version: String,
+    /// Version the occurrences are aligned to; differs from
+    /// `highest_version` under `align --to`.
+    pub target_version: String,
     pub is_misaligned: bool,
     pub occurrences: Vec<AlignOccurrence>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct AlignSummary {
     pub files_scanned: usize,
     pub packages: usize,
@@ -184,14 +339,16 @@ pub struct AlignSummary {
     pub misaligned_occurrences: usize,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct AlignReport {
+    /// [`REPORT_SCHEMA_VERSION`] of this report's structure.
+    pub schema_version: u32,
     pub command: &'static str,
     pub packages: Vec<AlignPackage>,
     pub summary: AlignSummary,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct AuditVulnerability {
     pub package: String,
     pub version: String,
@@ -201,13 +358,16 @@ pub struct AuditVulnerability {
     pub summary: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub severity: Option<String>,
+    /// CVSS base score computed from the advisory's vector.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cvss_score: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fixed_version: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct AuditSummary {
     pub packages_checked: usize,
     pub vulnerable_packages: usize,
@@ -215,8 +375,10 @@ pub struct AuditSummary {
     pub errors: usize,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct AuditReport {
+    /// [`REPORT_SCHEMA_VERSION`] of this report's structure.
+    pub schema_version: u32,
     pub command: &'static str,
     /// `"complete"` when every scanned package was checked successfully;
     /// `"incomplete"` when the audit could not run (e.g. network failure).
@@ -237,17 +399,21 @@ pub fn build_update_file_report(
     cooldown_seconds: i64,
     classify: impl Fn(&str, &str) -> &'static str,
 ) -> UpdateFileReport {
-    let updates = result
-        .updated
-        .iter()
-        .map(|(name, old, new, line)| UpdateEntry {
-            package: name.clone(),
-            current: old.clone(),
-            latest: new.clone(),
-            bump: classify(old, new),
-            line: *line,
-        })
-        .collect();
+    let update_entries = |updates: &[(String, String, String, Option<usize>)]| {
+        updates
+            .iter()
+            .map(|(name, old, new, line)| UpdateEntry {
+                package: name.clone(),
+                current: old.clone(),
+                latest: new.clone(),
+                bump: classify(old, new),
+                line: *line,
+            })
+            .collect()
+    };
+    let updates = update_entries(&result.updated);
+    let not_applied = update_entries(&result.not_applied);
+    let deferred = update_entries(&result.deferred);
 
     let pinned = result
         .pinned
@@ -270,6 +436,16 @@ pub fn build_update_file_report(
         })
         .collect();
 
+    let skipped_sources = result
+        .skipped_sources
+        .iter()
+        .map(|(name, reason, line)| SkippedSourceEntry {
+            package: name.clone(),
+            reason: reason.clone(),
+            line: *line,
+        })
+        .collect();
+
     let held_back = result
         .held_back
         .iter()
@@ -307,13 +483,91 @@ pub fn build_update_file_report(
         file_type: file_type.as_str(),
         lang: file_type.lang().as_str(),
         updates,
+        not_applied,
+        deferred,
         pinned,
         ignored,
+        skipped_sources,
         held_back,
         skipped_by_cooldown,
         errors,
         warnings: result.warnings.clone(),
+        offline_skipped: result.offline_skipped.clone(),
+        timed_out: result.timed_out.clone(),
+    }
+}
+
+/// Render an update report as markdown for `--format markdown`: one table
+/// of updates and pins per file, then a summary line. Major updates are
+/// flagged in the Type column.
+pub fn render_update_markdown(report: &UpdateReport) -> String {
+    // A `|` inside a cell would end it early.
+    let cell = |text: &str| text.replace('|', "\\|");
+    let mut out = String::new();
+
+    for file in &report.files {
+        if file.updates.is_empty() && file.pinned.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("### `{}`\n\n", file.path));
+        out.push_str("| Package | From | To | Type | Ecosystem |\n");
+        out.push_str("|---------|------|----|------|-----------|\n");
+        for update in &file.updates {
+            let bump = if update.bump == "major" {
+                "**major** \u{26a0}\u{fe0f}"
+            } else {
+                update.bump
+            };
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                cell(&update.package),
+                cell(&update.current),
+                cell(&update.latest),
+                bump,
+                file.lang
+            ));
+        }
+        for pin in &file.pinned {
+            out.push_str(&format!(
+                "| {} | {} | {} | pinned | {} |\n",
+                cell(&pin.package),
+                cell(&pin.current),
+                cell(&pin.pinned_to),
+                file.lang
+            ));
+        }
+        out.push('\n');
+    }
+
+    let summary = &report.summary;
+    let verb = if report.mode == "applied" {
+        "applied"
+    } else {
+        "available"
+    };
+    let mut line = if summary.updates_total == 0 {
+        format!("**No updates {}.**", verb)
+    } else {
+        format!(
+            "**{} update(s) {}** ({} major, {} minor, {} patch) in {} of {} file(s).",
+            summary.updates_total,
+            verb,
+            summary.updates_major,
+            summary.updates_minor,
+            summary.updates_patch,
+            summary.files_with_changes,
+            summary.files_scanned
+        )
+    };
+    if summary.pinned > 0 {
+        line.push_str(&format!(" {} pinned.", summary.pinned));
+    }
+    if summary.errors > 0 {
+        line.push_str(&format!(" {} error(s).", summary.errors));
     }
+    out.push_str(&line);
+    out.push('\n');
+    out
 }
 
 /// Build an [`AlignPackage`] from an internal [`PackageAlignment`].
@@ -321,20 +575,20 @@ pub fn build_align_package(alignment: &PackageAlignment) -> AlignPackage {
     let occurrences = alignment
         .occurrences
         .iter()
-        .map(|o| occurrence_to_json(o, &alignment.highest_version))
+        .map(|o| occurrence_to_json(o, alignment.is_misaligned(o)))
         .collect();
 
     AlignPackage {
         package: alignment.package_name.clone(),
         lang: alignment.lang.as_str(),
         highest_version: alignment.highest_version.clone(),
+        target_version: alignment.target_version.clone(),
         is_misaligned: alignment.has_misalignment(),
         occurrences,
     }
 }
 
-fn occurrence_to_json(o: &PackageOccurrence, highest: &str) -> AlignOccurrence {
-    let misaligned = !o.has_upper_bound && o.version != highest;
+fn occurrence_to_json(o: &PackageOccurrence, misaligned: bool) -> AlignOccurrence {
     AlignOccurrence {
         path: o.file_path.display().to_string(),
         file_type: o.file_type.as_str(),
@@ -530,12 +784,16 @@ pub fn build_sarif_audit_report(audit: &AuditResult, occurrences: &SarifOccurren
 
         for vuln in &pkg_result.vulnerabilities {
             let level = osv_severity_to_sarif_level(vuln.severity.as_deref());
-            let message_text = vuln.summary.clone().unwrap_or_else(|| {
+            let summary = vuln.summary.clone().unwrap_or_else(|| {
                 format!(
                     "Vulnerability in {} {}",
                     pkg_result.package.name, pkg_result.package.version
                 )
             });
+            let message_text = match &vuln.fixed_version {
+                Some(fixed) => format!("{summary} (fixed in {fixed})"),
+                None => format!("{summary} (no fixed version)"),
+            };
 
             // Emit one location per file where the package is pinned, or a
             // placeholder location when no file occurrence data is available.
@@ -617,6 +875,7 @@ pub fn build_audit_report(
     let vulnerability_count = vulnerabilities.len();
 
     AuditReport {
+        schema_version: REPORT_SCHEMA_VERSION,
         command: "audit",
         status,
         vulnerabilities,
@@ -651,6 +910,7 @@ fn build_vulnerability(
         id: v.id.clone(),
         summary: v.summary.clone(),
         severity: v.severity.clone(),
+        cvss_score: v.cvss_score,
         fixed_version: v.fixed_version.clone(),
         url: v.url.clone(),
     }
@@ -676,12 +936,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn text_stream_follows_progress_stderr() {
+        assert_eq!(text_stream(), TextStream::Stdout);
+        set_text_stream(TextStream::Stderr);
+        assert_eq!(text_stream(), TextStream::Stderr);
+        set_text_stream(TextStream::Stdout);
+        assert_eq!(text_stream(), TextStream::Stdout);
+    }
+
+    #[test]
+    fn update_markdown_flags_major_and_escapes_cells() {
+        let result = UpdateResult {
+            updated: vec![
+                ("react".into(), "1.2.0".into(), "2.0.0".into(), Some(7)),
+                ("a|b".into(), "1.0.0".into(), "1.0.1".into(), None),
+            ],
+            pinned: vec![("lodash".into(), "4.17.0".into(), "4.17.21".into(), None)],
+            ..Default::default()
+        };
+        let report = UpdateReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            command: "update",
+            mode: "dry-run",
+            files: vec![build_update_file_report(
+                Path::new("package.json"),
+                FileType::PackageJson,
+                &result,
+                0,
+                stub_classify,
+            )],
+            summary: UpdateSummary {
+                files_scanned: 1,
+                files_with_changes: 1,
+                updates_total: 2,
+                updates_major: 1,
+                updates_patch: 1,
+                pinned: 1,
+                ..Default::default()
+            },
+            cooldown_notes: Vec::new(),
+            transitive: Vec::new(),
+            changelogs: Vec::new(),
+        };
+
+        let markdown = render_update_markdown(&report);
+        assert_eq!(
+            markdown,
+            "### `package.json`\n\n\
+             | Package | From | To | Type | Ecosystem |\n\
+             |---------|------|----|------|-----------|\n\
+             | react | 1.2.0 | 2.0.0 | **major** \u{26a0}\u{fe0f} | node |\n\
+             | a\\|b | 1.0.0 | 1.0.1 | patch | node |\n\
+             | lodash | 4.17.0 | 4.17.21 | pinned | node |\n\n\
+             **2 update(s) available** (1 major, 0 minor, 1 patch) in 1 of 1 file(s). 1 pinned.\n"
+        );
+    }
+
     #[test]
     fn update_file_report_serializes_all_sections() {
         let result = UpdateResult {
             updated: vec![("react".into(), "18.2.0".into(), "19.0.0".into(), Some(7))],
             pinned: vec![("lodash".into(), "4.17.0".into(), "4.17.21".into(), Some(12))],
             ignored: vec![("chalk".into(), "5.0.0".into(), Some(20))],
+            skipped_sources: vec![("local".into(), "path dependency".into(), Some(22))],
             errors: vec!["lookup failed: foo".into()],
             warnings: vec![
                 "skipping bar: current version \"%version%\" is not a valid PEP 440 version".into(),
@@ -714,6 +1032,7 @@ mod tests {
         assert_eq!(json["updates"][0]["line"], 7);
         assert_eq!(json["pinned"][0]["pinned_to"], "4.17.21");
         assert_eq!(json["ignored"][0]["package"], "chalk");
+        assert_eq!(json["skipped_sources"][0]["reason"], "path dependency");
         assert_eq!(json["errors"][0]["message"], "lookup failed: foo");
         assert_eq!(json["errors"][0]["kind"], "other");
         assert_eq!(json["errors"][0]["file"], "package.json");
@@ -812,6 +1131,7 @@ mod tests {
         let alignment = PackageAlignment {
             package_name: "react".into(),
             highest_version: "19.0.0".into(),
+            target_version: "19.0.0".into(),
             lang: Lang::Node,
             occurrences: vec![
                 PackageOccurrence {
@@ -822,6 +1142,7 @@ mod tests {
                     has_upper_bound: false,
                     original_name: "react".into(),
                     is_bumpable: true,
+                    inherits_workspace: false,
                 },
                 PackageOccurrence {
                     file_path: PathBuf::from("api/package.json"),
@@ -831,6 +1152,7 @@ mod tests {
                     has_upper_bound: false,
                     original_name: "react".into(),
                     is_bumpable: true,
+                    inherits_workspace: false,
                 },
                 PackageOccurrence {
                     file_path: PathBuf::from("legacy/package.json"),
@@ -840,6 +1162,7 @@ mod tests {
                     has_upper_bound: true,
                     original_name: "react".into(),
                     is_bumpable: true,
+                    inherits_workspace: false,
                 },
             ],
         };
@@ -871,6 +1194,7 @@ mod tests {
                         id: "GHSA-abc".into(),
                         summary: Some("Prototype pollution".into()),
                         severity: Some("High".into()),
+                        cvss_score: None,
                         url: Some("https://example/abc".into()),
                         fixed_version: Some("4.17.21".into()),
                     },
@@ -878,6 +1202,7 @@ mod tests {
                         id: "CVE-2020-1234".into(),
                         summary: None,
                         severity: Some("Unknown".into()),
+                        cvss_score: None,
                         url: None,
                         fixed_version: None,
                     },
@@ -903,6 +1228,39 @@ mod tests {
         assert_eq!(json["summary"]["vulnerabilities"], 2);
     }
 
+    #[test]
+    fn report_json_schema_validates_reports() {
+        let schema = report_json_schema();
+        let validator = jsonschema::draft202012::new(&schema).expect("valid Draft 2020-12 schema");
+
+        let audit = AuditResult {
+            vulnerable: Vec::new(),
+            safe_count: 3,
+            errors: vec!["osv: timed out".into()],
+        };
+        let audit_json = serde_json::to_value(build_audit_report(&audit, 1, "incomplete")).unwrap();
+        assert_eq!(audit_json["schema_version"], REPORT_SCHEMA_VERSION);
+        assert!(validator.is_valid(&audit_json));
+
+        let align_json = serde_json::to_value(AlignReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            command: "align",
+            packages: Vec::new(),
+            summary: AlignSummary {
+                files_scanned: 0,
+                packages: 0,
+                misaligned_packages: 0,
+                misaligned_occurrences: 0,
+            },
+        })
+        .unwrap();
+        assert!(validator.is_valid(&align_json));
+
+        let mut broken = audit_json;
+        broken["summary"]["errors"] = "one".into();
+        assert!(!validator.is_valid(&broken));
+    }
+
     #[test]
     fn audit_report_incomplete_when_errors_present() {
         let audit = AuditResult {
@@ -934,6 +1292,7 @@ mod tests {
                         id: "GHSA-abcd-1234-efgh".into(),
                         summary: Some("Remote code execution in requests".into()),
                         severity: Some("High".into()),
+                        cvss_score: None,
                         url: Some("https://osv.dev/vulnerability/GHSA-abcd-1234-efgh".into()),
                         fixed_version: Some("2.28.0".into()),
                     },
@@ -941,6 +1300,7 @@ mod tests {
                         id: "CVE-2023-99999".into(),
                         summary: None,
                         severity: Some("Medium".into()),
+                        cvss_score: None,
                         url: None,
                         fixed_version: None,
                     },
@@ -990,6 +1350,7 @@ mod tests {
                         id: "GHSA-dup-test".into(),
                         summary: Some("Shared vuln".into()),
                         severity: Some("High".into()),
+                        cvss_score: None,
                         url: None,
                         fixed_version: None,
                     }],
@@ -1004,6 +1365,7 @@ mod tests {
                         id: "GHSA-dup-test".into(),
                         summary: Some("Shared vuln".into()),
                         severity: Some("High".into()),
+                        cvss_score: None,
                         url: None,
                         fixed_version: None,
                     }],
@@ -1108,4 +1470,21 @@ mod tests {
             "fixedVersion must be absent when not set"
         );
     }
+
+    #[test]
+    fn sarif_result_message_names_fixed_version() {
+        let audit = make_audit_with_vuln();
+        let occurrences = std::collections::HashMap::new();
+        let log = build_sarif_audit_report(&audit, &occurrences);
+        let results = &log.runs[0].results;
+
+        assert_eq!(
+            results[0].message.text,
+            "Remote code execution in requests (fixed in 2.28.0)"
+        );
+        assert_eq!(
+            results[1].message.text,
+            "Vulnerability in requests 2.27.0 (no fixed version)"
+        );
+    }
 }
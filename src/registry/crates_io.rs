@@ -1,20 +1,14 @@
 use super::utils::home_dir;
-use super::{Registry, VersionMeta, http_error_message};
+use super::{PackageLinks, Registry, VersionMeta, http_error_message};
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
 use reqwest::{Client, Response};
 use serde::Deserialize;
 use std::io::BufRead;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-/// Maximum number of retry attempts for failed HTTP requests
-const MAX_RETRIES: u32 = 3;
-
-/// Base delay for exponential backoff (100ms, 200ms, 400ms)
-const BASE_DELAY_MS: u64 = 100;
-
 /// Credentials for authenticating with a Cargo registry
 #[derive(Clone)]
 pub struct CargoCredentials {
@@ -45,6 +39,10 @@ struct CratesResponse {
 #[derive(Debug, Deserialize)]
 struct CrateInfo {
     max_stable_version: Option<String>,
+    #[serde(default)]
+    repository: Option<String>,
+    #[serde(default)]
+    homepage: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -53,6 +51,9 @@ struct VersionInfo {
     yanked: bool,
     #[serde(default)]
     created_at: Option<String>,
+    /// `rust-version` from the published manifest, when declared.
+    #[serde(default)]
+    rust_version: Option<String>,
 }
 
 /// Cargo registry configuration from config.toml
@@ -94,6 +95,23 @@ pub fn read_cargo_config() -> CargoConfig {
     config
 }
 
+/// Named registries from project-level `.cargo/config.toml` files, searched
+/// from `dir` up through its ancestors the way Cargo does. The nearest
+/// declaration of a name wins; `~/.cargo` is left to [`read_cargo_config`].
+pub fn read_project_cargo_registries(dir: &Path) -> std::collections::HashMap<String, String> {
+    let mut registries = std::collections::HashMap::new();
+    for ancestor in dir.ancestors() {
+        for file in ["config.toml", "config"] {
+            if let Some(parsed) = read_cargo_config_from_path(&ancestor.join(".cargo").join(file)) {
+                for (name, url) in parsed.registries {
+                    registries.entry(name).or_insert(url);
+                }
+            }
+        }
+    }
+    registries
+}
+
 /// Read cargo configuration from a specific file path
 fn read_cargo_config_from_path(path: &PathBuf) -> Option<CargoConfig> {
     let content = std::fs::read_to_string(path).ok()?;
@@ -188,7 +206,7 @@ fn read_cargo_config_from_path(path: &PathBuf) -> Option<CargoConfig> {
 
 /// Convert a sparse registry index URL to an API URL
 /// sparse+https://my-registry.com/index/ -> https://my-registry.com/api/v1/crates
-fn sparse_index_to_api_url(index_url: &str) -> String {
+pub(crate) fn sparse_index_to_api_url(index_url: &str) -> String {
     let url = index_url
         .strip_prefix("sparse+")
         .unwrap_or(index_url)
@@ -296,7 +314,6 @@ impl CratesIoRegistry {
                     env!("CARGO_PKG_VERSION"),
                     " (https://github.com/rvben/upd)"
                 ))
-                .timeout(Duration::from_secs(30))
                 .connect_timeout(Duration::from_secs(10))
                 .default_headers(headers),
         )
@@ -371,28 +388,35 @@ impl CratesIoRegistry {
     }
 
     /// Execute a GET request with retry
+    #[tracing::instrument(level = "debug", skip(self))]
     async fn get_with_retry(&self, url: &str) -> anyhow::Result<Response> {
+        crate::http::ensure_host_allowed(url)?;
+        let _slot = crate::http::acquire_host_slot(url).await;
         let mut last_error = None;
 
-        for attempt in 0..MAX_RETRIES {
+        let attempts = super::max_attempts();
+
+        for attempt in 0..attempts {
+            crate::http::acquire_rate_token().await;
             match self.client.get(url).send().await {
                 Ok(response) => {
-                    if response.status().is_client_error() || response.status().is_success() {
+                    let status = response.status();
+                    tracing::debug!(attempt, status = status.as_u16(), "response");
+                    if status.is_client_error() || status.is_success() {
                         return Ok(response);
                     }
-                    if response.status().is_server_error() && attempt < MAX_RETRIES - 1 {
-                        let delay = Duration::from_millis(BASE_DELAY_MS * (1 << attempt));
-                        tokio::time::sleep(delay).await;
+                    if status.is_server_error() && attempt + 1 < attempts {
+                        super::backoff(attempt, &status).await;
                         continue;
                     }
                     return Ok(response);
                 }
                 Err(e) => {
-                    last_error = Some(e);
-                    if attempt < MAX_RETRIES - 1 {
-                        let delay = Duration::from_millis(BASE_DELAY_MS * (1 << attempt));
-                        tokio::time::sleep(delay).await;
+                    tracing::debug!(attempt, error = %e, "request failed");
+                    if attempt + 1 < attempts {
+                        super::backoff(attempt, &e).await;
                     }
+                    last_error = Some(e);
                 }
             }
         }
@@ -543,11 +567,23 @@ impl Registry for CratesIoRegistry {
                     published_at,
                     yanked: v.yanked,
                     prerelease,
+                    rust_version: v.rust_version,
+                    node_engines: None,
+                    deprecated: None,
                 }
             })
             .collect())
     }
 
+    async fn package_links(&self, package: &str) -> Result<PackageLinks> {
+        let data = self.fetch_crate(package).await?;
+        Ok(PackageLinks {
+            repository: data.krate.repository,
+            homepage: data.krate.homepage,
+            changelog: None,
+        })
+    }
+
     fn name(&self) -> &'static str {
         "crates.io"
     }
@@ -565,27 +601,33 @@ mod tests {
         let data = CratesResponse {
             krate: CrateInfo {
                 max_stable_version: None,
+                repository: None,
+                homepage: None,
             },
             versions: vec![
                 VersionInfo {
                     num: "1.0.0".to_string(),
                     yanked: false,
                     created_at: None,
+                    rust_version: None,
                 },
                 VersionInfo {
                     num: "2.0.0".to_string(),
                     yanked: false,
                     created_at: None,
+                    rust_version: None,
                 },
                 VersionInfo {
                     num: "1.5.0".to_string(),
                     yanked: true,
                     created_at: None,
+                    rust_version: None,
                 },
                 VersionInfo {
                     num: "3.0.0-alpha.1".to_string(),
                     yanked: false,
                     created_at: None,
+                    rust_version: None,
                 },
             ],
         };
@@ -600,6 +642,34 @@ mod tests {
         assert_eq!(all[0].1, "3.0.0-alpha.1");
     }
 
+    #[test]
+    fn test_read_project_cargo_registries_nearest_wins() {
+        let root = tempfile::tempdir().unwrap();
+        let member = root.path().join("crates").join("app");
+        std::fs::create_dir_all(member.join(".cargo")).unwrap();
+        std::fs::create_dir_all(root.path().join(".cargo")).unwrap();
+        std::fs::write(
+            root.path().join(".cargo").join("config.toml"),
+            "[registries.my-corp]\nindex = \"sparse+https://root.example/index/\"\n\n[registries.shared]\nindex = \"sparse+https://shared.example/index/\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            member.join(".cargo").join("config.toml"),
+            "[registries]\nmy-corp = { index = \"sparse+https://member.example/index/\" }\n",
+        )
+        .unwrap();
+
+        let registries = read_project_cargo_registries(&member);
+        assert_eq!(
+            registries.get("my-corp").map(String::as_str),
+            Some("sparse+https://member.example/index/")
+        );
+        assert_eq!(
+            registries.get("shared").map(String::as_str),
+            Some("sparse+https://shared.example/index/")
+        );
+    }
+
     #[test]
     fn test_detect_credentials_from_env() {
         // SAFETY: Test runs in isolation
@@ -784,7 +854,7 @@ mod tests {
                 r#"{
               "crate": {"max_stable_version": "1.0.200"},
               "versions": [
-                {"num": "1.0.200", "created_at": "2024-03-01T12:00:00Z", "yanked": false},
+                {"num": "1.0.200", "created_at": "2024-03-01T12:00:00Z", "yanked": false, "rust_version": "1.61"},
                 {"num": "1.0.199", "created_at": "2024-02-01T12:00:00Z", "yanked": true},
                 {"num": "2.0.0-alpha.1", "created_at": "2024-04-01T12:00:00Z", "yanked": false}
               ]
@@ -808,5 +878,83 @@ mod tests {
         assert!(!stable.prerelease);
         assert!(!stable.yanked);
         assert!(stable.published_at.is_some());
+        assert_eq!(stable.rust_version.as_deref(), Some("1.61"));
+        assert_eq!(yanked.rust_version, None);
+    }
+
+    #[tokio::test]
+    async fn test_crates_io_get_latest_version_matching() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/tokio"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+              "crate": {"max_stable_version": "2.0.0"},
+              "versions": [
+                {"num": "2.0.0", "created_at": "2024-05-01T12:00:00Z", "yanked": false},
+                {"num": "1.9.0-rc.1", "created_at": "2024-04-15T12:00:00Z", "yanked": false},
+                {"num": "1.8.1", "created_at": "2024-04-01T12:00:00Z", "yanked": true},
+                {"num": "1.8.0", "created_at": "2024-03-01T12:00:00Z", "yanked": false},
+                {"num": "1.2.9", "created_at": "2024-02-01T12:00:00Z", "yanked": false},
+                {"num": "1.2.3", "created_at": "2024-01-01T12:00:00Z", "yanked": false}
+              ]
+            }"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let registry = CratesIoRegistry::with_registry_url(mock_server.uri());
+
+        // The yanked 1.8.1 and the 1.9.0 pre-release are never selected.
+        for (req, expected) in [("^1", "1.8.0"), ("~1.2", "1.2.9"), (">=1,<2", "1.8.0")] {
+            let version = registry
+                .get_latest_version_matching("tokio", req)
+                .await
+                .unwrap();
+            assert_eq!(version, expected, "requirement {req}");
+        }
+
+        let err = registry
+            .get_latest_version_matching("tokio", "^3")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No version of 'tokio' matches"));
+    }
+
+    #[tokio::test]
+    async fn test_crates_io_package_links_reads_repository_and_homepage() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/serde"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+              "crate": {
+                "max_stable_version": "1.0.200",
+                "repository": "https://github.com/serde-rs/serde",
+                "homepage": "https://serde.rs"
+              },
+              "versions": []
+            }"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let registry = CratesIoRegistry::with_registry_url(mock_server.uri());
+        let links = registry.package_links("serde").await.unwrap();
+
+        assert_eq!(
+            links.repository.as_deref(),
+            Some("https://github.com/serde-rs/serde")
+        );
+        assert_eq!(links.homepage.as_deref(), Some("https://serde.rs"));
+        assert_eq!(links.changelog, None);
     }
 }
@@ -0,0 +1,316 @@
+use super::{Registry, get_with_retry, http_error_message};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Tags are listed newest first, so the first pages hold every recent
+/// release; images with years of history are not paged through to the end.
+const MAX_TAG_PAGES: usize = 10;
+
+pub struct DockerHubRegistry {
+    client: Client,
+    api_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+    next: Option<String>,
+    results: Vec<Tag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tag {
+    name: String,
+}
+
+/// The shape of an image tag: its numeric release segments and the variant
+/// that follows them (`3.11.2-slim` is `[3, 11, 2]` and `-slim`).
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct TagShape<'a> {
+    pub release: Vec<u64>,
+    pub variant: &'a str,
+}
+
+/// Split a tag into its [`TagShape`], or `None` for tags that do not start
+/// with a dotted version (`latest`, `bookworm`) or whose version runs into
+/// other text (`3.14.0rc1`).
+pub(crate) fn tag_shape(tag: &str) -> Option<TagShape<'_>> {
+    let end = tag.find('-').unwrap_or(tag.len());
+    let (version, variant) = tag.split_at(end);
+    let release = version
+        .split('.')
+        .map(|part| {
+            if !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()) {
+                part.parse().ok()
+            } else {
+                None
+            }
+        })
+        .collect::<Option<Vec<u64>>>()?;
+    Some(TagShape { release, variant })
+}
+
+impl DockerHubRegistry {
+    pub fn new() -> Self {
+        Self::with_api_url("https://hub.docker.com".to_string())
+    }
+
+    #[cfg(test)]
+    pub fn with_api_url(api_url: String) -> Self {
+        let client = crate::http::apply(
+            Client::builder()
+                .gzip(true)
+                .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
+                .connect_timeout(Duration::from_secs(10)),
+        )
+        .build()
+        .expect("Failed to create HTTP client");
+
+        Self { client, api_url }
+    }
+
+    #[cfg(not(test))]
+    fn with_api_url(api_url: String) -> Self {
+        let client = crate::http::apply(
+            Client::builder()
+                .gzip(true)
+                .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
+                .connect_timeout(Duration::from_secs(10)),
+        )
+        .build()
+        .expect("Failed to create HTTP client");
+
+        Self { client, api_url }
+    }
+
+    /// Docker Hub repository path for an image: official images live under
+    /// `library/`.
+    fn repository(image: &str) -> String {
+        if image.contains('/') {
+            image.to_string()
+        } else {
+            format!("library/{}", image)
+        }
+    }
+
+    /// Fetch tag names, newest first. A non-empty `name_filter` narrows the
+    /// listing server-side to tags containing it.
+    async fn get_tags(&self, image: &str, name_filter: &str) -> Result<Vec<String>> {
+        let mut url = format!(
+            "{}/v2/repositories/{}/tags?page_size=100&ordering=last_updated",
+            self.api_url,
+            Self::repository(image)
+        );
+        if !name_filter.is_empty() {
+            url.push_str("&name=");
+            url.push_str(name_filter);
+        }
+
+        let mut tags = Vec::new();
+        for _ in 0..MAX_TAG_PAGES {
+            let response = get_with_retry(&self.client, &url).await?;
+
+            if !response.status().is_success() {
+                return Err(anyhow!(http_error_message(
+                    response.status(),
+                    "Image",
+                    image,
+                    None
+                )));
+            }
+
+            let data: TagsResponse = response.json().await.map_err(|e| {
+                anyhow!("Failed to parse Docker Hub response for '{}': {}", image, e)
+            })?;
+
+            tags.extend(data.results.into_iter().map(|tag| tag.name));
+            match data.next {
+                Some(next) => url = next,
+                None => break,
+            }
+        }
+
+        Ok(tags)
+    }
+
+    /// The highest tag with the same number of release segments and the
+    /// same variant as `current`.
+    fn find_latest_same_shape(tags: &[String], current: &TagShape<'_>) -> Option<String> {
+        tags.iter()
+            .filter_map(|tag| tag_shape(tag).map(|shape| (tag, shape)))
+            .filter(|(_, shape)| {
+                shape.variant == current.variant && shape.release.len() == current.release.len()
+            })
+            .max_by(|(_, a), (_, b)| a.release.cmp(&b.release))
+            .map(|(tag, _)| tag.clone())
+    }
+}
+
+impl Default for DockerHubRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Registry for DockerHubRegistry {
+    /// The highest plain version tag (no variant), preferring the most
+    /// precise tag of a release (`3.13.1` over `3.13`).
+    async fn get_latest_version(&self, package: &str) -> Result<String> {
+        let tags = self.get_tags(package, "").await?;
+
+        tags.iter()
+            .filter_map(|tag| tag_shape(tag).map(|shape| (tag, shape)))
+            .filter(|(_, shape)| shape.variant.is_empty())
+            .max_by(|(_, a), (_, b)| {
+                a.release
+                    .cmp(&b.release)
+                    .then(a.release.len().cmp(&b.release.len()))
+            })
+            .map(|(tag, _)| tag.clone())
+            .ok_or_else(|| anyhow!("No version tags found for '{}' on Docker Hub", package))
+    }
+
+    /// `constraints` is the tag currently in use; the answer is the newest
+    /// tag of the same shape, so `3.11-slim` moves to `3.13-slim`, never to
+    /// `3.13.1-slim` or `3.13`.
+    async fn get_latest_version_matching(
+        &self,
+        package: &str,
+        constraints: &str,
+    ) -> Result<String> {
+        let current = tag_shape(constraints)
+            .ok_or_else(|| anyhow!("Tag '{}' of '{}' is not a version", constraints, package))?;
+        let tags = self.get_tags(package, current.variant).await?;
+
+        Self::find_latest_same_shape(&tags, &current).ok_or_else(|| {
+            anyhow!(
+                "No tag of '{}' on Docker Hub matches the shape of '{}'",
+                package,
+                constraints
+            )
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "docker-hub"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_tag_shape() {
+        assert_eq!(
+            tag_shape("3.11.2-slim"),
+            Some(TagShape {
+                release: vec![3, 11, 2],
+                variant: "-slim"
+            })
+        );
+        assert_eq!(
+            tag_shape("18"),
+            Some(TagShape {
+                release: vec![18],
+                variant: ""
+            })
+        );
+        assert_eq!(
+            tag_shape("3.12-slim-bookworm").unwrap().variant,
+            "-slim-bookworm"
+        );
+        assert_eq!(tag_shape("latest"), None);
+        assert_eq!(tag_shape("3.14.0rc1-slim"), None);
+    }
+
+    #[tokio::test]
+    async fn test_latest_matching_keeps_precision_and_variant() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v2/repositories/library/python/tags"))
+            .and(query_param("name", "-slim"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"next": null, "results": [
+                    {"name": "3.14.0rc1-slim"},
+                    {"name": "3.13.1-slim"},
+                    {"name": "3.13-slim"},
+                    {"name": "3.13.1-slim-bookworm"},
+                    {"name": "3.12.8-slim"},
+                    {"name": "3.9.21-slim"}
+                ]}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let registry = DockerHubRegistry::with_api_url(mock_server.uri());
+        assert_eq!(
+            registry
+                .get_latest_version_matching("python", "3.11.2-slim")
+                .await
+                .unwrap(),
+            "3.13.1-slim"
+        );
+        assert_eq!(
+            registry
+                .get_latest_version_matching("python", "3.11-slim")
+                .await
+                .unwrap(),
+            "3.13-slim"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_follows_pagination() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v2/repositories/bitnami/redis/tags"))
+            .and(query_param("page", "2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"next": null, "results": [{"name": "7.4.1"}]}"#),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/repositories/bitnami/redis/tags"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"{{"next": "{}/v2/repositories/bitnami/redis/tags?page=2", "results": [{{"name": "7.2.5"}}, {{"name": "latest"}}]}}"#,
+                mock_server.uri()
+            )))
+            .mount(&mock_server)
+            .await;
+
+        let registry = DockerHubRegistry::with_api_url(mock_server.uri());
+        let version = registry.get_latest_version("bitnami/redis").await.unwrap();
+        assert_eq!(version, "7.4.1");
+    }
+
+    #[tokio::test]
+    async fn test_image_not_found() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v2/repositories/library/nonexistent/tags"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let registry = DockerHubRegistry::with_api_url(mock_server.uri());
+        let result = registry.get_latest_version("nonexistent").await;
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn test_registry_name() {
+        let registry = DockerHubRegistry::new();
+        assert_eq!(registry.name(), "docker-hub");
+    }
+}
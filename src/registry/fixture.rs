@@ -0,0 +1,289 @@
+//! Pre-seeded, in-memory version resolution for whole-run tests and benchmarks.
+//!
+//! Setting `UPD_REGISTRY_FIXTURE` to a JSON file makes every registry resolve
+//! from that file instead of the network, so an entire `upd` run is
+//! deterministic. This is the run-level counterpart of the per-updater
+//! `MockRegistry` used in unit tests. It is compiled only with the
+//! `registry-fixture` cargo feature, which the integration tests enable;
+//! release builds ignore the variable.
+//!
+//! The file maps registry names (as returned by [`Registry::name`], e.g.
+//! `pypi`, `npm`, `crates.io`, `go-proxy`) to packages. A package is either a
+//! bare version string or an object:
+//!
+//! ```json
+//! {
+//!   "pypi": {
+//!     "requests": "2.32.3",
+//!     "django": {
+//!       "latest": "5.1.0",
+//!       "prerelease": "5.2a1",
+//!       "matching": { ">=4.2,<5": "4.2.16" },
+//!       "versions": ["4.2.16", "5.1.0"]
+//!     }
+//!   },
+//!   "npm": { "lodash": "4.17.21" },
+//!   "crates.io": {
+//!     "clap": {
+//!       "latest": "4.5.20",
+//!       "versions": ["4.4.18", "4.5.20"],
+//!       "rust_versions": { "4.5.20": "1.74" }
+//!     }
+//!   },
+//!   "github-releases": {
+//!     "actions/checkout": {
+//!       "latest": "v4.2.2",
+//!       "commits": { "v4.2.2": "11bd71901bbe5b1630ceea73d27597364c9af683" }
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! A package missing from the fixture fails like a registry 404; nothing falls
+//! through to the network.
+
+use super::{Registry, VersionMeta};
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Environment variable naming the fixture file.
+pub const REGISTRY_FIXTURE_ENV: &str = "UPD_REGISTRY_FIXTURE";
+
+/// One package's entry in a fixture file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum FixturePackage {
+    Latest(String),
+    Detailed {
+        latest: String,
+        #[serde(default)]
+        prerelease: Option<String>,
+        /// Constraint string → resolved version.
+        #[serde(default)]
+        matching: HashMap<String, String>,
+        /// Versions returned by `list_versions` (used by cooldown).
+        #[serde(default)]
+        versions: Vec<String>,
+        /// Version → declared MSRV, reported through `list_versions`.
+        #[serde(default)]
+        rust_versions: HashMap<String, String>,
+        /// Tag → commit SHA, answered by `resolve_tag_commit`.
+        #[serde(default)]
+        commits: HashMap<String, String>,
+    },
+}
+
+impl FixturePackage {
+    fn latest(&self) -> &str {
+        match self {
+            Self::Latest(v) => v,
+            Self::Detailed { latest, .. } => latest,
+        }
+    }
+}
+
+/// Parsed fixture file covering every registry.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct RegistryFixture {
+    registries: HashMap<String, HashMap<String, FixturePackage>>,
+}
+
+impl RegistryFixture {
+    /// Parse a fixture from JSON text.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("invalid registry fixture")
+    }
+
+    /// Load a fixture file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read registry fixture {}", path.display()))?;
+        Self::from_json(&content)
+    }
+
+    /// Load the fixture named by `UPD_REGISTRY_FIXTURE`, if set.
+    pub fn from_env() -> Result<Option<Self>> {
+        match std::env::var_os(REGISTRY_FIXTURE_ENV) {
+            Some(path) if !path.is_empty() => Self::load(Path::new(&path)).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// The resolver for one registry. Registries absent from the file resolve
+    /// nothing, so they still never touch the network.
+    pub fn for_registry(&self, name: &'static str) -> FixtureRegistry {
+        FixtureRegistry {
+            name,
+            packages: self.registries.get(name).cloned().unwrap_or_default(),
+        }
+    }
+}
+
+/// A [`Registry`] answering only from a fixture.
+#[derive(Debug, Clone)]
+pub struct FixtureRegistry {
+    name: &'static str,
+    packages: HashMap<String, FixturePackage>,
+}
+
+impl FixtureRegistry {
+    fn package(&self, package: &str) -> Result<&FixturePackage> {
+        self.packages
+            .get(package)
+            .ok_or_else(|| anyhow!("Package not found: {}", package))
+    }
+}
+
+#[async_trait]
+impl Registry for FixtureRegistry {
+    async fn get_latest_version(&self, package: &str) -> Result<String> {
+        Ok(self.package(package)?.latest().to_string())
+    }
+
+    async fn get_latest_version_including_prereleases(&self, package: &str) -> Result<String> {
+        Ok(match self.package(package)? {
+            FixturePackage::Detailed {
+                prerelease: Some(pre),
+                ..
+            } => pre.clone(),
+            entry => entry.latest().to_string(),
+        })
+    }
+
+    async fn get_latest_version_matching(
+        &self,
+        package: &str,
+        constraints: &str,
+    ) -> Result<String> {
+        let entry = self.package(package)?;
+        if let FixturePackage::Detailed { matching, .. } = entry
+            && let Some(version) = matching.get(constraints)
+        {
+            return Ok(version.clone());
+        }
+        Ok(entry.latest().to_string())
+    }
+
+    async fn list_versions(&self, package: &str) -> Result<Vec<VersionMeta>> {
+        let Some(FixturePackage::Detailed {
+            versions,
+            rust_versions,
+            ..
+        }) = self.packages.get(package)
+        else {
+            return Ok(Vec::new());
+        };
+        Ok(versions
+            .iter()
+            .map(|version| VersionMeta {
+                version: version.clone(),
+                published_at: None,
+                yanked: false,
+                prerelease: false,
+                rust_version: rust_versions.get(version).cloned(),
+                node_engines: None,
+                deprecated: None,
+            })
+            .collect())
+    }
+
+    async fn resolve_tag_commit(&self, package: &str, tag: &str) -> Result<String> {
+        if let FixturePackage::Detailed { commits, .. } = self.package(package)?
+            && let Some(sha) = commits.get(tag)
+        {
+            return Ok(sha.clone());
+        }
+        Err(anyhow!("Tag not found: {}@{}", package, tag))
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"{
+        "pypi": {
+            "requests": "2.32.3",
+            "django": {
+                "latest": "5.1.0",
+                "prerelease": "5.2a1",
+                "matching": { ">=4.2,<5": "4.2.16" },
+                "versions": ["4.2.16", "5.1.0"]
+            }
+        }
+    }"#;
+
+    #[tokio::test]
+    async fn test_fixture_resolves_bare_and_detailed_entries() {
+        let pypi = RegistryFixture::from_json(FIXTURE)
+            .unwrap()
+            .for_registry("pypi");
+
+        assert_eq!(pypi.get_latest_version("requests").await.unwrap(), "2.32.3");
+        assert_eq!(pypi.get_latest_version("django").await.unwrap(), "5.1.0");
+        assert_eq!(
+            pypi.get_latest_version_including_prereleases("django")
+                .await
+                .unwrap(),
+            "5.2a1"
+        );
+        assert_eq!(
+            pypi.get_latest_version_including_prereleases("requests")
+                .await
+                .unwrap(),
+            "2.32.3"
+        );
+        assert_eq!(
+            pypi.get_latest_version_matching("django", ">=4.2,<5")
+                .await
+                .unwrap(),
+            "4.2.16"
+        );
+        assert_eq!(pypi.list_versions("django").await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fixture_missing_package_and_registry_fail() {
+        let fixture = RegistryFixture::from_json(FIXTURE).unwrap();
+        let err = fixture
+            .for_registry("pypi")
+            .get_latest_version("flask")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Package not found"));
+
+        let npm = fixture.for_registry("npm");
+        assert_eq!(npm.name(), "npm");
+        assert!(npm.get_latest_version("lodash").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fixture_reports_declared_rust_versions() {
+        let crates = RegistryFixture::from_json(
+            r#"{"crates.io": {"clap": {
+                "latest": "4.5.20",
+                "versions": ["4.4.18", "4.5.20"],
+                "rust_versions": {"4.5.20": "1.74"}
+            }}}"#,
+        )
+        .unwrap()
+        .for_registry("crates.io");
+
+        let versions = crates.list_versions("clap").await.unwrap();
+        assert_eq!(versions[0].rust_version, None);
+        assert_eq!(versions[1].rust_version.as_deref(), Some("1.74"));
+    }
+
+    #[test]
+    fn test_fixture_rejects_malformed_json() {
+        assert!(RegistryFixture::from_json("{\"pypi\": 3}").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use super::gitlab_tags::{self, GitLabTags};
 use super::{Registry, VersionMeta, get_with_retry, http_error_message};
 use crate::version::TagVersion;
 use anyhow::{Result, anyhow};
@@ -8,9 +9,13 @@ use reqwest::header::{ACCEPT, AUTHORIZATION, HeaderMap, HeaderValue};
 use serde::Deserialize;
 use std::time::Duration;
 
+/// Releases and tags of GitHub repositories (`owner/repo`). Host-qualified
+/// GitLab project paths (`gitlab.com/group/project`) are answered from the
+/// tags of that GitLab host instead.
 pub struct GitHubReleasesRegistry {
     client: Client,
     api_url: String,
+    gitlab: GitLabTags,
 }
 
 #[derive(Debug, Deserialize)]
@@ -23,6 +28,11 @@ struct TagResponse {
     name: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct CommitResponse {
+    sha: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct ReleaseListEntry {
     tag_name: String,
@@ -40,9 +50,13 @@ impl GitHubReleasesRegistry {
         Self::with_api_url_and_token("https://api.github.com".to_string(), token)
     }
 
+    /// Also serves GitLab lookups from `api_url`.
     #[cfg(test)]
     pub fn with_api_url(api_url: String) -> Self {
-        Self::with_api_url_and_token(api_url, None)
+        Self {
+            gitlab: GitLabTags::with_api_url(Some(api_url.clone())),
+            ..Self::with_api_url_and_token(api_url, None)
+        }
     }
 
     pub fn with_api_url_and_token(api_url: String, token: Option<String>) -> Self {
@@ -62,14 +76,17 @@ impl GitHubReleasesRegistry {
         let client = crate::http::apply(
             Client::builder()
                 .user_agent(user_agent)
-                .timeout(Duration::from_secs(30))
                 .connect_timeout(Duration::from_secs(10))
                 .default_headers(headers),
         )
         .build()
         .expect("Failed to create HTTP client for GitHub API.");
 
-        Self { client, api_url }
+        Self {
+            client,
+            api_url,
+            gitlab: GitLabTags::new(),
+        }
     }
 
     /// Check `GITHUB_TOKEN` then `GH_TOKEN` for an auth token.
@@ -135,6 +152,9 @@ impl Registry for GitHubReleasesRegistry {
     }
 
     async fn get_latest_version(&self, package: &str) -> Result<String> {
+        if gitlab_tags::split_project(package).is_some() {
+            return self.gitlab.latest_tag(package, false).await;
+        }
         let (owner, repo) = Self::extract_owner_repo(package)?;
 
         // Try releases/latest first — it returns the most recent non-prerelease.
@@ -185,6 +205,9 @@ impl Registry for GitHubReleasesRegistry {
     }
 
     async fn get_latest_version_including_prereleases(&self, package: &str) -> Result<String> {
+        if gitlab_tags::split_project(package).is_some() {
+            return self.gitlab.latest_tag(package, true).await;
+        }
         let (owner, repo) = Self::extract_owner_repo(package)?;
 
         let tags = self.fetch_tags(owner, repo).await?;
@@ -203,6 +226,9 @@ impl Registry for GitHubReleasesRegistry {
     }
 
     async fn list_versions(&self, package: &str) -> Result<Vec<VersionMeta>> {
+        if gitlab_tags::split_project(package).is_some() {
+            return self.gitlab.list_versions(package).await;
+        }
         let (owner, repo) = Self::extract_owner_repo(package)?;
         let url = format!("{}/repos/{}/{}/releases", self.api_url, owner, repo);
 
@@ -243,10 +269,43 @@ impl Registry for GitHubReleasesRegistry {
                     published_at,
                     yanked: false,
                     prerelease: r.prerelease,
+                    rust_version: None,
+                    node_engines: None,
+                    deprecated: None,
                 }
             })
             .collect())
     }
+
+    async fn resolve_tag_commit(&self, package: &str, tag: &str) -> Result<String> {
+        if gitlab_tags::split_project(package).is_some() {
+            return self.gitlab.resolve_tag_commit(package, tag).await;
+        }
+        let (owner, repo) = Self::extract_owner_repo(package)?;
+        // The commits endpoint peels annotated tags down to the commit.
+        let url = format!("{}/repos/{}/{}/commits/{}", self.api_url, owner, repo, tag);
+
+        let response = get_with_retry(&self.client, &url).await?;
+        let status = response.status();
+        if !status.is_success() {
+            let hint = match status.as_u16() {
+                403 | 429 => Some("Set GITHUB_TOKEN to increase the API rate limit."),
+                _ => None,
+            };
+            return Err(anyhow!(http_error_message(
+                status,
+                "Tag",
+                &format!("{owner}/{repo}@{tag}"),
+                hint,
+            )));
+        }
+
+        let commit: CommitResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse commit of '{package}@{tag}': {e}"))?;
+        Ok(commit.sha)
+    }
 }
 
 #[cfg(test)]
@@ -260,6 +319,32 @@ mod tests {
         GitHubReleasesRegistry::with_api_url(server.uri())
     }
 
+    #[tokio::test]
+    async fn test_resolve_tag_commit() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/actions/checkout/commits/v4.2.2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"sha": "11bd71901bbe5b1630ceea73d27597364c9af683", "commit": {}}"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let sha = registry(&server)
+            .resolve_tag_commit("actions/checkout", "v4.2.2")
+            .await
+            .unwrap();
+        assert_eq!(sha, "11bd71901bbe5b1630ceea73d27597364c9af683");
+
+        let err = registry(&server)
+            .resolve_tag_commit("actions/checkout", "v9")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("actions/checkout@v9"), "{err}");
+    }
+
     #[tokio::test]
     async fn test_get_latest_version_from_releases() {
         let server = MockServer::start().await;
@@ -352,6 +437,27 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_gitlab_projects_use_the_gitlab_tags_api() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/projects/pycqa%2Fflake8/repository/tags"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"[{"name": "7.1.1", "commit": {"id": "abc"}},
+                    {"name": "7.0.0", "commit": {"id": "def"}}]"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let reg = registry(&server);
+        assert_eq!(
+            reg.get_latest_version("gitlab.com/pycqa/flake8")
+                .await
+                .unwrap(),
+            "7.1.1"
+        );
+    }
+
     #[tokio::test]
     async fn test_registry_name() {
         let server = MockServer::start().await;
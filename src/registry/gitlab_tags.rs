@@ -0,0 +1,210 @@
+use super::{VersionMeta, get_with_retry, http_error_message};
+use crate::version::TagVersion;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Tags of GitLab projects, from the `/api/v4/projects/<id>/repository/tags`
+/// endpoint of the project's own host. Packages are host-qualified project
+/// paths, e.g. `gitlab.com/pycqa/flake8`.
+pub(super) struct GitLabTags {
+    client: Client,
+    /// Replaces `https://<host>/api/v4` for every host (tests).
+    api_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagResponse {
+    name: String,
+    commit: TagCommit,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagCommit {
+    id: String,
+    #[serde(default)]
+    committed_date: Option<String>,
+}
+
+/// Split a host-qualified package into its host and project path. GitHub
+/// owners cannot contain dots, so a dotted first segment is a host.
+pub(super) fn split_project(package: &str) -> Option<(&str, &str)> {
+    let (host, project) = package.split_once('/')?;
+    (host.contains('.') && !project.is_empty()).then_some((host, project))
+}
+
+impl GitLabTags {
+    pub(super) fn new() -> Self {
+        Self::with_api_url(None)
+    }
+
+    pub(super) fn with_api_url(api_url: Option<String>) -> Self {
+        let client = crate::http::apply(
+            Client::builder()
+                .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
+                .connect_timeout(Duration::from_secs(10)),
+        )
+        .build()
+        .expect("Failed to create HTTP client for GitLab API.");
+
+        Self { client, api_url }
+    }
+
+    /// All tags of `package`, newest first.
+    async fn fetch_tags(&self, package: &str) -> Result<Vec<TagResponse>> {
+        let (host, project) = split_project(package)
+            .ok_or_else(|| anyhow!("Invalid GitLab project '{}'", package))?;
+        let api_url = self
+            .api_url
+            .clone()
+            .unwrap_or_else(|| format!("https://{host}/api/v4"));
+        let url = format!(
+            "{}/projects/{}/repository/tags?per_page=100",
+            api_url,
+            project.replace('/', "%2F")
+        );
+
+        let response = get_with_retry(&self.client, &url).await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(http_error_message(
+                response.status(),
+                "Repository",
+                package,
+                None
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse GitLab tags for '{}': {}", package, e))
+    }
+
+    /// The highest version-like tag, skipping pre-releases unless
+    /// `include_prereleases`.
+    pub(super) async fn latest_tag(
+        &self,
+        package: &str,
+        include_prereleases: bool,
+    ) -> Result<String> {
+        self.fetch_tags(package)
+            .await?
+            .into_iter()
+            .filter_map(|t| TagVersion::parse(&t.name).map(|v| (v, t.name)))
+            .filter(|(v, _)| include_prereleases || !v.is_prerelease())
+            .max_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, tag)| tag)
+            .ok_or_else(|| anyhow!("Repository '{}' has no version tags.", package))
+    }
+
+    /// Version-like tags with the date of the commit they point to.
+    pub(super) async fn list_versions(&self, package: &str) -> Result<Vec<VersionMeta>> {
+        Ok(self
+            .fetch_tags(package)
+            .await?
+            .into_iter()
+            .filter_map(|t| {
+                let version = TagVersion::parse(&t.name)?;
+                Some(VersionMeta {
+                    published_at: t
+                        .commit
+                        .committed_date
+                        .as_deref()
+                        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                        .map(|dt| dt.with_timezone(&Utc)),
+                    version: t.name,
+                    yanked: false,
+                    prerelease: version.is_prerelease(),
+                    rust_version: None,
+                    node_engines: None,
+                    deprecated: None,
+                })
+            })
+            .collect())
+    }
+
+    pub(super) async fn resolve_tag_commit(&self, package: &str, tag: &str) -> Result<String> {
+        self.fetch_tags(package)
+            .await?
+            .into_iter()
+            .find(|t| t.name == tag)
+            .map(|t| t.commit.id)
+            .ok_or_else(|| anyhow!("Tag '{}' not found in '{}'", tag, package))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mock_flake8() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/projects/pycqa%2Fflake8/repository/tags"))
+            .and(query_param("per_page", "100"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"[
+                    {"name": "7.1.0rc1", "commit": {"id": "ccc", "committed_date": "2024-06-10T12:00:00.000+00:00"}},
+                    {"name": "latest", "commit": {"id": "bbb"}},
+                    {"name": "7.1.0-rc.1", "commit": {"id": "ddd", "committed_date": "2024-06-10T12:00:00.000+00:00"}},
+                    {"name": "7.0.0", "commit": {"id": "aaa", "committed_date": "2024-01-04T19:25:00.000+00:00"}},
+                    {"name": "6.1.0", "commit": {"id": "999", "committed_date": "2023-07-29T22:00:00.000+00:00"}}
+                ]"#,
+            ))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[test]
+    fn test_split_project_requires_a_host() {
+        assert_eq!(
+            split_project("gitlab.com/group/sub/project"),
+            Some(("gitlab.com", "group/sub/project"))
+        );
+        assert_eq!(split_project("psf/black"), None);
+        assert_eq!(split_project("gitlab.com/"), None);
+    }
+
+    #[tokio::test]
+    async fn test_latest_tag_skips_non_version_and_prerelease_tags() {
+        let server = mock_flake8().await;
+        let tags = GitLabTags::with_api_url(Some(server.uri()));
+
+        assert_eq!(
+            tags.latest_tag("gitlab.com/pycqa/flake8", false)
+                .await
+                .unwrap(),
+            "7.0.0"
+        );
+        assert_eq!(
+            tags.latest_tag("gitlab.com/pycqa/flake8", true)
+                .await
+                .unwrap(),
+            "7.1.0-rc.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_versions_and_tag_commit() {
+        let server = mock_flake8().await;
+        let tags = GitLabTags::with_api_url(Some(server.uri()));
+
+        let versions = tags.list_versions("gitlab.com/pycqa/flake8").await.unwrap();
+        let names: Vec<&str> = versions.iter().map(|v| v.version.as_str()).collect();
+        assert_eq!(names, vec!["7.1.0-rc.1", "7.0.0", "6.1.0"]);
+        assert!(versions[0].prerelease);
+        assert!(versions[1].published_at.is_some());
+
+        assert_eq!(
+            tags.resolve_tag_commit("gitlab.com/pycqa/flake8", "7.0.0")
+                .await
+                .unwrap(),
+            "aaa"
+        );
+    }
+}
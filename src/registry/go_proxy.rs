@@ -10,12 +10,6 @@ use reqwest::{Client, Response};
 use serde::Deserialize;
 use std::time::Duration;
 
-/// Maximum number of retry attempts for failed HTTP requests
-const MAX_RETRIES: u32 = 3;
-
-/// Base delay for exponential backoff (100ms, 200ms, 400ms)
-const BASE_DELAY_MS: u64 = 100;
-
 /// Configuration for Go private modules from environment variables
 #[derive(Debug, Clone, Default)]
 pub struct GoPrivateConfig {
@@ -187,7 +181,6 @@ impl GoProxyRegistry {
             Client::builder()
                 .gzip(true)
                 .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
-                .timeout(Duration::from_secs(30))
                 .connect_timeout(Duration::from_secs(10))
                 .default_headers(headers),
         )
@@ -239,26 +232,29 @@ impl GoProxyRegistry {
 
     /// Execute a GET request with retry
     async fn get_with_retry(&self, url: &str) -> anyhow::Result<Response> {
+        crate::http::ensure_host_allowed(url)?;
+        let _slot = crate::http::acquire_host_slot(url).await;
         let mut last_error = None;
 
-        for attempt in 0..MAX_RETRIES {
+        let attempts = super::max_attempts();
+
+        for attempt in 0..attempts {
+            crate::http::acquire_rate_token().await;
             match self.client.get(url).send().await {
                 Ok(response) => {
                     if response.status().is_client_error() || response.status().is_success() {
                         return Ok(response);
                     }
-                    if response.status().is_server_error() && attempt < MAX_RETRIES - 1 {
-                        let delay = Duration::from_millis(BASE_DELAY_MS * (1 << attempt));
-                        tokio::time::sleep(delay).await;
+                    if response.status().is_server_error() && attempt + 1 < attempts {
+                        tokio::time::sleep(super::retry_backoff(attempt)).await;
                         continue;
                     }
                     return Ok(response);
                 }
                 Err(e) => {
                     last_error = Some(e);
-                    if attempt < MAX_RETRIES - 1 {
-                        let delay = Duration::from_millis(BASE_DELAY_MS * (1 << attempt));
-                        tokio::time::sleep(delay).await;
+                    if attempt + 1 < attempts {
+                        tokio::time::sleep(super::retry_backoff(attempt)).await;
                     }
                 }
             }
@@ -465,6 +461,9 @@ impl Registry for GoProxyRegistry {
                     version: info.version,
                     published_at,
                     yanked: false,
+                    rust_version: None,
+                    node_engines: None,
+                    deprecated: None,
                 }
             })
             .collect())
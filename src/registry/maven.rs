@@ -0,0 +1,362 @@
+use super::{Registry, get_with_retry, http_error_message};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::cmp::Ordering;
+use std::time::Duration;
+
+/// JVM artifacts from a Maven repository's `maven-metadata.xml`
+/// (`<repo>/<group path>/<artifact>/maven-metadata.xml`). Packages are
+/// `group:artifact` coordinates.
+pub struct MavenRegistry {
+    client: Client,
+    repo_url: String,
+}
+
+/// The `<versioning>` block of `maven-metadata.xml`.
+#[derive(Debug, Default, PartialEq)]
+struct MavenMetadata {
+    latest: Option<String>,
+    release: Option<String>,
+    versions: Vec<String>,
+}
+
+/// Text of the first `<tag>...</tag>` element in `xml`, trimmed.
+fn element<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("<{tag}>");
+    let start = xml.find(&open)? + open.len();
+    let end = xml[start..].find(&format!("</{tag}>"))? + start;
+    Some(xml[start..end].trim())
+}
+
+impl MavenMetadata {
+    fn parse(xml: &str) -> Self {
+        let versions = element(xml, "versions")
+            .map(|block| {
+                block
+                    .split("</version>")
+                    .filter_map(|part| Some(part.split_once("<version>")?.1.trim()))
+                    .filter(|version| !version.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            latest: element(xml, "latest").map(str::to_string),
+            release: element(xml, "release").map(str::to_string),
+            versions,
+        }
+    }
+
+    /// The highest listed version accepted by `keep`, falling back to the
+    /// `<release>` and then `<latest>` markers when the list is empty.
+    /// Snapshots are never returned.
+    fn highest(&self, keep: impl Fn(&str) -> bool) -> Option<String> {
+        let keep = |v: &&String| !is_snapshot(v) && keep(v);
+        self.versions
+            .iter()
+            .filter(keep)
+            .max_by(|a, b| maven_version_cmp(a, b))
+            .or_else(|| self.release.as_ref().filter(keep))
+            .or_else(|| self.latest.as_ref().filter(keep))
+            .cloned()
+    }
+}
+
+/// One token of a Maven version, split at `.`, `-` and digit/letter
+/// boundaries (`1.0-RC2` → `1`, `0`, `rc`, `2`).
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Number(u64),
+    Qualifier(String),
+}
+
+/// Rank of a plain release among qualifiers; trailing release tokens
+/// (`1.0-ga`, `1.0.0`) compare equal to no token at all.
+const RELEASE_RANK: u8 = 5;
+
+/// Qualifier order of Maven's `ComparableVersion`, extended with the
+/// `ea`/`dev`/`preview` labels JVM projects use for early builds. Unknown
+/// qualifiers (`jre`, `android`) sort after a plain release.
+fn qualifier_rank(qualifier: &str) -> u8 {
+    match qualifier {
+        "dev" | "ea" | "alpha" | "a" => 0,
+        "beta" | "b" => 1,
+        "milestone" | "m" => 2,
+        "rc" | "cr" | "pre" | "preview" => 3,
+        "snapshot" => 4,
+        "" | "ga" | "final" | "release" => RELEASE_RANK,
+        "sp" => 6,
+        _ => 7,
+    }
+}
+
+fn tokenize(version: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let flush = |current: &mut String, tokens: &mut Vec<Token>| {
+        if current.is_empty() {
+            return;
+        }
+        tokens.push(match current.parse() {
+            Ok(n) => Token::Number(n),
+            Err(_) => Token::Qualifier(current.to_ascii_lowercase()),
+        });
+        current.clear();
+    };
+    for c in version.trim().chars() {
+        if c == '.' || c == '-' || c == '_' {
+            flush(&mut current, &mut tokens);
+            continue;
+        }
+        let boundary = current
+            .chars()
+            .last()
+            .is_some_and(|last| last.is_ascii_digit() != c.is_ascii_digit());
+        if boundary {
+            flush(&mut current, &mut tokens);
+        }
+        current.push(c);
+    }
+    flush(&mut current, &mut tokens);
+    tokens
+}
+
+fn compare_tokens(a: Option<&Token>, b: Option<&Token>) -> Ordering {
+    match (a, b) {
+        (Some(Token::Number(a)), Some(Token::Number(b))) => a.cmp(b),
+        (Some(Token::Number(_)), Some(Token::Qualifier(_))) => Ordering::Greater,
+        (Some(Token::Qualifier(_)), Some(Token::Number(_))) => Ordering::Less,
+        (Some(Token::Qualifier(a)), Some(Token::Qualifier(b))) => qualifier_rank(a)
+            .cmp(&qualifier_rank(b))
+            .then_with(|| match qualifier_rank(a) {
+                7 => a.cmp(b),
+                _ => Ordering::Equal,
+            }),
+        (Some(Token::Number(n)), None) => n.cmp(&0),
+        (None, Some(Token::Number(n))) => 0.cmp(n),
+        (Some(Token::Qualifier(q)), None) => qualifier_rank(q).cmp(&RELEASE_RANK),
+        (None, Some(Token::Qualifier(q))) => RELEASE_RANK.cmp(&qualifier_rank(q)),
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Order two Maven versions the way Maven does: numerically per segment,
+/// `alpha` < `beta` < `milestone` < `rc` < `snapshot` < release < `sp`.
+pub(crate) fn maven_version_cmp(a: &str, b: &str) -> Ordering {
+    let (a, b) = (tokenize(a), tokenize(b));
+    (0..a.len().max(b.len()))
+        .map(|i| compare_tokens(a.get(i), b.get(i)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Whether a Maven version is a pre-release (`2.0.0-RC1`, `1.0-beta-2`,
+/// `6.0.0-M1`, `1.0-SNAPSHOT`). Variant suffixes such as Guava's `-jre` are
+/// stable.
+pub(crate) fn is_maven_prerelease(version: &str) -> bool {
+    tokenize(version).iter().any(|token| match token {
+        Token::Qualifier(q) => qualifier_rank(q) < RELEASE_RANK,
+        Token::Number(_) => false,
+    })
+}
+
+fn is_snapshot(version: &str) -> bool {
+    version.to_ascii_uppercase().ends_with("SNAPSHOT")
+}
+
+impl MavenRegistry {
+    pub fn new() -> Self {
+        Self::with_repo_url("https://repo1.maven.org/maven2".to_string())
+    }
+
+    pub fn with_repo_url(repo_url: String) -> Self {
+        let client = crate::http::apply(
+            Client::builder()
+                .gzip(true)
+                .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
+                .connect_timeout(Duration::from_secs(10)),
+        )
+        .build()
+        .expect("Failed to create HTTP client. This usually indicates a TLS/SSL configuration issue on your system.");
+
+        Self { client, repo_url }
+    }
+
+    async fn fetch_metadata(&self, package: &str) -> Result<MavenMetadata> {
+        let (group, artifact) = package
+            .split_once(':')
+            .filter(|(group, artifact)| !group.is_empty() && !artifact.is_empty())
+            .ok_or_else(|| anyhow!("Maven package '{}' is not a group:artifact", package))?;
+        let url = format!(
+            "{}/{}/{}/maven-metadata.xml",
+            self.repo_url,
+            group.replace('.', "/"),
+            artifact
+        );
+        let response = get_with_retry(&self.client, &url).await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(http_error_message(
+                response.status(),
+                "Maven artifact",
+                package,
+                None
+            )));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| anyhow!("Failed to read Maven metadata for '{}': {}", package, e))?;
+        Ok(MavenMetadata::parse(&body))
+    }
+}
+
+impl Default for MavenRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Registry for MavenRegistry {
+    async fn get_latest_version(&self, package: &str) -> Result<String> {
+        self.fetch_metadata(package)
+            .await?
+            .highest(|v| !is_maven_prerelease(v))
+            .ok_or_else(|| anyhow!("Maven artifact '{}' has no stable versions", package))
+    }
+
+    async fn get_latest_version_including_prereleases(&self, package: &str) -> Result<String> {
+        self.fetch_metadata(package)
+            .await?
+            .highest(|_| true)
+            .ok_or_else(|| anyhow!("Maven artifact '{}' has no versions", package))
+    }
+
+    fn name(&self) -> &'static str {
+        "maven"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const METADATA: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>com.google.guava</groupId>
+  <artifactId>guava</artifactId>
+  <versioning>
+    <latest>34.0.0-rc1</latest>
+    <release>34.0.0-rc1</release>
+    <versions>
+      <version>32.1.3-jre</version>
+      <version>33.0.0-jre</version>
+      <version>33.1.0-SNAPSHOT</version>
+      <version>34.0.0-rc1</version>
+    </versions>
+  </versioning>
+</metadata>"#;
+
+    #[test]
+    fn test_maven_version_cmp_follows_maven_ordering() {
+        let ordered = [
+            "1.0-alpha-1",
+            "1.0-beta",
+            "1.0-M2",
+            "1.0-RC1",
+            "1.0-SNAPSHOT",
+            "1.0",
+            "1.0-sp1",
+            "1.0.1",
+            "1.2",
+            "1.10",
+        ];
+        for pair in ordered.windows(2) {
+            assert_eq!(
+                maven_version_cmp(pair[0], pair[1]),
+                Ordering::Less,
+                "{pair:?}"
+            );
+        }
+        assert_eq!(maven_version_cmp("1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(maven_version_cmp("1.0-ga", "1"), Ordering::Equal);
+        assert_eq!(
+            maven_version_cmp("33.0.0-jre", "32.1.3-jre"),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn test_is_maven_prerelease() {
+        for version in [
+            "2.0.0-RC1",
+            "1.0-beta-2",
+            "6.0.0-M1",
+            "1.0-SNAPSHOT",
+            "1.0a1",
+        ] {
+            assert!(is_maven_prerelease(version), "{version}");
+        }
+        for version in ["1.9.0", "33.0.0-jre", "1.0-sp1", "5.10.0.Final"] {
+            assert!(!is_maven_prerelease(version), "{version}");
+        }
+    }
+
+    #[test]
+    fn test_metadata_parse_reads_versioning() {
+        let metadata = MavenMetadata::parse(METADATA);
+        assert_eq!(metadata.latest.as_deref(), Some("34.0.0-rc1"));
+        assert_eq!(metadata.release.as_deref(), Some("34.0.0-rc1"));
+        assert_eq!(metadata.versions.len(), 4);
+        assert_eq!(
+            metadata.highest(|v| !is_maven_prerelease(v)).as_deref(),
+            Some("33.0.0-jre")
+        );
+    }
+
+    #[test]
+    fn test_metadata_without_version_list_falls_back_to_release() {
+        let metadata = MavenMetadata::parse(
+            "<metadata><versioning><release>2.1</release></versioning></metadata>",
+        );
+        assert_eq!(metadata.highest(|_| true).as_deref(), Some("2.1"));
+    }
+
+    #[tokio::test]
+    async fn test_maven_latest_skips_prereleases_and_snapshots() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/com/google/guava/guava/maven-metadata.xml"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(METADATA))
+            .mount(&mock_server)
+            .await;
+
+        let registry = MavenRegistry::with_repo_url(mock_server.uri());
+        assert_eq!(
+            registry
+                .get_latest_version("com.google.guava:guava")
+                .await
+                .unwrap(),
+            "33.0.0-jre"
+        );
+        assert_eq!(
+            registry
+                .get_latest_version_including_prereleases("com.google.guava:guava")
+                .await
+                .unwrap(),
+            "34.0.0-rc1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_maven_rejects_package_without_group() {
+        let registry = MavenRegistry::with_repo_url("http://127.0.0.1:1".to_string());
+        let err = registry.get_latest_version("guava").await.unwrap_err();
+        assert!(err.to_string().contains("not a group:artifact"), "{err}");
+    }
+}
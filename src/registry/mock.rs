@@ -1,6 +1,6 @@
 //! Mock registry for testing updaters without network calls.
 
-use super::{Registry, VersionMeta};
+use super::{PackageLinks, Registry, VersionMeta};
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -14,6 +14,10 @@ pub struct MockRegistry {
     constrained_versions: HashMap<(String, String), String>,
     /// Map of package name to full version metadata entries
     version_metas: HashMap<String, Vec<VersionMeta>>,
+    /// Map of package name to published project links
+    links: HashMap<String, PackageLinks>,
+    /// Map of package name + tag to the commit SHA it points to
+    commits: HashMap<(String, String), String>,
     /// Registry name
     name: &'static str,
 }
@@ -25,6 +29,8 @@ impl MockRegistry {
             versions: HashMap::new(),
             constrained_versions: HashMap::new(),
             version_metas: HashMap::new(),
+            links: HashMap::new(),
+            commits: HashMap::new(),
             name,
         }
     }
@@ -62,10 +68,77 @@ impl MockRegistry {
                 published_at,
                 yanked,
                 prerelease,
+                rust_version: None,
+                node_engines: None,
+                deprecated: None,
             });
         self
     }
 
+    /// Add a stable version metadata entry declaring a minimum Rust version.
+    pub fn with_rust_version(mut self, package: &str, version: &str, rust_version: &str) -> Self {
+        self.version_metas
+            .entry(package.to_string())
+            .or_default()
+            .push(VersionMeta {
+                version: version.to_string(),
+                published_at: None,
+                yanked: false,
+                prerelease: false,
+                rust_version: Some(rust_version.to_string()),
+                node_engines: None,
+                deprecated: None,
+            });
+        self
+    }
+
+    /// Add a stable version metadata entry declaring an `engines.node` range.
+    pub fn with_node_engines(mut self, package: &str, version: &str, node_engines: &str) -> Self {
+        self.version_metas
+            .entry(package.to_string())
+            .or_default()
+            .push(VersionMeta {
+                version: version.to_string(),
+                published_at: None,
+                yanked: false,
+                prerelease: false,
+                rust_version: None,
+                node_engines: Some(node_engines.to_string()),
+                deprecated: None,
+            });
+        self
+    }
+
+    /// Add a stable version metadata entry that npm marks as deprecated.
+    pub fn with_deprecated(mut self, package: &str, version: &str, message: &str) -> Self {
+        self.version_metas
+            .entry(package.to_string())
+            .or_default()
+            .push(VersionMeta {
+                version: version.to_string(),
+                published_at: None,
+                yanked: true,
+                prerelease: false,
+                rust_version: None,
+                node_engines: None,
+                deprecated: Some(message.to_string()),
+            });
+        self
+    }
+
+    /// Add the project links published for a package.
+    pub fn with_links(mut self, package: &str, links: PackageLinks) -> Self {
+        self.links.insert(package.to_string(), links);
+        self
+    }
+
+    /// Add the commit SHA a package's tag points to.
+    pub fn with_commit(mut self, package: &str, tag: &str, sha: &str) -> Self {
+        self.commits
+            .insert((package.to_string(), tag.to_string()), sha.to_string());
+        self
+    }
+
     /// Add a constrained version result for a package.
     pub fn with_constrained(mut self, package: &str, constraints: &str, version: &str) -> Self {
         self.constrained_versions.insert(
@@ -113,6 +186,17 @@ impl Registry for MockRegistry {
         Ok(self.version_metas.get(package).cloned().unwrap_or_default())
     }
 
+    async fn package_links(&self, package: &str) -> Result<PackageLinks> {
+        Ok(self.links.get(package).cloned().unwrap_or_default())
+    }
+
+    async fn resolve_tag_commit(&self, package: &str, tag: &str) -> Result<String> {
+        self.commits
+            .get(&(package.to_string(), tag.to_string()))
+            .cloned()
+            .ok_or_else(|| anyhow!("Tag not found: {}@{}", package, tag))
+    }
+
     fn name(&self) -> &'static str {
         self.name
     }
@@ -1,9 +1,9 @@
 use super::utils::home_dir;
-use super::{Registry, VersionMeta, get_with_retry, http_error_message};
+use super::{PackageLinks, Registry, Revalidated, VersionMeta, get_with_retry, http_error_message};
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use reqwest::Client;
-use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use reqwest::header::{AUTHORIZATION, ETAG, HeaderMap, HeaderValue, IF_NONE_MATCH};
 use serde::Deserialize;
 use serde_json::Value;
 use std::io::BufRead;
@@ -50,6 +50,9 @@ struct DistTags {
 struct NpmVersionMetaDe {
     #[serde(default)]
     deprecated: Option<String>,
+    /// `{ "node": ">=18" }`; anything else is ignored.
+    #[serde(default)]
+    engines: Option<Value>,
 }
 
 /// Full npm package metadata, used for `list_versions`.
@@ -61,6 +64,11 @@ struct NpmPackageMetadata {
     versions: std::collections::HashMap<String, NpmVersionMetaDe>,
     #[serde(default)]
     time: std::collections::HashMap<String, String>,
+    /// Either a URL / `github:owner/repo` shorthand string or `{ "url": ... }`.
+    #[serde(default)]
+    repository: Option<Value>,
+    #[serde(default)]
+    homepage: Option<String>,
 }
 
 /// Scoped registry configuration from .npmrc
@@ -267,7 +275,6 @@ impl NpmRegistry {
             Client::builder()
                 .gzip(true)
                 .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
-                .timeout(Duration::from_secs(30))
                 .connect_timeout(Duration::from_secs(10))
                 .default_headers(headers),
         )
@@ -346,17 +353,42 @@ impl NpmRegistry {
     /// Fetch abbreviated package metadata from npm
     /// Uses the install-v1 format which is smaller and faster
     async fn fetch_package(&self, package: &str) -> Result<NpmAbbreviatedResponse> {
+        self.fetch_package_revalidated(package, None)
+            .await?
+            .map(|(data, _)| data)
+            .ok_or_else(|| anyhow!("npm answered 304 for '{}' without If-None-Match", package))
+    }
+
+    /// Like [`Self::fetch_package`], sending `etag` as `If-None-Match`.
+    /// Returns `None` on HTTP 304, otherwise the metadata and its `ETag`.
+    async fn fetch_package_revalidated(
+        &self,
+        package: &str,
+        etag: Option<&str>,
+    ) -> Result<Option<(NpmAbbreviatedResponse, Option<String>)>> {
         let url = format!("{}/{}", self.registry_url, package);
+        crate::http::ensure_host_allowed(&url)?;
+        let _slot = crate::http::acquire_host_slot(&url).await;
 
         // Use abbreviated metadata format (much smaller for large packages like react)
-        let response = self
+        let mut request = self
             .client
             .get(&url)
-            .header("Accept", "application/vnd.npm.install-v1+json")
+            .header("Accept", "application/vnd.npm.install-v1+json");
+        if let Some(etag) = etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        crate::http::acquire_rate_token().await;
+        let response = request
             .send()
             .await
             .map_err(|e| crate::http::wrap_send_err(e, &url))?;
 
+        if etag.is_some() && response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            tracing::debug!(package, "not modified");
+            return Ok(None);
+        }
+
         if !response.status().is_success() {
             return Err(anyhow!(http_error_message(
                 response.status(),
@@ -366,15 +398,21 @@ impl NpmRegistry {
             )));
         }
 
-        response
+        let new_etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let data = response
             .json()
             .await
-            .map_err(|e| anyhow!("Failed to parse npm response for '{}': {}", package, e))
+            .map_err(|e| anyhow!("Failed to parse npm response for '{}': {}", package, e))?;
+        Ok(Some((data, new_etag)))
     }
 
     /// Get all stable (non-prerelease) versions sorted descending.
-    /// Note: abbreviated metadata doesn't include deprecated info, but dist-tags.latest
-    /// is authoritative for the recommended version.
+    /// Deprecated versions are kept; the updater checks deprecation against
+    /// `list_versions` once it has picked a candidate.
     fn get_stable_versions(data: &NpmAbbreviatedResponse) -> Vec<(semver::Version, String)> {
         let versions_obj = match data.versions.as_object() {
             Some(obj) => obj,
@@ -430,6 +468,27 @@ impl NpmRegistry {
         self.fetch_package(package).await
     }
 
+    /// Latest stable version in `data`: the `latest` dist-tag when it is
+    /// stable, otherwise the highest stable version listed.
+    fn latest_stable(data: &NpmAbbreviatedResponse, package: &str) -> Result<String> {
+        // Use the 'latest' dist-tag (this is the authoritative answer from npm)
+        if let Some(latest) = &data.dist_tags.latest
+            && let Ok(v) = semver::Version::parse(latest)
+            && v.pre.is_empty()
+        {
+            return Ok(latest.clone());
+        }
+
+        // Fall back to finding the latest stable version from the versions list
+        let versions = Self::get_stable_versions(data);
+        versions.first().map(|(_, s)| s.clone()).ok_or_else(|| {
+            anyhow!(
+                "Package '{}' exists but has no stable versions. Only pre-releases are available.",
+                package
+            )
+        })
+    }
+
     /// Fetch full package metadata (not the abbreviated install-v1 format).
     /// Resolves scoped packages to their configured private registry so the
     /// correct endpoint and auth headers are used for `@scope/pkg` packages.
@@ -441,9 +500,17 @@ impl NpmRegistry {
         // For scoped packages, delegate to the scoped-registry instance so that
         // its configured registry URL and auth headers are used.
         if let Some(scoped_registry) = Self::for_scoped_package(package) {
-            return Box::pin(scoped_registry.fetch_full_metadata(package)).await;
+            return scoped_registry.fetch_full_metadata_unscoped(package).await;
         }
+        self.fetch_full_metadata_unscoped(package).await
+    }
 
+    /// Fetch full package metadata from this instance's registry URL, without
+    /// scope resolution (which would otherwise recurse for scoped packages).
+    async fn fetch_full_metadata_unscoped(
+        &self,
+        package: &str,
+    ) -> Result<(reqwest::StatusCode, Option<NpmPackageMetadata>)> {
         let url = format!("{}/{}", self.registry_url, package);
         // The `time` publish-date map is only present on the full metadata document.
         let response = get_with_retry(&self.client, &url).await?;
@@ -477,22 +544,28 @@ impl Default for NpmRegistry {
 impl Registry for NpmRegistry {
     async fn get_latest_version(&self, package: &str) -> Result<String> {
         let data = self.fetch_package_with_scope_resolution(package).await?;
+        Self::latest_stable(&data, package)
+    }
 
-        // Use the 'latest' dist-tag (this is the authoritative answer from npm)
-        if let Some(latest) = &data.dist_tags.latest
-            && let Ok(v) = semver::Version::parse(latest)
-            && v.pre.is_empty()
-        {
-            return Ok(latest.clone());
-        }
-
-        // Fall back to finding the latest stable version from the versions list
-        let versions = Self::get_stable_versions(&data);
-        versions.first().map(|(_, s)| s.clone()).ok_or_else(|| {
-            anyhow!(
-                "Package '{}' exists but has no stable versions. Only pre-releases are available.",
-                package
-            )
+    async fn get_latest_version_revalidated(
+        &self,
+        package: &str,
+        etag: Option<&str>,
+    ) -> Result<Revalidated> {
+        let fetched = match Self::for_scoped_package(package) {
+            Some(scoped_registry) => {
+                scoped_registry
+                    .fetch_package_revalidated(package, etag)
+                    .await?
+            }
+            None => self.fetch_package_revalidated(package, etag).await?,
+        };
+        Ok(match fetched {
+            None => Revalidated::NotModified,
+            Some((data, etag)) => Revalidated::Modified {
+                version: Self::latest_stable(&data, package)?,
+                etag,
+            },
         })
     }
 
@@ -557,7 +630,9 @@ impl Registry for NpmRegistry {
 
         let mut out = Vec::new();
         for (ver, v_meta) in meta.versions.iter() {
-            let yanked = v_meta.deprecated.as_deref().is_some_and(|s| !s.is_empty());
+            // npm marks un-deprecation with an empty string.
+            let deprecated = v_meta.deprecated.clone().filter(|s| !s.is_empty());
+            let yanked = deprecated.is_some();
             let published_at = meta
                 .time
                 .get(ver)
@@ -569,11 +644,50 @@ impl Registry for NpmRegistry {
                 published_at,
                 yanked,
                 prerelease,
+                rust_version: None,
+                node_engines: v_meta
+                    .engines
+                    .as_ref()
+                    .and_then(|engines| engines.get("node"))
+                    .and_then(|node| node.as_str())
+                    .map(str::to_string),
+                deprecated,
             });
         }
         Ok(out)
     }
 
+    async fn package_links(&self, package: &str) -> Result<PackageLinks> {
+        let (status, meta) = self.fetch_full_metadata(package).await?;
+        if !status.is_success() {
+            return Err(anyhow!(http_error_message(
+                status,
+                "Package",
+                package,
+                Some("For private npm, add authToken to ~/.npmrc or set NPM_TOKEN.")
+            )));
+        }
+        let Some(meta) = meta else {
+            return Ok(PackageLinks::default());
+        };
+        let repository = match meta.repository {
+            // Bare `owner/repo` is npm's shorthand for a GitHub repository.
+            Some(Value::String(url)) if !url.contains(':') && url.matches('/').count() == 1 => {
+                Some(format!("github:{url}"))
+            }
+            Some(Value::String(url)) => Some(url),
+            Some(Value::Object(repo)) => {
+                repo.get("url").and_then(Value::as_str).map(str::to_string)
+            }
+            _ => None,
+        };
+        Ok(PackageLinks {
+            repository,
+            homepage: meta.homepage,
+            changelog: None,
+        })
+    }
+
     fn name(&self) -> &'static str {
         "npm"
     }
@@ -789,6 +903,52 @@ mod tests {
         assert!(config.is_none());
     }
 
+    #[tokio::test]
+    async fn test_npm_revalidates_with_if_none_match() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/lodash"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/lodash"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .set_body_string(
+                        r#"{"dist-tags": {"latest": "4.17.21"}, "versions": {"4.17.21": {}}}"#,
+                    ),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let registry = NpmRegistry::with_registry_url(mock_server.uri());
+
+        assert_eq!(
+            registry
+                .get_latest_version_revalidated("lodash", None)
+                .await
+                .unwrap(),
+            Revalidated::Modified {
+                version: "4.17.21".to_string(),
+                etag: Some("\"v1\"".to_string()),
+            }
+        );
+        assert_eq!(
+            registry
+                .get_latest_version_revalidated("lodash", Some("\"v1\""))
+                .await
+                .unwrap(),
+            Revalidated::NotModified
+        );
+    }
+
     #[tokio::test]
     async fn test_npm_list_versions_returns_publish_dates() {
         use wiremock::matchers::{method, path};
@@ -805,7 +965,7 @@ mod tests {
               "versions": {
                 "4.17.20": {"version": "4.17.20"},
                 "4.17.21": {"version": "4.17.21", "deprecated": "use later version"},
-                "5.0.0-rc.1": {"version": "5.0.0-rc.1"}
+                "5.0.0-rc.1": {"version": "5.0.0-rc.1", "engines": {"node": ">=18"}}
               },
               "time": {
                 "created": "2010-01-01T00:00:00.000Z",
@@ -833,6 +993,8 @@ mod tests {
 
         let v_21 = versions.iter().find(|v| v.version == "4.17.21").unwrap();
         assert!(v_21.yanked, "deprecated should mark as yanked for cooldown");
+        assert_eq!(v_21.deprecated.as_deref(), Some("use later version"));
+        assert_eq!(v_20.deprecated, None);
         assert!(
             v_21.published_at.is_some(),
             "4.17.21 timestamp should parse"
@@ -840,12 +1002,58 @@ mod tests {
 
         let rc = versions.iter().find(|v| v.version == "5.0.0-rc.1").unwrap();
         assert!(rc.prerelease, "pre-release version should be flagged");
+        assert_eq!(rc.node_engines.as_deref(), Some(">=18"));
+        assert_eq!(v_20.node_engines, None);
         assert!(
             rc.published_at.is_some(),
             "5.0.0-rc.1 timestamp should parse"
         );
     }
 
+    #[tokio::test]
+    async fn test_npm_package_links_reads_repository_object_and_shorthand() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/react"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+              "name": "react",
+              "repository": {"type": "git", "url": "git+https://github.com/facebook/react.git"},
+              "homepage": "https://react.dev/"
+            }"#,
+            ))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/left-pad"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"name": "left-pad", "repository": "stevemao/left-pad"}"#),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let registry = NpmRegistry::with_registry_url(mock_server.uri());
+
+        let react = registry.package_links("react").await.unwrap();
+        assert_eq!(
+            react.repository.as_deref(),
+            Some("git+https://github.com/facebook/react.git")
+        );
+        assert_eq!(react.homepage.as_deref(), Some("https://react.dev/"));
+
+        let left_pad = registry.package_links("left-pad").await.unwrap();
+        assert_eq!(
+            left_pad.repository.as_deref(),
+            Some("github:stevemao/left-pad")
+        );
+        assert_eq!(left_pad.homepage, None);
+    }
+
     #[test]
     fn test_get_scoped_registry_url_non_scoped() {
         // Non-scoped packages should return None
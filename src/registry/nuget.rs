@@ -29,7 +29,6 @@ impl NuGetRegistry {
             Client::builder()
                 .gzip(true)
                 .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
-                .timeout(Duration::from_secs(30))
                 .connect_timeout(Duration::from_secs(10)),
         )
         .build()
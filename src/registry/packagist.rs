@@ -0,0 +1,431 @@
+use super::{Registry, VersionMeta, get_with_retry, http_error_message};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Composer packages from Packagist's metadata API
+/// (`/p2/<vendor>/<package>.json`).
+pub struct PackagistRegistry {
+    client: Client,
+    api_url: String,
+}
+
+/// `/p2/` response: one list of version objects per package name. Dev
+/// branches live in a separate `~dev.json` file and never show up here.
+#[derive(Debug, Deserialize)]
+struct P2Response {
+    #[serde(default)]
+    packages: HashMap<String, Vec<P2Version>>,
+}
+
+/// One entry of a `/p2/` version list. The list is "minified" (each entry
+/// repeats only the fields that differ from the previous one), but `version`
+/// and `time` change with every release.
+#[derive(Debug, Deserialize)]
+struct P2Version {
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    time: Option<String>,
+}
+
+/// Parse a Composer version (`v2.1.0`, `5.4`, `3.0.0-RC1`, `1.0.0beta2`) as
+/// semver. Dev versions (`dev-main`, `2.x-dev`) are `None`; patch releases
+/// (`1.0.0-p1`) count as stable.
+pub(crate) fn composer_version(version: &str) -> Option<semver::Version> {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    let split = version
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(version.len());
+    let (release, suffix) = version.split_at(split);
+
+    let segments: Vec<u64> = release
+        .trim_end_matches('.')
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+    if segments.is_empty() || segments.len() > 4 {
+        return None;
+    }
+
+    let suffix = suffix
+        .trim_start_matches(['-', '.', '_', '+'])
+        .to_ascii_lowercase();
+    if suffix.starts_with("dev") || suffix.ends_with("dev") {
+        return None;
+    }
+    let pre = if suffix.is_empty() || ["p", "pl", "patch"].iter().any(|p| suffix.starts_with(p)) {
+        semver::Prerelease::EMPTY
+    } else {
+        let normalized: String = suffix
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '.' })
+            .collect();
+        semver::Prerelease::new(&normalized).ok()?
+    };
+
+    let segment = |i: usize| segments.get(i).copied().unwrap_or(0);
+    Some(semver::Version {
+        major: segment(0),
+        minor: segment(1),
+        patch: segment(2),
+        pre,
+        build: semver::BuildMetadata::EMPTY,
+    })
+}
+
+/// Translate a Composer constraint into a semver requirement string, one
+/// `||` alternative at a time. Composer's `~1.2` means `>=1.2, <2.0` (not
+/// npm's `<1.3`), and a bare `1.2` is exactly `1.2.0`. Returns `None` for
+/// shapes semver cannot express (`dev-main`, `!=`).
+fn composer_constraint_to_semver(constraint: &str) -> Option<Vec<String>> {
+    constraint
+        .split("||")
+        .flat_map(|alternative| alternative.split('|'))
+        .map(|alternative| {
+            let alternative = alternative.trim();
+            // `1.0 - 2.0` is an inclusive range.
+            if let Some((low, high)) = alternative.split_once(" - ") {
+                return Some(format!(
+                    ">={}, <={}",
+                    composer_version(low)?,
+                    composer_version(high)?
+                ));
+            }
+            let comparators = alternative
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|part| !part.is_empty())
+                .map(comparator_to_semver)
+                .collect::<Option<Vec<_>>>()?;
+            (!comparators.is_empty()).then(|| comparators.join(", "))
+        })
+        .collect()
+}
+
+fn comparator_to_semver(comparator: &str) -> Option<String> {
+    // Stability flags (`^1.0@beta`) do not change which releases match.
+    let comparator = comparator.split('@').next().unwrap_or_default();
+    if comparator == "*" {
+        return Some("*".to_string());
+    }
+    let split = comparator
+        .find(|c: char| c.is_ascii_alphanumeric())
+        .unwrap_or(comparator.len());
+    let (operator, version) = comparator.split_at(split);
+
+    if version.contains('*') || version.ends_with(".x") {
+        let wildcard = version.trim_start_matches(['v', 'V']).replace(".x", ".*");
+        return (operator.is_empty()).then_some(wildcard);
+    }
+    let parsed = composer_version(version)?;
+    let precision = version
+        .trim_start_matches(['v', 'V'])
+        .split(|c: char| !c.is_ascii_digit() && c != '.')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('.')
+        .split('.')
+        .count();
+
+    match operator {
+        "~" => {
+            let upper = match precision {
+                1 | 2 => format!("{}.0.0", parsed.major + 1),
+                _ => format!("{}.{}.0", parsed.major, parsed.minor + 1),
+            };
+            Some(format!(">={}, <{}", parsed, upper))
+        }
+        "^" | ">=" | ">" | "<=" | "<" => Some(format!("{}{}", operator, parsed)),
+        "" | "=" | "==" => Some(format!("={}", parsed)),
+        _ => None,
+    }
+}
+
+/// Whether `version` satisfies a Composer `constraint`; `None` when the
+/// constraint cannot be evaluated.
+fn composer_matches(constraint: &str, version: &semver::Version) -> Option<bool> {
+    let alternatives = composer_constraint_to_semver(constraint)?;
+    let mut matched = false;
+    for alternative in alternatives {
+        let req = semver::VersionReq::parse(&alternative).ok()?;
+        matched |= req.matches(version);
+    }
+    Some(matched)
+}
+
+impl PackagistRegistry {
+    pub fn new() -> Self {
+        Self::with_api_url("https://repo.packagist.org".to_string())
+    }
+
+    pub fn with_api_url(api_url: String) -> Self {
+        let client = crate::http::apply(
+            Client::builder()
+                .gzip(true)
+                .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
+                .connect_timeout(Duration::from_secs(10)),
+        )
+        .build()
+        .expect("Failed to create HTTP client. This usually indicates a TLS/SSL configuration issue on your system.");
+
+        Self { client, api_url }
+    }
+
+    /// Every tagged release of `package`, as written (`v2.1.0`), with its
+    /// parsed version. Dev versions are dropped.
+    async fn fetch_versions(&self, package: &str) -> Result<Vec<(P2Version, semver::Version)>> {
+        let name = package.to_lowercase();
+        let url = format!("{}/p2/{}.json", self.api_url, name);
+        let response = get_with_retry(&self.client, &url).await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(http_error_message(
+                response.status(),
+                "Composer package",
+                package,
+                None
+            )));
+        }
+
+        let mut body: P2Response = response.json().await.map_err(|e| {
+            anyhow!(
+                "Failed to parse Packagist response for '{}': {}",
+                package,
+                e
+            )
+        })?;
+
+        Ok(body
+            .packages
+            .remove(&name)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|entry| {
+                let parsed = composer_version(&entry.version)?;
+                Some((entry, parsed))
+            })
+            .collect())
+    }
+
+    /// The highest release accepted by `keep`, without its `v` prefix.
+    fn highest(
+        versions: Vec<(P2Version, semver::Version)>,
+        keep: impl Fn(&semver::Version) -> bool,
+    ) -> Option<String> {
+        versions
+            .into_iter()
+            .filter(|(_, parsed)| keep(parsed))
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(entry, _)| entry.version.trim_start_matches(['v', 'V']).to_string())
+    }
+}
+
+impl Default for PackagistRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Registry for PackagistRegistry {
+    async fn get_latest_version(&self, package: &str) -> Result<String> {
+        let versions = self.fetch_versions(package).await?;
+        Self::highest(versions, |v| v.pre.is_empty())
+            .ok_or_else(|| anyhow!("Composer package '{}' has no stable versions", package))
+    }
+
+    async fn get_latest_version_including_prereleases(&self, package: &str) -> Result<String> {
+        let versions = self.fetch_versions(package).await?;
+        Self::highest(versions, |_| true)
+            .ok_or_else(|| anyhow!("Composer package '{}' has no versions", package))
+    }
+
+    /// `constraints` is a Composer constraint (`^5.4`, `~1.2`, `>=2.0 <3.0`);
+    /// the answer is the highest stable release it admits.
+    async fn get_latest_version_matching(
+        &self,
+        package: &str,
+        constraints: &str,
+    ) -> Result<String> {
+        if composer_constraint_to_semver(constraints).is_none() {
+            return Err(anyhow!(
+                "Unsupported Composer constraint '{}' for '{}'",
+                constraints,
+                package
+            ));
+        }
+        let versions = self.fetch_versions(package).await?;
+        Self::highest(versions, |v| {
+            v.pre.is_empty() && composer_matches(constraints, v) == Some(true)
+        })
+        .ok_or_else(|| {
+            anyhow!(
+                "No version of '{}' matches constraint '{}'",
+                package,
+                constraints
+            )
+        })
+    }
+
+    async fn list_versions(&self, package: &str) -> Result<Vec<VersionMeta>> {
+        let versions = self.fetch_versions(package).await?;
+        Ok(versions
+            .into_iter()
+            .map(|(entry, parsed)| VersionMeta {
+                version: entry.version.trim_start_matches(['v', 'V']).to_string(),
+                published_at: entry
+                    .time
+                    .as_deref()
+                    .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                    .map(|dt| dt.with_timezone(&chrono::Utc)),
+                yanked: false,
+                prerelease: !parsed.pre.is_empty(),
+                rust_version: None,
+                node_engines: None,
+                deprecated: None,
+            })
+            .collect())
+    }
+
+    fn name(&self) -> &'static str {
+        "packagist"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mock_monolog() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/p2/monolog/monolog.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"minified": "composer/2.0", "packages": {"monolog/monolog": [
+                    {"version": "3.8.0-RC1", "time": "2024-11-01T10:00:00+00:00"},
+                    {"version": "3.7.0", "time": "2024-06-28T09:40:51+00:00"},
+                    {"version": "2.10.0", "time": "2024-11-12T12:43:37+00:00"},
+                    {"version": "2.9.3", "time": "2024-04-12T21:02:21+00:00"},
+                    {"version": "v1.27.1", "time": "2022-06-09T08:53:42+00:00"}
+                ]}}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[test]
+    fn test_composer_version() {
+        assert_eq!(
+            composer_version("v2.1.0"),
+            Some(semver::Version::new(2, 1, 0))
+        );
+        assert_eq!(composer_version("5.4"), Some(semver::Version::new(5, 4, 0)));
+        assert_eq!(composer_version("3.0.0-RC1").unwrap().pre.as_str(), "rc1");
+        assert_eq!(
+            composer_version("1.0.0beta2").unwrap().pre.as_str(),
+            "beta2"
+        );
+        assert!(composer_version("1.0.0-p1").unwrap().pre.is_empty());
+        assert_eq!(composer_version("dev-main"), None);
+        assert_eq!(composer_version("2.x-dev"), None);
+    }
+
+    #[test]
+    fn test_composer_constraints() {
+        let v = |s: &str| semver::Version::parse(s).unwrap();
+        // Composer's tilde keeps the major for two segments, the minor for three.
+        assert_eq!(composer_matches("~1.2", &v("1.9.0")), Some(true));
+        assert_eq!(composer_matches("~1.2", &v("2.0.0")), Some(false));
+        assert_eq!(composer_matches("~1.2.3", &v("1.2.9")), Some(true));
+        assert_eq!(composer_matches("~1.2.3", &v("1.3.0")), Some(false));
+        assert_eq!(composer_matches("^5.4", &v("5.9.1")), Some(true));
+        assert_eq!(composer_matches("^5.4", &v("6.0.0")), Some(false));
+        assert_eq!(composer_matches("^1.0 || ^2.0", &v("2.3.0")), Some(true));
+        assert_eq!(composer_matches(">=2.0 <3.0", &v("3.0.0")), Some(false));
+        assert_eq!(composer_matches("1.2.*", &v("1.2.7")), Some(true));
+        assert_eq!(composer_matches("1.2", &v("1.2.1")), Some(false));
+        assert_eq!(composer_matches("dev-main", &v("1.0.0")), None);
+    }
+
+    #[tokio::test]
+    async fn test_latest_stable_and_prerelease() {
+        let mock_server = mock_monolog().await;
+        let registry = PackagistRegistry::with_api_url(mock_server.uri());
+
+        assert_eq!(
+            registry
+                .get_latest_version("monolog/monolog")
+                .await
+                .unwrap(),
+            "3.7.0"
+        );
+        assert_eq!(
+            registry
+                .get_latest_version_including_prereleases("monolog/monolog")
+                .await
+                .unwrap(),
+            "3.8.0-RC1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_latest_matching_and_lowercased_name() {
+        let mock_server = mock_monolog().await;
+        let registry = PackagistRegistry::with_api_url(mock_server.uri());
+
+        assert_eq!(
+            registry
+                .get_latest_version_matching("Monolog/Monolog", "^2.9")
+                .await
+                .unwrap(),
+            "2.10.0"
+        );
+        assert_eq!(
+            registry
+                .get_latest_version_matching("monolog/monolog", "~1.0")
+                .await
+                .unwrap(),
+            "1.27.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_versions_reads_time() {
+        let mock_server = mock_monolog().await;
+        let registry = PackagistRegistry::with_api_url(mock_server.uri());
+
+        let versions = registry.list_versions("monolog/monolog").await.unwrap();
+        assert_eq!(versions.len(), 5);
+        assert!(versions[0].prerelease);
+        assert_eq!(versions[4].version, "1.27.1");
+        assert!(versions[4].published_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_package_not_found() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/p2/acme/missing.json"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let registry = PackagistRegistry::with_api_url(mock_server.uri());
+        let err = registry
+            .get_latest_version("acme/missing")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_registry_name() {
+        assert_eq!(PackagistRegistry::new().name(), "packagist");
+    }
+}
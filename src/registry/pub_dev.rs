@@ -0,0 +1,274 @@
+use super::{Registry, VersionMeta, get_with_retry, http_error_message};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Dart and Flutter packages from the pub.dev package API
+/// (`/api/packages/<name>`).
+pub struct PubRegistry {
+    client: Client,
+    api_url: String,
+}
+
+/// `/api/packages/<name>` response: the latest stable release and every
+/// published version.
+#[derive(Debug, Deserialize)]
+struct PackageResponse {
+    latest: Option<PackageVersion>,
+    #[serde(default)]
+    versions: Vec<PackageVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageVersion {
+    version: String,
+    #[serde(default)]
+    retracted: bool,
+    #[serde(default)]
+    published: Option<String>,
+}
+
+impl PackageResponse {
+    /// Non-retracted versions with their parsed semver, build metadata and all.
+    fn parsed(&self) -> impl Iterator<Item = (&PackageVersion, semver::Version)> {
+        self.versions
+            .iter()
+            .filter(|v| !v.retracted)
+            .filter_map(|v| Some((v, semver::Version::parse(&v.version).ok()?)))
+    }
+
+    /// The highest release accepted by `keep`.
+    fn highest(&self, keep: impl Fn(&semver::Version) -> bool) -> Option<String> {
+        self.parsed()
+            .filter(|(_, parsed)| keep(parsed))
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(entry, _)| entry.version.clone())
+    }
+}
+
+impl PubRegistry {
+    pub fn new() -> Self {
+        Self::with_api_url("https://pub.dev".to_string())
+    }
+
+    pub fn with_api_url(api_url: String) -> Self {
+        let client = crate::http::apply(
+            Client::builder()
+                .gzip(true)
+                .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
+                .connect_timeout(Duration::from_secs(10)),
+        )
+        .build()
+        .expect("Failed to create HTTP client. This usually indicates a TLS/SSL configuration issue on your system.");
+
+        Self {
+            client,
+            api_url: api_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// The package server `dart pub` uses, from `PUB_HOSTED_URL`.
+    pub fn detect_hosted_url() -> Option<String> {
+        std::env::var("PUB_HOSTED_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+    }
+
+    async fn fetch_package(&self, package: &str) -> Result<PackageResponse> {
+        let url = format!("{}/api/packages/{}", self.api_url, package);
+        let response = get_with_retry(&self.client, &url).await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(http_error_message(
+                response.status(),
+                "Dart package",
+                package,
+                None
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse pub.dev response for '{}': {}", package, e))
+    }
+}
+
+impl Default for PubRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Registry for PubRegistry {
+    async fn get_latest_version(&self, package: &str) -> Result<String> {
+        let body = self.fetch_package(package).await?;
+        // `latest` is pub's own pick of the newest stable release, unless
+        // the package has only ever published pre-releases.
+        if let Some(latest) = &body.latest
+            && semver::Version::parse(&latest.version).is_ok_and(|v| v.pre.is_empty())
+        {
+            return Ok(latest.version.clone());
+        }
+        body.highest(|v| v.pre.is_empty())
+            .ok_or_else(|| anyhow!("Dart package '{}' has no stable versions", package))
+    }
+
+    async fn get_latest_version_including_prereleases(&self, package: &str) -> Result<String> {
+        self.fetch_package(package)
+            .await?
+            .highest(|_| true)
+            .ok_or_else(|| anyhow!("Dart package '{}' has no versions", package))
+    }
+
+    /// `constraints` is a semver requirement such as `^1.2.0`; the answer is
+    /// the highest stable release it admits.
+    async fn get_latest_version_matching(
+        &self,
+        package: &str,
+        constraints: &str,
+    ) -> Result<String> {
+        let req = semver::VersionReq::parse(constraints).map_err(|e| {
+            anyhow!(
+                "Unsupported Dart constraint '{}' for '{}': {}",
+                constraints,
+                package,
+                e
+            )
+        })?;
+        self.fetch_package(package)
+            .await?
+            .highest(|v| v.pre.is_empty() && req.matches(v))
+            .ok_or_else(|| {
+                anyhow!(
+                    "No version of '{}' matches constraint '{}'",
+                    package,
+                    constraints
+                )
+            })
+    }
+
+    async fn list_versions(&self, package: &str) -> Result<Vec<VersionMeta>> {
+        let body = self.fetch_package(package).await?;
+        Ok(body
+            .versions
+            .iter()
+            .map(|entry| VersionMeta {
+                version: entry.version.clone(),
+                published_at: entry
+                    .published
+                    .as_deref()
+                    .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                    .map(|dt| dt.with_timezone(&chrono::Utc)),
+                yanked: entry.retracted,
+                prerelease: semver::Version::parse(&entry.version)
+                    .map(|v| !v.pre.is_empty())
+                    .unwrap_or(false),
+                rust_version: None,
+                node_engines: None,
+                deprecated: None,
+            })
+            .collect())
+    }
+
+    fn name(&self) -> &'static str {
+        "pub"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mock_http() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/packages/http"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"name": "http",
+                    "latest": {"version": "1.2.2", "published": "2024-07-16T21:17:46.627Z"},
+                    "versions": [
+                        {"version": "0.13.6", "published": "2023-04-25T19:24:31.480Z"},
+                        {"version": "1.2.1", "published": "2024-03-01T18:05:10.000Z"},
+                        {"version": "1.2.3", "retracted": true, "published": "2024-08-01T00:00:00.000Z"},
+                        {"version": "1.2.2", "published": "2024-07-16T21:17:46.627Z"},
+                        {"version": "1.3.0-beta.1", "published": "2024-09-02T10:00:00.000Z"}
+                    ]}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_latest_stable_and_prerelease() {
+        let mock_server = mock_http().await;
+        let registry = PubRegistry::with_api_url(mock_server.uri());
+
+        assert_eq!(registry.get_latest_version("http").await.unwrap(), "1.2.2");
+        assert_eq!(
+            registry
+                .get_latest_version_including_prereleases("http")
+                .await
+                .unwrap(),
+            "1.3.0-beta.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_latest_matching_skips_retracted() {
+        let mock_server = mock_http().await;
+        let registry = PubRegistry::with_api_url(mock_server.uri());
+
+        assert_eq!(
+            registry
+                .get_latest_version_matching("http", "^1.2.0")
+                .await
+                .unwrap(),
+            "1.2.2"
+        );
+        assert_eq!(
+            registry
+                .get_latest_version_matching("http", "^0.13.0")
+                .await
+                .unwrap(),
+            "0.13.6"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_versions_reads_published() {
+        let mock_server = mock_http().await;
+        let registry = PubRegistry::with_api_url(mock_server.uri());
+
+        let versions = registry.list_versions("http").await.unwrap();
+        assert_eq!(versions.len(), 5);
+        assert!(versions[2].yanked);
+        assert!(versions[4].prerelease);
+        assert!(versions[0].published_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_package_not_found() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/packages/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let registry = PubRegistry::with_api_url(mock_server.uri());
+        let err = registry.get_latest_version("missing").await.unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_registry_name() {
+        assert_eq!(PubRegistry::new().name(), "pub");
+    }
+}
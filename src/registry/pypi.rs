@@ -1,7 +1,7 @@
 #[cfg(test)]
 use super::utils::read_netrc_credentials_from_path;
 use super::utils::{base64_encode, read_netrc_credentials, read_pip_config};
-use super::{Registry, VersionMeta, http_error_message};
+use super::{PackageLinks, Registry, VersionMeta, http_error_message};
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use pep440_rs::{Version, VersionSpecifiers};
@@ -13,12 +13,6 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
-/// Maximum number of retry attempts for failed HTTP requests
-const MAX_RETRIES: u32 = 3;
-
-/// Base delay for exponential backoff (100ms, 200ms, 400ms)
-const BASE_DELAY_MS: u64 = 100;
-
 /// Credentials for authenticating with a PyPI registry
 #[derive(Clone)]
 pub struct PyPiCredentials {
@@ -44,9 +38,47 @@ pub enum PyPiAuth {
     Bearer(String),
 }
 
+/// Order in which `PyPiRegistry` tries the PEP 691 Simple API and the
+/// legacy JSON API when resolving versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum PypiApiOrder {
+    /// Simple API first, JSON API when it fails (default).
+    #[default]
+    #[value(name = "simple,json")]
+    SimpleThenJson,
+    /// JSON API first, Simple API when it fails.
+    #[value(name = "json,simple")]
+    JsonThenSimple,
+    /// Simple API only.
+    #[value(name = "simple-only")]
+    SimpleOnly,
+    /// JSON API only.
+    #[value(name = "json-only")]
+    JsonOnly,
+}
+
+/// One of the two PyPI version-listing endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PypiApi {
+    Simple,
+    Json,
+}
+
+impl PypiApiOrder {
+    fn endpoints(self) -> &'static [PypiApi] {
+        match self {
+            Self::SimpleThenJson => &[PypiApi::Simple, PypiApi::Json],
+            Self::JsonThenSimple => &[PypiApi::Json, PypiApi::Simple],
+            Self::SimpleOnly => &[PypiApi::Simple],
+            Self::JsonOnly => &[PypiApi::Json],
+        }
+    }
+}
+
 pub struct PyPiRegistry {
     client: Client,
     index_url: String,
+    api_order: PypiApiOrder,
 }
 
 #[derive(Debug, Deserialize)]
@@ -54,6 +86,50 @@ struct PyPiResponse {
     releases: HashMap<String, Vec<ReleaseFile>>,
 }
 
+/// The `info` block of the JSON API, read for `--changelog` links.
+#[derive(Debug, Deserialize)]
+struct PyPiProjectResponse {
+    info: PyPiProjectInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct PyPiProjectInfo {
+    #[serde(default)]
+    home_page: Option<String>,
+    #[serde(default)]
+    project_urls: Option<HashMap<String, String>>,
+}
+
+impl PyPiProjectInfo {
+    /// Sort `project_urls` into links. Labels are free-form, so match the
+    /// common spellings case-insensitively.
+    fn into_links(self) -> PackageLinks {
+        let mut links = PackageLinks {
+            homepage: self.home_page.filter(|url| !url.is_empty()),
+            ..PackageLinks::default()
+        };
+        let mut urls: Vec<(String, String)> = self
+            .project_urls
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(label, url)| (label.to_lowercase().replace(['-', '_', ' '], ""), url))
+            .collect();
+        urls.sort();
+        for (label, url) in urls {
+            let slot = match label.as_str() {
+                "changelog" | "changes" | "releasenotes" | "history" | "releases" => {
+                    &mut links.changelog
+                }
+                "source" | "sourcecode" | "repository" | "code" | "github" => &mut links.repository,
+                "homepage" | "home" => &mut links.homepage,
+                _ => continue,
+            };
+            slot.get_or_insert(url);
+        }
+        links
+    }
+}
+
 /// PEP 691 JSON Simple API response format
 #[derive(Debug, Clone, Deserialize)]
 struct SimpleApiResponse {
@@ -126,7 +202,7 @@ impl PyPiRegistry {
     /// Convert a Simple API URL to JSON API URL format
     /// Strips "/simple" suffix since JSON API uses base path + /{package}/json
     /// e.g., "https://example.com/repository/pypi/simple" -> "https://example.com/repository/pypi"
-    fn normalize_index_url(url: &str) -> String {
+    pub(crate) fn normalize_index_url(url: &str) -> String {
         let trimmed = url.trim_end_matches('/');
         if let Some(stripped) = trimmed.strip_suffix("/simple") {
             stripped.to_string()
@@ -174,14 +250,23 @@ impl PyPiRegistry {
             Client::builder()
                 .gzip(true)
                 .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
-                .timeout(Duration::from_secs(30))
                 .connect_timeout(Duration::from_secs(10))
                 .default_headers(headers),
         )
         .build()
         .expect("Failed to create HTTP client. This usually indicates a TLS/SSL configuration issue on your system.");
 
-        Self { client, index_url }
+        Self {
+            client,
+            index_url,
+            api_order: PypiApiOrder::default(),
+        }
+    }
+
+    /// Try the Simple and JSON APIs in `api_order` when resolving versions.
+    pub fn with_api_order(mut self, api_order: PypiApiOrder) -> Self {
+        self.api_order = api_order;
+        self
     }
 
     /// Detect custom index URL from environment or config
@@ -338,14 +423,19 @@ impl PyPiRegistry {
         url: &str,
         headers: Option<HeaderMap>,
     ) -> anyhow::Result<Response> {
+        crate::http::ensure_host_allowed(url)?;
+        let _slot = crate::http::acquire_host_slot(url).await;
         let mut last_error = None;
 
-        for attempt in 0..MAX_RETRIES {
+        let attempts = super::max_attempts();
+
+        for attempt in 0..attempts {
             let mut request = self.client.get(url);
             if let Some(ref h) = headers {
                 request = request.headers(h.clone());
             }
 
+            crate::http::acquire_rate_token().await;
             match request.send().await {
                 Ok(response) => {
                     // Don't retry client errors (4xx) - they won't succeed on retry
@@ -354,9 +444,8 @@ impl PyPiRegistry {
                     }
 
                     // Retry server errors (5xx)
-                    if response.status().is_server_error() && attempt < MAX_RETRIES - 1 {
-                        let delay = Duration::from_millis(BASE_DELAY_MS * (1 << attempt));
-                        tokio::time::sleep(delay).await;
+                    if response.status().is_server_error() && attempt + 1 < attempts {
+                        tokio::time::sleep(super::retry_backoff(attempt)).await;
                         continue;
                     }
 
@@ -366,9 +455,8 @@ impl PyPiRegistry {
                     last_error = Some(e);
 
                     // Don't retry on the last attempt
-                    if attempt < MAX_RETRIES - 1 {
-                        let delay = Duration::from_millis(BASE_DELAY_MS * (1 << attempt));
-                        tokio::time::sleep(delay).await;
+                    if attempt + 1 < attempts {
+                        tokio::time::sleep(super::retry_backoff(attempt)).await;
                     }
                 }
             }
@@ -405,53 +493,72 @@ impl PyPiRegistry {
     ) -> Result<Vec<(Version, String)>> {
         let normalized = package.to_lowercase().replace('_', "-");
 
-        // Try Simple API with PEP 691 content negotiation
-        // Request JSON format, but accept HTML as fallback
-        let simple_url = format!("{}/simple/{}/", self.index_url, normalized);
+        // Try each endpoint in the configured order; a non-success status
+        // falls through to the next one.
+        let mut last_status = reqwest::StatusCode::NOT_FOUND;
+        for api in self.api_order.endpoints() {
+            match api {
+                PypiApi::Simple => {
+                    // Simple API with PEP 691 content negotiation
+                    // Request JSON format, but accept HTML as fallback
+                    let simple_url = format!("{}/simple/{}/", self.index_url, normalized);
+
+                    let mut headers = HeaderMap::new();
+                    // PEP 691: Request JSON format with HTML fallback
+                    if let Ok(accept) = HeaderValue::from_str(
+                        "application/vnd.pypi.simple.v1+json, application/vnd.pypi.simple.v1+html;q=0.9, text/html;q=0.8",
+                    ) {
+                        headers.insert(reqwest::header::ACCEPT, accept);
+                    }
+
+                    let simple_response = self
+                        .get_with_retry_and_headers(&simple_url, Some(headers))
+                        .await?;
 
-        let mut headers = HeaderMap::new();
-        // PEP 691: Request JSON format with HTML fallback
-        if let Ok(accept) = HeaderValue::from_str(
-            "application/vnd.pypi.simple.v1+json, application/vnd.pypi.simple.v1+html;q=0.9, text/html;q=0.8",
-        ) {
-            headers.insert(reqwest::header::ACCEPT, accept);
-        }
-
-        let simple_response = self
-            .get_with_retry_and_headers(&simple_url, Some(headers))
-            .await?;
-
-        if simple_response.status().is_success() {
-            // Check Content-Type to determine response format
-            let content_type = simple_response
-                .headers()
-                .get(reqwest::header::CONTENT_TYPE)
-                .and_then(|v| v.to_str().ok())
-                .unwrap_or("");
-
-            if content_type.contains("application/vnd.pypi.simple") && content_type.contains("json")
-            {
-                // PEP 691 JSON format
-                let data: SimpleApiResponse = simple_response.json().await?;
-                return self.parse_simple_api_json_response(data, package, include_prereleases);
-            } else {
-                // HTML format (standard Simple API or PEP 691 HTML)
-                let html = simple_response.text().await?;
-                return self.parse_simple_api_response(&html, package, include_prereleases);
-            }
-        }
+                    if !simple_response.status().is_success() {
+                        last_status = simple_response.status();
+                        continue;
+                    }
 
-        // Simple API failed - try legacy JSON API (PyPI.org style)
-        let json_url = format!("{}/pypi/{}/json", self.index_url, normalized);
-        let response = self.get_with_retry(&json_url).await?;
+                    // Check Content-Type to determine response format
+                    let content_type = simple_response
+                        .headers()
+                        .get(reqwest::header::CONTENT_TYPE)
+                        .and_then(|v| v.to_str().ok())
+                        .unwrap_or("");
+
+                    if content_type.contains("application/vnd.pypi.simple")
+                        && content_type.contains("json")
+                    {
+                        // PEP 691 JSON format
+                        let data: SimpleApiResponse = simple_response.json().await?;
+                        return self.parse_simple_api_json_response(
+                            data,
+                            package,
+                            include_prereleases,
+                        );
+                    }
+                    // HTML format (standard Simple API or PEP 691 HTML)
+                    let html = simple_response.text().await?;
+                    return self.parse_simple_api_response(&html, package, include_prereleases);
+                }
+                PypiApi::Json => {
+                    // Legacy JSON API (PyPI.org style)
+                    let json_url = format!("{}/pypi/{}/json", self.index_url, normalized);
+                    let response = self.get_with_retry(&json_url).await?;
 
-        if response.status().is_success() {
-            let data: PyPiResponse = response.json().await?;
-            return self.parse_json_response(data, include_prereleases);
+                    if !response.status().is_success() {
+                        last_status = response.status();
+                        continue;
+                    }
+                    let data: PyPiResponse = response.json().await?;
+                    return self.parse_json_response(data, include_prereleases);
+                }
+            }
         }
 
         Err(anyhow!(http_error_message(
-            response.status(),
+            last_status,
             "Package",
             package,
             Some(
@@ -844,6 +951,19 @@ impl Registry for MultiPyPiRegistry {
         Ok(Vec::new())
     }
 
+    async fn package_links(&self, package: &str) -> Result<PackageLinks> {
+        // First index with any links wins; failures fall through like
+        // `list_versions`, since links are best-effort.
+        for registry in &self.registries {
+            if let Ok(links) = registry.package_links(package).await
+                && links != PackageLinks::default()
+            {
+                return Ok(links);
+            }
+        }
+        Ok(PackageLinks::default())
+    }
+
     fn name(&self) -> &'static str {
         "pypi"
     }
@@ -904,6 +1024,11 @@ impl Registry for PyPiRegistry {
     }
 
     async fn list_versions(&self, package: &str) -> Result<Vec<VersionMeta>> {
+        // Upload times come from the JSON API only; with `simple-only` the
+        // metadata is reported as unavailable.
+        if self.api_order == PypiApiOrder::SimpleOnly {
+            return Ok(Vec::new());
+        }
         let normalized = package.to_lowercase().replace('_', "-");
         let json_url = format!("{}/pypi/{}/json", self.index_url, normalized);
         let response = self.get_with_retry(&json_url).await?;
@@ -945,11 +1070,34 @@ impl Registry for PyPiRegistry {
                 published_at,
                 yanked: all_yanked,
                 prerelease,
+                rust_version: None,
+                node_engines: None,
+                deprecated: None,
             });
         }
         Ok(out)
     }
 
+    async fn package_links(&self, package: &str) -> Result<PackageLinks> {
+        // Project URLs come from the JSON API only.
+        if self.api_order == PypiApiOrder::SimpleOnly {
+            return Ok(PackageLinks::default());
+        }
+        let normalized = package.to_lowercase().replace('_', "-");
+        let json_url = format!("{}/pypi/{}/json", self.index_url, normalized);
+        let response = self.get_with_retry(&json_url).await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(http_error_message(
+                response.status(),
+                "Package",
+                package,
+                None
+            )));
+        }
+        let data: PyPiProjectResponse = response.json().await?;
+        Ok(data.info.into_links())
+    }
+
     fn name(&self) -> &'static str {
         "pypi"
     }
@@ -2131,4 +2279,123 @@ mod tests {
         let v_1_0 = versions.iter().find(|v| v.version == "1.0.0").unwrap();
         assert!(v_1_0.yanked);
     }
+
+    #[tokio::test]
+    async fn test_pypi_package_links_reads_project_urls() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/pypi/requests/json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+              "info": {
+                "home_page": "",
+                "project_urls": {
+                  "Documentation": "https://requests.readthedocs.io",
+                  "Homepage": "https://requests.readthedocs.io",
+                  "Source": "https://github.com/psf/requests",
+                  "Change Log": "https://github.com/psf/requests/blob/main/HISTORY.md"
+                }
+              },
+              "releases": {}
+            }"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let registry = PyPiRegistry::with_index_url(mock_server.uri());
+        let links = registry.package_links("requests").await.unwrap();
+
+        assert_eq!(
+            links,
+            PackageLinks {
+                repository: Some("https://github.com/psf/requests".to_string()),
+                homepage: Some("https://requests.readthedocs.io".to_string()),
+                changelog: Some("https://github.com/psf/requests/blob/main/HISTORY.md".to_string()),
+            }
+        );
+    }
+
+    /// Mount both endpoints for `testpkg`, each serving a different version
+    /// and expecting `simple_calls` / `json_calls` requests.
+    async fn mount_both_apis(
+        mock_server: &wiremock::MockServer,
+        simple_status: u16,
+        simple_calls: u64,
+        json_calls: u64,
+    ) {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        Mock::given(method("GET"))
+            .and(path("/simple/testpkg/"))
+            .respond_with(
+                ResponseTemplate::new(simple_status)
+                    .set_body_string(r#"<a href="testpkg-2.0.0.tar.gz">testpkg-2.0.0.tar.gz</a>"#),
+            )
+            .expect(simple_calls)
+            .mount(mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/pypi/testpkg/json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"releases": {"1.0.0": [{"yanked": false}]}}"#),
+            )
+            .expect(json_calls)
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_api_order_default_hits_simple_first() {
+        let mock_server = wiremock::MockServer::start().await;
+        mount_both_apis(&mock_server, 200, 1, 0).await;
+
+        let registry = PyPiRegistry::with_index_url(mock_server.uri());
+        assert_eq!(
+            registry.get_latest_version("testpkg").await.unwrap(),
+            "2.0.0"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_order_json_first_skips_simple() {
+        let mock_server = wiremock::MockServer::start().await;
+        mount_both_apis(&mock_server, 200, 0, 1).await;
+
+        let registry = PyPiRegistry::with_index_url(mock_server.uri())
+            .with_api_order(PypiApiOrder::JsonThenSimple);
+        assert_eq!(
+            registry.get_latest_version("testpkg").await.unwrap(),
+            "1.0.0"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_order_simple_only_never_falls_back_to_json() {
+        let mock_server = wiremock::MockServer::start().await;
+        mount_both_apis(&mock_server, 404, 1, 0).await;
+
+        let registry = PyPiRegistry::with_index_url(mock_server.uri())
+            .with_api_order(PypiApiOrder::SimpleOnly);
+        let err = registry.get_latest_version("testpkg").await.unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_api_order_json_only_skips_simple() {
+        let mock_server = wiremock::MockServer::start().await;
+        mount_both_apis(&mock_server, 200, 0, 1).await;
+
+        let registry =
+            PyPiRegistry::with_index_url(mock_server.uri()).with_api_order(PypiApiOrder::JsonOnly);
+        assert_eq!(
+            registry.get_latest_version("testpkg").await.unwrap(),
+            "1.0.0"
+        );
+    }
 }
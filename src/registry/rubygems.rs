@@ -1,10 +1,38 @@
+use super::utils::base64_encode;
 use super::{Registry, VersionMeta, get_with_retry, http_error_message};
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use reqwest::Client;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
 use serde::Deserialize;
 use std::time::Duration;
 
+/// Credentials for a private gem server, in Bundler's `user:password` form.
+/// Token-only servers (Gemfury, GitHub Packages) take the token as the user.
+#[derive(Clone)]
+pub struct RubyGemsCredentials {
+    pub username: String,
+    pub password: String,
+}
+
+impl std::fmt::Debug for RubyGemsCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RubyGemsCredentials")
+            .field("username", &"[REDACTED]")
+            .field("password", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Bundler's environment key for a source host's credentials:
+/// `gems.my-corp.com` → `BUNDLE_GEMS__MY___CORP__COM`.
+fn bundle_credentials_key(host: &str) -> String {
+    format!(
+        "BUNDLE_{}",
+        host.to_uppercase().replace('-', "___").replace('.', "__")
+    )
+}
+
 pub struct RubyGemsRegistry {
     client: Client,
     api_url: String,
@@ -33,17 +61,61 @@ impl RubyGemsRegistry {
     }
 
     pub fn with_api_url(api_url: String) -> Self {
+        Self::with_api_url_and_credentials(api_url, None)
+    }
+
+    pub fn with_api_url_and_credentials(
+        api_url: String,
+        credentials: Option<RubyGemsCredentials>,
+    ) -> Self {
+        let mut headers = HeaderMap::new();
+
+        // Add Basic Auth header if credentials are provided
+        if let Some(ref creds) = credentials {
+            let auth = format!("{}:{}", creds.username, creds.password);
+            if let Ok(header_value) =
+                HeaderValue::from_str(&format!("Basic {}", base64_encode(&auth)))
+            {
+                headers.insert(AUTHORIZATION, header_value);
+            }
+        }
+
         let client = crate::http::apply(
             Client::builder()
                 .gzip(true)
                 .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
-                .timeout(Duration::from_secs(30))
-                .connect_timeout(Duration::from_secs(10)),
+                .connect_timeout(Duration::from_secs(10))
+                .default_headers(headers),
         )
         .build()
         .expect("Failed to create HTTP client. This usually indicates a TLS/SSL configuration issue on your system.");
 
-        Self { client, api_url }
+        Self {
+            client,
+            api_url: api_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Detect a private gem server from `RUBYGEMS_HOST`
+    pub fn detect_api_url() -> Option<String> {
+        std::env::var("RUBYGEMS_HOST")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+    }
+
+    /// Detect credentials for `api_url` the way Bundler does: from the
+    /// `BUNDLE_<HOST>` environment variable (`user:password` or a token).
+    pub fn detect_credentials(api_url: &str) -> Option<RubyGemsCredentials> {
+        let url = url::Url::parse(api_url).ok()?;
+        let value = std::env::var(bundle_credentials_key(url.host_str()?)).ok()?;
+        if value.is_empty() {
+            return None;
+        }
+        let (username, password) = value.split_once(':').unwrap_or((&value, ""));
+        Some(RubyGemsCredentials {
+            username: username.to_string(),
+            password: password.to_string(),
+        })
     }
 }
 
@@ -173,6 +245,9 @@ impl Registry for RubyGemsRegistry {
                     published_at,
                     yanked: v.yanked,
                     prerelease: v.prerelease,
+                    rust_version: None,
+                    node_engines: None,
+                    deprecated: None,
                 }
             })
             .collect())
@@ -264,6 +339,42 @@ mod tests {
         assert_eq!(version, "7.2.1");
     }
 
+    #[test]
+    fn test_bundle_credentials_key() {
+        assert_eq!(
+            bundle_credentials_key("gems.my-corp.com"),
+            "BUNDLE_GEMS__MY___CORP__COM"
+        );
+        assert_eq!(
+            bundle_credentials_key("rubygems.pkg.github.com"),
+            "BUNDLE_RUBYGEMS__PKG__GITHUB__COM"
+        );
+    }
+
+    #[test]
+    fn test_detect_credentials_from_bundle_env() {
+        // SAFETY: the variable names are unique to this test
+        unsafe {
+            std::env::set_var("BUNDLE_GEMS__UPD___TEST__EXAMPLE", "ci-bot:s3cret");
+            std::env::set_var("BUNDLE_TOKEN__UPD___TEST__EXAMPLE", "tok123");
+        }
+
+        let creds =
+            RubyGemsRegistry::detect_credentials("https://gems.upd-test.example/private").unwrap();
+        assert_eq!(creds.username, "ci-bot");
+        assert_eq!(creds.password, "s3cret");
+        let creds = RubyGemsRegistry::detect_credentials("https://token.upd-test.example").unwrap();
+        assert_eq!(creds.username, "tok123");
+        assert_eq!(creds.password, "");
+        assert!(RubyGemsRegistry::detect_credentials("https://rubygems.org").is_none());
+
+        // SAFETY: see above
+        unsafe {
+            std::env::remove_var("BUNDLE_GEMS__UPD___TEST__EXAMPLE");
+            std::env::remove_var("BUNDLE_TOKEN__UPD___TEST__EXAMPLE");
+        }
+    }
+
     #[tokio::test]
     async fn test_package_not_found() {
         let mock_server = MockServer::start().await;
@@ -0,0 +1,275 @@
+use super::{Registry, get_with_retry, http_error_message};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Releases of language runtimes (`node`, `python`), read from each
+/// project's own download index rather than a package registry.
+pub struct RuntimeRegistry {
+    client: Client,
+    node_url: String,
+    python_url: String,
+}
+
+/// An entry of nodejs.org's `dist/index.json`.
+#[derive(Debug, Deserialize)]
+struct NodeRelease {
+    /// `v22.12.0`
+    version: String,
+}
+
+/// An entry of python.org's downloads API.
+#[derive(Debug, Deserialize)]
+struct PythonRelease {
+    /// `Python 3.13.1`
+    name: String,
+    #[serde(default)]
+    pre_release: bool,
+}
+
+/// Numeric segments of a `1.2.3` release, or `None` for anything else
+/// (`3.14.0rc1`, `lts/iron`).
+fn release_segments(version: &str) -> Option<Vec<u64>> {
+    version
+        .split('.')
+        .map(|part| {
+            if !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()) {
+                part.parse().ok()
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+impl RuntimeRegistry {
+    pub fn new() -> Self {
+        Self::with_api_urls(
+            "https://nodejs.org".to_string(),
+            "https://www.python.org".to_string(),
+        )
+    }
+
+    #[cfg(test)]
+    pub fn with_api_url(api_url: String) -> Self {
+        Self::with_api_urls(api_url.clone(), api_url)
+    }
+
+    fn with_api_urls(node_url: String, python_url: String) -> Self {
+        let client = crate::http::apply(
+            Client::builder()
+                .gzip(true)
+                .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
+                .connect_timeout(Duration::from_secs(10)),
+        )
+        .build()
+        .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            node_url,
+            python_url,
+        }
+    }
+
+    /// Every final release of a runtime, without a `v` prefix, in no
+    /// particular order.
+    async fn get_releases(&self, runtime: &str) -> Result<Vec<String>> {
+        let url = match runtime {
+            "node" => format!("{}/dist/index.json", self.node_url),
+            "python" => format!(
+                "{}/api/v2/downloads/release/?is_published=true",
+                self.python_url
+            ),
+            _ => return Err(anyhow!("Unknown runtime '{}'", runtime)),
+        };
+
+        let response = get_with_retry(&self.client, &url).await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(http_error_message(
+                response.status(),
+                "Runtime",
+                runtime,
+                None
+            )));
+        }
+
+        let parse_error =
+            |e: reqwest::Error| anyhow!("Failed to parse release index for '{}': {}", runtime, e);
+        let releases = if runtime == "node" {
+            let data: Vec<NodeRelease> = response.json().await.map_err(parse_error)?;
+            data.into_iter()
+                .map(|release| release.version.trim_start_matches('v').to_string())
+                .collect()
+        } else {
+            let data: Vec<PythonRelease> = response.json().await.map_err(parse_error)?;
+            data.into_iter()
+                .filter(|release| !release.pre_release)
+                .filter_map(|release| {
+                    release
+                        .name
+                        .strip_prefix("Python ")
+                        .map(|version| version.trim().to_string())
+                })
+                .collect()
+        };
+
+        Ok(releases)
+    }
+
+    /// The highest release whose leading segments equal `prefix`'s.
+    fn find_latest_with_prefix(releases: &[String], prefix: &[u64]) -> Option<String> {
+        releases
+            .iter()
+            .filter_map(|version| release_segments(version).map(|segments| (version, segments)))
+            .filter(|(_, segments)| segments.starts_with(prefix))
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(version, _)| version.clone())
+    }
+}
+
+impl Default for RuntimeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Registry for RuntimeRegistry {
+    async fn get_latest_version(&self, package: &str) -> Result<String> {
+        let releases = self.get_releases(package).await?;
+
+        Self::find_latest_with_prefix(&releases, &[])
+            .ok_or_else(|| anyhow!("No releases found for runtime '{}'", package))
+    }
+
+    /// `constraints` is a release prefix such as `20` or `3.12`; the answer
+    /// is the newest release that starts with it.
+    async fn get_latest_version_matching(
+        &self,
+        package: &str,
+        constraints: &str,
+    ) -> Result<String> {
+        let prefix = release_segments(constraints).ok_or_else(|| {
+            anyhow!(
+                "Constraint '{}' of runtime '{}' is not a release prefix",
+                constraints,
+                package
+            )
+        })?;
+        let releases = self.get_releases(package).await?;
+
+        Self::find_latest_with_prefix(&releases, &prefix).ok_or_else(|| {
+            anyhow!(
+                "No release of runtime '{}' matches '{}'",
+                package,
+                constraints
+            )
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "runtime"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mock_indexes() -> MockServer {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/dist/index.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"[
+                    {"version": "v23.4.0", "lts": false},
+                    {"version": "v22.12.0", "lts": "Jod"},
+                    {"version": "v20.18.1", "lts": "Iron"},
+                    {"version": "v20.9.0", "lts": "Iron"},
+                    {"version": "v18.20.5", "lts": "Hydrogen"}
+                ]"#,
+            ))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/downloads/release/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"[
+                    {"name": "Python 3.14.0a3", "pre_release": true},
+                    {"name": "Python 3.13.1", "pre_release": false},
+                    {"name": "Python 3.12.8", "pre_release": false},
+                    {"name": "Python 3.9.21", "pre_release": false},
+                    {"name": "Python 2.7.18", "pre_release": false}
+                ]"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_latest_node_and_python() {
+        let mock_server = mock_indexes().await;
+        let registry = RuntimeRegistry::with_api_url(mock_server.uri());
+
+        assert_eq!(registry.get_latest_version("node").await.unwrap(), "23.4.0");
+        assert_eq!(
+            registry.get_latest_version("python").await.unwrap(),
+            "3.13.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_latest_matching_stays_within_prefix() {
+        let mock_server = mock_indexes().await;
+        let registry = RuntimeRegistry::with_api_url(mock_server.uri());
+
+        assert_eq!(
+            registry
+                .get_latest_version_matching("node", "20")
+                .await
+                .unwrap(),
+            "20.18.1"
+        );
+        assert_eq!(
+            registry
+                .get_latest_version_matching("python", "3.12")
+                .await
+                .unwrap(),
+            "3.12.8"
+        );
+        assert_eq!(
+            registry
+                .get_latest_version_matching("python", "2")
+                .await
+                .unwrap(),
+            "2.7.18"
+        );
+        assert!(
+            registry
+                .get_latest_version_matching("node", "19")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_runtime() {
+        let registry = RuntimeRegistry::new();
+        let result = registry.get_latest_version("ruby").await;
+        assert!(result.unwrap_err().to_string().contains("Unknown runtime"));
+    }
+
+    #[tokio::test]
+    async fn test_registry_name() {
+        let registry = RuntimeRegistry::new();
+        assert_eq!(registry.name(), "runtime");
+    }
+}
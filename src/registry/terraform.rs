@@ -46,7 +46,6 @@ impl TerraformRegistry {
             Client::builder()
                 .gzip(true)
                 .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
-                .timeout(Duration::from_secs(30))
                 .connect_timeout(Duration::from_secs(10)),
         )
         .build()
@@ -61,7 +60,6 @@ impl TerraformRegistry {
             Client::builder()
                 .gzip(true)
                 .user_agent(concat!("upd/", env!("CARGO_PKG_VERSION")))
-                .timeout(Duration::from_secs(30))
                 .connect_timeout(Duration::from_secs(10)),
         )
         .build()
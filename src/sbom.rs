@@ -0,0 +1,310 @@
+//! SBOM output for `upd sbom`.
+//!
+//! Builds a CycloneDX 1.5 or SPDX 2.3 JSON document listing the dependencies
+//! declared in the scanned manifests, each identified by a package URL (purl).
+//! Versions are the declared ones (the first version of each constraint), not
+//! lockfile-resolved versions. The purls use the same type names that
+//! `upd audit --sbom` reads back.
+
+use crate::align::PackageOccurrence;
+use crate::updater::Lang;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap};
+
+/// SBOM document format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbomFormat {
+    CycloneDx,
+    Spdx,
+}
+
+/// One component of a generated SBOM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SbomComponent {
+    pub name: String,
+    pub version: String,
+    pub purl: String,
+}
+
+/// The purl type for an ecosystem, if the purl spec defines one.
+///
+/// pre-commit hooks, mise tools and Terraform providers have no purl type and
+/// are left out of generated SBOMs.
+pub fn purl_type_for_lang(lang: Lang) -> Option<&'static str> {
+    match lang {
+        Lang::Python => Some("pypi"),
+        Lang::Node => Some("npm"),
+        Lang::Rust => Some("cargo"),
+        Lang::Go => Some("golang"),
+        Lang::Ruby => Some("gem"),
+        Lang::DotNet => Some("nuget"),
+        Lang::Actions => Some("github"),
+        Lang::Docker => Some("docker"),
+        Lang::Php => Some("composer"),
+        Lang::Jvm => Some("maven"),
+        Lang::Dart => Some("pub"),
+        Lang::PreCommit | Lang::Mise | Lang::Terraform | Lang::Runtime => None,
+    }
+}
+
+/// Build the purl for a declared dependency.
+pub fn build_purl(lang: Lang, name: &str, version: &str) -> Option<String> {
+    let purl_type = purl_type_for_lang(lang)?;
+    let segments: Vec<String> = match lang {
+        // The purl spec normalizes PyPI names: lowercase, `_` → `-`.
+        Lang::Python => vec![name.to_lowercase().replace('_', "-")],
+        // `owner/repo/sub/path@ref` → the repository is the package.
+        Lang::Actions => name.split('/').take(2).map(str::to_string).collect(),
+        // `group:artifact` → `group/artifact`.
+        Lang::Jvm => name.split(':').map(str::to_string).collect(),
+        _ => name.split('/').map(str::to_string).collect(),
+    };
+    if segments.iter().all(|s| s.is_empty()) || version.is_empty() {
+        return None;
+    }
+    let path: Vec<String> = segments
+        .iter()
+        .filter(|s| !s.is_empty())
+        .map(|s| percent_encode(s))
+        .collect();
+    Some(format!(
+        "pkg:{}/{}@{}",
+        purl_type,
+        path.join("/"),
+        percent_encode(version)
+    ))
+}
+
+/// Collect one component per distinct purl, sorted by purl.
+pub fn collect_components(
+    packages: &HashMap<(String, Lang), Vec<PackageOccurrence>>,
+) -> Vec<SbomComponent> {
+    let mut components: BTreeMap<String, SbomComponent> = BTreeMap::new();
+    for ((_, lang), occurrences) in packages {
+        for occurrence in occurrences {
+            let Some(purl) = build_purl(*lang, &occurrence.original_name, &occurrence.version)
+            else {
+                continue;
+            };
+            components
+                .entry(purl.clone())
+                .or_insert_with(|| SbomComponent {
+                    name: occurrence.original_name.clone(),
+                    version: occurrence.version.clone(),
+                    purl,
+                });
+        }
+    }
+    components.into_values().collect()
+}
+
+/// Render the SBOM document. `created` is an RFC 3339 UTC timestamp.
+pub fn render(format: SbomFormat, components: &[SbomComponent], created: &str) -> Value {
+    match format {
+        SbomFormat::CycloneDx => render_cyclonedx(components, created),
+        SbomFormat::Spdx => render_spdx(components, created),
+    }
+}
+
+fn render_cyclonedx(components: &[SbomComponent], created: &str) -> Value {
+    let components: Vec<Value> = components
+        .iter()
+        .map(|c| {
+            json!({
+                "type": "library",
+                "bom-ref": c.purl,
+                "name": c.name,
+                "version": c.version,
+                "purl": c.purl,
+            })
+        })
+        .collect();
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "timestamp": created,
+            "tools": {
+                "components": [
+                    {"type": "application", "name": "upd", "version": env!("CARGO_PKG_VERSION")}
+                ]
+            }
+        },
+        "components": components,
+    })
+}
+
+fn render_spdx(components: &[SbomComponent], created: &str) -> Value {
+    let packages: Vec<Value> = components
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            json!({
+                "name": c.name,
+                "SPDXID": format!("SPDXRef-Package-{}", i + 1),
+                "versionInfo": c.version,
+                "downloadLocation": "NOASSERTION",
+                "filesAnalyzed": false,
+                "externalRefs": [
+                    {
+                        "referenceCategory": "PACKAGE-MANAGER",
+                        "referenceType": "purl",
+                        "referenceLocator": c.purl,
+                    }
+                ],
+            })
+        })
+        .collect();
+    json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": "upd-sbom",
+        "documentNamespace": format!("https://spdx.org/spdxdocs/upd-sbom-{}", created),
+        "creationInfo": {
+            "created": created,
+            "creators": [format!("Tool: upd-{}", env!("CARGO_PKG_VERSION"))],
+        },
+        "packages": packages,
+    })
+}
+
+/// Percent-encode everything outside the purl-safe set.
+fn percent_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'-' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::sbom::parse_sbom;
+    use crate::updater::FileType;
+    use std::path::PathBuf;
+
+    fn occurrence(name: &str, version: &str, file_type: FileType) -> PackageOccurrence {
+        PackageOccurrence {
+            file_path: PathBuf::from("manifest"),
+            file_type,
+            version: version.to_string(),
+            line_number: Some(1),
+            has_upper_bound: false,
+            original_name: name.to_string(),
+            is_bumpable: true,
+            inherits_workspace: false,
+        }
+    }
+
+    fn packages() -> HashMap<(String, Lang), Vec<PackageOccurrence>> {
+        let mut packages = HashMap::new();
+        for (name, version, file_type) in [
+            ("Django_Rest", "3.15.0", FileType::Requirements),
+            ("@angular/core", "17.0.0", FileType::PackageJson),
+            ("serde", "1.0.210", FileType::CargoToml),
+            ("golang.org/x/net", "v0.30.0", FileType::GoMod),
+            ("actions/cache/restore", "v4", FileType::GithubActions),
+            ("black", "24.1.0", FileType::PreCommitConfig),
+        ] {
+            packages
+                .entry((name.to_lowercase(), file_type.lang()))
+                .or_insert_with(Vec::new)
+                .push(occurrence(name, version, file_type));
+        }
+        // The same declaration in two files is one component.
+        packages
+            .get_mut(&("serde".to_string(), Lang::Rust))
+            .unwrap()
+            .push(occurrence("serde", "1.0.210", FileType::CargoToml));
+        packages
+    }
+
+    #[test]
+    fn test_build_purl_per_ecosystem() {
+        assert_eq!(
+            build_purl(Lang::Python, "Django_Rest", "4.2+local").as_deref(),
+            Some("pkg:pypi/django-rest@4.2%2Blocal")
+        );
+        assert_eq!(
+            build_purl(Lang::Node, "@angular/core", "17.0.0").as_deref(),
+            Some("pkg:npm/%40angular/core@17.0.0")
+        );
+        assert_eq!(
+            build_purl(Lang::DotNet, "Newtonsoft.Json", "13.0.1").as_deref(),
+            Some("pkg:nuget/Newtonsoft.Json@13.0.1")
+        );
+        assert_eq!(
+            build_purl(Lang::Actions, "actions/cache/restore", "v4").as_deref(),
+            Some("pkg:github/actions/cache@v4")
+        );
+        assert_eq!(build_purl(Lang::Mise, "node", "20.0.0"), None);
+        assert_eq!(build_purl(Lang::Rust, "serde", ""), None);
+    }
+
+    #[test]
+    fn test_build_purl_plain_names() {
+        assert_eq!(
+            build_purl(Lang::Python, "requests", "2.28.0").as_deref(),
+            Some("pkg:pypi/requests@2.28.0")
+        );
+        assert_eq!(
+            build_purl(Lang::Node, "react", "17.0.0").as_deref(),
+            Some("pkg:npm/react@17.0.0")
+        );
+        assert_eq!(
+            build_purl(Lang::Node, "@scope/name", "1.0.0").as_deref(),
+            Some("pkg:npm/%40scope/name@1.0.0")
+        );
+        assert_eq!(
+            build_purl(Lang::Rust, "serde", "1.0.0").as_deref(),
+            Some("pkg:cargo/serde@1.0.0")
+        );
+        assert_eq!(
+            build_purl(Lang::Go, "github.com/stretchr/testify", "v1.9.0").as_deref(),
+            Some("pkg:golang/github.com/stretchr/testify@v1.9.0")
+        );
+    }
+
+    #[test]
+    fn test_collect_components_dedupes_and_sorts_by_purl() {
+        let purls: Vec<String> = collect_components(&packages())
+            .into_iter()
+            .map(|c| c.purl)
+            .collect();
+        assert_eq!(
+            purls,
+            vec![
+                "pkg:cargo/serde@1.0.210",
+                "pkg:github/actions/cache@v4",
+                "pkg:golang/golang.org/x/net@v0.30.0",
+                "pkg:npm/%40angular/core@17.0.0",
+                "pkg:pypi/django-rest@3.15.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rendered_documents_round_trip_through_audit_parser() {
+        let components = collect_components(&packages());
+        for format in [SbomFormat::CycloneDx, SbomFormat::Spdx] {
+            let doc = render(format, &components, "2026-01-01T00:00:00Z");
+            let parsed = parse_sbom(&doc.to_string()).unwrap();
+            let mut names: Vec<String> = parsed.packages.into_iter().map(|p| p.name).collect();
+            names.sort();
+            assert_eq!(
+                names,
+                vec!["@angular/core", "django-rest", "golang.org/x/net", "serde"],
+                "{format:?}"
+            );
+            // GitHub Actions have no OSV ecosystem, so audit skips them.
+            assert_eq!(parsed.skipped.len(), 1, "{format:?}");
+        }
+    }
+}
@@ -5,6 +5,12 @@ pub fn print_schema() {
     println!("{}", serde_json::to_string_pretty(&schema).unwrap());
 }
 
+/// Print the JSON Schema of the `--format json` reports (`upd schema --report`).
+pub fn print_report_schema() {
+    let schema = crate::output::report_json_schema();
+    println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+}
+
 pub fn build_schema_value() -> Value {
     build_schema()
 }
@@ -14,154 +20,8 @@ fn build_schema() -> Value {
         "clispec": "0.2",
         "name": "upd",
         "version": env!("CARGO_PKG_VERSION"),
-        "description": "A fast dependency updater for Python, Node.js, Rust, Go, Ruby, .NET, Terraform, GitHub Actions, pre-commit, and Mise/asdf projects",
-        "global_args": [
-            {
-                "name": "paths",
-                "description": "Paths to update (files or directories; default: nearest git root)",
-                "type": "path[]",
-                "required": false
-            },
-            {
-                "name": "output",
-                "short": "o",
-                "description": "Output format. auto emits JSON when stdout is not a TTY, explicit value always wins",
-                "type": "string",
-                "enum": ["auto", "text", "json"],
-                "default": "auto"
-            },
-            {
-                "name": "apply",
-                "description": "Apply updates to files. Without --apply (and without --interactive), runs in dry-run mode",
-                "type": "boolean"
-            },
-            {
-                "name": "yes",
-                "description": "Alias for --apply: apply updates non-interactively (for scripted use)",
-                "type": "boolean"
-            },
-            {
-                "name": "dry-run",
-                "short": "n",
-                "description": "Show what would change without writing any files",
-                "type": "boolean"
-            },
-            {
-                "name": "check",
-                "description": "Exit 1 if updates are available, without writing any changes (CI use)",
-                "type": "boolean"
-            },
-            {
-                "name": "max-bump",
-                "description": "Include updates up to and including the given bump level",
-                "type": "string",
-                "enum": ["patch", "minor", "major"]
-            },
-            {
-                "name": "only-bump",
-                "description": "Include only updates whose bump level exactly matches. Repeatable or comma-separated. Mutually exclusive with --max-bump",
-                "type": "string[]",
-                "enum": ["patch", "minor", "major"]
-            },
-            {
-                "name": "lang",
-                "short": "l",
-                "description": "Filter by language/ecosystem (repeatable or comma-separated)",
-                "type": "string[]",
-                "enum": ["python", "node", "rust", "go", "ruby", "dotnet", "actions", "pre-commit", "mise", "terraform"]
-            },
-            {
-                "name": "limit",
-                "description": "Limit output to N items",
-                "type": "integer"
-            },
-            {
-                "name": "offset",
-                "description": "Skip first N items",
-                "type": "integer",
-                "default": 0
-            },
-            {
-                "name": "fields",
-                "description": "Comma-separated list of fields to include in JSON output",
-                "type": "string"
-            },
-            {
-                "name": "format",
-                "description": "Set output format: text (default), json, or sarif. Use --output/-o for auto-detection",
-                "type": "string",
-                "enum": ["text", "json", "sarif"]
-            },
-            {
-                "name": "package",
-                "description": "Update only the named package(s). Comma-separated or repeatable",
-                "type": "string[]"
-            },
-            {
-                "name": "full-precision",
-                "description": "Use full version precision (e.g. 3.1.5 instead of 3.1)",
-                "type": "boolean"
-            },
-            {
-                "name": "interactive",
-                "short": "i",
-                "description": "Prompt before applying each update",
-                "type": "boolean"
-            },
-            {
-                "name": "lock",
-                "description": "Regenerate lockfiles after updating",
-                "type": "boolean"
-            },
-            {
-                "name": "no-cache",
-                "description": "Disable version caching",
-                "type": "boolean"
-            },
-            {
-                "name": "no-color",
-                "description": "Disable colored output",
-                "type": "boolean"
-            },
-            {
-                "name": "no-ignore",
-                "description": "Disable .gitignore filtering and walk every dependency file",
-                "type": "boolean"
-            },
-            {
-                "name": "verbose",
-                "short": "v",
-                "description": "Verbose output",
-                "type": "boolean"
-            },
-            {
-                "name": "quiet",
-                "short": "q",
-                "description": "Suppress all output except errors and warnings",
-                "type": "boolean"
-            },
-            {
-                "name": "min-age",
-                "description": "Minimum release age before a version is eligible for update (e.g. 72h, 7d, 2w)",
-                "type": "string"
-            },
-            {
-                "name": "config",
-                "short": "c",
-                "description": "Path to config file (default: auto-discover .updrc.toml, upd.toml, or .updrc)",
-                "type": "path"
-            },
-            {
-                "name": "show-config",
-                "description": "Print the effective configuration and exit",
-                "type": "boolean"
-            },
-            {
-                "name": "insecure",
-                "description": "Disable TLS certificate verification for all HTTPS requests",
-                "type": "boolean"
-            }
-        ],
+        "description": "A fast dependency updater for Python, Node.js, Rust, Go, Ruby, .NET, PHP, Terraform, Docker, GitHub Actions, pre-commit, and Mise/asdf projects",
+        "global_args": global_args(),
         "commands": [
             {
                 "name": "update",
@@ -178,13 +38,15 @@ fn build_schema() -> Value {
                 "output_fields": [
                     {"name": "command", "type": "string", "description": "Always \"update\""},
                     {"name": "mode", "type": "string", "description": "\"dry-run\" or \"applied\""},
-                    {"name": "files", "type": "array", "description": "Per-file update reports"},
-                    {"name": "summary", "type": "object", "description": "Aggregate counts (files_scanned, updates_total, etc.)"}
+                    {"name": "files", "type": "array", "description": "Per-file update reports; with --offline, uncached lookups are listed under offline_skipped instead of errors; lookups cut short by --max-runtime are listed under timed_out; git, path and unconfigured-registry Cargo dependencies, and pyproject dependencies with a git, path, workspace or url [tool.uv.sources] entry, are listed under skipped_sources with a reason"},
+                    {"name": "summary", "type": "object", "description": "Aggregate counts (files_scanned, updates_total, offline_skipped, timed_out, etc.)"},
+                    {"name": "transitive", "type": "array", "description": "With --include-transitive: outdated lockfile-only packages (lockfile, package, current, latest)"},
+                    {"name": "changelogs", "type": "array", "description": "With --changelog: a release-notes URL per updated package (package, url)"}
                 ]
             },
             {
                 "name": "align",
-                "description": "Align all packages to the highest version found in the repository. Dry-run by default; pass --apply to write",
+                "description": "Align all packages to the highest version found in the repository (the lowest with --lowest), or the --package selection to a --to version. Dry-run by default; pass --apply to write",
                 "mutating": true,
                 "args": [
                     {
@@ -192,14 +54,47 @@ fn build_schema() -> Value {
                         "description": "Paths to scan and align",
                         "type": "path[]",
                         "required": false
+                    },
+                    {
+                        "name": "to",
+                        "description": "Align the packages selected with --package to this version instead of the highest found, downgrading higher occurrences. Constrained occurrences are still skipped, with a warning when the version falls below their lower bound",
+                        "type": "string"
+                    },
+                    {
+                        "name": "lowest",
+                        "description": "Align down to the lowest version in use instead of the highest, downgrading higher occurrences. Constrained occurrences are still skipped",
+                        "type": "boolean"
                     }
                 ],
                 "output_fields": [
                     {"name": "command", "type": "string", "description": "Always \"align\""},
-                    {"name": "packages", "type": "array", "description": "Per-package alignment records (name, highest_version, occurrences with file/line/is_misaligned)"},
+                    {"name": "packages", "type": "array", "description": "Per-package alignment records (name, highest_version, target_version, occurrences with file/line/is_misaligned)"},
                     {"name": "summary", "type": "object", "description": "Aggregate counts (files_scanned, misaligned_packages, misaligned_occurrences, packages)"}
                 ]
             },
+            {
+                "name": "outdated",
+                "description": "List dependencies with a newer version and how many majors each is behind, counted from released versions. Never writes",
+                "mutating": false,
+                "args": [
+                    {
+                        "name": "paths",
+                        "description": "Paths to scan",
+                        "type": "path[]",
+                        "required": false
+                    },
+                    {
+                        "name": "major",
+                        "description": "Only list dependencies behind by at least one major version",
+                        "type": "boolean"
+                    }
+                ],
+                "output_fields": [
+                    {"name": "command", "type": "string", "description": "Always \"outdated\""},
+                    {"name": "packages", "type": "array", "description": "Per dependency: file, package, lang, current, latest, bump and majors_behind"},
+                    {"name": "summary", "type": "object", "description": "Counts: outdated, behind_a_major, errors"}
+                ]
+            },
             {
                 "name": "audit",
                 "description": "Check dependencies for known security vulnerabilities",
@@ -218,28 +113,105 @@ fn build_schema() -> Value {
                     },
                     {
                         "name": "fix-audit",
+                        "aliases": ["fix"],
                         "description": "Bump vulnerable packages to the minimum version that clears all known CVEs. Read-only on its own; combined with --apply this makes `audit` MUTATING (it writes to dependency files), despite the command-level mutating:false default",
                         "type": "boolean"
                     },
                     {
-                        "name": "offline",
-                        "description": "Use local audit cache only; do not contact OSV",
-                        "type": "boolean"
+                        "name": "severity",
+                        "description": "Only report vulnerabilities at or above this severity; ones with no known severity are dropped. Applies before the exit-code decision",
+                        "type": "string",
+                        "enum": ["low", "medium", "high", "critical"]
+                    },
+                    {
+                        "name": "sbom",
+                        "description": "Audit the components of a CycloneDX or SPDX JSON SBOM instead of scanning manifests",
+                        "type": "path"
                     }
                 ],
                 "output_fields": [
                     {"name": "command", "type": "string", "description": "Always \"audit\""},
                     {"name": "status", "type": "string", "description": "\"ok\", \"vulnerable\", or \"incomplete\" (e.g. an offline cache miss)"},
-                    {"name": "vulnerabilities", "type": "array", "description": "Vulnerable packages, each with package, ecosystem, version, and a vulnerabilities list (id, severity, fixed_version, url)"},
+                    {"name": "vulnerabilities", "type": "array", "description": "Vulnerable packages, each with package, ecosystem, version, and a vulnerabilities list (id, severity, cvss_score when a CVSS vector could be scored, fixed_version, url)"},
                     {"name": "summary", "type": "object", "description": "Aggregate counts (packages_checked, vulnerabilities, vulnerable_packages, errors)"},
                     {"name": "errors", "type": "array", "description": "Per-package audit errors (e.g. unreachable registry, offline cache miss)"}
                 ]
             },
+            {
+                "name": "sbom",
+                "description": "Write a CycloneDX (default) or SPDX JSON SBOM of the declared dependencies, selected with --format cyclonedx|spdx",
+                "mutating": false,
+                "args": [
+                    {
+                        "name": "out",
+                        "description": "Output file (`-` for stdout)",
+                        "type": "path",
+                        "required": true
+                    },
+                    {
+                        "name": "paths",
+                        "description": "Paths to scan",
+                        "type": "path[]",
+                        "required": false
+                    }
+                ]
+            },
+            {
+                "name": "rollback",
+                "description": "Restore the files changed by the last --backup run from their .<name>.upd.bak copies",
+                "mutating": true,
+                "args": [
+                    {
+                        "name": "paths",
+                        "description": "Paths to scan for backups",
+                        "type": "path[]",
+                        "required": false
+                    }
+                ],
+                "output_fields": [
+                    {"name": "command", "type": "string", "description": "Always \"rollback\""},
+                    {"name": "restored", "type": "array", "description": "Paths of the restored files; empty when there was nothing to restore"}
+                ]
+            },
             {
                 "name": "clean-cache",
                 "description": "Clear the version cache",
                 "mutating": true
             },
+            {
+                "name": "cache-stats",
+                "description": "Summarize the version cache: entries per registry, size on disk, oldest/newest entry and lookup hit rate. Alias: stats",
+                "mutating": false,
+                "output_fields": [
+                    {"name": "entries", "type": "integer", "description": "Cached versions across all registries"},
+                    {"name": "registries", "type": "object", "description": "Cached versions per registry"},
+                    {"name": "expired", "type": "integer", "description": "Entries older than --cache-ttl (default 1h)"},
+                    {"name": "size_bytes", "type": "integer", "description": "Size of the cache file (0 when absent)"},
+                    {"name": "oldest", "type": "string", "description": "RFC 3339 time of the oldest entry (null when empty)"},
+                    {"name": "newest", "type": "string", "description": "RFC 3339 time of the newest entry (null when empty)"},
+                    {"name": "hits", "type": "integer", "description": "Lookups answered from the cache"},
+                    {"name": "misses", "type": "integer", "description": "Lookups sent to a registry"},
+                    {"name": "hit_rate", "type": "number", "description": "hits / (hits + misses); null before any lookup"}
+                ]
+            },
+            {
+                "name": "merge-reports",
+                "description": "Merge `upd update --format json` reports from separate runs into totals, a per-report (repo) breakdown and a per-package list",
+                "mutating": false,
+                "args": [
+                    {
+                        "name": "reports",
+                        "description": "Report files; each is named by its path in the output",
+                        "type": "path[]",
+                        "required": true
+                    }
+                ],
+                "output_fields": [
+                    {"name": "summary", "type": "object", "description": "reports count plus summed files_scanned, files_with_changes, updates_total, updates_major/minor/patch, errors, warnings"},
+                    {"name": "repos", "type": "array", "description": "Per report: repo (path), mode and its summary counts"},
+                    {"name": "packages", "type": "array", "description": "Per package: package, lang, repos (distinct reports) and updates [{repo, file, current, latest, bump}], most widely updated first"}
+                ]
+            },
             {
                 "name": "self-update",
                 "description": "Update upd itself to the latest release",
@@ -249,6 +221,13 @@ fn build_schema() -> Value {
                 "name": "schema",
                 "description": "Print machine-readable schema (clispec v0.2 JSON). Works offline with no config required",
                 "mutating": false,
+                "args": [
+                    {
+                        "name": "report",
+                        "description": "Print the JSON Schema (draft 2020-12) of the --format json reports of update, align and audit instead",
+                        "type": "boolean"
+                    }
+                ],
                 "output_fields": [
                     {"name": "clispec", "type": "string", "description": "Spec version"},
                     {"name": "name", "type": "string", "description": "Tool name"},
@@ -269,6 +248,11 @@ fn build_schema() -> Value {
                 "code": 6,
                 "name": "vulnerabilities_found",
                 "description": "Security vulnerabilities found during audit; the report is on stdout. Not an error. Use --no-fail to exit 0 instead"
+            },
+            {
+                "code": 7,
+                "name": "timed_out_partial",
+                "description": "--max-runtime ran out before every lookup finished; the partial report is on stdout and resolved updates were applied in apply mode. Skipped packages are listed under timed_out. Errors (exit 2) take precedence"
             }
         ],
         "errors": [
@@ -300,6 +284,411 @@ fn build_schema() -> Value {
     })
 }
 
+/// Flags accepted by every command. Kept out of `build_schema`'s literal, and
+/// split across two literals, so each `json!` expansion stays under the macro
+/// recursion limit.
+fn global_args() -> Value {
+    let mut args = json!([
+        {
+            "name": "paths",
+            "description": "Paths to update (files or directories; default: nearest git root)",
+            "type": "path[]",
+            "required": false
+        },
+        {
+            "name": "output",
+            "short": "o",
+            "description": "Output format. auto emits JSON when stdout is not a TTY, explicit value always wins",
+            "type": "string",
+            "enum": ["auto", "text", "json"],
+            "default": "auto"
+        },
+        {
+            "name": "apply",
+            "description": "Apply updates to files. Without --apply (and without --interactive), runs in dry-run mode",
+            "type": "boolean"
+        },
+        {
+            "name": "yes",
+            "description": "Alias for --apply: apply updates non-interactively (for scripted use)",
+            "type": "boolean"
+        },
+        {
+            "name": "dry-run",
+            "short": "n",
+            "description": "Show what would change without writing any files",
+            "type": "boolean"
+        },
+        {
+            "name": "check",
+            "description": "Exit 1 if updates are available, without writing any changes (CI use)",
+            "type": "boolean"
+        },
+        {
+            "name": "error-on",
+            "description": "Set the exit code from what the run found: 1 for updates at or above the level (or any pending change), 2 on errors; `none` always exits 0",
+            "type": "string",
+            "enum": ["major", "minor", "patch", "any", "error", "none"]
+        },
+        {
+            "name": "max-bump",
+            "description": "Include updates up to and including the given bump level",
+            "type": "string",
+            "enum": ["patch", "minor", "major"]
+        },
+        {
+            "name": "only-bump",
+            "description": "Include only updates whose bump level exactly matches. Repeatable or comma-separated. Mutually exclusive with --max-bump",
+            "type": "string[]",
+            "enum": ["patch", "minor", "major"]
+        },
+        {
+            "name": "lang",
+            "short": "l",
+            "description": "Filter by language/ecosystem (repeatable or comma-separated)",
+            "type": "string[]",
+            "enum": ["python", "node", "rust", "go", "ruby", "dotnet", "actions", "pre-commit", "mise", "terraform", "docker", "php", "jvm", "dart", "runtime"]
+        },
+        {
+            "name": "limit",
+            "description": "Limit output to N items",
+            "type": "integer"
+        },
+        {
+            "name": "offset",
+            "description": "Skip first N items",
+            "type": "integer",
+            "default": 0
+        },
+        {
+            "name": "fields",
+            "description": "Comma-separated list of fields to include in JSON output",
+            "type": "string"
+        },
+        {
+            "name": "format",
+            "description": "Set output format: text (default), json, sarif, cyclonedx, spdx, or markdown. Use --output/-o for auto-detection",
+            "type": "string",
+            "enum": ["text", "json", "sarif", "cyclonedx", "spdx", "markdown"]
+        },
+        {
+            "name": "package",
+            "aliases": ["only"],
+            "description": "Update only the named package(s). Comma-separated or repeatable",
+            "type": "string[]"
+        },
+        {
+            "name": "allow-prerelease",
+            "description": "Let the named package(s) update into pre-releases while others stay stable. Comma-separated or repeatable",
+            "type": "string[]"
+        },
+        {
+            "name": "group",
+            "description": "Update only the named dependency group(s) in pyproject.toml and package.json. Comma-separated or repeatable",
+            "type": "string[]"
+        },
+        {
+            "name": "min-update-count",
+            "description": "Skip the run (no writes, exit 0) when fewer than N filtered updates are available",
+            "type": "integer"
+        },
+        {
+            "name": "newest-only",
+            "description": "Apply at most N updates, ranked major before minor before patch; the rest are reported as deferred",
+            "type": "integer"
+        },
+        {
+            "name": "apply-only-if-tests-pass",
+            "description": "After applying, run COMMAND in each changed directory and restore that directory's files if it fails",
+            "type": "string"
+        },
+        {
+            "name": "backup",
+            "description": "Copy each file's previous content to .<name>.upd.bak before writing; `upd rollback` restores it. Replaces the previous run's backups",
+            "type": "boolean"
+        },
+        {
+            "name": "decisions-file",
+            "description": "Write a JSON record of every dependency's final decision and reason to PATH",
+            "type": "string"
+        },
+        {
+            "name": "summary-json",
+            "description": "Also write the run's top-level counts (updated/major/minor/patch/pinned/ignored/errors, overall and per ecosystem) as JSON to PATH",
+            "type": "string"
+        },
+        {
+            "name": "treat-unconstrained-as-error",
+            "description": "Report every dependency declared without a lower bound (npm \"*\", bare PyPI names, Cargo \"*\") as an error and exit 2",
+            "type": "boolean"
+        },
+        {
+            "name": "respect-msrv",
+            "description": "Skip Cargo versions whose rust-version exceeds the project's rust-version (or the installed rustc)",
+            "type": "boolean"
+        },
+        {
+            "name": "ignore-engines",
+            "description": "Update npm packages even when their engines.node excludes the Node versions package.json declares",
+            "type": "boolean"
+        },
+        {
+            "name": "allow-deprecated",
+            "description": "Update npm packages to versions the registry marks as deprecated instead of the newest non-deprecated one",
+            "type": "boolean"
+        },
+        {
+            "name": "diff",
+            "description": "Text output: show each rewritten file as a colored unified diff instead of per-package lines",
+            "type": "boolean"
+        },
+        {
+            "name": "link-types",
+            "description": "Text output: print each @types/* update right below the update of its runtime package from the same file",
+            "type": "boolean"
+        },
+        {
+            "name": "stdin-format",
+            "description": "Read a manifest of this type from stdin (pass - as the only path) and write the updated manifest to stdout; no file is written. Alias: --file-type",
+            "type": "string",
+            "enum": ["requirements", "pyproject", "pipfile", "setup_py", "conda_env", "package_json", "pnpm_workspace", "cargo_toml", "go_mod", "gemfile", "csproj", "github_actions", "pre_commit", "mise_toml", "tool_versions", "terraform_tf", "dockerfile", "composer_json", "gradle_catalog", "pubspec", "nvmrc", "python_version"]
+        },
+        {
+            "name": "offline",
+            "description": "Answer from the local cache only and never contact a registry; uncached packages are reported as offline-skipped (audit: cached OSV results only, misses are errors)",
+            "type": "boolean"
+        },
+        {
+            "name": "pypi-api",
+            "description": "Order in which PyPI's Simple and JSON APIs are tried (default simple,json); the -only values never fall back",
+            "type": "string",
+            "enum": ["simple,json", "json,simple", "simple-only", "json-only"],
+            "default": "simple,json"
+        },
+        {
+            "name": "registry-map",
+            "description": "Override the registry URL of an ecosystem as LANG=URL (python, node, rust, go, ruby, dotnet, php, jvm, dart); comma-separated or repeated, taking precedence over environment and config-file detection",
+            "type": "string[]"
+        },
+        {
+            "name": "max-runtime",
+            "description": "Overall time budget for registry lookups, e.g. 90s or 5m; once it passes, remaining packages are skipped and listed under timed_out, resolved updates are still reported (and applied with --apply), and the run exits 7",
+            "type": "string"
+        },
+        {
+            "name": "prune-dead-markers",
+            "description": "Warn about pyproject requirements whose environment markers can never match [project] requires-python (lines are never deleted)",
+            "type": "boolean"
+        }
+    ]);
+    if let (Some(args), Value::Array(rest)) = (args.as_array_mut(), output_args()) {
+        args.extend(rest);
+    }
+    args
+}
+
+/// The second half of [`global_args`]: precision, prompting, output and
+/// network flags.
+fn output_args() -> Value {
+    json!([
+        {
+            "name": "full-precision",
+            "description": "Use full version precision (e.g. 3.1.5 instead of 3.1)",
+            "type": "boolean"
+        },
+        {
+            "name": "strategy",
+            "description": "How far updates may move. compatible stays within the current version's compatible range (^1.2.0 below 2.0.0, ~1.2.0 below 1.3.0); applies to Python, npm, Cargo and Go. n-1 picks the highest release below the newest one",
+            "type": "string",
+            "enum": ["latest", "compatible", "n-1"],
+            "default": "latest"
+        },
+        {
+            "name": "interactive",
+            "short": "i",
+            "description": "Prompt before applying each update",
+            "type": "boolean"
+        },
+        {
+            "name": "edit",
+            "description": "Offer an [e]dit choice at interactive prompts to type a custom version (requires --interactive)",
+            "type": "boolean"
+        },
+        {
+            "name": "lock",
+            "description": "Regenerate lockfiles after updating",
+            "type": "boolean"
+        },
+        {
+            "name": "update-lockfile-only",
+            "description": "Skip the updaters and regenerate every discovered manifest's lockfile with its manifest-wide refresh command; dry-run lists the lockfiles, --apply runs the tools",
+            "type": "boolean"
+        },
+        {
+            "name": "allow-scripts",
+            "description": "Let --lock run install scripts and builds (by default npm, pnpm and bun get --ignore-scripts and uv gets --no-build)",
+            "type": "boolean"
+        },
+        {
+            "name": "no-cache",
+            "description": "Disable version caching",
+            "type": "boolean"
+        },
+        {
+            "name": "no-color",
+            "description": "Disable colored output (same as --color never)",
+            "type": "boolean"
+        },
+        {
+            "name": "color",
+            "description": "When to color output. auto colors on a TTY and honours NO_COLOR / FORCE_COLOR; always and never override both",
+            "type": "string",
+            "enum": ["auto", "always", "never"],
+            "default": "auto"
+        },
+        {
+            "name": "no-ignore",
+            "aliases": ["no-gitignore"],
+            "description": "Disable .gitignore filtering and walk every dependency file",
+            "type": "boolean"
+        },
+        {
+            "name": "include-hidden",
+            "description": "Descend into hidden directories and pick up hidden dependency files (.git is never walked)",
+            "type": "boolean"
+        },
+        {
+            "name": "only-direct",
+            "description": "Only consider declared (direct) dependencies. This is the default",
+            "type": "boolean"
+        },
+        {
+            "name": "include-transitive",
+            "description": "Also report lockfile-pinned transitive dependencies: outdated ones in update, vulnerable ones in audit. Never rewritten",
+            "type": "boolean"
+        },
+        {
+            "name": "changelog",
+            "description": "Print a release-notes link for each updated package: its changelog or repository releases page, else the registry page",
+            "type": "boolean"
+        },
+        {
+            "name": "exclude",
+            "description": "Skip dependency files matching the glob, relative to the scanned directory. Repeatable",
+            "type": "string[]"
+        },
+        {
+            "name": "verbose",
+            "short": "v",
+            "description": "Verbose output",
+            "type": "boolean"
+        },
+        {
+            "name": "quiet",
+            "short": "q",
+            "description": "Print only errors (on stderr) and a one-line update summary",
+            "type": "boolean"
+        },
+        {
+            "name": "log-level",
+            "description": "Write diagnostic logs (file processing, registry requests, retries, cache hits and misses) to stderr at this level and above. Off unless given",
+            "type": "string",
+            "enum": ["error", "warn", "info", "debug", "trace"]
+        },
+        {
+            "name": "progress-stderr",
+            "description": "Print human-readable text on stderr, keeping stdout for structured output",
+            "type": "boolean"
+        },
+        {
+            "name": "min-age",
+            "description": "Minimum release age before a version is eligible for update (e.g. 72h, 7d, 2w)",
+            "type": "string"
+        },
+        {
+            "name": "config",
+            "short": "c",
+            "description": "Path to config file (default: auto-discover .updrc.toml, upd.toml, or .updrc)",
+            "type": "path"
+        },
+        {
+            "name": "no-config",
+            "description": "Ignore all config files: no discovery, and no ignore, pin, exclude, or cooldown rules",
+            "type": "boolean"
+        },
+        {
+            "name": "emit-purls",
+            "description": "Print the package URL (purl) of every declared dependency, one per line, and exit",
+            "type": "boolean"
+        },
+        {
+            "name": "show-config",
+            "description": "Print the effective configuration and exit",
+            "type": "boolean"
+        },
+        {
+            "name": "print-config",
+            "description": "Print the resolved ignore list and pin map, and the config file(s) they came from, then exit",
+            "type": "boolean"
+        },
+        {
+            "name": "insecure",
+            "description": "Disable TLS certificate verification for all HTTPS requests",
+            "type": "boolean"
+        },
+        {
+            "name": "timeout",
+            "description": "Per-request timeout in seconds for registry and advisory lookups",
+            "type": "integer",
+            "default": 30
+        },
+        {
+            "name": "retries",
+            "description": "Retries after a network error or 5xx response, with jittered exponential backoff (up to 100ms doubling, capped at 5s); 0 disables retrying",
+            "type": "integer",
+            "default": 2
+        },
+        {
+            "name": "cache-ttl",
+            "description": "How long a cached version stays fresh before a lookup refetches it, e.g. 30m or 24h",
+            "type": "string",
+            "default": "1h"
+        },
+        {
+            "name": "negative-cache-ttl",
+            "description": "How long a definitive \"not found\" (HTTP 404) answer is cached; server errors and timeouts are never cached",
+            "type": "string",
+            "default": "5m"
+        },
+        {
+            "name": "resolve-concurrency-per-host",
+            "description": "Maximum registry requests in flight to one host, shared across ecosystems; unlimited when unset",
+            "type": "integer"
+        },
+        {
+            "name": "rate-limit",
+            "description": "Maximum registry requests per second across all hosts and ecosystems, retries included; fractions allowed, unlimited when unset",
+            "type": "number"
+        },
+        {
+            "name": "max-concurrency",
+            "description": "Maximum files processed at once; must be at least 1",
+            "type": "integer",
+            "default": 8
+        },
+        {
+            "name": "no-parallel",
+            "description": "Process files and registry requests one at a time so output follows file discovery order; overrides resolve-concurrency-per-host and max-concurrency",
+            "type": "boolean"
+        },
+        {
+            "name": "fail-fast",
+            "description": "Stop at the first file whose lookups fail, cancelling lookups in flight, instead of collecting every error",
+            "type": "boolean"
+        }
+    ])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -394,6 +783,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn schema_declares_timed_out_partial_outcome_with_code_7() {
+        let s = build_schema();
+        let outcomes = s["outcomes"].as_array().expect("outcomes must be an array");
+        let timed_out = outcomes
+            .iter()
+            .find(|o| o["name"].as_str() == Some("timed_out_partial"))
+            .expect("must declare a 'timed_out_partial' outcome");
+        assert_eq!(
+            timed_out["code"].as_u64(),
+            Some(crate::EXIT_TIMED_OUT as u64)
+        );
+    }
+
     #[test]
     fn schema_declares_conflict_error_kind() {
         let s = build_schema();
@@ -492,6 +895,11 @@ mod tests {
             "pre-commit",
             "mise",
             "terraform",
+            "docker",
+            "php",
+            "jvm",
+            "dart",
+            "runtime",
         ] {
             assert!(
                 values.iter().any(|v| v == eco),
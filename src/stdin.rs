@@ -0,0 +1,120 @@
+//! Manifests piped on stdin for `--stdin-format`.
+//!
+//! Updaters read and rewrite files by path, so the piped content is staged in
+//! a private scratch directory under a name the updater expects. The run is a
+//! dry run: the rewrite is taken from the result's recorded content, and the
+//! scratch directory is removed when the [`StdinManifest`] is dropped.
+
+use crate::updater::FileType;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A piped manifest staged on disk for the updaters.
+#[derive(Debug)]
+pub struct StdinManifest {
+    dir: PathBuf,
+    path: PathBuf,
+    file_type: FileType,
+    content: String,
+}
+
+impl StdinManifest {
+    /// Read all of stdin and stage it as a manifest of `file_type`.
+    pub fn read(file_type: FileType) -> io::Result<Self> {
+        let content = io::read_to_string(io::stdin())?;
+        Self::stage(file_type, content)
+    }
+
+    /// Stage `content` as a manifest of `file_type` in a new scratch directory.
+    pub fn stage(file_type: FileType, content: String) -> io::Result<Self> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or_default();
+        let dir = std::env::temp_dir().join(format!("upd-stdin-{}-{nanos}", std::process::id()));
+        std::fs::create_dir(&dir)?;
+        let manifest = Self {
+            path: dir.join(file_name(file_type)),
+            dir,
+            file_type,
+            content,
+        };
+        if let Some(parent) = manifest.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&manifest.path, &manifest.content)?;
+        Ok(manifest)
+    }
+
+    /// Where the manifest is staged.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// The manifest as it was read from stdin.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+impl Drop for StdinManifest {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// A file name (relative to the scratch directory) that [`FileType::detect`]
+/// maps back to `file_type`.
+fn file_name(file_type: FileType) -> &'static str {
+    match file_type {
+        FileType::Requirements => "requirements.txt",
+        FileType::PyProject => "pyproject.toml",
+        FileType::Pipfile => "Pipfile",
+        FileType::SetupPy => "setup.py",
+        FileType::CondaEnv => "environment.yml",
+        FileType::PackageJson => "package.json",
+        FileType::PnpmWorkspace => "pnpm-workspace.yaml",
+        FileType::CargoToml => "Cargo.toml",
+        FileType::GoMod => "go.mod",
+        FileType::Gemfile => "Gemfile",
+        FileType::Csproj => "stdin.csproj",
+        FileType::GithubActions => ".github/workflows/stdin.yml",
+        FileType::PreCommitConfig => ".pre-commit-config.yaml",
+        FileType::MiseToml => ".mise.toml",
+        FileType::ToolVersions => ".tool-versions",
+        FileType::TerraformTf => "main.tf",
+        FileType::Dockerfile => "Dockerfile",
+        FileType::ComposerJson => "composer.json",
+        FileType::GradleCatalog => "gradle/libs.versions.toml",
+        FileType::Pubspec => "pubspec.yaml",
+        FileType::Nvmrc => ".nvmrc",
+        FileType::PythonVersion => ".python-version",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_staged_manifest_is_detected_as_its_type_and_cleaned_up() {
+        for &file_type in FileType::value_variants() {
+            let manifest = StdinManifest::stage(file_type, "content\n".to_string()).unwrap();
+            assert_eq!(FileType::detect(manifest.path()), Some(file_type));
+            assert_eq!(
+                std::fs::read_to_string(manifest.path()).unwrap(),
+                "content\n"
+            );
+
+            let dir = manifest.dir.clone();
+            drop(manifest);
+            assert!(!dir.exists(), "scratch directory must be removed");
+        }
+    }
+}
@@ -0,0 +1,494 @@
+//! Transitive dependencies read from lockfiles, for `--include-transitive`.
+//!
+//! `upd` only ever rewrites declared (direct) dependencies. With
+//! `--include-transitive` it additionally reads the lockfile next to each
+//! manifest and reports locked packages the manifest does not declare: newer
+//! registry versions in `update`, and OSV advisories in `audit`. Lockfiles are
+//! never modified here.
+//!
+//! Parsed formats: `Cargo.lock`, `package-lock.json`, `poetry.lock`,
+//! `uv.lock` and `Pipfile.lock`. Other lockfiles are skipped.
+
+use crate::align::{compare_versions, parse_file_dependencies};
+use crate::lockfile::{LockfileType, detect_lockfiles};
+use crate::registry::Registry;
+use crate::updater::{FileType, Lang, read_file_safe};
+use anyhow::{Result, anyhow};
+use futures::stream::{self, StreamExt};
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item};
+
+/// Registry lookups in flight per lockfile.
+const LOOKUP_CONCURRENCY: usize = 8;
+
+/// A package version pinned by a lockfile.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+}
+
+/// Locked packages a manifest does not declare, with the lockfile they came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransitivePackages {
+    pub lockfile: PathBuf,
+    pub lang: Lang,
+    pub packages: Vec<LockedPackage>,
+}
+
+/// A transitive package with a newer version on its registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutdatedTransitive {
+    pub lockfile: PathBuf,
+    pub package: String,
+    pub current: String,
+    pub latest: String,
+}
+
+/// Result of checking transitive packages against their registries.
+#[derive(Debug, Default)]
+pub struct TransitiveReport {
+    pub outdated: Vec<OutdatedTransitive>,
+    /// Lockfiles that could not be read and lookups that failed, as messages.
+    pub failures: Vec<String>,
+}
+
+/// Read every third-party package pinned in a lockfile, sorted and deduplicated.
+///
+/// Returns `Ok(None)` for lockfile formats that are not parsed. Workspace
+/// members, path and git sources are left out: they have no registry version.
+pub fn parse_locked_packages(
+    path: &Path,
+    lockfile: LockfileType,
+) -> Result<Option<Vec<LockedPackage>>> {
+    let packages = match lockfile {
+        LockfileType::CargoLock | LockfileType::UvLock | LockfileType::PoetryLock => {
+            parse_toml_lock(path, lockfile)?
+        }
+        LockfileType::PackageLockJson => parse_package_lock(path)?,
+        LockfileType::PipfileLock => parse_pipfile_lock(path)?,
+        _ => return Ok(None),
+    };
+    let unique: BTreeSet<LockedPackage> = packages.into_iter().collect();
+    Ok(Some(unique.into_iter().collect()))
+}
+
+/// Locked packages next to `manifest` that `manifest` does not declare.
+pub fn collect_transitive(manifest: &Path, file_type: FileType) -> Result<Vec<TransitivePackages>> {
+    let declared: HashSet<String> = parse_file_dependencies(manifest, file_type)?
+        .into_iter()
+        .map(|dep| normalize_name(&dep.name, file_type.lang()))
+        .collect();
+    let dir = manifest.parent().unwrap_or(Path::new("."));
+
+    let mut found = Vec::new();
+    for lockfile in detect_lockfiles(manifest) {
+        let lockfile_path = dir.join(lockfile.filename());
+        let Some(locked) = parse_locked_packages(&lockfile_path, lockfile)? else {
+            continue;
+        };
+        let packages: Vec<LockedPackage> = locked
+            .into_iter()
+            .filter(|p| !declared.contains(&normalize_name(&p.name, file_type.lang())))
+            .collect();
+        found.push(TransitivePackages {
+            lockfile: lockfile_path,
+            lang: file_type.lang(),
+            packages,
+        });
+    }
+    Ok(found)
+}
+
+/// Check the transitive packages of each manifest against its registry.
+///
+/// `registry_for` maps an ecosystem to its registry; ecosystems without one
+/// are skipped. A lockfile shared by several manifests is checked once.
+pub async fn find_outdated_transitive<'a>(
+    manifests: &[(PathBuf, FileType)],
+    registry_for: impl Fn(Lang) -> Option<&'a dyn Registry>,
+) -> TransitiveReport {
+    let mut report = TransitiveReport::default();
+    let mut seen_lockfiles: HashSet<PathBuf> = HashSet::new();
+
+    for (manifest, file_type) in manifests {
+        let Some(registry) = registry_for(file_type.lang()) else {
+            continue;
+        };
+        let transitive = match collect_transitive(manifest, *file_type) {
+            Ok(transitive) => transitive,
+            Err(e) => {
+                report
+                    .failures
+                    .push(format!("{}: {}", manifest.display(), e));
+                continue;
+            }
+        };
+
+        for group in transitive {
+            if !seen_lockfiles.insert(group.lockfile.clone()) {
+                continue;
+            }
+            let lang = group.lang;
+            let lookups: Vec<_> = stream::iter(group.packages)
+                .map(|package| async move {
+                    let latest = registry.get_latest_version(&package.name).await;
+                    (package, latest)
+                })
+                .buffered(LOOKUP_CONCURRENCY)
+                .collect()
+                .await;
+
+            for (package, latest) in lookups {
+                match latest {
+                    Ok(latest)
+                        if compare_versions(&latest, &package.version, lang)
+                            == std::cmp::Ordering::Greater =>
+                    {
+                        report.outdated.push(OutdatedTransitive {
+                            lockfile: group.lockfile.clone(),
+                            package: package.name,
+                            current: package.version,
+                            latest,
+                        });
+                    }
+                    Ok(_) => {}
+                    Err(e) => report.failures.push(format!("{}: {}", package.name, e)),
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Compare names the way the ecosystem does: PEP 503 for Python, case-insensitive
+/// elsewhere.
+fn normalize_name(name: &str, lang: Lang) -> String {
+    let lower = name.to_lowercase();
+    if lang == Lang::Python {
+        lower.replace(['_', '.'], "-")
+    } else {
+        lower
+    }
+}
+
+/// `[[package]]` arrays shared by Cargo.lock, uv.lock and poetry.lock.
+fn parse_toml_lock(path: &Path, lockfile: LockfileType) -> Result<Vec<LockedPackage>> {
+    let content = read_file_safe(path)?;
+    let doc: DocumentMut = content
+        .parse()
+        .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?;
+    let Some(packages) = doc.get("package").and_then(Item::as_array_of_tables) else {
+        return Ok(Vec::new());
+    };
+
+    let mut locked = Vec::new();
+    for package in packages.iter() {
+        let (Some(name), Some(version)) = (
+            package.get("name").and_then(|v| v.as_str()),
+            package.get("version").and_then(|v| v.as_str()),
+        ) else {
+            continue;
+        };
+        let source = package.get("source");
+        let from_registry = match lockfile {
+            // Workspace members and path crates carry no `source`.
+            LockfileType::CargoLock => source
+                .and_then(|s| s.as_str())
+                .is_some_and(|s| s.starts_with("registry+") || s.starts_with("sparse+")),
+            // `source = { registry = "..." }` vs `{ editable = "." }`, `{ git = ... }`.
+            LockfileType::UvLock => source
+                .and_then(|s| s.as_table_like())
+                .is_some_and(|t| t.contains_key("registry")),
+            // Poetry only records a source for non-default origins.
+            _ => source
+                .and_then(|s| s.as_table_like())
+                .and_then(|t| t.get("type"))
+                .and_then(|t| t.as_str())
+                .is_none_or(|kind| kind == "legacy"),
+        };
+        if from_registry {
+            locked.push(LockedPackage {
+                name: name.to_string(),
+                version: version.to_string(),
+            });
+        }
+    }
+    Ok(locked)
+}
+
+/// npm lockfile v2/v3 `packages` map, or the nested v1 `dependencies` tree.
+fn parse_package_lock(path: &Path) -> Result<Vec<LockedPackage>> {
+    let content = read_file_safe(path)?;
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?;
+
+    let mut locked = Vec::new();
+    if let Some(packages) = json.get("packages").and_then(|p| p.as_object()) {
+        for (key, entry) in packages {
+            // "" is the root project; links point at workspace folders.
+            let Some((_, name)) = key.rsplit_once("node_modules/") else {
+                continue;
+            };
+            if entry.get("link").and_then(|l| l.as_bool()) == Some(true) {
+                continue;
+            }
+            if let Some(version) = entry.get("version").and_then(|v| v.as_str()) {
+                locked.push(LockedPackage {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                });
+            }
+        }
+    } else if let Some(dependencies) = json.get("dependencies") {
+        collect_v1_dependencies(dependencies, &mut locked);
+    }
+    Ok(locked)
+}
+
+fn collect_v1_dependencies(dependencies: &serde_json::Value, locked: &mut Vec<LockedPackage>) {
+    let Some(dependencies) = dependencies.as_object() else {
+        return;
+    };
+    for (name, entry) in dependencies {
+        if let Some(version) = entry.get("version").and_then(|v| v.as_str())
+            && semver::Version::parse(version).is_ok()
+        {
+            locked.push(LockedPackage {
+                name: name.clone(),
+                version: version.to_string(),
+            });
+        }
+        if let Some(nested) = entry.get("dependencies") {
+            collect_v1_dependencies(nested, locked);
+        }
+    }
+}
+
+/// `default` and `develop` maps; git and path entries carry no `version`.
+fn parse_pipfile_lock(path: &Path) -> Result<Vec<LockedPackage>> {
+    let content = read_file_safe(path)?;
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?;
+
+    let mut locked = Vec::new();
+    for section in ["default", "develop"] {
+        let Some(entries) = json.get(section).and_then(|s| s.as_object()) else {
+            continue;
+        };
+        for (name, entry) in entries {
+            if let Some(version) = entry
+                .get("version")
+                .and_then(|v| v.as_str())
+                .and_then(|v| v.strip_prefix("=="))
+            {
+                locked.push(LockedPackage {
+                    name: name.clone(),
+                    version: version.to_string(),
+                });
+            }
+        }
+    }
+    Ok(locked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MockRegistry;
+    use std::fs;
+
+    const CARGO_TOML: &str = r#"[package]
+name = "demo"
+version = "0.1.0"
+
+[dependencies]
+serde_json = "1.0.128"
+"#;
+
+    const CARGO_LOCK: &str = r#"version = 3
+
+[[package]]
+name = "demo"
+version = "0.1.0"
+dependencies = ["serde_json"]
+
+[[package]]
+name = "itoa"
+version = "1.0.9"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde_json"
+version = "1.0.128"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["itoa", "ryu"]
+
+[[package]]
+name = "ryu"
+version = "1.0.18"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "local"
+version = "0.2.0"
+source = "git+https://github.com/example/local#abc"
+"#;
+
+    #[test]
+    fn test_parse_cargo_lock_keeps_registry_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Cargo.lock");
+        fs::write(&path, CARGO_LOCK).unwrap();
+
+        let names: Vec<String> = parse_locked_packages(&path, LockfileType::CargoLock)
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .map(|p| format!("{}@{}", p.name, p.version))
+            .collect();
+        assert_eq!(
+            names,
+            vec!["itoa@1.0.9", "ryu@1.0.18", "serde_json@1.0.128"]
+        );
+    }
+
+    #[test]
+    fn test_parse_package_lock_v3_and_v1() {
+        let dir = tempfile::tempdir().unwrap();
+        let v3 = dir.path().join("v3.json");
+        fs::write(
+            &v3,
+            r#"{"lockfileVersion": 3, "packages": {
+                "": {"name": "app", "dependencies": {"express": "^4.18.0"}},
+                "node_modules/express": {"version": "4.19.2"},
+                "node_modules/express/node_modules/debug": {"version": "2.6.9"},
+                "node_modules/@types/node": {"version": "20.1.0"},
+                "node_modules/app-lib": {"resolved": "packages/lib", "link": true}
+            }}"#,
+        )
+        .unwrap();
+        let v1 = dir.path().join("v1.json");
+        fs::write(
+            &v1,
+            r#"{"lockfileVersion": 1, "dependencies": {
+                "express": {"version": "4.17.0", "dependencies": {
+                    "debug": {"version": "2.6.9"}
+                }},
+                "local": {"version": "file:../local"}
+            }}"#,
+        )
+        .unwrap();
+
+        let names = |path: &Path| -> Vec<String> {
+            parse_locked_packages(path, LockfileType::PackageLockJson)
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .map(|p| p.name)
+                .collect()
+        };
+        assert_eq!(names(&v3), vec!["@types/node", "debug", "express"]);
+        assert_eq!(names(&v1), vec!["debug", "express"]);
+    }
+
+    #[test]
+    fn test_parse_python_lockfiles() {
+        let dir = tempfile::tempdir().unwrap();
+        let uv = dir.path().join("uv.lock");
+        fs::write(
+            &uv,
+            r#"version = 1
+
+[[package]]
+name = "app"
+version = "0.1.0"
+source = { editable = "." }
+
+[[package]]
+name = "idna"
+version = "3.6"
+source = { registry = "https://pypi.org/simple" }
+"#,
+        )
+        .unwrap();
+        let poetry = dir.path().join("poetry.lock");
+        fs::write(
+            &poetry,
+            r#"[[package]]
+name = "certifi"
+version = "2024.2.2"
+
+[[package]]
+name = "mylib"
+version = "1.0.0"
+
+[package.source]
+type = "git"
+url = "https://github.com/example/mylib.git"
+"#,
+        )
+        .unwrap();
+        let pipfile = dir.path().join("Pipfile.lock");
+        fs::write(
+            &pipfile,
+            r#"{"default": {"urllib3": {"version": "==2.2.1"}, "mylib": {"git": "https://x"}},
+                "develop": {"iniconfig": {"version": "==2.0.0"}}}"#,
+        )
+        .unwrap();
+
+        let parse = |path: &Path, lockfile| -> Vec<String> {
+            parse_locked_packages(path, lockfile)
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .map(|p| format!("{}@{}", p.name, p.version))
+                .collect()
+        };
+        assert_eq!(parse(&uv, LockfileType::UvLock), vec!["idna@3.6"]);
+        assert_eq!(
+            parse(&poetry, LockfileType::PoetryLock),
+            vec!["certifi@2024.2.2"]
+        );
+        assert_eq!(
+            parse(&pipfile, LockfileType::PipfileLock),
+            vec!["iniconfig@2.0.0", "urllib3@2.2.1"]
+        );
+        assert!(
+            parse_locked_packages(&uv, LockfileType::YarnLock)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_outdated_transitive_excludes_current_declared_dependency() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        fs::write(&manifest, CARGO_TOML).unwrap();
+        fs::write(dir.path().join("Cargo.lock"), CARGO_LOCK).unwrap();
+
+        let registry = MockRegistry::new("crates.io")
+            .with_version("serde_json", "1.0.128")
+            .with_version("itoa", "1.0.11")
+            .with_version("ryu", "1.0.18");
+
+        let report = find_outdated_transitive(&[(manifest, FileType::CargoToml)], |lang| {
+            (lang == Lang::Rust).then_some(&registry as &dyn Registry)
+        })
+        .await;
+
+        assert!(report.failures.is_empty(), "{:?}", report.failures);
+        assert_eq!(
+            report.outdated,
+            vec![OutdatedTransitive {
+                lockfile: dir.path().join("Cargo.lock"),
+                package: "itoa".to_string(),
+                current: "1.0.9".to_string(),
+                latest: "1.0.11".to_string(),
+            }]
+        );
+    }
+}
@@ -1,16 +1,17 @@
 use super::{
-    FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
-    read_file_safe, write_file_atomic,
+    FileType, ParsedDependency, UnconstrainedDependency, UpdateOptions, UpdateResult, Updater,
+    downgrade_warning, read_file_safe, resolve_pin,
 };
 use crate::align::compare_versions;
-use crate::registry::{CratesIoRegistry, Registry};
+use crate::registry::{CratesIoRegistry, Registry, read_project_cargo_registries};
 use crate::updater::Lang;
+use crate::version::semver_util::parse_partial_semver;
 use crate::version::{is_prerelease_semver, is_stable_semver, match_version_precision};
 use anyhow::{Result, anyhow};
 use futures::future::join_all;
 use std::collections::HashMap;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use toml_edit::{DocumentMut, Formatted, Item, Table, Value};
 
 pub struct CargoTomlUpdater;
@@ -23,6 +24,28 @@ struct CargoTomlLineIndex {
 /// (package, prefix, current_version, optional registry name, source line).
 type DependencyLookup = (String, String, String, Option<String>, Option<usize>);
 
+/// Cargo's name for the default registry in `registry = "..."` keys.
+const DEFAULT_REGISTRY_NAME: &str = "crates-io";
+
+/// A minimum supported Rust version, kept as declared for messages.
+#[derive(Debug, Clone)]
+struct RustVersion {
+    declared: String,
+    version: semver::Version,
+}
+
+impl RustVersion {
+    fn parse(declared: &str) -> Option<Self> {
+        let mut version = parse_partial_semver(declared)?;
+        // Nightly/beta toolchains (`1.82.0-nightly`) satisfy the release MSRV.
+        version.pre = semver::Prerelease::EMPTY;
+        Some(Self {
+            declared: declared.trim().to_string(),
+            version,
+        })
+    }
+}
+
 impl CargoTomlUpdater {
     pub fn new() -> Self {
         Self
@@ -52,27 +75,44 @@ impl CargoTomlUpdater {
         registries
     }
 
-    /// Get the registry name from a dependency item (if specified)
+    /// Get the alternate registry name from a dependency item (if specified).
+    ///
+    /// `registry = "crates-io"` names Cargo's default registry explicitly, so it
+    /// is treated like an entry without `registry` rather than looked up as an
+    /// alternate registry.
     fn get_registry_name(item: &Item) -> Option<String> {
-        match item {
-            Item::Value(Value::InlineTable(t)) => t
-                .get("registry")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            Item::Table(t) => t
-                .get("registry")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
+        let name = match item {
+            Item::Value(Value::InlineTable(t)) => t.get("registry").and_then(|v| v.as_str()),
+            Item::Table(t) => t.get("registry").and_then(|v| v.as_str()),
             _ => None,
+        }?;
+        (name != DEFAULT_REGISTRY_NAME).then(|| name.to_string())
+    }
+
+    /// Why a dependency is not resolved from a registry, for `path` and `git`
+    /// dependencies.
+    fn non_registry_source(item: &Item) -> Option<&'static str> {
+        let has_key = |key: &str| match item {
+            Item::Value(Value::InlineTable(t)) => t.contains_key(key),
+            Item::Table(t) => t.contains_key(key),
+            _ => false,
+        };
+        if has_key("git") {
+            Some("git dependency")
+        } else if has_key("path") {
+            Some("path dependency")
+        } else {
+            None
         }
     }
 
-    /// Create a registry for a named registry defined in Cargo.toml or config.toml
+    /// Create a registry for a named registry defined in Cargo.toml, a project
+    /// `.cargo/config.toml` or `~/.cargo/config.toml`
     fn create_registry_for_name(
         name: &str,
         cargo_toml_registries: &HashMap<String, String>,
     ) -> Option<Arc<dyn Registry + Send + Sync>> {
-        // First check Cargo.toml [registries] section
+        // First check Cargo.toml [registries] and project .cargo/config.toml
         if let Some(index_url) = cargo_toml_registries.get(name) {
             let api_url = Self::sparse_index_to_api_url(index_url);
             let credentials = CratesIoRegistry::detect_credentials(name);
@@ -116,6 +156,167 @@ impl CargoTomlUpdater {
         }
     }
 
+    /// The project's MSRV for `--respect-msrv`.
+    ///
+    /// Reads `[package] rust-version`, then `[workspace.package] rust-version`
+    /// of this manifest or of the nearest enclosing workspace root (covering
+    /// `rust-version.workspace = true`), and finally falls back to the
+    /// installed `rustc`.
+    fn project_rust_version(doc: &DocumentMut, path: &Path) -> Option<RustVersion> {
+        let declared = |doc: &DocumentMut, table: &str| {
+            doc.get(table)
+                .and_then(|t| t.get("rust-version"))
+                .and_then(|v| v.as_str())
+                .and_then(RustVersion::parse)
+        };
+        let workspace_declared = |doc: &DocumentMut| {
+            doc.get("workspace")
+                .and_then(|w| w.get("package"))
+                .and_then(|t| t.get("rust-version"))
+                .and_then(|v| v.as_str())
+                .and_then(RustVersion::parse)
+        };
+
+        if let Some(msrv) = declared(doc, "package").or_else(|| workspace_declared(doc)) {
+            return Some(msrv);
+        }
+        if doc.get("workspace").is_none() {
+            let dir = path.parent().unwrap_or(Path::new("."));
+            for ancestor in dir.ancestors().skip(1) {
+                let Ok(content) = std::fs::read_to_string(ancestor.join("Cargo.toml")) else {
+                    continue;
+                };
+                let Ok(root) = content.parse::<DocumentMut>() else {
+                    continue;
+                };
+                if root.get("workspace").is_some() {
+                    if let Some(msrv) = workspace_declared(&root) {
+                        return Some(msrv);
+                    }
+                    break;
+                }
+            }
+        }
+        Self::installed_rust_version()
+    }
+
+    /// `rustc --version` of the active toolchain, probed once per process.
+    fn installed_rust_version() -> Option<RustVersion> {
+        static INSTALLED: OnceLock<Option<RustVersion>> = OnceLock::new();
+        INSTALLED
+            .get_or_init(|| {
+                let output = std::process::Command::new("rustc")
+                    .arg("--version")
+                    .output()
+                    .ok()?;
+                let stdout = String::from_utf8(output.stdout).ok()?;
+                RustVersion::parse(stdout.split_whitespace().nth(1)?)
+            })
+            .clone()
+    }
+
+    /// With `--respect-msrv`, the newest version up to `latest` whose declared
+    /// `rust-version` fits `msrv`, plus a note when `latest` had to be skipped.
+    ///
+    /// Falls back to `current` when no newer version fits. Versions without a
+    /// declared `rust-version`, and registries that expose no per-version
+    /// metadata, are treated as compatible.
+    async fn msrv_compatible_version(
+        registry: &dyn Registry,
+        package: &str,
+        prefix: &str,
+        current: &str,
+        latest: &str,
+        msrv: &RustVersion,
+    ) -> (String, Option<String>) {
+        let metas = registry.list_versions(package).await.unwrap_or_default();
+        let required = |version: &str| {
+            metas
+                .iter()
+                .find(|m| m.version == version)
+                .and_then(|m| m.rust_version.as_deref())
+                .and_then(RustVersion::parse)
+        };
+        let Some(latest_requires) = required(latest).filter(|r| r.version > msrv.version) else {
+            return (latest.to_string(), None);
+        };
+        let (Ok(latest_parsed), Some(current_parsed)) = (
+            semver::Version::parse(latest),
+            parse_partial_semver(current),
+        ) else {
+            return (latest.to_string(), None);
+        };
+        let req = if matches!(prefix, "^" | "~") {
+            semver::VersionReq::parse(&format!("{prefix}{current}")).ok()
+        } else {
+            None
+        };
+        let include_prereleases = !current_parsed.pre.is_empty();
+
+        let fallback = metas
+            .iter()
+            .filter(|m| !m.yanked && (include_prereleases || !m.prerelease))
+            .filter(|m| required(&m.version).is_none_or(|r| r.version <= msrv.version))
+            .filter_map(|m| Some((semver::Version::parse(&m.version).ok()?, &m.version)))
+            .filter(|(v, _)| {
+                *v > current_parsed
+                    && *v < latest_parsed
+                    && req.as_ref().is_none_or(|req| req.matches(v))
+            })
+            .max_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, version)| version.clone());
+
+        let outcome = match &fallback {
+            Some(version) => format!("using {version}"),
+            None => format!("keeping {current}"),
+        };
+        let note = format!(
+            "{package}: {latest} requires Rust {} but the project targets {}; {outcome}",
+            latest_requires.declared, msrv.declared
+        );
+        (fallback.unwrap_or_else(|| current.to_string()), Some(note))
+    }
+
+    /// Whether a dependency inherits its requirement from the workspace root
+    /// (`foo = { workspace = true }` or `foo.workspace = true`).
+    fn is_workspace_inherited(item: &Item) -> bool {
+        let flag = match item {
+            Item::Value(Value::InlineTable(t)) => t.get("workspace").and_then(|v| v.as_bool()),
+            Item::Table(t) => t.get("workspace").and_then(|v| v.as_bool()),
+            _ => None,
+        };
+        flag == Some(true)
+    }
+
+    /// The `[workspace.dependencies]` table this manifest's inherited
+    /// dependencies resolve against: its own when it is the workspace root,
+    /// otherwise that of the nearest enclosing workspace root.
+    fn inherited_workspace_dependencies(doc: &DocumentMut, path: &Path) -> Option<Table> {
+        let workspace_deps = |doc: &DocumentMut| {
+            doc.get("workspace")
+                .and_then(|w| w.get("dependencies"))
+                .and_then(Item::as_table)
+                .cloned()
+        };
+
+        if doc.get("workspace").is_some() {
+            return workspace_deps(doc);
+        }
+        let dir = path.parent().unwrap_or(Path::new("."));
+        for ancestor in dir.ancestors().skip(1) {
+            let Ok(content) = std::fs::read_to_string(ancestor.join("Cargo.toml")) else {
+                continue;
+            };
+            let Ok(root) = content.parse::<DocumentMut>() else {
+                continue;
+            };
+            if root.get("workspace").is_some() {
+                return workspace_deps(&root);
+            }
+        }
+        None
+    }
+
     /// Set version on a dependency item, preserving structure
     fn set_version(item: &mut Item, new_version: &str) {
         match item {
@@ -179,6 +380,14 @@ impl CargoTomlUpdater {
                 || section.ends_with(".build-dependencies")))
     }
 
+    /// Whether a `[section]` header (brackets stripped) names a dependency
+    /// table. Text-level edits use this to stay out of `[package.metadata.*]`,
+    /// `[features]`, `[lints]` and other tables that may hold version-like
+    /// strings.
+    pub fn is_dependency_table(section: &str) -> bool {
+        Self::is_dependency_section_path(&Self::normalize_section_path(section.trim()))
+    }
+
     fn dependency_assignment_key(line: &str) -> Option<String> {
         let trimmed = line.trim();
         if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('[') {
@@ -189,6 +398,12 @@ impl CargoTomlUpdater {
         if key.is_empty() {
             return None;
         }
+        // `tokio.workspace = true` declares `tokio` through a dotted key.
+        let key = if key.starts_with(['"', '\'']) {
+            key
+        } else {
+            key.split('.').next()?.trim()
+        };
 
         Some(key.trim_matches('"').trim_matches('\'').to_string())
     }
@@ -205,22 +420,34 @@ impl CargoTomlUpdater {
         line_index: &CargoTomlLineIndex,
         section_path: &str,
         options: &UpdateOptions,
+        msrv: Option<&RustVersion>,
     ) {
         // First pass: collect dependencies and separate by config status
         let mut ignored_deps: Vec<(String, String, Option<usize>)> = Vec::new();
-        let mut pinned_deps: Vec<(String, String, String, String, Option<usize>)> = Vec::new();
+        let mut pinned_deps: Vec<(DependencyLookup, String)> = Vec::new();
         let mut deps_to_check: Vec<DependencyLookup> = Vec::new();
 
         for (key, item) in table.iter() {
-            // Skip path/git dependencies (they have no version to update from registry)
-            if let Item::Value(Value::InlineTable(t)) = item
-                && (t.contains_key("path") || t.contains_key("git"))
-            {
+            // Path and git dependencies have no registry version to update to
+            if let Some(reason) = Self::non_registry_source(item) {
+                let package = key.to_string();
+                let line_num = line_index.line_for(section_path, &package);
+                result
+                    .skipped_sources
+                    .push((package, reason.to_string(), line_num));
                 continue;
             }
-            if let Item::Table(t) = item
-                && (t.contains_key("path") || t.contains_key("git"))
-            {
+
+            // Inherited requirements live in the root's [workspace.dependencies],
+            // which is updated when the root manifest is processed.
+            if Self::is_workspace_inherited(item) {
+                let package = key.to_string();
+                let line_num = line_index.line_for(section_path, &package);
+                result.skipped_sources.push((
+                    package,
+                    "inherited from workspace".to_string(),
+                    line_num,
+                ));
                 continue;
             }
 
@@ -247,11 +474,8 @@ impl CargoTomlUpdater {
             // Check if package has a pinned version
             if let Some(pinned_version) = options.get_pinned_version(&package) {
                 pinned_deps.push((
-                    package,
-                    prefix,
-                    current_version,
+                    (package, prefix, current_version, registry_name, line_num),
                     pinned_version.to_string(),
-                    line_num,
                 ));
                 continue;
             }
@@ -264,8 +488,71 @@ impl CargoTomlUpdater {
             result.ignored.push((package, version, line_num));
         }
 
-        // Process pinned packages (no registry fetch needed)
-        for (key, prefix, current_version, pinned_version, line_num) in pinned_deps {
+        // Ensure custom registries are created and cached
+        let lookups = deps_to_check
+            .iter()
+            .chain(pinned_deps.iter().map(|(dep, _)| dep));
+        for (_, _, _, registry_name, _) in lookups {
+            if let Some(name) = registry_name
+                && !registry_cache.contains_key(name)
+                && let Some(reg) = Self::create_registry_for_name(name, cargo_toml_registries)
+            {
+                registry_cache.insert(name.clone(), reg);
+            }
+        }
+
+        // A registry we cannot locate would otherwise be looked up on crates.io,
+        // which may hold an unrelated crate of the same name.
+        deps_to_check.retain(
+            |(package, _, _, registry_name, line_num)| match registry_name {
+                Some(name) if !registry_cache.contains_key(name) => {
+                    result.skipped_sources.push((
+                        package.clone(),
+                        format!("registry `{name}` is not configured"),
+                        *line_num,
+                    ));
+                    false
+                }
+                _ => true,
+            },
+        );
+
+        // Process pinned packages; only range pins need a registry lookup
+        let pin_futures: Vec<_> = pinned_deps
+            .iter()
+            .map(|((key, _, _, registry_name, _), pinned_version)| {
+                let effective_registry: Option<&dyn Registry> = match registry_name {
+                    Some(name) => registry_cache
+                        .get(name)
+                        .map(|r| r.as_ref() as &dyn Registry),
+                    None => Some(default_registry),
+                };
+                async move {
+                    match effective_registry {
+                        Some(registry) => resolve_pin(registry, key, pinned_version).await,
+                        // An exact pin needs no lookup, even from an unknown registry.
+                        None if !crate::config::is_range_pin(pinned_version) => {
+                            Ok(pinned_version.clone())
+                        }
+                        None => Err(anyhow!(
+                            "registry `{}` is not configured",
+                            registry_name.as_deref().unwrap_or_default()
+                        )),
+                    }
+                }
+            })
+            .collect();
+        let pin_results = join_all(pin_futures).await;
+        for (((key, prefix, current_version, _, line_num), _), pin_result) in
+            pinned_deps.into_iter().zip(pin_results)
+        {
+            let pinned_version = match pin_result {
+                Ok(version) => version,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", key, e));
+                    continue;
+                }
+            };
             let matched_version = if options.full_precision {
                 pinned_version.clone()
             } else {
@@ -285,16 +572,6 @@ impl CargoTomlUpdater {
             }
         }
 
-        // Ensure custom registries are created and cached
-        for (_, _, _, registry_name, _) in &deps_to_check {
-            if let Some(name) = registry_name
-                && !registry_cache.contains_key(name)
-                && let Some(reg) = Self::create_registry_for_name(name, cargo_toml_registries)
-            {
-                registry_cache.insert(name.clone(), reg);
-            }
-        }
-
         // Fetch all versions in parallel for non-ignored, non-pinned packages
         let version_futures: Vec<_> = deps_to_check
             .iter()
@@ -309,7 +586,7 @@ impl CargoTomlUpdater {
                 };
 
                 async move {
-                    if !is_stable_semver(current_version) {
+                    if !is_stable_semver(current_version) || options.allows_prerelease(key) {
                         effective_registry
                             .get_latest_version_including_prereleases(key)
                             .await
@@ -321,6 +598,12 @@ impl CargoTomlUpdater {
                         effective_registry
                             .get_latest_version_matching(key, &req)
                             .await
+                    } else if let Some(req) = options.semver_compatible_req(current_version) {
+                        // `--strategy compatible`: a bare version is already a
+                        // caret requirement to Cargo, so stay inside it.
+                        effective_registry
+                            .get_latest_version_matching(key, &req)
+                            .await
                     } else {
                         effective_registry.get_latest_version(key).await
                     }
@@ -388,6 +671,23 @@ impl CargoTomlUpdater {
                         }
                     };
 
+                    let latest_version = match msrv {
+                        Some(msrv) => {
+                            let (chosen, note) = Self::msrv_compatible_version(
+                                effective_registry,
+                                &key,
+                                &prefix,
+                                &current_version,
+                                &latest_version,
+                                msrv,
+                            )
+                            .await;
+                            result.warnings.extend(note);
+                            chosen
+                        }
+                        None => latest_version,
+                    };
+
                     // Match the precision of the original version (unless full precision requested)
                     let matched_version = if options.full_precision {
                         latest_version.clone()
@@ -455,6 +755,7 @@ impl CargoTomlUpdater {
         line_index: &CargoTomlLineIndex,
         section_path: &str,
         options: &UpdateOptions,
+        msrv: Option<&RustVersion>,
     ) {
         let table = match deps_item {
             Item::Table(t) => t,
@@ -470,6 +771,7 @@ impl CargoTomlUpdater {
             line_index,
             section_path,
             options,
+            msrv,
         )
         .await;
     }
@@ -553,8 +855,27 @@ impl Updater for CargoTomlUpdater {
         let mut result = UpdateResult::default();
         let line_index = CargoTomlLineIndex::from_content(&content);
 
-        // Extract registries defined in Cargo.toml
-        let cargo_toml_registries = Self::extract_registries(&doc);
+        // Extract registries defined in Cargo.toml, then those of the
+        // project's `.cargo/config.toml` files (`~/.cargo` is consulted last,
+        // per registry, in `create_registry_for_name`).
+        let mut cargo_toml_registries = Self::extract_registries(&doc);
+        if let Some(dir) = path.parent() {
+            for (name, index) in read_project_cargo_registries(dir) {
+                cargo_toml_registries.entry(name).or_insert(index);
+            }
+        }
+        let msrv = if options.respect_msrv {
+            let msrv = Self::project_rust_version(&doc, path);
+            if msrv.is_none() {
+                result.warnings.push(format!(
+                    "--respect-msrv: {} declares no rust-version and rustc is unavailable; MSRV not enforced",
+                    path.display()
+                ));
+            }
+            msrv
+        } else {
+            None
+        };
         // Cache for registry instances (reused across dependency tables)
         let mut registry_cache: HashMap<String, Arc<dyn Registry + Send + Sync>> = HashMap::new();
 
@@ -569,6 +890,7 @@ impl Updater for CargoTomlUpdater {
                 &line_index,
                 "dependencies",
                 &options,
+                msrv.as_ref(),
             )
             .await;
         }
@@ -584,6 +906,7 @@ impl Updater for CargoTomlUpdater {
                 &line_index,
                 "dev-dependencies",
                 &options,
+                msrv.as_ref(),
             )
             .await;
         }
@@ -599,6 +922,7 @@ impl Updater for CargoTomlUpdater {
                 &line_index,
                 "build-dependencies",
                 &options,
+                msrv.as_ref(),
             )
             .await;
         }
@@ -616,6 +940,7 @@ impl Updater for CargoTomlUpdater {
                 &line_index,
                 "workspace.dependencies",
                 &options,
+                msrv.as_ref(),
             )
             .await;
         }
@@ -638,6 +963,7 @@ impl Updater for CargoTomlUpdater {
                             &line_index,
                             &section_path,
                             &options,
+                            msrv.as_ref(),
                         )
                         .await;
                     }
@@ -652,6 +978,7 @@ impl Updater for CargoTomlUpdater {
                             &line_index,
                             &section_path,
                             &options,
+                            msrv.as_ref(),
                         )
                         .await;
                     }
@@ -666,6 +993,7 @@ impl Updater for CargoTomlUpdater {
                             &line_index,
                             &section_path,
                             &options,
+                            msrv.as_ref(),
                         )
                         .await;
                     }
@@ -673,8 +1001,8 @@ impl Updater for CargoTomlUpdater {
             }
         }
 
-        if (!result.updated.is_empty() || !result.pinned.is_empty()) && !options.dry_run {
-            write_file_atomic(path, &doc.to_string())?;
+        if !result.updated.is_empty() || !result.pinned.is_empty() {
+            result.commit_content(path, &content, doc.to_string(), options.dry_run)?;
         }
 
         Ok(result)
@@ -698,10 +1026,34 @@ impl Updater for CargoTomlUpdater {
 
         // Helper to parse dependencies from a table
         let line_index = CargoTomlLineIndex::from_content(&content);
+        // Resolved on the first inherited dependency: most manifests have none.
+        let workspace_deps = std::cell::OnceCell::new();
 
         let parse_table =
             |table: &toml_edit::Table, section_path: &str, deps: &mut Vec<ParsedDependency>| {
                 for (key, item) in table.iter() {
+                    // Inherited entries carry the root's version but are not
+                    // bumpable here: the root definition is the one to change.
+                    if Self::is_workspace_inherited(item) {
+                        let root_version = workspace_deps
+                            .get_or_init(|| Self::inherited_workspace_dependencies(&doc, path))
+                            .as_ref()
+                            .and_then(|t: &Table| t.get(key))
+                            .and_then(Self::get_version);
+                        if let Some(version_req) = root_version {
+                            let (_, version) = Self::parse_version_req(&version_req);
+                            deps.push(ParsedDependency {
+                                name: key.to_string(),
+                                version,
+                                line_number: line_index.line_for(section_path, key),
+                                has_upper_bound: false,
+                                is_bumpable: false,
+                                inherits_workspace: true,
+                            });
+                        }
+                        continue;
+                    }
+
                     // Skip path/git dependencies
                     if let Item::Value(Value::InlineTable(t)) = item
                         && (t.contains_key("path") || t.contains_key("git"))
@@ -723,6 +1075,7 @@ impl Updater for CargoTomlUpdater {
                             line_number: line_num,
                             has_upper_bound: false, // Cargo.toml doesn't use same constraint syntax as Python
                             is_bumpable: true,
+                            inherits_workspace: false,
                         });
                     }
                 }
@@ -772,6 +1125,20 @@ impl Updater for CargoTomlUpdater {
 
         Ok(deps)
     }
+
+    fn unconstrained_dependencies(&self, path: &Path) -> Result<Vec<UnconstrainedDependency>> {
+        // The only Cargo requirement without a version number is the wildcard.
+        Ok(self
+            .parse_dependencies(path)?
+            .into_iter()
+            .filter(|dep| dep.version.is_empty())
+            .map(|dep| UnconstrainedDependency {
+                name: dep.name,
+                spec: "*".to_string(),
+                line_number: dep.line_number,
+            })
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -868,6 +1235,62 @@ tokio = {{ version = "1.28.0", features = ["full"] }}
         assert!(content.contains("1.35.0"));
     }
 
+    #[tokio::test]
+    async fn test_update_cargo_toml_leaves_non_dependency_tables_alone() {
+        let original = r#"[package]
+name = "test-crate"
+version = "1.0.0"
+
+[package.metadata.release]
+version = "1.0.0"
+serde = "1.0.0"
+
+[features]
+default = ["serde"]
+serde = ["dep:serde", "serde-1.0.0"]
+
+[lints.rust]
+serde = "1.0.0"
+
+[dependencies]
+serde = { version = "1.0.0", optional = true }
+"#;
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(file, "{original}").unwrap();
+
+        let registry = MockRegistry::new("crates.io").with_version("serde", "1.0.195");
+
+        let result = CargoTomlUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(
+            fs::read_to_string(file.path()).unwrap(),
+            original.replace(
+                r#"serde = { version = "1.0.0""#,
+                r#"serde = { version = "1.0.195""#
+            )
+        );
+    }
+
+    #[test]
+    fn test_is_dependency_table() {
+        assert!(CargoTomlUpdater::is_dependency_table("dependencies"));
+        assert!(CargoTomlUpdater::is_dependency_table(
+            "workspace.dependencies"
+        ));
+        assert!(CargoTomlUpdater::is_dependency_table(
+            "target.'cfg(unix)'.dev-dependencies"
+        ));
+        assert!(!CargoTomlUpdater::is_dependency_table(
+            "package.metadata.release"
+        ));
+        assert!(!CargoTomlUpdater::is_dependency_table("features"));
+        assert!(!CargoTomlUpdater::is_dependency_table("lints.rust"));
+    }
+
     #[tokio::test]
     async fn test_update_cargo_toml_dry_run() {
         let mut file = NamedTempFile::with_suffix(".toml").unwrap();
@@ -1071,6 +1494,259 @@ tokio = "1.28.0"
         assert!(content.contains("1.35.0"));
     }
 
+    #[tokio::test]
+    async fn test_update_cargo_toml_member_skips_workspace_inherited() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        let original = r#"[package]
+name = "crate-a"
+version.workspace = true
+
+[dependencies]
+serde = { workspace = true, features = ["derive"] }
+tokio.workspace = true
+anyhow = "1.0.0"
+
+[dev-dependencies.tempfile]
+workspace = true
+"#;
+        write!(file, "{}", original).unwrap();
+
+        // Inherited crates are absent from the registry: a lookup would error.
+        let registry = MockRegistry::new("crates.io").with_version("anyhow", "1.0.80");
+
+        let updater = CargoTomlUpdater::new();
+        let options = UpdateOptions::new(true, false);
+
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].0, "anyhow");
+        assert_eq!(result.unchanged, 0);
+        let mut inherited: Vec<_> = result
+            .skipped_sources
+            .iter()
+            .map(|(name, reason, _)| (name.as_str(), reason.as_str()))
+            .collect();
+        inherited.sort();
+        assert_eq!(
+            inherited,
+            vec![
+                ("serde", "inherited from workspace"),
+                ("tempfile", "inherited from workspace"),
+                ("tokio", "inherited from workspace"),
+            ]
+        );
+        assert!(
+            updater
+                .parse_dependencies(file.path())
+                .unwrap()
+                .iter()
+                .all(|d| d.name == "anyhow")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_cargo_toml_workspace_root_with_inherited_package_deps() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[workspace]
+members = ["crate-a"]
+
+[workspace.dependencies]
+serde = "1.0.0"
+
+[package]
+name = "root"
+version = "0.1.0"
+
+[dependencies]
+serde = {{ workspace = true }}
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("crates.io").with_version("serde", "1.0.195");
+
+        let updater = CargoTomlUpdater::new();
+        let options = UpdateOptions::new(false, false);
+
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].3, Some(5));
+        assert_eq!(result.unchanged, 0);
+        assert_eq!(
+            result.skipped_sources,
+            vec![(
+                "serde".to_string(),
+                "inherited from workspace".to_string(),
+                Some(12)
+            )]
+        );
+
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(r#"serde = "1.0.195""#));
+        assert!(content.contains("serde = { workspace = true }"));
+    }
+
+    #[test]
+    fn test_parse_dependencies_marks_workspace_inherited() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            r#"[workspace]
+members = ["crates/a"]
+
+[workspace.dependencies]
+serde = "1.0.200"
+tokio = { version = "^1.40", features = ["full"] }
+"#,
+        )
+        .unwrap();
+        let member = dir.path().join("crates/a");
+        fs::create_dir_all(&member).unwrap();
+        fs::write(
+            member.join("Cargo.toml"),
+            r#"[package]
+name = "a"
+
+[dependencies]
+serde = { workspace = true }
+tokio.workspace = true
+anyhow = "1.0.0"
+missing = { workspace = true }
+"#,
+        )
+        .unwrap();
+
+        let deps = CargoTomlUpdater::new()
+            .parse_dependencies(&member.join("Cargo.toml"))
+            .unwrap();
+        let summary: Vec<_> = deps
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.version.as_str(),
+                    d.is_bumpable,
+                    d.inherits_workspace,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("serde", "1.0.200", false, true),
+                ("tokio", "1.40", false, true),
+                ("anyhow", "1.0.0", true, false),
+            ]
+        );
+        assert_eq!(deps[1].line_number, Some(6));
+    }
+
+    fn msrv_registry() -> MockRegistry {
+        MockRegistry::new("crates.io")
+            .with_version("serde", "1.0.210")
+            .with_rust_version("serde", "1.0.190", "1.56")
+            .with_rust_version("serde", "1.0.200", "1.61")
+            .with_rust_version("serde", "1.0.205", "1.74")
+            .with_rust_version("serde", "1.0.210", "1.80")
+    }
+
+    #[tokio::test]
+    async fn test_update_cargo_toml_respect_msrv_picks_compatible_version() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[package]
+name = "test-crate"
+version = "0.1.0"
+rust-version = "1.70"
+
+[dependencies]
+serde = "1.0.190"
+"#
+        )
+        .unwrap();
+
+        let updater = CargoTomlUpdater::new();
+        let result = updater
+            .update(
+                file.path(),
+                &msrv_registry(),
+                UpdateOptions::new(true, false).with_respect_msrv(true),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].2, "1.0.200");
+        assert_eq!(
+            result.warnings,
+            vec![
+                "serde: 1.0.210 requires Rust 1.80 but the project targets 1.70; using 1.0.200"
+                    .to_string()
+            ]
+        );
+
+        // Without the flag the latest version wins.
+        let result = updater
+            .update(
+                file.path(),
+                &msrv_registry(),
+                UpdateOptions::new(true, false),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.updated[0].2, "1.0.210");
+        assert!(result.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_cargo_toml_respect_msrv_reads_workspace_rust_version() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"member\"]\n\n[workspace.package]\nrust-version = \"1.58\"\n",
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("member")).unwrap();
+        let manifest = dir.path().join("member/Cargo.toml");
+        fs::write(
+            &manifest,
+            "[package]\nname = \"member\"\nversion = \"0.1.0\"\nrust-version.workspace = true\n\n[dependencies]\nserde = \"1.0.190\"\n",
+        )
+        .unwrap();
+
+        let result = CargoTomlUpdater::new()
+            .update(
+                &manifest,
+                &msrv_registry(),
+                UpdateOptions::new(false, false).with_respect_msrv(true),
+            )
+            .await
+            .unwrap();
+
+        // Nothing newer than 1.0.190 builds with Rust 1.58.
+        assert!(result.updated.is_empty());
+        assert_eq!(result.unchanged, 1);
+        assert!(result.warnings[0].ends_with("keeping 1.0.190"));
+        assert!(
+            fs::read_to_string(&manifest)
+                .unwrap()
+                .contains(r#"serde = "1.0.190""#)
+        );
+    }
+
     #[tokio::test]
     async fn test_update_cargo_toml_preserves_formatting() {
         let mut file = NamedTempFile::with_suffix(".toml").unwrap();
@@ -1222,6 +1898,216 @@ registry = "my-registry"
         assert_eq!(CargoTomlUpdater::get_registry_name(&item), None);
     }
 
+    #[test]
+    fn test_get_registry_name_crates_io_is_default() {
+        let content = r#"
+[dependencies]
+serde = { version = "1.0.0", registry = "crates-io" }
+"#;
+        let doc: DocumentMut = content.parse().unwrap();
+        let serde = doc.get("dependencies").unwrap().get("serde").unwrap();
+        assert_eq!(CargoTomlUpdater::get_registry_name(serde), None);
+    }
+
+    #[tokio::test]
+    async fn test_update_skips_git_path_and_unconfigured_registry_deps() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[dependencies]
+serde = "1.0.0"
+local = {{ path = "../local" }}
+forked = {{ git = "https://github.com/user/forked", version = "0.3" }}
+internal = {{ version = "2.0.0", registry = "nowhere" }}
+
+[dependencies.vendored]
+path = "vendor/vendored"
+"#
+        )
+        .unwrap();
+
+        // Same-named crates on crates.io must not be picked up for these.
+        let registry = MockRegistry::new("crates.io")
+            .with_version("serde", "1.0.195")
+            .with_version("local", "9.0.0")
+            .with_version("forked", "9.0.0")
+            .with_version("internal", "9.0.0")
+            .with_version("vendored", "9.0.0");
+        let updater = CargoTomlUpdater::new();
+        let options = UpdateOptions::new(false, false);
+
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(
+            result.updated,
+            vec![(
+                "serde".to_string(),
+                "1.0.0".to_string(),
+                "1.0.195".to_string(),
+                Some(2)
+            )]
+        );
+        assert_eq!(
+            result.skipped_sources,
+            vec![
+                ("local".to_string(), "path dependency".to_string(), Some(3)),
+                ("forked".to_string(), "git dependency".to_string(), Some(4)),
+                (
+                    "vendored".to_string(),
+                    "path dependency".to_string(),
+                    Some(7)
+                ),
+                (
+                    "internal".to_string(),
+                    "registry `nowhere` is not configured".to_string(),
+                    Some(5)
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_explicit_crates_io_registry_uses_default_registry() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[dependencies]
+serde = {{ version = "1.0.0", registry = "crates-io" }}
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("crates.io").with_version("serde", "1.0.195");
+        let updater = CargoTomlUpdater::new();
+        let options = UpdateOptions::new(false, false);
+
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1, "errors: {:?}", result.errors);
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(r#"serde = { version = "1.0.195", registry = "crates-io" }"#));
+    }
+
+    #[tokio::test]
+    async fn test_update_alt_registry_routes_to_configured_index() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let alt = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/crates/internal-crate"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(
+                    r#"{"crate": {"max_stable_version": "2.1.0"}, "versions": []}"#,
+                ),
+            )
+            .expect(1)
+            .mount(&alt)
+            .await;
+
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[registries]
+my-alt = {{ index = "sparse+{}/index/" }}
+
+[dependencies]
+internal-crate = {{ version = "2.0.0", registry = "my-alt" }}
+"#,
+            alt.uri()
+        )
+        .unwrap();
+
+        // The default registry does not know the crate, so an update can only
+        // come from the alternate index.
+        let registry = MockRegistry::new("crates.io");
+        let updater = CargoTomlUpdater::new();
+        let options = UpdateOptions::new(false, false);
+
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(
+            result.updated,
+            vec![(
+                "internal-crate".to_string(),
+                "2.0.0".to_string(),
+                "2.1.0".to_string(),
+                Some(5)
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_alt_registry_from_project_cargo_config_sends_token() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let alt = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/crates/corp-utils"))
+            .and(header("authorization", "corp-secret"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(
+                    r#"{"crate": {"max_stable_version": "1.4.0"}, "versions": []}"#,
+                ),
+            )
+            .expect(1)
+            .mount(&alt)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join(".cargo")).unwrap();
+        fs::write(
+            dir.path().join(".cargo").join("config.toml"),
+            format!(
+                "[registries.upd-test-corp]\nindex = \"sparse+{}/index/\"\n",
+                alt.uri()
+            ),
+        )
+        .unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        fs::write(
+            &manifest,
+            r#"[dependencies]
+corp-utils = { version = "1.2.0", registry = "upd-test-corp" }
+serde = "1.0.100"
+"#,
+        )
+        .unwrap();
+
+        // SAFETY: the variable name is unique to this test
+        unsafe {
+            std::env::set_var("CARGO_REGISTRIES_UPD_TEST_CORP_TOKEN", "corp-secret");
+        }
+        let registry = MockRegistry::new("crates.io").with_version("serde", "1.0.200");
+        let result = CargoTomlUpdater::new()
+            .update(&manifest, &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+        // SAFETY: see above
+        unsafe {
+            std::env::remove_var("CARGO_REGISTRIES_UPD_TEST_CORP_TOKEN");
+        }
+
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        let content = fs::read_to_string(&manifest).unwrap();
+        assert!(
+            content.contains(r#"corp-utils = { version = "1.4.0", registry = "upd-test-corp" }"#)
+        );
+        assert!(content.contains(r#"serde = "1.0.200""#));
+    }
+
     #[test]
     fn test_sparse_index_to_api_url() {
         // Sparse prefix
@@ -1285,6 +2171,9 @@ anyhow = "1.0.0"
             ignore: vec!["tokio".to_string()],
             pin: std::collections::HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = CargoTomlUpdater::new();
@@ -1338,6 +2227,9 @@ tokio = "1.0.0"
             ignore: Vec::new(),
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = CargoTomlUpdater::new();
@@ -1362,6 +2254,54 @@ tokio = "1.0.0"
         assert!(content.contains("tokio = \"1.37.0\""));
     }
 
+    #[tokio::test]
+    async fn test_update_cargo_toml_range_pin_resolves_within_range() {
+        use crate::config::UpdConfig;
+
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[package]
+name = "test"
+version = "0.1.0"
+
+[dependencies]
+clap = "4.0.0"
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("crates.io")
+            .with_version("clap", "5.1.0")
+            .with_constrained("clap", ">=4, <5", "4.5.20");
+
+        let mut pin = std::collections::HashMap::new();
+        pin.insert("clap".to_string(), ">=4, <5".to_string());
+        let config = UpdConfig {
+            exclude: Vec::new(),
+            ignore: Vec::new(),
+            pin,
+            cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
+        };
+
+        let updater = CargoTomlUpdater::new();
+        let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
+
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert!(result.updated.is_empty());
+        assert_eq!(result.pinned.len(), 1);
+        assert_eq!(result.pinned[0].2, "4.5.20");
+        let content = std::fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains("clap = \"4.5.20\""));
+    }
+
     #[tokio::test]
     async fn test_update_cargo_toml_duplicate_dependency_names_keep_occurrence_line_numbers() {
         use crate::config::UpdConfig;
@@ -1389,6 +2329,9 @@ serde = "1.0.1"
             ignore: Vec::new(),
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = CargoTomlUpdater::new();
@@ -1443,6 +2386,9 @@ anyhow = "1.0.0"
             ignore: vec!["tokio".to_string()],
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = CargoTomlUpdater::new();
@@ -1496,6 +2442,9 @@ tempfile = "3.0.0"
             ignore: vec!["tempfile".to_string()],
             pin: std::collections::HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = CargoTomlUpdater::new();
@@ -1551,6 +2500,9 @@ anyhow = "=1.0.0"
             ignore: Vec::new(),
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = CargoTomlUpdater::new();
@@ -1598,6 +2550,9 @@ tokio = {{ version = "1.0.0", features = ["full"] }}
             ignore: vec!["serde".to_string()],
             pin: std::collections::HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = CargoTomlUpdater::new();
@@ -1619,4 +2574,34 @@ tokio = {{ version = "1.0.0", features = ["full"] }}
         assert!(content.contains("serde = { version = \"1.0.0\""));
         assert!(content.contains("tokio = { version = \"1.37.0\""));
     }
+
+    #[test]
+    fn test_unconstrained_dependencies_flags_wildcard() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"[package]
+name = "demo"
+version = "0.1.0"
+
+[dependencies]
+serde = "*"
+tokio = {{ version = "1", features = ["full"] }}
+local = {{ path = "../local" }}
+
+[dev-dependencies]
+rand = {{ version = "*" }}
+"#
+        )
+        .unwrap();
+
+        let deps = CargoTomlUpdater::new()
+            .unconstrained_dependencies(file.path())
+            .unwrap();
+        let found: Vec<(&str, Option<usize>)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.line_number))
+            .collect();
+        assert_eq!(found, vec![("serde", Some(6)), ("rand", Some(11))]);
+    }
 }
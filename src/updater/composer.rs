@@ -0,0 +1,562 @@
+use super::{
+    FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
+    read_file_safe,
+};
+use crate::align::compare_versions;
+use crate::registry::{Registry, composer_version};
+use crate::updater::Lang;
+use crate::version::match_version_precision;
+use anyhow::Result;
+use futures::future::join_all;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+pub struct ComposerUpdater;
+
+const DEPENDENCY_SECTIONS: [&str; 2] = ["require", "require-dev"];
+
+/// The `--group` name of a dependency section. `None` for `require`.
+fn section_group(section: &str) -> Option<&str> {
+    match section {
+        "require-dev" => Some("dev"),
+        _ => None,
+    }
+}
+
+/// A Composer version with a pre-release stability (`3.0.0-RC1`, `2.0beta`).
+fn is_composer_prerelease(version: &str) -> bool {
+    composer_version(version).is_some_and(|v| !v.pre.is_empty())
+}
+
+/// A single-version constraint the updater can rewrite, split into its
+/// operator and version. Platform requirements (`php`, `ext-json`), branch
+/// aliases and multi-part ranges (`^1.0 || ^2.0`, `>=1.0 <2.0`) are `None`.
+fn parse_requirement(package: &str, constraint: &str) -> Option<(String, String)> {
+    // Packages are always `vendor/name`; bare names are platform packages.
+    if !package.contains('/') {
+        return None;
+    }
+    let constraint = constraint.trim();
+    if constraint.is_empty()
+        || constraint.starts_with("dev-")
+        || constraint.contains(|c: char| c.is_whitespace() || "|,*@".contains(c))
+    {
+        return None;
+    }
+
+    for prefix in [">=", "^", "~", ">", "="] {
+        if let Some(version) = constraint.strip_prefix(prefix) {
+            return composer_version(version).map(|_| (prefix.to_string(), version.to_string()));
+        }
+    }
+    composer_version(constraint).map(|_| (String::new(), constraint.to_string()))
+}
+
+/// 1-based line of each `"vendor/name":` entry, per dependency section.
+fn line_index(content: &str) -> HashMap<(String, String), usize> {
+    let section_re = Regex::new(r#""(require|require-dev)"\s*:\s*\{"#).expect("Invalid pattern");
+    let entry_re = Regex::new(r#""([^"]+)"\s*:"#).expect("Invalid pattern");
+    let mut lines = HashMap::new();
+    let mut section: Option<String> = None;
+
+    for (idx, line) in content.lines().enumerate() {
+        if let Some(caps) = section_re.captures(line) {
+            section = Some(caps[1].to_string());
+            continue;
+        }
+        if let Some(current) = &section {
+            if let Some(caps) = entry_re.captures(line) {
+                lines
+                    .entry((current.clone(), caps[1].to_string()))
+                    .or_insert(idx + 1);
+            }
+            if line.contains('}') {
+                section = None;
+            }
+        }
+    }
+    lines
+}
+
+impl ComposerUpdater {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn update_version_in_content(
+        &self,
+        content: &str,
+        package: &str,
+        old_constraint: &str,
+        new_constraint: &str,
+    ) -> String {
+        let pattern = format!(
+            r#""({})"(\s*:\s*)"{}""#,
+            regex::escape(package),
+            regex::escape(old_constraint)
+        );
+        let re = Regex::new(&pattern).expect("Invalid pattern");
+        re.replace_all(content, |caps: &regex::Captures| {
+            format!(r#""{}"{}"{}""#, &caps[1], &caps[2], new_constraint)
+        })
+        .to_string()
+    }
+}
+
+impl Default for ComposerUpdater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Updater for ComposerUpdater {
+    async fn update(
+        &self,
+        path: &Path,
+        registry: &dyn Registry,
+        options: UpdateOptions,
+    ) -> Result<UpdateResult> {
+        let content = read_file_safe(path)?;
+        let json: Value = serde_json::from_str(&content)?;
+        let mut result = UpdateResult::default();
+        let mut new_content = content.clone();
+        let lines = line_index(&content);
+        let line_for = |section: &str, package: &str| {
+            lines
+                .get(&(section.to_string(), package.to_string()))
+                .copied()
+        };
+
+        // (section, package, constraint, prefix, current version)
+        let mut packages_to_check: Vec<(String, String, String, String, String)> = Vec::new();
+
+        for section in DEPENDENCY_SECTIONS {
+            let group_filtered = options.is_group_filtered_out(section_group(section));
+            let Some(deps) = json.get(section).and_then(|v| v.as_object()) else {
+                continue;
+            };
+            for (package, value) in deps {
+                let Some(constraint) = value.as_str() else {
+                    continue;
+                };
+                let Some((prefix, current_version)) = parse_requirement(package, constraint) else {
+                    continue;
+                };
+
+                if group_filtered || options.is_package_filtered_out(package) {
+                    result.unchanged += 1;
+                    continue;
+                }
+                if options.should_ignore(package) {
+                    result.ignored.push((
+                        package.clone(),
+                        current_version,
+                        line_for(section, package),
+                    ));
+                    continue;
+                }
+
+                if let Some(warning) = options.range_pin_warning(package) {
+                    result.warnings.push(warning);
+                    continue;
+                }
+
+                if let Some(pinned_version) = options.get_pinned_version(package) {
+                    let matched_version = if options.full_precision {
+                        pinned_version.to_string()
+                    } else {
+                        match_version_precision(&current_version, pinned_version)
+                    };
+                    if matched_version != current_version {
+                        result.pinned.push((
+                            package.clone(),
+                            current_version,
+                            matched_version.clone(),
+                            line_for(section, package),
+                        ));
+                        new_content = self.update_version_in_content(
+                            &new_content,
+                            package,
+                            constraint,
+                            &format!("{}{}", prefix, matched_version),
+                        );
+                    } else {
+                        result.unchanged += 1;
+                    }
+                    continue;
+                }
+
+                packages_to_check.push((
+                    section.to_string(),
+                    package.clone(),
+                    constraint.to_string(),
+                    prefix,
+                    current_version,
+                ));
+            }
+        }
+
+        // Stay on pre-releases only when the constraint already is one.
+        let version_futures: Vec<_> = packages_to_check
+            .iter()
+            .map(|(_, package, constraint, prefix, current_version)| async {
+                if is_composer_prerelease(current_version) || options.allows_prerelease(package) {
+                    registry
+                        .get_latest_version_including_prereleases(package)
+                        .await
+                } else if matches!(prefix.as_str(), "^" | "~") {
+                    // Keep within the range the caret/tilde already allows.
+                    registry
+                        .get_latest_version_matching(package, constraint)
+                        .await
+                } else if let Some(req) = options.semver_compatible_req(current_version) {
+                    registry.get_latest_version_matching(package, &req).await
+                } else {
+                    registry.get_latest_version(package).await
+                }
+            })
+            .collect();
+
+        let version_results = join_all(version_futures).await;
+
+        for ((section, package, constraint, prefix, current_version), version_result) in
+            packages_to_check.into_iter().zip(version_results)
+        {
+            let latest_version = match version_result {
+                Ok(v) => v,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", package, e));
+                    continue;
+                }
+            };
+
+            let current_is_prerelease = is_composer_prerelease(&current_version);
+            if current_is_prerelease && !is_composer_prerelease(&latest_version) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            let (outcome, note) = crate::updater::apply_cooldown(
+                registry,
+                &package,
+                &current_version,
+                &latest_version,
+                None,
+                current_is_prerelease,
+                &options,
+            )
+            .await;
+            if let Some(msg) = note {
+                options.note_cooldown_unavailable(&msg);
+            }
+            let (latest_version, held_back_record) = match outcome {
+                crate::updater::CooldownOutcome::Unchanged(v) => (v, None),
+                crate::updater::CooldownOutcome::HeldBack {
+                    chosen,
+                    skipped_version,
+                    skipped_published_at,
+                } => (chosen, Some((skipped_version, skipped_published_at))),
+                crate::updater::CooldownOutcome::Skipped {
+                    skipped_version,
+                    skipped_published_at,
+                } => {
+                    result.skipped_by_cooldown.push((
+                        package,
+                        current_version,
+                        skipped_version,
+                        skipped_published_at,
+                    ));
+                    continue;
+                }
+            };
+
+            let matched_version = if options.full_precision {
+                latest_version.clone()
+            } else {
+                match_version_precision(&current_version, &latest_version)
+            };
+            if matched_version == current_version {
+                result.unchanged += 1;
+                continue;
+            }
+            if compare_versions(&matched_version, &current_version, Lang::Php)
+                != std::cmp::Ordering::Greater
+            {
+                result.warnings.push(downgrade_warning(
+                    &package,
+                    &matched_version,
+                    &current_version,
+                ));
+                result.unchanged += 1;
+                continue;
+            }
+            if !options.allows_bump(&current_version, &matched_version) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            result.updated.push((
+                package.clone(),
+                current_version.clone(),
+                matched_version.clone(),
+                line_for(&section, &package),
+            ));
+            if let Some((skipped_version, skipped_published_at)) = held_back_record {
+                result.held_back.push((
+                    package.clone(),
+                    current_version,
+                    matched_version.clone(),
+                    skipped_version,
+                    skipped_published_at,
+                ));
+            }
+            new_content = self.update_version_in_content(
+                &new_content,
+                &package,
+                &constraint,
+                &format!("{}{}", prefix, matched_version),
+            );
+        }
+
+        if !result.updated.is_empty() || !result.pinned.is_empty() {
+            result.commit_content(path, &content, new_content, options.dry_run)?;
+        }
+
+        Ok(result)
+    }
+
+    fn handles(&self, file_type: FileType) -> bool {
+        file_type == FileType::ComposerJson
+    }
+
+    fn parse_dependencies(&self, path: &Path) -> Result<Vec<ParsedDependency>> {
+        let content = read_file_safe(path)?;
+        let json: Value = serde_json::from_str(&content)?;
+        let lines = line_index(&content);
+        let mut deps = Vec::new();
+
+        for section in DEPENDENCY_SECTIONS {
+            let Some(section_deps) = json.get(section).and_then(|v| v.as_object()) else {
+                continue;
+            };
+            for (package, value) in section_deps {
+                let Some((_, version)) = value
+                    .as_str()
+                    .and_then(|constraint| parse_requirement(package, constraint))
+                else {
+                    continue;
+                };
+                deps.push(ParsedDependency {
+                    name: package.clone(),
+                    version,
+                    line_number: lines.get(&(section.to_string(), package.clone())).copied(),
+                    has_upper_bound: false,
+                    is_bumpable: true,
+                    inherits_workspace: false,
+                });
+            }
+        }
+
+        Ok(deps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MockRegistry;
+    use std::fs;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn composer_file(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(file, "{}", content).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_parse_requirement() {
+        assert_eq!(
+            parse_requirement("monolog/monolog", "^2.9"),
+            Some(("^".to_string(), "2.9".to_string()))
+        );
+        assert_eq!(
+            parse_requirement("symfony/console", ">=5.4.0"),
+            Some((">=".to_string(), "5.4.0".to_string()))
+        );
+        assert_eq!(
+            parse_requirement("acme/lib", "1.2.3"),
+            Some((String::new(), "1.2.3".to_string()))
+        );
+        assert_eq!(parse_requirement("php", ">=8.1"), None);
+        assert_eq!(parse_requirement("ext-json", "*"), None);
+        assert_eq!(parse_requirement("acme/lib", "^1.0 || ^2.0"), None);
+        assert_eq!(parse_requirement("acme/lib", "dev-main"), None);
+        assert_eq!(parse_requirement("acme/lib", "1.0.*"), None);
+    }
+
+    #[tokio::test]
+    async fn test_update_composer_json_preserves_operators() {
+        let file = composer_file(
+            r#"{
+    "require": {
+        "php": ">=8.1",
+        "ext-json": "*",
+        "monolog/monolog": "^2.9",
+        "guzzlehttp/guzzle": "~7.4.0"
+    },
+    "require-dev": {
+        "phpunit/phpunit": ">=9.5"
+    }
+}"#,
+        );
+
+        let registry = MockRegistry::new("packagist")
+            .with_version("monolog/monolog", "3.7.0")
+            .with_constrained("monolog/monolog", "^2.9", "2.10.0")
+            .with_constrained("guzzlehttp/guzzle", "~7.4.0", "7.4.5")
+            .with_version("phpunit/phpunit", "11.4.3");
+
+        let result = ComposerUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 3);
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(r#""monolog/monolog": "^2.10""#));
+        assert!(content.contains(r#""guzzlehttp/guzzle": "~7.4.5""#));
+        assert!(content.contains(r#""phpunit/phpunit": ">=11.4""#));
+        assert!(content.contains(r#""php": ">=8.1""#));
+        assert!(content.contains(r#""ext-json": "*""#));
+
+        let monolog = result
+            .updated
+            .iter()
+            .find(|(name, ..)| name == "monolog/monolog")
+            .unwrap();
+        assert_eq!(monolog.3, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_update_composer_json_prerelease_stays_on_prerelease() {
+        let file = composer_file(
+            r#"{
+    "require": {
+        "acme/beta": "^2.0.0-beta1",
+        "acme/stable": "^1.0"
+    }
+}"#,
+        );
+
+        let registry = MockRegistry::new("packagist")
+            .with_prerelease("acme/beta", "1.9.0", "2.0.0-beta3")
+            .with_prerelease("acme/stable", "1.1", "2.0.0-RC1");
+
+        let result = ComposerUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(r#""acme/beta": "^2.0.0-beta3""#));
+        assert!(content.contains(r#""acme/stable": "^1.1""#));
+        assert_eq!(result.updated.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_update_composer_json_with_config_ignore_and_pin() {
+        use crate::config::UpdConfig;
+        use std::sync::Arc;
+
+        let file = composer_file(
+            r#"{
+    "require": {
+        "monolog/monolog": "^2.9",
+        "symfony/console": "^5.4"
+    }
+}"#,
+        );
+
+        let registry = MockRegistry::new("packagist")
+            .with_version("monolog/monolog", "3.7.0")
+            .with_version("symfony/console", "7.1.0");
+
+        let config = UpdConfig {
+            ignore: vec!["monolog/monolog".to_string()],
+            pin: [("symfony/console".to_string(), "6.4.0".to_string())]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
+
+        let result = ComposerUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert_eq!(result.ignored.len(), 1);
+        assert_eq!(result.pinned.len(), 1);
+        assert!(result.updated.is_empty());
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(r#""monolog/monolog": "^2.9""#));
+        assert!(content.contains(r#""symfony/console": "^6.4""#));
+    }
+
+    #[tokio::test]
+    async fn test_update_composer_json_dev_group_filter() {
+        let file = composer_file(
+            r#"{
+    "require": {
+        "monolog/monolog": "^2.9"
+    },
+    "require-dev": {
+        "phpunit/phpunit": "^9.5"
+    }
+}"#,
+        );
+
+        let registry = MockRegistry::new("packagist")
+            .with_version("monolog/monolog", "2.10.0")
+            .with_version("phpunit/phpunit", "9.6.21");
+
+        let options = UpdateOptions::new(false, false).with_groups(vec!["dev".to_string()]);
+        let result = ComposerUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].0, "phpunit/phpunit");
+        assert_eq!(result.unchanged, 1);
+    }
+
+    #[test]
+    fn test_parse_dependencies() {
+        let file = composer_file(
+            r#"{
+    "require": {
+        "php": "^8.1",
+        "monolog/monolog": "^2.9"
+    },
+    "require-dev": {
+        "phpunit/phpunit": "9.6.0"
+    }
+}"#,
+        );
+
+        let deps = ComposerUpdater::new()
+            .parse_dependencies(file.path())
+            .unwrap();
+        assert_eq!(deps.len(), 2);
+        assert_eq!(deps[0].name, "monolog/monolog");
+        assert_eq!(deps[0].version, "2.9");
+        assert_eq!(deps[0].line_number, Some(4));
+        assert_eq!(deps[1].line_number, Some(7));
+    }
+}
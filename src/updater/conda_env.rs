@@ -0,0 +1,354 @@
+use super::{
+    FileType, ParsedDependency, RequirementsUpdater, UnconstrainedDependency, UpdateOptions,
+    UpdateResult, Updater, read_file_safe,
+};
+use crate::registry::Registry;
+use anyhow::Result;
+use regex::Regex;
+use std::ops::Range;
+use std::path::Path;
+
+/// Reason recorded for conda-channel packages, which need a conda registry.
+const CONDA_CHANNEL: &str = "conda package, no conda registry support";
+
+pub struct CondaEnvUpdater {
+    /// The `- pip:` list holds requirement lines, so it is parsed and resolved
+    /// exactly like a requirements file.
+    requirements: RequirementsUpdater,
+    /// Name of a conda spec such as `numpy=1.24` or `conda-forge::numpy`.
+    conda_name_re: Regex,
+}
+
+/// A line of the nested `- pip:` list.
+struct PipLine {
+    /// 0-based line index in the file
+    line_idx: usize,
+    /// Byte range of the requirement text, without quotes or comment
+    range: Range<usize>,
+}
+
+/// What the `dependencies:` list of an `environment.yml` contains.
+#[derive(Default)]
+struct CondaEnvScan {
+    /// Lines of the `- pip:` list.
+    pip: Vec<PipLine>,
+    /// Conda-channel packages; detected and reported, never resolved.
+    conda: Vec<(String, Option<usize>)>,
+}
+
+impl CondaEnvScan {
+    /// The `- pip:` list as requirements-file content: each requirement on the
+    /// line it has in `content`, every other line blank, so line numbers carry
+    /// over unchanged.
+    fn pip_requirements(&self, content: &str) -> String {
+        let mut lines = vec![""; content.lines().count()];
+        for pip in &self.pip {
+            lines[pip.line_idx] = &content[pip.range.clone()];
+        }
+        lines.join("\n")
+    }
+}
+
+/// Strip a YAML comment (` # ...`) from a sequence item.
+fn strip_comment(item: &str) -> &str {
+    if item.starts_with('#') {
+        return "";
+    }
+    match item.find(" #").or_else(|| item.find("\t#")) {
+        Some(idx) => &item[..idx],
+        None => item,
+    }
+}
+
+impl CondaEnvUpdater {
+    pub fn new() -> Self {
+        let conda_name_re =
+            Regex::new(r"^(?:[^\s:]+::)?([A-Za-z0-9_][A-Za-z0-9._-]*)").expect("Invalid regex");
+        Self {
+            requirements: RequirementsUpdater::new(),
+            conda_name_re,
+        }
+    }
+
+    /// Walk the top-level `dependencies:` sequence line by line, splitting
+    /// conda specs from the requirements of its `- pip:` list.
+    fn scan(&self, content: &str) -> CondaEnvScan {
+        let mut scan = CondaEnvScan::default();
+        let mut in_dependencies = false;
+        // Indentation of the `- pip:` item while inside its list.
+        let mut pip_indent: Option<usize> = None;
+        let mut offset = 0;
+
+        for (idx, raw_line) in content.split_inclusive('\n').enumerate() {
+            let line_start = offset;
+            offset += raw_line.len();
+            let line = raw_line.trim_end_matches(['\n', '\r']);
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+
+            let item = trimmed
+                .strip_prefix('-')
+                .filter(|rest| rest.is_empty() || rest.starts_with([' ', '\t']));
+            let Some(item) = item else {
+                // Any other top-level key ends the list.
+                if indent == 0 {
+                    in_dependencies = strip_comment(trimmed).trim_end() == "dependencies:";
+                    pip_indent = None;
+                }
+                continue;
+            };
+            if !in_dependencies {
+                continue;
+            }
+
+            let text = strip_comment(item.trim_start()).trim_end();
+            let text_start = line_start + (line.len() - item.trim_start().len());
+
+            if let Some(pip) = pip_indent {
+                if indent > pip {
+                    scan.pip.push(PipLine {
+                        line_idx: idx,
+                        range: unquoted(text, text_start),
+                    });
+                    continue;
+                }
+                pip_indent = None;
+            }
+
+            if text == "pip:" {
+                pip_indent = Some(indent);
+                continue;
+            }
+            let text = text.trim_matches(['"', '\'']);
+            if let Some(caps) = self.conda_name_re.captures(text) {
+                scan.conda
+                    .push((caps.get(1).unwrap().as_str().to_string(), Some(idx + 1)));
+            }
+        }
+
+        scan
+    }
+}
+
+/// Byte range of `text` (starting at `start`) without its enclosing quotes.
+fn unquoted(text: &str, start: usize) -> Range<usize> {
+    match text.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let inner = &text[1..];
+            let len = inner.find(quote).unwrap_or(inner.len());
+            start + 1..start + 1 + len
+        }
+        _ => start..start + text.len(),
+    }
+}
+
+impl Default for CondaEnvUpdater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Updater for CondaEnvUpdater {
+    async fn update(
+        &self,
+        path: &Path,
+        registry: &dyn Registry,
+        options: UpdateOptions,
+    ) -> Result<UpdateResult> {
+        let content = read_file_safe(path)?;
+        let scan = self.scan(&content);
+
+        let (mut result, new_lines) = self
+            .requirements
+            .update_lines(path, &scan.pip_requirements(&content), registry, &options)
+            .await;
+        for (name, line_num) in scan.conda {
+            result
+                .skipped_sources
+                .push((name, CONDA_CHANNEL.to_string(), line_num));
+        }
+
+        if let Some(new_lines) = new_lines {
+            // Splice from the end so earlier ranges stay valid.
+            let mut new_content = content.clone();
+            for pip in scan.pip.iter().rev() {
+                new_content.replace_range(pip.range.clone(), &new_lines[pip.line_idx]);
+            }
+            result.commit_content(path, &content, new_content, options.dry_run)?;
+        }
+
+        Ok(result)
+    }
+
+    fn handles(&self, file_type: FileType) -> bool {
+        file_type == FileType::CondaEnv
+    }
+
+    fn parse_dependencies(&self, path: &Path) -> Result<Vec<ParsedDependency>> {
+        let content = read_file_safe(path)?;
+        let pip = self.scan(&content).pip_requirements(&content);
+        Ok(self.requirements.dependencies_in(&pip))
+    }
+
+    fn unconstrained_dependencies(&self, path: &Path) -> Result<Vec<UnconstrainedDependency>> {
+        let content = read_file_safe(path)?;
+        let pip = self.scan(&content).pip_requirements(&content);
+        Ok(RequirementsUpdater::unconstrained_in(&pip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MockRegistry;
+    use std::fs;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn environment_yml(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{}", content).unwrap();
+        file
+    }
+
+    const ENVIRONMENT_YML: &str = "name: analysis
+channels:
+  - conda-forge
+dependencies:
+  - python=3.11
+  - conda-forge::numpy=1.24  # array maths
+  - pip
+  - pip:
+      - requests==2.31.0  # http
+      - \"flask[async]>=2.0.0,<4\"
+      - rich
+      - -r requirements.txt
+      - mylib @ git+https://github.com/me/mylib
+  - pandas>=2.0
+variables:
+  - notes: not a dependency
+";
+
+    #[test]
+    fn test_scan_splits_conda_and_pip_entries() {
+        let scan = CondaEnvUpdater::new().scan(ENVIRONMENT_YML);
+
+        let conda: Vec<_> = scan.conda.iter().map(|(n, l)| (n.as_str(), *l)).collect();
+        assert_eq!(
+            conda,
+            vec![
+                ("python", Some(5)),
+                ("numpy", Some(6)),
+                ("pip", Some(7)),
+                ("pandas", Some(14)),
+            ]
+        );
+
+        let pip: Vec<_> = scan
+            .pip
+            .iter()
+            .map(|p| (&ENVIRONMENT_YML[p.range.clone()], p.line_idx + 1))
+            .collect();
+        assert_eq!(
+            pip,
+            vec![
+                ("requests==2.31.0", 9),
+                ("flask[async]>=2.0.0,<4", 10),
+                ("rich", 11),
+                ("-r requirements.txt", 12),
+                ("mylib @ git+https://github.com/me/mylib", 13),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dependencies_follow_requirements_parsing() {
+        let file = environment_yml(ENVIRONMENT_YML);
+        let updater = CondaEnvUpdater::new();
+
+        let deps: Vec<_> = updater
+            .parse_dependencies(file.path())
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.name, d.version, d.line_number))
+            .collect();
+        assert_eq!(
+            deps,
+            vec![
+                ("requests".to_string(), "2.31.0".to_string(), Some(9)),
+                ("flask".to_string(), "2.0.0".to_string(), Some(10)),
+            ]
+        );
+
+        let unconstrained = updater.unconstrained_dependencies(file.path()).unwrap();
+        assert_eq!(unconstrained.len(), 1);
+        assert_eq!(unconstrained[0].name, "rich");
+        assert_eq!(unconstrained[0].line_number, Some(11));
+    }
+
+    #[tokio::test]
+    async fn test_update_pip_list_and_report_conda_packages() {
+        let file = environment_yml(ENVIRONMENT_YML);
+        let registry = MockRegistry::new("pypi")
+            .with_version("requests", "2.32.3")
+            .with_constrained("flask", ">=2.0.0,<4", "3.1.0");
+
+        let result = CondaEnvUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 2, "errors: {:?}", result.errors);
+        let skipped: Vec<_> = result
+            .skipped_sources
+            .iter()
+            .map(|(name, reason, _)| (name.as_str(), reason.as_str()))
+            .collect();
+        assert_eq!(skipped.len(), 4);
+        assert_eq!(skipped[1], ("numpy", CONDA_CHANNEL));
+
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains("      - requests==2.32.3  # http\n"));
+        assert!(content.contains("      - \"flask[async]>=3.1.0,<4\"\n"));
+        assert!(content.contains("  - conda-forge::numpy=1.24  # array maths\n"));
+        assert!(content.contains("  - pandas>=2.0\n"));
+    }
+
+    #[test]
+    fn test_dependencies_must_be_top_level() {
+        let content = "name: x\nextra:\n  dependencies:\n    - pip:\n        - requests==1.0\n";
+        let scan = CondaEnvUpdater::new().scan(content);
+        assert!(scan.pip.is_empty());
+        assert!(scan.conda.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_keeps_compatible_release_ceiling() {
+        let file = environment_yml(
+            "dependencies:\n  - pip:\n      - zope.interface~=5.0\n      - attrs~=23.1\n",
+        );
+        // zope.interface falls back to an unconstrained latest past its `<6` ceiling.
+        let registry = MockRegistry::new("pypi")
+            .with_version("zope.interface", "7.0.3")
+            .with_version("attrs", "24.2.0")
+            .with_constrained("attrs", "~=23.1", "23.2.0");
+
+        let result = CondaEnvUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(
+            content.contains("      - zope.interface~=5.0\n"),
+            "{content}"
+        );
+        assert!(content.contains("      - attrs~=23.2\n"), "{content}");
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].3, Some(4));
+    }
+}
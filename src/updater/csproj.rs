@@ -1,6 +1,6 @@
 use super::{
     FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
-    read_file_safe, write_file_atomic,
+    read_file_safe,
 };
 use crate::align::compare_versions;
 use crate::registry::Registry;
@@ -212,6 +212,11 @@ impl Updater for CsprojUpdater {
                 continue;
             }
 
+            if let Some(warning) = options.range_pin_warning(&pkg.name) {
+                result.warnings.push(warning);
+                continue;
+            }
+
             if let Some(pinned_version) = options.get_pinned_version(&pkg.name) {
                 pinned_packages.push((
                     pkg.line_idx,
@@ -459,7 +464,7 @@ impl Updater for CsprojUpdater {
             }
         }
 
-        if modified && !options.dry_run {
+        if modified {
             let line_ending = if content.contains("\r\n") {
                 "\r\n"
             } else {
@@ -472,7 +477,7 @@ impl Updater for CsprojUpdater {
                 new_content.push_str(line_ending);
             }
 
-            write_file_atomic(path, &new_content)?;
+            result.commit_content(path, &content, new_content, options.dry_run)?;
         }
 
         Ok(result)
@@ -515,6 +520,7 @@ impl Updater for CsprojUpdater {
                         line_number: Some(tag_line_idx + 1),
                         has_upper_bound: has_range,
                         is_bumpable: true,
+                        inherits_workspace: false,
                     });
                     pending_tag = None;
                     continue;
@@ -532,6 +538,7 @@ impl Updater for CsprojUpdater {
                     line_number: Some(line_idx + 1),
                     has_upper_bound: parsed.has_range_constraint,
                     is_bumpable: true,
+                    inherits_workspace: false,
                 });
                 continue;
             }
@@ -718,6 +725,9 @@ mod tests {
             ignore: vec!["Newtonsoft.Json".to_string()],
             pin: pins,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = CsprojUpdater::new();
@@ -759,6 +769,9 @@ mod tests {
             ignore: vec![],
             pin: pins,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = CsprojUpdater::new();
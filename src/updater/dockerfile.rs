@@ -0,0 +1,405 @@
+use super::{
+    FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
+    read_file_safe,
+};
+use crate::align::compare_versions;
+use crate::registry::{Registry, tag_shape};
+use crate::updater::Lang;
+use anyhow::Result;
+use futures::future::join_all;
+use regex::Regex;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::Path;
+
+/// Reason recorded for images hosted outside Docker Hub.
+const OTHER_REGISTRY: &str = "image not on Docker Hub, no registry support";
+
+pub struct DockerfileUpdater {
+    /// Matches a `FROM` instruction. Group 1: image reference, group 2: the
+    /// stage name after `AS`. `--platform=...` style flags are skipped.
+    from_re: Regex,
+}
+
+/// A `FROM` image pinned to a tag that can be compared with newer releases.
+struct DockerfileImage {
+    /// The image as written, without a `docker.io/` prefix
+    image: String,
+    tag: String,
+    /// Byte range of the tag within the file
+    tag_range: Range<usize>,
+    line_num: Option<usize>,
+}
+
+/// What a Dockerfile's `FROM` lines reference.
+#[derive(Default)]
+struct DockerfileScan {
+    images: Vec<DockerfileImage>,
+    /// Images on other registries, with their 1-indexed line
+    other_registry: Vec<(String, Option<usize>)>,
+}
+
+impl DockerfileUpdater {
+    pub fn new() -> Self {
+        let from_re = Regex::new(r"(?i)^\s*FROM\s+(?:--\S+\s+)*(\S+)(?:\s+AS\s+(\S+))?\s*$")
+            .expect("Invalid regex");
+        Self { from_re }
+    }
+
+    fn scan(&self, content: &str) -> DockerfileScan {
+        let mut scan = DockerfileScan::default();
+        // Stage names seen so far; `FROM builder` starts from an earlier
+        // stage, not from an image.
+        let mut stages: HashSet<String> = HashSet::new();
+        let mut offset = 0;
+
+        for (idx, line) in content.split_inclusive('\n').enumerate() {
+            let line_start = offset;
+            offset += line.len();
+            let line_num = Some(idx + 1);
+
+            let Some(caps) = self.from_re.captures(line.trim_end_matches(['\n', '\r'])) else {
+                continue;
+            };
+            let reference = caps.get(1).unwrap();
+            let is_stage = stages.contains(&reference.as_str().to_lowercase());
+            if let Some(stage) = caps.get(2) {
+                stages.insert(stage.as_str().to_lowercase());
+            }
+
+            // Stages, `scratch`, build args (`python:${PY}`) and digest-pinned
+            // images (`python@sha256:...`) have no tag to move.
+            let text = reference.as_str();
+            if is_stage
+                || text.eq_ignore_ascii_case("scratch")
+                || text.contains('$')
+                || text.contains('@')
+            {
+                continue;
+            }
+
+            // The tag follows the last `:` after the last `/`; a `:` before
+            // that belongs to a registry port.
+            let name_end = text.rfind('/').map_or(0, |slash| slash + 1);
+            let Some(colon) = text[name_end..].find(':').map(|c| name_end + c) else {
+                continue;
+            };
+            let (name, tag) = (&text[..colon], &text[colon + 1..]);
+
+            // `docker.io/library/python` is the official `python` image.
+            let name = name
+                .strip_prefix("docker.io/")
+                .or_else(|| name.strip_prefix("index.docker.io/"))
+                .map_or(name, |name| name.strip_prefix("library/").unwrap_or(name));
+            if Self::is_other_registry(name) {
+                scan.other_registry.push((name.to_string(), line_num));
+                continue;
+            }
+            if tag_shape(tag).is_none() {
+                continue;
+            }
+
+            let tag_start = line_start + reference.start() + colon + 1;
+            scan.images.push(DockerfileImage {
+                image: name.to_string(),
+                tag: tag.to_string(),
+                tag_range: tag_start..tag_start + tag.len(),
+                line_num,
+            });
+        }
+
+        scan
+    }
+
+    /// Whether the image's first path segment names a registry host
+    /// (`ghcr.io/org/app`, `localhost:5000/app`) rather than a Docker Hub
+    /// namespace.
+    fn is_other_registry(name: &str) -> bool {
+        match name.split_once('/') {
+            Some((first, _)) => first.contains(['.', ':']) || first == "localhost",
+            None => false,
+        }
+    }
+
+    /// The numeric part of a tag, which bump classification understands
+    /// (`3.11.2-slim` gives `3.11.2`).
+    fn release(tag: &str) -> String {
+        tag_shape(tag)
+            .map(|shape| {
+                shape
+                    .release
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(".")
+            })
+            .unwrap_or_else(|| tag.to_string())
+    }
+}
+
+impl Default for DockerfileUpdater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Updater for DockerfileUpdater {
+    async fn update(
+        &self,
+        path: &Path,
+        registry: &dyn Registry,
+        options: UpdateOptions,
+    ) -> Result<UpdateResult> {
+        let content = read_file_safe(path)?;
+        let scan = self.scan(&content);
+
+        let mut result = UpdateResult::default();
+        for (name, line_num) in scan.other_registry {
+            result
+                .skipped_sources
+                .push((name, OTHER_REGISTRY.to_string(), line_num));
+        }
+
+        let mut pinned_images: Vec<(DockerfileImage, String)> = Vec::new();
+        let mut images_to_check: Vec<DockerfileImage> = Vec::new();
+
+        for entry in scan.images {
+            if options.is_package_filtered_out(&entry.image) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            if options.should_ignore(&entry.image) {
+                result
+                    .ignored
+                    .push((entry.image, entry.tag, entry.line_num));
+                continue;
+            }
+
+            if let Some(warning) = options.range_pin_warning(&entry.image) {
+                result.warnings.push(warning);
+                continue;
+            }
+
+            if let Some(pinned_tag) = options.get_pinned_version(&entry.image) {
+                pinned_images.push((entry, pinned_tag.to_string()));
+                continue;
+            }
+
+            images_to_check.push(entry);
+        }
+
+        // (byte range of the old tag, new tag)
+        let mut writes: Vec<(Range<usize>, String)> = Vec::new();
+
+        // A pinned tag is written as given: it names the variant as well.
+        for (entry, pinned_tag) in pinned_images {
+            if pinned_tag != entry.tag {
+                writes.push((entry.tag_range.clone(), pinned_tag.clone()));
+                result
+                    .pinned
+                    .push((entry.image, entry.tag, pinned_tag, entry.line_num));
+            } else {
+                result.unchanged += 1;
+            }
+        }
+
+        // The current tag is the constraint: the registry answers with the
+        // newest tag of the same precision and variant.
+        let tag_futures: Vec<_> = images_to_check
+            .iter()
+            .map(|entry| registry.get_latest_version_matching(&entry.image, &entry.tag))
+            .collect();
+        let tag_results = join_all(tag_futures).await;
+
+        for (entry, tag_result) in images_to_check.into_iter().zip(tag_results) {
+            let latest_tag = match tag_result {
+                Ok(tag) => tag,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", entry.image, e));
+                    continue;
+                }
+            };
+            if latest_tag == entry.tag {
+                result.unchanged += 1;
+                continue;
+            }
+
+            // Refuse to write a downgrade.
+            if compare_versions(&latest_tag, &entry.tag, Lang::Docker)
+                != std::cmp::Ordering::Greater
+            {
+                result
+                    .warnings
+                    .push(downgrade_warning(&entry.image, &latest_tag, &entry.tag));
+                result.unchanged += 1;
+                continue;
+            }
+
+            // Bump level exceeds the --only-bump/--max-bump ceiling.
+            if !options.allows_bump(&Self::release(&entry.tag), &Self::release(&latest_tag)) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            writes.push((entry.tag_range.clone(), latest_tag.clone()));
+            result
+                .updated
+                .push((entry.image, entry.tag, latest_tag, entry.line_num));
+        }
+
+        if !writes.is_empty() {
+            // Splice from the end so earlier ranges stay valid.
+            writes.sort_by_key(|w| std::cmp::Reverse(w.0.start));
+            let mut new_content = content.clone();
+            for (range, new_tag) in &writes {
+                new_content.replace_range(range.clone(), new_tag);
+            }
+            result.commit_content(path, &content, new_content, options.dry_run)?;
+        }
+
+        Ok(result)
+    }
+
+    fn handles(&self, file_type: FileType) -> bool {
+        file_type == FileType::Dockerfile
+    }
+
+    fn parse_dependencies(&self, path: &Path) -> Result<Vec<ParsedDependency>> {
+        let content = read_file_safe(path)?;
+        Ok(self
+            .scan(&content)
+            .images
+            .into_iter()
+            .map(|entry| ParsedDependency {
+                name: entry.image,
+                version: entry.tag,
+                line_number: entry.line_num,
+                has_upper_bound: false,
+                // Tags of different variants (`3.12-slim`, `3.12-alpine`)
+                // are not interchangeable, so images are never aligned.
+                is_bumpable: false,
+                inherits_workspace: false,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MockRegistry;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const MULTI_STAGE: &str = "\
+# syntax=docker/dockerfile:1
+FROM --platform=$BUILDPLATFORM python:3.11.2-slim AS builder
+RUN pip install build
+
+FROM builder AS test
+RUN pytest
+
+from node:18-alpine as assets
+FROM ghcr.io/acme/runtime:1.4.0
+FROM python:3.11-slim
+COPY --from=builder /app /app
+";
+
+    fn write_dockerfile(content: &str) -> (TempDir, std::path::PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Dockerfile");
+        fs::write(&path, content).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn test_scan_skips_stages_and_other_registries() {
+        let updater = DockerfileUpdater::new();
+        let scan = updater.scan(MULTI_STAGE);
+
+        let images: Vec<(&str, &str, Option<usize>)> = scan
+            .images
+            .iter()
+            .map(|i| (i.image.as_str(), i.tag.as_str(), i.line_num))
+            .collect();
+        assert_eq!(
+            images,
+            vec![
+                ("python", "3.11.2-slim", Some(2)),
+                ("node", "18-alpine", Some(8)),
+                ("python", "3.11-slim", Some(10)),
+            ]
+        );
+        assert_eq!(
+            scan.other_registry,
+            vec![("ghcr.io/acme/runtime".to_string(), Some(9))]
+        );
+        assert_eq!(
+            &MULTI_STAGE[scan.images[0].tag_range.clone()],
+            "3.11.2-slim"
+        );
+    }
+
+    #[test]
+    fn test_scan_skips_untagged_digest_and_arg_images() {
+        let updater = DockerfileUpdater::new();
+        let scan = updater.scan(
+            "FROM scratch\nFROM python\nFROM python:latest\nFROM python:${PY}\n\
+             FROM python:3.12@sha256:abc\nFROM localhost:5000/app:1.0\nFROM docker.io/library/redis:7.2\n",
+        );
+
+        assert_eq!(scan.images.len(), 1);
+        assert_eq!(scan.images[0].image, "redis");
+        assert_eq!(scan.images[0].tag, "7.2");
+        assert_eq!(
+            scan.other_registry,
+            vec![("localhost:5000/app".to_string(), Some(6))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_keeps_aliases_and_variants() {
+        let (_dir, path) = write_dockerfile(MULTI_STAGE);
+        let registry = MockRegistry::new("docker-hub")
+            .with_constrained("python", "3.11.2-slim", "3.13.1-slim")
+            .with_constrained("python", "3.11-slim", "3.13-slim")
+            .with_constrained("node", "18-alpine", "22-alpine");
+
+        let updater = DockerfileUpdater::new();
+        let result = updater
+            .update(&path, &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 3);
+        assert_eq!(result.skipped_sources.len(), 1);
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("FROM --platform=$BUILDPLATFORM python:3.13.1-slim AS builder\n"));
+        assert!(content.contains("FROM builder AS test\n"));
+        assert!(content.contains("from node:22-alpine as assets\n"));
+        assert!(content.contains("FROM ghcr.io/acme/runtime:1.4.0\n"));
+        assert!(content.contains("FROM python:3.13-slim\n"));
+    }
+
+    #[tokio::test]
+    async fn test_update_refuses_downgrade() {
+        let (_dir, path) = write_dockerfile("FROM python:3.12-slim\n");
+        let registry =
+            MockRegistry::new("docker-hub").with_constrained("python", "3.12-slim", "3.9-slim");
+
+        let updater = DockerfileUpdater::new();
+        let result = updater
+            .update(&path, &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.updated.is_empty());
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "FROM python:3.12-slim\n"
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use super::{
     FileType, ParsedDependency, PendingVersion, UpdateOptions, UpdateResult, Updater,
-    downgrade_warning, read_file_safe, write_file_atomic,
+    downgrade_warning, read_file_safe,
 };
 use crate::align::compare_versions;
 use crate::registry::Registry;
@@ -54,8 +54,12 @@ impl GemfileUpdater {
             return None;
         }
 
-        // Skip gems with path: or git: options (local/git sources)
-        if trimmed.contains("path:") || trimmed.contains("git:") {
+        // Skip gems with path:, git: or github: options (local/git sources),
+        // in either keyword or hash-rocket (`:git =>`) syntax
+        if ["path:", "git:", "github:", ":path", ":git"]
+            .iter()
+            .any(|option| trimmed.contains(option))
+        {
             return None;
         }
 
@@ -142,6 +146,11 @@ impl Updater for GemfileUpdater {
                 continue;
             }
 
+            if let Some(warning) = options.range_pin_warning(&parsed.name) {
+                result.warnings.push(warning);
+                continue;
+            }
+
             if let Some(pinned_version) = options.get_pinned_version(&parsed.name) {
                 pinned_packages.push((
                     *line_idx,
@@ -182,10 +191,11 @@ impl Updater for GemfileUpdater {
         // Fetch versions in parallel.
         // When the current version is a pre-release, request the latest pre-release
         // to avoid silently promoting the gem to a stable release.
+        let options_ref = &options;
         let version_futures: Vec<_> = unique_gems
             .iter()
             .map(|(name, operator, version)| async move {
-                if Self::is_prerelease_ruby(version) {
+                if Self::is_prerelease_ruby(version) || options_ref.allows_prerelease(name) {
                     registry
                         .get_latest_version_including_prereleases(name)
                         .await
@@ -392,7 +402,7 @@ impl Updater for GemfileUpdater {
             }
         }
 
-        if modified && !options.dry_run {
+        if modified {
             let line_ending = if content.contains("\r\n") {
                 "\r\n"
             } else {
@@ -405,7 +415,7 @@ impl Updater for GemfileUpdater {
                 new_content.push_str(line_ending);
             }
 
-            write_file_atomic(path, &new_content)?;
+            result.commit_content(path, &content, new_content, options.dry_run)?;
         }
 
         Ok(result)
@@ -427,6 +437,7 @@ impl Updater for GemfileUpdater {
                     line_number: Some(line_idx + 1),
                     has_upper_bound: Self::has_upper_bound(&parsed.operator),
                     is_bumpable: true,
+                    inherits_workspace: false,
                 });
             }
         }
@@ -490,6 +501,16 @@ mod tests {
                 .parse_line("gem 'my-gem', git: 'https://github.com/user/repo'")
                 .is_none()
         );
+        assert!(
+            updater
+                .parse_line("gem 'my-gem', '1.0.0', github: 'user/repo'")
+                .is_none()
+        );
+        assert!(
+            updater
+                .parse_line("gem 'my-gem', '~> 1.0', :git => 'https://example.com/repo.git'")
+                .is_none()
+        );
     }
 
     #[tokio::test]
@@ -604,6 +625,9 @@ mod tests {
             ignore: vec!["rails".to_string()],
             pin: pins,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = GemfileUpdater::new();
@@ -627,6 +651,44 @@ mod tests {
         assert!(!updated_names.contains(&"devise"));
     }
 
+    #[tokio::test]
+    async fn test_range_pin_is_refused() {
+        use crate::config::UpdConfig;
+        use std::sync::Arc;
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "gem 'rails', '7.0.0'").unwrap();
+
+        let registry = MockRegistry::new("rubygems").with_version("rails", "7.2.3");
+
+        let mut pins = std::collections::HashMap::new();
+        pins.insert("rails".to_string(), ">=7.0,<7.2".to_string());
+        let config = UpdConfig {
+            exclude: Vec::new(),
+            ignore: Vec::new(),
+            pin: pins,
+            cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
+        };
+
+        let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
+        let result = GemfileUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert!(result.pinned.is_empty());
+        assert!(result.updated.is_empty());
+        assert_eq!(
+            result.warnings,
+            ["skipping rails: range pin \">=7.0,<7.2\" is not supported for this file"]
+        );
+        let content = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!(content, "gem 'rails', '7.0.0'\n");
+    }
+
     #[test]
     fn test_parse_gem_with_indentation() {
         let updater = GemfileUpdater::new();
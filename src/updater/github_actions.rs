@@ -1,6 +1,6 @@
 use super::{
     FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
-    read_file_safe, write_file_atomic,
+    read_file_safe,
 };
 use crate::align::compare_versions;
 use crate::registry::Registry;
@@ -14,13 +14,20 @@ use std::path::Path;
 
 pub struct GithubActionsUpdater {
     uses_re: Regex,
+    /// Version comment after a SHA ref: `@<sha> # v4.1.0`.
+    sha_comment_re: Regex,
 }
 
 impl GithubActionsUpdater {
     pub fn new() -> Self {
         let uses_re =
             Regex::new(r#"uses:\s*"?([^@\s"]+)@([^"'\s#]+)"#).expect("Invalid uses regex");
-        Self { uses_re }
+        let sha_comment_re =
+            Regex::new(r#"^"?\s+#\s*(v?\d[^\s]*)"#).expect("Invalid SHA comment regex");
+        Self {
+            uses_re,
+            sha_comment_re,
+        }
     }
 
     /// Returns true if the ref looks like a commit SHA (7+ hex characters)
@@ -48,6 +55,38 @@ impl GithubActionsUpdater {
         Self::is_sha_ref(ref_str) || Self::is_branch_ref(ref_str)
     }
 
+    /// The version a `uses:` match tracks, and the SHA it is pinned to if any.
+    ///
+    /// A SHA ref followed by a version comment (`@<sha> # v4.1.0`) tracks the
+    /// comment's tag. Bare SHAs and branches track nothing and return `None`.
+    fn tracked_ref<'a>(
+        &self,
+        line: &'a str,
+        caps: &regex::Captures<'a>,
+    ) -> Option<(&'a str, Option<&'a str>)> {
+        let version_ref = caps.get(2)?.as_str();
+        if Self::is_sha_ref(version_ref) {
+            let rest = &line[caps.get(0)?.end()..];
+            let tag = self.sha_comment_re.captures(rest)?.get(1)?.as_str();
+            return Some((tag, Some(version_ref)));
+        }
+        if Self::should_skip_ref(version_ref) {
+            return None;
+        }
+        Some((version_ref, None))
+    }
+
+    /// Point a SHA-pinned line at `new_sha` and bump the tag in its comment.
+    fn rewrite_sha_pin(line: &str, sha: &str, new_sha: &str, tag: &str, new_tag: &str) -> String {
+        let split = line.find(sha).map_or(0, |start| start + sha.len());
+        let (uses, comment) = line.split_at(split);
+        format!(
+            "{}{}",
+            uses.replacen(sha, new_sha, 1),
+            comment.replacen(tag, new_tag, 1)
+        )
+    }
+
     /// Returns true if the action reference should be skipped entirely
     fn should_skip_action(action: &str) -> bool {
         if action.starts_with("./") || action.starts_with("docker://") {
@@ -150,11 +189,12 @@ impl GithubActionsUpdater {
 
             if let Some(caps) = self.uses_re.captures(line) {
                 let action = caps.get(1).unwrap().as_str();
-                let version_ref = caps.get(2).unwrap().as_str();
-
-                if Self::should_skip_action(action) || Self::should_skip_ref(version_ref) {
+                if Self::should_skip_action(action) {
                     continue;
                 }
+                let Some((version_ref, _)) = self.tracked_ref(line, &caps) else {
+                    continue;
+                };
 
                 let owner_repo = Self::extract_owner_repo(action);
 
@@ -164,6 +204,7 @@ impl GithubActionsUpdater {
                     line_number: Some(line_idx + 1),
                     has_upper_bound: false,
                     is_bumpable: true,
+                    inherits_workspace: false,
                 });
             }
         }
@@ -194,6 +235,8 @@ impl Updater for GithubActionsUpdater {
         let mut ignored_actions: Vec<(usize, String, String)> = Vec::new();
         let mut pinned_actions: Vec<(usize, String, String, String)> = Vec::new();
         let mut actions_to_check: Vec<(usize, String, String)> = Vec::new();
+        // line_idx -> SHA of actions pinned as `@<sha> # <tag>`
+        let mut sha_pins: HashMap<usize, String> = HashMap::new();
 
         let mut in_block_scalar = false;
         let mut block_parent_indent: usize = 0;
@@ -224,11 +267,15 @@ impl Updater for GithubActionsUpdater {
 
             if let Some(caps) = self.uses_re.captures(line) {
                 let action = caps.get(1).unwrap().as_str();
-                let version_ref = caps.get(2).unwrap().as_str();
-
-                if Self::should_skip_action(action) || Self::should_skip_ref(version_ref) {
+                if Self::should_skip_action(action) {
                     continue;
                 }
+                let Some((version_ref, pinned_sha)) = self.tracked_ref(line, &caps) else {
+                    continue;
+                };
+                if let Some(sha) = pinned_sha {
+                    sha_pins.insert(line_idx, sha.to_string());
+                }
 
                 let owner_repo = Self::extract_owner_repo(action).to_string();
 
@@ -243,6 +290,11 @@ impl Updater for GithubActionsUpdater {
                     continue;
                 }
 
+                if let Some(warning) = options.range_pin_warning(&owner_repo) {
+                    result.warnings.push(warning);
+                    continue;
+                }
+
                 if let Some(pinned_version) = options.get_pinned_version(&owner_repo) {
                     pinned_actions.push((
                         line_idx,
@@ -420,7 +472,27 @@ impl Updater for GithubActionsUpdater {
                                 result.unchanged += 1;
                                 new_lines.push(line.to_string());
                             } else {
-                                let new_line = line.replacen(current_version, &new_version, 1);
+                                // SHA pins stay pinned: move to the new tag's commit.
+                                let new_line = match sha_pins.get(&line_idx) {
+                                    Some(sha) => match registry
+                                        .resolve_tag_commit(owner_repo, &new_version)
+                                        .await
+                                    {
+                                        Ok(new_sha) => Self::rewrite_sha_pin(
+                                            line,
+                                            sha,
+                                            &new_sha,
+                                            current_version,
+                                            &new_version,
+                                        ),
+                                        Err(e) => {
+                                            result.errors.push(format!("{}: {}", owner_repo, e));
+                                            new_lines.push(line.to_string());
+                                            continue;
+                                        }
+                                    },
+                                    None => line.replacen(current_version, &new_version, 1),
+                                };
                                 new_lines.push(new_line);
 
                                 if *is_pinned {
@@ -465,7 +537,7 @@ impl Updater for GithubActionsUpdater {
             }
         }
 
-        if (!result.updated.is_empty() || !result.pinned.is_empty()) && !options.dry_run {
+        if !result.updated.is_empty() || !result.pinned.is_empty() {
             let line_ending = if content.contains("\r\n") {
                 "\r\n"
             } else {
@@ -479,7 +551,7 @@ impl Updater for GithubActionsUpdater {
                 new_content
             };
 
-            write_file_atomic(path, &final_content)?;
+            result.commit_content(path, &content, final_content, options.dry_run)?;
         }
 
         Ok(result)
@@ -775,6 +847,92 @@ jobs:
         assert!(content.contains("a5ac7e51b28d7f9f3091645916e8170a8b5cbc47"));
     }
 
+    #[test]
+    fn test_parses_comment_pinned_sha_as_its_tag() {
+        let updater = GithubActionsUpdater::new();
+        let deps = updater.parse_dependencies_from_content(
+            "      - uses: actions/checkout@a5ac7e51b28d7f9f3091645916e8170a8b5cbc47 # v3.1.0\n",
+        );
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "actions/checkout");
+        assert_eq!(deps[0].version, "v3.1.0");
+    }
+
+    #[tokio::test]
+    async fn test_updates_comment_pinned_sha_to_new_tag_commit() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"jobs:
+  build:
+    steps:
+      - uses: actions/checkout@a5ac7e51b28d7f9f3091645916e8170a8b5cbc47 # v3.1.0
+      - uses: "actions/setup-node@1a4442cacd436585916779262731d5b162bc6ec7"  # v3.8.1
+      - uses: ./.github/actions/local
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("github-releases")
+            .with_version("actions/checkout", "v4.2.2")
+            .with_commit(
+                "actions/checkout",
+                "v4.2.2",
+                "11bd71901bbe5b1630ceea73d27597364c9af683",
+            )
+            .with_version("actions/setup-node", "v4.1.0")
+            .with_commit(
+                "actions/setup-node",
+                "v4.1.0",
+                "39370e3970a6d050c480ffad4ff0ed4d3fdee5af",
+            );
+
+        let updater = GithubActionsUpdater::new();
+        let result = updater
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.updated.len(), 2);
+        assert_eq!(result.updated[0].1, "v3.1.0");
+        assert_eq!(result.updated[0].2, "v4.2.2");
+
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert_eq!(
+            content,
+            r#"jobs:
+  build:
+    steps:
+      - uses: actions/checkout@11bd71901bbe5b1630ceea73d27597364c9af683 # v4.2.2
+      - uses: "actions/setup-node@39370e3970a6d050c480ffad4ff0ed4d3fdee5af"  # v4.1.0
+      - uses: ./.github/actions/local
+"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unresolvable_tag_leaves_sha_pin_untouched() {
+        let mut file = NamedTempFile::new().unwrap();
+        let original =
+            "      - uses: actions/checkout@a5ac7e51b28d7f9f3091645916e8170a8b5cbc47 # v3.1.0\n";
+        write!(file, "{}", original).unwrap();
+
+        let registry =
+            MockRegistry::new("github-releases").with_version("actions/checkout", "v4.2.2");
+
+        let updater = GithubActionsUpdater::new();
+        let result = updater
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.updated.is_empty());
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].starts_with("actions/checkout: "));
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), original);
+    }
+
     #[tokio::test]
     async fn test_skips_block_scalar_content() {
         let mut file = NamedTempFile::new().unwrap();
@@ -1119,6 +1277,9 @@ jobs:
             ignore: vec!["actions/checkout".to_string()],
             pin: pins,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = GithubActionsUpdater::new();
@@ -1,6 +1,6 @@
 use super::{
     FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
-    read_file_safe, write_file_atomic,
+    read_file_safe, resolve_pin,
 };
 use crate::align::compare_versions;
 use crate::registry::Registry;
@@ -247,11 +247,19 @@ impl Updater for GoModUpdater {
         // Fetch all versions in parallel for non-ignored, non-pinned modules
         let version_futures: Vec<_> = modules_to_check
             .iter()
-            .map(|(_, module, _, is_prerelease)| async {
-                if *is_prerelease {
+            .map(|(_, module, current_version, is_prerelease)| async {
+                if *is_prerelease || options.allows_prerelease(module) {
                     registry
                         .get_latest_version_including_prereleases(module)
                         .await
+                } else if let Some(req) = options.semver_compatible_req(
+                    current_version
+                        .trim_start_matches('v')
+                        .trim_end_matches("+incompatible"),
+                ) {
+                    // `--strategy compatible`; the major is already fixed by the
+                    // module path, so this mostly matters for v0 modules.
+                    registry.get_latest_version_matching(module, &req).await
                 } else {
                     registry.get_latest_version(module).await
                 }
@@ -266,9 +274,14 @@ impl Updater for GoModUpdater {
             version_map.insert(*line_idx, version_result);
         }
 
-        // Add pinned modules to version_map with their pinned version
-        for (line_idx, _, _, pinned_version) in &pinned_modules {
-            version_map.insert(*line_idx, Ok(pinned_version.clone()));
+        // Add pinned modules to version_map, resolving range pins in the registry
+        let pin_futures: Vec<_> = pinned_modules
+            .iter()
+            .map(|(_, module, _, pinned_version)| resolve_pin(registry, module, pinned_version))
+            .collect();
+        let pin_results = join_all(pin_futures).await;
+        for ((line_idx, _, _, _), pin_result) in pinned_modules.iter().zip(pin_results) {
+            version_map.insert(*line_idx, pin_result);
         }
 
         // Create a map from line_idx to (module, current_version, is_pinned) for easy lookup
@@ -384,7 +397,7 @@ impl Updater for GoModUpdater {
                                 new_lines.push(new_line);
 
                                 if *is_pinned {
-                                    // Record as pinned (bypassed registry lookup)
+                                    // Record as pinned (exact or resolved within the pinned range)
                                     result.pinned.push((
                                         module.clone(),
                                         current_version.clone(),
@@ -449,7 +462,7 @@ impl Updater for GoModUpdater {
             }
         }
 
-        if (!result.updated.is_empty() || !result.pinned.is_empty()) && !options.dry_run {
+        if !result.updated.is_empty() || !result.pinned.is_empty() {
             // Preserve original line ending
             let line_ending = if content.contains("\r\n") {
                 "\r\n"
@@ -465,7 +478,7 @@ impl Updater for GoModUpdater {
                 new_content
             };
 
-            write_file_atomic(path, &final_content)?;
+            result.commit_content(path, &content, final_content, options.dry_run)?;
         }
 
         Ok(result)
@@ -533,6 +546,7 @@ impl Updater for GoModUpdater {
                     line_number: Some(line_idx + 1),
                     has_upper_bound: false, // Go doesn't have explicit upper bounds
                     is_bumpable,
+                    inherits_workspace: false,
                 });
             }
         }
@@ -911,6 +925,9 @@ require (
             ignore: vec!["github.com/foo/bar".to_string()],
             pin: std::collections::HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = GoModUpdater::new();
@@ -963,6 +980,9 @@ require (
             ignore: vec![],
             pin: pins,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = GoModUpdater::new();
@@ -985,6 +1005,55 @@ require (
         assert!(content.contains("v2.3.0")); // baz/qux updated
     }
 
+    #[tokio::test]
+    async fn test_config_range_pin_module() {
+        use crate::config::UpdConfig;
+        use std::sync::Arc;
+
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"module example.com/mymodule
+
+require github.com/foo/bar v1.0.0
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("go-proxy")
+            .with_version("github.com/foo/bar", "v1.5.0")
+            .with_constrained("github.com/foo/bar", ">=1.0.0, <1.3.0", "v1.2.4");
+
+        let mut pins = std::collections::HashMap::new();
+        pins.insert(
+            "github.com/foo/bar".to_string(),
+            ">=1.0.0, <1.3.0".to_string(),
+        );
+        let config = UpdConfig {
+            exclude: Vec::new(),
+            ignore: vec![],
+            pin: pins,
+            cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
+        };
+
+        let updater = GoModUpdater::new();
+        let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
+
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert!(result.updated.is_empty());
+        assert_eq!(result.pinned.len(), 1);
+        assert_eq!(result.pinned[0].2, "v1.2.4");
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains("github.com/foo/bar v1.2.4"));
+    }
+
     #[tokio::test]
     async fn test_config_pin_only_writes_file() {
         use crate::config::UpdConfig;
@@ -1011,6 +1080,9 @@ require github.com/foo/bar v1.0.0
             ignore: vec![],
             pin: pins,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = GoModUpdater::new();
@@ -1062,6 +1134,9 @@ require (
             ignore: vec!["github.com/ignored/mod".to_string()],
             pin: pins,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = GoModUpdater::new();
@@ -1117,6 +1192,9 @@ require github.com/baz/qux v2.0.0
             ignore: vec!["github.com/baz/qux".to_string()],
             pin: pins,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = GoModUpdater::new();
@@ -1165,6 +1243,9 @@ require (
             ignore: vec![],
             pin: pins,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = GoModUpdater::new();
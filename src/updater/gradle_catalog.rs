@@ -0,0 +1,492 @@
+use super::{
+    FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
+    read_file_safe,
+};
+use crate::align::compare_versions;
+use crate::registry::{Registry, is_maven_prerelease};
+use crate::updater::Lang;
+use anyhow::{Result, anyhow};
+use futures::future::join_all;
+use std::collections::HashMap;
+use std::path::Path;
+use toml_edit::{DocumentMut, Formatted, Item, Value};
+
+/// Updates the `[versions]` table of a Gradle version catalog
+/// (`gradle/libs.versions.toml`).
+///
+/// Each version is resolved through the first `[libraries]` entry that
+/// references it with `version.ref`, looked up as `group:artifact`. Versions
+/// only plugins reference have no Maven coordinate and are left alone.
+pub struct GradleCatalogUpdater;
+
+/// A `[versions]` entry with the library coordinate it resolves through.
+struct CatalogVersion {
+    /// Key in `[versions]`, e.g. `kotlin`
+    key: String,
+    /// `group:artifact`, e.g. `org.jetbrains.kotlin:kotlin-stdlib`
+    coordinate: String,
+    version: String,
+    line_num: Option<usize>,
+}
+
+/// A version Gradle takes literally. Dynamic (`1.+`, `latest.release`) and
+/// range (`[1.0,2.0)`) versions are left untouched.
+fn is_plain_version(version: &str) -> bool {
+    version.starts_with(|c: char| c.is_ascii_digit())
+        && !version.contains(|c: char| "+[](),".contains(c) || c.is_whitespace())
+}
+
+/// `group:artifact` of a `[libraries]` entry, from `module` or `group` + `name`.
+fn library_coordinate(item: &Item) -> Option<String> {
+    if let Some(module) = item.get("module").and_then(|v| v.as_str()) {
+        return module.contains(':').then(|| module.to_string());
+    }
+    let group = item.get("group").and_then(|v| v.as_str())?;
+    let name = item.get("name").and_then(|v| v.as_str())?;
+    Some(format!("{group}:{name}"))
+}
+
+/// The coordinate each version key resolves through: the first library
+/// whose `version.ref` names it.
+fn version_refs(doc: &DocumentMut) -> HashMap<String, String> {
+    let mut refs = HashMap::new();
+    let Some(libraries) = doc.get("libraries").and_then(|l| l.as_table_like()) else {
+        return refs;
+    };
+    for (_, item) in libraries.iter() {
+        let Some(version_ref) = item
+            .get("version")
+            .and_then(|v| v.get("ref"))
+            .and_then(|v| v.as_str())
+        else {
+            continue;
+        };
+        let Some(coordinate) = library_coordinate(item) else {
+            continue;
+        };
+        refs.entry(version_ref.to_string()).or_insert(coordinate);
+    }
+    refs
+}
+
+/// 1-based line of each key in the `[versions]` table.
+fn line_index(content: &str) -> HashMap<String, usize> {
+    let mut lines = HashMap::new();
+    let mut in_versions = false;
+
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_versions =
+                trimmed.trim_start_matches('[').trim_end_matches(']').trim() == "versions";
+            continue;
+        }
+        if !in_versions || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some((key, _)) = trimmed.split_once('=') {
+            let key = key.trim().trim_matches('"').trim_matches('\'');
+            lines.entry(key.to_string()).or_insert(i + 1);
+        }
+    }
+    lines
+}
+
+impl GradleCatalogUpdater {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn parse_error(path: &Path, e: toml_edit::TomlError) -> anyhow::Error {
+        anyhow!(
+            "Failed to parse {}:\n  {}",
+            path.display(),
+            e.to_string().replace('\n', "\n  ")
+        )
+    }
+
+    /// Versions that resolve through a library, in file order.
+    fn catalog_versions(doc: &DocumentMut, content: &str) -> Vec<CatalogVersion> {
+        let refs = version_refs(doc);
+        let lines = line_index(content);
+        let Some(versions) = doc.get("versions").and_then(|v| v.as_table_like()) else {
+            return Vec::new();
+        };
+
+        versions
+            .iter()
+            .filter_map(|(key, item)| {
+                // Rich versions (`{ strictly = "..." }`) are not plain strings.
+                let version = item.as_str()?;
+                if !is_plain_version(version) {
+                    return None;
+                }
+                Some(CatalogVersion {
+                    key: key.to_string(),
+                    coordinate: refs.get(key)?.clone(),
+                    version: version.to_string(),
+                    line_num: lines.get(key).copied(),
+                })
+            })
+            .collect()
+    }
+
+    /// Write `new_version` into `[versions]`, keeping the value's decor.
+    fn set_version(doc: &mut DocumentMut, key: &str, new_version: &str) {
+        let Some(versions) = doc.get_mut("versions").and_then(|v| v.as_table_like_mut()) else {
+            return;
+        };
+        if let Some(Item::Value(Value::String(formatted))) = versions.get_mut(key) {
+            let decor = formatted.decor().clone();
+            let mut new_formatted = Formatted::new(new_version.to_string());
+            *new_formatted.decor_mut() = decor;
+            *formatted = new_formatted;
+        }
+    }
+}
+
+impl Default for GradleCatalogUpdater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Updater for GradleCatalogUpdater {
+    async fn update(
+        &self,
+        path: &Path,
+        registry: &dyn Registry,
+        options: UpdateOptions,
+    ) -> Result<UpdateResult> {
+        let content = read_file_safe(path)?;
+        let mut doc: DocumentMut = content.parse().map_err(|e| Self::parse_error(path, e))?;
+        let mut result = UpdateResult::default();
+        let mut to_check: Vec<CatalogVersion> = Vec::new();
+
+        for entry in Self::catalog_versions(&doc, &content) {
+            let names = [entry.coordinate.as_str(), entry.key.as_str()];
+            if options.is_package_filtered_out(&entry.coordinate) {
+                result.unchanged += 1;
+                continue;
+            }
+            if names.iter().any(|name| options.should_ignore(name)) {
+                result
+                    .ignored
+                    .push((entry.coordinate, entry.version, entry.line_num));
+                continue;
+            }
+            if let Some(warning) = names
+                .iter()
+                .find(|name| options.get_pinned_version(name).is_some())
+                .and_then(|name| options.range_pin_warning(name))
+            {
+                result.warnings.push(warning);
+                continue;
+            }
+            if let Some(pinned) = names
+                .iter()
+                .find_map(|name| options.get_pinned_version(name))
+            {
+                if pinned != entry.version {
+                    Self::set_version(&mut doc, &entry.key, pinned);
+                    result.pinned.push((
+                        entry.coordinate,
+                        entry.version,
+                        pinned.to_string(),
+                        entry.line_num,
+                    ));
+                } else {
+                    result.unchanged += 1;
+                }
+                continue;
+            }
+            to_check.push(entry);
+        }
+
+        let version_futures: Vec<_> = to_check
+            .iter()
+            .map(|entry| async {
+                if is_maven_prerelease(&entry.version)
+                    || options.allows_prerelease(&entry.coordinate)
+                {
+                    registry
+                        .get_latest_version_including_prereleases(&entry.coordinate)
+                        .await
+                } else {
+                    registry.get_latest_version(&entry.coordinate).await
+                }
+            })
+            .collect();
+        let version_results = join_all(version_futures).await;
+
+        for (entry, version_result) in to_check.into_iter().zip(version_results) {
+            let latest_version = match version_result {
+                Ok(v) => v,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", entry.coordinate, e));
+                    continue;
+                }
+            };
+
+            let (outcome, note) = crate::updater::apply_cooldown(
+                registry,
+                &entry.coordinate,
+                &entry.version,
+                &latest_version,
+                None,
+                is_maven_prerelease(&entry.version),
+                &options,
+            )
+            .await;
+            if let Some(msg) = note {
+                options.note_cooldown_unavailable(&msg);
+            }
+            let (latest_version, held_back_record) = match outcome {
+                crate::updater::CooldownOutcome::Unchanged(v) => (v, None),
+                crate::updater::CooldownOutcome::HeldBack {
+                    chosen,
+                    skipped_version,
+                    skipped_published_at,
+                } => (chosen, Some((skipped_version, skipped_published_at))),
+                crate::updater::CooldownOutcome::Skipped {
+                    skipped_version,
+                    skipped_published_at,
+                } => {
+                    result.skipped_by_cooldown.push((
+                        entry.coordinate,
+                        entry.version,
+                        skipped_version,
+                        skipped_published_at,
+                    ));
+                    continue;
+                }
+            };
+
+            // Maven versions are exact, so the latest is written as published.
+            if latest_version == entry.version {
+                result.unchanged += 1;
+                continue;
+            }
+            if compare_versions(&latest_version, &entry.version, Lang::Jvm)
+                != std::cmp::Ordering::Greater
+            {
+                result.warnings.push(downgrade_warning(
+                    &entry.coordinate,
+                    &latest_version,
+                    &entry.version,
+                ));
+                result.unchanged += 1;
+                continue;
+            }
+            if !options.allows_bump(&entry.version, &latest_version) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            Self::set_version(&mut doc, &entry.key, &latest_version);
+            result.updated.push((
+                entry.coordinate.clone(),
+                entry.version.clone(),
+                latest_version.clone(),
+                entry.line_num,
+            ));
+            if let Some((skipped_version, skipped_published_at)) = held_back_record {
+                result.held_back.push((
+                    entry.coordinate,
+                    entry.version,
+                    latest_version,
+                    skipped_version,
+                    skipped_published_at,
+                ));
+            }
+        }
+
+        if !result.updated.is_empty() || !result.pinned.is_empty() {
+            result.commit_content(path, &content, doc.to_string(), options.dry_run)?;
+        }
+
+        Ok(result)
+    }
+
+    fn handles(&self, file_type: FileType) -> bool {
+        file_type == FileType::GradleCatalog
+    }
+
+    fn parse_dependencies(&self, path: &Path) -> Result<Vec<ParsedDependency>> {
+        let content = read_file_safe(path)?;
+        let doc: DocumentMut = content.parse().map_err(|e| Self::parse_error(path, e))?;
+
+        Ok(Self::catalog_versions(&doc, &content)
+            .into_iter()
+            .map(|entry| ParsedDependency {
+                name: entry.coordinate,
+                version: entry.version,
+                line_number: entry.line_num,
+                has_upper_bound: false,
+                is_bumpable: true,
+                inherits_workspace: false,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MockRegistry;
+    use std::fs;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    const CATALOG: &str = r#"[versions]
+kotlin = "1.9.22" # keep in sync with the plugin
+guava = "32.1.3-jre"
+junit = "5.10.0"
+agp = "8.2.0"
+dynamic = "1.+"
+
+[libraries]
+kotlin-stdlib = { module = "org.jetbrains.kotlin:kotlin-stdlib", version.ref = "kotlin" }
+kotlin-reflect = { module = "org.jetbrains.kotlin:kotlin-reflect", version.ref = "kotlin" }
+guava = { group = "com.google.guava", name = "guava", version = { ref = "guava" } }
+junit-jupiter = { module = "org.junit.jupiter:junit-jupiter", version.ref = "junit" }
+okio = "com.squareup.okio:okio:3.6.0"
+lib-dynamic = { module = "com.example:dynamic", version.ref = "dynamic" }
+
+[plugins]
+android-application = { id = "com.android.application", version.ref = "agp" }
+"#;
+
+    fn catalog_file(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::with_suffix(".versions.toml").unwrap();
+        write!(file, "{}", content).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_is_plain_version() {
+        assert!(is_plain_version("1.9.22"));
+        assert!(is_plain_version("32.1.3-jre"));
+        assert!(!is_plain_version("1.+"));
+        assert!(!is_plain_version("[1.0,2.0)"));
+        assert!(!is_plain_version("latest.release"));
+    }
+
+    #[test]
+    fn test_parse_dependencies_resolves_version_refs() {
+        let file = catalog_file(CATALOG);
+        let deps = GradleCatalogUpdater::new()
+            .parse_dependencies(file.path())
+            .unwrap();
+
+        let names: Vec<_> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "org.jetbrains.kotlin:kotlin-stdlib",
+                "com.google.guava:guava",
+                "org.junit.jupiter:junit-jupiter",
+            ]
+        );
+        assert_eq!(deps[0].version, "1.9.22");
+        assert_eq!(deps[0].line_number, Some(2));
+        assert_eq!(deps[2].line_number, Some(4));
+    }
+
+    #[tokio::test]
+    async fn test_update_gradle_catalog_preserves_layout() {
+        let file = catalog_file(CATALOG);
+        let registry = MockRegistry::new("maven")
+            .with_version("org.jetbrains.kotlin:kotlin-stdlib", "2.0.21")
+            .with_version("com.google.guava:guava", "33.3.1-jre")
+            .with_version("org.junit.jupiter:junit-jupiter", "5.10.0");
+
+        let result = GradleCatalogUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 2);
+        assert_eq!(result.unchanged, 1);
+        assert_eq!(
+            result.updated[0],
+            (
+                "org.jetbrains.kotlin:kotlin-stdlib".to_string(),
+                "1.9.22".to_string(),
+                "2.0.21".to_string(),
+                Some(2)
+            )
+        );
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert_eq!(
+            content,
+            CATALOG
+                .replace(r#""1.9.22""#, r#""2.0.21""#)
+                .replace(r#""32.1.3-jre""#, r#""33.3.1-jre""#)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_gradle_catalog_prerelease_only_when_current_is_one() {
+        let file = catalog_file(
+            r#"[versions]
+stable = "1.0.0"
+beta = "2.0.0-beta1"
+
+[libraries]
+a = { module = "com.example:stable", version.ref = "stable" }
+b = { module = "com.example:beta", version.ref = "beta" }
+"#,
+        );
+        let registry = MockRegistry::new("maven")
+            .with_prerelease("com.example:stable", "1.1.0", "2.0.0-RC1")
+            .with_prerelease("com.example:beta", "1.9.0", "2.0.0-RC1");
+
+        GradleCatalogUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(r#"stable = "1.1.0""#));
+        assert!(content.contains(r#"beta = "2.0.0-RC1""#));
+    }
+
+    #[tokio::test]
+    async fn test_update_gradle_catalog_ignore_and_pin_by_alias_or_coordinate() {
+        use crate::config::UpdConfig;
+        use std::sync::Arc;
+
+        let file = catalog_file(CATALOG);
+        let registry = MockRegistry::new("maven")
+            .with_version("org.jetbrains.kotlin:kotlin-stdlib", "2.0.21")
+            .with_version("com.google.guava:guava", "33.3.1-jre")
+            .with_version("org.junit.jupiter:junit-jupiter", "5.11.3");
+
+        let config = UpdConfig {
+            ignore: vec!["kotlin".to_string()],
+            pin: [(
+                "com.google.guava:guava".to_string(),
+                "33.0.0-jre".to_string(),
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
+
+        let result = GradleCatalogUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert_eq!(result.ignored.len(), 1);
+        assert_eq!(result.pinned.len(), 1);
+        assert_eq!(result.updated.len(), 1);
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(r#"kotlin = "1.9.22""#));
+        assert!(content.contains(r#"guava = "33.0.0-jre""#));
+        assert!(content.contains(r#"junit = "5.11.3""#));
+    }
+}
@@ -1,6 +1,6 @@
 use super::{
     FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
-    read_file_safe, write_file_atomic,
+    read_file_safe,
 };
 use crate::align::compare_versions;
 use crate::registry::Registry;
@@ -140,6 +140,7 @@ impl MiseUpdater {
                         line_number: Some(line_idx + 1),
                         has_upper_bound: false,
                         is_bumpable: true,
+                        inherits_workspace: false,
                     });
                 }
             }
@@ -177,6 +178,7 @@ impl MiseUpdater {
                         line_number: Some(line_idx + 1),
                         has_upper_bound: false,
                         is_bumpable: true,
+                        inherits_workspace: false,
                     });
                 }
             }
@@ -243,6 +245,8 @@ impl Updater for MiseUpdater {
                 result.unchanged += 1;
             } else if options.should_ignore(&dep.name) {
                 ignored_tools.push((line_idx, dep.name, dep.version));
+            } else if let Some(warning) = options.range_pin_warning(&dep.name) {
+                result.warnings.push(warning);
             } else if let Some(pinned_version) = options.get_pinned_version(&dep.name) {
                 pinned_tools.push((line_idx, dep.name, dep.version, pinned_version.to_string()));
             } else {
@@ -446,7 +450,7 @@ impl Updater for MiseUpdater {
             }
         }
 
-        if (!result.updated.is_empty() || !result.pinned.is_empty()) && !options.dry_run {
+        if !result.updated.is_empty() || !result.pinned.is_empty() {
             let line_ending = if content.contains("\r\n") {
                 "\r\n"
             } else {
@@ -460,7 +464,7 @@ impl Updater for MiseUpdater {
                 new_content
             };
 
-            write_file_atomic(path, &final_content)?;
+            result.commit_content(path, &content, final_content, options.dry_run)?;
         }
 
         Ok(result)
@@ -814,6 +818,9 @@ uv = "latest"
             ignore: vec!["node".to_string()],
             pin: pins,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = MiseUpdater::new();
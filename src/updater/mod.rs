@@ -1,37 +1,57 @@
 mod cargo_toml;
+mod composer;
+mod conda_env;
 mod csproj;
+mod dockerfile;
 mod gemfile;
 mod github_actions;
 mod go_mod;
+mod gradle_catalog;
 mod mise;
 mod npm_range;
 mod package_json;
+mod pipfile;
+mod pnpm_workspace;
 mod pre_commit;
+mod pubspec;
 mod pyproject;
+mod python_markers;
 mod requirements;
+mod runtime_version;
+mod setup_py;
 mod terraform;
 
 pub use cargo_toml::CargoTomlUpdater;
+pub use composer::ComposerUpdater;
+pub use conda_env::CondaEnvUpdater;
 pub use csproj::CsprojUpdater;
+pub use dockerfile::DockerfileUpdater;
 pub use gemfile::GemfileUpdater;
 pub use github_actions::GithubActionsUpdater;
 pub use go_mod::GoModUpdater;
+pub use gradle_catalog::GradleCatalogUpdater;
 pub use mise::MiseUpdater;
 
 pub use package_json::PackageJsonUpdater;
+pub use pipfile::PipfileUpdater;
+pub use pnpm_workspace::PnpmWorkspaceUpdater;
 pub use pre_commit::PreCommitUpdater;
+pub use pubspec::PubspecUpdater;
 pub use pyproject::PyProjectUpdater;
 pub use requirements::RequirementsUpdater;
+pub use runtime_version::RuntimeVersionUpdater;
+pub use setup_py::SetupPyUpdater;
 pub use terraform::TerraformUpdater;
 
 use crate::config::UpdConfig;
 use crate::cooldown::CooldownPolicy;
-use crate::registry::Registry;
+use crate::registry::{Registry, VersionMeta};
 use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
 use ignore::WalkBuilder;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
 /// Maximum file size allowed for dependency files (10 MB)
@@ -65,6 +85,20 @@ pub(crate) fn downgrade_warning(pkg: &str, latest: &str, current: &str) -> Strin
     format!("skipping {pkg}: latest \"{latest}\" is not greater than current \"{current}\"")
 }
 
+/// Resolve a `[pin]` value to the version to write: an exact pin as-is, a
+/// range pin to the newest registry version inside the range.
+pub(crate) async fn resolve_pin(
+    registry: &dyn Registry,
+    package: &str,
+    pin: &str,
+) -> Result<String> {
+    if crate::config::is_range_pin(pin) {
+        registry.get_latest_version_matching(package, pin).await
+    } else {
+        Ok(pin.to_string())
+    }
+}
+
 /// UTF-8 byte-order mark, as bytes.
 const UTF8_BOM_BYTES: [u8; 3] = [0xEF, 0xBB, 0xBF];
 
@@ -99,8 +133,66 @@ fn apply_original_encoding(original: Option<&[u8]>, content: &str) -> Vec<u8> {
     out
 }
 
+/// Set by `--backup`: [`write_file_atomic`] keeps each file's previous content
+/// in a sibling backup that `upd rollback` restores.
+static WRITE_BACKUPS: AtomicBool = AtomicBool::new(false);
+
+/// Make every later [`write_file_atomic`] call back up the file it replaces.
+pub fn enable_backups() {
+    WRITE_BACKUPS.store(true, Ordering::Relaxed);
+}
+
+/// Where the `--backup` copy of `path` lives: `.{file_name}.upd.bak` beside it.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let parent = path.parent().unwrap_or(Path::new("."));
+    let file_name = path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    parent.join(format!(".{}.upd.bak", file_name))
+}
+
+/// Delete the backups of `paths` left by an earlier run, so a backup always
+/// holds the content from before the most recent `--backup` run.
+pub fn discard_backups<'a>(paths: impl IntoIterator<Item = &'a Path>) {
+    for path in paths {
+        let _ = std::fs::remove_file(backup_path(path));
+    }
+}
+
+/// Move the backup of `path` back into place. Returns false when there is no
+/// backup to restore.
+pub fn restore_backup(path: &Path) -> Result<bool> {
+    let backup = backup_path(path);
+    if !backup.is_file() {
+        return Ok(false);
+    }
+    std::fs::rename(&backup, path)?;
+    Ok(true)
+}
+
 /// Write a file atomically (write to temp file, then rename)
+///
+/// After [`enable_backups`], the original file is first copied to its
+/// [`backup_path`]. An existing backup is kept, so a file written twice in
+/// one run still backs up the content from before the run.
 pub fn write_file_atomic(path: &Path, content: &str) -> Result<()> {
+    // Capture the original file's bytes to preserve BOM + line endings.
+    let original_bytes = std::fs::read(path).ok();
+    let final_bytes = apply_original_encoding(original_bytes.as_deref(), content);
+
+    let backup = backup_path(path);
+    if WRITE_BACKUPS.load(Ordering::Relaxed) && original_bytes.is_some() && !backup.exists() {
+        std::fs::copy(path, &backup)?;
+    }
+
+    write_bytes_atomic(path, &final_bytes)
+}
+
+/// Replace `path` with exactly `bytes` through a temp file and rename, keeping
+/// its permissions. Unlike [`write_file_atomic`] the bytes are not re-encoded
+/// and no `--backup` copy is made.
+pub fn write_bytes_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
     use std::io::Write;
 
     // Create temp file in same directory to ensure atomic rename works
@@ -111,18 +203,15 @@ pub fn write_file_atomic(path: &Path, content: &str) -> Result<()> {
         .unwrap_or_else(|| "temp".to_string());
     let tmp_path = parent.join(format!(".{}.upd.tmp", file_name));
 
-    // Capture the original file's bytes (to preserve BOM + line endings) and
-    // permissions so the atomic rename does not silently change them. Without
-    // the permission capture, a read-only (0o444) manifest is rewritten as
-    // 0o644: the rename replaces the inode with the temp file, which was
-    // created with the umask default.
-    let original_bytes = std::fs::read(path).ok();
+    // Capture the permissions so the atomic rename does not silently change
+    // them. Without this, a read-only (0o444) manifest is rewritten as 0o644:
+    // the rename replaces the inode with the temp file, which was created with
+    // the umask default.
     let original_perms = std::fs::metadata(path).ok().map(|m| m.permissions());
-    let final_bytes = apply_original_encoding(original_bytes.as_deref(), content);
 
     // Write to temporary file
     let mut file = std::fs::File::create(&tmp_path)?;
-    file.write_all(&final_bytes)?;
+    file.write_all(bytes)?;
     file.sync_all()?;
 
     // Atomically rename to target path
@@ -216,6 +305,44 @@ impl BumpFilter {
     }
 }
 
+/// How far an update may move a dependency (`--strategy`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Strategy {
+    /// Move to the newest release.
+    #[default]
+    Latest,
+    /// Stay within the range the current version is compatible with: `^1.2.0`
+    /// stays below 2.0.0, `~1.2.0` below 1.3.0, and an exact or open-ended
+    /// version keeps its major (its minor, for `0.x`).
+    Compatible,
+    /// Stay one release behind: the highest version below the newest release,
+    /// so brand-new majors and minors are never adopted first.
+    #[value(name = "n-1")]
+    NMinusOne,
+}
+
+/// Registries for package indexes that dependency files declare themselves
+/// (a Pipfile `[[source]]`), keyed by the index URL as written in the file.
+#[derive(Clone, Default)]
+pub struct SourceRegistries(Arc<HashMap<String, Arc<dyn Registry>>>);
+
+impl SourceRegistries {
+    pub fn new(registries: HashMap<String, Arc<dyn Registry>>) -> Self {
+        Self(Arc::new(registries))
+    }
+
+    /// The registry serving the index at `url`, if one was built for it.
+    pub fn get(&self, url: &str) -> Option<&dyn Registry> {
+        self.0.get(url).map(|registry| registry.as_ref())
+    }
+}
+
+impl std::fmt::Debug for SourceRegistries {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
 /// Options for updating dependencies
 #[derive(Debug, Clone, Default)]
 pub struct UpdateOptions {
@@ -228,6 +355,10 @@ pub struct UpdateOptions {
     /// When non-empty, only packages whose name is in this set are processed.
     /// An empty set means "process all packages" (no filter active).
     pub packages: Vec<String>,
+    /// When non-empty, only dependency groups in this set are processed
+    /// (`--group`). Ungrouped (production) dependencies are skipped while a
+    /// group filter is active.
+    pub groups: Vec<String>,
     /// Active cooldown policy, if configured. None => cooldown disabled.
     pub cooldown_policy: Option<Arc<CooldownPolicy>>,
     /// Wall-clock used for cooldown decisions. None => `Utc::now()` at call time.
@@ -240,6 +371,29 @@ pub struct UpdateOptions {
     /// level, so updates are only skipped when `--only-bump` / `--max-bump`
     /// narrow it.
     pub bump_filter: BumpFilter,
+    /// Skip Cargo candidates whose declared `rust-version` exceeds the
+    /// project's (`--respect-msrv`).
+    pub respect_msrv: bool,
+    /// Update npm dependencies even when the new version's `engines.node`
+    /// excludes the Node versions package.json declares (`--ignore-engines`).
+    pub ignore_engines: bool,
+    /// Write npm versions the registry marks as deprecated instead of falling
+    /// back to the newest non-deprecated one (`--allow-deprecated`).
+    pub allow_deprecated: bool,
+    /// Report pyproject requirements whose markers contradict
+    /// `requires-python` (`--prune-dead-markers`). Lines are never removed.
+    pub prune_dead_markers: bool,
+    /// Constraints files (`-c`) inherited from requirements files that
+    /// include this one with `-r`; their entries cap version bumps.
+    pub constraint_files: Vec<PathBuf>,
+    /// Whether updates may leave the current version's compatible range.
+    pub strategy: Strategy,
+    /// Packages resolved against pre-releases even from a stable version
+    /// (`--allow-prerelease`); the config's `allow_prerelease` list adds to it.
+    pub allow_prerelease: Vec<String>,
+    /// Registries for the indexes dependency files declare, wrapped like the
+    /// default ones (cache, `--offline`, `--max-runtime`).
+    pub source_registries: SourceRegistries,
 }
 
 impl UpdateOptions {
@@ -250,10 +404,19 @@ impl UpdateOptions {
             full_precision,
             config: None,
             packages: Vec::new(),
+            groups: Vec::new(),
             cooldown_policy: None,
             cooldown_now: None,
             cooldown_unavailable_notes: Arc::default(),
             bump_filter: BumpFilter::default(),
+            respect_msrv: false,
+            ignore_engines: false,
+            allow_deprecated: false,
+            prune_dead_markers: false,
+            constraint_files: Vec::new(),
+            strategy: Strategy::default(),
+            allow_prerelease: Vec::new(),
+            source_registries: SourceRegistries::default(),
         }
     }
 
@@ -263,6 +426,98 @@ impl UpdateOptions {
         self
     }
 
+    /// Hold Cargo dependencies at versions whose MSRV the project supports.
+    pub fn with_respect_msrv(mut self, respect_msrv: bool) -> Self {
+        self.respect_msrv = respect_msrv;
+        self
+    }
+
+    /// Update npm dependencies regardless of their `engines.node` range.
+    pub fn with_ignore_engines(mut self, ignore_engines: bool) -> Self {
+        self.ignore_engines = ignore_engines;
+        self
+    }
+
+    /// Update npm dependencies to deprecated versions, with a warning.
+    pub fn with_allow_deprecated(mut self, allow_deprecated: bool) -> Self {
+        self.allow_deprecated = allow_deprecated;
+        self
+    }
+
+    /// Report requirements whose environment markers can never be satisfied.
+    pub fn with_prune_dead_markers(mut self, prune_dead_markers: bool) -> Self {
+        self.prune_dead_markers = prune_dead_markers;
+        self
+    }
+
+    /// Resolve packages of files that declare their own index with these
+    /// registries.
+    pub fn with_source_registries(mut self, source_registries: SourceRegistries) -> Self {
+        self.source_registries = source_registries;
+        self
+    }
+
+    /// Apply the `-c` constraints files of the requirements files including
+    /// this one.
+    pub fn with_constraint_files(mut self, constraint_files: Vec<PathBuf>) -> Self {
+        self.constraint_files = constraint_files;
+        self
+    }
+
+    /// Choose between the newest release and the newest compatible one.
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Let the named packages update into pre-releases.
+    pub fn with_allow_prerelease(mut self, packages: Vec<String>) -> Self {
+        self.allow_prerelease = packages;
+        self
+    }
+
+    /// Returns `true` when `package` should be looked up including
+    /// pre-releases whatever its current version, because `--allow-prerelease`
+    /// or the config's `allow_prerelease` names it.
+    pub fn allows_prerelease(&self, package: &str) -> bool {
+        let target = crate::config::normalize_package_name(package);
+        self.allow_prerelease
+            .iter()
+            .any(|p| crate::config::normalize_package_name(p) == target)
+            || self
+                .config
+                .as_ref()
+                .is_some_and(|c| c.allows_prerelease(package))
+    }
+
+    /// The semver requirement to resolve a `current` version against under
+    /// `--strategy compatible` (`^{current}`), or `None` for the latest release.
+    pub fn semver_compatible_req(&self, current: &str) -> Option<String> {
+        (self.strategy == Strategy::Compatible).then(|| format!("^{current}"))
+    }
+
+    /// The PEP 440 specifier to resolve a requirement against.
+    ///
+    /// Under `--strategy compatible`, a specifier without an upper bound
+    /// (`==1.4.2`, `>=1.4`) is replaced by the caret range of `current`,
+    /// keeping any `!=` exclusions. Otherwise the specifier is used as written.
+    pub fn pep440_resolution_spec(&self, spec: &str, current: &str) -> String {
+        let clauses: Vec<&str> = spec.split(',').map(str::trim).collect();
+        let bounded = clauses
+            .iter()
+            .any(|clause| clause.starts_with('<') || clause.starts_with("~="));
+        if self.strategy != Strategy::Compatible || bounded {
+            return spec.to_string();
+        }
+        let Some(range) = crate::version::pep440_caret_range(current) else {
+            return spec.to_string();
+        };
+        std::iter::once(range.as_str())
+            .chain(clauses.into_iter().filter(|c| c.starts_with("!=")))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
     /// Returns `true` when an update from `current` to `new` is within the
     /// permitted bump levels. Updaters consult this immediately before recording
     /// and writing a change so a capped-out update never reaches disk.
@@ -288,6 +543,19 @@ impl UpdateOptions {
         !self.packages.is_empty() && !self.packages.iter().any(|p| p == package)
     }
 
+    /// Restrict processing to the named dependency groups.
+    pub fn with_groups(mut self, groups: Vec<String>) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    /// Returns `true` when dependencies in `group` should be skipped because a
+    /// `--group` filter is active and does not name it. `None` is the
+    /// ungrouped (production) dependency set.
+    pub fn is_group_filtered_out(&self, group: Option<&str>) -> bool {
+        !self.groups.is_empty() && !group.is_some_and(|g| self.groups.iter().any(|n| n == g))
+    }
+
     /// Check if a package should be ignored
     pub fn should_ignore(&self, package: &str) -> bool {
         self.config
@@ -303,6 +571,16 @@ impl UpdateOptions {
             .and_then(|c| c.get_pinned_version(package))
     }
 
+    /// Warning for a range pin (`>=4.2,<5`) on `package` in a format that only
+    /// takes exact pins; such a dependency is left unchanged.
+    pub fn range_pin_warning(&self, package: &str) -> Option<String> {
+        self.get_pinned_version(package)
+            .filter(|pin| crate::config::is_range_pin(pin))
+            .map(|pin| {
+                format!("skipping {package}: range pin \"{pin}\" is not supported for this file")
+            })
+    }
+
     /// Activate a cooldown policy with a fixed reference time for decisions.
     pub fn with_cooldown_policy(mut self, policy: CooldownPolicy, now: DateTime<Utc>) -> Self {
         self.cooldown_policy = Some(Arc::new(policy));
@@ -344,6 +622,62 @@ pub struct ParsedDependency {
     /// Such entries are still included so that audit paths can see them, but the
     /// update path and alignment logic must not attempt to bump them.
     pub is_bumpable: bool,
+    /// Whether the requirement is inherited from the workspace root
+    /// (Cargo's `foo = { workspace = true }`). `version` is then the root's
+    /// `[workspace.dependencies]` version and `is_bumpable` is `false`: the
+    /// root definition is the one alignment and updates rewrite.
+    pub inherits_workspace: bool,
+}
+
+/// A dependency declared without a lower bound (npm `"*"`, a bare PyPI name,
+/// Cargo `"*"`, ...), reported by `--treat-unconstrained-as-error`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnconstrainedDependency {
+    /// Package name
+    pub name: String,
+    /// Specifier as declared; empty for a bare name
+    pub spec: String,
+    /// Line number in the file (1-indexed)
+    pub line_number: Option<usize>,
+}
+
+/// True if a PEP 440 specifier set sets a floor (`==`, `===`, `>=`, `>`, `~=`).
+/// `<2` or `!=1.5` alone admit every older release.
+pub(crate) fn pep440_has_lower_bound(spec: &str) -> bool {
+    spec.split(',').any(|clause| {
+        let clause = clause.trim();
+        clause.starts_with("==") || clause.starts_with('>') || clause.starts_with("~=")
+    })
+}
+
+/// Split a PEP 508 requirement into name and version specifier, dropping
+/// extras and environment markers. Direct references (`name @ url`) and
+/// anything else that is not `name [specifier]` yield `None`.
+pub(crate) fn split_pep508(requirement: &str) -> Option<(String, String)> {
+    let requirement = requirement.split(';').next().unwrap_or(requirement).trim();
+    if !requirement.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    let name_end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    let mut rest = requirement[name_end..].trim_start();
+    if let Some(extras) = rest.strip_prefix('[') {
+        rest = extras.split_once(']')?.1.trim_start();
+    }
+    let spec = rest.trim_start_matches('(').trim_end_matches(')').trim();
+    (spec.is_empty() || spec.starts_with(['<', '>', '=', '!', '~']))
+        .then(|| (requirement[..name_end].to_string(), spec.to_string()))
+}
+
+/// 1-indexed line of the first quoted string literal equal to `literal`.
+pub(crate) fn line_of_quoted_literal(content: &str, literal: &str) -> Option<usize> {
+    let double = format!("\"{}\"", literal);
+    let single = format!("'{}'", literal);
+    content
+        .lines()
+        .position(|line| line.contains(&double) || line.contains(&single))
+        .map(|idx| idx + 1)
 }
 
 /// Result of updating a single file
@@ -359,8 +693,17 @@ pub struct UpdateResult {
     pub warnings: Vec<String>,
     /// Packages that were ignored due to config: (name, current_version, line_number)
     pub ignored: Vec<(String, String, Option<usize>)>,
+    /// Dependencies not resolved from a registry (git or path sources, or an
+    /// unconfigured alternate registry): (name, reason, line_number)
+    pub skipped_sources: Vec<(String, String, Option<usize>)>,
     /// Packages that were pinned to a specific version: (name, current_version, pinned_version, line_number)
     pub pinned: Vec<(String, String, String, Option<usize>)>,
+    /// Updates found but not written because the config's `auto_apply` policy
+    /// excludes their bump level: (name, current_version, available_version, line_number)
+    pub not_applied: Vec<(String, String, String, Option<usize>)>,
+    /// Updates found but left for a later run by `--newest-only`:
+    /// (name, current_version, available_version, line_number)
+    pub deferred: Vec<(String, String, String, Option<usize>)>,
     /// Packages where cooldown forced us to a safer-older version than the
     /// absolute latest. Tuple: (name, old_version, chosen_version,
     /// skipped_latest_version, skipped_latest_published_at).
@@ -369,18 +712,86 @@ pub struct UpdateResult {
     /// we kept the current version. Tuple: (name, current_version,
     /// skipped_latest_version, skipped_latest_published_at).
     pub skipped_by_cooldown: Vec<(String, String, String, DateTime<Utc>)>,
+    /// Lookups skipped by `--offline` because nothing was cached. Kept apart
+    /// from `errors` so they neither fail the run nor read as registry faults.
+    pub offline_skipped: Vec<String>,
+    /// Lookups skipped or abandoned because the `--max-runtime` deadline
+    /// passed. Kept apart from `errors`; the run exits with the timed-out
+    /// status instead.
+    pub timed_out: Vec<String>,
+    /// Before/after content of the file when the updater rewrote it (also
+    /// populated in dry-run). Per-file only; not carried over by `merge`.
+    pub content_change: Option<ContentChange>,
+}
+
+/// Original and rewritten content of a dependency file, kept for `--diff`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentChange {
+    pub original: String,
+    pub updated: String,
 }
 
 impl UpdateResult {
+    /// Record the rewritten content of `path` and write it unless `dry_run`.
+    /// Updaters call this once they have built the new file content.
+    pub(crate) fn commit_content(
+        &mut self,
+        path: &Path,
+        original: &str,
+        updated: String,
+        dry_run: bool,
+    ) -> Result<()> {
+        if !dry_run {
+            write_file_atomic(path, &updated)?;
+        }
+        self.content_change = Some(ContentChange {
+            original: original.to_string(),
+            updated,
+        });
+        Ok(())
+    }
+
+    /// Move `--offline` cache misses out of `errors` into `offline_skipped`.
+    pub fn separate_offline_misses(&mut self) {
+        let (offline, errors): (Vec<String>, Vec<String>) = std::mem::take(&mut self.errors)
+            .into_iter()
+            .partition(|e| crate::cache::is_offline_miss(e));
+        self.errors = errors;
+        self.offline_skipped.extend(offline);
+    }
+
+    /// Move `--max-runtime` deadline misses out of `errors` into `timed_out`.
+    pub fn separate_deadline_misses(&mut self) {
+        let (timed_out, errors): (Vec<String>, Vec<String>) = std::mem::take(&mut self.errors)
+            .into_iter()
+            .partition(|e| crate::cache::is_deadline_miss(e));
+        self.errors = errors;
+        self.timed_out.extend(timed_out);
+    }
+
+    /// The first genuine lookup error: `--offline` and `--max-runtime`
+    /// misses are reported apart and do not count.
+    pub fn first_error(&self) -> Option<&str> {
+        self.errors
+            .iter()
+            .map(String::as_str)
+            .find(|e| !crate::cache::is_offline_miss(e) && !crate::cache::is_deadline_miss(e))
+    }
+
     pub fn merge(&mut self, other: UpdateResult) {
         self.updated.extend(other.updated);
         self.unchanged += other.unchanged;
         self.errors.extend(other.errors);
         self.warnings.extend(other.warnings);
         self.ignored.extend(other.ignored);
+        self.skipped_sources.extend(other.skipped_sources);
         self.pinned.extend(other.pinned);
+        self.not_applied.extend(other.not_applied);
+        self.deferred.extend(other.deferred);
         self.held_back.extend(other.held_back);
         self.skipped_by_cooldown.extend(other.skipped_by_cooldown);
+        self.offline_skipped.extend(other.offline_skipped);
+        self.timed_out.extend(other.timed_out);
     }
 }
 
@@ -405,6 +816,11 @@ pub enum Lang {
     PreCommit,
     Mise,
     Terraform,
+    Docker,
+    Php,
+    Jvm,
+    Dart,
+    Runtime,
 }
 
 impl Lang {
@@ -421,33 +837,55 @@ impl Lang {
             Lang::PreCommit => "pre_commit",
             Lang::Mise => "mise",
             Lang::Terraform => "terraform",
+            Lang::Docker => "docker",
+            Lang::Php => "php",
+            Lang::Jvm => "jvm",
+            Lang::Dart => "dart",
+            Lang::Runtime => "runtime",
         }
     }
 }
 
 /// Type of dependency file
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+#[value(rename_all = "snake_case")]
 pub enum FileType {
     Requirements,
+    #[value(name = "pyproject")]
     PyProject,
+    Pipfile,
+    SetupPy,
+    CondaEnv,
     PackageJson,
+    PnpmWorkspace,
     CargoToml,
     GoMod,
     Gemfile,
     Csproj,
     GithubActions,
+    #[value(name = "pre_commit")]
     PreCommitConfig,
     MiseToml,
     ToolVersions,
     TerraformTf,
+    Dockerfile,
+    ComposerJson,
+    GradleCatalog,
+    Pubspec,
+    Nvmrc,
+    PythonVersion,
 }
 
 impl FileType {
     /// Get the language/ecosystem for this file type
     pub fn lang(&self) -> Lang {
         match self {
-            FileType::Requirements | FileType::PyProject => Lang::Python,
-            FileType::PackageJson => Lang::Node,
+            FileType::Requirements
+            | FileType::PyProject
+            | FileType::Pipfile
+            | FileType::SetupPy
+            | FileType::CondaEnv => Lang::Python,
+            FileType::PackageJson | FileType::PnpmWorkspace => Lang::Node,
             FileType::CargoToml => Lang::Rust,
             FileType::GoMod => Lang::Go,
             FileType::Gemfile => Lang::Ruby,
@@ -456,6 +894,11 @@ impl FileType {
             FileType::PreCommitConfig => Lang::PreCommit,
             FileType::MiseToml | FileType::ToolVersions => Lang::Mise,
             FileType::TerraformTf => Lang::Terraform,
+            FileType::Dockerfile => Lang::Docker,
+            FileType::ComposerJson => Lang::Php,
+            FileType::GradleCatalog => Lang::Jvm,
+            FileType::Pubspec => Lang::Dart,
+            FileType::Nvmrc | FileType::PythonVersion => Lang::Runtime,
         }
     }
 
@@ -464,7 +907,11 @@ impl FileType {
         match self {
             FileType::Requirements => "requirements",
             FileType::PyProject => "pyproject",
+            FileType::Pipfile => "pipfile",
+            FileType::SetupPy => "setup_py",
+            FileType::CondaEnv => "conda_env",
             FileType::PackageJson => "package_json",
+            FileType::PnpmWorkspace => "pnpm_workspace",
             FileType::CargoToml => "cargo_toml",
             FileType::GoMod => "go_mod",
             FileType::Gemfile => "gemfile",
@@ -474,6 +921,12 @@ impl FileType {
             FileType::MiseToml => "mise_toml",
             FileType::ToolVersions => "tool_versions",
             FileType::TerraformTf => "terraform_tf",
+            FileType::Dockerfile => "dockerfile",
+            FileType::ComposerJson => "composer_json",
+            FileType::GradleCatalog => "gradle_catalog",
+            FileType::Pubspec => "pubspec",
+            FileType::Nvmrc => "nvmrc",
+            FileType::PythonVersion => "python_version",
         }
     }
 }
@@ -486,10 +939,22 @@ impl FileType {
             return Some(FileType::PyProject);
         }
 
+        if file_name == "Pipfile" {
+            return Some(FileType::Pipfile);
+        }
+
+        if file_name == "setup.py" {
+            return Some(FileType::SetupPy);
+        }
+
         if file_name == "package.json" {
             return Some(FileType::PackageJson);
         }
 
+        if file_name == "pnpm-workspace.yaml" {
+            return Some(FileType::PnpmWorkspace);
+        }
+
         if file_name == "Cargo.toml" {
             return Some(FileType::CargoToml);
         }
@@ -502,6 +967,29 @@ impl FileType {
             return Some(FileType::Gemfile);
         }
 
+        if file_name == "composer.json" {
+            return Some(FileType::ComposerJson);
+        }
+
+        // Gradle version catalogs: `gradle/libs.versions.toml` and any other
+        // `*.versions.toml` kept in the `gradle/` directory.
+        if file_name.ends_with(".versions.toml")
+            && path
+                .parent()
+                .and_then(|dir| dir.file_name())
+                .is_some_and(|dir| dir == "gradle")
+        {
+            return Some(FileType::GradleCatalog);
+        }
+
+        if file_name == "pubspec.yaml" {
+            return Some(FileType::Pubspec);
+        }
+
+        if file_name == "Dockerfile" || file_name.ends_with(".Dockerfile") {
+            return Some(FileType::Dockerfile);
+        }
+
         // .csproj files (case-insensitive extension check)
         if file_name
             .rsplit('.')
@@ -530,6 +1018,14 @@ impl FileType {
             return Some(FileType::ToolVersions);
         }
 
+        if file_name == ".nvmrc" {
+            return Some(FileType::Nvmrc);
+        }
+
+        if file_name == ".python-version" {
+            return Some(FileType::PythonVersion);
+        }
+
         // GitHub Actions workflows: *.yml or *.yaml inside .github/workflows/
         if (file_name.ends_with(".yml") || file_name.ends_with(".yaml"))
             && let Some(parent) = path.parent()
@@ -543,6 +1039,11 @@ impl FileType {
             return Some(FileType::GithubActions);
         }
 
+        // Conda environments; checked after workflows, which may share the name
+        if file_name == "environment.yml" || file_name == "environment.yaml" {
+            return Some(FileType::CondaEnv);
+        }
+
         // Terraform .tf files (exclude files inside .terraform/ directories)
         if file_name.ends_with(".tf") {
             let path_str = path.to_string_lossy();
@@ -603,6 +1104,12 @@ pub trait Updater: Send + Sync {
 
     /// Parse dependencies from a file (for alignment purposes)
     fn parse_dependencies(&self, path: &Path) -> Result<Vec<ParsedDependency>>;
+
+    /// Dependencies declared without a lower bound. Ecosystems whose
+    /// manifests always carry an exact version report none.
+    fn unconstrained_dependencies(&self, _path: &Path) -> Result<Vec<UnconstrainedDependency>> {
+        Ok(Vec::new())
+    }
 }
 
 /// Outcome of applying the cooldown layer to a resolved `(current -> latest)`
@@ -626,9 +1133,42 @@ pub enum CooldownOutcome {
     },
 }
 
-/// Apply the active cooldown policy to a resolved `(current -> latest)` pair.
-/// Returns the outcome plus an optional diagnostic note the caller should
-/// stash on `UpdateOptions::note_cooldown_unavailable` for later reporting.
+/// The `--strategy n-1` target for a resolved `latest`: the highest release
+/// on the current track (stable or prerelease) below the newest one, capped
+/// at `latest`. `None` when no such release is newer than `current`.
+fn select_n_minus_one(
+    versions: &[VersionMeta],
+    current: &str,
+    latest: &str,
+    current_is_prerelease: bool,
+) -> Option<String> {
+    use crate::version::compare::compare_versions;
+    use std::cmp::Ordering;
+
+    let mut candidates: Vec<&str> = versions
+        .iter()
+        .filter(|v| !v.yanked && v.prerelease == current_is_prerelease)
+        .map(|v| v.version.as_str())
+        .collect();
+    candidates.sort_by(|a, b| compare_versions(b, a));
+    let newest = *candidates.first()?;
+
+    // A constraint already held `latest` below the newest release.
+    let target = if compare_versions(latest, newest) == Ordering::Less {
+        latest
+    } else {
+        candidates.into_iter().find(|v| {
+            compare_versions(v, newest) == Ordering::Less
+                && compare_versions(v, latest) != Ordering::Greater
+        })?
+    };
+    (compare_versions(target, current) == Ordering::Greater).then(|| target.to_string())
+}
+
+/// Apply `--strategy n-1` and the active cooldown policy to a resolved
+/// `(current -> latest)` pair. Returns the outcome plus an optional diagnostic
+/// note the caller should stash on `UpdateOptions::note_cooldown_unavailable`
+/// for later reporting.
 pub async fn apply_cooldown(
     registry: &dyn Registry,
     package: &str,
@@ -639,17 +1179,28 @@ pub async fn apply_cooldown(
     options: &UpdateOptions,
 ) -> (CooldownOutcome, Option<String>) {
     let ecosystem = registry.name();
-    let Some(policy) = options.cooldown_policy.as_ref() else {
-        return (CooldownOutcome::Unchanged(latest.to_string()), None);
-    };
-    let cooldown = policy.effective_for(ecosystem);
-    if cooldown <= chrono::Duration::zero() {
+    let cooldown = options
+        .cooldown_policy
+        .as_ref()
+        .map(|policy| policy.effective_for(ecosystem))
+        .filter(|cooldown| *cooldown > chrono::Duration::zero());
+    let n_minus_one = options.strategy == Strategy::NMinusOne;
+    if cooldown.is_none() && !n_minus_one {
         return (CooldownOutcome::Unchanged(latest.to_string()), None);
     }
-    let now = options.cooldown_now.unwrap_or_else(Utc::now);
 
     let versions = match registry.list_versions(package).await {
         Ok(v) if !v.is_empty() => v,
+        // Without a version list there is no release to stay behind on, so
+        // the cautious strategy keeps the current version.
+        _ if n_minus_one => {
+            return (
+                CooldownOutcome::Unchanged(current.to_string()),
+                Some(format!(
+                    "--strategy n-1 unavailable for {ecosystem}; current versions kept"
+                )),
+            );
+        }
         _ => {
             return (
                 CooldownOutcome::Unchanged(latest.to_string()),
@@ -658,11 +1209,33 @@ pub async fn apply_cooldown(
         }
     };
 
+    let (latest, versions) = if n_minus_one {
+        let Some(target) = select_n_minus_one(&versions, current, latest, current_is_prerelease)
+        else {
+            return (CooldownOutcome::Unchanged(current.to_string()), None);
+        };
+        // Cooldown may only hold back further, never past the n-1 target.
+        let versions = versions
+            .into_iter()
+            .filter(|v| {
+                crate::version::compare::compare_versions(&v.version, &target)
+                    != std::cmp::Ordering::Greater
+            })
+            .collect::<Vec<_>>();
+        (target, versions)
+    } else {
+        (latest.to_string(), versions)
+    };
+    let Some(cooldown) = cooldown else {
+        return (CooldownOutcome::Unchanged(latest), None);
+    };
+    let now = options.cooldown_now.unwrap_or_else(Utc::now);
+
     use crate::cooldown::{CooldownDecision, select};
     match select(
         &versions,
         current,
-        latest,
+        &latest,
         constraints,
         current_is_prerelease,
         cooldown,
@@ -691,7 +1264,7 @@ pub async fn apply_cooldown(
             None,
         ),
         CooldownDecision::Unsupported => (
-            CooldownOutcome::Unchanged(latest.to_string()),
+            CooldownOutcome::Unchanged(latest),
             Some(format!("cooldown unavailable for {ecosystem}")),
         ),
     }
@@ -707,6 +1280,8 @@ const ALLOWED_HIDDEN_ENTRIES: &[&str] = &[
     ".pre-commit-config.yaml",
     ".mise.toml",
     ".tool-versions",
+    ".nvmrc",
+    ".python-version",
 ];
 
 /// Knobs for [`discover_files_with`].
@@ -716,6 +1291,10 @@ pub struct DiscoverOptions<'a> {
     /// gitignore — walk every dependency file regardless. Mirrors
     /// `rg --no-ignore`.
     pub no_ignore: bool,
+    /// When true, descend into hidden directories and yield hidden files
+    /// instead of pruning everything outside [`ALLOWED_HIDDEN_ENTRIES`].
+    /// `.git` is still never walked. Mirrors `rg --hidden`.
+    pub include_hidden: bool,
     /// When true, emit one `skipping <path>: gitignored` (or `excluded by
     /// config`) line on stderr for each dependency file discovery dropped, so
     /// users can see why `upd` is silent on a given file.
@@ -726,21 +1305,26 @@ pub struct DiscoverOptions<'a> {
     /// makes a pattern depth-independent. Explicit file-path arguments bypass
     /// this list, mirroring the gitignore bypass for explicit files.
     pub exclude: &'a [String],
+    /// Path glob patterns from `--exclude`, matched against each file's path
+    /// relative to the scan root it was discovered under. Explicit file-path
+    /// arguments bypass them, like `exclude`.
+    pub relative_exclude: &'a [String],
 }
 
 /// Discover dependency files in the given paths, optionally filtered by language.
 ///
 /// Directory walks honor `.gitignore`, `.git/info/exclude`, and the global
 /// gitignore — even outside a git repository. Hidden directories and files are
-/// skipped except for the small allowlist in [`ALLOWED_HIDDEN_ENTRIES`]. Explicit
-/// file paths bypass the filter and are always processed.
+/// skipped except for the small allowlist in [`ALLOWED_HIDDEN_ENTRIES`]; see
+/// [`DiscoverOptions`] to relax either rule. Explicit file paths bypass the
+/// filter and are always processed.
 ///
 /// Convenience wrapper around [`discover_files_with`] with default options.
 pub fn discover_files(paths: &[PathBuf], langs: &[Lang]) -> Vec<(PathBuf, FileType)> {
     discover_files_with(paths, langs, DiscoverOptions::default())
 }
 
-/// Compile the config `exclude` globs into a matcher.
+/// Compile `exclude` globs (config or `--exclude`) into a matcher.
 ///
 /// Returns `None` when there are no usable patterns. Individual invalid
 /// patterns emit a warning on stderr and are skipped so one typo does not
@@ -781,7 +1365,8 @@ pub fn discover_files_with(
     langs: &[Lang],
     options: DiscoverOptions<'_>,
 ) -> Vec<(PathBuf, FileType)> {
-    let after_gitignore = walk_dependency_files(paths, langs, options.no_ignore);
+    let after_gitignore =
+        walk_dependency_files(paths, langs, options.no_ignore, options.include_hidden);
 
     // Explicit file-path arguments bypass the exclude list, just as they bypass
     // gitignore (the directory walker is never consulted for them).
@@ -792,14 +1377,27 @@ pub fn discover_files_with(
         .collect();
 
     let exclude_set = build_exclude_set(options.exclude);
+    let relative_exclude_set = build_exclude_set(options.relative_exclude);
+    let scan_roots: Vec<&Path> = paths
+        .iter()
+        .filter(|p| p.is_dir())
+        .map(|p| p.as_path())
+        .collect();
 
     let mut kept: Vec<(PathBuf, FileType)> = Vec::with_capacity(after_gitignore.len());
-    let mut excluded: Vec<PathBuf> = Vec::new();
+    let mut excluded: Vec<(PathBuf, &str)> = Vec::new();
     for (path, file_type) in after_gitignore {
-        let dropped = !explicit_files.contains(path.as_path())
-            && exclude_set.as_ref().is_some_and(|set| set.is_match(&path));
-        if dropped {
-            excluded.push(path);
+        if explicit_files.contains(path.as_path()) {
+            kept.push((path, file_type));
+        } else if exclude_set.as_ref().is_some_and(|set| set.is_match(&path)) {
+            excluded.push((path, "excluded by config"));
+        } else if relative_exclude_set.as_ref().is_some_and(|set| {
+            scan_roots
+                .iter()
+                .filter_map(|root| path.strip_prefix(root).ok())
+                .any(|relative| set.is_match(relative))
+        }) {
+            excluded.push((path, "excluded by --exclude"));
         } else {
             kept.push((path, file_type));
         }
@@ -811,11 +1409,11 @@ pub fn discover_files_with(
         // Gitignored files: present without ignore rules but absent after them.
         // Diff against the pre-exclude set so exclude drops are not mislabeled.
         if !options.no_ignore {
-            let unrestricted = walk_dependency_files(paths, langs, true);
+            let unrestricted = walk_dependency_files(paths, langs, true, options.include_hidden);
             let after_gitignore_set: std::collections::HashSet<&Path> = kept
                 .iter()
                 .map(|(p, _)| p.as_path())
-                .chain(excluded.iter().map(|p| p.as_path()))
+                .chain(excluded.iter().map(|(p, _)| p.as_path()))
                 .collect();
             for (path, _) in &unrestricted {
                 if !after_gitignore_set.contains(path.as_path()) {
@@ -823,8 +1421,8 @@ pub fn discover_files_with(
                 }
             }
         }
-        for path in &excluded {
-            eprintln!("skipping {}: excluded by config", path.display());
+        for (path, reason) in &excluded {
+            eprintln!("skipping {}: {reason}", path.display());
         }
     }
 
@@ -835,6 +1433,7 @@ fn walk_dependency_files(
     paths: &[PathBuf],
     langs: &[Lang],
     no_ignore: bool,
+    include_hidden: bool,
 ) -> Vec<(PathBuf, FileType)> {
     let mut files = Vec::new();
 
@@ -858,7 +1457,7 @@ fn walk_dependency_files(
             .git_global(!no_ignore)
             .git_exclude(!no_ignore)
             .require_git(false)
-            .filter_entry(|entry| {
+            .filter_entry(move |entry| {
                 // Always traverse the user-supplied root, even when it is hidden
                 // (e.g. `upd .github/workflows`).
                 if entry.depth() == 0 {
@@ -872,7 +1471,7 @@ fn walk_dependency_files(
                     return false;
                 }
 
-                if !name.starts_with('.') {
+                if include_hidden || !name.starts_with('.') {
                     return true;
                 }
 
@@ -896,6 +1495,28 @@ fn walk_dependency_files(
     files
 }
 
+/// `files` plus every requirements file their `-r`/`--requirement` lines
+/// reach, resolved relative to the including file. A file reached from
+/// several roots (or through a cycle) is listed once; `-c` constraints files
+/// are not followed here since they are never rewritten.
+pub fn with_included_requirements(files: Vec<(PathBuf, FileType)>) -> Vec<(PathBuf, FileType)> {
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut seen: std::collections::HashSet<PathBuf> =
+        files.iter().map(|(path, _)| canonical(path)).collect();
+    let mut extended = files.clone();
+    for (path, file_type) in &files {
+        if *file_type != FileType::Requirements {
+            continue;
+        }
+        for included in RequirementsUpdater::included_requirement_files(path) {
+            if seen.insert(canonical(&included)) {
+                extended.push((included, FileType::Requirements));
+            }
+        }
+    }
+    extended
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1051,6 +1672,63 @@ mod tests {
         assert_eq!(result1.pinned[1].0, "pinned2");
     }
 
+    #[test]
+    fn test_split_pep508_and_lower_bounds() {
+        assert_eq!(
+            split_pep508("uvicorn[standard] >=0.30 ; python_version >= '3.8'"),
+            Some(("uvicorn".to_string(), ">=0.30".to_string()))
+        );
+        assert_eq!(
+            split_pep508("requests"),
+            Some(("requests".to_string(), String::new()))
+        );
+        assert_eq!(
+            split_pep508("six (>=1.16)"),
+            Some(("six".to_string(), ">=1.16".to_string()))
+        );
+        assert_eq!(split_pep508("mylib @ https://example.com/mylib.whl"), None);
+        assert_eq!(split_pep508("./local"), None);
+
+        assert!(pep440_has_lower_bound("==1.0"));
+        assert!(pep440_has_lower_bound("<2, >=1.0"));
+        assert!(pep440_has_lower_bound("~=1.4"));
+        assert!(!pep440_has_lower_bound(""));
+        assert!(!pep440_has_lower_bound("<2"));
+        assert!(!pep440_has_lower_bound("!=1.5"));
+    }
+
+    #[test]
+    fn test_compatible_strategy_resolution_specs() {
+        let latest = UpdateOptions::new(false, false);
+        assert_eq!(latest.semver_compatible_req("1.2.0"), None);
+        assert_eq!(latest.pep440_resolution_spec("==1.4.2", "1.4.2"), "==1.4.2");
+
+        let compatible = UpdateOptions::new(false, false).with_strategy(Strategy::Compatible);
+        assert_eq!(
+            compatible.semver_compatible_req("1.2.0").as_deref(),
+            Some("^1.2.0")
+        );
+        assert_eq!(
+            compatible.pep440_resolution_spec("==1.4.2", "1.4.2"),
+            ">=1.4.2,<2"
+        );
+        assert_eq!(
+            compatible.pep440_resolution_spec(">=0.4, !=0.5.1", "0.4"),
+            ">=0.4,<0.5,!=0.5.1"
+        );
+        // Specifiers that already carry a ceiling keep it.
+        assert_eq!(
+            compatible.pep440_resolution_spec(">=1.0,<3", "1.0"),
+            ">=1.0,<3"
+        );
+        assert_eq!(compatible.pep440_resolution_spec("~=1.4", "1.4"), "~=1.4");
+        // Pre-releases track their own release line.
+        assert_eq!(
+            compatible.pep440_resolution_spec("==2.0.0rc1", "2.0.0rc1"),
+            "==2.0.0rc1"
+        );
+    }
+
     #[test]
     fn test_update_result_default() {
         let result = UpdateResult::default();
@@ -1064,7 +1742,11 @@ mod tests {
         let variants = [
             FileType::Requirements,
             FileType::PyProject,
+            FileType::Pipfile,
+            FileType::SetupPy,
+            FileType::CondaEnv,
             FileType::PackageJson,
+            FileType::PnpmWorkspace,
             FileType::CargoToml,
             FileType::GoMod,
             FileType::Gemfile,
@@ -1074,10 +1756,21 @@ mod tests {
             FileType::MiseToml,
             FileType::ToolVersions,
             FileType::TerraformTf,
+            FileType::Dockerfile,
+            FileType::ComposerJson,
+            FileType::GradleCatalog,
+            FileType::Pubspec,
+            FileType::Nvmrc,
+            FileType::PythonVersion,
         ];
         let mut seen = std::collections::HashSet::new();
         for ft in variants {
             let name = ft.as_str();
+            assert_eq!(
+                clap::ValueEnum::to_possible_value(&ft).unwrap().get_name(),
+                name,
+                "the --stdin-format value must match FileType::as_str"
+            );
             assert!(
                 seen.insert(name),
                 "duplicate FileType::as_str value: {name}"
@@ -1105,6 +1798,11 @@ mod tests {
             Lang::PreCommit,
             Lang::Mise,
             Lang::Terraform,
+            Lang::Docker,
+            Lang::Php,
+            Lang::Jvm,
+            Lang::Dart,
+            Lang::Runtime,
         ];
         let mut seen = std::collections::HashSet::new();
         for lang in variants {
@@ -1213,6 +1911,66 @@ mod tests {
             Some(FileType::PyProject)
         );
 
+        // Pipfile (but not its lockfile)
+        assert_eq!(
+            FileType::detect(Path::new("/some/path/Pipfile")),
+            Some(FileType::Pipfile)
+        );
+        assert_eq!(FileType::detect(Path::new("Pipfile.lock")), None);
+
+        // setup.py
+        assert_eq!(
+            FileType::detect(Path::new("/some/path/setup.py")),
+            Some(FileType::SetupPy)
+        );
+        assert_eq!(FileType::detect(Path::new("setup.cfg")), None);
+
+        // Conda environments
+        assert_eq!(
+            FileType::detect(Path::new("/some/path/environment.yml")),
+            Some(FileType::CondaEnv)
+        );
+        assert_eq!(
+            FileType::detect(Path::new("environment.yaml")),
+            Some(FileType::CondaEnv)
+        );
+
+        // Dockerfiles
+        assert_eq!(
+            FileType::detect(Path::new("/some/path/Dockerfile")),
+            Some(FileType::Dockerfile)
+        );
+        assert_eq!(
+            FileType::detect(Path::new("api.Dockerfile")),
+            Some(FileType::Dockerfile)
+        );
+        assert_eq!(FileType::detect(Path::new("Dockerfile.dev")), None);
+
+        // Composer
+        assert_eq!(
+            FileType::detect(Path::new("/some/path/composer.json")),
+            Some(FileType::ComposerJson)
+        );
+        assert_eq!(FileType::detect(Path::new("composer.lock")), None);
+
+        // Gradle version catalog
+        assert_eq!(
+            FileType::detect(Path::new("/some/path/gradle/libs.versions.toml")),
+            Some(FileType::GradleCatalog)
+        );
+        assert_eq!(
+            FileType::detect(Path::new("gradle/tools.versions.toml")),
+            Some(FileType::GradleCatalog)
+        );
+        assert_eq!(FileType::detect(Path::new("libs.versions.toml")), None);
+
+        // Dart pubspec
+        assert_eq!(
+            FileType::detect(Path::new("/some/path/pubspec.yaml")),
+            Some(FileType::Pubspec)
+        );
+        assert_eq!(FileType::detect(Path::new("pubspec.lock")), None);
+
         // Package.json
         assert_eq!(
             FileType::detect(Path::new("package.json")),
@@ -1223,6 +1981,13 @@ mod tests {
             Some(FileType::PackageJson)
         );
 
+        // pnpm workspace catalogs
+        assert_eq!(
+            FileType::detect(Path::new("/some/path/pnpm-workspace.yaml")),
+            Some(FileType::PnpmWorkspace)
+        );
+        assert_eq!(FileType::detect(Path::new("pnpm-lock.yaml")), None);
+
         // Requirements.txt patterns
         assert_eq!(
             FileType::detect(Path::new("requirements.txt")),
@@ -1298,10 +2063,17 @@ mod tests {
             Some(FileType::ToolVersions)
         );
 
+        // Runtime version files
+        assert_eq!(FileType::detect(Path::new(".nvmrc")), Some(FileType::Nvmrc));
+        assert_eq!(
+            FileType::detect(Path::new("/some/path/.python-version")),
+            Some(FileType::PythonVersion)
+        );
+
         // Non-matching patterns
         assert_eq!(FileType::detect(Path::new("requirements")), None);
         assert_eq!(FileType::detect(Path::new("requirements-dev")), None);
-        assert_eq!(FileType::detect(Path::new("setup.py")), None);
+        assert_eq!(FileType::detect(Path::new("setup.cfg")), None);
         assert_eq!(FileType::detect(Path::new("cargo.toml")), None); // lowercase doesn't match
     }
 
@@ -1310,6 +2082,7 @@ mod tests {
         assert_eq!(FileType::Requirements.lang(), Lang::Python);
         assert_eq!(FileType::PyProject.lang(), Lang::Python);
         assert_eq!(FileType::PackageJson.lang(), Lang::Node);
+        assert_eq!(FileType::PnpmWorkspace.lang(), Lang::Node);
         assert_eq!(FileType::CargoToml.lang(), Lang::Rust);
         assert_eq!(FileType::GoMod.lang(), Lang::Go);
         assert_eq!(FileType::Gemfile.lang(), Lang::Ruby);
@@ -1317,6 +2090,8 @@ mod tests {
         assert_eq!(FileType::PreCommitConfig.lang(), Lang::PreCommit);
         assert_eq!(FileType::MiseToml.lang(), Lang::Mise);
         assert_eq!(FileType::ToolVersions.lang(), Lang::Mise);
+        assert_eq!(FileType::Nvmrc.lang(), Lang::Runtime);
+        assert_eq!(FileType::PythonVersion.lang(), Lang::Runtime);
     }
 
     #[test]
@@ -1448,6 +2223,7 @@ mod tests {
         fs::write(nested.join(".pre-commit-config.yaml"), "repos: []").unwrap();
         fs::write(nested.join(".mise.toml"), "[tools]\nnode = \"20\"").unwrap();
         fs::write(nested.join(".tool-versions"), "node 20").unwrap();
+        fs::write(nested.join(".nvmrc"), "20").unwrap();
 
         let files = discover_files(&[temp.path().to_path_buf()], &[]);
         let paths: Vec<_> = files.iter().map(|(path, _)| path.clone()).collect();
@@ -1456,6 +2232,7 @@ mod tests {
         assert!(paths.contains(&nested.join(".pre-commit-config.yaml")));
         assert!(paths.contains(&nested.join(".mise.toml")));
         assert!(paths.contains(&nested.join(".tool-versions")));
+        assert!(paths.contains(&nested.join(".nvmrc")));
     }
 
     #[test]
@@ -1617,8 +2394,10 @@ mod tests {
             &[],
             DiscoverOptions {
                 no_ignore: true,
+                include_hidden: false,
                 verbose: false,
                 exclude: &[],
+                relative_exclude: &[],
             },
         );
         let paths: Vec<PathBuf> = unrestricted.iter().map(|(p, _)| p.clone()).collect();
@@ -1631,6 +2410,36 @@ mod tests {
         // up despite being gitignored.
     }
 
+    /// `--include-hidden` descends into hidden directories that are pruned by
+    /// default, but still never walks `.git`.
+    #[test]
+    fn test_discover_files_with_include_hidden_walks_hidden_dirs() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+
+        fs::create_dir_all(root.join(".vendor").join("lib")).unwrap();
+        fs::write(root.join(".vendor/lib/package.json"), "{}").unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join(".git/requirements.txt"), "flask>=2.0").unwrap();
+
+        let discover = |include_hidden| {
+            discover_files_with(
+                &[root.to_path_buf()],
+                &[],
+                DiscoverOptions {
+                    include_hidden,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert!(discover(false).is_empty());
+        assert_eq!(
+            discover(true),
+            vec![(root.join(".vendor/lib/package.json"), FileType::PackageJson)]
+        );
+    }
+
     /// `--lang` filtering must compose with gitignore filtering: ignored
     /// files of the requested language stay out, kept files of other
     /// languages also stay out.
@@ -1695,8 +2504,10 @@ mod tests {
             &[],
             DiscoverOptions {
                 no_ignore: false,
+                include_hidden: false,
                 verbose: false,
                 exclude: &patterns,
+                relative_exclude: &[],
             },
         );
         let paths: Vec<PathBuf> = files.iter().map(|(p, _)| p.clone()).collect();
@@ -1711,6 +2522,42 @@ mod tests {
         );
     }
 
+    /// `--exclude` patterns match the path relative to the scan root, so an
+    /// anchored pattern works wherever the root lives on disk.
+    #[test]
+    fn test_discover_files_relative_exclude_matches_from_scan_root() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+
+        fs::write(root.join("Cargo.toml"), "[package]").unwrap();
+        let fixtures = root.join("crates/demo/tests/fixtures/basic");
+        fs::create_dir_all(&fixtures).unwrap();
+        fs::write(fixtures.join("Cargo.toml"), "[package]").unwrap();
+        let vendor = root.join("vendor");
+        fs::create_dir_all(&vendor).unwrap();
+        fs::write(vendor.join("requirements.txt"), "flask").unwrap();
+
+        let patterns = vec![
+            "**/tests/fixtures/**/Cargo.toml".to_string(),
+            "vendor/*.txt".to_string(),
+            "nothing/matches/this".to_string(),
+        ];
+        let files = discover_files_with(
+            &[root.to_path_buf()],
+            &[],
+            DiscoverOptions {
+                no_ignore: false,
+                include_hidden: false,
+                verbose: false,
+                exclude: &[],
+                relative_exclude: &patterns,
+            },
+        );
+        let paths: Vec<PathBuf> = files.iter().map(|(p, _)| p.clone()).collect();
+
+        assert_eq!(paths, vec![root.join("Cargo.toml")], "got: {paths:?}");
+    }
+
     /// An explicit file-path argument bypasses `exclude` even when the glob
     /// would match it in a directory walk.
     #[test]
@@ -1729,8 +2576,10 @@ mod tests {
             &[],
             DiscoverOptions {
                 no_ignore: false,
+                include_hidden: false,
                 verbose: false,
                 exclude: &patterns,
+                relative_exclude: &[],
             },
         );
         let paths: Vec<PathBuf> = files.iter().map(|(p, _)| p.clone()).collect();
@@ -1761,8 +2610,10 @@ mod tests {
             &[],
             DiscoverOptions {
                 no_ignore: false,
+                include_hidden: false,
                 verbose: false,
                 exclude: &patterns,
+                relative_exclude: &[],
             },
         );
         let paths: Vec<PathBuf> = files.iter().map(|(p, _)| p.clone()).collect();
@@ -1875,6 +2726,111 @@ mod cooldown_integration_tests {
         assert_eq!(skipped, "2.31.0");
     }
 
+    fn metas(versions: &[&str]) -> Vec<VersionMeta> {
+        versions
+            .iter()
+            .map(|v| VersionMeta {
+                version: v.to_string(),
+                published_at: None,
+                yanked: false,
+                prerelease: false,
+                rust_version: None,
+                node_engines: None,
+                deprecated: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_select_n_minus_one() {
+        let versions = metas(&["1.0.0", "3.0.0", "2.0.0"]);
+        assert_eq!(
+            select_n_minus_one(&versions, "1.0.0", "3.0.0", false).as_deref(),
+            Some("2.0.0")
+        );
+        // Already on the n-1 release, or past it: nothing to do.
+        assert_eq!(select_n_minus_one(&versions, "2.0.0", "3.0.0", false), None);
+        assert_eq!(select_n_minus_one(&versions, "3.0.0", "3.0.0", false), None);
+        // A constraint already kept `latest` behind the newest release.
+        let versions = metas(&["1.0.0", "1.1.0", "1.2.0", "2.0.0"]);
+        assert_eq!(
+            select_n_minus_one(&versions, "1.0.0", "1.2.0", false).as_deref(),
+            Some("1.2.0")
+        );
+        // A single release has no n-1.
+        assert_eq!(
+            select_n_minus_one(&metas(&["1.0.0"]), "0.9.0", "1.0.0", false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_select_n_minus_one_skips_yanked_and_prereleases() {
+        let mut versions = metas(&["1.0.0", "2.0.0", "3.0.0"]);
+        versions[1].yanked = true;
+        versions.push(VersionMeta {
+            version: "4.0.0rc1".to_string(),
+            published_at: None,
+            yanked: false,
+            prerelease: true,
+            rust_version: None,
+            node_engines: None,
+            deprecated: None,
+        });
+        assert_eq!(
+            select_n_minus_one(&versions, "0.5.0", "3.0.0", false).as_deref(),
+            Some("1.0.0")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_n_minus_one_strategy_writes_second_newest() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "requests==1.0.0").unwrap();
+        file.flush().unwrap();
+
+        let registry = MockRegistry::new("pypi")
+            .with_version("requests", "3.0.0")
+            .with_version_meta("requests", "1.0.0", None, false, false)
+            .with_version_meta("requests", "2.0.0", None, false, false)
+            .with_version_meta("requests", "3.0.0", None, false, false);
+
+        let options = UpdateOptions::new(false, false).with_strategy(Strategy::NMinusOne);
+        let result = RequirementsUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].2, "2.0.0");
+        let content = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!(content, "requests==2.0.0\n");
+    }
+
+    #[tokio::test]
+    async fn test_n_minus_one_strategy_keeps_current_without_version_list() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "requests==1.0.0").unwrap();
+        file.flush().unwrap();
+
+        let registry = MockRegistry::new("pypi").with_version("requests", "3.0.0");
+        let options = UpdateOptions::new(true, false).with_strategy(Strategy::NMinusOne);
+        let notes = Arc::clone(&options.cooldown_unavailable_notes);
+        let result = RequirementsUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert!(result.updated.is_empty());
+        assert_eq!(result.unchanged, 1);
+        assert!(
+            notes
+                .lock()
+                .unwrap()
+                .contains("--strategy n-1 unavailable for pypi; current versions kept")
+        );
+    }
+
     #[tokio::test]
     async fn test_update_skipped_when_nothing_old_enough() {
         let now = Utc.with_ymd_and_hms(2026, 4, 22, 12, 0, 0).unwrap();
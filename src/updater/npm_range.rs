@@ -141,6 +141,96 @@ pub fn lower_bound_anchor(spec: &str) -> Option<&str> {
     None
 }
 
+/// Parse an npm range such as `"^18.18.0 || >=20"` or `">= 14.17"` into one
+/// `semver::VersionReq` per `||` alternative.
+///
+/// Returns `None` for syntax `semver` cannot express (dist-tags, URLs);
+/// callers treat that as "unknown" rather than incompatible.
+fn parse_npm_range(range: &str) -> Option<Vec<semver::VersionReq>> {
+    range
+        .split("||")
+        .map(|alternative| {
+            let alternative = alternative.trim();
+            if alternative.is_empty() || alternative == "*" {
+                return Some(semver::VersionReq::STAR);
+            }
+            let comparators = if let Some((low, high)) = alternative.split_once(" - ") {
+                format!(">={}, <={}", low.trim(), high.trim())
+            } else {
+                // `>= 14` → `>=14`, then one comparator per token.
+                let mut collapsed = alternative.to_string();
+                for op in [">=", "<=", ">", "<", "=", "^", "~"] {
+                    collapsed = collapsed.replace(&format!("{op} "), op);
+                }
+                collapsed
+                    .split_whitespace()
+                    .map(|token| {
+                        let split = token
+                            .find(|c: char| c.is_ascii_digit() || c == 'v')
+                            .unwrap_or(token.len());
+                        let (op, version) = token.split_at(split);
+                        let version = version.trim_start_matches('v');
+                        // A bare full version is an exact match in npm, not a caret.
+                        if op.is_empty() && semver::Version::parse(version).is_ok() {
+                            format!("={version}")
+                        } else {
+                            format!("{op}{version}")
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            semver::VersionReq::parse(&comparators).ok()
+        })
+        .collect()
+}
+
+/// The lowest version an npm range admits, e.g. `18.0.0` for `">=18"` or
+/// `"^18.18.0 || >=20"` → `18.18.0`.
+///
+/// Returns `None` when the range cannot be parsed or some alternative has no
+/// lower bound (`"<20"`, `"*"`).
+pub fn range_floor(range: &str) -> Option<semver::Version> {
+    parse_npm_range(range)?
+        .iter()
+        .map(|req| {
+            req.comparators
+                .iter()
+                .filter_map(|c| {
+                    let (minor, patch) = (c.minor.unwrap_or(0), c.patch.unwrap_or(0));
+                    match c.op {
+                        semver::Op::Exact
+                        | semver::Op::GreaterEq
+                        | semver::Op::Tilde
+                        | semver::Op::Caret
+                        | semver::Op::Wildcard => Some(semver::Version::new(c.major, minor, patch)),
+                        semver::Op::Greater => Some(match (c.minor, c.patch) {
+                            (None, _) => semver::Version::new(c.major + 1, 0, 0),
+                            (Some(minor), None) => semver::Version::new(c.major, minor + 1, 0),
+                            (Some(minor), Some(patch)) => {
+                                semver::Version::new(c.major, minor, patch + 1)
+                            }
+                        }),
+                        _ => None,
+                    }
+                })
+                .max()
+        })
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .min()
+}
+
+/// Whether `version` satisfies an npm range, or `None` if the range cannot
+/// be parsed.
+pub fn range_allows(range: &str, version: &semver::Version) -> Option<bool> {
+    Some(
+        parse_npm_range(range)?
+            .iter()
+            .any(|req| req.matches(version)),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,4 +344,27 @@ mod tests {
         assert_eq!(lower_bound_anchor("<3"), None);
         assert_eq!(lower_bound_anchor("<=2.0.0"), None);
     }
+
+    #[test]
+    fn range_floor_takes_lowest_alternative() {
+        let floor = |range: &str| range_floor(range).map(|v| v.to_string());
+        assert_eq!(floor(">=18").as_deref(), Some("18.0.0"));
+        assert_eq!(floor(">= 14.17").as_deref(), Some("14.17.0"));
+        assert_eq!(floor("^18.18.0 || >=20").as_deref(), Some("18.18.0"));
+        assert_eq!(floor("16 || 18").as_deref(), Some("16.0.0"));
+        assert_eq!(floor(">16").as_deref(), Some("17.0.0"));
+        assert_eq!(floor("<20"), None);
+        assert_eq!(floor("*"), None);
+    }
+
+    #[test]
+    fn range_allows_handles_or_and_bare_versions() {
+        let v = |s: &str| semver::Version::parse(s).unwrap();
+        assert_eq!(range_allows(">=20", &v("18.0.0")), Some(false));
+        assert_eq!(range_allows("^18.18.0 || >=20", &v("18.19.0")), Some(true));
+        assert_eq!(range_allows(">=v14.17.0 <21", &v("20.0.0")), Some(true));
+        assert_eq!(range_allows("18.0.0", &v("18.1.0")), Some(false));
+        assert_eq!(range_allows("14.x", &v("14.2.0")), Some(true));
+        assert_eq!(range_allows("lts", &v("14.0.0")), None);
+    }
 }
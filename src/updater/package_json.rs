@@ -1,7 +1,9 @@
-use super::npm_range::{SpecShape, classify, lower_bound_anchor, rewrite_lower_bound};
+use super::npm_range::{
+    SpecShape, classify, lower_bound_anchor, range_allows, range_floor, rewrite_lower_bound,
+};
 use super::{
-    FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
-    read_file_safe, write_file_atomic,
+    FileType, ParsedDependency, UnconstrainedDependency, UpdateOptions, UpdateResult, Updater,
+    downgrade_warning, read_file_safe,
 };
 use crate::align::compare_versions;
 use crate::registry::Registry;
@@ -23,6 +25,36 @@ const DEPENDENCY_SECTIONS: [&str; 4] = [
     "optionalDependencies",
 ];
 
+/// The `--group` name of a dependency section. `None` for the production
+/// `dependencies` table.
+fn section_group(section: &str) -> Option<&str> {
+    match section {
+        "devDependencies" => Some("dev"),
+        "peerDependencies" => Some("peer"),
+        "optionalDependencies" => Some("optional"),
+        _ => None,
+    }
+}
+
+/// The project's own `engines.node`, with the oldest Node it admits.
+struct NodeEngines {
+    declared: String,
+    floor: semver::Version,
+}
+
+/// How a candidate version that npm may have deprecated is resolved.
+pub(super) enum Deprecation {
+    /// Not deprecated, or no metadata to tell: write the candidate.
+    None,
+    /// Deprecated but `--allow-deprecated` was passed; carries the notice.
+    Allowed(String),
+    /// Replaced by the newest non-deprecated version between the current
+    /// one and the candidate.
+    Replaced { version: String, note: String },
+    /// Deprecated with no non-deprecated alternative; carries the notice.
+    HeldBack(String),
+}
+
 #[derive(Default)]
 struct PackageJsonLineIndex {
     lines_by_section: HashMap<String, HashMap<String, usize>>,
@@ -39,6 +71,31 @@ impl PackageJsonUpdater {
         Self
     }
 
+    /// The DefinitelyTyped package holding the typings of `package`:
+    /// `react` → `@types/react`, `@babel/core` → `@types/babel__core`.
+    pub fn types_package_for(package: &str) -> Option<String> {
+        if package.starts_with("@types/") {
+            return None;
+        }
+        match package.strip_prefix('@') {
+            Some(scoped) => {
+                let (scope, name) = scoped.split_once('/')?;
+                Some(format!("@types/{scope}__{name}"))
+            }
+            None => Some(format!("@types/{package}")),
+        }
+    }
+
+    /// The runtime package a DefinitelyTyped package describes; the inverse
+    /// of [`Self::types_package_for`].
+    pub fn runtime_package_for_types(package: &str) -> Option<String> {
+        let name = package.strip_prefix("@types/")?;
+        match name.split_once("__") {
+            Some((scope, name)) => Some(format!("@{scope}/{name}")),
+            None => Some(name.to_string()),
+        }
+    }
+
     fn extract_version_info(&self, version_str: &str) -> (String, String) {
         // Extract prefix and version from strings like "^1.0.0", "~2.0.0", ">=3.0.0"
         let prefixes = [">=", "<=", "~>", "^", "~", ">", "<"];
@@ -53,6 +110,96 @@ impl PackageJsonUpdater {
         (String::new(), version_str.to_string())
     }
 
+    /// The `engines.node` the package declares, unless `--ignore-engines`.
+    /// Ranges without a lower bound (`"*"`, `"<20"`) are not enforced.
+    fn node_engines(json: &Value, options: &UpdateOptions) -> Option<NodeEngines> {
+        if options.ignore_engines {
+            return None;
+        }
+        let declared = json.get("engines")?.get("node")?.as_str()?;
+        Some(NodeEngines {
+            declared: declared.to_string(),
+            floor: range_floor(declared)?,
+        })
+    }
+
+    /// A note when `version` of `package` declares an `engines.node` range
+    /// that excludes the oldest Node the project supports.
+    async fn engines_conflict(
+        registry: &dyn Registry,
+        package: &str,
+        version: &str,
+        engines: Option<&NodeEngines>,
+    ) -> Option<String> {
+        let engines = engines?;
+        let metas = registry.list_versions(package).await.ok()?;
+        let required = metas
+            .iter()
+            .find(|m| m.version == version)?
+            .node_engines
+            .as_deref()?;
+        if range_allows(required, &engines.floor) != Some(false) {
+            return None;
+        }
+        Some(format!(
+            "{package}: {version} requires Node {required} but engines.node is {}; held back by engines",
+            engines.declared
+        ))
+    }
+
+    /// Check whether npm deprecated `candidate` and, unless deprecated
+    /// versions are allowed, fall back to the newest non-deprecated version
+    /// above `current`. Pre-releases are only considered when the candidate
+    /// is one.
+    pub(super) async fn resolve_deprecation(
+        registry: &dyn Registry,
+        package: &str,
+        candidate: &str,
+        current: Option<&str>,
+        allow_deprecated: bool,
+    ) -> Deprecation {
+        if current == Some(candidate) {
+            return Deprecation::None;
+        }
+        let Ok(metas) = registry.list_versions(package).await else {
+            return Deprecation::None;
+        };
+        let Some(message) = metas
+            .iter()
+            .find(|m| m.version == candidate)
+            .and_then(|m| m.deprecated.as_deref())
+        else {
+            return Deprecation::None;
+        };
+        if allow_deprecated {
+            return Deprecation::Allowed(format!(
+                "{package}: {candidate} is deprecated: {message}"
+            ));
+        }
+
+        let Ok(ceiling) = semver::Version::parse(candidate) else {
+            return Deprecation::None;
+        };
+        let floor = current.and_then(|c| semver::Version::parse(c).ok());
+        let fallback = metas
+            .iter()
+            .filter(|m| m.deprecated.is_none() && (!m.prerelease || !ceiling.pre.is_empty()))
+            .filter_map(|m| Some((semver::Version::parse(&m.version).ok()?, &m.version)))
+            .filter(|(v, _)| *v < ceiling && floor.as_ref().is_none_or(|f| v > f))
+            .max_by(|a, b| a.0.cmp(&b.0));
+        match fallback {
+            Some((_, version)) => Deprecation::Replaced {
+                version: version.clone(),
+                note: format!(
+                    "{package}: {candidate} is deprecated ({message}); updated to {version} instead"
+                ),
+            },
+            None => Deprecation::HeldBack(format!(
+                "{package}: {candidate} is deprecated ({message}); held back (pass --allow-deprecated to update anyway)"
+            )),
+        }
+    }
+
     fn update_version_in_content(
         &self,
         content: &str,
@@ -224,6 +371,7 @@ impl Updater for PackageJsonUpdater {
         let mut result = UpdateResult::default();
         let mut new_content = content.clone();
         let line_index = PackageJsonLineIndex::from_content(&content);
+        let engines = Self::node_engines(&json, &options);
 
         // First pass: collect all packages and separate by config status
         let mut ignored_packages: Vec<(String, String, String)> = Vec::new();
@@ -231,6 +379,7 @@ impl Updater for PackageJsonUpdater {
         let mut packages_to_check: Vec<(String, String, String, String, String)> = Vec::new();
 
         for section in DEPENDENCY_SECTIONS {
+            let group_filtered = options.is_group_filtered_out(section_group(section));
             if let Some(deps) = json.get(section).and_then(|v| v.as_object()) {
                 for (package, version_value) in deps {
                     if let Some(version_str) = version_value.as_str() {
@@ -245,10 +394,25 @@ impl Updater for PackageJsonUpdater {
                             continue;
                         }
 
+                        // `catalog:` entries take their version from the pnpm
+                        // workspace file, which is updated on its own.
+                        if let Some(catalog) = version_str.strip_prefix("catalog:") {
+                            let reason = match catalog {
+                                "" | "default" => "pnpm catalog".to_string(),
+                                name => format!("pnpm catalog `{name}`"),
+                            };
+                            result.skipped_sources.push((
+                                package.clone(),
+                                reason,
+                                line_index.line_for(section, package),
+                            ));
+                            continue;
+                        }
+
                         let (prefix, current_version) = self.extract_version_info(version_str);
 
                         // Apply config guards uniformly before any per-shape routing.
-                        if options.is_package_filtered_out(package) {
+                        if group_filtered || options.is_package_filtered_out(package) {
                             result.unchanged += 1;
                             continue;
                         }
@@ -266,6 +430,11 @@ impl Updater for PackageJsonUpdater {
                         // re-parsing the string.
                         let spec_shape = classify(version_str);
 
+                        if let Some(warning) = options.range_pin_warning(package) {
+                            result.warnings.push(warning);
+                            continue;
+                        }
+
                         if let Some(pinned_version) = options.get_pinned_version(package) {
                             match spec_shape {
                                 SpecShape::SingleComparator | SpecShape::TwoComparatorRange => {
@@ -301,6 +470,14 @@ impl Updater for PackageJsonUpdater {
                                     }
                                     continue;
                                 }
+                                SpecShape::Unsupported => {
+                                    // OR and hyphen ranges (common in peerDependencies)
+                                    // have no single version token to replace.
+                                    result.warnings.push(format!(
+                                        "cannot pin range spec '{version_str}' for '{package}': unsupported range shape"
+                                    ));
+                                    continue;
+                                }
                                 _ => {
                                     // Non-comparator specs go through the standard
                                     // pinned_packages flow (processed after the loop).
@@ -388,6 +565,38 @@ impl Updater for PackageJsonUpdater {
                                                     (Some(matched), None)
                                                 };
 
+                                            let (effective_version, deprecation_note) =
+                                                match effective_version {
+                                                    Some(effective) => {
+                                                        match Self::resolve_deprecation(
+                                                            registry,
+                                                            package,
+                                                            &effective,
+                                                            lower_bound_anchor(version_str),
+                                                            options.allow_deprecated,
+                                                        )
+                                                        .await
+                                                        {
+                                                            Deprecation::None => {
+                                                                (Some(effective), None)
+                                                            }
+                                                            Deprecation::Allowed(note) => {
+                                                                (Some(effective), Some(note))
+                                                            }
+                                                            Deprecation::Replaced {
+                                                                version,
+                                                                note,
+                                                            } => (Some(version), Some(note)),
+                                                            Deprecation::HeldBack(note) => {
+                                                                result.warnings.push(note);
+                                                                result.unchanged += 1;
+                                                                (None, None)
+                                                            }
+                                                        }
+                                                    }
+                                                    None => (None, None),
+                                                };
+
                                             if let Some(effective) = effective_version {
                                                 if let Some(new_spec) =
                                                     rewrite_lower_bound(version_str, &effective)
@@ -403,6 +612,17 @@ impl Updater for PackageJsonUpdater {
                                                         // --only-bump/--max-bump ceiling: leave the
                                                         // dependency spec untouched.
                                                         result.unchanged += 1;
+                                                    } else if new_spec != version_str
+                                                        && let Some(note) = Self::engines_conflict(
+                                                            registry,
+                                                            package,
+                                                            &effective,
+                                                            engines.as_ref(),
+                                                        )
+                                                        .await
+                                                    {
+                                                        result.warnings.push(note);
+                                                        result.unchanged += 1;
                                                     } else if new_spec != version_str {
                                                         let line_num =
                                                             line_index.line_for(section, package);
@@ -412,6 +632,9 @@ impl Updater for PackageJsonUpdater {
                                                             new_spec.clone(),
                                                             line_num,
                                                         ));
+                                                        if let Some(note) = deprecation_note {
+                                                            result.warnings.push(note);
+                                                        }
                                                         if let Some((
                                                             skipped_version,
                                                             skipped_published_at,
@@ -517,7 +740,7 @@ impl Updater for PackageJsonUpdater {
         let version_futures: Vec<_> = packages_to_check
             .iter()
             .map(|(_, package, version_str, prefix, current_version)| async {
-                if is_prerelease_semver(current_version) {
+                if is_prerelease_semver(current_version) || options.allows_prerelease(package) {
                     registry
                         .get_latest_version_including_prereleases(package)
                         .await
@@ -528,6 +751,10 @@ impl Updater for PackageJsonUpdater {
                     registry
                         .get_latest_version_matching(package, version_str)
                         .await
+                } else if let Some(req) = options.semver_compatible_req(current_version) {
+                    // `--strategy compatible`: exact and open-ended specs stay
+                    // within the current version's caret range.
+                    registry.get_latest_version_matching(package, &req).await
                 } else {
                     registry.get_latest_version(package).await
                 }
@@ -585,6 +812,25 @@ impl Updater for PackageJsonUpdater {
                         }
                     };
 
+                    let (latest_version, deprecation_note) = match Self::resolve_deprecation(
+                        registry,
+                        &package,
+                        &latest_version,
+                        Some(&current_version),
+                        options.allow_deprecated,
+                    )
+                    .await
+                    {
+                        Deprecation::None => (latest_version, None),
+                        Deprecation::Allowed(note) => (latest_version, Some(note)),
+                        Deprecation::Replaced { version, note } => (version, Some(note)),
+                        Deprecation::HeldBack(note) => {
+                            result.warnings.push(note);
+                            result.unchanged += 1;
+                            continue;
+                        }
+                    };
+
                     // Match the precision of the original version (unless full precision requested)
                     let matched_version = if options.full_precision {
                         latest_version.clone()
@@ -605,6 +851,16 @@ impl Updater for PackageJsonUpdater {
                         } else if !options.allows_bump(&current_version, &matched_version) {
                             // Bump level exceeds the --only-bump/--max-bump ceiling.
                             result.unchanged += 1;
+                        } else if let Some(note) = Self::engines_conflict(
+                            registry,
+                            &package,
+                            &latest_version,
+                            engines.as_ref(),
+                        )
+                        .await
+                        {
+                            result.warnings.push(note);
+                            result.unchanged += 1;
                         } else {
                             let line_num = line_index.line_for(&section, &package);
                             result.updated.push((
@@ -613,6 +869,9 @@ impl Updater for PackageJsonUpdater {
                                 matched_version.clone(),
                                 line_num,
                             ));
+                            if let Some(note) = deprecation_note {
+                                result.warnings.push(note);
+                            }
                             if let Some((skipped_version, skipped_published_at)) = held_back_record
                             {
                                 result.held_back.push((
@@ -642,8 +901,8 @@ impl Updater for PackageJsonUpdater {
             }
         }
 
-        if (!result.updated.is_empty() || !result.pinned.is_empty()) && !options.dry_run {
-            write_file_atomic(path, &new_content)?;
+        if !result.updated.is_empty() || !result.pinned.is_empty() {
+            result.commit_content(path, &content, new_content, options.dry_run)?;
         }
 
         Ok(result)
@@ -688,6 +947,7 @@ impl Updater for PackageJsonUpdater {
                             line_number: line_num,
                             has_upper_bound: false, // npm versions don't have explicit upper bounds like Python
                             is_bumpable: true,
+                            inherits_workspace: false,
                         });
                     }
                 }
@@ -696,6 +956,38 @@ impl Updater for PackageJsonUpdater {
 
         Ok(deps)
     }
+
+    fn unconstrained_dependencies(&self, path: &Path) -> Result<Vec<UnconstrainedDependency>> {
+        let content = read_file_safe(path)?;
+        let json: Value = serde_json::from_str(&content)?;
+        let line_index = PackageJsonLineIndex::from_content(&content);
+        let mut deps = Vec::new();
+
+        for section in DEPENDENCY_SECTIONS {
+            let Some(section_deps) = json.get(section).and_then(|v| v.as_object()) else {
+                continue;
+            };
+            for (package, version_value) in section_deps {
+                let Some(spec) = version_value.as_str() else {
+                    continue;
+                };
+                let trimmed = spec.trim();
+                // Any version, a moving dist-tag, or a ceiling with no floor ("<3").
+                let unconstrained = matches!(trimmed, "" | "*" | "x" | "X" | "latest")
+                    || (classify(trimmed) == SpecShape::SingleComparator
+                        && lower_bound_anchor(trimmed).is_none());
+                if unconstrained {
+                    deps.push(UnconstrainedDependency {
+                        name: package.clone(),
+                        spec: spec.to_string(),
+                        line_number: line_index.line_for(section, package),
+                    });
+                }
+            }
+        }
+
+        Ok(deps)
+    }
 }
 
 #[cfg(test)]
@@ -709,6 +1001,25 @@ mod tests {
     use wiremock::matchers::{method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
+    #[test]
+    fn test_types_package_mapping_round_trips() {
+        for (runtime, types) in [
+            ("react", "@types/react"),
+            ("@babel/core", "@types/babel__core"),
+        ] {
+            assert_eq!(
+                PackageJsonUpdater::types_package_for(runtime).as_deref(),
+                Some(types)
+            );
+            assert_eq!(
+                PackageJsonUpdater::runtime_package_for_types(types).as_deref(),
+                Some(runtime)
+            );
+        }
+        assert_eq!(PackageJsonUpdater::types_package_for("@types/node"), None);
+        assert_eq!(PackageJsonUpdater::runtime_package_for_types("react"), None);
+    }
+
     struct EnvVarGuard {
         key: &'static str,
         previous: Option<String>,
@@ -799,6 +1110,159 @@ mod tests {
         assert!(content.contains("~4.17.21"));
     }
 
+    #[tokio::test]
+    async fn test_update_package_json_holds_back_by_engines() {
+        let content = r#"{
+  "engines": { "node": ">=18" },
+  "devDependencies": {
+    "eslint": "8.57.0",
+    "vite": "5.4.0"
+  }
+}"#;
+        let registry = || {
+            MockRegistry::new("npm")
+                .with_version("eslint", "9.0.0")
+                .with_node_engines("eslint", "9.0.0", "^18.18.0 || ^20.9.0 || >=21.1.0")
+                .with_version("vite", "6.0.0")
+                .with_node_engines("vite", "6.0.0", "^18.0.0 || ^20.0.0 || >=22.0.0")
+        };
+
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(file, "{content}").unwrap();
+        let result = PackageJsonUpdater::new()
+            .update(file.path(), &registry(), UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].0, "vite");
+        assert_eq!(result.unchanged, 1);
+        assert_eq!(
+            result.warnings,
+            vec![
+                "eslint: 9.0.0 requires Node ^18.18.0 || ^20.9.0 || >=21.1.0 but engines.node is >=18; held back by engines"
+            ]
+        );
+        assert!(
+            fs::read_to_string(file.path())
+                .unwrap()
+                .contains(r#""eslint": "8.57.0""#)
+        );
+
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(file, "{content}").unwrap();
+        let result = PackageJsonUpdater::new()
+            .update(
+                file.path(),
+                &registry(),
+                UpdateOptions::new(false, false).with_ignore_engines(true),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.updated.len(), 2);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_package_json_avoids_deprecated_versions() {
+        let content = r#"{
+  "dependencies": {
+    "@scope/widget": "^2.0.0",
+    "request": "2.88.0",
+    "left-pad": ">=1.0.0 <2.0.0"
+  }
+}"#;
+        let registry = || {
+            MockRegistry::new("npm")
+                .with_version("@scope/widget", "2.4.0")
+                .with_version_meta("@scope/widget", "2.3.1", None, false, false)
+                .with_deprecated("@scope/widget", "2.4.0", "broken build, use 2.3.1")
+                .with_version("request", "2.88.2")
+                .with_deprecated("request", "2.88.2", "request has been deprecated")
+                .with_version("left-pad", "1.3.0")
+                .with_version_meta("left-pad", "1.2.0", None, false, false)
+                .with_deprecated("left-pad", "1.3.0", "use String.prototype.padStart()")
+        };
+
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(file, "{content}").unwrap();
+        let result = PackageJsonUpdater::new()
+            .update(file.path(), &registry(), UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 2);
+        assert_eq!(result.unchanged, 1);
+        let written = fs::read_to_string(file.path()).unwrap();
+        assert!(written.contains(r#""@scope/widget": "^2.3.1""#));
+        assert!(written.contains(r#""request": "2.88.0""#));
+        assert!(written.contains(r#""left-pad": ">=1.2.0 <2.0.0""#));
+        assert!(result.warnings.contains(
+            &"@scope/widget: 2.4.0 is deprecated (broken build, use 2.3.1); updated to 2.3.1 instead"
+                .to_string()
+        ));
+        assert!(result.warnings.contains(
+            &"request: 2.88.2 is deprecated (request has been deprecated); held back (pass --allow-deprecated to update anyway)"
+                .to_string()
+        ));
+
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(file, "{content}").unwrap();
+        let result = PackageJsonUpdater::new()
+            .update(
+                file.path(),
+                &registry(),
+                UpdateOptions::new(false, false).with_allow_deprecated(true),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.updated.len(), 3);
+        assert!(
+            result.warnings.contains(
+                &"request: 2.88.2 is deprecated: request has been deprecated".to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_package_json_group_filter() {
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(
+            file,
+            r#"{{
+  "dependencies": {{
+    "react": "^17.0.0"
+  }},
+  "devDependencies": {{
+    "jest": "^28.0.0"
+  }},
+  "optionalDependencies": {{
+    "fsevents": "^2.0.0"
+  }}
+}}"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("npm")
+            .with_version("react", "18.2.0")
+            .with_version("jest", "29.7.0")
+            .with_version("fsevents", "2.3.3");
+        let options = UpdateOptions::new(false, false).with_groups(vec!["dev".to_string()]);
+
+        let result = PackageJsonUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].0, "jest");
+        assert_eq!(result.unchanged, 2);
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(r#""react": "^17.0.0""#));
+        assert!(content.contains(r#""jest": "^29.7.0""#));
+        assert!(content.contains(r#""fsevents": "^2.0.0""#));
+    }
+
     #[tokio::test]
     async fn test_update_package_json_dry_run() {
         let mut file = NamedTempFile::with_suffix(".json").unwrap();
@@ -843,7 +1307,9 @@ mod tests {
   }
 }"#,
             ))
-            .expect(1)
+            // The abbreviated document for the update, then the full one for
+            // the deprecation check.
+            .expect(2)
             .mount(&scoped_registry)
             .await;
 
@@ -881,6 +1347,101 @@ mod tests {
         assert!(content.contains("\"@private/pkg\": \"^1.2.3\""));
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_update_package_json_skips_deprecated_version_from_scoped_registry() {
+        let default_registry = MockServer::start().await;
+        let scoped_registry = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/@private/pkg"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+  "dist-tags": { "latest": "1.2.3" },
+  "versions": {
+    "1.0.0": {},
+    "1.1.0": {},
+    "1.2.3": { "deprecated": "critical bug, use 1.1.0" }
+  }
+}"#,
+            ))
+            .mount(&scoped_registry)
+            .await;
+
+        let mut npmrc = NamedTempFile::new().unwrap();
+        writeln!(npmrc, "@private:registry={}", scoped_registry.uri()).unwrap();
+        let _npmrc_guard =
+            EnvVarGuard::set("NPM_CONFIG_USERCONFIG", npmrc.path().to_str().unwrap());
+
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(file, r#"{{"dependencies": {{"@private/pkg": "^1.0.0"}}}}"#).unwrap();
+
+        let registry = NpmRegistry::with_registry_url(default_registry.uri());
+        let result = PackageJsonUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty());
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].2, "1.1.0");
+        assert_eq!(
+            result.warnings,
+            vec![
+                "@private/pkg: 1.2.3 is deprecated (critical bug, use 1.1.0); updated to 1.1.0 instead"
+            ]
+        );
+        assert!(
+            default_registry
+                .received_requests()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_update_package_json_checks_deprecation_and_engines_from_one_version_list() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/vite"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+  "dist-tags": { "latest": "6.0.1" },
+  "versions": {
+    "5.4.0": {},
+    "6.0.0": { "engines": { "node": ">=18" } },
+    "6.0.1": { "engines": { "node": ">=18" }, "deprecated": "broken build" }
+  }
+}"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(
+            file,
+            r#"{{"engines": {{"node": ">=18"}}, "dependencies": {{"vite": "5.4.0"}}}}"#
+        )
+        .unwrap();
+
+        let registry = crate::cache::CachedRegistry::new(
+            NpmRegistry::with_registry_url(server.uri()),
+            std::sync::Arc::new(std::sync::Mutex::new(crate::cache::Cache::default())),
+            false,
+        );
+        let result = PackageJsonUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.updated[0].2, "6.0.0");
+        // The latest version, then one version list for both checks.
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
     #[tokio::test]
     async fn test_update_package_json_preserves_prefix() {
         let mut file = NamedTempFile::with_suffix(".json").unwrap();
@@ -1111,6 +1672,9 @@ mod tests {
             ignore: Vec::new(),
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PackageJsonUpdater::new();
@@ -1162,6 +1726,9 @@ mod tests {
             ignore: Vec::new(),
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PackageJsonUpdater::new();
@@ -1245,6 +1812,9 @@ mod tests {
             ignore: vec!["lodash".to_string()],
             pin: std::collections::HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PackageJsonUpdater::new();
@@ -1298,6 +1868,9 @@ mod tests {
             ignore: Vec::new(),
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PackageJsonUpdater::new();
@@ -1354,6 +1927,9 @@ mod tests {
             ignore: vec!["lodash".to_string()],
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PackageJsonUpdater::new();
@@ -1406,6 +1982,9 @@ mod tests {
             ignore: vec!["typescript".to_string()],
             pin: std::collections::HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PackageJsonUpdater::new();
@@ -1460,6 +2039,9 @@ mod tests {
             ignore: Vec::new(),
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PackageJsonUpdater::new();
@@ -1523,6 +2105,92 @@ mod tests {
         assert!(content.contains("^2.3.3"));
     }
 
+    #[tokio::test]
+    async fn test_update_package_json_config_applies_to_peer_and_optional_dependencies() {
+        use crate::config::UpdConfig;
+        use std::sync::Arc;
+
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(
+            file,
+            r#"{{
+  "peerDependencies": {{
+    "react": "^17.0.0",
+    "react-dom": ">=16 || >=17"
+  }},
+  "optionalDependencies": {{
+    "fsevents": "^2.3.0"
+  }}
+}}"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("npm")
+            .with_version("react", "18.2.0")
+            .with_version("react-dom", "18.2.0")
+            .with_version("fsevents", "2.3.3");
+
+        let mut pin = std::collections::HashMap::new();
+        pin.insert("react".to_string(), "17.0.2".to_string());
+        pin.insert("react-dom".to_string(), "18.0.0".to_string());
+        let config = UpdConfig {
+            exclude: Vec::new(),
+            ignore: vec!["fsevents".to_string()],
+            pin,
+            cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
+        };
+
+        let updater = PackageJsonUpdater::new();
+        let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
+
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert!(result.updated.is_empty());
+        assert_eq!(result.pinned.len(), 1);
+        assert_eq!(result.pinned[0].0, "react");
+        assert_eq!(result.ignored.len(), 1);
+        assert_eq!(result.ignored[0].0, "fsevents");
+        assert!(
+            result.warnings.iter().any(|w| w.contains(">=16 || >=17")),
+            "pinning an OR range must warn: {:?}",
+            result.warnings
+        );
+
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains("\"^17.0.2\""));
+        assert!(content.contains("\">=16 || >=17\""));
+        assert!(content.contains("\"^2.3.0\""));
+    }
+
+    #[tokio::test]
+    async fn test_update_package_json_leaves_peer_or_range_untouched() {
+        let original = r#"{
+  "peerDependencies": {
+    "react": ">=16 || >=17"
+  }
+}"#;
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(file, "{original}").unwrap();
+
+        let registry = MockRegistry::new("npm").with_version("react", "18.2.0");
+        let updater = PackageJsonUpdater::new();
+        let options = UpdateOptions::new(false, false);
+
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert!(result.updated.is_empty());
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), original);
+    }
+
     #[tokio::test]
     async fn test_update_package_json_skips_workspace_protocol() {
         let mut file = NamedTempFile::with_suffix(".json").unwrap();
@@ -1564,6 +2232,43 @@ mod tests {
         assert!(content.contains("\"workspace:1.0.0\""));
     }
 
+    #[tokio::test]
+    async fn test_update_package_json_skips_catalog_protocol() {
+        let content = r#"{
+  "dependencies": {
+    "react": "catalog:",
+    "react-dom": "catalog:react17",
+    "real-pkg": "^1.0.0"
+  }
+}"#;
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(file, "{content}").unwrap();
+
+        let registry = MockRegistry::new("npm")
+            .with_version("react", "19.0.0")
+            .with_version("real-pkg", "2.0.0");
+        let result = PackageJsonUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert!(result.errors.is_empty() && result.warnings.is_empty());
+        assert_eq!(
+            result.skipped_sources,
+            vec![
+                ("react".to_string(), "pnpm catalog".to_string(), Some(3)),
+                (
+                    "react-dom".to_string(),
+                    "pnpm catalog `react17`".to_string(),
+                    Some(4)
+                ),
+            ]
+        );
+        let written = fs::read_to_string(file.path()).unwrap();
+        assert!(written.contains(r#""react": "catalog:""#));
+    }
+
     #[tokio::test]
     async fn test_update_package_json_does_not_touch_overrides() {
         // `overrides` is not part of DEPENDENCY_SECTIONS — any pin in there
@@ -1834,6 +2539,36 @@ mod tests {
         assert!(!content.contains("3.0.0-rc.1"));
     }
 
+    #[tokio::test]
+    async fn test_update_package_json_allow_prerelease() {
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(
+            file,
+            r#"{{
+  "dependencies": {{
+    "my-lib": "^2.0.0",
+    "other": "^2.0.0"
+  }}
+}}"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("npm")
+            .with_prerelease("my-lib", "2.0.0", "3.0.0-rc.1")
+            .with_prerelease("other", "2.0.0", "3.0.0-rc.1");
+
+        let options =
+            UpdateOptions::new(false, false).with_allow_prerelease(vec!["my-lib".to_string()]);
+        PackageJsonUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(r#""my-lib": "^3.0.0-rc.1""#), "{content}");
+        assert!(content.contains(r#""other": "^2.0.0""#), "{content}");
+    }
+
     /// Regression: a comparator-range spec for an ignored package must not be rewritten.
     #[tokio::test]
     async fn test_update_package_json_respects_ignore_for_comparator_range() {
@@ -1860,6 +2595,9 @@ mod tests {
             ignore: vec!["ranged".to_string()],
             pin: std::collections::HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
         let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
 
@@ -1972,6 +2710,9 @@ mod tests {
             ignore: Vec::new(),
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
         let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
 
@@ -1998,4 +2739,41 @@ mod tests {
             "file must preserve upper bound, got: {content}"
         );
     }
+
+    #[test]
+    fn test_unconstrained_dependencies_flags_wildcards_and_tags() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"{{
+  "dependencies": {{
+    "express": "*",
+    "react": "^18.2.0",
+    "lodash": "latest"
+  }},
+  "devDependencies": {{
+    "typescript": ">=5.0.0",
+    "legacy": "<3",
+    "local": "file:../local"
+  }}
+}}"#
+        )
+        .unwrap();
+
+        let deps = PackageJsonUpdater::new()
+            .unconstrained_dependencies(file.path())
+            .unwrap();
+        let found: Vec<(&str, &str, Option<usize>)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.spec.as_str(), d.line_number))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("express", "*", Some(3)),
+                ("lodash", "latest", Some(5)),
+                ("legacy", "<3", Some(9)),
+            ]
+        );
+    }
 }
@@ -0,0 +1,903 @@
+use super::{
+    FileType, ParsedDependency, PyProjectUpdater, UnconstrainedDependency, UpdateOptions,
+    UpdateResult, Updater, downgrade_warning, pep440_has_lower_bound, read_file_safe, resolve_pin,
+};
+use crate::align::compare_versions;
+use crate::registry::{PyPiRegistry, Registry};
+use crate::updater::Lang;
+use crate::version::{
+    is_prerelease_pep440, is_stable_pep440, match_compatible_release, match_version_precision,
+};
+use anyhow::{Result, anyhow};
+use futures::future::join_all;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use toml_edit::{DocumentMut, Formatted, Item, Table, Value};
+
+/// Pipfile tables that declare dependencies.
+const SECTIONS: [&str; 2] = ["packages", "dev-packages"];
+
+/// Keys that make an entry a VCS/local reference rather than a registry release.
+const NON_REGISTRY_KEYS: [&str; 3] = ["git", "path", "file"];
+
+pub struct PipfileUpdater {
+    /// Matches the first version in a specifier such as `==2.31.0` or `>=1.0,<2`.
+    /// Group 1: operator, group 2: version
+    version_re: Regex,
+}
+
+/// A dependency entry read from a Pipfile section.
+struct PipfileEntry {
+    section: &'static str,
+    name: String,
+    /// Full specifier, e.g. `>=1.0,<2`
+    spec: String,
+    /// First version in the specifier, e.g. `1.0`
+    version: String,
+    line_num: Option<usize>,
+}
+
+/// How a Pipfile entry references its package.
+enum EntryKind {
+    /// Version specifier to check against the registry.
+    Spec(String),
+    /// `"*"` (any version): left untouched.
+    Wildcard,
+    /// `git`/`path`/`file` reference; carries the git ref when there is one.
+    NonRegistry(Option<String>),
+}
+
+/// Line numbers of Pipfile entries, keyed by `(section, package)`.
+#[derive(Default)]
+struct PipfileLineIndex {
+    lines: HashMap<(String, String), usize>,
+}
+
+impl PipfileLineIndex {
+    fn from_content(content: &str) -> Self {
+        let mut index = Self::default();
+        let mut section = String::new();
+        // `[packages.name]` sub-tables: the line of their `version` key.
+        let mut subtable: Option<(String, String)> = None;
+
+        for (i, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if let Some(header) = trimmed.strip_prefix('[') {
+                let header = header.trim_end_matches(']').trim();
+                match header.split_once('.') {
+                    Some((sec, name)) => {
+                        let key = (sec.trim().to_string(), unquote(name).to_string());
+                        index.lines.insert(key.clone(), i + 1);
+                        subtable = Some(key);
+                        section.clear();
+                    }
+                    None => {
+                        section = header.to_string();
+                        subtable = None;
+                    }
+                }
+                continue;
+            }
+            let Some((key, _)) = trimmed.split_once('=') else {
+                continue;
+            };
+            let key = unquote(key);
+            if let Some(sub) = &subtable {
+                if key == "version" {
+                    index.lines.insert(sub.clone(), i + 1);
+                }
+            } else if !section.is_empty() && !key.starts_with('#') {
+                index
+                    .lines
+                    .entry((section.clone(), key.to_string()))
+                    .or_insert(i + 1);
+            }
+        }
+        index
+    }
+
+    fn line_for(&self, section: &str, package: &str) -> Option<usize> {
+        self.lines
+            .get(&(section.to_string(), package.to_string()))
+            .copied()
+    }
+}
+
+fn unquote(s: &str) -> &str {
+    s.trim().trim_matches('"').trim_matches('\'')
+}
+
+impl PipfileUpdater {
+    pub fn new() -> Self {
+        let version_re =
+            Regex::new(r"^\s*(===|==|>=|<=|~=|!=|>|<)\s*([^\s,;]+)").expect("Invalid regex");
+        Self { version_re }
+    }
+
+    fn entry_kind(item: &Item) -> Option<EntryKind> {
+        let spec = match item {
+            Item::Value(Value::String(s)) => Some(s.value().clone()),
+            Item::Value(Value::InlineTable(t)) => {
+                if NON_REGISTRY_KEYS.iter().any(|k| t.contains_key(k)) {
+                    let git_ref = t.get("ref").and_then(|v| v.as_str()).map(str::to_string);
+                    return Some(EntryKind::NonRegistry(git_ref));
+                }
+                t.get("version")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            }
+            Item::Table(t) => {
+                if NON_REGISTRY_KEYS.iter().any(|k| t.contains_key(k)) {
+                    let git_ref = t.get("ref").and_then(|v| v.as_str()).map(str::to_string);
+                    return Some(EntryKind::NonRegistry(git_ref));
+                }
+                t.get("version")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            }
+            _ => None,
+        }?;
+
+        let trimmed = spec.trim();
+        if trimmed.is_empty() || trimmed == "*" {
+            Some(EntryKind::Wildcard)
+        } else {
+            Some(EntryKind::Spec(trimmed.to_string()))
+        }
+    }
+
+    /// First version in a specifier (e.g. `1.0` from `>=1.0,<2`).
+    fn spec_version(&self, spec: &str) -> Option<String> {
+        self.version_re
+            .captures(spec)
+            .map(|caps| caps.get(2).unwrap().as_str().to_string())
+    }
+
+    /// Replace the first version in a specifier, keeping operators and any
+    /// further clauses (`,<2`) intact.
+    fn update_spec(&self, spec: &str, new_version: &str) -> String {
+        match self.version_re.captures(spec) {
+            Some(caps) => {
+                let mut updated = spec.to_string();
+                updated.replace_range(caps.get(2).unwrap().range(), new_version);
+                updated
+            }
+            None => spec.to_string(),
+        }
+    }
+
+    /// Write `new_spec` into an entry, touching only the version string so
+    /// extras, markers and formatting survive.
+    fn set_spec(table: &mut Table, name: &str, new_spec: &str) {
+        let target = match table.get_mut(name) {
+            Some(Item::Value(Value::String(s))) => Some(s),
+            Some(Item::Value(Value::InlineTable(t))) => match t.get_mut("version") {
+                Some(Value::String(s)) => Some(s),
+                _ => None,
+            },
+            Some(Item::Table(t)) => match t.get_mut("version") {
+                Some(Item::Value(Value::String(s))) => Some(s),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(formatted) = target {
+            let decor = formatted.decor().clone();
+            let mut new_formatted = Formatted::new(new_spec.to_string());
+            *new_formatted.decor_mut() = decor;
+            *formatted = new_formatted;
+        }
+    }
+
+    /// The first `[[source]]` URL, as written, when it points somewhere other
+    /// than public PyPI; the default registry already honors index overrides
+    /// for PyPI.
+    fn source_url(doc: &DocumentMut) -> Option<&str> {
+        let Some(Item::ArrayOfTables(sources)) = doc.get("source") else {
+            return None;
+        };
+        let url = sources.iter().next()?.get("url").and_then(|v| v.as_str())?;
+        if url.is_empty() || url.contains("pypi.org") || url.contains("pypi.python.org") {
+            return None;
+        }
+        Some(url)
+    }
+
+    /// The private index a Pipfile resolves from, if any. Callers build one
+    /// registry per URL with [`Self::source_registry`] and pass them in
+    /// through [`UpdateOptions::with_source_registries`].
+    pub fn source_index_url(path: &Path) -> Option<String> {
+        let doc: DocumentMut = read_file_safe(path).ok()?.parse().ok()?;
+        Self::source_url(&doc).map(str::to_string)
+    }
+
+    /// A registry for a `[[source]]` URL. `${VAR}` placeholders are expanded
+    /// from the environment as pipenv does; credentials embedded in the URL
+    /// or found for its host are attached.
+    pub fn source_registry(url: &str) -> PyPiRegistry {
+        PyPiRegistry::from_url(&expand_env_vars(url))
+    }
+
+    fn parse_error(path: &Path, e: toml_edit::TomlError) -> anyhow::Error {
+        anyhow!(
+            "Failed to parse {}:\n  {}",
+            path.display(),
+            e.to_string().replace('\n', "\n  ")
+        )
+    }
+}
+
+/// Replace each `${VAR}` with the value of the environment variable `VAR`
+/// (empty when unset).
+fn expand_env_vars(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let name = &rest[start + 2..start + 2 + len];
+        out.push_str(&std::env::var(name).unwrap_or_default());
+        rest = &rest[start + 3 + len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+impl Default for PipfileUpdater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Updater for PipfileUpdater {
+    async fn update(
+        &self,
+        path: &Path,
+        registry: &dyn Registry,
+        options: UpdateOptions,
+    ) -> Result<UpdateResult> {
+        let content = read_file_safe(path)?;
+        let mut doc: DocumentMut = content.parse().map_err(|e| Self::parse_error(path, e))?;
+
+        let mut result = UpdateResult::default();
+        let line_index = PipfileLineIndex::from_content(&content);
+
+        // Never fall back to the default index for a private source: its
+        // package names must not leak to public PyPI.
+        let registry: &dyn Registry = match Self::source_url(&doc) {
+            Some(url) => options
+                .source_registries
+                .get(url)
+                .ok_or_else(|| anyhow!("No registry configured for Pipfile source '{}'", url))?,
+            None => registry,
+        };
+
+        // First pass: collect dependencies and separate by config status
+        let mut pinned_deps: Vec<(PipfileEntry, String)> = Vec::new();
+        let mut deps_to_check: Vec<PipfileEntry> = Vec::new();
+
+        for section in SECTIONS {
+            let Some(Item::Table(table)) = doc.get(section) else {
+                continue;
+            };
+            for (name, item) in table.iter() {
+                // Wildcards and git/path entries have no version to bump.
+                let Some(EntryKind::Spec(spec)) = Self::entry_kind(item) else {
+                    continue;
+                };
+                let Some(version) = self.spec_version(&spec) else {
+                    continue;
+                };
+                let entry = PipfileEntry {
+                    section,
+                    name: name.to_string(),
+                    spec,
+                    version,
+                    line_num: line_index.line_for(section, name),
+                };
+
+                if options.is_package_filtered_out(&entry.name) {
+                    result.unchanged += 1;
+                    continue;
+                }
+
+                if options.should_ignore(&entry.name) {
+                    result
+                        .ignored
+                        .push((entry.name, entry.version, entry.line_num));
+                    continue;
+                }
+
+                if let Some(pinned_version) = options.get_pinned_version(&entry.name) {
+                    pinned_deps.push((entry, pinned_version.to_string()));
+                    continue;
+                }
+
+                deps_to_check.push(entry);
+            }
+        }
+
+        // (section, name, new spec)
+        let mut writes: Vec<(&'static str, String, String)> = Vec::new();
+
+        // Process pinned packages; only range pins need a registry lookup
+        let pin_futures: Vec<_> = pinned_deps
+            .iter()
+            .map(|(entry, pinned_version)| resolve_pin(registry, &entry.name, pinned_version))
+            .collect();
+        let pin_results = join_all(pin_futures).await;
+        for ((entry, _), pin_result) in pinned_deps.into_iter().zip(pin_results) {
+            let pinned_version = match pin_result {
+                Ok(version) => version,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", entry.name, e));
+                    continue;
+                }
+            };
+            let matched_version = if options.full_precision {
+                pinned_version
+            } else {
+                match_version_precision(&entry.version, &pinned_version)
+            };
+
+            if matched_version != entry.version {
+                writes.push((
+                    entry.section,
+                    entry.name.clone(),
+                    self.update_spec(&entry.spec, &matched_version),
+                ));
+                result
+                    .pinned
+                    .push((entry.name, entry.version, matched_version, entry.line_num));
+            } else {
+                result.unchanged += 1;
+            }
+        }
+
+        // Fetch versions for remaining deps in parallel
+        let version_futures: Vec<_> = deps_to_check
+            .iter()
+            .map(|entry| async {
+                let spec = options.pep440_resolution_spec(&entry.spec, &entry.version);
+                if !is_stable_pep440(&entry.version) || options.allows_prerelease(&entry.name) {
+                    registry
+                        .get_latest_version_including_prereleases(&entry.name)
+                        .await
+                } else if PyProjectUpdater::is_simple_constraint(&spec) {
+                    registry.get_latest_version(&entry.name).await
+                } else {
+                    registry
+                        .get_latest_version_matching(&entry.name, &spec)
+                        .await
+                }
+            })
+            .collect();
+
+        let version_results = join_all(version_futures).await;
+
+        for (entry, version_result) in deps_to_check.into_iter().zip(version_results) {
+            // Upper-bound-only specifiers (e.g. "<6") define a ceiling, not a floor.
+            if PyProjectUpdater::is_upper_bound_only(&entry.spec) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            let latest_version = match version_result {
+                Ok(v) => v,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", entry.name, e));
+                    continue;
+                }
+            };
+
+            // Refuse silent promotion from a pre-release to a stable release.
+            let current_is_prerelease = is_prerelease_pep440(&entry.version);
+            if current_is_prerelease && !is_prerelease_pep440(&latest_version) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            let spec = options.pep440_resolution_spec(&entry.spec, &entry.version);
+            let (outcome, note) = crate::updater::apply_cooldown(
+                registry,
+                &entry.name,
+                &entry.version,
+                &latest_version,
+                Some(spec.as_str()),
+                current_is_prerelease,
+                &options,
+            )
+            .await;
+            if let Some(msg) = note {
+                options.note_cooldown_unavailable(&msg);
+            }
+            let (latest_version, held_back_record) = match outcome {
+                crate::updater::CooldownOutcome::Unchanged(v) => (v, None),
+                crate::updater::CooldownOutcome::HeldBack {
+                    chosen,
+                    skipped_version,
+                    skipped_published_at,
+                } => (chosen, Some((skipped_version, skipped_published_at))),
+                crate::updater::CooldownOutcome::Skipped {
+                    skipped_version,
+                    skipped_published_at,
+                } => {
+                    result.skipped_by_cooldown.push((
+                        entry.name,
+                        entry.version,
+                        skipped_version,
+                        skipped_published_at,
+                    ));
+                    continue;
+                }
+            };
+
+            // A `~=` pin always keeps its precision, since that sets its
+            // ceiling; a release past the ceiling leaves the entry alone.
+            let matched_version = if entry.spec.trim_start().starts_with("~=") {
+                match match_compatible_release(&entry.version, &latest_version) {
+                    Some(version) => version,
+                    None => {
+                        result.unchanged += 1;
+                        continue;
+                    }
+                }
+            } else if options.full_precision {
+                latest_version
+            } else {
+                match_version_precision(&entry.version, &latest_version)
+            };
+            if matched_version == entry.version {
+                result.unchanged += 1;
+                continue;
+            }
+
+            // Refuse to write a downgrade.
+            if compare_versions(&matched_version, &entry.version, Lang::Python)
+                != std::cmp::Ordering::Greater
+            {
+                result.warnings.push(downgrade_warning(
+                    &entry.name,
+                    &matched_version,
+                    &entry.version,
+                ));
+                result.unchanged += 1;
+                continue;
+            }
+
+            // Bump level exceeds the --only-bump/--max-bump ceiling.
+            if !options.allows_bump(&entry.version, &matched_version) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            writes.push((
+                entry.section,
+                entry.name.clone(),
+                self.update_spec(&entry.spec, &matched_version),
+            ));
+            result.updated.push((
+                entry.name.clone(),
+                entry.version.clone(),
+                matched_version.clone(),
+                entry.line_num,
+            ));
+            if let Some((skipped_version, skipped_published_at)) = held_back_record {
+                result.held_back.push((
+                    entry.name,
+                    entry.version,
+                    matched_version,
+                    skipped_version,
+                    skipped_published_at,
+                ));
+            }
+        }
+
+        if !writes.is_empty() {
+            for (section, name, new_spec) in &writes {
+                if let Some(Item::Table(table)) = doc.get_mut(section) {
+                    Self::set_spec(table, name, new_spec);
+                }
+            }
+            result.commit_content(path, &content, doc.to_string(), options.dry_run)?;
+        }
+
+        Ok(result)
+    }
+
+    fn handles(&self, file_type: FileType) -> bool {
+        file_type == FileType::Pipfile
+    }
+
+    fn parse_dependencies(&self, path: &Path) -> Result<Vec<ParsedDependency>> {
+        let content = read_file_safe(path)?;
+        let doc: DocumentMut = content.parse().map_err(|e| Self::parse_error(path, e))?;
+        let line_index = PipfileLineIndex::from_content(&content);
+
+        let mut deps = Vec::new();
+        for section in SECTIONS {
+            let Some(Item::Table(table)) = doc.get(section) else {
+                continue;
+            };
+            for (name, item) in table.iter() {
+                let line_number = line_index.line_for(section, name);
+                match Self::entry_kind(item) {
+                    Some(EntryKind::Spec(spec)) => {
+                        if let Some(version) = self.spec_version(&spec) {
+                            deps.push(ParsedDependency {
+                                name: name.to_string(),
+                                version,
+                                line_number,
+                                has_upper_bound: !PyProjectUpdater::is_simple_constraint(&spec),
+                                is_bumpable: true,
+                                inherits_workspace: false,
+                            });
+                        }
+                    }
+                    // Git entries pinned to a ref are reported but never bumped,
+                    // like Go pseudo-versions.
+                    Some(EntryKind::NonRegistry(Some(git_ref))) => {
+                        deps.push(ParsedDependency {
+                            name: name.to_string(),
+                            version: git_ref,
+                            line_number,
+                            has_upper_bound: false,
+                            is_bumpable: false,
+                            inherits_workspace: false,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(deps)
+    }
+
+    fn unconstrained_dependencies(&self, path: &Path) -> Result<Vec<UnconstrainedDependency>> {
+        let content = read_file_safe(path)?;
+        let doc: DocumentMut = content.parse().map_err(|e| Self::parse_error(path, e))?;
+        let line_index = PipfileLineIndex::from_content(&content);
+
+        let mut deps = Vec::new();
+        for section in SECTIONS {
+            let Some(Item::Table(table)) = doc.get(section) else {
+                continue;
+            };
+            for (name, item) in table.iter() {
+                let spec = match Self::entry_kind(item) {
+                    Some(EntryKind::Wildcard) => "*".to_string(),
+                    Some(EntryKind::Spec(spec)) if !pep440_has_lower_bound(&spec) => spec,
+                    _ => continue,
+                };
+                deps.push(UnconstrainedDependency {
+                    name: name.to_string(),
+                    spec,
+                    line_number: line_index.line_for(section, name),
+                });
+            }
+        }
+
+        Ok(deps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MockRegistry;
+    use crate::updater::SourceRegistries;
+    use std::fs;
+    use std::io::Write;
+    use std::sync::Arc;
+    use tempfile::NamedTempFile;
+
+    fn pipfile(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{}", content).unwrap();
+        file
+    }
+
+    #[tokio::test]
+    async fn test_update_pipfile_packages_and_dev_packages() {
+        let file = pipfile(
+            r#"[[source]]
+url = "https://pypi.org/simple"
+verify_ssl = true
+name = "pypi"
+
+[packages]
+requests = "==2.28.0"
+flask = ">=2.0"
+
+[dev-packages]
+pytest = "==7.0.0"
+
+[requires]
+python_version = "3.12"
+"#,
+        );
+
+        let registry = MockRegistry::new("pypi")
+            .with_version("requests", "2.31.0")
+            .with_version("flask", "3.0.0")
+            .with_version("pytest", "8.1.0");
+        let updater = PipfileUpdater::new();
+        let result = updater
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 3, "errors: {:?}", result.errors);
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(r#"requests = "==2.31.0""#));
+        assert!(content.contains(r#"flask = ">=3.0""#));
+        assert!(content.contains(r#"pytest = "==8.1.0""#));
+        assert!(content.contains(r#"python_version = "3.12""#));
+
+        let requests = result.updated.iter().find(|u| u.0 == "requests").unwrap();
+        assert_eq!(requests.3, Some(7));
+        let pytest = result.updated.iter().find(|u| u.0 == "pytest").unwrap();
+        assert_eq!(pytest.3, Some(11));
+    }
+
+    #[tokio::test]
+    async fn test_update_pipfile_keeps_compatible_release_ceiling() {
+        let file = pipfile("[packages]\n\"zope.interface\" = \"~=5.0\"\nattrs = \"~=23.1\"\n");
+
+        let registry = MockRegistry::new("pypi")
+            .with_version("zope.interface", "7.0.3")
+            .with_version("attrs", "23.2.0");
+        let result = PipfileUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.unchanged, 1);
+        assert_eq!(
+            fs::read_to_string(file.path()).unwrap(),
+            "[packages]\n\"zope.interface\" = \"~=5.0\"\nattrs = \"~=23.2\"\n"
+        );
+    }
+
+    const PRIVATE_SOURCE_PIPFILE: &str = r#"[[source]]
+url = "https://${NEXUS_HOST}/simple"
+name = "nexus"
+
+[packages]
+internal-lib = "==1.0.0"
+"#;
+
+    #[tokio::test]
+    async fn test_update_pipfile_resolves_private_source_from_options() {
+        let file = pipfile(PRIVATE_SOURCE_PIPFILE);
+        let public = MockRegistry::new("pypi").with_version("internal-lib", "9.9.9");
+        let private: Arc<dyn Registry> =
+            Arc::new(MockRegistry::new("pypi").with_version("internal-lib", "1.2.0"));
+        let sources = SourceRegistries::new(HashMap::from([(
+            "https://${NEXUS_HOST}/simple".to_string(),
+            private,
+        )]));
+
+        let result = PipfileUpdater::new()
+            .update(
+                file.path(),
+                &public,
+                UpdateOptions::new(true, false).with_source_registries(sources),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated[0].2, "1.2.0", "errors: {:?}", result.errors);
+    }
+
+    #[tokio::test]
+    async fn test_update_pipfile_private_source_never_falls_back_to_default() {
+        let file = pipfile(PRIVATE_SOURCE_PIPFILE);
+        let public = MockRegistry::new("pypi").with_version("internal-lib", "9.9.9");
+
+        let err = PipfileUpdater::new()
+            .update(file.path(), &public, UpdateOptions::new(true, false))
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("Pipfile source"), "{err}");
+    }
+
+    #[test]
+    fn test_source_index_url_skips_public_pypi() {
+        let private = pipfile(PRIVATE_SOURCE_PIPFILE);
+        assert_eq!(
+            PipfileUpdater::source_index_url(private.path()).as_deref(),
+            Some("https://${NEXUS_HOST}/simple")
+        );
+        let public = pipfile("[[source]]\nurl = \"https://pypi.org/simple\"\n");
+        assert_eq!(PipfileUpdater::source_index_url(public.path()), None);
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        let home = std::env::var("HOME").unwrap_or_default();
+        assert_eq!(expand_env_vars("${HOME}/simple"), format!("{home}/simple"));
+        assert_eq!(
+            expand_env_vars("https://${UPD_TEST_UNSET_VAR}x"),
+            "https://x"
+        );
+        assert_eq!(expand_env_vars("https://a/${open"), "https://a/${open");
+    }
+
+    #[tokio::test]
+    async fn test_update_pipfile_leaves_wildcards_and_git_refs_untouched() {
+        let original = r#"[packages]
+requests = "*"
+mylib = {git = "https://github.com/example/mylib.git", ref = "v1.2.0"}
+local = {path = ".", editable = true}
+"#;
+        let file = pipfile(original);
+
+        let registry = MockRegistry::new("pypi")
+            .with_version("requests", "2.31.0")
+            .with_version("mylib", "9.9.9");
+        let updater = PipfileUpdater::new();
+        let result = updater
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.updated.is_empty());
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn test_update_pipfile_inline_table_updates_only_version() {
+        let file = pipfile(
+            r#"[packages]
+requests = {version = "==2.28.0", extras = ["socks"]}
+
+[packages.django]
+version = ">=4.0,<5"
+markers = "python_version >= '3.10'"
+"#,
+        );
+
+        let registry = MockRegistry::new("pypi")
+            .with_version("requests", "2.31.0")
+            .with_version("django", "5.0.1")
+            .with_constrained("django", ">=4.0,<5", "4.2.9");
+        let updater = PipfileUpdater::new();
+        let result = updater
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 2, "errors: {:?}", result.errors);
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(r#"requests = {version = "==2.31.0", extras = ["socks"]}"#));
+        assert!(content.contains(r#"version = ">=4.2,<5""#));
+        assert!(content.contains(r#"markers = "python_version >= '3.10'""#));
+        let django = result.updated.iter().find(|u| u.0 == "django").unwrap();
+        assert_eq!(django.3, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_update_pipfile_respects_ignore_and_pin() {
+        use crate::config::UpdConfig;
+        use std::sync::Arc;
+
+        let file = pipfile(
+            r#"[packages]
+requests = "==2.28.0"
+flask = "==2.0.0"
+"#,
+        );
+        let config = UpdConfig {
+            ignore: vec!["requests".to_string()],
+            pin: [("flask".to_string(), "2.3.3".to_string())]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+
+        let registry = MockRegistry::new("pypi")
+            .with_version("requests", "2.31.0")
+            .with_version("flask", "3.0.0");
+        let updater = PipfileUpdater::new();
+        let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert_eq!(result.ignored.len(), 1);
+        assert_eq!(result.pinned.len(), 1);
+        assert!(result.updated.is_empty());
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(r#"requests = "==2.28.0""#));
+        assert!(content.contains(r#"flask = "==2.3.3""#));
+    }
+
+    #[tokio::test]
+    async fn test_update_pipfile_dry_run_does_not_write() {
+        let original = "[packages]\nrequests = \"==2.28.0\"\n";
+        let file = pipfile(original);
+        let registry = MockRegistry::new("pypi").with_version("requests", "2.31.0");
+        let result = PipfileUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(true, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), original);
+    }
+
+    #[test]
+    fn test_parse_pipfile_dependencies() {
+        let file = pipfile(
+            r#"[packages]
+requests = "==2.28.0"
+anything = "*"
+django = {version = ">=4.0,<5"}
+mylib = {git = "https://github.com/example/mylib.git", ref = "abc123"}
+
+[dev-packages]
+pytest = ">=7.0"
+"#,
+        );
+
+        let deps = PipfileUpdater::new()
+            .parse_dependencies(file.path())
+            .unwrap();
+        let summary: Vec<(&str, &str, bool, bool)> = deps
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.version.as_str(),
+                    d.has_upper_bound,
+                    d.is_bumpable,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("requests", "2.28.0", false, true),
+                ("django", "4.0", true, true),
+                ("mylib", "abc123", false, false),
+                ("pytest", "7.0", false, true),
+            ]
+        );
+        assert_eq!(deps[0].line_number, Some(2));
+        assert_eq!(deps[3].line_number, Some(8));
+    }
+
+    #[test]
+    fn test_unconstrained_pipfile_dependencies() {
+        let file = pipfile(
+            r#"[packages]
+requests = "*"
+flask = ">=3.0"
+numpy = {version = "<2"}
+mylib = {git = "https://github.com/example/mylib.git"}
+"#,
+        );
+
+        let deps = PipfileUpdater::new()
+            .unconstrained_dependencies(file.path())
+            .unwrap();
+        let found: Vec<(&str, &str, Option<usize>)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.spec.as_str(), d.line_number))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("requests", "*", Some(2)), ("numpy", "<2", Some(4))]
+        );
+    }
+}
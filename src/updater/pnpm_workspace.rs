@@ -0,0 +1,515 @@
+use super::package_json::Deprecation;
+use super::{
+    FileType, PackageJsonUpdater, ParsedDependency, UpdateOptions, UpdateResult, Updater,
+    downgrade_warning, read_file_safe,
+};
+use crate::align::compare_versions;
+use crate::registry::Registry;
+use crate::updater::Lang;
+use crate::version::is_prerelease_semver;
+use anyhow::Result;
+use futures::future::join_all;
+use std::ops::Range;
+use std::path::Path;
+
+/// Updates the shared versions of a pnpm workspace's `catalog:` and
+/// `catalogs:` maps. package.json entries that use the `catalog:` protocol
+/// take their version from here.
+pub struct PnpmWorkspaceUpdater;
+
+/// A `name: version` entry of a catalog map.
+struct CatalogEntry {
+    name: String,
+    /// Range operator kept in front of the version: `^`, `~`, `>=` or none.
+    prefix: String,
+    /// Version after the prefix, e.g. `18.2.0`
+    version: String,
+    /// Byte range of `version` in the file
+    version_range: Range<usize>,
+    line_num: Option<usize>,
+}
+
+/// Strip a YAML comment (` # ...`) from the value of a mapping entry.
+fn strip_comment(value: &str) -> &str {
+    match value.find(" #").or_else(|| value.find("\t#")) {
+        Some(idx) => &value[..idx],
+        None if value.starts_with('#') => "",
+        None => value,
+    }
+}
+
+/// Split a block-mapping line into its key and the byte offset of the value
+/// within `line`. Quoted keys (`"@types/node": ...`) are unquoted.
+fn split_mapping_entry(line: &str) -> Option<(&str, usize)> {
+    let (key, after_key) = match line.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let end = line[1..].find(quote)? + 1;
+            (&line[1..end], end + 1)
+        }
+        _ => {
+            let end = line
+                .find(": ")
+                .or_else(|| line.find(":\t"))
+                .or_else(|| line.strip_suffix(':').map(str::len))?;
+            (line[..end].trim_end(), end)
+        }
+    };
+    let rest = line[after_key..].trim_start();
+    let rest = rest.strip_prefix(':')?;
+    Some((key, line.len() - rest.len()))
+}
+
+impl PnpmWorkspaceUpdater {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Walk the top-level `catalog:` map and the named maps under `catalogs:`
+    /// line by line, collecting entries whose value is a plain, caret, tilde,
+    /// or `>=` semver version. Protocols (`workspace:`, `npm:`), tags, and
+    /// other ranges are left alone.
+    fn scan(content: &str) -> Vec<CatalogEntry> {
+        let mut entries = Vec::new();
+        let mut in_catalogs = false;
+        let mut offset = 0;
+
+        for (idx, raw_line) in content.split_inclusive('\n').enumerate() {
+            let line_start = offset;
+            offset += raw_line.len();
+            let line = raw_line.trim_end_matches(['\n', '\r']);
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+
+            if indent == 0 {
+                let key = split_mapping_entry(trimmed).map(|(key, _)| key);
+                in_catalogs = matches!(key, Some("catalog" | "catalogs"));
+                continue;
+            }
+            if !in_catalogs {
+                continue;
+            }
+
+            // Catalog names under `catalogs:` have no value of their own.
+            let Some((name, value_offset)) = split_mapping_entry(trimmed) else {
+                continue;
+            };
+            let raw_value = &trimmed[value_offset..];
+            let padding = raw_value.len() - raw_value.trim_start().len();
+            let value = strip_comment(&raw_value[padding..]).trim_end();
+            let mut value_start = line_start + indent + value_offset + padding;
+            let value = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) if value.len() > 1 && value.ends_with(quote) => {
+                    value_start += 1;
+                    &value[1..value.len() - 1]
+                }
+                _ => value,
+            };
+            if value.is_empty() {
+                continue;
+            }
+
+            let prefix = ["^", "~", ">="]
+                .into_iter()
+                .find(|p| value.starts_with(p))
+                .unwrap_or("");
+            let version = &value[prefix.len()..];
+            if semver::Version::parse(version).is_err() {
+                continue;
+            }
+            let version_start = value_start + prefix.len();
+            entries.push(CatalogEntry {
+                name: name.to_string(),
+                prefix: prefix.to_string(),
+                version: version.to_string(),
+                version_range: version_start..version_start + version.len(),
+                line_num: Some(idx + 1),
+            });
+        }
+
+        entries
+    }
+}
+
+impl Default for PnpmWorkspaceUpdater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Updater for PnpmWorkspaceUpdater {
+    async fn update(
+        &self,
+        path: &Path,
+        registry: &dyn Registry,
+        options: UpdateOptions,
+    ) -> Result<UpdateResult> {
+        let content = read_file_safe(path)?;
+        let mut result = UpdateResult::default();
+
+        // First pass: collect dependencies and separate by config status
+        let mut pinned_deps: Vec<(CatalogEntry, String)> = Vec::new();
+        let mut deps_to_check: Vec<CatalogEntry> = Vec::new();
+
+        for entry in Self::scan(&content) {
+            if options.is_package_filtered_out(&entry.name) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            if options.should_ignore(&entry.name) {
+                result
+                    .ignored
+                    .push((entry.name, entry.version, entry.line_num));
+                continue;
+            }
+
+            if let Some(warning) = options.range_pin_warning(&entry.name) {
+                result.warnings.push(warning);
+                continue;
+            }
+
+            if let Some(pinned_version) = options.get_pinned_version(&entry.name) {
+                pinned_deps.push((entry, pinned_version.to_string()));
+                continue;
+            }
+
+            deps_to_check.push(entry);
+        }
+
+        // (byte range of the old version, new version)
+        let mut writes: Vec<(Range<usize>, String)> = Vec::new();
+
+        // Process pinned packages (no registry fetch needed)
+        for (entry, pinned_version) in pinned_deps {
+            if pinned_version != entry.version {
+                writes.push((entry.version_range.clone(), pinned_version.clone()));
+                result
+                    .pinned
+                    .push((entry.name, entry.version, pinned_version, entry.line_num));
+            } else {
+                result.unchanged += 1;
+            }
+        }
+
+        // Fetch versions for remaining deps in parallel, resolving them the
+        // way package.json entries are.
+        let version_futures: Vec<_> = deps_to_check
+            .iter()
+            .map(|entry| async {
+                if is_prerelease_semver(&entry.version) || options.allows_prerelease(&entry.name) {
+                    registry
+                        .get_latest_version_including_prereleases(&entry.name)
+                        .await
+                } else if matches!(entry.prefix.as_str(), "^" | "~") {
+                    let spec = format!("{}{}", entry.prefix, entry.version);
+                    registry
+                        .get_latest_version_matching(&entry.name, &spec)
+                        .await
+                } else if let Some(req) = options.semver_compatible_req(&entry.version) {
+                    registry
+                        .get_latest_version_matching(&entry.name, &req)
+                        .await
+                } else {
+                    registry.get_latest_version(&entry.name).await
+                }
+            })
+            .collect();
+
+        let version_results = join_all(version_futures).await;
+
+        for (entry, version_result) in deps_to_check.into_iter().zip(version_results) {
+            let latest_version = match version_result {
+                Ok(v) => v,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", entry.name, e));
+                    continue;
+                }
+            };
+
+            // Refuse silent promotion from a pre-release to a stable release.
+            let current_is_prerelease = is_prerelease_semver(&entry.version);
+            if current_is_prerelease && !is_prerelease_semver(&latest_version) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            let (outcome, note) = crate::updater::apply_cooldown(
+                registry,
+                &entry.name,
+                &entry.version,
+                &latest_version,
+                None,
+                current_is_prerelease,
+                &options,
+            )
+            .await;
+            if let Some(msg) = note {
+                options.note_cooldown_unavailable(&msg);
+            }
+            let (latest_version, held_back_record) = match outcome {
+                crate::updater::CooldownOutcome::Unchanged(v) => (v, None),
+                crate::updater::CooldownOutcome::HeldBack {
+                    chosen,
+                    skipped_version,
+                    skipped_published_at,
+                } => (chosen, Some((skipped_version, skipped_published_at))),
+                crate::updater::CooldownOutcome::Skipped {
+                    skipped_version,
+                    skipped_published_at,
+                } => {
+                    result.skipped_by_cooldown.push((
+                        entry.name,
+                        entry.version,
+                        skipped_version,
+                        skipped_published_at,
+                    ));
+                    continue;
+                }
+            };
+
+            let (latest_version, deprecation_note) = match PackageJsonUpdater::resolve_deprecation(
+                registry,
+                &entry.name,
+                &latest_version,
+                Some(&entry.version),
+                options.allow_deprecated,
+            )
+            .await
+            {
+                Deprecation::None => (latest_version, None),
+                Deprecation::Allowed(note) => (latest_version, Some(note)),
+                Deprecation::Replaced { version, note } => (version, Some(note)),
+                Deprecation::HeldBack(note) => {
+                    result.warnings.push(note);
+                    result.unchanged += 1;
+                    continue;
+                }
+            };
+
+            if latest_version == entry.version {
+                result.unchanged += 1;
+                continue;
+            }
+
+            // Refuse to write a downgrade.
+            if compare_versions(&latest_version, &entry.version, Lang::Node)
+                != std::cmp::Ordering::Greater
+            {
+                result.warnings.push(downgrade_warning(
+                    &entry.name,
+                    &latest_version,
+                    &entry.version,
+                ));
+                result.unchanged += 1;
+                continue;
+            }
+
+            // Bump level exceeds the --only-bump/--max-bump ceiling.
+            if !options.allows_bump(&entry.version, &latest_version) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            if let Some(note) = deprecation_note {
+                result.warnings.push(note);
+            }
+            writes.push((entry.version_range.clone(), latest_version.clone()));
+            result.updated.push((
+                entry.name.clone(),
+                entry.version.clone(),
+                latest_version.clone(),
+                entry.line_num,
+            ));
+            if let Some((skipped_version, skipped_published_at)) = held_back_record {
+                result.held_back.push((
+                    entry.name,
+                    entry.version,
+                    latest_version,
+                    skipped_version,
+                    skipped_published_at,
+                ));
+            }
+        }
+
+        if !writes.is_empty() {
+            // Splice from the end so earlier ranges stay valid.
+            writes.sort_by_key(|w| std::cmp::Reverse(w.0.start));
+            let mut new_content = content.clone();
+            for (range, new_version) in &writes {
+                new_content.replace_range(range.clone(), new_version);
+            }
+            result.commit_content(path, &content, new_content, options.dry_run)?;
+        }
+
+        Ok(result)
+    }
+
+    fn handles(&self, file_type: FileType) -> bool {
+        file_type == FileType::PnpmWorkspace
+    }
+
+    fn parse_dependencies(&self, path: &Path) -> Result<Vec<ParsedDependency>> {
+        let content = read_file_safe(path)?;
+        Ok(Self::scan(&content)
+            .into_iter()
+            .map(|entry| ParsedDependency {
+                name: entry.name,
+                version: entry.version,
+                line_number: entry.line_num,
+                has_upper_bound: false,
+                is_bumpable: true,
+                inherits_workspace: false,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MockRegistry;
+    use std::fs;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn pnpm_workspace(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::with_suffix(".yaml").unwrap();
+        write!(file, "{}", content).unwrap();
+        file
+    }
+
+    const PNPM_WORKSPACE: &str = "packages:
+  - 'packages/*'
+  - apps/web
+
+# Shared versions
+catalog:
+  react: ^18.2.0  # UI
+  \"@types/node\": '~20.11.0'
+  lodash: 4.17.20
+  local-lib: workspace:*
+  aliased: npm:other@^1.0.0
+
+catalogs:
+  react17:
+    react: ^17.0.2
+    react-dom: \"^17.0.2\"
+
+onlyBuiltDependencies:
+  - esbuild
+";
+
+    #[test]
+    fn test_scan_collects_default_and_named_catalogs() {
+        let entries = PnpmWorkspaceUpdater::scan(PNPM_WORKSPACE);
+        let summary: Vec<(&str, &str, &str, Option<usize>)> = entries
+            .iter()
+            .map(|e| {
+                (
+                    e.name.as_str(),
+                    e.prefix.as_str(),
+                    e.version.as_str(),
+                    e.line_num,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("react", "^", "18.2.0", Some(7)),
+                ("@types/node", "~", "20.11.0", Some(8)),
+                ("lodash", "", "4.17.20", Some(9)),
+                ("react", "^", "17.0.2", Some(15)),
+                ("react-dom", "^", "17.0.2", Some(16)),
+            ]
+        );
+        for entry in &entries {
+            assert_eq!(&PNPM_WORKSPACE[entry.version_range.clone()], entry.version);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_catalog_versions_preserves_yaml() {
+        let file = pnpm_workspace(PNPM_WORKSPACE);
+        let registry = MockRegistry::new("npm")
+            .with_version("react", "18.3.1")
+            .with_constrained("react", "^17.0.2", "17.0.2")
+            .with_constrained("react", "^18.2.0", "18.3.1")
+            .with_constrained("@types/node", "~20.11.0", "20.11.30")
+            .with_constrained("react-dom", "^17.0.2", "17.0.2")
+            .with_version("lodash", "4.17.21");
+
+        let result = PnpmWorkspaceUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let updated: Vec<(&str, &str)> = result
+            .updated
+            .iter()
+            .map(|u| (u.0.as_str(), u.2.as_str()))
+            .collect();
+        assert_eq!(
+            updated,
+            vec![
+                ("react", "18.3.1"),
+                ("@types/node", "20.11.30"),
+                ("lodash", "4.17.21"),
+            ]
+        );
+        assert_eq!(result.unchanged, 2);
+
+        let expected = PNPM_WORKSPACE
+            .replace("react: ^18.2.0", "react: ^18.3.1")
+            .replace("'~20.11.0'", "'~20.11.30'")
+            .replace("lodash: 4.17.20", "lodash: 4.17.21");
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_update_catalog_respects_ignore_and_pin() {
+        let file = pnpm_workspace("catalog:\n  react: ^18.2.0\n  lodash: 4.17.20\n");
+        let config = crate::config::UpdConfig {
+            ignore: vec!["react".to_string()],
+            pin: [("lodash".to_string(), "4.17.15".to_string())].into(),
+            ..Default::default()
+        };
+        let registry = MockRegistry::new("npm")
+            .with_version("react", "18.3.1")
+            .with_version("lodash", "4.17.21");
+
+        let result = PnpmWorkspaceUpdater::new()
+            .update(
+                file.path(),
+                &registry,
+                UpdateOptions::new(false, false).with_config(std::sync::Arc::new(config)),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.ignored.len(), 1);
+        assert_eq!(result.pinned.len(), 1);
+        assert_eq!(
+            fs::read_to_string(file.path()).unwrap(),
+            "catalog:\n  react: ^18.2.0\n  lodash: 4.17.15\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_dependencies_reads_catalog_entries() {
+        let file = pnpm_workspace(PNPM_WORKSPACE);
+        let deps = PnpmWorkspaceUpdater::new()
+            .parse_dependencies(file.path())
+            .unwrap();
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["react", "@types/node", "lodash", "react", "react-dom"]
+        );
+    }
+}
@@ -1,11 +1,11 @@
 use super::{
     FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
-    read_file_safe, write_file_atomic,
+    read_file_safe,
 };
 use crate::align::compare_versions;
 use crate::registry::Registry;
 use crate::updater::Lang;
-use crate::version::match_version_precision;
+use crate::version::{TagVersion, match_version_precision};
 use anyhow::Result;
 use futures::future::join_all;
 use regex::Regex;
@@ -45,12 +45,34 @@ impl PreCommitUpdater {
         Some(format!("{}/{}", owner, repo))
     }
 
-    /// Returns true if the repo line should be skipped (local, meta, or non-GitHub)
-    fn should_skip_repo(repo_url: &str) -> bool {
-        let trimmed = repo_url.trim();
-        trimmed == "local"
-            || trimmed == "meta"
-            || Self::extract_github_owner_repo(trimmed).is_none()
+    /// Extract the host-qualified project path from a GitLab URL, e.g.
+    /// `gitlab.com/pycqa/flake8`. Self-managed instances are recognized by a
+    /// `gitlab.` host name. Returns None for other URLs.
+    fn extract_gitlab_project(url: &str) -> Option<String> {
+        let url = url.trim();
+        let rest = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))?;
+        let (host, path) = rest.split_once('/')?;
+        if host != "gitlab.com" && !host.starts_with("gitlab.") {
+            return None;
+        }
+
+        let path = path.trim_end_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        let segments: Vec<&str> = path.split('/').collect();
+        if segments.len() < 2 || segments.iter().any(|s| s.is_empty()) {
+            return None;
+        }
+
+        Some(format!("{}/{}", host, path))
+    }
+
+    /// The registry key of a hook repo: `owner/repo` on GitHub, the
+    /// host-qualified project path on GitLab, None for anything else
+    /// (including `local` and `meta`).
+    fn hook_repo(repo_url: &str) -> Option<String> {
+        Self::extract_github_owner_repo(repo_url).or_else(|| Self::extract_gitlab_project(repo_url))
     }
 
     /// Compute the updated version string, preserving the `v` prefix and precision
@@ -88,11 +110,7 @@ impl PreCommitUpdater {
             // Check for repo: line
             if let Some(caps) = self.repo_re.captures(line) {
                 let repo_url = caps.get(1).unwrap().as_str().trim();
-                if Self::should_skip_repo(repo_url) {
-                    current_repo = None;
-                } else {
-                    current_repo = Self::extract_github_owner_repo(repo_url);
-                }
+                current_repo = Self::hook_repo(repo_url);
                 continue;
             }
 
@@ -107,6 +125,7 @@ impl PreCommitUpdater {
                     line_number: Some(line_idx + 1),
                     has_upper_bound: false,
                     is_bumpable: true,
+                    inherits_workspace: false,
                 });
                 current_repo = None;
             }
@@ -149,11 +168,7 @@ impl Updater for PreCommitUpdater {
 
             if let Some(caps) = self.repo_re.captures(line) {
                 let repo_url = caps.get(1).unwrap().as_str().trim();
-                if Self::should_skip_repo(repo_url) {
-                    current_repo = None;
-                } else {
-                    current_repo = Self::extract_github_owner_repo(repo_url);
-                }
+                current_repo = Self::hook_repo(repo_url);
                 continue;
             }
 
@@ -167,8 +182,18 @@ impl Updater for PreCommitUpdater {
                     result.unchanged += 1;
                 } else if options.should_ignore(&owner_repo) {
                     ignored_repos.push((line_idx, owner_repo, version));
+                } else if let Some(warning) = options.range_pin_warning(&owner_repo) {
+                    result.warnings.push(warning);
                 } else if let Some(pinned_version) = options.get_pinned_version(&owner_repo) {
                     pinned_repos.push((line_idx, owner_repo, version, pinned_version.to_string()));
+                } else if TagVersion::parse(&version).is_none() {
+                    // Commit SHAs and branch names are left alone unless pinned.
+                    result.skipped_sources.push((
+                        owner_repo,
+                        "rev is not a version tag".to_string(),
+                        Some(line_idx + 1),
+                    ));
+                    result.unchanged += 1;
                 } else {
                     repos_to_check.push((line_idx, owner_repo, version));
                 }
@@ -256,6 +281,14 @@ impl Updater for PreCommitUpdater {
                 };
 
                 match version_result {
+                    Ok(latest_version) if TagVersion::parse(&latest_version).is_none() => {
+                        result.warnings.push(format!(
+                            "{}: latest release tag '{}' does not look like a version",
+                            owner_repo, latest_version
+                        ));
+                        result.unchanged += 1;
+                        new_lines.push(line.to_string());
+                    }
                     Ok(latest_version) => {
                         // Apply cooldown policy before writing (registry path only; pins bypass it).
                         let (latest_version, held_back_record) = if *is_pinned {
@@ -297,11 +330,18 @@ impl Updater for PreCommitUpdater {
                             }
                         };
 
-                        let new_version = Self::compute_updated_version(
-                            current_version,
-                            &latest_version,
-                            options.full_precision,
-                        );
+                        // A pin replaces a SHA or branch rev as written: there
+                        // is no precision to match.
+                        let new_version =
+                            if *is_pinned && TagVersion::parse(current_version).is_none() {
+                                latest_version.clone()
+                            } else {
+                                Self::compute_updated_version(
+                                    current_version,
+                                    &latest_version,
+                                    options.full_precision,
+                                )
+                            };
 
                         if new_version != *current_version {
                             // Refuse to write a downgrade (registry path only; pins are intentional).
@@ -369,7 +409,7 @@ impl Updater for PreCommitUpdater {
             }
         }
 
-        if (!result.updated.is_empty() || !result.pinned.is_empty()) && !options.dry_run {
+        if !result.updated.is_empty() || !result.pinned.is_empty() {
             let line_ending = if content.contains("\r\n") {
                 "\r\n"
             } else {
@@ -383,7 +423,7 @@ impl Updater for PreCommitUpdater {
                 new_content
             };
 
-            write_file_atomic(path, &final_content)?;
+            result.commit_content(path, &content, final_content, options.dry_run)?;
         }
 
         Ok(result)
@@ -500,13 +540,13 @@ mod tests {
     }
 
     #[test]
-    fn test_skips_non_github_repos() {
+    fn test_skips_repos_on_unsupported_hosts() {
         let updater = PreCommitUpdater::new();
         let content = r#"repos:
-  - repo: https://gitlab.com/pycqa/flake8
+  - repo: https://bitbucket.org/team/hooks
     rev: 7.0.0
     hooks:
-      - id: flake8
+      - id: lint
   - repo: https://github.com/psf/black
     rev: 24.3.0
     hooks:
@@ -674,6 +714,9 @@ mod tests {
             ignore: vec!["pre-commit/pre-commit-hooks".to_string()],
             pin: pins,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PreCommitUpdater::new();
@@ -691,6 +734,66 @@ mod tests {
         assert_eq!(result.updated[0].0, "astral-sh/ruff-pre-commit");
     }
 
+    #[tokio::test]
+    async fn test_config_pin_moves_sha_rev() {
+        use crate::config::UpdConfig;
+        use std::sync::Arc;
+
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"repos:
+  - repo: https://github.com/psf/black
+    rev: 8a737e727ac5ab2f1d4cf5876720ed276dc8dc4b
+    hooks:
+      - id: black
+  - repo: https://github.com/astral-sh/ruff-pre-commit
+    rev: 3b2b2e3c1a0d8f5e6c7b9a0d1e2f3a4b5c6d7e8f
+    hooks:
+      - id: ruff
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("github-releases")
+            .with_version("psf/black", "24.3.0")
+            .with_version("astral-sh/ruff-pre-commit", "v0.4.0");
+
+        let mut pins = std::collections::HashMap::new();
+        pins.insert("psf/black".to_string(), "24.2.0".to_string());
+        let config = UpdConfig {
+            exclude: Vec::new(),
+            ignore: Vec::new(),
+            pin: pins,
+            cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
+        };
+
+        let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
+        let result = PreCommitUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.pinned,
+            vec![(
+                "psf/black".to_string(),
+                "8a737e727ac5ab2f1d4cf5876720ed276dc8dc4b".to_string(),
+                "24.2.0".to_string(),
+                Some(3)
+            )]
+        );
+        assert!(result.updated.is_empty());
+        assert_eq!(result.skipped_sources.len(), 1);
+        assert_eq!(result.skipped_sources[0].0, "astral-sh/ruff-pre-commit");
+        let written = std::fs::read_to_string(file.path()).unwrap();
+        assert!(written.contains("rev: 24.2.0\n"), "{written}");
+        assert!(written.contains("rev: 3b2b2e3c1a0d8f5e6c7b9a0d1e2f3a4b5c6d7e8f\n"));
+    }
+
     #[test]
     fn test_skips_commented_lines() {
         let updater = PreCommitUpdater::new();
@@ -714,6 +817,74 @@ mod tests {
         assert!(!updater.handles(FileType::Requirements));
     }
 
+    #[test]
+    fn test_parses_gitlab_repos_as_host_qualified_projects() {
+        let updater = PreCommitUpdater::new();
+        let content = r#"repos:
+  - repo: https://gitlab.com/pycqa/flake8.git
+    rev: 7.0.0
+    hooks:
+      - id: flake8
+  - repo: https://gitlab.corp.example/platform/tools/hooks
+    rev: v1.2.0
+    hooks:
+      - id: check
+"#;
+        let deps = updater.parse_dependencies_from_content(content);
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "gitlab.com/pycqa/flake8",
+                "gitlab.corp.example/platform/tools/hooks"
+            ]
+        );
+        assert_eq!(
+            PreCommitUpdater::extract_gitlab_project("https://gitlab.com/pycqa"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_only_version_tags_are_bumped() {
+        let mut file = NamedTempFile::new().unwrap();
+        let content = r#"repos:
+  - repo: https://gitlab.com/pycqa/flake8
+    rev: 7.0.0
+    hooks:
+      - id: flake8
+  - repo: https://github.com/psf/black
+    rev: 8c5b5b2e4c3d5c3c1a8e3fd6a2b2a9f5a0f3e7d1
+    hooks:
+      - id: black
+  - repo: https://github.com/example/nightly-hooks
+    rev: v1.0.0
+    hooks:
+      - id: nightly
+"#;
+        write!(file, "{}", content).unwrap();
+
+        let registry = MockRegistry::new("github-releases")
+            .with_version("gitlab.com/pycqa/flake8", "7.1.1")
+            .with_version("psf/black", "24.3.0")
+            .with_version("example/nightly-hooks", "nightly");
+        let result = PreCommitUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].0, "gitlab.com/pycqa/flake8");
+        assert_eq!(result.skipped_sources.len(), 1);
+        assert_eq!(result.skipped_sources[0].0, "psf/black");
+        assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+        assert!(result.warnings[0].contains("nightly"));
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            content.replace("rev: 7.0.0", "rev: 7.1.1")
+        );
+    }
+
     #[tokio::test]
     async fn test_registry_error_populates_errors() {
         let mut file = NamedTempFile::new().unwrap();
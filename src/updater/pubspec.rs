@@ -0,0 +1,541 @@
+use super::{
+    FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
+    read_file_safe,
+};
+use crate::align::compare_versions;
+use crate::registry::Registry;
+use crate::updater::Lang;
+use crate::version::is_prerelease_semver;
+use anyhow::Result;
+use futures::future::join_all;
+use std::ops::Range;
+use std::path::Path;
+
+/// Updates the hosted `dependencies:` and `dev_dependencies:` of a Dart or
+/// Flutter `pubspec.yaml` from pub.dev.
+pub struct PubspecUpdater;
+
+/// Top-level pubspec sections whose entries are updated.
+const DEPENDENCY_SECTIONS: [&str; 2] = ["dependencies", "dev_dependencies"];
+
+/// Map a pubspec section onto a `--group` name.
+fn section_group(section: &str) -> Option<&str> {
+    match section {
+        "dev_dependencies" => Some("dev"),
+        _ => None,
+    }
+}
+
+/// A `name: version` entry of a dependency section.
+struct PubspecEntry {
+    /// Section the entry was found in, e.g. `dev_dependencies`
+    section: String,
+    name: String,
+    /// Constraint operator kept in front of the version: `^`, `>=` or none.
+    prefix: String,
+    /// Version after the prefix, e.g. `1.2.0`
+    version: String,
+    /// Byte range of `version` in the file
+    version_range: Range<usize>,
+    line_num: Option<usize>,
+}
+
+/// Strip a YAML comment (` # ...`) from the value of a mapping entry.
+fn strip_comment(value: &str) -> &str {
+    match value.find(" #").or_else(|| value.find("\t#")) {
+        Some(idx) => &value[..idx],
+        None if value.starts_with('#') => "",
+        None => value,
+    }
+}
+
+/// Split a block-mapping line into its key and the byte offset of the value
+/// within `line`. Quoted keys are unquoted.
+fn split_mapping_entry(line: &str) -> Option<(&str, usize)> {
+    let (key, after_key) = match line.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let end = line[1..].find(quote)? + 1;
+            (&line[1..end], end + 1)
+        }
+        _ => {
+            let end = line
+                .find(": ")
+                .or_else(|| line.find(":\t"))
+                .or_else(|| line.strip_suffix(':').map(str::len))?;
+            (line[..end].trim_end(), end)
+        }
+    };
+    let rest = line[after_key..].trim_start();
+    let rest = rest.strip_prefix(':')?;
+    Some((key, line.len() - rest.len()))
+}
+
+impl PubspecUpdater {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Walk the top-level dependency sections line by line, collecting
+    /// entries whose value is a plain, caret or `>=` version. Entries written
+    /// as maps (`sdk:`, `git:`, `path:` and custom `hosted:` sources) and
+    /// other constraints (`any`, `>=1.0.0 <2.0.0`) are left alone.
+    fn scan(content: &str) -> Vec<PubspecEntry> {
+        let mut entries = Vec::new();
+        let mut section: Option<&str> = None;
+        // Indentation of the entries of the current section; deeper lines
+        // belong to a map-valued entry.
+        let mut entry_indent: Option<usize> = None;
+        let mut offset = 0;
+
+        for (idx, raw_line) in content.split_inclusive('\n').enumerate() {
+            let line_start = offset;
+            offset += raw_line.len();
+            let line = raw_line.trim_end_matches(['\n', '\r']);
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+
+            if indent == 0 {
+                let key = split_mapping_entry(trimmed).map(|(key, _)| key);
+                section = key.and_then(|key| DEPENDENCY_SECTIONS.into_iter().find(|s| *s == key));
+                entry_indent = None;
+                continue;
+            }
+            let Some(current_section) = section else {
+                continue;
+            };
+            if *entry_indent.get_or_insert(indent) != indent {
+                continue;
+            }
+
+            let Some((name, value_offset)) = split_mapping_entry(trimmed) else {
+                continue;
+            };
+            let raw_value = &trimmed[value_offset..];
+            let padding = raw_value.len() - raw_value.trim_start().len();
+            let value = strip_comment(&raw_value[padding..]).trim_end();
+            let mut value_start = line_start + indent + value_offset + padding;
+            let value = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) if value.len() > 1 && value.ends_with(quote) => {
+                    value_start += 1;
+                    &value[1..value.len() - 1]
+                }
+                _ => value,
+            };
+            if value.is_empty() {
+                continue;
+            }
+
+            let prefix = ["^", ">="]
+                .into_iter()
+                .find(|p| value.starts_with(p))
+                .unwrap_or("");
+            let version = &value[prefix.len()..];
+            if semver::Version::parse(version).is_err() {
+                continue;
+            }
+            let version_start = value_start + prefix.len();
+            entries.push(PubspecEntry {
+                section: current_section.to_string(),
+                name: name.to_string(),
+                prefix: prefix.to_string(),
+                version: version.to_string(),
+                version_range: version_start..version_start + version.len(),
+                line_num: Some(idx + 1),
+            });
+        }
+
+        entries
+    }
+}
+
+impl Default for PubspecUpdater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Updater for PubspecUpdater {
+    async fn update(
+        &self,
+        path: &Path,
+        registry: &dyn Registry,
+        options: UpdateOptions,
+    ) -> Result<UpdateResult> {
+        let content = read_file_safe(path)?;
+        let mut result = UpdateResult::default();
+
+        // First pass: collect dependencies and separate by config status
+        let mut pinned_deps: Vec<(PubspecEntry, String)> = Vec::new();
+        let mut deps_to_check: Vec<PubspecEntry> = Vec::new();
+
+        for entry in Self::scan(&content) {
+            if options.is_group_filtered_out(section_group(&entry.section))
+                || options.is_package_filtered_out(&entry.name)
+            {
+                result.unchanged += 1;
+                continue;
+            }
+
+            if options.should_ignore(&entry.name) {
+                result
+                    .ignored
+                    .push((entry.name, entry.version, entry.line_num));
+                continue;
+            }
+
+            if let Some(warning) = options.range_pin_warning(&entry.name) {
+                result.warnings.push(warning);
+                continue;
+            }
+
+            if let Some(pinned_version) = options.get_pinned_version(&entry.name) {
+                pinned_deps.push((entry, pinned_version.to_string()));
+                continue;
+            }
+
+            deps_to_check.push(entry);
+        }
+
+        // (byte range of the old version, new version)
+        let mut writes: Vec<(Range<usize>, String)> = Vec::new();
+
+        // Process pinned packages (no registry fetch needed)
+        for (entry, pinned_version) in pinned_deps {
+            if pinned_version != entry.version {
+                writes.push((entry.version_range.clone(), pinned_version.clone()));
+                result
+                    .pinned
+                    .push((entry.name, entry.version, pinned_version, entry.line_num));
+            } else {
+                result.unchanged += 1;
+            }
+        }
+
+        // Fetch versions for remaining deps in parallel. Stable releases
+        // only, unless the current version is already a pre-release.
+        let version_futures: Vec<_> = deps_to_check
+            .iter()
+            .map(|entry| async {
+                if is_prerelease_semver(&entry.version) || options.allows_prerelease(&entry.name) {
+                    registry
+                        .get_latest_version_including_prereleases(&entry.name)
+                        .await
+                } else if entry.prefix == "^" {
+                    // Keep within the range the caret already allows.
+                    let spec = format!("^{}", entry.version);
+                    registry
+                        .get_latest_version_matching(&entry.name, &spec)
+                        .await
+                } else if let Some(req) = options.semver_compatible_req(&entry.version) {
+                    registry
+                        .get_latest_version_matching(&entry.name, &req)
+                        .await
+                } else {
+                    registry.get_latest_version(&entry.name).await
+                }
+            })
+            .collect();
+
+        let version_results = join_all(version_futures).await;
+
+        for (entry, version_result) in deps_to_check.into_iter().zip(version_results) {
+            let latest_version = match version_result {
+                Ok(v) => v,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", entry.name, e));
+                    continue;
+                }
+            };
+
+            // Refuse silent promotion from a pre-release to a stable release.
+            let current_is_prerelease = is_prerelease_semver(&entry.version);
+            if current_is_prerelease && !is_prerelease_semver(&latest_version) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            let (outcome, note) = crate::updater::apply_cooldown(
+                registry,
+                &entry.name,
+                &entry.version,
+                &latest_version,
+                None,
+                current_is_prerelease,
+                &options,
+            )
+            .await;
+            if let Some(msg) = note {
+                options.note_cooldown_unavailable(&msg);
+            }
+            let (latest_version, held_back_record) = match outcome {
+                crate::updater::CooldownOutcome::Unchanged(v) => (v, None),
+                crate::updater::CooldownOutcome::HeldBack {
+                    chosen,
+                    skipped_version,
+                    skipped_published_at,
+                } => (chosen, Some((skipped_version, skipped_published_at))),
+                crate::updater::CooldownOutcome::Skipped {
+                    skipped_version,
+                    skipped_published_at,
+                } => {
+                    result.skipped_by_cooldown.push((
+                        entry.name,
+                        entry.version,
+                        skipped_version,
+                        skipped_published_at,
+                    ));
+                    continue;
+                }
+            };
+
+            if latest_version == entry.version {
+                result.unchanged += 1;
+                continue;
+            }
+
+            // Refuse to write a downgrade.
+            if compare_versions(&latest_version, &entry.version, Lang::Dart)
+                != std::cmp::Ordering::Greater
+            {
+                result.warnings.push(downgrade_warning(
+                    &entry.name,
+                    &latest_version,
+                    &entry.version,
+                ));
+                result.unchanged += 1;
+                continue;
+            }
+
+            // Bump level exceeds the --only-bump/--max-bump ceiling.
+            if !options.allows_bump(&entry.version, &latest_version) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            writes.push((entry.version_range.clone(), latest_version.clone()));
+            result.updated.push((
+                entry.name.clone(),
+                entry.version.clone(),
+                latest_version.clone(),
+                entry.line_num,
+            ));
+            if let Some((skipped_version, skipped_published_at)) = held_back_record {
+                result.held_back.push((
+                    entry.name,
+                    entry.version,
+                    latest_version,
+                    skipped_version,
+                    skipped_published_at,
+                ));
+            }
+        }
+
+        if !writes.is_empty() {
+            // Splice from the end so earlier ranges stay valid.
+            writes.sort_by_key(|w| std::cmp::Reverse(w.0.start));
+            let mut new_content = content.clone();
+            for (range, new_version) in &writes {
+                new_content.replace_range(range.clone(), new_version);
+            }
+            result.commit_content(path, &content, new_content, options.dry_run)?;
+        }
+
+        Ok(result)
+    }
+
+    fn handles(&self, file_type: FileType) -> bool {
+        file_type == FileType::Pubspec
+    }
+
+    fn parse_dependencies(&self, path: &Path) -> Result<Vec<ParsedDependency>> {
+        let content = read_file_safe(path)?;
+        Ok(Self::scan(&content)
+            .into_iter()
+            .map(|entry| ParsedDependency {
+                name: entry.name,
+                version: entry.version,
+                line_number: entry.line_num,
+                has_upper_bound: false,
+                is_bumpable: true,
+                inherits_workspace: false,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MockRegistry;
+    use std::fs;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn pubspec(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::with_suffix(".yaml").unwrap();
+        write!(file, "{}", content).unwrap();
+        file
+    }
+
+    const PUBSPEC: &str = "name: my_app
+version: 1.0.0+1
+
+environment:
+  sdk: ^3.4.0
+
+dependencies:
+  flutter:
+    sdk: flutter
+  http: ^1.1.0  # networking
+  path: 1.8.3
+  provider: \">=6.0.0\"
+  local_lib:
+    path: ../local_lib
+  forked:
+    git:
+      url: https://github.com/example/forked.git
+      ref: v1.0.0
+  private_pkg:
+    hosted: https://pub.example.com
+    version: ^2.0.0
+  anything: any
+
+dev_dependencies:
+  flutter_test:
+    sdk: flutter
+  lints: ^3.0.0
+  build_runner: 2.4.0-dev.1
+
+flutter:
+  uses-material-design: true
+";
+
+    #[test]
+    fn test_scan_collects_hosted_version_entries() {
+        let entries = PubspecUpdater::scan(PUBSPEC);
+        let summary: Vec<(&str, &str, &str, &str, Option<usize>)> = entries
+            .iter()
+            .map(|e| {
+                (
+                    e.section.as_str(),
+                    e.name.as_str(),
+                    e.prefix.as_str(),
+                    e.version.as_str(),
+                    e.line_num,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("dependencies", "http", "^", "1.1.0", Some(10)),
+                ("dependencies", "path", "", "1.8.3", Some(11)),
+                ("dependencies", "provider", ">=", "6.0.0", Some(12)),
+                ("dev_dependencies", "lints", "^", "3.0.0", Some(27)),
+                (
+                    "dev_dependencies",
+                    "build_runner",
+                    "",
+                    "2.4.0-dev.1",
+                    Some(28)
+                ),
+            ]
+        );
+        for entry in &entries {
+            assert_eq!(&PUBSPEC[entry.version_range.clone()], entry.version);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_preserves_caret_and_skips_non_hosted() {
+        let file = pubspec(PUBSPEC);
+        let registry = MockRegistry::new("pub")
+            .with_version("http", "2.0.0")
+            .with_constrained("http", "^1.1.0", "1.2.2")
+            .with_version("path", "1.9.0")
+            .with_version("provider", "6.1.2")
+            .with_constrained("lints", "^3.0.0", "3.0.0")
+            .with_prerelease("build_runner", "2.4.13", "2.5.0-dev.2");
+
+        let result = PubspecUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let updated: Vec<(&str, &str)> = result
+            .updated
+            .iter()
+            .map(|u| (u.0.as_str(), u.2.as_str()))
+            .collect();
+        assert_eq!(
+            updated,
+            vec![
+                ("http", "1.2.2"),
+                ("path", "1.9.0"),
+                ("provider", "6.1.2"),
+                ("build_runner", "2.5.0-dev.2"),
+            ]
+        );
+        assert_eq!(result.unchanged, 1);
+
+        let expected = PUBSPEC
+            .replace("http: ^1.1.0", "http: ^1.2.2")
+            .replace("path: 1.8.3", "path: 1.9.0")
+            .replace("\">=6.0.0\"", "\">=6.1.2\"")
+            .replace("2.4.0-dev.1", "2.5.0-dev.2");
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_update_respects_ignore_pin_and_group() {
+        let file = pubspec(
+            "dependencies:\n  http: ^1.1.0\n  path: 1.8.3\ndev_dependencies:\n  lints: 3.0.0\n",
+        );
+        let config = crate::config::UpdConfig {
+            ignore: vec!["http".to_string()],
+            pin: [("lints".to_string(), "3.0.1".to_string())].into(),
+            ..Default::default()
+        };
+        let registry = MockRegistry::new("pub")
+            .with_version("http", "1.2.2")
+            .with_version("path", "1.9.0")
+            .with_version("lints", "4.0.0");
+
+        let result = PubspecUpdater::new()
+            .update(
+                file.path(),
+                &registry,
+                UpdateOptions::new(false, false)
+                    .with_config(std::sync::Arc::new(config))
+                    .with_groups(vec!["dev".to_string()]),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.ignored.len(), 0);
+        assert_eq!(result.pinned.len(), 1);
+        assert_eq!(result.unchanged, 2);
+        assert_eq!(
+            fs::read_to_string(file.path()).unwrap(),
+            "dependencies:\n  http: ^1.1.0\n  path: 1.8.3\ndev_dependencies:\n  lints: 3.0.1\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_dependencies_reads_hosted_entries() {
+        let file = pubspec(PUBSPEC);
+        let deps = PubspecUpdater::new()
+            .parse_dependencies(file.path())
+            .unwrap();
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["http", "path", "provider", "lints", "build_runner"]
+        );
+    }
+}
@@ -1,18 +1,99 @@
+use super::python_markers::{RequiresPython, marker_is_dead};
 use super::{
-    FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
-    read_file_safe, write_file_atomic,
+    FileType, ParsedDependency, Strategy, UnconstrainedDependency, UpdateOptions, UpdateResult,
+    Updater, downgrade_warning, line_of_quoted_literal, pep440_has_lower_bound, read_file_safe,
+    resolve_pin, split_pep508,
 };
 use crate::align::compare_versions;
+use crate::config::normalize_package_name;
 use crate::registry::{MultiPyPiRegistry, PyPiRegistry, Registry};
 use crate::updater::Lang;
-use crate::version::{is_prerelease_pep440, is_stable_pep440, match_version_precision};
+use crate::version::{
+    is_prerelease_pep440, is_stable_pep440, match_compatible_release, match_version_precision,
+    pep440_caret_range, pep440_tilde_range,
+};
 use anyhow::{Result, anyhow};
 use futures::future::join_all;
 use regex::Regex;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
-use toml_edit::{DocumentMut, Formatted, Item, Value};
+use toml_edit::{DocumentMut, Formatted, Item, TableLike, Value};
+
+/// (package, entry, prefix, current_version, range to resolve within, source line).
+///
+/// `entry` is the index into a multiple-constraints array
+/// (`foo = [{version = "1.0", python = "<3.8"}, ...]`), `None` for a plain
+/// version string.
+type PoetryLookup = (
+    String,
+    Option<usize>,
+    String,
+    String,
+    Option<String>,
+    Option<usize>,
+);
+
+/// (package, entry, prefix, current_version, pinned_version, source line).
+type PoetryPin = (String, Option<usize>, String, String, String, Option<usize>);
+
+/// A Poetry version string (`^1.2`, `~1.2`, `1.2`) split into prefix and version.
+fn split_poetry_version(version_str: &str) -> (String, String) {
+    if version_str.starts_with('^') || version_str.starts_with('~') {
+        (version_str[..1].to_string(), version_str[1..].to_string())
+    } else {
+        (String::new(), version_str.to_string())
+    }
+}
+
+/// The version string of a Poetry dependency, or of one entry of its
+/// multiple-constraints array.
+fn poetry_version_mut<'a>(
+    deps_table: &'a mut toml_edit::Table,
+    key: &str,
+    entry: Option<usize>,
+) -> Option<&'a mut Formatted<String>> {
+    match (deps_table.get_mut(key)?, entry) {
+        (Item::Value(Value::String(formatted)), None) => Some(formatted),
+        (Item::Value(Value::Array(entries)), Some(index)) => {
+            match entries
+                .get_mut(index)?
+                .as_inline_table_mut()?
+                .get_mut("version")?
+            {
+                Value::String(formatted) => Some(formatted),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Replace a Poetry version string, keeping its surrounding decoration.
+fn set_poetry_version(
+    deps_table: &mut toml_edit::Table,
+    key: &str,
+    entry: Option<usize>,
+    new_val: String,
+) {
+    if let Some(formatted) = poetry_version_mut(deps_table, key, entry) {
+        let decor = formatted.decor().clone();
+        let mut new_formatted = Formatted::new(new_val);
+        *new_formatted.decor_mut() = decor;
+        *formatted = new_formatted;
+    }
+}
+
+/// The dependency group a section belongs to, for `--group` filtering.
+/// `None` for the ungrouped `[project] dependencies` and Poetry's main table.
+fn section_group(section_path: &str) -> Option<&str> {
+    if section_path == "tool.poetry.dev-dependencies" {
+        return Some("dev");
+    }
+    section_path
+        .strip_prefix("project.optional-dependencies.")
+        .or_else(|| section_path.strip_prefix("dependency-groups."))
+}
 
 pub struct PyProjectUpdater {
     // Regex to extract version from dependency string
@@ -32,6 +113,8 @@ struct PyProjectLineIndex {
 struct ArraySectionState {
     section_path: String,
     depth: usize,
+    /// Multi-line string left open at the end of the previous line.
+    open_string: Option<MultilineString>,
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -40,17 +123,33 @@ struct ArrayBracketCounts {
     closing: usize,
 }
 
+/// Kind of TOML multi-line string (`"""` or `'''`) spanning array lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MultilineString {
+    Basic,
+    Literal,
+}
+
+/// Structural brackets and string-literal contents found on one array line.
+#[derive(Debug, Default)]
+struct ArrayLineScan<'a> {
+    brackets: ArrayBracketCounts,
+    /// Contents of each string on the line; for a multi-line string, only
+    /// the part on this line.
+    literals: Vec<&'a str>,
+}
+
 impl PyProjectUpdater {
     pub fn new() -> Self {
         let version_re = Regex::new(
-            r"^([a-zA-Z0-9][-a-zA-Z0-9._]*)(\[[^\]]+\])?\s*(==|>=|<=|~=|!=|>|<)\s*([^\s,;]+)",
+            r"^([a-zA-Z0-9][-a-zA-Z0-9._]*)(\[[^\]]+\])?\s*(===|==|>=|<=|~=|!=|>|<)\s*([^\s,;]+)",
         )
         .expect("Invalid regex");
 
         // Match the full constraint including additional constraints after commas
         // E.g., ">=2.8.0,<9" or ">=1.0.0,!=1.5.0,<2.0.0"
         let constraint_re = Regex::new(
-            r"^([a-zA-Z0-9][-a-zA-Z0-9._]*)(\[[^\]]+\])?\s*((?:==|>=|<=|~=|!=|>|<)[^\s;]+(?:\s*,\s*(?:==|>=|<=|~=|!=|>|<)[^\s;,]+)*)",
+            r"^([a-zA-Z0-9][-a-zA-Z0-9._]*)(\[[^\]]+\])?\s*((?:===|==|>=|<=|~=|!=|>|<)[^\s;]+(?:\s*,\s*(?:===|==|>=|<=|~=|!=|>|<)[^\s;,]+)*)",
         )
         .expect("Invalid regex");
 
@@ -62,6 +161,10 @@ impl PyProjectUpdater {
 
     /// Parse dependency string and return (package, first_version, full_constraint)
     fn parse_dependency(&self, dep: &str) -> Option<(String, String, String)> {
+        // Generated files sometimes pad the string (or spread it over a
+        // multi-line string); the requirement starts at the first non-blank.
+        let dep = dep.trim_start();
+
         // First get the full constraint
         let full_constraint = self
             .constraint_re
@@ -78,7 +181,7 @@ impl PyProjectUpdater {
     }
 
     /// Check if constraint is simple (no upper bounds that could be violated)
-    fn is_simple_constraint(constraint: &str) -> bool {
+    pub(super) fn is_simple_constraint(constraint: &str) -> bool {
         // If there are multiple constraints (comma-separated), need constraint-aware lookup
         if constraint.contains(',') {
             return false;
@@ -104,19 +207,33 @@ impl PyProjectUpdater {
     /// Check if constraint is an upper-bound-only constraint (e.g., "<6", "<=5.0")
     /// These should never be "updated" because they define a ceiling, not a floor.
     /// Updating them would only make the constraint more restrictive.
-    fn is_upper_bound_only(constraint: &str) -> bool {
+    pub(super) fn is_upper_bound_only(constraint: &str) -> bool {
         let trimmed = constraint.trim();
         (trimmed.starts_with('<') || trimmed.starts_with("<=")) && !trimmed.contains(',') // No other constraints (like >=x,<y)
     }
 
+    /// Poetry requirements: caret, tilde and bare versions all set a floor;
+    /// `*` does not.
+    fn poetry_has_lower_bound(requirement: &str) -> bool {
+        let trimmed = requirement.trim();
+        if trimmed.is_empty() || trimmed == "*" {
+            return false;
+        }
+        trimmed.starts_with(['^', '~'])
+            || trimmed.starts_with(|c: char| c.is_ascii_digit())
+            || pep440_has_lower_bound(trimmed)
+    }
+
     fn update_dependency(&self, dep: &str, new_version: &str) -> String {
-        if let Some(caps) = self.version_re.captures(dep) {
+        let padding = dep.len() - dep.trim_start().len();
+        if let Some(caps) = self.version_re.captures(&dep[padding..]) {
             // Only replace the version number itself, preserving everything else
             // (package name, extras, operator, AND any additional constraints like ,<6)
             let version_match = caps.get(4).unwrap();
 
             let mut result = dep.to_string();
-            result.replace_range(version_match.range(), new_version);
+            let range = version_match.start() + padding..version_match.end() + padding;
+            result.replace_range(range, new_version);
             result
         } else {
             dep.to_string()
@@ -215,6 +332,94 @@ impl PyProjectUpdater {
         }
     }
 
+    /// Describe PEP 508 requirements whose environment markers can never hold
+    /// for an interpreter allowed by `[project] requires-python`.
+    fn dead_marker_warnings(doc: &DocumentMut, content: &str) -> Vec<String> {
+        let Some(Item::Table(project)) = doc.get("project") else {
+            return Vec::new();
+        };
+        let Some(requires_python) = project.get("requires-python").and_then(Item::as_str) else {
+            return Vec::new();
+        };
+        let Some(parsed) = RequiresPython::parse(requires_python) else {
+            return Vec::new();
+        };
+
+        let mut arrays = Vec::new();
+        arrays.extend(project.get("dependencies").and_then(Item::as_array));
+        if let Some(Item::Table(opt_deps)) = project.get("optional-dependencies") {
+            arrays.extend(opt_deps.iter().filter_map(|(_, group)| group.as_array()));
+        }
+        if let Some(Item::Table(groups)) = doc.get("dependency-groups") {
+            arrays.extend(groups.iter().filter_map(|(_, group)| group.as_array()));
+        }
+
+        let mut warnings = Vec::new();
+        for requirement in arrays.into_iter().flatten().filter_map(|v| v.as_str()) {
+            let Some((_, marker)) = requirement.split_once(';') else {
+                continue;
+            };
+            let marker = marker.trim();
+            if marker_is_dead(marker, &parsed) {
+                let location = line_of_quoted_literal(content, requirement)
+                    .map(|line| format!("line {line}: "))
+                    .unwrap_or_default();
+                warnings.push(format!(
+                    "{location}'{requirement}' is dead: marker '{marker}' can never match requires-python '{requires_python}'"
+                ));
+            }
+        }
+        warnings
+    }
+
+    /// Dependencies `[tool.uv.sources]` maps to a git, path, workspace, or
+    /// direct URL source, keyed by normalized name, with the reason they are
+    /// not resolved from PyPI. Sources naming an `index` stay registry-backed.
+    fn uv_non_registry_sources(doc: &DocumentMut) -> HashMap<String, &'static str> {
+        let Some(sources) = doc
+            .get("tool")
+            .and_then(|tool| tool.get("uv"))
+            .and_then(|uv| uv.get("sources"))
+            .and_then(Item::as_table_like)
+        else {
+            return HashMap::new();
+        };
+
+        sources
+            .iter()
+            .filter_map(|(name, item)| {
+                // Alternatives split by marker (`foo = [{ git = ... }, { index = ... }]`)
+                // are skipped when any of them is a non-registry source.
+                let reason = match item {
+                    Item::Value(Value::Array(alternatives)) => alternatives
+                        .iter()
+                        .filter_map(Value::as_inline_table)
+                        .find_map(|source| Self::uv_source_kind(source)),
+                    Item::ArrayOfTables(alternatives) => alternatives
+                        .iter()
+                        .find_map(|source| Self::uv_source_kind(source)),
+                    _ => item.as_table_like().and_then(Self::uv_source_kind),
+                }?;
+                Some((normalize_package_name(name), reason))
+            })
+            .collect()
+    }
+
+    fn uv_source_kind(source: &dyn TableLike) -> Option<&'static str> {
+        if source.contains_key("git") {
+            Some("uv git source")
+        } else if source.contains_key("path") {
+            Some("uv path source")
+        } else if source.contains_key("workspace") {
+            Some("uv workspace source")
+        } else if source.contains_key("url") {
+            Some("uv url source")
+        } else {
+            None
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn update_array_deps(
         &self,
         array: &mut toml_edit::Array,
@@ -222,6 +427,7 @@ impl PyProjectUpdater {
         result: &mut UpdateResult,
         line_index: &PyProjectLineIndex,
         section_path: &str,
+        uv_sources: &HashMap<String, &'static str>,
         options: &UpdateOptions,
     ) {
         // First pass: collect all dependencies and separate by config status
@@ -230,6 +436,7 @@ impl PyProjectUpdater {
             Vec::new();
         let mut deps_to_check: Vec<(usize, String, String, String, String, Option<usize>)> =
             Vec::new();
+        let group_filtered = options.is_group_filtered_out(section_group(section_path));
 
         for i in 0..array.len() {
             if let Some(item) = array.get(i)
@@ -238,7 +445,15 @@ impl PyProjectUpdater {
             {
                 let line_num = line_index.line_for(section_path, &package);
 
-                if options.is_package_filtered_out(&package) {
+                // git, path, and workspace sources have no PyPI version to update to
+                if let Some(reason) = uv_sources.get(&normalize_package_name(&package)) {
+                    result
+                        .skipped_sources
+                        .push((package, reason.to_string(), line_num));
+                    continue;
+                }
+
+                if group_filtered || options.is_package_filtered_out(&package) {
                     result.unchanged += 1;
                     continue;
                 }
@@ -262,6 +477,9 @@ impl PyProjectUpdater {
                     continue;
                 }
 
+                // Resolve against the compatible range under `--strategy compatible`.
+                let full_constraint =
+                    options.pep440_resolution_spec(&full_constraint, &current_version);
                 deps_to_check.push((
                     i,
                     s.to_string(),
@@ -278,9 +496,25 @@ impl PyProjectUpdater {
             result.ignored.push((package, version, line_num));
         }
 
-        // Process pinned packages (no registry fetch needed)
+        // Process pinned packages; only range pins need a registry lookup
+        let pin_futures: Vec<_> = pinned_deps
+            .iter()
+            .map(|(_, _, package, _, pinned_version, _)| {
+                resolve_pin(registry, package, pinned_version)
+            })
+            .collect();
+        let pin_results = join_all(pin_futures).await;
         let mut updates: Vec<(usize, String)> = Vec::new();
-        for (i, dep_str, package, current_version, pinned_version, line_num) in pinned_deps {
+        for ((i, dep_str, package, current_version, _, line_num), pin_result) in
+            pinned_deps.into_iter().zip(pin_results)
+        {
+            let pinned_version = match pin_result {
+                Ok(version) => version,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", package, e));
+                    continue;
+                }
+            };
             let matched_version = if options.full_precision {
                 pinned_version.clone()
             } else {
@@ -303,7 +537,7 @@ impl PyProjectUpdater {
             .iter()
             .map(
                 |(_, _, package, current_version, full_constraint, _)| async {
-                    if !is_stable_pep440(current_version) {
+                    if !is_stable_pep440(current_version) || options.allows_prerelease(package) {
                         registry
                             .get_latest_version_including_prereleases(package)
                             .await
@@ -381,8 +615,18 @@ impl PyProjectUpdater {
                         }
                     };
 
-                    // Match the precision of the original version (unless full precision requested)
-                    let matched_version = if options.full_precision {
+                    // Match the precision of the original version (unless full precision
+                    // requested). A `~=` pin always keeps its precision, since that sets
+                    // its ceiling; a release past the ceiling leaves the entry alone.
+                    let matched_version = if full_constraint.starts_with("~=") {
+                        match match_compatible_release(&current_version, &latest_version) {
+                            Some(version) => version,
+                            None => {
+                                result.unchanged += 1;
+                                continue;
+                            }
+                        }
+                    } else if options.full_precision {
                         latest_version.clone()
                     } else {
                         match_version_precision(&current_version, &latest_version)
@@ -458,41 +702,63 @@ impl PyProjectUpdater {
     ) {
         // First pass: collect dependencies and separate by config status
         let mut ignored_deps: Vec<(String, String, Option<usize>)> = Vec::new();
-        let mut pinned_deps: Vec<(String, String, String, String, Option<usize>)> = Vec::new();
-        let mut deps_to_check: Vec<(String, String, String, Option<usize>)> = Vec::new();
+        let mut pinned_deps: Vec<PoetryPin> = Vec::new();
+        let mut deps_to_check: Vec<PoetryLookup> = Vec::new();
+        let group_filtered = options.is_group_filtered_out(section_group(section_path));
 
         for (key, item) in deps_table.iter() {
             if key == "python" {
                 continue;
             }
 
-            if let Item::Value(Value::String(s)) = item {
-                let version_str = s.value().to_string();
-                let (prefix, version) =
-                    if version_str.starts_with('^') || version_str.starts_with('~') {
-                        (version_str[..1].to_string(), version_str[1..].to_string())
-                    } else {
-                        (String::new(), version_str.clone())
-                    };
+            // A plain version string, or a multiple-constraints array whose
+            // entries each pin a version for a range of Python versions.
+            let entries: Vec<(Option<usize>, String)> = match item {
+                Item::Value(Value::String(s)) => vec![(None, s.value().to_string())],
+                Item::Value(Value::Array(arr)) => arr
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, entry)| {
+                        let version = entry.as_inline_table()?.get("version")?.as_str()?;
+                        Some((Some(index), version.to_string()))
+                    })
+                    .collect(),
+                _ => continue,
+            };
+            if entries.is_empty() {
+                continue;
+            }
 
-                let package = key.to_string();
-                let line_num = line_index.line_for(section_path, &package);
+            let package = key.to_string();
+            let line_num = line_index.line_for(section_path, &package);
 
-                if options.is_package_filtered_out(&package) {
-                    result.unchanged += 1;
-                    continue;
-                }
+            if group_filtered || options.is_package_filtered_out(&package) {
+                result.unchanged += entries.len();
+                continue;
+            }
+
+            // Only the newest entry of a multiple-constraints array follows
+            // `--strategy`; the others belong to older Python versions and
+            // stay within the range their own constraint implies.
+            let newest = entries
+                .iter()
+                .map(|(_, version_str)| split_poetry_version(version_str).1)
+                .max_by(|a, b| compare_versions(a, b, Lang::Python));
+
+            for (entry, version_str) in entries {
+                let (prefix, version) = split_poetry_version(&version_str);
 
                 // Check if package should be ignored
                 if options.should_ignore(&package) {
-                    ignored_deps.push((package, version, line_num));
+                    ignored_deps.push((package.clone(), version, line_num));
                     continue;
                 }
 
                 // Check if package has a pinned version
                 if let Some(pinned_version) = options.get_pinned_version(&package) {
                     pinned_deps.push((
-                        package,
+                        package.clone(),
+                        entry,
                         prefix,
                         version,
                         pinned_version.to_string(),
@@ -501,7 +767,27 @@ impl PyProjectUpdater {
                     continue;
                 }
 
-                deps_to_check.push((package, prefix, version, line_num));
+                // Under `--strategy compatible` (and for the older entries of
+                // an array), caret, tilde and bare versions resolve within the
+                // range they imply.
+                let ranged = options.strategy == Strategy::Compatible
+                    || (entry.is_some() && newest.as_deref() != Some(version.as_str()));
+                let compatible_range = if ranged {
+                    match prefix.as_str() {
+                        "~" => pep440_tilde_range(&version),
+                        _ => pep440_caret_range(&version),
+                    }
+                } else {
+                    None
+                };
+                deps_to_check.push((
+                    package.clone(),
+                    entry,
+                    prefix,
+                    version,
+                    compatible_range,
+                    line_num,
+                ));
             }
         }
 
@@ -510,8 +796,22 @@ impl PyProjectUpdater {
             result.ignored.push((package, version, line_num));
         }
 
-        // Process pinned packages (no registry fetch needed)
-        for (key, prefix, version, pinned_version, line_num) in pinned_deps {
+        // Process pinned packages; only range pins need a registry lookup
+        let pin_futures: Vec<_> = pinned_deps
+            .iter()
+            .map(|(key, _, _, _, pinned_version, _)| resolve_pin(registry, key, pinned_version))
+            .collect();
+        let pin_results = join_all(pin_futures).await;
+        for ((key, entry, prefix, version, _, line_num), pin_result) in
+            pinned_deps.into_iter().zip(pin_results)
+        {
+            let pinned_version = match pin_result {
+                Ok(version) => version,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", key, e));
+                    continue;
+                }
+            };
             let matched_version = if options.full_precision {
                 pinned_version.clone()
             } else {
@@ -525,12 +825,7 @@ impl PyProjectUpdater {
                     .push((key.clone(), version, matched_version.clone(), line_num));
 
                 // Preserve decoration when updating
-                if let Some(Item::Value(Value::String(formatted))) = deps_table.get_mut(&key) {
-                    let decor = formatted.decor().clone();
-                    let mut new_formatted = Formatted::new(new_val);
-                    *new_formatted.decor_mut() = decor;
-                    *formatted = new_formatted;
-                }
+                set_poetry_version(deps_table, &key, entry, new_val);
             } else {
                 result.unchanged += 1;
             }
@@ -539,8 +834,10 @@ impl PyProjectUpdater {
         // Fetch versions for remaining deps in parallel
         let version_futures: Vec<_> = deps_to_check
             .iter()
-            .map(|(key, _, version, _)| async {
-                if is_stable_pep440(version) {
+            .map(|(key, _, _, version, compatible_range, _)| async move {
+                if let Some(range) = compatible_range {
+                    registry.get_latest_version_matching(key, range).await
+                } else if is_stable_pep440(version) && !options.allows_prerelease(key) {
                     registry.get_latest_version(key).await
                 } else {
                     registry.get_latest_version_including_prereleases(key).await
@@ -551,7 +848,7 @@ impl PyProjectUpdater {
         let version_results = join_all(version_futures).await;
 
         // Process results
-        for ((key, prefix, version, line_num), version_result) in
+        for ((key, entry, prefix, version, compatible_range, line_num), version_result) in
             deps_to_check.into_iter().zip(version_results)
         {
             match version_result {
@@ -565,7 +862,8 @@ impl PyProjectUpdater {
                         continue;
                     }
 
-                    let full_constraint = format!("{}{}", prefix, version);
+                    let full_constraint =
+                        compatible_range.unwrap_or_else(|| format!("{}{}", prefix, version));
                     let constraints_for_cooldown = if full_constraint.is_empty() {
                         None
                     } else {
@@ -645,14 +943,7 @@ impl PyProjectUpdater {
                             }
 
                             // Preserve decoration when updating
-                            if let Some(Item::Value(Value::String(formatted))) =
-                                deps_table.get_mut(&key)
-                            {
-                                let decor = formatted.decor().clone();
-                                let mut new_formatted = Formatted::new(new_val);
-                                *new_formatted.decor_mut() = decor;
-                                *formatted = new_formatted;
-                            }
+                            set_poetry_version(deps_table, &key, entry, new_val);
                         }
                     } else {
                         result.unchanged += 1;
@@ -670,14 +961,12 @@ impl PyProjectLineIndex {
     fn record_dependency_literals(
         lines_by_section: &mut HashMap<String, HashMap<String, usize>>,
         section_path: &str,
-        line: &str,
-        literal_re: &Regex,
+        literals: &[&str],
         updater: &PyProjectUpdater,
         line_num: usize,
     ) {
-        for caps in literal_re.captures_iter(line) {
-            let dep = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
-            if let Some((package, _, _)) = updater.parse_dependency(dep) {
+        for literal in literals {
+            if let Some((package, _, _)) = updater.parse_dependency(literal) {
                 lines_by_section
                     .entry(section_path.to_string())
                     .or_default()
@@ -687,51 +976,92 @@ impl PyProjectLineIndex {
         }
     }
 
-    fn count_structural_array_brackets(line: &str) -> ArrayBracketCounts {
-        #[derive(Clone, Copy)]
-        enum ScanState {
-            Normal,
-            BasicString,
-            LiteralString,
+    /// Scan one line of an array value for structural brackets and string
+    /// literals, skipping comments. `open_string` carries a multi-line string
+    /// across lines: on entry it is the string still open from the previous
+    /// line, on exit the one left open at the end of this line.
+    fn scan_array_line<'a>(
+        line: &'a str,
+        open_string: &mut Option<MultilineString>,
+    ) -> ArrayLineScan<'a> {
+        let bytes = line.as_bytes();
+        let mut scan = ArrayLineScan::default();
+        let mut i = 0;
+
+        if let Some(kind) = *open_string {
+            let quote = match kind {
+                MultilineString::Basic => b'"',
+                MultilineString::Literal => b'\'',
+            };
+            match Self::find_string_end(line, 0, quote, true) {
+                Some(end) => {
+                    scan.literals.push(&line[..end]);
+                    *open_string = None;
+                    i = end + 3;
+                }
+                None => {
+                    scan.literals.push(line);
+                    return scan;
+                }
+            }
         }
 
-        let mut counts = ArrayBracketCounts::default();
-        let mut state = ScanState::Normal;
-        let mut chars = line.chars();
-
-        while let Some(ch) = chars.next() {
-            match state {
-                ScanState::Normal => match ch {
-                    '#' => break,
-                    '"' => state = ScanState::BasicString,
-                    '\'' => state = ScanState::LiteralString,
-                    '[' => counts.opening += 1,
-                    ']' => counts.closing += 1,
-                    _ => {}
-                },
-                ScanState::BasicString => match ch {
-                    '\\' => {
-                        let _ = chars.next();
-                    }
-                    '"' => state = ScanState::Normal,
-                    _ => {}
-                },
-                ScanState::LiteralString => {
-                    if ch == '\'' {
-                        state = ScanState::Normal;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'#' => break,
+                b'[' => scan.brackets.opening += 1,
+                b']' => scan.brackets.closing += 1,
+                quote @ (b'"' | b'\'') => {
+                    let multiline = bytes[i..].starts_with(&[quote; 3]);
+                    let start = i + if multiline { 3 } else { 1 };
+                    match Self::find_string_end(line, start, quote, multiline) {
+                        Some(end) => {
+                            scan.literals.push(&line[start..end]);
+                            i = end + if multiline { 3 } else { 1 };
+                            continue;
+                        }
+                        None => {
+                            if multiline {
+                                scan.literals.push(&line[start..]);
+                                *open_string = Some(if quote == b'"' {
+                                    MultilineString::Basic
+                                } else {
+                                    MultilineString::Literal
+                                });
+                            }
+                            return scan;
+                        }
                     }
                 }
+                _ => {}
             }
+            i += 1;
         }
 
-        counts
+        scan
+    }
+
+    /// Byte offset of the delimiter closing a string whose contents start at
+    /// `start`, or `None` when the string does not close on this line.
+    fn find_string_end(line: &str, start: usize, quote: u8, multiline: bool) -> Option<usize> {
+        let bytes = line.as_bytes();
+        let mut i = start;
+        while i < bytes.len() {
+            if quote == b'"' && bytes[i] == b'\\' {
+                i += 2;
+                continue;
+            }
+            if bytes[i] == quote && (!multiline || bytes[i..].starts_with(&[quote; 3])) {
+                return Some(i);
+            }
+            i += 1;
+        }
+        None
     }
 
     fn from_content(content: &str, updater: &PyProjectUpdater) -> Self {
         let section_re =
             Regex::new(r#"^\s*\[([^\]]+)\]\s*$"#).expect("Invalid pyproject section regex");
-        let literal_re =
-            Regex::new(r#""([^"]+)"|'([^']+)'"#).expect("Invalid dependency literal regex");
         let mut lines_by_section: HashMap<String, HashMap<String, usize>> = HashMap::new();
         let mut current_section: Option<String> = None;
         let mut current_array_section: Option<ArraySectionState> = None;
@@ -744,16 +1074,16 @@ impl PyProjectLineIndex {
             }
 
             if let Some(array_state) = current_array_section.as_mut() {
+                let scan = Self::scan_array_line(line, &mut array_state.open_string);
                 Self::record_dependency_literals(
                     &mut lines_by_section,
                     &array_state.section_path,
-                    line,
-                    &literal_re,
+                    &scan.literals,
                     updater,
                     line_idx + 1,
                 );
 
-                let brackets = Self::count_structural_array_brackets(line);
+                let brackets = scan.brackets;
                 let next_depth = array_state.depth as isize + brackets.opening as isize
                     - brackets.closing as isize;
                 if next_depth <= 0 {
@@ -774,7 +1104,9 @@ impl PyProjectLineIndex {
                     if let Some((key, value)) = PyProjectUpdater::assignment_parts(line)
                         && key == "dependencies"
                     {
-                        let brackets = Self::count_structural_array_brackets(value);
+                        let mut open_string = None;
+                        let scan = Self::scan_array_line(value, &mut open_string);
+                        let brackets = scan.brackets;
                         if brackets.opening == 0 {
                             continue;
                         }
@@ -783,8 +1115,7 @@ impl PyProjectLineIndex {
                         Self::record_dependency_literals(
                             &mut lines_by_section,
                             &section_path,
-                            line,
-                            &literal_re,
+                            &scan.literals,
                             updater,
                             line_idx + 1,
                         );
@@ -794,13 +1125,16 @@ impl PyProjectLineIndex {
                             current_array_section = Some(ArraySectionState {
                                 section_path,
                                 depth,
+                                open_string,
                             });
                         }
                     }
                 }
                 "project.optional-dependencies" | "dependency-groups" => {
                     if let Some((group, value)) = PyProjectUpdater::assignment_parts(line) {
-                        let brackets = Self::count_structural_array_brackets(value);
+                        let mut open_string = None;
+                        let scan = Self::scan_array_line(value, &mut open_string);
+                        let brackets = scan.brackets;
                         if brackets.opening == 0 {
                             continue;
                         }
@@ -809,8 +1143,7 @@ impl PyProjectLineIndex {
                         Self::record_dependency_literals(
                             &mut lines_by_section,
                             &section_path,
-                            line,
-                            &literal_re,
+                            &scan.literals,
                             updater,
                             line_idx + 1,
                         );
@@ -820,6 +1153,7 @@ impl PyProjectLineIndex {
                             current_array_section = Some(ArraySectionState {
                                 section_path,
                                 depth,
+                                open_string,
                             });
                         }
                     }
@@ -876,6 +1210,14 @@ impl Updater for PyProjectUpdater {
         let mut result = UpdateResult::default();
         let line_index = PyProjectLineIndex::from_content(&content, self);
 
+        if options.prune_dead_markers {
+            result
+                .warnings
+                .extend(Self::dead_marker_warnings(&doc, &content));
+        }
+
+        let uv_sources = Self::uv_non_registry_sources(&doc);
+
         // Check for inline index configuration (Poetry/PDM/uv)
         // If found, use that registry instead of the default
         let inline_registry = Self::create_registry_from_config(&doc);
@@ -894,6 +1236,7 @@ impl Updater for PyProjectUpdater {
                     &mut result,
                     &line_index,
                     "project.dependencies",
+                    &uv_sources,
                     &options,
                 )
                 .await;
@@ -912,6 +1255,7 @@ impl Updater for PyProjectUpdater {
                             &mut result,
                             &line_index,
                             &section_path,
+                            &uv_sources,
                             &options,
                         )
                         .await;
@@ -932,6 +1276,7 @@ impl Updater for PyProjectUpdater {
                         &mut result,
                         &line_index,
                         &section_path,
+                        &uv_sources,
                         &options,
                     )
                     .await;
@@ -968,8 +1313,8 @@ impl Updater for PyProjectUpdater {
             }
         }
 
-        if (!result.updated.is_empty() || !result.pinned.is_empty()) && !options.dry_run {
-            write_file_atomic(path, &doc.to_string())?;
+        if !result.updated.is_empty() || !result.pinned.is_empty() {
+            result.commit_content(path, &content, doc.to_string(), options.dry_run)?;
         }
 
         Ok(result)
@@ -1007,6 +1352,7 @@ impl Updater for PyProjectUpdater {
                             line_number: line_num,
                             has_upper_bound,
                             is_bumpable: true,
+                            inherits_workspace: false,
                         });
                     }
                 }
@@ -1031,6 +1377,7 @@ impl Updater for PyProjectUpdater {
                                     line_number: line_num,
                                     has_upper_bound,
                                     is_bumpable: true,
+                                    inherits_workspace: false,
                                 });
                             }
                         }
@@ -1065,6 +1412,7 @@ impl Updater for PyProjectUpdater {
                                 line_number: line_num,
                                 has_upper_bound: false,
                                 is_bumpable: true,
+                                inherits_workspace: false,
                             });
                         }
                     }
@@ -1072,6 +1420,84 @@ impl Updater for PyProjectUpdater {
             }
         }
 
+        let uv_sources = Self::uv_non_registry_sources(&doc);
+        deps.retain(|dep| !uv_sources.contains_key(&normalize_package_name(&dep.name)));
+
+        Ok(deps)
+    }
+
+    fn unconstrained_dependencies(&self, path: &Path) -> Result<Vec<UnconstrainedDependency>> {
+        let content = read_file_safe(path)?;
+        let doc: DocumentMut = content.parse().map_err(|e: toml_edit::TomlError| {
+            anyhow!(
+                "Failed to parse {}:\n  {}",
+                path.display(),
+                e.to_string().replace('\n', "\n  ")
+            )
+        })?;
+        let line_index = PyProjectLineIndex::from_content(&content, self);
+        let mut deps = Vec::new();
+
+        // PEP 508 strings: [project] dependencies, optional-dependencies and
+        // [dependency-groups]
+        let mut arrays = Vec::new();
+        if let Some(Item::Table(project)) = doc.get("project") {
+            arrays.extend(project.get("dependencies").and_then(Item::as_array));
+            if let Some(Item::Table(opt_deps)) = project.get("optional-dependencies") {
+                arrays.extend(opt_deps.iter().filter_map(|(_, group)| group.as_array()));
+            }
+        }
+        if let Some(Item::Table(groups)) = doc.get("dependency-groups") {
+            arrays.extend(groups.iter().filter_map(|(_, group)| group.as_array()));
+        }
+        // git, path, and workspace sources carry no version requirement
+        let uv_sources = Self::uv_non_registry_sources(&doc);
+        for requirement in arrays.into_iter().flatten().filter_map(|v| v.as_str()) {
+            if let Some((name, spec)) = split_pep508(requirement)
+                && !pep440_has_lower_bound(&spec)
+                && !uv_sources.contains_key(&normalize_package_name(&name))
+            {
+                deps.push(UnconstrainedDependency {
+                    name,
+                    spec,
+                    line_number: line_of_quoted_literal(&content, requirement),
+                });
+            }
+        }
+
+        // Poetry: "*" (or a ceiling-only requirement)
+        if let Some(Item::Table(tool)) = doc.get("tool")
+            && let Some(Item::Table(poetry)) = tool.get("poetry")
+        {
+            for section in ["dependencies", "dev-dependencies"] {
+                let Some(Item::Table(section_deps)) = poetry.get(section) else {
+                    continue;
+                };
+                for (key, item) in section_deps.iter() {
+                    if key == "python" {
+                        continue;
+                    }
+                    let requirement = match item {
+                        Item::Value(Value::String(s)) => s.value().to_string(),
+                        Item::Value(Value::InlineTable(t)) => match t.get("version") {
+                            Some(v) => v.as_str().unwrap_or_default().to_string(),
+                            // git/path/url entries carry no version requirement
+                            None => continue,
+                        },
+                        _ => continue,
+                    };
+                    if !Self::poetry_has_lower_bound(&requirement) {
+                        deps.push(UnconstrainedDependency {
+                            name: key.to_string(),
+                            spec: requirement,
+                            line_number: line_index
+                                .line_for(&format!("tool.poetry.{}", section), key),
+                        });
+                    }
+                }
+            }
+        }
+
         Ok(deps)
     }
 }
@@ -1110,6 +1536,7 @@ mod tests {
     fn test_is_simple_constraint() {
         // Simple constraints - no upper bound, no exclusions
         assert!(PyProjectUpdater::is_simple_constraint("==1.0.0"));
+        assert!(PyProjectUpdater::is_simple_constraint("===1.0"));
         assert!(PyProjectUpdater::is_simple_constraint(">=1.0.0"));
         assert!(PyProjectUpdater::is_simple_constraint(">1.0.0"));
 
@@ -1177,20 +1604,24 @@ mod tests {
     }
 
     #[test]
-    fn test_count_structural_array_brackets_ignores_strings_and_comments() {
+    fn test_scan_array_line_brackets_ignore_strings_and_comments() {
         assert_eq!(
-            PyProjectLineIndex::count_structural_array_brackets(
+            PyProjectLineIndex::scan_array_line(
                 r#"[ "requests[socks]>=2.28.0", 'flask[async]>=2.0.0' ] # ]"#,
-            ),
+                &mut None,
+            )
+            .brackets,
             ArrayBracketCounts {
                 opening: 1,
                 closing: 1,
             }
         );
         assert_eq!(
-            PyProjectLineIndex::count_structural_array_brackets(
+            PyProjectLineIndex::scan_array_line(
                 r#"  "requests[socks]>=2.28.0", # ] inside a comment"#,
-            ),
+                &mut None,
+            )
+            .brackets,
             ArrayBracketCounts::default()
         );
     }
@@ -1232,6 +1663,275 @@ dev = [
         );
     }
 
+    #[test]
+    fn test_line_index_matches_package_boundary_not_prefix() {
+        let updater = PyProjectUpdater::new();
+        let content = r#"[project]
+dependencies = [
+  # "requests>=1.0.0" was dropped in favour of the oauth client
+  "requests-oauthlib>=1.3.0",
+  "requests>=2.28.0",
+  "requests_toolbelt>=1.0.0",
+]
+"#;
+
+        let line_index = PyProjectLineIndex::from_content(content, &updater);
+
+        assert_eq!(
+            line_index.line_for("project.dependencies", "requests-oauthlib"),
+            Some(4)
+        );
+        assert_eq!(
+            line_index.line_for("project.dependencies", "requests"),
+            Some(5)
+        );
+        assert_eq!(
+            line_index.line_for("project.dependencies", "requests_toolbelt"),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn test_line_index_handles_multiline_and_padded_strings() {
+        let updater = PyProjectUpdater::new();
+        let content = r#"[project]
+dependencies = ["""
+    requests[socks]>=2.28.0
+    """,
+  "  flask>=2.0.0",
+  '''
+django>=4.2.0''',
+]
+"#;
+
+        let line_index = PyProjectLineIndex::from_content(content, &updater);
+
+        assert_eq!(
+            line_index.line_for("project.dependencies", "requests"),
+            Some(3)
+        );
+        assert_eq!(
+            line_index.line_for("project.dependencies", "flask"),
+            Some(5)
+        );
+        assert_eq!(
+            line_index.line_for("project.dependencies", "django"),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn test_update_dependency_preserves_leading_padding() {
+        let updater = PyProjectUpdater::new();
+        assert_eq!(
+            updater.parse_dependency("  flask>=2.0.0"),
+            Some((
+                "flask".to_string(),
+                "2.0.0".to_string(),
+                ">=2.0.0".to_string()
+            ))
+        );
+        assert_eq!(
+            updater.update_dependency("\n    flask>=2.0.0\n    ", "3.0.0"),
+            "\n    flask>=3.0.0\n    "
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_pyproject_prefix_colliding_names_get_own_lines() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[project]
+name = "myproject"
+dependencies = [
+    "requests-oauthlib>=1.3.0",
+    "requests>=2.28.0",
+]
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("PyPI")
+            .with_version("requests-oauthlib", "2.0.0")
+            .with_version("requests", "2.32.0");
+
+        let result = PyProjectUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        let lines: Vec<(&str, Option<usize>)> = result
+            .updated
+            .iter()
+            .map(|(package, _, _, line)| (package.as_str(), *line))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![("requests-oauthlib", Some(4)), ("requests", Some(5))]
+        );
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert!(contents.contains(r#""requests-oauthlib>=2.0.0""#));
+        assert!(contents.contains(r#""requests>=2.32.0""#));
+    }
+
+    #[tokio::test]
+    async fn test_update_pyproject_group_filter_skips_other_sections() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[project]
+name = "myproject"
+dependencies = ["requests>=2.28.0"]
+
+[project.optional-dependencies]
+docs = ["sphinx>=6.0.0"]
+
+[dependency-groups]
+dev = ["pytest>=7.0.0"]
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("PyPI")
+            .with_version("requests", "2.32.0")
+            .with_version("sphinx", "7.2.0")
+            .with_version("pytest", "8.3.0");
+        let options = UpdateOptions::new(false, false).with_groups(vec!["dev".to_string()]);
+
+        let result = PyProjectUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        let updated: Vec<&str> = result.updated.iter().map(|u| u.0.as_str()).collect();
+        assert_eq!(updated, vec!["pytest"]);
+        assert_eq!(result.unchanged, 2);
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert!(contents.contains(r#""requests>=2.28.0""#));
+        assert!(contents.contains(r#""sphinx>=6.0.0""#));
+        assert!(contents.contains(r#""pytest>=8.3.0""#));
+    }
+
+    #[tokio::test]
+    async fn test_update_pyproject_skips_uv_non_registry_sources() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[project]
+name = "myproject"
+dependencies = [
+    "requests>=2.28.0",
+    "my-lib>=1.0.0",
+    "Shared_Utils>=0.1.0",
+    "vendored",
+    "torch>=2.0.0",
+]
+
+[dependency-groups]
+dev = ["devtool>=0.1.0"]
+
+[tool.uv.sources]
+my-lib = {{ git = "https://github.com/example/my-lib", tag = "v1.0.0" }}
+shared-utils = {{ workspace = true }}
+vendored = {{ path = "../vendored", editable = true }}
+torch = {{ index = "pytorch" }}
+devtool = [
+    {{ path = "../devtool", marker = "sys_platform == 'linux'" }},
+    {{ index = "internal" }},
+]
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("PyPI")
+            .with_version("requests", "2.32.0")
+            .with_version("my-lib", "9.9.9")
+            .with_version("torch", "2.4.0");
+
+        let result = PyProjectUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        let updated: Vec<&str> = result.updated.iter().map(|u| u.0.as_str()).collect();
+        assert_eq!(updated, vec!["requests", "torch"]);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let skipped: Vec<(&str, &str)> = result
+            .skipped_sources
+            .iter()
+            .map(|(name, reason, _)| (name.as_str(), reason.as_str()))
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![
+                ("my-lib", "uv git source"),
+                ("Shared_Utils", "uv workspace source"),
+                ("devtool", "uv path source"),
+            ]
+        );
+        assert_eq!(result.skipped_sources[0].2, Some(5));
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert!(contents.contains(r#""my-lib>=1.0.0""#));
+
+        let parsed: Vec<String> = PyProjectUpdater::new()
+            .parse_dependencies(file.path())
+            .unwrap()
+            .into_iter()
+            .map(|d| d.name)
+            .collect();
+        assert_eq!(parsed, vec!["requests", "torch"]);
+        let unconstrained = PyProjectUpdater::new()
+            .unconstrained_dependencies(file.path())
+            .unwrap();
+        assert!(unconstrained.is_empty(), "{unconstrained:?}");
+    }
+
+    #[test]
+    fn test_section_group_names() {
+        assert_eq!(section_group("project.dependencies"), None);
+        assert_eq!(section_group("tool.poetry.dependencies"), None);
+        assert_eq!(section_group("tool.poetry.dev-dependencies"), Some("dev"));
+        assert_eq!(
+            section_group("project.optional-dependencies.docs"),
+            Some("docs")
+        );
+        assert_eq!(section_group("dependency-groups.lint"), Some("lint"));
+    }
+
+    #[tokio::test]
+    async fn test_update_pyproject_multiline_string_dependency() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[project]
+name = "myproject"
+dependencies = [
+    """
+    requests>=2.28.0
+    """,
+]
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("PyPI").with_version("requests", "2.32.0");
+
+        let result = PyProjectUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].3, Some(5));
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        let doc: DocumentMut = contents.parse().unwrap();
+        assert_eq!(
+            doc["project"]["dependencies"][0].as_str().map(str::trim),
+            Some("requests>=2.32.0")
+        );
+    }
+
     // Integration tests using MockRegistry and temp files
 
     #[tokio::test]
@@ -1270,6 +1970,111 @@ dependencies = [
         assert!(contents.contains("flask>=3.0.0"));
     }
 
+    #[tokio::test]
+    async fn test_update_pyproject_compatible_release_and_arbitrary_equality() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[project]
+name = "myproject"
+version = "1.0.0"
+dependencies = [
+    "flask~=2.2.1",
+    "attrs~=23.1.0",
+    "legacy===1.0",
+]
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("PyPI")
+            .with_version("flask", "3.0.3")
+            .with_constrained("flask", "~=2.2.1", "2.2.5")
+            .with_version("attrs", "24.2.0")
+            .with_version("legacy", "2.0");
+
+        let updater = PyProjectUpdater::new();
+        let result = updater
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 2);
+        assert_eq!(result.unchanged, 1);
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert!(contents.contains(r#""flask~=2.2.5""#));
+        assert!(contents.contains(r#""attrs~=23.1.0""#));
+        assert!(contents.contains(r#""legacy===2.0""#));
+    }
+
+    #[tokio::test]
+    async fn test_update_pyproject_poetry_compatible_strategy() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[tool.poetry.dependencies]
+python = "^3.9"
+requests = "~2.28.0"
+flask = "^2.0.0"
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("PyPI")
+            .with_version("requests", "3.0.0")
+            .with_constrained("requests", ">=2.28.0,<2.29", "2.28.2")
+            .with_version("flask", "3.0.0")
+            .with_constrained("flask", ">=2.0.0,<3", "2.3.3");
+
+        let updater = PyProjectUpdater::new();
+        let options = UpdateOptions::new(false, false).with_strategy(Strategy::Compatible);
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 2);
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert!(contents.contains(r#"requests = "~2.28.2""#));
+        assert!(contents.contains(r#"flask = "^2.3.3""#));
+    }
+
+    #[tokio::test]
+    async fn test_update_pyproject_poetry_multiple_constraints() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[tool.poetry.dependencies]
+python = "^3.7"
+numpy = [
+    {{ version = "1.21", python = "<3.8" }},
+    {{ version = "^1.24.0", python = ">=3.8" }},
+]
+"#
+        )
+        .unwrap();
+
+        // The entry for older Pythons stays on its own release line; the
+        // newest entry moves to the latest release.
+        let registry = MockRegistry::new("PyPI")
+            .with_version("numpy", "2.1.0")
+            .with_constrained("numpy", ">=1.21,<2", "1.26.4");
+
+        let updater = PyProjectUpdater::new();
+        let options = UpdateOptions::new(false, false);
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 2);
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert!(contents.contains(r#"{ version = "1.26", python = "<3.8" }"#));
+        assert!(contents.contains(r#"{ version = "^2.1.0", python = ">=3.8" }"#));
+    }
+
     #[tokio::test]
     async fn test_update_pyproject_poetry() {
         // Poetry uses table format: key = "version"
@@ -1714,6 +2519,9 @@ dependencies = [
             ignore: vec!["flask".to_string()],
             pin: std::collections::HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PyProjectUpdater::new();
@@ -1766,6 +2574,9 @@ dependencies = [
             ignore: Vec::new(),
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PyProjectUpdater::new();
@@ -1819,6 +2630,9 @@ flask = "^2.0.0"
             ignore: vec!["requests".to_string()],
             pin: std::collections::HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PyProjectUpdater::new();
@@ -1840,6 +2654,55 @@ flask = "^2.0.0"
         assert!(contents.contains("flask = \"^3.0.0\""));
     }
 
+    #[tokio::test]
+    async fn test_update_pyproject_range_pin_resolves_within_range() {
+        use crate::config::UpdConfig;
+
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            r#"[project]
+name = "myproject"
+dependencies = [
+    "django>=4.1.0",
+]
+"#
+        )
+        .unwrap();
+
+        let registry = MockRegistry::new("PyPI")
+            .with_version("django", "5.1.0")
+            .with_constrained("django", ">=4.2,<5", "4.2.16");
+
+        let mut pin = std::collections::HashMap::new();
+        pin.insert("django".to_string(), ">=4.2,<5".to_string());
+        let config = UpdConfig {
+            exclude: Vec::new(),
+            ignore: Vec::new(),
+            pin,
+            cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
+        };
+
+        let updater = PyProjectUpdater::new();
+        let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
+
+        let result = updater
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        // The pin resolves to the newest 4.x, never the 5.x latest
+        assert!(result.updated.is_empty());
+        assert_eq!(result.pinned.len(), 1);
+        assert_eq!(result.pinned[0].1, "4.1.0");
+        assert_eq!(result.pinned[0].2, "4.2.16");
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert!(contents.contains("django>=4.2.16"));
+    }
+
     #[tokio::test]
     async fn test_update_pyproject_poetry_with_config_pin() {
         use crate::config::UpdConfig;
@@ -1868,6 +2731,9 @@ requests = "^2.28.0"
             ignore: Vec::new(),
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PyProjectUpdater::new();
@@ -1917,6 +2783,9 @@ dev = [
             ignore: Vec::new(),
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PyProjectUpdater::new();
@@ -2027,6 +2896,9 @@ dependencies = [
             ignore: vec!["flask".to_string()],
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PyProjectUpdater::new();
@@ -2084,6 +2956,9 @@ dev = ["pytest>=7.0.0", "black>=23.0.0"]
             ignore: vec!["pytest".to_string()],
             pin: std::collections::HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PyProjectUpdater::new();
@@ -2136,6 +3011,9 @@ flask = "~2.0.0"
             ignore: Vec::new(),
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = PyProjectUpdater::new();
@@ -2153,4 +3031,45 @@ flask = "~2.0.0"
         assert!(contents.contains("requests = \"^2.30.0\""));
         assert!(contents.contains("flask = \"~2.5.0\""));
     }
+
+    #[test]
+    fn test_unconstrained_dependencies_pep621_and_poetry() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"[project]
+name = "demo"
+dependencies = [
+    "requests",
+    "flask>=3.0",
+    "mylib @ https://example.com/mylib.whl",
+]
+
+[project.optional-dependencies]
+dev = ["pytest ; python_version >= '3.8'"]
+
+[tool.poetry.dependencies]
+python = "^3.12"
+httpx = "*"
+rich = "^13.0"
+"#
+        )
+        .unwrap();
+
+        let deps = PyProjectUpdater::new()
+            .unconstrained_dependencies(file.path())
+            .unwrap();
+        let found: Vec<(&str, &str, Option<usize>)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.spec.as_str(), d.line_number))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("requests", "", Some(4)),
+                ("pytest", "", Some(10)),
+                ("httpx", "*", Some(14)),
+            ]
+        );
+    }
 }
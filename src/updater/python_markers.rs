@@ -0,0 +1,428 @@
+//! Evaluation of PEP 508 environment markers against `requires-python`.
+//!
+//! Only the interpreter-version markers (`python_version` and
+//! `python_full_version`) are understood. Every other marker variable is
+//! treated as unknown, so a requirement is only reported as dead when its
+//! Python version clause rules it out no matter what the remaining markers
+//! evaluate to.
+
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+/// A comparison from a PEP 440 specifier, e.g. `>=3.8` or `==3.9.*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Specifier {
+    op: String,
+    release: Vec<u64>,
+    wildcard: bool,
+}
+
+impl Specifier {
+    fn parse(op: &str, version: &str) -> Option<Self> {
+        let version = version.trim();
+        let (version, wildcard) = match version.strip_suffix(".*") {
+            Some(stripped) if op == "==" || op == "!=" => (stripped, true),
+            Some(_) => return None,
+            None => (version, false),
+        };
+        Some(Self {
+            op: op.to_string(),
+            release: parse_release(version)?,
+            wildcard,
+        })
+    }
+
+    fn matches(&self, candidate: &[u64]) -> bool {
+        if self.wildcard {
+            let prefix_eq = (0..self.release.len())
+                .all(|i| candidate.get(i).copied().unwrap_or(0) == self.release[i]);
+            return if self.op == "==" {
+                prefix_eq
+            } else {
+                !prefix_eq
+            };
+        }
+        let ord = compare_release(candidate, &self.release);
+        match self.op.as_str() {
+            "==" | "===" => ord == Ordering::Equal,
+            "!=" => ord != Ordering::Equal,
+            "<" => ord == Ordering::Less,
+            "<=" => ord != Ordering::Greater,
+            ">" => ord == Ordering::Greater,
+            ">=" => ord != Ordering::Less,
+            "~=" => {
+                // ~=X.Y.Z means >=X.Y.Z, ==X.Y.*
+                let prefix_len = self.release.len().saturating_sub(1).max(1);
+                ord != Ordering::Less
+                    && (0..prefix_len)
+                        .all(|i| candidate.get(i).copied().unwrap_or(0) == self.release[i])
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Parse the numeric release segment of a version, ignoring any pre/post/dev
+/// suffix (`3.11.0rc1` -> `[3, 11, 0]`).
+fn parse_release(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    let mut release = Vec::new();
+    for part in version.split('.') {
+        let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+        if digits.is_empty() {
+            break;
+        }
+        release.push(digits.parse().ok()?);
+        if digits.len() != part.len() {
+            break;
+        }
+    }
+    (!release.is_empty()).then_some(release)
+}
+
+fn compare_release(a: &[u64], b: &[u64]) -> Ordering {
+    let len = a.len().max(b.len());
+    for i in 0..len {
+        let ord = a
+            .get(i)
+            .copied()
+            .unwrap_or(0)
+            .cmp(&b.get(i).copied().unwrap_or(0));
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    Ordering::Equal
+}
+
+const OPERATORS: [&str; 8] = ["===", "==", "!=", "<=", ">=", "~=", "<", ">"];
+
+/// A parsed `requires-python` specifier set such as `">=3.8,<3.11"`.
+#[derive(Debug, Clone)]
+pub(crate) struct RequiresPython {
+    specifiers: Vec<Specifier>,
+}
+
+impl RequiresPython {
+    pub(crate) fn parse(spec: &str) -> Option<Self> {
+        let mut specifiers = Vec::new();
+        for clause in spec.split(',') {
+            let clause = clause.trim();
+            if clause.is_empty() {
+                continue;
+            }
+            let op = OPERATORS.iter().find(|op| clause.starts_with(**op))?;
+            specifiers.push(Specifier::parse(op, &clause[op.len()..])?);
+        }
+        (!specifiers.is_empty()).then_some(Self { specifiers })
+    }
+
+    fn allows(&self, candidate: &[u64]) -> bool {
+        self.specifiers.iter().all(|s| s.matches(candidate))
+    }
+}
+
+#[derive(Debug)]
+enum Marker {
+    /// A `python_version` / `python_full_version` comparison.
+    Python {
+        full: bool,
+        specifier: Specifier,
+    },
+    /// Any other comparison; its outcome is unknown to us.
+    Unknown,
+    And(Box<Marker>, Box<Marker>),
+    Or(Box<Marker>, Box<Marker>),
+}
+
+impl Marker {
+    /// Three-valued evaluation: `None` means the outcome depends on markers we
+    /// do not model.
+    fn evaluate(&self, candidate: &[u64]) -> Option<bool> {
+        match self {
+            Marker::Python { full, specifier } => {
+                let value = if *full {
+                    candidate
+                } else {
+                    &candidate[..candidate.len().min(2)]
+                };
+                Some(specifier.matches(value))
+            }
+            Marker::Unknown => None,
+            Marker::And(a, b) => match (a.evaluate(candidate), b.evaluate(candidate)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            Marker::Or(a, b) => match (a.evaluate(candidate), b.evaluate(candidate)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+        }
+    }
+
+    fn collect_releases(&self, out: &mut Vec<Vec<u64>>) {
+        match self {
+            Marker::Python { specifier, .. } => out.push(specifier.release.clone()),
+            Marker::Unknown => {}
+            Marker::And(a, b) | Marker::Or(a, b) => {
+                a.collect_releases(out);
+                b.collect_releases(out);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Op(String),
+    Value(String),
+}
+
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = input.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::Open);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::Close);
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            let end = chars[i + 1..].iter().position(|&ch| ch == c)? + i + 1;
+            tokens.push(Token::Value(chars[i + 1..end].iter().collect()));
+            i = end + 1;
+        } else if "<>=!~".contains(c) {
+            let start = i;
+            while i < chars.len() && "<>=!~".contains(chars[i]) {
+                i += 1;
+            }
+            tokens.push(Token::Op(chars[start..i].iter().collect()));
+        } else {
+            let start = i;
+            while i < chars.len() && !chars[i].is_whitespace() && !"()<>=!~\"'".contains(chars[i]) {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            tokens.push(match word.as_str() {
+                "and" => Token::And,
+                "or" => Token::Or,
+                "in" => Token::Op("in".to_string()),
+                "not" => Token::Op("not".to_string()),
+                _ => Token::Value(word),
+            });
+        }
+    }
+    Some(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Option<Marker> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            left = Marker::Or(Box::new(left), Box::new(self.parse_and()?));
+        }
+        Some(left)
+    }
+
+    fn parse_and(&mut self) -> Option<Marker> {
+        let mut left = self.parse_atom()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            left = Marker::And(Box::new(left), Box::new(self.parse_atom()?));
+        }
+        Some(left)
+    }
+
+    fn parse_atom(&mut self) -> Option<Marker> {
+        if self.peek() == Some(&Token::Open) {
+            self.pos += 1;
+            let inner = self.parse_or()?;
+            return (self.next()? == Token::Close).then_some(inner);
+        }
+        let Token::Value(lhs) = self.next()? else {
+            return None;
+        };
+        let Token::Op(mut op) = self.next()? else {
+            return None;
+        };
+        if op == "not" {
+            if self.next()? != Token::Op("in".to_string()) {
+                return None;
+            }
+            op = "not in".to_string();
+        }
+        let Token::Value(rhs) = self.next()? else {
+            return None;
+        };
+        Some(python_comparison(&lhs, &op, &rhs).unwrap_or(Marker::Unknown))
+    }
+}
+
+/// Build a Python version marker from `lhs op rhs`, flipping the comparison
+/// when the variable is on the right (`"3.8" <= python_version`).
+fn python_comparison(lhs: &str, op: &str, rhs: &str) -> Option<Marker> {
+    let is_var = |s: &str| s == "python_version" || s == "python_full_version";
+    let (var, op, version) = if is_var(lhs) {
+        (lhs, op.to_string(), rhs)
+    } else if is_var(rhs) {
+        let flipped = match op {
+            "<" => ">",
+            "<=" => ">=",
+            ">" => "<",
+            ">=" => "<=",
+            "==" | "!=" | "===" => op,
+            _ => return None,
+        };
+        (rhs, flipped.to_string(), lhs)
+    } else {
+        return None;
+    };
+    if !OPERATORS.contains(&op.as_str()) {
+        return None;
+    }
+    Some(Marker::Python {
+        full: var == "python_full_version",
+        specifier: Specifier::parse(&op, version)?,
+    })
+}
+
+fn parse_marker(input: &str) -> Option<Marker> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        pos: 0,
+    };
+    let marker = parser.parse_or()?;
+    (parser.pos == parser.tokens.len()).then_some(marker)
+}
+
+/// Interpreter versions worth probing: a grid of realistic releases plus the
+/// neighbours of every boundary named by the marker or `requires-python`.
+fn candidate_versions(boundaries: &[Vec<u64>]) -> Vec<Vec<u64>> {
+    let mut majors: BTreeSet<u64> = [2, 3].into_iter().collect();
+    let mut minors: BTreeSet<u64> = (0..=40).collect();
+    let mut patches: BTreeSet<u64> = [0].into_iter().collect();
+    for release in boundaries {
+        let at = |i: usize| release.get(i).copied().unwrap_or(0);
+        for (set, value) in [
+            (&mut majors, at(0)),
+            (&mut minors, at(1)),
+            (&mut patches, at(2)),
+        ] {
+            set.insert(value);
+            set.insert(value + 1);
+            set.insert(value.saturating_sub(1));
+        }
+    }
+    let mut candidates = Vec::new();
+    for &major in &majors {
+        for &minor in &minors {
+            for &patch in &patches {
+                candidates.push(vec![major, minor, patch]);
+            }
+        }
+    }
+    candidates
+}
+
+/// Returns `true` when `marker` cannot hold for any interpreter permitted by
+/// `requires_python`. Markers that fail to parse are never reported.
+pub(crate) fn marker_is_dead(marker: &str, requires_python: &RequiresPython) -> bool {
+    let Some(parsed) = parse_marker(marker) else {
+        return false;
+    };
+    let mut boundaries: Vec<Vec<u64>> = requires_python
+        .specifiers
+        .iter()
+        .map(|s| s.release.clone())
+        .collect();
+    parsed.collect_releases(&mut boundaries);
+
+    candidate_versions(&boundaries)
+        .iter()
+        .filter(|candidate| requires_python.allows(candidate))
+        .all(|candidate| parsed.evaluate(candidate) == Some(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dead(marker: &str, requires_python: &str) -> bool {
+        marker_is_dead(marker, &RequiresPython::parse(requires_python).unwrap())
+    }
+
+    #[test]
+    fn test_marker_contradicting_requires_python_is_dead() {
+        assert!(dead(r#"python_version >= "3.11""#, "<3.11"));
+        assert!(dead(r#"python_version < "3.8""#, ">=3.8"));
+        assert!(dead(r#"python_version == "3.7.*""#, ">=3.9,<4"));
+        assert!(dead(r#"python_full_version < "3.9.0""#, ">=3.9"));
+        assert!(dead(r#""3.8" > python_version"#, ">=3.9"));
+    }
+
+    #[test]
+    fn test_satisfiable_marker_is_not_dead() {
+        assert!(!dead(r#"python_version >= "3.11""#, ">=3.8"));
+        assert!(!dead(r#"python_version < "3.10""#, ">=3.8,<3.13"));
+        assert!(!dead(r#"python_version >= "3.40""#, ">=3.8"));
+        assert!(!dead(r#"python_full_version >= "3.8.1""#, "~=3.8"));
+    }
+
+    #[test]
+    fn test_unknown_markers_only_dead_when_python_clause_decides() {
+        assert!(dead(
+            r#"python_version >= "3.11" and sys_platform == "win32""#,
+            "<3.11"
+        ));
+        assert!(!dead(
+            r#"python_version >= "3.11" or sys_platform == "win32""#,
+            "<3.11"
+        ));
+        assert!(!dead(r#"sys_platform == "linux""#, ">=3.8"));
+        assert!(!dead(r#"extra == "docs""#, ">=3.8"));
+        assert!(dead(
+            r#"(python_version < "3.8" or python_version >= "3.13") and platform_system != "Windows""#,
+            ">=3.8,<3.13"
+        ));
+    }
+
+    #[test]
+    fn test_unparseable_marker_is_never_dead() {
+        assert!(!dead("python_version >=", ">=3.8"));
+        assert!(!dead(r#"python_version >= "3.11" and ("#, "<3.11"));
+    }
+
+    #[test]
+    fn test_parse_requires_python() {
+        assert!(RequiresPython::parse(">=3.8, <4").is_some());
+        assert!(RequiresPython::parse("").is_none());
+        assert!(RequiresPython::parse("3.8").is_none());
+    }
+}
@@ -1,16 +1,18 @@
 use super::{
-    FileType, ParsedDependency, PendingVersion, UpdateOptions, UpdateResult, Updater,
-    downgrade_warning, read_file_safe, write_file_atomic,
+    FileType, ParsedDependency, PendingVersion, UnconstrainedDependency, UpdateOptions,
+    UpdateResult, Updater, downgrade_warning, pep440_has_lower_bound, read_file_safe, resolve_pin,
+    split_pep508,
 };
 use crate::align::compare_versions;
 use crate::registry::{MultiPyPiRegistry, PyPiRegistry, Registry};
 use crate::updater::Lang;
-use crate::version::{is_prerelease_pep440, match_version_precision};
+use crate::version::{is_prerelease_pep440, match_compatible_release, match_version_precision};
 use anyhow::Result;
 use futures::future::join_all;
 use pep440_rs::Version as Pep440Version;
 use regex::Regex;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 pub struct RequirementsUpdater {
@@ -39,14 +41,14 @@ impl RequirementsUpdater {
         // Match package name (with optional extras), operator, and version
         // Captures: 1=package_name, 2=extras (optional), 3=operator, 4=version
         let package_re = Regex::new(
-            r"^([a-zA-Z0-9][-a-zA-Z0-9._]*)(\[[^\]]+\])?\s*(==|>=|<=|~=|!=|>|<)\s*([^\s,;#]+)",
+            r"^([a-zA-Z0-9][-a-zA-Z0-9._]*)(\[[^\]]+\])?\s*(===|==|>=|<=|~=|!=|>|<)\s*([^\s,;#]+)",
         )
         .expect("Invalid regex");
 
         // Match the full constraint including additional constraints after commas
         // E.g., ">=2.8.0,<9" or ">=1.0.0,!=1.5.0,<2.0.0"
         let constraint_re = Regex::new(
-            r"^([a-zA-Z0-9][-a-zA-Z0-9._]*)(\[[^\]]+\])?\s*((?:==|>=|<=|~=|!=|>|<)[^\s#;]+(?:\s*,\s*(?:==|>=|<=|~=|!=|>|<)[^\s#;,]+)*)",
+            r"^([a-zA-Z0-9][-a-zA-Z0-9._]*)(\[[^\]]+\])?\s*((?:===|==|>=|<=|~=|!=|>|<)[^\s#;]+(?:\s*,\s*(?:===|==|>=|<=|~=|!=|>|<)[^\s#;,]+)*)",
         )
         .expect("Invalid regex");
 
@@ -153,6 +155,176 @@ impl RequirementsUpdater {
         None
     }
 
+    /// Name and specifier of a requirement line; `None` for comments, option
+    /// lines and direct references (URLs, VCS, local paths and archives).
+    fn requirement_spec(line: &str) -> Option<(String, String)> {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('-') {
+            return None;
+        }
+        let code_part = line.split('#').next().unwrap_or(line);
+        let (name, spec) = split_pep508(code_part)?;
+        let is_archive = [".whl", ".tar.gz", ".zip"]
+            .iter()
+            .any(|ext| name.ends_with(ext));
+        (!is_archive).then_some((name, spec))
+    }
+
+    /// Target of an `-r`/`--requirement` or `-c`/`--constraint` line, with
+    /// `true` for a constraints file.
+    fn include_target(line: &str) -> Option<(bool, &str)> {
+        let code = line.split(" #").next().unwrap_or(line).trim();
+        for (flag, is_constraint) in [
+            ("--requirement", false),
+            ("--constraint", true),
+            ("-r", false),
+            ("-c", true),
+        ] {
+            let Some(rest) = code.strip_prefix(flag) else {
+                continue;
+            };
+            // Long options need a separator; short ones also accept `-rfile.txt`.
+            if flag.starts_with("--") && !rest.starts_with(['=', ' ', '\t']) {
+                continue;
+            }
+            let target = rest.strip_prefix('=').unwrap_or(rest).trim();
+            if !target.is_empty() {
+                return Some((is_constraint, target));
+            }
+        }
+        None
+    }
+
+    /// Constraints files referenced with `-c` from `path`, resolved relative
+    /// to its directory.
+    pub fn constraint_files(path: &Path) -> Vec<PathBuf> {
+        let Ok(content) = read_file_safe(path) else {
+            return Vec::new();
+        };
+        let dir = path.parent().unwrap_or(Path::new("."));
+        content
+            .lines()
+            .filter_map(Self::include_target)
+            .filter(|(is_constraint, _)| *is_constraint)
+            .map(|(_, target)| dir.join(target))
+            .collect()
+    }
+
+    /// Visit `path` and every file it reaches through `-r`/`-c` lines, passing
+    /// each file's content and whether it is read as a constraints file.
+    /// `visited` guards against include cycles.
+    fn walk_includes(
+        path: &Path,
+        in_constraints: bool,
+        visited: &mut HashSet<(PathBuf, bool)>,
+        visit: &mut dyn FnMut(&Path, bool, &str),
+    ) {
+        let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if !visited.insert((key, in_constraints)) {
+            return;
+        }
+        let Ok(content) = read_file_safe(path) else {
+            return;
+        };
+        visit(path, in_constraints, &content);
+        let dir = path.parent().unwrap_or(Path::new("."));
+        for (is_constraint, target) in content.lines().filter_map(Self::include_target) {
+            Self::walk_includes(
+                &dir.join(target),
+                in_constraints || is_constraint,
+                visited,
+                visit,
+            );
+        }
+    }
+
+    /// Requirements files `path` pulls in through `-r` lines, directly or
+    /// transitively, in include order. Constraints files are not listed.
+    pub fn included_requirement_files(path: &Path) -> Vec<PathBuf> {
+        let root_key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let mut included = Vec::new();
+        Self::walk_includes(
+            path,
+            false,
+            &mut HashSet::new(),
+            &mut |file, in_constraints, _| {
+                let key = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+                if !in_constraints && key != root_key {
+                    included.push(file.to_path_buf());
+                }
+            },
+        );
+        included
+    }
+
+    /// For each file reached from `roots` through `-r` includes, the
+    /// constraints files its including root applies. pip applies a root's
+    /// `-c` files to every requirement it installs, included files too.
+    /// Keys and values are canonicalized paths.
+    pub fn inherited_constraint_files(roots: &[PathBuf]) -> HashMap<PathBuf, Vec<PathBuf>> {
+        let mut inherited: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+        for root in roots {
+            let root_key = root.canonicalize().unwrap_or_else(|_| root.clone());
+            let mut included = Vec::new();
+            let mut constraint_files = Vec::new();
+            Self::walk_includes(
+                root,
+                false,
+                &mut HashSet::new(),
+                &mut |file, in_constraints, _| {
+                    let key = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+                    if in_constraints {
+                        constraint_files.push(key);
+                    } else if key != root_key {
+                        included.push(key);
+                    }
+                },
+            );
+            for file in included {
+                let entry = inherited.entry(file).or_default();
+                for constraint_file in &constraint_files {
+                    if !entry.contains(constraint_file) {
+                        entry.push(constraint_file.clone());
+                    }
+                }
+            }
+        }
+        inherited.retain(|_, files| !files.is_empty());
+        inherited
+    }
+
+    /// Collect the version ceilings that apply to `path`: every requirement in
+    /// a `-c` constraints file reachable from it (directly or through `-r`
+    /// includes) or passed in `extra_constraint_files`. Keys are PEP
+    /// 503-normalized names.
+    fn collect_constraints(
+        &self,
+        path: &Path,
+        extra_constraint_files: &[PathBuf],
+    ) -> HashMap<String, String> {
+        let mut constraints: HashMap<String, String> = HashMap::new();
+        let mut visited = HashSet::new();
+        let mut visit = |_: &Path, in_constraints: bool, content: &str| {
+            if !in_constraints {
+                return;
+            }
+            for parsed in content.lines().filter_map(|line| self.parse_line(line)) {
+                constraints
+                    .entry(normalize_name(&parsed.package))
+                    .and_modify(|spec| {
+                        spec.push(',');
+                        spec.push_str(&parsed.full_constraint);
+                    })
+                    .or_insert(parsed.full_constraint);
+            }
+        };
+        Self::walk_includes(path, false, &mut visited, &mut visit);
+        for file in extra_constraint_files {
+            Self::walk_includes(file, true, &mut visited, &mut visit);
+        }
+        constraints
+    }
+
     /// Check if constraint is a simple single-version constraint that doesn't need
     /// constraint-aware lookup (i.e., no upper bounds that could be violated)
     fn is_simple_constraint(constraint: &str) -> bool {
@@ -206,25 +378,32 @@ impl RequirementsUpdater {
     }
 }
 
+/// PEP 503 name normalization for matching requirements against constraints.
+fn normalize_name(name: &str) -> String {
+    name.to_lowercase().replace(['_', '.'], "-")
+}
+
 impl Default for RequirementsUpdater {
     fn default() -> Self {
         Self::new()
     }
 }
 
-#[async_trait::async_trait]
-impl Updater for RequirementsUpdater {
-    async fn update(
+impl RequirementsUpdater {
+    /// Resolve the requirement lines of `content`, the text of `path` (whose
+    /// `-r`/`-c` includes supply constraints), and return the rewritten lines
+    /// when any changed. Line numbers in the result are those of `content`.
+    pub(super) async fn update_lines(
         &self,
         path: &Path,
+        content: &str,
         registry: &dyn Registry,
-        options: UpdateOptions,
-    ) -> Result<UpdateResult> {
-        let content = read_file_safe(path)?;
+        options: &UpdateOptions,
+    ) -> (UpdateResult, Option<Vec<String>>) {
         let mut result = UpdateResult::default();
 
         // Check for inline index URLs in the requirements file
-        let (inline_index, extra_indexes) = Self::extract_index_urls(&content);
+        let (inline_index, extra_indexes) = Self::extract_index_urls(content);
 
         // Build effective registry based on inline index configuration
         // If file has --index-url, use that (with any --extra-index-url) instead of env vars
@@ -248,6 +427,23 @@ impl Updater for RequirementsUpdater {
             None => registry,
         };
 
+        // Requirements in `-c` constraints files cap how far a package may move;
+        // the constraints files themselves are never rewritten. So does
+        // `--strategy compatible`, which narrows unbounded requirements.
+        let constraints = self.collect_constraints(path, &options.constraint_files);
+        let constrained_spec = |parsed: &ParsedDep| -> Option<String> {
+            let spec =
+                options.pep440_resolution_spec(&parsed.full_constraint, &parsed.first_version);
+            let narrowed = spec != parsed.full_constraint;
+            match constraints.get(&normalize_name(&parsed.package)) {
+                Some(ceiling) if !narrowed && Self::is_simple_constraint(&spec) => {
+                    Some(ceiling.clone())
+                }
+                Some(ceiling) => Some(format!("{spec},{ceiling}")),
+                None => narrowed.then_some(spec),
+            }
+        };
+
         // First pass: collect all packages that need version checks
         let lines: Vec<&str> = content.lines().collect();
         let mut parsed_deps: Vec<(usize, &str, ParsedDep)> = Vec::new();
@@ -315,7 +511,13 @@ impl Updater for RequirementsUpdater {
         let version_futures: Vec<_> = fetch_deps
             .iter()
             .map(|(_, _, parsed)| async {
-                if is_prerelease_pep440(&parsed.first_version) {
+                if let Some(spec) = constrained_spec(parsed) {
+                    effective_registry
+                        .get_latest_version_matching(&parsed.package, &spec)
+                        .await
+                } else if is_prerelease_pep440(&parsed.first_version)
+                    || options.allows_prerelease(&parsed.package)
+                {
                     effective_registry
                         .get_latest_version_including_prereleases(&parsed.package)
                         .await
@@ -339,8 +541,20 @@ impl Updater for RequirementsUpdater {
         }
 
         // Add pinned versions to version_map; they are recorded during the apply pass.
-        for (line_idx, _package, _current_version, pinned_version) in pinned_packages {
-            version_map.insert(line_idx, PendingVersion::Pinned(pinned_version));
+        // Only range pins need a registry lookup.
+        let pin_futures: Vec<_> = pinned_packages
+            .iter()
+            .map(|(_, package, _, pinned_version)| {
+                resolve_pin(effective_registry, package, pinned_version)
+            })
+            .collect();
+        let pin_results = join_all(pin_futures).await;
+        for ((line_idx, _, _, _), pin_result) in pinned_packages.into_iter().zip(pin_results) {
+            let pending = match pin_result {
+                Ok(pinned_version) => PendingVersion::Pinned(pinned_version),
+                Err(e) => PendingVersion::Registry(Err(e)),
+            };
+            version_map.insert(line_idx, pending);
         }
 
         // Second pass: apply updates
@@ -394,7 +608,10 @@ impl Updater for RequirementsUpdater {
 
                             // Apply cooldown policy before precision-matching so we select
                             // from the full version list, not just the pre-matched string.
-                            let constraints_for_cooldown = if parsed.full_constraint.is_empty() {
+                            let constrained = constrained_spec(&parsed);
+                            let constraints_for_cooldown = if let Some(spec) = &constrained {
+                                Some(spec.as_str())
+                            } else if parsed.full_constraint.is_empty() {
                                 None
                             } else {
                                 Some(parsed.full_constraint.as_str())
@@ -406,7 +623,7 @@ impl Updater for RequirementsUpdater {
                                 &latest_version,
                                 constraints_for_cooldown,
                                 current_is_prerelease,
-                                &options,
+                                options,
                             )
                             .await;
                             if let Some(msg) = note {
@@ -434,8 +651,22 @@ impl Updater for RequirementsUpdater {
                                 }
                             };
 
-                            // Match the precision of the original version (unless full precision requested)
-                            let matched_version = if options.full_precision {
+                            // Match the precision of the original version (unless full precision
+                            // requested). A `~=` pin always keeps its precision, since that sets
+                            // its ceiling; a release past the ceiling leaves the line alone.
+                            let matched_version = if parsed.full_constraint.starts_with("~=") {
+                                match match_compatible_release(
+                                    &parsed.first_version,
+                                    &latest_version,
+                                ) {
+                                    Some(version) => version,
+                                    None => {
+                                        result.unchanged += 1;
+                                        new_lines.push(line.to_string());
+                                        continue;
+                                    }
+                                }
+                            } else if options.full_precision {
                                 latest_version.clone()
                             } else {
                                 match_version_precision(&parsed.first_version, &latest_version)
@@ -501,7 +732,62 @@ impl Updater for RequirementsUpdater {
             }
         }
 
-        if modified && !options.dry_run {
+        (result, modified.then_some(new_lines))
+    }
+    /// Versioned requirements of `content`, one per line.
+    pub(super) fn dependencies_in(&self, content: &str) -> Vec<ParsedDependency> {
+        let mut deps = Vec::new();
+
+        for (line_idx, line) in content.lines().enumerate() {
+            if let Some(parsed) = self.parse_line(line) {
+                let has_upper_bound = !Self::is_simple_constraint(&parsed.full_constraint);
+                deps.push(ParsedDependency {
+                    name: parsed.package,
+                    version: parsed.first_version,
+                    line_number: Some(line_idx + 1),
+                    has_upper_bound,
+                    is_bumpable: true,
+                    inherits_workspace: false,
+                });
+            }
+        }
+
+        deps
+    }
+
+    /// Requirements of `content` without a lower bound, e.g. `requests` or
+    /// `urllib3<3`.
+    pub(super) fn unconstrained_in(content: &str) -> Vec<UnconstrainedDependency> {
+        let mut deps = Vec::new();
+
+        for (line_idx, line) in content.lines().enumerate() {
+            if let Some((name, spec)) = Self::requirement_spec(line)
+                && !pep440_has_lower_bound(&spec)
+            {
+                deps.push(UnconstrainedDependency {
+                    name,
+                    spec,
+                    line_number: Some(line_idx + 1),
+                });
+            }
+        }
+
+        deps
+    }
+}
+
+#[async_trait::async_trait]
+impl Updater for RequirementsUpdater {
+    async fn update(
+        &self,
+        path: &Path,
+        registry: &dyn Registry,
+        options: UpdateOptions,
+    ) -> Result<UpdateResult> {
+        let content = read_file_safe(path)?;
+        let (mut result, new_lines) = self.update_lines(path, &content, registry, &options).await;
+
+        if let Some(new_lines) = new_lines {
             // Preserve original line ending style
             let line_ending = if content.contains("\r\n") {
                 "\r\n"
@@ -516,7 +802,7 @@ impl Updater for RequirementsUpdater {
                 new_content.push_str(line_ending);
             }
 
-            write_file_atomic(path, &new_content)?;
+            result.commit_content(path, &content, new_content, options.dry_run)?;
         }
 
         Ok(result)
@@ -528,22 +814,12 @@ impl Updater for RequirementsUpdater {
 
     fn parse_dependencies(&self, path: &Path) -> Result<Vec<ParsedDependency>> {
         let content = read_file_safe(path)?;
-        let mut deps = Vec::new();
-
-        for (line_idx, line) in content.lines().enumerate() {
-            if let Some(parsed) = self.parse_line(line) {
-                let has_upper_bound = !Self::is_simple_constraint(&parsed.full_constraint);
-                deps.push(ParsedDependency {
-                    name: parsed.package,
-                    version: parsed.first_version,
-                    line_number: Some(line_idx + 1),
-                    has_upper_bound,
-                    is_bumpable: true,
-                });
-            }
-        }
+        Ok(self.dependencies_in(&content))
+    }
 
-        Ok(deps)
+    fn unconstrained_dependencies(&self, path: &Path) -> Result<Vec<UnconstrainedDependency>> {
+        let content = read_file_safe(path)?;
+        Ok(Self::unconstrained_in(&content))
     }
 }
 
@@ -586,6 +862,11 @@ mod tests {
         assert_eq!(parsed.first_version, "1.0.0");
         assert_eq!(parsed.full_constraint, ">=1.0.0,!=1.5.0,<2.0.0");
 
+        // Arbitrary equality is its own operator, not `==` plus a stray `=`
+        let parsed = updater.parse_line("legacy===1.0").unwrap();
+        assert_eq!(parsed.first_version, "1.0");
+        assert_eq!(parsed.full_constraint, "===1.0");
+
         assert!(updater.parse_line("# comment").is_none());
         assert!(updater.parse_line("").is_none());
         assert!(updater.parse_line("-r other.txt").is_none());
@@ -680,6 +961,7 @@ flask>=2.0.0
     fn test_is_simple_constraint() {
         // Simple constraints - no upper bound, no exclusions
         assert!(RequirementsUpdater::is_simple_constraint("==1.0.0"));
+        assert!(RequirementsUpdater::is_simple_constraint("===1.0"));
         assert!(RequirementsUpdater::is_simple_constraint(">=1.0.0"));
         assert!(RequirementsUpdater::is_simple_constraint(">1.0.0"));
 
@@ -837,6 +1119,80 @@ flask>=2.0.0
         assert!(!contents.contains("2.31.0"));
     }
 
+    #[tokio::test]
+    async fn test_update_requirements_dry_run_records_content_change() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "requests==2.28.0\nflask==3.0.0").unwrap();
+
+        let registry = MockRegistry::new("PyPI")
+            .with_version("requests", "2.31.0")
+            .with_version("flask", "3.0.0");
+
+        let result = RequirementsUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(true, false))
+            .await
+            .unwrap();
+
+        let change = result.content_change.expect("dry-run keeps the rewrite");
+        assert_eq!(change.original, "requests==2.28.0\nflask==3.0.0\n");
+        assert_eq!(change.updated, "requests==2.31.0\nflask==3.0.0\n");
+    }
+
+    #[tokio::test]
+    async fn test_update_requirements_compatible_release_stays_under_ceiling() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "flask~=2.2.1").unwrap();
+        writeln!(file, "django~=4.1").unwrap();
+        writeln!(file, "attrs~=23.1.0").unwrap();
+
+        // The registry answers constrained lookups for flask; attrs falls back to
+        // an unconstrained latest that is past its `<23.2` ceiling.
+        let registry = MockRegistry::new("PyPI")
+            .with_version("flask", "3.0.3")
+            .with_constrained("flask", "~=2.2.1", "2.2.5")
+            .with_version("django", "4.2.11")
+            .with_version("attrs", "24.2.0");
+
+        let updater = RequirementsUpdater::new();
+        // Full precision must not widen `~=4.1` into `~=4.2.11` (a `<4.3` ceiling).
+        let result = updater
+            .update(file.path(), &registry, UpdateOptions::new(false, true))
+            .await
+            .unwrap();
+
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!(contents, "flask~=2.2.5\ndjango~=4.2\nattrs~=23.1.0\n");
+        assert_eq!(result.updated.len(), 2);
+        assert_eq!(result.unchanged, 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_requirements_arbitrary_equality_is_exact_pin() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "legacy===1.0").unwrap();
+
+        let registry = MockRegistry::new("PyPI").with_version("legacy", "2.0");
+
+        let updater = RequirementsUpdater::new();
+        let result = updater
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        assert_eq!(
+            result.updated,
+            vec![(
+                "legacy".to_string(),
+                "1.0".to_string(),
+                "2.0".to_string(),
+                Some(1)
+            )]
+        );
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!(contents, "legacy===2.0\n");
+    }
+
     #[tokio::test]
     async fn test_update_requirements_full_precision() {
         let mut file = NamedTempFile::new().unwrap();
@@ -1099,6 +1455,9 @@ flask>=2.0.0
             ignore: vec!["flask".to_string()],
             pin: HashMap::new(),
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = RequirementsUpdater::new();
@@ -1143,6 +1502,9 @@ flask>=2.0.0
             ignore: vec![],
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = RequirementsUpdater::new();
@@ -1190,6 +1552,9 @@ flask>=2.0.0
             ignore: vec!["flask".to_string()],
             pin,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = RequirementsUpdater::new();
@@ -1402,6 +1767,55 @@ flask>=2.0.0
         assert!(!contents.contains("26.3.1"), "must not promote to stable");
     }
 
+    /// `--allow-prerelease torch` moves a stable `torch` into release
+    /// candidates while other packages stay on stable releases.
+    #[tokio::test]
+    async fn test_allow_prerelease_applies_only_to_listed_packages() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "torch==2.4.0\nnumpy==1.26.0").unwrap();
+
+        let registry = MockRegistry::new("PyPI")
+            .with_prerelease("torch", "2.5.1", "2.6.0rc1")
+            .with_prerelease("numpy", "2.1.0", "2.2.0rc1");
+
+        let options =
+            UpdateOptions::new(false, false).with_allow_prerelease(vec!["Torch".to_string()]);
+        let result = RequirementsUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert!(contents.contains("torch==2.6.0rc1"), "{contents}");
+        assert!(contents.contains("numpy==2.1.0"), "{contents}");
+        assert_eq!(result.updated.len(), 2);
+    }
+
+    /// The config's `allow_prerelease` list works like the flag.
+    #[tokio::test]
+    async fn test_allow_prerelease_from_config() {
+        use crate::config::UpdConfig;
+        use std::sync::Arc;
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "torch==2.4.0").unwrap();
+
+        let registry = MockRegistry::new("PyPI").with_prerelease("torch", "2.5.1", "2.6.0rc1");
+        let config = UpdConfig {
+            allow_prerelease: vec!["torch".to_string()],
+            ..Default::default()
+        };
+
+        let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
+        RequirementsUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert!(contents.contains("torch==2.6.0rc1"), "{contents}");
+    }
+
     /// When no newer pre-release exists and only a newer stable is available,
     /// a pre-release-pinned package must not be silently promoted to stable.
     #[tokio::test]
@@ -1506,4 +1920,118 @@ flask>=2.0.0
             "no warning expected for equal version"
         );
     }
+
+    #[test]
+    fn test_unconstrained_dependencies_flags_bare_names() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            "# comment\nrequests\nflask==3.0.0\nuvicorn[standard]  # server\ndjango>=4.2,<5\nnumpy<2\n\
+             pytest ; python_version >= '3.8'\n-e ./local\nmylib @ git+https://example.com/mylib.git\n\
+             pkg-1.0-py3-none-any.whl\nattrs~=23.1"
+        )
+        .unwrap();
+
+        let deps = RequirementsUpdater::new()
+            .unconstrained_dependencies(file.path())
+            .unwrap();
+        let found: Vec<(&str, &str, Option<usize>)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.spec.as_str(), d.line_number))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("requests", "", Some(2)),
+                ("uvicorn", "", Some(4)),
+                ("numpy", "<2", Some(6)),
+                ("pytest", "", Some(7)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_include_target_parses_requirement_and_constraint_lines() {
+        assert_eq!(
+            RequirementsUpdater::include_target("-r base.txt"),
+            Some((false, "base.txt"))
+        );
+        assert_eq!(
+            RequirementsUpdater::include_target("--constraint=constraints.txt"),
+            Some((true, "constraints.txt"))
+        );
+        assert_eq!(
+            RequirementsUpdater::include_target("-cconstraints.txt"),
+            Some((true, "constraints.txt"))
+        );
+        assert_eq!(RequirementsUpdater::include_target("requests==2.0"), None);
+        assert_eq!(RequirementsUpdater::include_target("--requirementx"), None);
+    }
+
+    #[tokio::test]
+    async fn test_constraints_file_caps_bump_and_is_not_rewritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let constraints = "requests<=2.30.0\nurllib3==1.26.18\n";
+        std::fs::write(dir.path().join("constraints.txt"), constraints).unwrap();
+        let requirements = dir.path().join("requirements.txt");
+        std::fs::write(&requirements, "-c constraints.txt\nrequests==2.28.0\n").unwrap();
+
+        let registry = MockRegistry::new("PyPI")
+            .with_version("requests", "2.32.0")
+            .with_version("urllib3", "2.2.0")
+            .with_constrained("requests", "<=2.30.0", "2.30.0");
+
+        let result = RequirementsUpdater::new()
+            .update(&requirements, &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].0, "requests");
+        assert_eq!(result.updated[0].2, "2.30.0");
+        let content = std::fs::read_to_string(&requirements).unwrap();
+        assert!(content.contains("requests==2.30.0"));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("constraints.txt")).unwrap(),
+            constraints
+        );
+    }
+
+    #[tokio::test]
+    async fn test_included_file_inherits_constraints_of_including_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("constraints.txt"), "flask<3.1\n").unwrap();
+        std::fs::write(
+            dir.path().join("requirements.txt"),
+            "-r base.txt\n-c constraints.txt\n",
+        )
+        .unwrap();
+        let base = dir.path().join("base.txt");
+        std::fs::write(&base, "flask==2.3.0\n").unwrap();
+
+        let inherited =
+            RequirementsUpdater::inherited_constraint_files(&[dir.path().join("requirements.txt")]);
+        let base_key = base.canonicalize().unwrap();
+        assert_eq!(
+            inherited.get(&base_key).cloned(),
+            Some(vec![
+                dir.path().join("constraints.txt").canonicalize().unwrap()
+            ])
+        );
+
+        let registry = MockRegistry::new("PyPI")
+            .with_version("flask", "3.1.0")
+            .with_constrained("flask", "<3.1", "3.0.3");
+        let options = UpdateOptions::new(false, false)
+            .with_constraint_files(inherited.get(&base_key).cloned().unwrap());
+
+        let result = RequirementsUpdater::new()
+            .update(&base, &registry, options)
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].2, "3.0.3");
+        assert_eq!(std::fs::read_to_string(&base).unwrap(), "flask==3.0.3\n");
+    }
 }
@@ -0,0 +1,289 @@
+use super::{
+    FileType, ParsedDependency, UpdateOptions, UpdateResult, Updater, downgrade_warning,
+    read_file_safe,
+};
+use crate::align::compare_versions;
+use crate::registry::Registry;
+use crate::updater::Lang;
+use crate::version::match_version_precision;
+use anyhow::Result;
+use futures::future::join_all;
+use std::ops::Range;
+use std::path::Path;
+
+/// Updater for single-purpose runtime version files: `.nvmrc` (Node.js) and
+/// `.python-version` (pyenv).
+///
+/// Only numeric versions (`20`, `v20.11.0`, `3.12.1`) are checked; aliases
+/// such as `lts/iron`, `node`, `system` or `pypy3.10` are left alone. A
+/// version moves to the newest release of the same major, at the precision
+/// it was written with, and only the version token is rewritten.
+pub struct RuntimeVersionUpdater;
+
+/// A numeric runtime version found in the file.
+struct RuntimePin {
+    /// The version as written, without a `v` prefix
+    version: String,
+    /// Byte range of `version` within the file
+    range: Range<usize>,
+    line_num: Option<usize>,
+}
+
+/// The runtime a version file pins, which is also its package name.
+fn runtime_for(file_type: FileType) -> &'static str {
+    match file_type {
+        FileType::PythonVersion => "python",
+        _ => "node",
+    }
+}
+
+fn is_numeric_version(version: &str) -> bool {
+    version
+        .split('.')
+        .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+impl RuntimeVersionUpdater {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Find numeric versions, one per line. `.python-version` may list
+    /// several interpreters; `.nvmrc` holds a single line.
+    fn scan(content: &str) -> Vec<RuntimePin> {
+        let mut pins = Vec::new();
+        let mut offset = 0;
+
+        for (idx, line) in content.split_inclusive('\n').enumerate() {
+            let line_start = offset;
+            offset += line.len();
+
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let token = trimmed.split_whitespace().next().unwrap_or_default();
+            let version = token.strip_prefix('v').unwrap_or(token);
+            if !is_numeric_version(version) {
+                continue;
+            }
+
+            let start = line_start + (line.len() - trimmed.len()) + (token.len() - version.len());
+            pins.push(RuntimePin {
+                version: version.to_string(),
+                range: start..start + version.len(),
+                line_num: Some(idx + 1),
+            });
+        }
+
+        pins
+    }
+}
+
+impl Default for RuntimeVersionUpdater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Updater for RuntimeVersionUpdater {
+    async fn update(
+        &self,
+        path: &Path,
+        registry: &dyn Registry,
+        options: UpdateOptions,
+    ) -> Result<UpdateResult> {
+        let content = read_file_safe(path)?;
+        let runtime = runtime_for(FileType::detect(path).unwrap_or(FileType::Nvmrc));
+        let mut result = UpdateResult::default();
+
+        // (byte range of the old version, new version)
+        let mut writes: Vec<(Range<usize>, String)> = Vec::new();
+        let mut pins_to_check: Vec<RuntimePin> = Vec::new();
+
+        for pin in Self::scan(&content) {
+            if options.is_package_filtered_out(runtime) {
+                result.unchanged += 1;
+            } else if options.should_ignore(runtime) {
+                result
+                    .ignored
+                    .push((runtime.to_string(), pin.version, pin.line_num));
+            } else if let Some(warning) = options.range_pin_warning(runtime) {
+                result.warnings.push(warning);
+            } else if let Some(pinned_version) = options.get_pinned_version(runtime) {
+                let pinned_version = pinned_version.trim_start_matches('v');
+                if pinned_version != pin.version {
+                    writes.push((pin.range.clone(), pinned_version.to_string()));
+                    result.pinned.push((
+                        runtime.to_string(),
+                        pin.version,
+                        pinned_version.to_string(),
+                        pin.line_num,
+                    ));
+                } else {
+                    result.unchanged += 1;
+                }
+            } else {
+                pins_to_check.push(pin);
+            }
+        }
+
+        // Stay within the pinned major: `.nvmrc` at `20` tracks Node 20.x.
+        let version_futures: Vec<_> = pins_to_check
+            .iter()
+            .map(|pin| {
+                let major = pin.version.split('.').next().unwrap_or_default();
+                registry.get_latest_version_matching(runtime, major)
+            })
+            .collect();
+        let version_results = join_all(version_futures).await;
+
+        for (pin, version_result) in pins_to_check.into_iter().zip(version_results) {
+            let latest = match version_result {
+                Ok(version) => version,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", runtime, e));
+                    continue;
+                }
+            };
+
+            let new_version = if options.full_precision {
+                latest
+            } else {
+                match_version_precision(&pin.version, &latest)
+            };
+            if new_version == pin.version {
+                result.unchanged += 1;
+                continue;
+            }
+
+            // Refuse to write a downgrade.
+            if compare_versions(&new_version, &pin.version, Lang::Runtime)
+                != std::cmp::Ordering::Greater
+            {
+                result
+                    .warnings
+                    .push(downgrade_warning(runtime, &new_version, &pin.version));
+                result.unchanged += 1;
+                continue;
+            }
+
+            // Bump level exceeds the --only-bump/--max-bump ceiling.
+            if !options.allows_bump(&pin.version, &new_version) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            writes.push((pin.range.clone(), new_version.clone()));
+            result
+                .updated
+                .push((runtime.to_string(), pin.version, new_version, pin.line_num));
+        }
+
+        if !writes.is_empty() {
+            // Splice from the end so earlier ranges stay valid.
+            writes.sort_by_key(|w| std::cmp::Reverse(w.0.start));
+            let mut new_content = content.clone();
+            for (range, new_version) in &writes {
+                new_content.replace_range(range.clone(), new_version);
+            }
+            result.commit_content(path, &content, new_content, options.dry_run)?;
+        }
+
+        Ok(result)
+    }
+
+    fn handles(&self, file_type: FileType) -> bool {
+        file_type == FileType::Nvmrc || file_type == FileType::PythonVersion
+    }
+
+    fn parse_dependencies(&self, path: &Path) -> Result<Vec<ParsedDependency>> {
+        let content = read_file_safe(path)?;
+        let runtime = runtime_for(FileType::detect(path).unwrap_or(FileType::Nvmrc));
+        Ok(Self::scan(&content)
+            .into_iter()
+            .map(|pin| ParsedDependency {
+                name: runtime.to_string(),
+                version: pin.version,
+                line_number: pin.line_num,
+                has_upper_bound: false,
+                is_bumpable: true,
+                inherits_workspace: false,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MockRegistry;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_file(name: &str, content: &str) -> (TempDir, std::path::PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(name);
+        fs::write(&path, content).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn test_scan_skips_aliases_and_comments() {
+        let content = "# interpreters\n  3.12.1\npypy3.10-7.3.17\nsystem\nv20.11.0\nlts/iron\n";
+        let pins = RuntimeVersionUpdater::scan(content);
+
+        let found: Vec<(&str, Option<usize>)> = pins
+            .iter()
+            .map(|p| (p.version.as_str(), p.line_num))
+            .collect();
+        assert_eq!(found, vec![("3.12.1", Some(2)), ("20.11.0", Some(5))]);
+        assert_eq!(&content[pins[1].range.clone()], "20.11.0");
+    }
+
+    #[tokio::test]
+    async fn test_nvmrc_keeps_prefix_and_stays_in_major() {
+        let (_dir, path) = write_file(".nvmrc", "v20.11.0\r\n");
+        let registry = MockRegistry::new("runtime").with_constrained("node", "20", "20.18.1");
+
+        let result = RuntimeVersionUpdater::new()
+            .update(&path, &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v20.18.1\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_python_version_keeps_precision_per_line() {
+        let (_dir, path) = write_file(".python-version", "3.12\n2.7.17\n");
+        let registry = MockRegistry::new("runtime")
+            .with_constrained("python", "3", "3.13.1")
+            .with_constrained("python", "2", "2.7.18");
+
+        let result = RuntimeVersionUpdater::new()
+            .update(&path, &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 2);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3.13\n2.7.18\n");
+    }
+
+    #[tokio::test]
+    async fn test_major_only_pin_is_unchanged() {
+        let (_dir, path) = write_file(".nvmrc", "20\n");
+        let registry = MockRegistry::new("runtime").with_constrained("node", "20", "20.18.1");
+
+        let result = RuntimeVersionUpdater::new()
+            .update(&path, &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.updated.is_empty());
+        assert_eq!(result.unchanged, 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "20\n");
+    }
+}
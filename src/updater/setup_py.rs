@@ -0,0 +1,925 @@
+use super::{
+    FileType, ParsedDependency, PyProjectUpdater, UnconstrainedDependency, UpdateOptions,
+    UpdateResult, Updater, downgrade_warning, pep440_has_lower_bound, read_file_safe, resolve_pin,
+};
+use crate::align::compare_versions;
+use crate::registry::Registry;
+use crate::updater::Lang;
+use crate::version::{
+    is_prerelease_pep440, is_stable_pep440, match_compatible_release, match_version_precision,
+};
+use anyhow::Result;
+use futures::future::join_all;
+use regex::Regex;
+use std::ops::Range;
+use std::path::Path;
+
+/// `setup()` keywords whose literals hold requirement strings.
+const INSTALL_REQUIRES: &str = "install_requires";
+const EXTRAS_REQUIRE: &str = "extras_require";
+
+pub struct SetupPyUpdater {
+    /// Splits a requirement string into name (group 1) and specifier (group 2),
+    /// skipping extras; the specifier stops at an environment marker.
+    requirement_re: Regex,
+    /// Matches the first version in a specifier such as `==2.31.0` or `>=1.0,<2`.
+    /// Group 1: operator, group 2: version
+    version_re: Regex,
+}
+
+/// A requirement read from a plain string literal in `setup.py`.
+struct SetupPyEntry {
+    name: String,
+    /// Full specifier, e.g. `>=1.0,<2`
+    spec: String,
+    /// First version in the specifier, e.g. `1.0`
+    version: String,
+    /// Byte range of `version` in the file
+    version_range: Range<usize>,
+    line_num: Option<usize>,
+}
+
+/// What the dependency literals of a `setup.py` contain.
+#[derive(Default)]
+struct SetupPyScan {
+    entries: Vec<SetupPyEntry>,
+    /// Requirement strings without a version specifier, e.g. `"requests"`.
+    bare: Vec<(String, Option<usize>)>,
+    /// Entries that are not plain string literals (variables, calls,
+    /// f-strings, concatenations); reported, never rewritten.
+    dynamic: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum TokenKind {
+    /// String literal; `content` is the text between the quotes and `plain`
+    /// is false for f-strings, byte strings and anything with escapes.
+    Str {
+        content: Range<usize>,
+        plain: bool,
+    },
+    Ident,
+    Open(u8),
+    Close(u8),
+    Comma,
+    Colon,
+    Equals,
+    Other,
+}
+
+#[derive(Debug)]
+struct Token {
+    kind: TokenKind,
+    span: Range<usize>,
+}
+
+/// Tokenize just enough Python to find list and dict literals: strings,
+/// identifiers, brackets and separators. Comments are dropped.
+fn tokenize(src: &str) -> Vec<Token> {
+    let bytes = src.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        let start = i;
+        match b {
+            b'#' => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'"' | b'\'' => match scan_string(bytes, i, "") {
+                Some((kind, end)) => {
+                    tokens.push(Token {
+                        kind,
+                        span: start..end,
+                    });
+                    i = end;
+                }
+                None => break,
+            },
+            b if b.is_ascii_alphabetic() || b == b'_' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                let word = &src[start..i];
+                let is_prefix = word.len() <= 2
+                    && word.chars().all(|c| "rRbBuUfF".contains(c))
+                    && matches!(bytes.get(i), Some(b'"' | b'\''));
+                if is_prefix {
+                    match scan_string(bytes, i, word) {
+                        Some((kind, end)) => {
+                            tokens.push(Token {
+                                kind,
+                                span: start..end,
+                            });
+                            i = end;
+                        }
+                        None => break,
+                    }
+                } else {
+                    tokens.push(Token {
+                        kind: TokenKind::Ident,
+                        span: start..i,
+                    });
+                }
+            }
+            b'[' | b'(' | b'{' => {
+                i += 1;
+                tokens.push(Token {
+                    kind: TokenKind::Open(b),
+                    span: start..i,
+                });
+            }
+            b']' | b')' | b'}' => {
+                i += 1;
+                tokens.push(Token {
+                    kind: TokenKind::Close(b),
+                    span: start..i,
+                });
+            }
+            b',' | b':' => {
+                i += 1;
+                tokens.push(Token {
+                    kind: if b == b',' {
+                        TokenKind::Comma
+                    } else {
+                        TokenKind::Colon
+                    },
+                    span: start..i,
+                });
+            }
+            b'=' if bytes.get(i + 1) != Some(&b'=') => {
+                i += 1;
+                tokens.push(Token {
+                    kind: TokenKind::Equals,
+                    span: start..i,
+                });
+            }
+            b if b.is_ascii_whitespace() || b == b'\\' => i += 1,
+            _ => {
+                // Operators such as `==` or `+`, and non-ASCII bytes.
+                i += if b == b'=' { 2 } else { 1 };
+                tokens.push(Token {
+                    kind: TokenKind::Other,
+                    span: start..i,
+                });
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Scan the string literal whose opening quote is at `open`. Returns its
+/// token kind and the index just past the closing quote, or `None` when the
+/// literal is unterminated.
+fn scan_string(bytes: &[u8], open: usize, prefix: &str) -> Option<(TokenKind, usize)> {
+    let quote = bytes[open];
+    let triple = bytes.get(open..open + 3) == Some(&[quote; 3][..]);
+    let delim = if triple { 3 } else { 1 };
+    let content_start = open + delim;
+    let mut i = content_start;
+    let mut escaped = false;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => {
+                escaped = true;
+                i += 2;
+                continue;
+            }
+            b'\n' if !triple => return None,
+            b if b == quote && (!triple || bytes.get(i..i + 3) == Some(&[quote; 3][..])) => {
+                let plain = !escaped && !prefix.contains(['f', 'F', 'b', 'B']);
+                return Some((
+                    TokenKind::Str {
+                        content: content_start..i,
+                        plain,
+                    },
+                    i + delim,
+                ));
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Index of the token closing the bracket opened at `open`.
+fn matching_close(tokens: &[Token], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        match token.kind {
+            TokenKind::Open(_) => depth += 1,
+            TokenKind::Close(_) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Token ranges of the top-level, comma-separated items between the
+/// brackets at `open` and `close`. Empty items (trailing commas) are dropped.
+fn items(tokens: &[Token], open: usize, close: usize) -> Vec<Range<usize>> {
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut start = open + 1;
+    for (i, token) in tokens.iter().enumerate().take(close).skip(open + 1) {
+        match token.kind {
+            TokenKind::Open(_) => depth += 1,
+            TokenKind::Close(_) => depth = depth.saturating_sub(1),
+            TokenKind::Comma if depth == 0 => {
+                if start < i {
+                    items.push(start..i);
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < close {
+        items.push(start..close);
+    }
+    items
+}
+
+/// End (exclusive) of the expression starting at token `start`: the next
+/// comma or closing bracket outside any brackets it opens.
+fn value_end(tokens: &[Token], start: usize) -> usize {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        match token.kind {
+            TokenKind::Open(_) => depth += 1,
+            TokenKind::Close(_) if depth == 0 => return i,
+            TokenKind::Close(_) => depth -= 1,
+            TokenKind::Comma if depth == 0 => return i,
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+/// Source text of a token range with whitespace collapsed, for messages.
+fn item_text(src: &str, tokens: &[Token], item: &Range<usize>) -> String {
+    let text = &src[tokens[item.start].span.start..tokens[item.end - 1].span.end];
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn line_of(src: &str, offset: usize) -> usize {
+    src[..offset].matches('\n').count() + 1
+}
+
+impl SetupPyUpdater {
+    pub fn new() -> Self {
+        let requirement_re =
+            Regex::new(r"^\s*([A-Za-z0-9][A-Za-z0-9._-]*)\s*(?:\[[^\]]*\])?\s*([^;]*)")
+                .expect("Invalid regex");
+        let version_re =
+            Regex::new(r"^\s*(===|==|>=|<=|~=|!=|>|<)\s*([^\s,;]+)").expect("Invalid regex");
+        Self {
+            requirement_re,
+            version_re,
+        }
+    }
+
+    /// Collect the requirement strings of `install_requires` and
+    /// `extras_require`, both as `setup()` keywords and as dict keys.
+    fn scan(&self, src: &str) -> SetupPyScan {
+        let tokens = tokenize(src);
+        let mut scan = SetupPyScan::default();
+
+        for i in 0..tokens.len() {
+            let field = match &tokens[i].kind {
+                TokenKind::Ident => &src[tokens[i].span.clone()],
+                TokenKind::Str { content, .. } => &src[content.clone()],
+                _ => continue,
+            };
+            if field != INSTALL_REQUIRES && field != EXTRAS_REQUIRE {
+                continue;
+            }
+            let separator = matches!(
+                (&tokens[i].kind, tokens.get(i + 1).map(|t| &t.kind)),
+                (TokenKind::Ident, Some(TokenKind::Equals))
+                    | (TokenKind::Str { .. }, Some(TokenKind::Colon))
+            );
+            if !separator {
+                continue;
+            }
+            let value = i + 2;
+            let Some(value_token) = tokens.get(value) else {
+                continue;
+            };
+
+            let close = match value_token.kind {
+                TokenKind::Open(b'[') if field == INSTALL_REQUIRES => {
+                    matching_close(&tokens, value)
+                }
+                TokenKind::Open(b'{') if field == EXTRAS_REQUIRE => matching_close(&tokens, value),
+                _ => None,
+            };
+            let Some(close) = close else {
+                let end = value_end(&tokens, value);
+                if end > value {
+                    let text = item_text(src, &tokens, &(value..end));
+                    scan.dynamic.push(format!("{field} = {text}"));
+                }
+                continue;
+            };
+
+            if field == INSTALL_REQUIRES {
+                self.scan_list(src, &tokens, value, close, field, &mut scan);
+                continue;
+            }
+            for item in items(&tokens, value, close) {
+                let Some(colon) =
+                    (item.start..item.end).find(|&t| tokens[t].kind == TokenKind::Colon)
+                else {
+                    continue;
+                };
+                let extra = item_text(src, &tokens, &(item.start..colon));
+                let label = format!("{field}[{extra}]");
+                let list = colon + 1;
+                let list_close = (list < item.end && tokens[list].kind == TokenKind::Open(b'['))
+                    .then(|| matching_close(&tokens, list))
+                    .flatten()
+                    .filter(|&c| c + 1 == item.end);
+                match list_close {
+                    Some(list_close) => {
+                        self.scan_list(src, &tokens, list, list_close, &label, &mut scan)
+                    }
+                    None if list < item.end => {
+                        let text = item_text(src, &tokens, &(list..item.end));
+                        scan.dynamic.push(format!("{label}: {text}"));
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        scan
+    }
+
+    fn scan_list(
+        &self,
+        src: &str,
+        tokens: &[Token],
+        open: usize,
+        close: usize,
+        label: &str,
+        scan: &mut SetupPyScan,
+    ) {
+        for item in items(tokens, open, close) {
+            let literal = match &tokens[item.start].kind {
+                TokenKind::Str {
+                    content,
+                    plain: true,
+                } if item.len() == 1 => content.clone(),
+                _ => {
+                    let text = item_text(src, tokens, &item);
+                    scan.dynamic.push(format!("{label}: {text}"));
+                    continue;
+                }
+            };
+            self.add_requirement(src, literal, scan);
+        }
+    }
+
+    fn add_requirement(&self, src: &str, literal: Range<usize>, scan: &mut SetupPyScan) {
+        let requirement = &src[literal.clone()];
+        // Direct references (`name @ git+https://...`) have no registry version.
+        if requirement.contains('@') {
+            return;
+        }
+        let Some(caps) = self.requirement_re.captures(requirement) else {
+            return;
+        };
+        let name = caps.get(1).unwrap().as_str().to_string();
+        let spec_match = caps.get(2).unwrap();
+        let spec = spec_match.as_str().trim();
+        let line_num = Some(line_of(src, literal.start));
+
+        let Some(version_caps) = self.version_re.captures(spec_match.as_str()) else {
+            scan.bare.push((name, line_num));
+            return;
+        };
+        let version = version_caps.get(2).unwrap();
+        let start = literal.start + spec_match.start() + version.start();
+        scan.entries.push(SetupPyEntry {
+            name,
+            spec: spec.to_string(),
+            version: version.as_str().to_string(),
+            version_range: start..start + version.len(),
+            line_num,
+        });
+    }
+}
+
+impl Default for SetupPyUpdater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Updater for SetupPyUpdater {
+    async fn update(
+        &self,
+        path: &Path,
+        registry: &dyn Registry,
+        options: UpdateOptions,
+    ) -> Result<UpdateResult> {
+        let content = read_file_safe(path)?;
+        let scan = self.scan(&content);
+
+        let mut result = UpdateResult::default();
+        for dynamic in scan.dynamic {
+            result.warnings.push(format!(
+                "{dynamic}: not a plain string literal, left unchanged"
+            ));
+        }
+
+        // First pass: collect dependencies and separate by config status
+        let mut pinned_deps: Vec<(SetupPyEntry, String)> = Vec::new();
+        let mut deps_to_check: Vec<SetupPyEntry> = Vec::new();
+
+        for entry in scan.entries {
+            if options.is_package_filtered_out(&entry.name) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            if options.should_ignore(&entry.name) {
+                result
+                    .ignored
+                    .push((entry.name, entry.version, entry.line_num));
+                continue;
+            }
+
+            if let Some(pinned_version) = options.get_pinned_version(&entry.name) {
+                pinned_deps.push((entry, pinned_version.to_string()));
+                continue;
+            }
+
+            deps_to_check.push(entry);
+        }
+
+        // (byte range of the old version, new version)
+        let mut writes: Vec<(Range<usize>, String)> = Vec::new();
+
+        // Process pinned packages; only range pins need a registry lookup
+        let pin_futures: Vec<_> = pinned_deps
+            .iter()
+            .map(|(entry, pinned_version)| resolve_pin(registry, &entry.name, pinned_version))
+            .collect();
+        let pin_results = join_all(pin_futures).await;
+        for ((entry, _), pin_result) in pinned_deps.into_iter().zip(pin_results) {
+            let pinned_version = match pin_result {
+                Ok(version) => version,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", entry.name, e));
+                    continue;
+                }
+            };
+            let matched_version = if options.full_precision {
+                pinned_version
+            } else {
+                match_version_precision(&entry.version, &pinned_version)
+            };
+
+            if matched_version != entry.version {
+                writes.push((entry.version_range.clone(), matched_version.clone()));
+                result
+                    .pinned
+                    .push((entry.name, entry.version, matched_version, entry.line_num));
+            } else {
+                result.unchanged += 1;
+            }
+        }
+
+        // Fetch versions for remaining deps in parallel
+        let version_futures: Vec<_> = deps_to_check
+            .iter()
+            .map(|entry| async {
+                let spec = options.pep440_resolution_spec(&entry.spec, &entry.version);
+                if !is_stable_pep440(&entry.version) || options.allows_prerelease(&entry.name) {
+                    registry
+                        .get_latest_version_including_prereleases(&entry.name)
+                        .await
+                } else if PyProjectUpdater::is_simple_constraint(&spec) {
+                    registry.get_latest_version(&entry.name).await
+                } else {
+                    registry
+                        .get_latest_version_matching(&entry.name, &spec)
+                        .await
+                }
+            })
+            .collect();
+
+        let version_results = join_all(version_futures).await;
+
+        for (entry, version_result) in deps_to_check.into_iter().zip(version_results) {
+            // Upper-bound-only specifiers (e.g. "<6") define a ceiling, not a floor.
+            if PyProjectUpdater::is_upper_bound_only(&entry.spec) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            let latest_version = match version_result {
+                Ok(v) => v,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", entry.name, e));
+                    continue;
+                }
+            };
+
+            // Refuse silent promotion from a pre-release to a stable release.
+            let current_is_prerelease = is_prerelease_pep440(&entry.version);
+            if current_is_prerelease && !is_prerelease_pep440(&latest_version) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            let spec = options.pep440_resolution_spec(&entry.spec, &entry.version);
+            let (outcome, note) = crate::updater::apply_cooldown(
+                registry,
+                &entry.name,
+                &entry.version,
+                &latest_version,
+                Some(spec.as_str()),
+                current_is_prerelease,
+                &options,
+            )
+            .await;
+            if let Some(msg) = note {
+                options.note_cooldown_unavailable(&msg);
+            }
+            let (latest_version, held_back_record) = match outcome {
+                crate::updater::CooldownOutcome::Unchanged(v) => (v, None),
+                crate::updater::CooldownOutcome::HeldBack {
+                    chosen,
+                    skipped_version,
+                    skipped_published_at,
+                } => (chosen, Some((skipped_version, skipped_published_at))),
+                crate::updater::CooldownOutcome::Skipped {
+                    skipped_version,
+                    skipped_published_at,
+                } => {
+                    result.skipped_by_cooldown.push((
+                        entry.name,
+                        entry.version,
+                        skipped_version,
+                        skipped_published_at,
+                    ));
+                    continue;
+                }
+            };
+
+            // A `~=` pin always keeps its precision, since that sets its
+            // ceiling; a release past the ceiling leaves the entry alone.
+            let matched_version = if entry.spec.trim_start().starts_with("~=") {
+                match match_compatible_release(&entry.version, &latest_version) {
+                    Some(version) => version,
+                    None => {
+                        result.unchanged += 1;
+                        continue;
+                    }
+                }
+            } else if options.full_precision {
+                latest_version
+            } else {
+                match_version_precision(&entry.version, &latest_version)
+            };
+            if matched_version == entry.version {
+                result.unchanged += 1;
+                continue;
+            }
+
+            // Refuse to write a downgrade.
+            if compare_versions(&matched_version, &entry.version, Lang::Python)
+                != std::cmp::Ordering::Greater
+            {
+                result.warnings.push(downgrade_warning(
+                    &entry.name,
+                    &matched_version,
+                    &entry.version,
+                ));
+                result.unchanged += 1;
+                continue;
+            }
+
+            // Bump level exceeds the --only-bump/--max-bump ceiling.
+            if !options.allows_bump(&entry.version, &matched_version) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            writes.push((entry.version_range.clone(), matched_version.clone()));
+            result.updated.push((
+                entry.name.clone(),
+                entry.version.clone(),
+                matched_version.clone(),
+                entry.line_num,
+            ));
+            if let Some((skipped_version, skipped_published_at)) = held_back_record {
+                result.held_back.push((
+                    entry.name,
+                    entry.version,
+                    matched_version,
+                    skipped_version,
+                    skipped_published_at,
+                ));
+            }
+        }
+
+        if !writes.is_empty() {
+            // Splice from the end so earlier ranges stay valid.
+            writes.sort_by_key(|w| std::cmp::Reverse(w.0.start));
+            let mut new_content = content.clone();
+            for (range, new_version) in &writes {
+                new_content.replace_range(range.clone(), new_version);
+            }
+            result.commit_content(path, &content, new_content, options.dry_run)?;
+        }
+
+        Ok(result)
+    }
+
+    fn handles(&self, file_type: FileType) -> bool {
+        file_type == FileType::SetupPy
+    }
+
+    fn parse_dependencies(&self, path: &Path) -> Result<Vec<ParsedDependency>> {
+        let content = read_file_safe(path)?;
+        Ok(self
+            .scan(&content)
+            .entries
+            .into_iter()
+            .map(|entry| ParsedDependency {
+                has_upper_bound: !PyProjectUpdater::is_simple_constraint(&entry.spec),
+                name: entry.name,
+                version: entry.version,
+                line_number: entry.line_num,
+                is_bumpable: true,
+                inherits_workspace: false,
+            })
+            .collect())
+    }
+
+    fn unconstrained_dependencies(&self, path: &Path) -> Result<Vec<UnconstrainedDependency>> {
+        let content = read_file_safe(path)?;
+        let scan = self.scan(&content);
+
+        let bare = scan
+            .bare
+            .into_iter()
+            .map(|(name, line_number)| UnconstrainedDependency {
+                name,
+                spec: String::new(),
+                line_number,
+            });
+        let unbounded = scan
+            .entries
+            .into_iter()
+            .filter(|entry| !pep440_has_lower_bound(&entry.spec))
+            .map(|entry| UnconstrainedDependency {
+                name: entry.name,
+                spec: entry.spec,
+                line_number: entry.line_num,
+            });
+        let mut deps: Vec<_> = bare.chain(unbounded).collect();
+        deps.sort_by_key(|dep| dep.line_number);
+        Ok(deps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MockRegistry;
+    use std::fs;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn setup_py(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{}", content).unwrap();
+        file
+    }
+
+    const SETUP_PY: &str = r#"from setuptools import setup
+
+EXTRA = ["rich>=10"]
+
+setup(
+    name="demo",
+    version="1.0.0",  # not a dependency
+    install_requires=[
+        "requests>=2.28.0",  # http
+        'flask[async]==2.0.0; python_version >= "3.8"',
+        "click",
+        "numpy>=1.20,<2",
+        f"pandas>={PANDAS}",
+        "mylib @ git+https://github.com/example/mylib.git",
+    ],
+    extras_require={
+        "dev": ["pytest==7.0.0", "black"],
+        "docs": EXTRA,
+    },
+)
+"#;
+
+    #[tokio::test]
+    async fn test_update_setup_py_rewrites_literals_in_place() {
+        let file = setup_py(SETUP_PY);
+        let registry = MockRegistry::new("pypi")
+            .with_version("requests", "2.31.0")
+            .with_version("flask", "3.0.0")
+            .with_version("numpy", "2.1.0")
+            .with_constrained("numpy", ">=1.20,<2", "1.26.4")
+            .with_version("pytest", "8.1.0");
+        let result = SetupPyUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let updated: Vec<(&str, &str, &str, Option<usize>)> = result
+            .updated
+            .iter()
+            .map(|(n, o, v, l)| (n.as_str(), o.as_str(), v.as_str(), *l))
+            .collect();
+        assert_eq!(
+            updated,
+            vec![
+                ("requests", "2.28.0", "2.31.0", Some(9)),
+                ("flask", "2.0.0", "3.0.0", Some(10)),
+                ("numpy", "1.20", "1.26", Some(12)),
+                ("pytest", "7.0.0", "8.1.0", Some(17)),
+            ]
+        );
+        let expected = SETUP_PY
+            .replace("requests>=2.28.0", "requests>=2.31.0")
+            .replace("flask[async]==2.0.0", "flask[async]==3.0.0")
+            .replace("numpy>=1.20,<2", "numpy>=1.26,<2")
+            .replace("pytest==7.0.0", "pytest==8.1.0");
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_update_setup_py_reports_dynamic_entries() {
+        let file = setup_py(SETUP_PY);
+        let registry = MockRegistry::new("pypi")
+            .with_version("requests", "2.28.0")
+            .with_version("flask", "2.0.0")
+            .with_version("numpy", "1.20")
+            .with_version("pytest", "7.0.0");
+        let result = SetupPyUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(true, false))
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.warnings,
+            vec![
+                "install_requires: f\"pandas>={PANDAS}\": not a plain string literal, left unchanged",
+                "extras_require[\"docs\"]: EXTRA: not a plain string literal, left unchanged",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_setup_py_computed_install_requires_is_reported() {
+        let original = "from setuptools import setup\n\nsetup(install_requires=read_requirements(), name='x')\n";
+        let file = setup_py(original);
+        let registry = MockRegistry::new("pypi");
+        let result = SetupPyUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty());
+        assert_eq!(
+            result.warnings,
+            vec![
+                "install_requires = read_requirements(): not a plain string literal, left unchanged"
+            ]
+        );
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn test_update_setup_py_keeps_compatible_release_ceiling() {
+        let file = setup_py("setup(install_requires=[\"zope.interface~=5.0\", \"attrs~=23.1\"])\n");
+        let registry = MockRegistry::new("pypi")
+            .with_version("zope.interface", "7.0.3")
+            .with_version("attrs", "23.2.0");
+        let result = SetupPyUpdater::new()
+            .update(file.path(), &registry, UpdateOptions::new(false, false))
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.unchanged, 1);
+        assert_eq!(
+            fs::read_to_string(file.path()).unwrap(),
+            "setup(install_requires=[\"zope.interface~=5.0\", \"attrs~=23.2\"])\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_setup_py_dict_form_and_pin() {
+        use crate::config::UpdConfig;
+        use std::sync::Arc;
+
+        let file = setup_py(
+            "META = {\n    \"install_requires\": [\"requests==2.28.0\", \"flask==2.0.0\"],\n}\nsetup(**META)\n",
+        );
+        let config = UpdConfig {
+            pin: [("flask".to_string(), "2.3.3".to_string())]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let registry = MockRegistry::new("pypi")
+            .with_version("requests", "2.31.0")
+            .with_version("flask", "3.0.0");
+        let options = UpdateOptions::new(false, false).with_config(Arc::new(config));
+        let result = SetupPyUpdater::new()
+            .update(file.path(), &registry, options)
+            .await
+            .unwrap();
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.pinned.len(), 1);
+        assert_eq!(
+            fs::read_to_string(file.path()).unwrap(),
+            "META = {\n    \"install_requires\": [\"requests==2.31.0\", \"flask==2.3.3\"],\n}\nsetup(**META)\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_setup_py_dependencies() {
+        let file = setup_py(SETUP_PY);
+        let deps = SetupPyUpdater::new()
+            .parse_dependencies(file.path())
+            .unwrap();
+        let summary: Vec<(&str, &str, bool, Option<usize>)> = deps
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.version.as_str(),
+                    d.has_upper_bound,
+                    d.line_number,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("requests", "2.28.0", false, Some(9)),
+                ("flask", "2.0.0", false, Some(10)),
+                ("numpy", "1.20", true, Some(12)),
+                ("pytest", "7.0.0", false, Some(17)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unconstrained_setup_py_dependencies() {
+        let file = setup_py(SETUP_PY);
+        let deps = SetupPyUpdater::new()
+            .unconstrained_dependencies(file.path())
+            .unwrap();
+        let found: Vec<(&str, &str, Option<usize>)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.spec.as_str(), d.line_number))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("click", "", Some(11)), ("black", "", Some(17))]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_skips_comments_and_keeps_string_offsets() {
+        let src = "x = ['a', # ']\n \"\"\"b\"\"\", f'c']";
+        let strings: Vec<(&str, bool)> = tokenize(src)
+            .into_iter()
+            .filter_map(|t| match t.kind {
+                TokenKind::Str { content, plain } => Some((&src[content], plain)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(strings, vec![("a", true), ("b", true), ("c", false)]);
+    }
+}
@@ -1,6 +1,6 @@
 use super::{
     FileType, ParsedDependency, PendingVersion, UpdateOptions, UpdateResult, Updater,
-    downgrade_warning, read_file_safe, write_file_atomic,
+    downgrade_warning, read_file_safe,
 };
 use crate::align::compare_versions;
 use crate::registry::Registry;
@@ -312,6 +312,11 @@ impl Updater for TerraformUpdater {
                 continue;
             }
 
+            if let Some(warning) = options.range_pin_warning(&dep.source) {
+                result.warnings.push(warning);
+                continue;
+            }
+
             if let Some(pinned_version) = options.get_pinned_version(&dep.source) {
                 pinned_packages.push((
                     dep.version_line_idx,
@@ -518,7 +523,7 @@ impl Updater for TerraformUpdater {
             }
         }
 
-        if modified && !options.dry_run {
+        if modified {
             let line_ending = if content.contains("\r\n") {
                 "\r\n"
             } else {
@@ -531,7 +536,7 @@ impl Updater for TerraformUpdater {
                 new_content.push_str(line_ending);
             }
 
-            write_file_atomic(path, &new_content)?;
+            result.commit_content(path, &content, new_content, options.dry_run)?;
         }
 
         Ok(result)
@@ -553,6 +558,7 @@ impl Updater for TerraformUpdater {
                 line_number: Some(dep.version_line_idx + 1),
                 has_upper_bound: Self::has_upper_bound(&dep.operator),
                 is_bumpable: true,
+                inherits_workspace: false,
             })
             .collect())
     }
@@ -803,6 +809,9 @@ module "vpc" {{
             ignore: vec!["hashicorp/aws".to_string()],
             pin: pins,
             cooldown: None,
+            allowed_registries: Vec::new(),
+            auto_apply: Vec::new(),
+            allow_prerelease: Vec::new(),
         };
 
         let updater = TerraformUpdater::new();
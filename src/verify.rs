@@ -0,0 +1,334 @@
+//! Safe-apply support for `--apply-only-if-tests-pass`.
+//!
+//! Before any file is written, [`FileBackup::capture`] snapshots every manifest
+//! that may change together with the lockfiles and included files it reaches.
+//! After updates (and `--lock` regeneration) are applied, [`run_verification`]
+//! runs the user's command in each changed directory; when it fails,
+//! [`FileBackup::restore_unit`] puts every file of that verification unit back
+//! exactly as it was, wherever the file lives.
+
+use crate::lockfile::detect_lockfiles;
+use crate::updater::{FileType, RequirementsUpdater, write_bytes_atomic};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Number of trailing output lines kept in a verification failure message.
+const FAILURE_OUTPUT_LINES: usize = 10;
+
+/// In-memory snapshot of files taken before an apply run.
+#[derive(Debug, Default)]
+pub struct FileBackup {
+    entries: Vec<BackupEntry>,
+}
+
+#[derive(Debug)]
+struct BackupEntry {
+    path: PathBuf,
+    /// `None` records a file that did not exist, so restoring removes
+    /// anything created since (e.g. a fresh lockfile).
+    original: Option<Vec<u8>>,
+    /// Directories of the manifests whose verification covers this file. A
+    /// file shared by several (a workspace lockfile, a common `-r` include)
+    /// is restored when any of them fails.
+    units: Vec<PathBuf>,
+}
+
+impl FileBackup {
+    /// Snapshot each manifest under its verification unit, the manifest's
+    /// directory, together with the lockfiles it may change (next to it or at
+    /// its workspace root) and, for a requirements file, the files its `-r`
+    /// lines include.
+    pub fn capture<'a>(manifests: impl IntoIterator<Item = (&'a Path, FileType)>) -> Self {
+        let mut backup = Self::default();
+        for (manifest, file_type) in manifests {
+            let unit = manifest.parent().unwrap_or(Path::new("."));
+            backup.add(unit, manifest);
+            for lockfile in lockfiles_for(manifest) {
+                backup.add(unit, &lockfile);
+            }
+            if file_type == FileType::Requirements {
+                for included in RequirementsUpdater::included_requirement_files(manifest) {
+                    backup.add(unit, &included);
+                }
+            }
+        }
+        backup
+    }
+
+    fn add(&mut self, unit: &Path, path: &Path) {
+        let key = canonical(path);
+        if let Some(entry) = self.entries.iter_mut().find(|e| canonical(&e.path) == key) {
+            if !entry.units.iter().any(|u| u == unit) {
+                entry.units.push(unit.to_path_buf());
+            }
+            return;
+        }
+        self.entries.push(BackupEntry {
+            path: path.to_path_buf(),
+            original: std::fs::read(path).ok(),
+            units: vec![unit.to_path_buf()],
+        });
+    }
+
+    /// The verification units a change to `path` affects: every unit whose
+    /// snapshot includes it, or else its own directory.
+    pub fn units_of(&self, path: &Path) -> Vec<PathBuf> {
+        let key = canonical(path);
+        match self.entries.iter().find(|e| canonical(&e.path) == key) {
+            Some(entry) => entry.units.clone(),
+            None => path.parent().map(Path::to_path_buf).into_iter().collect(),
+        }
+    }
+
+    /// Restore every snapshotted file of the verification unit `unit`.
+    ///
+    /// Returns the restored paths. Restoration is best-effort per file: a
+    /// failure is reported in the returned error list instead of aborting, so
+    /// one unwritable file does not leave the rest of the unit modified.
+    pub fn restore_unit(&self, unit: &Path) -> (Vec<PathBuf>, Vec<String>) {
+        let mut restored = Vec::new();
+        let mut errors = Vec::new();
+        for entry in &self.entries {
+            if !entry.units.iter().any(|u| u == unit) {
+                continue;
+            }
+            let path = &entry.path;
+            let result = match &entry.original {
+                // Atomic, so a crash mid-restore never leaves a truncated file.
+                Some(bytes) => write_bytes_atomic(path, bytes),
+                None if path.exists() => std::fs::remove_file(path).map_err(Into::into),
+                None => Ok(()),
+            };
+            match result {
+                Ok(()) => restored.push(path.clone()),
+                Err(e) => errors.push(format!("failed to restore {}: {}", path.display(), e)),
+            }
+        }
+        (restored, errors)
+    }
+}
+
+/// `path` with symlinks and `..` resolved, so one file reached by two
+/// spellings is snapshotted once.
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// The lockfiles `manifest` may change: those next to it, else those of the
+/// nearest enclosing manifest of the same name that has any, which is where a
+/// workspace keeps its lockfile. The search stops at the repository root.
+fn lockfiles_for(manifest: &Path) -> Vec<PathBuf> {
+    let dir = manifest.parent().unwrap_or(Path::new("."));
+    let lockfiles = detect_lockfiles(manifest);
+    if !lockfiles.is_empty() {
+        return lockfiles
+            .iter()
+            .map(|lockfile| dir.join(lockfile.filename()))
+            .collect();
+    }
+    let Some(name) = manifest.file_name() else {
+        return Vec::new();
+    };
+    for ancestor in canonical(dir).ancestors().skip(1) {
+        let candidate = ancestor.join(name);
+        if candidate.is_file() {
+            let lockfiles = detect_lockfiles(&candidate);
+            if !lockfiles.is_empty() {
+                return lockfiles
+                    .iter()
+                    .map(|lockfile| ancestor.join(lockfile.filename()))
+                    .collect();
+            }
+        }
+        if ancestor.join(".git").exists() {
+            break;
+        }
+    }
+    Vec::new()
+}
+
+/// Run `command` through the platform shell with `dir` as the working directory.
+///
+/// Returns `Err` with a description (exit status plus the tail of the combined
+/// output) when the command cannot be spawned or exits unsuccessfully.
+pub fn run_verification(command: &str, dir: &Path) -> Result<(), String> {
+    let mut cmd = if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.arg("/C");
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.arg("-c");
+        c
+    };
+    let output = cmd
+        .arg(command)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("failed to run `{command}`: {e}"))?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let status = match output.status.code() {
+        Some(code) => format!("exit code {code}"),
+        None => "terminated by signal".to_string(),
+    };
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines: Vec<&str> = combined.lines().collect();
+    let tail = lines[lines.len().saturating_sub(FAILURE_OUTPUT_LINES)..].join("\n");
+    if tail.is_empty() {
+        Err(format!("`{command}` failed ({status})"))
+    } else {
+        Err(format!("`{command}` failed ({status}):\n{tail}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_restore_unit_puts_back_original_content() {
+        let dir = tempdir().unwrap();
+        let manifest = dir.path().join("requirements.txt");
+        fs::write(&manifest, "requests==1.0.0\n").unwrap();
+
+        let backup = FileBackup::capture([(manifest.as_path(), FileType::Requirements)]);
+        fs::write(&manifest, "requests==2.0.0\n").unwrap();
+
+        let (restored, errors) = backup.restore_unit(dir.path());
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(restored, vec![manifest.clone()]);
+        assert_eq!(fs::read_to_string(&manifest).unwrap(), "requests==1.0.0\n");
+    }
+
+    #[test]
+    fn test_restore_unit_writes_original_bytes_atomically() {
+        let dir = tempdir().unwrap();
+        let manifest = dir.path().join("requirements.txt");
+        let original = b"\xef\xbb\xbfrequests==1.0.0\r\n";
+        fs::write(&manifest, original).unwrap();
+
+        let backup = FileBackup::capture([(manifest.as_path(), FileType::Requirements)]);
+        fs::write(&manifest, "requests==2.0.0\n").unwrap();
+
+        let (_, errors) = backup.restore_unit(dir.path());
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(fs::read(&manifest).unwrap(), original);
+        assert!(!dir.path().join(".requirements.txt.upd.tmp").exists());
+    }
+
+    #[test]
+    fn test_capture_includes_lockfiles() {
+        let dir = tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        let lock = dir.path().join("Cargo.lock");
+        fs::write(&manifest, "[package]\n").unwrap();
+        fs::write(&lock, "# lock v1\n").unwrap();
+
+        let backup = FileBackup::capture([(manifest.as_path(), FileType::CargoToml)]);
+        fs::write(&lock, "# lock v2\n").unwrap();
+
+        backup.restore_unit(dir.path());
+        assert_eq!(fs::read_to_string(&lock).unwrap(), "# lock v1\n");
+    }
+
+    #[test]
+    fn test_restore_unit_leaves_other_directories_alone() {
+        let a = tempdir().unwrap();
+        let b = tempdir().unwrap();
+        let ma = a.path().join("requirements.txt");
+        let mb = b.path().join("requirements.txt");
+        fs::write(&ma, "a==1\n").unwrap();
+        fs::write(&mb, "b==1\n").unwrap();
+
+        let backup = FileBackup::capture([
+            (ma.as_path(), FileType::Requirements),
+            (mb.as_path(), FileType::Requirements),
+        ]);
+        fs::write(&ma, "a==2\n").unwrap();
+        fs::write(&mb, "b==2\n").unwrap();
+
+        backup.restore_unit(a.path());
+        assert_eq!(fs::read_to_string(&ma).unwrap(), "a==1\n");
+        assert_eq!(fs::read_to_string(&mb).unwrap(), "b==2\n");
+    }
+
+    #[test]
+    fn test_restore_unit_restores_workspace_root_lockfile() {
+        let root = tempdir().unwrap();
+        fs::create_dir(root.path().join(".git")).unwrap();
+        fs::write(root.path().join("Cargo.toml"), "[workspace]\n").unwrap();
+        let lock = root.path().join("Cargo.lock");
+        fs::write(&lock, "# lock v1\n").unwrap();
+        let member = root.path().join("crates").join("a");
+        fs::create_dir_all(&member).unwrap();
+        let manifest = member.join("Cargo.toml");
+        fs::write(&manifest, "[package]\n").unwrap();
+
+        let backup = FileBackup::capture([(manifest.as_path(), FileType::CargoToml)]);
+        fs::write(&manifest, "[package]\nname = \"a\"\n").unwrap();
+        fs::write(&lock, "# lock v2\n").unwrap();
+
+        let (restored, errors) = backup.restore_unit(&member);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(restored.len(), 2);
+        assert_eq!(fs::read_to_string(&manifest).unwrap(), "[package]\n");
+        assert_eq!(fs::read_to_string(&lock).unwrap(), "# lock v1\n");
+    }
+
+    #[test]
+    fn test_restore_unit_restores_included_requirements_outside_the_unit() {
+        let root = tempdir().unwrap();
+        let common = root.path().join("common.txt");
+        fs::write(&common, "six==1.0.0\n").unwrap();
+        let sub = root.path().join("sub");
+        fs::create_dir(&sub).unwrap();
+        let manifest = sub.join("requirements.txt");
+        fs::write(&manifest, "-r ../common.txt\nrequests==1.0.0\n").unwrap();
+
+        let backup = FileBackup::capture([
+            (manifest.as_path(), FileType::Requirements),
+            (common.as_path(), FileType::Requirements),
+        ]);
+        fs::write(&common, "six==2.0.0\n").unwrap();
+
+        assert_eq!(
+            backup.units_of(&common),
+            vec![sub.clone(), root.path().to_path_buf()]
+        );
+        let (_, errors) = backup.restore_unit(&sub);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(fs::read_to_string(&common).unwrap(), "six==1.0.0\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_verification_passes_and_fails() {
+        let dir = tempdir().unwrap();
+        assert!(run_verification("true", dir.path()).is_ok());
+
+        let err = run_verification("echo boom >&2; exit 3", dir.path()).unwrap_err();
+        assert!(err.contains("exit code 3"), "got: {err}");
+        assert!(
+            err.contains("boom"),
+            "output tail should be included: {err}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_verification_uses_directory_as_cwd() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("marker"), "").unwrap();
+        assert!(run_verification("test -f marker", dir.path()).is_ok());
+    }
+}
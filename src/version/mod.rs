@@ -7,6 +7,89 @@ pub use pep440::{is_prerelease_pep440, is_stable_pep440};
 pub use semver_util::{is_prerelease_semver, is_stable_semver};
 pub use tag::TagVersion;
 
+use crate::updater::Lang;
+
+/// The range a caret requirement on a PEP 440 version allows, as a specifier
+/// set: the first non-zero release segment may not change.
+///
+/// Examples:
+/// - "1.4.2" → ">=1.4.2,<2"
+/// - "0.4.2" → ">=0.4.2,<0.5"
+/// - "0.0.3" → ">=0.0.3,<0.0.4"
+///
+/// Returns `None` for pre-releases, which track their own release line, and
+/// for versions that are not PEP 440.
+pub fn pep440_caret_range(version: &str) -> Option<String> {
+    let release = stable_release(version)?;
+    let pivot = release
+        .iter()
+        .position(|&n| n != 0)
+        .unwrap_or(release.len() - 1);
+    Some(format!(">={version},<{}", bump_release(&release, pivot)))
+}
+
+/// The range a tilde requirement on a PEP 440 version allows (Poetry `~`):
+/// the minor version is fixed when given, else the major.
+///
+/// Examples:
+/// - "1.2.3" → ">=1.2.3,<1.3"
+/// - "1" → ">=1,<2"
+pub fn pep440_tilde_range(version: &str) -> Option<String> {
+    let release = stable_release(version)?;
+    let pivot = release.len().min(2) - 1;
+    Some(format!(">={version},<{}", bump_release(&release, pivot)))
+}
+
+fn stable_release(version: &str) -> Option<Vec<u64>> {
+    let parsed = version.parse::<pep440_rs::Version>().ok()?;
+    (!parsed.is_pre() && !parsed.is_dev()).then(|| parsed.release().to_vec())
+}
+
+/// `release[..=pivot]` with the segment at `pivot` incremented.
+fn bump_release(release: &[u64], pivot: usize) -> String {
+    release[..=pivot]
+        .iter()
+        .enumerate()
+        .map(|(i, &n)| if i == pivot { n + 1 } else { n }.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Check that a hand-typed version is well formed for an ecosystem.
+///
+/// Python needs PEP 440, Node, Rust and Dart need semver (a `1.2` shorthand is
+/// accepted), Go needs a `v`-prefixed semver and PHP a Composer version.
+/// JVM versions only need to start with a digit, as Maven allows any
+/// qualifier after it.
+/// Ruby, .NET and Terraform take dotted release segments, as do runtime
+/// version files. Actions, pre-commit and mise pin git refs or tool aliases
+/// and Docker images pin arbitrary tags, so any single token is accepted
+/// there.
+pub fn is_valid_version(version: &str, lang: Lang) -> bool {
+    if version.is_empty() || version.contains(|c: char| c.is_whitespace() || "\"'`".contains(c)) {
+        return false;
+    }
+    match lang {
+        Lang::Python => version.parse::<pep440_rs::Version>().is_ok(),
+        Lang::Node | Lang::Rust | Lang::Dart => {
+            semver_util::parse_partial_semver(version).is_some()
+        }
+        Lang::Go => version
+            .strip_prefix('v')
+            .is_some_and(|rest| semver::Version::parse(rest).is_ok()),
+        Lang::Ruby => {
+            version.starts_with(|c: char| c.is_ascii_digit())
+                && version
+                    .split('.')
+                    .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()))
+        }
+        Lang::DotNet | Lang::Terraform | Lang::Runtime => TagVersion::parse(version).is_some(),
+        Lang::Php => crate::registry::composer_version(version).is_some(),
+        Lang::Jvm => version.starts_with(|c: char| c.is_ascii_digit()),
+        Lang::Actions | Lang::PreCommit | Lang::Mise | Lang::Docker => true,
+    }
+}
+
 /// Match the precision of a new version to the original version's precision.
 ///
 /// For PEP 440 versions (Python), the release segment length is determined by
@@ -60,6 +143,48 @@ pub fn match_version_precision(original: &str, new_version: &str) -> String {
     }
 }
 
+/// Rewrite a PEP 440 compatible-release (`~=`) pin for a newer version.
+///
+/// `~=X.Y` means `>=X.Y,<X+1` and `~=X.Y.Z` means `>=X.Y.Z,<X.Y+1`, so the
+/// pin keeps its release-segment count (which sets the ceiling) and only the
+/// floor moves. Returns `None` when `new_version` falls outside the ceiling
+/// implied by `original`, or either side is not a valid PEP 440 version.
+///
+/// Examples:
+/// - ("2.2.1", "2.2.9") → Some("2.2.9")
+/// - ("2.2.1", "2.3.0") → None (outside `<2.3`)
+/// - ("2.2", "2.9.1") → Some("2.9")
+/// - ("2.2", "3.0") → None (outside `<3`)
+pub fn match_compatible_release(original: &str, new_version: &str) -> Option<String> {
+    use pep440_rs::Version;
+
+    let orig = original.parse::<Version>().ok()?;
+    let new = new_version.parse::<Version>().ok()?;
+    let precision = orig.release().len();
+    // `~=1` is not a valid compatible-release clause.
+    if precision < 2 {
+        return None;
+    }
+
+    let segment = |release: &[u64], i: usize| release.get(i).copied().unwrap_or(0);
+    let shares_prefix =
+        (0..precision - 1).all(|i| segment(orig.release(), i) == segment(new.release(), i));
+    if !shares_prefix {
+        return None;
+    }
+
+    if new.release().len() >= precision {
+        Some(match_version_precision(original, new_version))
+    } else {
+        Some(
+            (0..precision)
+                .map(|i| segment(new.release(), i).to_string())
+                .collect::<Vec<_>>()
+                .join("."),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,4 +230,81 @@ mod tests {
         // Truncation path: suffix is dropped when original has lower precision
         assert_eq!(match_version_precision("2.0", "3.0.5.post1"), "3.0");
     }
+
+    #[test]
+    fn test_match_compatible_release() {
+        // `~=2.2.1` means `>=2.2.1,<2.3`
+        assert_eq!(
+            match_compatible_release("2.2.1", "2.2.9").as_deref(),
+            Some("2.2.9")
+        );
+        assert_eq!(match_compatible_release("2.2.1", "2.3.0"), None);
+        assert_eq!(match_compatible_release("2.2.1", "3.0.0"), None);
+
+        // `~=2.2` means `>=2.2,<3`: precision is kept, so the ceiling is too
+        assert_eq!(
+            match_compatible_release("2.2", "2.9.1").as_deref(),
+            Some("2.9")
+        );
+        assert_eq!(match_compatible_release("2.2", "3.0"), None);
+
+        // Shorter or suffixed candidates
+        assert_eq!(
+            match_compatible_release("1.4.0", "1.4").as_deref(),
+            Some("1.4.0")
+        );
+        assert_eq!(
+            match_compatible_release("1.4.0", "1.4.2.post1").as_deref(),
+            Some("1.4.2.post1")
+        );
+        assert_eq!(
+            match_compatible_release("1.4.0", "1.4.2.3").as_deref(),
+            Some("1.4.2")
+        );
+
+        // Not a valid compatible-release clause, or not PEP 440
+        assert_eq!(match_compatible_release("2", "2.1"), None);
+        assert_eq!(match_compatible_release("2.2", "latest"), None);
+    }
+
+    #[test]
+    fn test_is_valid_version_per_ecosystem() {
+        assert!(is_valid_version("2.5.0", Lang::Python));
+        assert!(is_valid_version("2.5.0rc1", Lang::Python));
+        assert!(!is_valid_version("latest", Lang::Python));
+
+        assert!(is_valid_version("18.3.1", Lang::Node));
+        assert!(is_valid_version("1.2", Lang::Rust));
+        assert!(!is_valid_version("1.2.3.4", Lang::Rust));
+
+        assert!(is_valid_version("v1.9.0", Lang::Go));
+        assert!(!is_valid_version("1.9.0", Lang::Go));
+
+        assert!(is_valid_version("7.1.3.pre1", Lang::Ruby));
+        assert!(!is_valid_version("7..1", Lang::Ruby));
+
+        assert!(is_valid_version("8.0.1-preview.1", Lang::DotNet));
+        assert!(is_valid_version("v4", Lang::Actions));
+
+        assert!(!is_valid_version("", Lang::Actions));
+        assert!(!is_valid_version("1.0 2.0", Lang::Node));
+        assert!(!is_valid_version("1.0\"", Lang::Python));
+    }
+
+    #[test]
+    fn test_pep440_caret_and_tilde_ranges() {
+        assert_eq!(pep440_caret_range("1.4.2").as_deref(), Some(">=1.4.2,<2"));
+        assert_eq!(pep440_caret_range("0.4.2").as_deref(), Some(">=0.4.2,<0.5"));
+        assert_eq!(
+            pep440_caret_range("0.0.3").as_deref(),
+            Some(">=0.0.3,<0.0.4")
+        );
+        assert_eq!(pep440_caret_range("0").as_deref(), Some(">=0,<1"));
+        assert_eq!(pep440_caret_range("2.0.0rc1"), None);
+        assert_eq!(pep440_caret_range("latest"), None);
+
+        assert_eq!(pep440_tilde_range("1.2.3").as_deref(), Some(">=1.2.3,<1.3"));
+        assert_eq!(pep440_tilde_range("1.2").as_deref(), Some(">=1.2,<1.3"));
+        assert_eq!(pep440_tilde_range("1").as_deref(), Some(">=1,<2"));
+    }
 }
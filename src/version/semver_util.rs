@@ -40,6 +40,12 @@ pub fn is_prerelease_semver(version_str: &str) -> bool {
     !is_stable_semver(version_str)
 }
 
+/// Parse a possibly incomplete version ("1.74" -> 1.74.0), as used by Cargo
+/// `rust-version` fields and shortened requirements.
+pub fn parse_partial_semver(version_str: &str) -> Option<Version> {
+    Version::parse(&normalize_version(version_str.trim())).ok()
+}
+
 /// Compare two semver version strings
 /// Returns None if either version is invalid
 pub fn compare_versions(a: &str, b: &str) -> Option<std::cmp::Ordering> {
@@ -52,6 +58,13 @@ pub fn compare_versions(a: &str, b: &str) -> Option<std::cmp::Ordering> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_partial_semver() {
+        assert_eq!(parse_partial_semver("1.74"), Some(Version::new(1, 74, 0)));
+        assert_eq!(parse_partial_semver("1.70.1"), Some(Version::new(1, 70, 1)));
+        assert_eq!(parse_partial_semver("stable"), None);
+    }
+
     #[test]
     fn test_normalize_version() {
         assert_eq!(normalize_version("1"), "1.0.0");
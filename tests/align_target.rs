@@ -0,0 +1,144 @@
+//! Integration tests for choosing the `upd align` target version:
+//!   * `--to` rewrites every occurrence of the `--package` selection,
+//!     downgrading higher ones and leaving other packages alone
+//!   * `--lowest` aligns every package down to the lowest version in use
+//!   * constrained occurrences are skipped, with a warning when the target
+//!     falls below their lower bound
+//!   * a `--to` version that is invalid for the ecosystem, or one given
+//!     without `--package`, is rejected
+//!
+//! `align` never hits the network, so no cache or registry stubbing is needed.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join("upd-cache"))
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+/// Two projects pinning `numpy` and `flask` at different versions.
+fn workspace() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().unwrap();
+    for (dir, pins) in [
+        ("proj_a", "numpy==1.26.4\nflask==1.0.0\n"),
+        ("proj_b", "numpy==2.2.6\nflask==2.0.0\n"),
+    ] {
+        fs::create_dir_all(tmp.path().join(dir)).unwrap();
+        fs::write(tmp.path().join(dir).join("requirements.txt"), pins).unwrap();
+    }
+    tmp
+}
+
+#[test]
+fn align_to_downgrades_selected_package_only() {
+    let tmp = workspace();
+    let (stdout, stderr, code) = run(
+        &[
+            "align",
+            "--to",
+            "1.26.4",
+            "--package",
+            "numpy",
+            "--apply",
+            "--output",
+            "text",
+            ".",
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stdout:\n{stdout}\nstderr:\n{stderr}");
+    assert!(stdout.contains("(requested)"), "got:\n{stdout}");
+    let b = fs::read_to_string(tmp.path().join("proj_b/requirements.txt")).unwrap();
+    assert_eq!(b, "numpy==1.26.4\nflask==2.0.0\n");
+    let a = fs::read_to_string(tmp.path().join("proj_a/requirements.txt")).unwrap();
+    assert_eq!(a, "numpy==1.26.4\nflask==1.0.0\n");
+}
+
+#[test]
+fn align_to_warns_and_skips_violated_constraint() {
+    let tmp = workspace();
+    fs::create_dir_all(tmp.path().join("proj_c")).unwrap();
+    fs::write(
+        tmp.path().join("proj_c/requirements.txt"),
+        "numpy>=2.0,<3\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, code) = run(
+        &[
+            "align",
+            "--to",
+            "1.26.4",
+            "--package",
+            "numpy",
+            "--apply",
+            "--output",
+            "text",
+            ".",
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stdout:\n{stdout}\nstderr:\n{stderr}");
+    assert!(
+        stderr.contains("Warning:") && stderr.contains("proj_c"),
+        "violated constraint must be warned about; stderr:\n{stderr}"
+    );
+    let c = fs::read_to_string(tmp.path().join("proj_c/requirements.txt")).unwrap();
+    assert_eq!(c, "numpy>=2.0,<3\n", "constrained pin must be left alone");
+}
+
+#[test]
+fn align_to_rejects_invalid_version() {
+    let tmp = workspace();
+    let (_stdout, stderr, code) = run(
+        &["align", "--to", "not a version", "--package", "numpy", "."],
+        tmp.path(),
+    );
+
+    assert_ne!(code, 0);
+    assert!(stderr.contains("not a valid python version"), "{stderr}");
+}
+
+#[test]
+fn align_to_requires_package() {
+    let tmp = workspace();
+    let (_stdout, stderr, code) = run(&["align", "--to", "1.26.4", "."], tmp.path());
+
+    assert_ne!(code, 0);
+    assert!(stderr.contains("--to requires --package"), "{stderr}");
+}
+
+#[test]
+fn align_lowest_downgrades_higher_occurrences() {
+    let tmp = workspace();
+    let (stdout, _stderr, code) = run(&["align", "--lowest", "--output", "text", "."], tmp.path());
+
+    assert_eq!(
+        code, 1,
+        "dry-run with misalignments exits 1; got:\n{stdout}"
+    );
+    assert!(stdout.contains("1.26.4 (lowest)"), "got:\n{stdout}");
+    assert!(stdout.contains("(downgrade)"), "got:\n{stdout}");
+
+    let (_stdout, _stderr, code) = run(&["align", "--lowest", "--apply", "."], tmp.path());
+    assert_eq!(code, 0);
+    let b = fs::read_to_string(tmp.path().join("proj_b/requirements.txt")).unwrap();
+    assert_eq!(b, "numpy==1.26.4\nflask==1.0.0\n");
+}
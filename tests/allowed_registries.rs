@@ -0,0 +1,164 @@
+//! Integration tests for the `allowed_registries` config key.
+//!
+//! When set, `upd` must refuse to send any request to a host outside the list
+//! (before the request leaves the process), so private package names cannot
+//! leak to a public or misconfigured index. Allowlisted hosts proceed normally.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use wiremock::matchers::{method, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_env(args: &[&str], cwd: &Path, env: &[(&str, &str)]) -> (String, String, i32) {
+    let mut cmd = Command::new(upd_bin());
+    cmd.args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap());
+    for (k, v) in env {
+        cmd.env(k, v);
+    }
+    let output = cmd.output().expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+async fn mount_requests(server: &MockServer) {
+    let html = r#"<!DOCTYPE html><html><body>
+<a href="requests-1.0.1.tar.gz">requests-1.0.1.tar.gz</a>
+</body></html>"#;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/simple/requests/?$"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(html.as_bytes(), "text/html"))
+        .mount(server)
+        .await;
+}
+
+/// The mock index lives on 127.0.0.1, which is not in the allowlist: the
+/// lookup must fail with a clear error and the server must see no requests.
+#[tokio::test]
+async fn non_allowlisted_host_is_blocked() {
+    let server = MockServer::start().await;
+    mount_requests(&server).await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let req = tmp.path().join("requirements.txt");
+    fs::write(&req, "requests==1.0.0\n").unwrap();
+    fs::write(
+        tmp.path().join(".updrc.toml"),
+        "allowed_registries = [\"nexus.corp\"]\n",
+    )
+    .unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_env(
+        &["--apply", "--no-cache", "--output", "text", &path_str],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 2, "a blocked lookup is an error; stderr: {stderr}");
+    let combined = format!("{stdout}{stderr}");
+    assert!(
+        combined.contains("not in allowed_registries"),
+        "expected a clear allowlist error; output: {combined}"
+    );
+    assert_eq!(fs::read_to_string(&req).unwrap(), "requests==1.0.0\n");
+
+    let received = server.received_requests().await.unwrap_or_default();
+    assert!(
+        received.is_empty(),
+        "no request may reach a non-allowlisted host; got {} request(s)",
+        received.len()
+    );
+}
+
+/// Allowlisting the mock index host lets the update proceed.
+#[tokio::test]
+async fn allowlisted_host_proceeds() {
+    let server = MockServer::start().await;
+    mount_requests(&server).await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let req = tmp.path().join("requirements.txt");
+    fs::write(&req, "requests==1.0.0\n").unwrap();
+    fs::write(
+        tmp.path().join(".updrc.toml"),
+        "allowed_registries = [\"nexus.corp\", \"127.0.0.1\"]\n",
+    )
+    .unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_env(
+        &["--apply", "--no-cache", &path_str],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(fs::read_to_string(&req).unwrap(), "requests==1.0.1\n");
+}
+
+/// Only the config for the scanned path restricts hosts. A nested config's
+/// allowlist is not enforced, so the run warns about it.
+#[tokio::test]
+async fn nested_allowlist_is_reported_as_not_enforced() {
+    let server = MockServer::start().await;
+    mount_requests(&server).await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    fs::create_dir(tmp.path().join(".git")).unwrap();
+    let sub = tmp.path().join("sub");
+    fs::create_dir(&sub).unwrap();
+    fs::write(sub.join("requirements.txt"), "requests==1.0.0\n").unwrap();
+    fs::write(
+        sub.join(".updrc.toml"),
+        "allowed_registries = [\"nexus.corp\"]\n",
+    )
+    .unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, _) = run_with_env(
+        &["--no-cache", &path_str],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+
+    assert!(
+        stderr.contains("allowed_registries in the config for")
+            && stderr.contains("is not enforced"),
+        "stderr: {stderr}"
+    );
+}
+
+/// `--verbose` config diagnostics go to stderr, keeping JSON stdout parseable.
+#[test]
+fn verbose_allowlist_note_stays_off_json_stdout() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==1.0.0\n").unwrap();
+    fs::write(
+        tmp.path().join(".updrc.toml"),
+        "allowed_registries = [\"nexus.corp\"]\n",
+    )
+    .unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, _) = run_with_env(
+        &["--verbose", "--offline", "--format", "json", &path_str],
+        tmp.path(),
+        &[],
+    );
+
+    serde_json::from_str::<serde_json::Value>(&stdout).expect(&stdout);
+    assert!(
+        stderr.contains("Restricting requests to 1 registry host(s)"),
+        "stderr: {stderr}"
+    );
+}
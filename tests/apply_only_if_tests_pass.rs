@@ -0,0 +1,182 @@
+//! Integration tests for `--apply-only-if-tests-pass`.
+//!
+//! Updates are applied, then the verification command runs in each changed
+//! directory. A failing command must restore the directory's files to their
+//! original content and report the reverted updates; a passing command keeps
+//! the updates.
+
+#![cfg(unix)]
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use wiremock::matchers::{method, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_env(args: &[&str], cwd: &Path, env: &[(&str, &str)]) -> (String, String, i32) {
+    let mut cmd = Command::new(upd_bin());
+    cmd.args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap());
+    for (k, v) in env {
+        cmd.env(k, v);
+    }
+    let output = cmd.output().expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+async fn mount_requests(server: &MockServer) {
+    let html = r#"<!DOCTYPE html><html><body>
+<a href="requests-1.0.1.tar.gz">requests-1.0.1.tar.gz</a>
+</body></html>"#;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/simple/requests/?$"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(html.as_bytes(), "text/html"))
+        .mount(server)
+        .await;
+}
+
+/// The hook sees the updated file (proving it ran after the write), fails,
+/// and the file is restored byte-for-byte.
+#[tokio::test]
+async fn failed_verification_reverts_files() {
+    let server = MockServer::start().await;
+    mount_requests(&server).await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let req = tmp.path().join("requirements.txt");
+    let original = "# deps\nrequests==1.0.0\n";
+    fs::write(&req, original).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_env(
+        &[
+            "--apply",
+            "--no-cache",
+            "--output",
+            "text",
+            "--apply-only-if-tests-pass",
+            "grep -q 'requests==1.0.1' requirements.txt && echo 'tests failed' && exit 1",
+            &path_str,
+        ],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+
+    assert_eq!(
+        code, 2,
+        "a failed verification is an error; stderr: {stderr}"
+    );
+    assert_eq!(
+        fs::read_to_string(&req).unwrap(),
+        original,
+        "files must be restored after a failed verification"
+    );
+    assert!(
+        stderr.contains("verification failed") && stderr.contains("tests failed"),
+        "error should include the command failure; stderr: {stderr}"
+    );
+    assert!(
+        stderr.contains("requests 1.0.0 → 1.0.1"),
+        "error should name the reverted update; stderr: {stderr}"
+    );
+}
+
+/// A passing command keeps the applied updates.
+#[tokio::test]
+async fn passing_verification_keeps_updates() {
+    let server = MockServer::start().await;
+    mount_requests(&server).await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let req = tmp.path().join("requirements.txt");
+    fs::write(&req, "requests==1.0.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_env(
+        &[
+            "--apply",
+            "--no-cache",
+            "--apply-only-if-tests-pass",
+            "true",
+            &path_str,
+        ],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(fs::read_to_string(&req).unwrap(), "requests==1.0.1\n");
+}
+
+/// Without --apply nothing is written, so the hook must not run at all.
+#[tokio::test]
+async fn dry_run_does_not_run_verification() {
+    let server = MockServer::start().await;
+    mount_requests(&server).await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==1.0.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, _code) = run_with_env(
+        &[
+            "--no-cache",
+            "--apply-only-if-tests-pass",
+            "touch hook-ran",
+            &path_str,
+        ],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+
+    assert!(
+        !tmp.path().join("hook-ran").exists(),
+        "verification must not run in dry-run mode; stderr: {stderr}"
+    );
+}
+
+/// A failure in one directory also restores the file its requirements pull in
+/// through `-r`, even though that file lives elsewhere.
+#[tokio::test]
+async fn failed_verification_reverts_included_files_outside_the_directory() {
+    let server = MockServer::start().await;
+    mount_requests(&server).await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let common = tmp.path().join("common.txt");
+    fs::write(&common, "requests==1.0.0\n").unwrap();
+    let sub = tmp.path().join("sub");
+    fs::create_dir(&sub).unwrap();
+    fs::write(sub.join("requirements.txt"), "-r ../common.txt\n").unwrap();
+    let sub_str = sub.to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_env(
+        &[
+            "--apply",
+            "--no-cache",
+            "--output",
+            "text",
+            "--apply-only-if-tests-pass",
+            "test \"$(basename \"$PWD\")\" != sub",
+            &sub_str,
+        ],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 2, "stderr: {stderr}");
+    assert_eq!(fs::read_to_string(&common).unwrap(), "requests==1.0.0\n");
+    assert!(
+        stderr.contains("requests 1.0.0 → 1.0.1"),
+        "error should name the reverted update; stderr: {stderr}"
+    );
+}
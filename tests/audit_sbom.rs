@@ -0,0 +1,146 @@
+//! Integration tests for `upd audit --sbom`.
+//!
+//! Components of an external SBOM are audited against OSV with the ecosystem
+//! taken from each component's package URL; no manifests are scanned.
+
+use std::fs;
+use std::process::Command;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_env(
+    args: &[&str],
+    cwd: &std::path::Path,
+    env: &[(&str, &str)],
+) -> (String, String, i32) {
+    let mut cmd = Command::new(upd_bin());
+    cmd.args(args).current_dir(cwd);
+    for (k, v) in env {
+        cmd.env(k, v);
+    }
+    let output = cmd.output().expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const CYCLONEDX: &str = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "components": [
+    { "type": "library", "name": "requests", "version": "2.19.0",
+      "purl": "pkg:pypi/requests@2.19.0" },
+    { "type": "library", "name": "core", "version": "7.0.0",
+      "purl": "pkg:npm/%40angular/core@7.0.0" },
+    { "type": "library", "name": "guava",
+      "purl": "pkg:maven/com.google.guava/guava@30.0" }
+  ]
+}"#;
+
+#[tokio::test]
+async fn audit_sbom_queries_osv_with_purl_ecosystems() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/querybatch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": [{ "vulns": [{ "id": "GHSA-sbom-001" }] }, {}]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/vulns/GHSA-sbom-001"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "GHSA-sbom-001",
+            "summary": "an sbom vulnerability",
+            "database_specific": { "severity": "HIGH" }
+        })))
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let sbom = tmp.path().join("bom.json");
+    fs::write(&sbom, CYCLONEDX).unwrap();
+    // A manifest in the working directory must not be scanned.
+    fs::write(tmp.path().join("requirements.txt"), "flask==1.0.0\n").unwrap();
+
+    let (stdout, stderr, code) = run_with_env(
+        &[
+            "audit",
+            "--no-cache",
+            "--format",
+            "json",
+            "--sbom",
+            sbom.to_str().unwrap(),
+        ],
+        tmp.path(),
+        &[("OSV_API_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 6, "vulnerabilities found; stderr: {stderr}");
+    assert!(
+        stderr.contains("guava: unsupported ecosystem 'maven'"),
+        "stderr: {stderr}"
+    );
+
+    let requests = server.received_requests().await.unwrap();
+    let batch = requests
+        .iter()
+        .find(|r| r.url.path() == "/querybatch")
+        .expect("querybatch request");
+    let body: serde_json::Value = serde_json::from_slice(&batch.body).unwrap();
+    let queried: Vec<(String, String, String)> = body["queries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|q| {
+            (
+                q["package"]["name"].as_str().unwrap().to_string(),
+                q["package"]["ecosystem"].as_str().unwrap().to_string(),
+                q["version"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        queried,
+        vec![
+            (
+                "requests".to_string(),
+                "PyPI".to_string(),
+                "2.19.0".to_string()
+            ),
+            (
+                "@angular/core".to_string(),
+                "npm".to_string(),
+                "7.0.0".to_string()
+            ),
+        ]
+    );
+
+    let report: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(report["vulnerabilities"][0]["package"], "requests");
+}
+
+#[test]
+fn audit_sbom_rejects_unrecognized_documents() {
+    let tmp = tempfile::tempdir().unwrap();
+    let sbom = tmp.path().join("bom.json");
+    fs::write(&sbom, r#"{"hello": "world"}"#).unwrap();
+
+    let (_stdout, stderr, code) = run_with_env(
+        &["audit", "--no-cache", "--sbom", sbom.to_str().unwrap()],
+        tmp.path(),
+        &[("OSV_API_URL", "http://127.0.0.1:1")],
+    );
+
+    assert_ne!(code, 0);
+    assert!(
+        stderr.contains("unrecognized SBOM format"),
+        "stderr: {stderr}"
+    );
+}
@@ -4,7 +4,8 @@
 //! - CVSS vector strings are converted to human-readable severity labels
 //! - `database_specific.severity` strings are normalised (e.g. "MODERATE" → "Medium")
 //! - Multiple vulnerabilities are sorted Critical → High → Medium → Low
-//! - JSON output carries the normalized label, not the raw vector
+//! - JSON output carries the normalized label, not the raw vector, plus the
+//!   computed CVSS score
 
 use std::fs;
 use std::process::Command;
@@ -332,4 +333,154 @@ async fn audit_json_output_contains_normalized_severity_label() {
         !sev.contains("CVSS:"),
         "JSON severity must not contain raw vector"
     );
+    let score = vulns[0]["cvss_score"]
+        .as_f64()
+        .expect("cvss_score must be a number when a vector is present");
+    assert!((score - 9.8).abs() < 0.05, "expected ~9.8, got {score}");
+}
+
+/// Mount one package with Critical, Medium, and no-severity vulnerabilities.
+async fn mount_mixed_severities(server: &wiremock::MockServer) {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    Mock::given(method("POST"))
+        .and(path("/querybatch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": [{ "vulns": [
+                { "id": "GHSA-mix-crit" },
+                { "id": "GHSA-mix-med" },
+                { "id": "GHSA-mix-unknown" }
+            ]}]
+        })))
+        .mount(server)
+        .await;
+
+    for (id, severity) in [
+        ("GHSA-mix-crit", Some("CRITICAL")),
+        ("GHSA-mix-med", Some("MODERATE")),
+        ("GHSA-mix-unknown", None),
+    ] {
+        let mut body = serde_json::json!({
+            "id": id,
+            "summary": "mixed severity",
+            "references": [{ "url": "https://example.com/mix" }]
+        });
+        if let Some(severity) = severity {
+            body["database_specific"] = serde_json::json!({ "severity": severity });
+        }
+        Mock::given(method("GET"))
+            .and(path(format!("/vulns/{id}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(server)
+            .await;
+    }
+}
+
+fn vulnerability_ids(stdout: &str) -> Vec<String> {
+    parse_json(stdout)["vulnerabilities"]
+        .as_array()
+        .expect("vulnerabilities must be an array")
+        .iter()
+        .map(|v| v["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+/// Without `--severity`, vulnerabilities with no known severity are reported.
+#[tokio::test]
+async fn audit_without_severity_threshold_reports_everything() {
+    let server = wiremock::MockServer::start().await;
+    mount_mixed_severities(&server).await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==1.0.0\n").unwrap();
+
+    let (stdout, _stderr, code) = run_with_env(
+        &["audit", "--no-cache", "--format", "json"],
+        tmp.path(),
+        &[("OSV_API_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 6, "vulnerabilities_found");
+    assert_eq!(
+        vulnerability_ids(&stdout),
+        vec!["GHSA-mix-crit", "GHSA-mix-med", "GHSA-mix-unknown"]
+    );
+}
+
+/// `--severity medium` drops vulnerabilities below the threshold and those
+/// with no severity.
+#[tokio::test]
+async fn audit_severity_threshold_filters_lower_and_unknown() {
+    let server = wiremock::MockServer::start().await;
+    mount_mixed_severities(&server).await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==1.0.0\n").unwrap();
+
+    let (stdout, _stderr, code) = run_with_env(
+        &[
+            "audit",
+            "--no-cache",
+            "--format",
+            "json",
+            "--severity",
+            "high",
+        ],
+        tmp.path(),
+        &[("OSV_API_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 6, "vulnerabilities_found");
+    assert_eq!(vulnerability_ids(&stdout), vec!["GHSA-mix-crit"]);
+}
+
+/// When every vulnerability is below the threshold, the audit passes.
+#[tokio::test]
+async fn audit_severity_threshold_above_all_findings_exits_zero() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/querybatch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": [{ "vulns": [{ "id": "GHSA-only-med" }] }]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/vulns/GHSA-only-med"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "GHSA-only-med",
+            "summary": "medium only",
+            "database_specific": { "severity": "MODERATE" },
+            "references": [{ "url": "https://example.com/only-med" }]
+        })))
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==1.0.0\n").unwrap();
+
+    let (stdout, stderr, code) = run_with_env(
+        &[
+            "audit",
+            "--no-cache",
+            "--format",
+            "text",
+            "--severity",
+            "critical",
+        ],
+        tmp.path(),
+        &[("OSV_API_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 0, "stdout: {stdout}\nstderr: {stderr}");
+    assert!(
+        stdout.contains("Hiding 1 vulnerability below --severity critical"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("No vulnerabilities found"), "{stdout}");
+    assert!(!stdout.contains("GHSA-only-med"), "{stdout}");
 }
@@ -0,0 +1,155 @@
+//! Config `auto_apply`: a non-interactive `--apply` run writes only the listed
+//! bump levels and reports the rest as available, resolved through
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use wiremock::matchers::{method, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+const FIXTURE: &str = r#"{
+    "pypi": { "requests": "3.0.0", "flask": "2.3.3", "click": "8.1.8" }
+}"#;
+
+const REQUIREMENTS: &str = "requests==2.28.0\nflask==2.0.0\nclick==8.1.3\n";
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, FIXTURE).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn write_project(dir: &Path) -> String {
+    fs::write(dir.join("requirements.txt"), REQUIREMENTS).unwrap();
+    fs::write(
+        dir.join(".updrc.toml"),
+        "auto_apply = [\"minor\", \"patch\"]\n",
+    )
+    .unwrap();
+    dir.to_str().unwrap().to_string()
+}
+
+#[test]
+fn auto_apply_writes_minors_and_patches_and_reports_majors() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = write_project(tmp.path());
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--apply", "--no-cache", "--format", "text", &path],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let content = fs::read_to_string(tmp.path().join("requirements.txt")).unwrap();
+    assert_eq!(content, "requests==2.28.0\nflask==2.3.3\nclick==8.1.8\n");
+    assert!(
+        stdout.contains("Available requests 2.28.0 → 3.0.0 (not applied)"),
+        "stdout: {stdout}"
+    );
+    assert!(
+        stdout.contains("Updated flask 2.0.0 → 2.3.3"),
+        "stdout: {stdout}"
+    );
+    assert!(
+        stdout.contains("1 update(s) not applied"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn auto_apply_reports_not_applied_updates_in_json() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = write_project(tmp.path());
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--apply", "--no-cache", "--format", "json", &path],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let file = &json["files"][0];
+    assert_eq!(file["updates"].as_array().unwrap().len(), 2);
+    assert_eq!(file["not_applied"][0]["package"], "requests");
+    assert_eq!(file["not_applied"][0]["latest"], "3.0.0");
+    assert_eq!(file["not_applied"][0]["bump"], "major");
+    assert_eq!(json["summary"]["not_applied"], 1);
+}
+
+#[test]
+fn auto_apply_does_not_change_dry_run_reports() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = write_project(tmp.path());
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--dry-run", "--no-cache", "--format", "json", &path],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 1, "pending updates; stderr: {stderr}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["files"][0]["updates"].as_array().unwrap().len(), 3);
+    assert!(json["files"][0].get("not_applied").is_none());
+    let content = fs::read_to_string(tmp.path().join("requirements.txt")).unwrap();
+    assert_eq!(content, REQUIREMENTS);
+}
+
+/// The previewed plan is narrowed by the policy and written, so each package
+/// is looked up only once.
+#[tokio::test]
+async fn auto_apply_looks_up_each_package_once() {
+    let server = MockServer::start().await;
+    for (package, version) in [
+        ("requests", "3.0.0"),
+        ("flask", "2.3.3"),
+        ("click", "8.1.8"),
+    ] {
+        let html = format!(
+            r#"<!DOCTYPE html><html><body>
+<a href="{package}-{version}.tar.gz">{package}-{version}.tar.gz</a>
+</body></html>"#
+        );
+        Mock::given(method("GET"))
+            .and(path_regex(format!(r"^/simple/{package}/?$")))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(html.into_bytes(), "text/html"))
+            .mount(&server)
+            .await;
+    }
+    let tmp = tempfile::tempdir().unwrap();
+    let path = write_project(tmp.path());
+
+    let output = Command::new(upd_bin())
+        .args(["--apply", "--no-cache", &path])
+        .current_dir(tmp.path())
+        .env("UPD_CACHE_DIR", tmp.path().join(".cache").to_str().unwrap())
+        .env("UV_INDEX_URL", server.uri())
+        .output()
+        .expect("failed to run upd");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let content = fs::read_to_string(tmp.path().join("requirements.txt")).unwrap();
+    assert_eq!(content, "requests==2.28.0\nflask==2.3.3\nclick==8.1.8\n");
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 3, "one lookup per package: {requests:?}");
+}
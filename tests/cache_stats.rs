@@ -0,0 +1,103 @@
+//! `upd cache-stats`: entry counts, size on disk, entry ages and hit rate of
+//! the version cache named by `UPD_CACHE_DIR`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[test]
+fn cache_stats_reports_entries_ages_and_hit_rate() {
+    let tmp = tempfile::tempdir().unwrap();
+    let cache = tmp.path().join(".cache");
+    fs::create_dir_all(&cache).unwrap();
+    let content = r#"{
+        "pypi": {
+            "flask": {"version": "3.0.3", "fetched_at": 1700000000},
+            "requests": {"version": "2.32.3", "fetched_at": 1700003600}
+        },
+        "npm": {"left-pad": {"version": "1.3.0", "fetched_at": 1700007200}},
+        "counters": {"hits": 3, "misses": 1}
+    }"#;
+    fs::write(cache.join("versions.json"), content).unwrap();
+
+    let (stdout, stderr, code) = run(&["cache-stats", "--output", "json"], tmp.path());
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["entries"], 3);
+    assert_eq!(json["registries"]["pypi"], 2);
+    assert_eq!(json["registries"]["npm"], 1);
+    assert_eq!(json["registries"]["crates.io"], 0);
+    assert_eq!(json["expired"], 3);
+    assert_eq!(json["size_bytes"], content.len());
+    assert_eq!(json["oldest"], "2023-11-14T22:13:20+00:00");
+    assert_eq!(json["newest"], "2023-11-15T00:13:20+00:00");
+    assert_eq!(json["hit_rate"], 0.75);
+
+    let (stdout, stderr, code) = run(&["stats", "--output", "text"], tmp.path());
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(stdout.contains("3 cached version(s)"), "stdout: {stdout}");
+    assert!(stdout.contains("Oldest entry: 2023-11-14 22:13:20 UTC"));
+    assert!(stdout.contains("Hit rate: 75.0% (3 hits, 1 misses)"));
+}
+
+#[test]
+fn cache_stats_without_a_cache_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (stdout, stderr, code) = run(&["cache-stats", "--output", "json"], tmp.path());
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["entries"], 0);
+    assert_eq!(json["size_bytes"], 0);
+    assert!(json["oldest"].is_null());
+    assert!(json["hit_rate"].is_null());
+}
+
+#[test]
+fn cache_stats_counts_expired_entries_against_cache_ttl() {
+    let tmp = tempfile::tempdir().unwrap();
+    let cache = tmp.path().join(".cache");
+    fs::create_dir_all(&cache).unwrap();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let two_hours_ago = now - 2 * 3600;
+    fs::write(
+        cache.join("versions.json"),
+        format!(
+            r#"{{"pypi": {{"flask": {{"version": "3.0.3", "fetched_at": {two_hours_ago}}}}}}}"#
+        ),
+    )
+    .unwrap();
+
+    let (stdout, stderr, code) = run(&["cache-stats", "--output", "json"], tmp.path());
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["expired"], 1, "older than the 1h default");
+
+    let (stdout, stderr, code) = run(
+        &["cache-stats", "--cache-ttl", "1d", "--output", "json"],
+        tmp.path(),
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["expired"], 0);
+}
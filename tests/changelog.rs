@@ -0,0 +1,87 @@
+//! `--changelog`: a release-notes link per updated package. Fixture-backed
+//! registries publish no repository metadata, so links fall back to the
+//! registry's package page.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "pypi": { "requests": "2.32.3", "flask": "3.0.3" } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn write_project(dir: &Path) -> String {
+    fs::write(
+        dir.join("requirements.txt"),
+        "requests==2.28.0\nflask==3.0.3\n",
+    )
+    .unwrap();
+    dir.to_str().unwrap().to_string()
+}
+
+#[test]
+fn changelog_lists_a_link_per_updated_package() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, stderr, code) =
+        run_with_fixture(&["--changelog", "--format", "text", &project], tmp.path());
+
+    assert_eq!(code, 1, "stderr: {stderr}");
+    assert!(stdout.contains("Changelogs:"), "stdout: {stdout}");
+    assert!(
+        stdout.contains("  requests: https://pypi.org/project/requests/"),
+        "stdout: {stdout}"
+    );
+    // flask is already current, so it gets no link.
+    assert!(!stdout.contains("flask: https://"), "stdout: {stdout}");
+}
+
+#[test]
+fn changelog_links_appear_in_json_output() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, stderr, _code) =
+        run_with_fixture(&["--changelog", "--format", "json", &project], tmp.path());
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    assert_eq!(
+        report["changelogs"],
+        serde_json::json!([
+            {"package": "requests", "url": "https://pypi.org/project/requests/"}
+        ])
+    );
+}
+
+#[test]
+fn without_changelog_no_links_are_printed() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, _stderr, _code) = run_with_fixture(&["--format", "json", &project], tmp.path());
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(report.get("changelogs").is_none(), "{report}");
+}
@@ -0,0 +1,101 @@
+//! End-to-end `composer.json` runs, resolved through `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "packagist": {
+            "monolog/monolog": { "latest": "3.7.0", "matching": { "^2.9": "2.10.0" } },
+            "symfony/console": { "latest": "7.1.6" },
+            "phpunit/phpunit": { "latest": "11.4.3", "matching": { "~9.6.0": "9.6.21" } }
+        } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const COMPOSER_JSON: &str = r#"{
+    "name": "acme/app",
+    "require": {
+        "php": ">=8.1",
+        "ext-mbstring": "*",
+        "monolog/monolog": "^2.9",
+        "symfony/console": ">=6.4"
+    },
+    "require-dev": {
+        "phpunit/phpunit": "~9.6.0"
+    }
+}
+"#;
+
+#[test]
+fn composer_requirements_are_updated_keeping_operators() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("composer.json"), COMPOSER_JSON).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--apply", "--no-cache", "--format", "json", &path_str],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("composer.json")).unwrap(),
+        COMPOSER_JSON
+            .replace(r#""^2.9""#, r#""^2.10""#)
+            .replace(r#"">=6.4""#, r#"">=7.1""#)
+            .replace(r#""~9.6.0""#, r#""~9.6.21""#)
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let file = &report["files"][0];
+    assert_eq!(file["file_type"], "composer_json");
+    assert_eq!(file["lang"], "php");
+    assert_eq!(file["updates"].as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn lang_php_limits_the_run_to_composer_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("composer.json"), COMPOSER_JSON).unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "flask==2.0.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--lang", "php", "--no-cache", "--format", "json", &path_str],
+        tmp.path(),
+    );
+
+    // Dry run with pending updates.
+    assert_eq!(code, 1, "stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), 1);
+    assert!(
+        files[0]["path"]
+            .as_str()
+            .unwrap()
+            .ends_with("composer.json"),
+        "{files:?}"
+    );
+}
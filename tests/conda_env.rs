@@ -0,0 +1,77 @@
+//! End-to-end conda `environment.yml` runs, resolved through
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "pypi": { "requests": "2.32.3", "flask": "3.0.3" } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const ENVIRONMENT_YML: &str = "# Analysis environment
+name: analysis
+channels:
+  - conda-forge
+dependencies:
+  - python=3.11
+  - numpy=1.24  # pinned for the notebooks
+  - pip
+  - pip:
+    - requests==2.31.0
+    - flask>=2.0.0  # web
+";
+
+#[test]
+fn pip_list_is_updated_and_conda_packages_reported() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("environment.yml"), ENVIRONMENT_YML).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--apply", "--no-cache", "--format", "json", &path_str],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("environment.yml")).unwrap(),
+        ENVIRONMENT_YML
+            .replace("requests==2.31.0", "requests==2.32.3")
+            .replace("flask>=2.0.0", "flask>=3.0.3")
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    let file = &report["files"][0];
+    assert_eq!(file["file_type"], "conda_env", "{file}");
+    assert_eq!(report["summary"]["errors"], 0);
+    let skipped: Vec<&str> = file["skipped_sources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["package"].as_str().unwrap())
+        .collect();
+    assert_eq!(skipped, ["python", "numpy", "pip"]);
+}
@@ -0,0 +1,156 @@
+//! Integration tests for `--decisions-file`.
+//!
+//! The record must list every dependency discovered in every scanned file
+//! exactly once, each with a decision and a non-empty reason.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use wiremock::matchers::{method, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_env(args: &[&str], cwd: &Path, env: &[(&str, &str)]) -> (String, String, i32) {
+    let mut cmd = Command::new(upd_bin());
+    cmd.args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap());
+    for (k, v) in env {
+        cmd.env(k, v);
+    }
+    let output = cmd.output().expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+async fn mount_simple(server: &MockServer, package: &str, versions: &[&str]) {
+    let links: String = versions
+        .iter()
+        .map(|v| format!("<a href=\"{package}-{v}.tar.gz\">{package}-{v}.tar.gz</a>\n"))
+        .collect();
+    let html = format!("<!DOCTYPE html><html><body>\n{links}</body></html>");
+    Mock::given(method("GET"))
+        .and(path_regex(format!(r"^/simple/{package}/?$")))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(html.into_bytes(), "text/html"))
+        .mount(server)
+        .await;
+}
+
+fn decisions_by_package(record: &serde_json::Value) -> Vec<(String, String, String)> {
+    record["decisions"]
+        .as_array()
+        .expect("decisions array")
+        .iter()
+        .map(|d| {
+            (
+                d["package"].as_str().unwrap().to_string(),
+                d["decision"].as_str().unwrap().to_string(),
+                d["reason"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn every_dependency_appears_exactly_once_with_a_reason() {
+    let server = MockServer::start().await;
+    mount_simple(&server, "requests", &["1.0.0", "1.0.1"]).await;
+    mount_simple(&server, "urllib3", &["2.0.0"]).await;
+    // The newest django is outside `<4`, so the constraint keeps it in place.
+    mount_simple(&server, "django", &["3.0", "5.0.0"]).await;
+    // `missing` is not mounted, so its lookup fails.
+
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(
+        tmp.path().join("requirements.txt"),
+        "requests==1.0.0\nurllib3==2.0.0\ndjango>=3.0,<4\nsix==1.16.0\nmissing==0.1.0\n",
+    )
+    .unwrap();
+    fs::write(tmp.path().join(".updrc.toml"), "ignore = [\"six\"]\n").unwrap();
+    let record_path = tmp.path().join("decisions.json");
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, _code) = run_with_env(
+        &[
+            "--no-cache",
+            "--decisions-file",
+            record_path.to_str().unwrap(),
+            &path_str,
+        ],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+
+    let record: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(&record_path).unwrap_or_else(|e| panic!("{e}; stderr: {stderr}")),
+    )
+    .unwrap();
+    assert_eq!(record["schema_version"], 1);
+    assert_eq!(record["mode"], "dry-run");
+
+    let decisions = decisions_by_package(&record);
+    for package in ["requests", "urllib3", "django", "six", "missing"] {
+        let count = decisions.iter().filter(|(p, _, _)| p == package).count();
+        assert_eq!(
+            count, 1,
+            "{package} must appear exactly once: {decisions:?}"
+        );
+    }
+    assert_eq!(decisions.len(), 5, "{decisions:?}");
+    assert!(
+        decisions.iter().all(|(_, _, reason)| !reason.is_empty()),
+        "every decision needs a reason: {decisions:?}"
+    );
+
+    let kind = |package: &str| {
+        decisions
+            .iter()
+            .find(|(p, _, _)| p == package)
+            .map(|(_, k, _)| k.as_str())
+            .unwrap()
+    };
+    assert_eq!(kind("requests"), "updated");
+    assert_eq!(kind("urllib3"), "unchanged");
+    assert_eq!(kind("django"), "constrained");
+    assert_eq!(kind("six"), "ignored");
+    assert_eq!(kind("missing"), "error");
+}
+
+#[tokio::test]
+async fn apply_mode_records_old_and_new_versions() {
+    let server = MockServer::start().await;
+    mount_simple(&server, "requests", &["1.0.0", "1.0.1"]).await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==1.0.0\n").unwrap();
+    let record_path = tmp.path().join("decisions.json");
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_env(
+        &[
+            "--apply",
+            "--no-cache",
+            "--decisions-file",
+            record_path.to_str().unwrap(),
+            &path_str,
+        ],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+
+    let record: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&record_path).unwrap()).unwrap();
+    assert_eq!(record["mode"], "apply");
+    let entry = &record["decisions"][0];
+    assert_eq!(entry["package"], "requests");
+    assert_eq!(entry["decision"], "updated");
+    assert_eq!(entry["current"], "1.0.0");
+    assert_eq!(entry["new"], "1.0.1");
+}
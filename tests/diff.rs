@@ -0,0 +1,72 @@
+//! `--diff`: text output shows a unified diff of each rewritten file instead
+//! of per-package lines. Registries resolve from `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{"pypi": {"flask": "3.0.3"}, "npm": {"react": "18.3.1"}}"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[test]
+fn diff_dry_run_prints_unified_diff_per_file_without_writing() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = tmp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(project.join("requirements.txt"), "flask==2.0.0\n").unwrap();
+    let package_json = "{\n  \"dependencies\": {\n    \"react\": \"^18.2.0\"\n  }\n}\n";
+    fs::write(project.join("package.json"), package_json).unwrap();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--diff",
+            "--dry-run",
+            "--no-color",
+            "--format",
+            "text",
+            project.to_str().unwrap(),
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 1, "stderr: {stderr}");
+    assert!(stdout.contains("requirements.txt\n"), "{stdout}");
+    assert!(
+        stdout.contains("@@ -1 +1 @@\n-flask==2.0.0\n+flask==3.0.3\n"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("-    \"react\": \"^18.2.0\"\n+    \"react\": \"^18.3.1\"\n"),
+        "{stdout}"
+    );
+    assert!(
+        !stdout.contains("→"),
+        "per-package lines replaced: {stdout}"
+    );
+    assert_eq!(
+        fs::read_to_string(project.join("package.json")).unwrap(),
+        package_json
+    );
+}
@@ -0,0 +1,98 @@
+//! End-to-end Dockerfile base-image runs, resolved through
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "docker-hub": {
+            "python": {
+                "latest": "3.13.1",
+                "matching": { "3.11.2-slim": "3.13.1-slim", "3.11": "3.13" }
+            },
+            "node": { "latest": "22.12.0", "matching": { "18-alpine": "22-alpine" } }
+        } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const DOCKERFILE: &str = "FROM python:3.11.2-slim AS builder
+RUN pip wheel -w /wheels .
+
+FROM builder AS test
+RUN pytest
+
+FROM ghcr.io/acme/base:1.0.0
+FROM python:3.11
+COPY --from=builder /wheels /wheels
+";
+
+#[test]
+fn base_images_are_updated_keeping_stages_and_variants() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("Dockerfile"), DOCKERFILE).unwrap();
+    fs::write(tmp.path().join("web.Dockerfile"), "FROM node:18-alpine\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--no-parallel",
+            "--format",
+            "json",
+            &path_str,
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("Dockerfile")).unwrap(),
+        DOCKERFILE
+            .replace(
+                "python:3.11.2-slim AS builder",
+                "python:3.13.1-slim AS builder"
+            )
+            .replace("FROM python:3.11\n", "FROM python:3.13\n")
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("web.Dockerfile")).unwrap(),
+        "FROM node:22-alpine\n"
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    assert_eq!(report["summary"]["errors"], 0);
+    let dockerfile = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["path"].as_str().unwrap().ends_with("/Dockerfile"))
+        .unwrap();
+    assert_eq!(dockerfile["file_type"], "dockerfile", "{dockerfile}");
+    assert_eq!(dockerfile["updates"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        dockerfile["skipped_sources"][0]["package"],
+        "ghcr.io/acme/base"
+    );
+}
@@ -0,0 +1,81 @@
+//! Integration tests for the cross-manifest consistency warning.
+//!
+//! When `requirements.txt` and `pyproject.toml` in one directory both declare
+//! a package at different versions, `upd` warns and suggests consolidating.
+//! The check is reporting-only: it never changes files or the exit code.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, r#"{ "pypi": { "requests": "2.28.0" } }"#).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn write_project(dir: &Path, pyproject_requests: &str) -> String {
+    fs::write(dir.join("requirements.txt"), "requests==2.28.0\n").unwrap();
+    fs::write(
+        dir.join("pyproject.toml"),
+        format!(
+            "[project]\nname = \"demo\"\nversion = \"0.1.0\"\ndependencies = [\"requests=={pyproject_requests}\"]\n"
+        ),
+    )
+    .unwrap();
+    dir.to_str().unwrap().to_string()
+}
+
+#[test]
+fn diverging_versions_across_manifest_types_warn() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path(), "2.25.0");
+
+    let (stdout, stderr, _code) = run_with_fixture(
+        &["--format", "text", "--lang", "python", &project],
+        tmp.path(),
+    );
+
+    assert!(
+        stderr.contains("requests is declared at different versions in")
+            && stderr.contains("pyproject.toml (2.25.0)")
+            && stderr.contains("requirements.txt (2.28.0)")
+            && stderr.contains("consider consolidating"),
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("requirements.txt")).unwrap(),
+        "requests==2.28.0\n"
+    );
+}
+
+#[test]
+fn matching_versions_do_not_warn() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path(), "2.28.0");
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--format", "json", "--lang", "python", &project],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    assert_eq!(report["summary"]["warnings"], 0);
+    assert!(!stderr.contains("declared at different versions"));
+}
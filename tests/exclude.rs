@@ -0,0 +1,96 @@
+//! `--exclude`: files matching the globs (relative to the scanned directory)
+//! are left out of discovery. Registries resolve from `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, r#"{"pypi": {"flask": "3.0.3"}}"#).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn write_project(dir: &Path) {
+    let fixtures = dir.join("project/tests/fixtures/old");
+    fs::create_dir_all(&fixtures).unwrap();
+    fs::write(dir.join("project/requirements.txt"), "flask==2.0.0\n").unwrap();
+    fs::write(fixtures.join("requirements.txt"), "flask==2.0.0\n").unwrap();
+}
+
+#[test]
+fn exclude_keeps_fixture_manifests_untouched() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+
+    let (_stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--exclude",
+            "**/tests/fixtures/**",
+            "--exclude",
+            "does/not/match",
+            "project",
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("project/requirements.txt")).unwrap(),
+        "flask==3.0.3\n"
+    );
+    assert_eq!(
+        fs::read_to_string(
+            tmp.path()
+                .join("project/tests/fixtures/old/requirements.txt")
+        )
+        .unwrap(),
+        "flask==2.0.0\n"
+    );
+}
+
+#[test]
+fn exclude_pattern_is_relative_to_the_scanned_directory() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--no-cache",
+            "--format",
+            "json",
+            "--exclude",
+            "tests/fixtures/*/requirements.txt",
+            "project",
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 1, "stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), 1, "{stdout}");
+    assert!(
+        files[0]["path"]
+            .as_str()
+            .unwrap()
+            .ends_with("project/requirements.txt")
+    );
+}
@@ -330,6 +330,96 @@ fn decide_exit_code_errors_take_precedence() {
     assert_eq!(decide_exit_code(false, false, true), 2);
 }
 
+/// Unit test: `decide_error_on_exit_code` fails only at or above the level.
+#[test]
+fn decide_error_on_exit_code_levels() {
+    use upd::{ErrorOn, decide_error_on_exit_code};
+    let patch_only = (0, 0, 3);
+    assert_eq!(
+        decide_error_on_exit_code(ErrorOn::Major, patch_only, true, false),
+        0
+    );
+    assert_eq!(
+        decide_error_on_exit_code(ErrorOn::Minor, patch_only, true, false),
+        0
+    );
+    assert_eq!(
+        decide_error_on_exit_code(ErrorOn::Patch, patch_only, true, false),
+        1
+    );
+    assert_eq!(
+        decide_error_on_exit_code(ErrorOn::Minor, (1, 0, 0), true, false),
+        1
+    );
+    // Pins alone count as pending for `any`.
+    assert_eq!(
+        decide_error_on_exit_code(ErrorOn::Any, (0, 0, 0), true, false),
+        1
+    );
+    assert_eq!(
+        decide_error_on_exit_code(ErrorOn::Error, (2, 0, 0), true, false),
+        0
+    );
+}
+
+/// Unit test: errors still exit 2 under `--error-on`, except `none`.
+#[test]
+fn decide_error_on_exit_code_errors() {
+    use upd::{ErrorOn, decide_error_on_exit_code};
+    assert_eq!(
+        decide_error_on_exit_code(ErrorOn::Major, (1, 0, 0), true, true),
+        2
+    );
+    assert_eq!(
+        decide_error_on_exit_code(ErrorOn::Error, (0, 0, 0), false, true),
+        2
+    );
+    assert_eq!(
+        decide_error_on_exit_code(ErrorOn::None, (1, 0, 0), true, true),
+        0
+    );
+}
+
+/// `--error-on major` ignores patch drift and fails on a major update, in
+/// dry-run and `--apply` alike.
+#[test]
+fn error_on_major_gates_on_major_updates_only() {
+    let tmp = tempfile::tempdir().unwrap();
+    let fixture = tmp.path().join("fixture.json");
+    fs::write(
+        &fixture,
+        r#"{"pypi":{"requests":"2.32.3","flask":"3.0.3"}}"#,
+    )
+    .unwrap();
+    let cache = tmp.path().join(".cache");
+    let env = [
+        ("UPD_REGISTRY_FIXTURE", fixture.to_str().unwrap()),
+        ("UPD_CACHE_DIR", cache.to_str().unwrap()),
+    ];
+    let reqs = tmp.path().join("requirements.txt");
+
+    fs::write(&reqs, "flask==3.0.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+    let (_stdout, stderr, code) = run_with_env(
+        &["--no-cache", "--error-on", "major", &path_str],
+        tmp.path(),
+        &env,
+    );
+    assert_eq!(code, 0, "patch drift must not fail; stderr: {stderr}");
+
+    fs::write(&reqs, "flask==3.0.0\nrequests==1.0.0\n").unwrap();
+    let (_stdout, stderr, code) = run_with_env(
+        &["--apply", "--no-cache", "--error-on", "major", &path_str],
+        tmp.path(),
+        &env,
+    );
+    assert_eq!(code, 1, "major update must fail; stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(&reqs).unwrap(),
+        "flask==3.0.3\nrequests==2.32.3\n"
+    );
+}
+
 // ── decide_audit_exit_code unit tests ────────────────────────────────────────
 
 /// Unit test: no vulns, no errors → exit 0 regardless of --no-fail.
@@ -507,6 +597,56 @@ fn show_config_exits_zero() {
     );
 }
 
+/// `--print-config` lists the merged ignore/pin rules with the files they
+/// were read from, in merge order.
+#[test]
+fn print_config_shows_resolved_rules_and_sources() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(
+        tmp.path().join("pyproject.toml"),
+        "[tool.upd]\nignore = [\"boto3\"]\n[tool.upd.pin]\nflask = \"2.3.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join(".updrc.toml"),
+        "ignore = [\"django\"]\n[pin]\nflask = \"3.0.0\"\n",
+    )
+    .unwrap();
+    let path_str = tmp.path().to_str().unwrap();
+
+    let (stdout, stderr, code) = run(
+        &["--print-config", "--format", "json", path_str],
+        tmp.path(),
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let json = parse_json(&stdout);
+    let sources: Vec<&str> = json["sources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s.as_str().unwrap())
+        .collect();
+    assert_eq!(sources.len(), 2, "{json}");
+    assert!(sources[0].ends_with("pyproject.toml"), "{json}");
+    assert!(sources[1].ends_with(".updrc.toml"), "{json}");
+    assert_eq!(json["ignore"], serde_json::json!(["boto3", "django"]));
+    assert_eq!(json["pin"], serde_json::json!({ "flask": "3.0.0" }));
+
+    let (stdout, _stderr, code) = run(
+        &[
+            "--print-config",
+            "--no-config",
+            "--format",
+            "text",
+            path_str,
+        ],
+        tmp.path(),
+    );
+    assert_eq!(code, 0);
+    assert!(stdout.contains("Config: none (--no-config)"), "{stdout}");
+    assert!(stdout.contains("ignore (0):"), "{stdout}");
+}
+
 // ── bad config parse tests ────────────────────────────────────────────────────
 
 /// A config file using `[ignore]` (table) instead of `ignore = [...]` (array)
@@ -0,0 +1,104 @@
+//! `--fail-fast`: the run stops at the first file whose lookups fail, so
+//! later files are neither checked nor written. Resolved through
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+// `missing-pkg` is absent, so looking it up fails.
+const FIXTURE: &str = r#"{
+    "pypi": { "requests": "2.32.3", "flask": "3.0.3" }
+}"#;
+
+fn run(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, FIXTURE).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn write_files(dir: &Path) {
+    fs::create_dir_all(dir.join("api")).unwrap();
+    fs::create_dir_all(dir.join("worker")).unwrap();
+    fs::write(
+        dir.join("api/requirements.txt"),
+        "requests==2.31.0\nmissing-pkg==1.0.0\n",
+    )
+    .unwrap();
+    fs::write(dir.join("worker/requirements.txt"), "flask==2.0.0\n").unwrap();
+}
+
+#[test]
+fn fail_fast_stops_before_later_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_files(tmp.path());
+
+    // --no-parallel makes `api` the first file processed.
+    let (stdout, stderr, code) = run(
+        &[
+            "--apply",
+            "--no-cache",
+            "--no-parallel",
+            "--fail-fast",
+            "--format",
+            "text",
+            "api/requirements.txt",
+            "worker/requirements.txt",
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 2, "stdout: {stdout}\nstderr: {stderr}");
+    assert!(stderr.contains("missing-pkg"), "stderr: {stderr}");
+    assert!(
+        stdout.contains("Stopped at the first error, in api/requirements.txt (--fail-fast)"),
+        "stdout: {stdout}"
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("worker/requirements.txt")).unwrap(),
+        "flask==2.0.0\n",
+        "files after the failure must not be written"
+    );
+}
+
+#[test]
+fn without_fail_fast_every_file_is_processed() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_files(tmp.path());
+
+    let (stdout, stderr, code) = run(
+        &[
+            "--apply",
+            "--no-cache",
+            "--no-parallel",
+            "--format",
+            "text",
+            "api/requirements.txt",
+            "worker/requirements.txt",
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 2, "stdout: {stdout}\nstderr: {stderr}");
+    assert!(!stdout.contains("--fail-fast"), "stdout: {stdout}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("worker/requirements.txt")).unwrap(),
+        "flask==3.0.3\n"
+    );
+}
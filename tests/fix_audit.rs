@@ -7,6 +7,8 @@
 //!   unchanged; falls through to the normal audit exit code (3 for unfixed vulns).
 //! - `--fix-audit --no-fail` exits 0 even when pending fixes exist in dry-run mode.
 //! - An already-clean audit (no vulnerabilities) with `--fix-audit` exits 0.
+//! - `--fix` (alias) picks the highest fixed version across a package's
+//!   vulnerabilities and keeps the declared version's precision.
 
 use std::fs;
 use std::process::Command;
@@ -302,3 +304,53 @@ async fn fix_audit_clean_audit_exits_0() {
         "file must be unchanged on clean audit; got: {content}"
     );
 }
+
+/// Two vulnerabilities on one package: `--fix` bumps to the higher fixed
+/// version, written at the precision of the declared `==2.1`.
+#[tokio::test]
+async fn fix_alias_uses_highest_fixed_version_and_keeps_precision() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/querybatch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": [{ "vulns": [{ "id": "GHSA-fix-low" }, { "id": "GHSA-fix-high" }] }]
+        })))
+        .mount(&server)
+        .await;
+
+    for (id, fixed) in [("GHSA-fix-low", "2.28.0"), ("GHSA-fix-high", "2.31.0")] {
+        Mock::given(method("GET"))
+            .and(path(format!("/vulns/{id}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": id,
+                "summary": "test vulnerability",
+                "affected": [{ "ranges": [{ "events": [{ "fixed": fixed }] }] }]
+            })))
+            .mount(&server)
+            .await;
+    }
+
+    let tmp = tempfile::tempdir().unwrap();
+    let req_path = tmp.path().join("requirements.txt");
+    fs::write(&req_path, "requests==2.1\n").unwrap();
+
+    let (stdout, stderr, code) = run_with_env(
+        &[
+            "audit",
+            "--fix",
+            "--apply",
+            "--no-cache",
+            "--format",
+            "text",
+        ],
+        tmp.path(),
+        &[("OSV_API_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 0, "stdout: {stdout}\nstderr: {stderr}");
+    assert_eq!(fs::read_to_string(&req_path).unwrap(), "requests==2.31\n");
+}
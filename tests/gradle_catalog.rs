@@ -0,0 +1,104 @@
+//! End-to-end Gradle version catalog runs, resolved through
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "maven": {
+            "org.jetbrains.kotlin:kotlin-stdlib": "2.0.21",
+            "com.google.guava:guava": "33.3.1-jre"
+        } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const CATALOG: &str = r#"[versions]
+kotlin = "1.9.22"
+guava = "32.1.3-jre" # pinned by the platform team
+agp = "8.2.0"
+
+[libraries]
+kotlin-stdlib = { module = "org.jetbrains.kotlin:kotlin-stdlib", version.ref = "kotlin" }
+guava = { group = "com.google.guava", name = "guava", version.ref = "guava" }
+
+[plugins]
+android-application = { id = "com.android.application", version.ref = "agp" }
+"#;
+
+fn write_catalog(dir: &Path) {
+    fs::create_dir_all(dir.join("gradle")).unwrap();
+    fs::write(dir.join("gradle/libs.versions.toml"), CATALOG).unwrap();
+}
+
+#[test]
+fn gradle_catalog_versions_are_updated_in_place() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_catalog(tmp.path());
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--apply", "--no-cache", "--format", "json", &path_str],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("gradle/libs.versions.toml")).unwrap(),
+        CATALOG
+            .replace(r#""1.9.22""#, r#""2.0.21""#)
+            .replace(r#""32.1.3-jre""#, r#""33.3.1-jre""#)
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let file = &report["files"][0];
+    assert_eq!(file["file_type"], "gradle_catalog");
+    assert_eq!(file["lang"], "jvm");
+    assert_eq!(file["updates"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn lang_jvm_limits_the_run_to_gradle_catalogs() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_catalog(tmp.path());
+    fs::write(tmp.path().join("requirements.txt"), "flask==2.0.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--lang", "jvm", "--no-cache", "--format", "json", &path_str],
+        tmp.path(),
+    );
+
+    // Dry run with pending updates.
+    assert_eq!(code, 1, "stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), 1);
+    assert!(
+        files[0]["path"]
+            .as_str()
+            .unwrap()
+            .ends_with("libs.versions.toml"),
+        "{files:?}"
+    );
+}
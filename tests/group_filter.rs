@@ -0,0 +1,76 @@
+//! Integration tests for `--group`: only the named dependency groups are
+//! updated and production dependencies are left as written.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "pypi": { "requests": "2.32.3", "pytest": "8.3.2" } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const PYPROJECT: &str = r#"[project]
+name = "demo"
+version = "0.1.0"
+dependencies = ["requests>=2.28.0"]
+
+[dependency-groups]
+dev = ["pytest>=7.0.0"]
+"#;
+
+#[test]
+fn group_flag_updates_only_the_named_group() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("pyproject.toml"), PYPROJECT).unwrap();
+    let project = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_fixture(
+        &["--apply", "--group", "dev", "--format", "text", &project],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("pyproject.toml")).unwrap(),
+        PYPROJECT.replace("pytest>=7.0.0", "pytest>=8.3.2")
+    );
+}
+
+#[test]
+fn without_group_flag_every_section_is_updated() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("pyproject.toml"), PYPROJECT).unwrap();
+    let project = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) =
+        run_with_fixture(&["--apply", "--format", "text", &project], tmp.path());
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("pyproject.toml")).unwrap(),
+        PYPROJECT
+            .replace("pytest>=7.0.0", "pytest>=8.3.2")
+            .replace("requests>=2.28.0", "requests>=2.32.3")
+    );
+}
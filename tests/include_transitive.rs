@@ -0,0 +1,144 @@
+//! `--include-transitive`: locked packages the manifest does not declare are
+//! reported when outdated, never rewritten. Registries resolve from
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{"crates.io": {"serde_json": "1.0.128", "itoa": "1.0.11", "ryu": "1.0.18"}}"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const CARGO_LOCK: &str = r#"version = 3
+
+[[package]]
+name = "demo"
+version = "0.1.0"
+dependencies = ["serde_json"]
+
+[[package]]
+name = "itoa"
+version = "1.0.9"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "ryu"
+version = "1.0.18"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde_json"
+version = "1.0.128"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["itoa", "ryu"]
+"#;
+
+fn write_project(dir: &Path) -> String {
+    let project = dir.join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde_json = \"1.0.128\"\n",
+    )
+    .unwrap();
+    fs::write(project.join("Cargo.lock"), CARGO_LOCK).unwrap();
+    project.to_str().unwrap().to_string()
+}
+
+#[test]
+fn include_transitive_reports_outdated_locked_packages_in_json() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--no-cache",
+            "--format",
+            "json",
+            "--include-transitive",
+            &project,
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "declared deps are current; stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(report["summary"]["updates_total"], 0);
+    let transitive = report["transitive"].as_array().unwrap();
+    assert_eq!(transitive.len(), 1, "{stdout}");
+    assert_eq!(transitive[0]["package"], "itoa");
+    assert_eq!(transitive[0]["current"], "1.0.9");
+    assert_eq!(transitive[0]["latest"], "1.0.11");
+    assert!(
+        transitive[0]["lockfile"]
+            .as_str()
+            .unwrap()
+            .ends_with("Cargo.lock")
+    );
+}
+
+#[test]
+fn include_transitive_text_output_leaves_lockfile_alone() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--format",
+            "text",
+            "--include-transitive",
+            &project,
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(
+        stdout.contains("Outdated transitive dependencies"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("itoa 1.0.9 → 1.0.11"), "{stdout}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("project/Cargo.lock")).unwrap(),
+        CARGO_LOCK
+    );
+}
+
+#[test]
+fn transitive_packages_are_not_reported_by_default() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--no-cache", "--format", "json", "--only-direct", &project],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(report.get("transitive").is_none(), "{stdout}");
+}
@@ -0,0 +1,93 @@
+//! `@types/*` updates are labelled with their runtime package, and
+//! `--link-types` prints them right below it.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "npm": {
+            "@types/react": "18.3.12",
+            "lodash": "4.17.21",
+            "react": "18.3.1"
+        } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+// `@types/react` sorts before its runtime package in the file.
+const PACKAGE_JSON: &str = r#"{
+  "dependencies": {
+    "@types/react": "18.2.0",
+    "lodash": "4.17.20",
+    "react": "18.2.0"
+  }
+}
+"#;
+
+fn update_lines(stdout: &str) -> Vec<&str> {
+    stdout
+        .lines()
+        .filter(|line| line.contains(": Would update"))
+        .collect()
+}
+
+#[test]
+fn types_update_names_its_runtime_package() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("package.json"), PACKAGE_JSON).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) =
+        run_with_fixture(&["--no-cache", "--format", "text", &path_str], tmp.path());
+
+    assert_eq!(code, 1, "stderr: {stderr}");
+    let lines = update_lines(&stdout);
+    assert_eq!(lines.len(), 3, "{stdout}");
+    assert!(
+        lines[0].contains("@types/react") && lines[0].ends_with("(types for react)"),
+        "{stdout}"
+    );
+    assert!(!lines[1].contains("types for"), "{stdout}");
+}
+
+#[test]
+fn link_types_prints_types_below_runtime_package() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("package.json"), PACKAGE_JSON).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--no-cache", "--format", "text", "--link-types", &path_str],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 1, "stderr: {stderr}");
+    let lines = update_lines(&stdout);
+    assert_eq!(lines.len(), 3, "{stdout}");
+    assert!(lines[0].contains("lodash"), "{stdout}");
+    assert!(lines[1].contains(" react "), "{stdout}");
+    assert!(
+        lines[2].starts_with("  ↳ ") && lines[2].contains("@types/react"),
+        "{stdout}"
+    );
+}
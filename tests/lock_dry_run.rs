@@ -0,0 +1,112 @@
+//! `--lock` in a dry run lists the lockfiles a real run would regenerate,
+//! without invoking any package manager. Registries resolve from
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, r#"{"npm": {"react": "18.3.1"}}"#).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        // No package manager may run in a dry run.
+        .env("PATH", "")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[test]
+fn dry_run_lists_lockfiles_it_would_regenerate() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = tmp.path().join("web");
+    fs::create_dir_all(&project).unwrap();
+    let manifest = r#"{"dependencies": {"react": "^18.2.0"}}"#;
+    fs::write(project.join("package.json"), manifest).unwrap();
+    fs::write(project.join("package-lock.json"), "{}\n").unwrap();
+    // An unchanged project's lockfile is not listed.
+    let other = tmp.path().join("api");
+    fs::create_dir_all(&other).unwrap();
+    fs::write(
+        other.join("package.json"),
+        r#"{"dependencies": {"react": "^18.3.1"}}"#,
+    )
+    .unwrap();
+    fs::write(other.join("package-lock.json"), "{}\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, _) = run_with_fixture(
+        &[
+            "--lock",
+            "--dry-run",
+            "--no-cache",
+            "--output",
+            "text",
+            "--no-color",
+            &path_str,
+        ],
+        tmp.path(),
+    );
+
+    assert!(
+        stdout.contains("Would regenerate lockfiles:"),
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+    let lockfile = project.join("package-lock.json");
+    assert!(
+        stdout.contains(&format!("{} (npm ", lockfile.display())),
+        "stdout: {stdout}"
+    );
+    assert!(
+        !stdout.contains(&other.join("package-lock.json").display().to_string()),
+        "stdout: {stdout}"
+    );
+    assert!(
+        !stdout.contains("Regenerating lockfiles"),
+        "stdout: {stdout}"
+    );
+    assert_eq!(
+        fs::read_to_string(project.join("package.json")).unwrap(),
+        manifest
+    );
+    assert_eq!(fs::read_to_string(&lockfile).unwrap(), "{}\n");
+}
+
+#[test]
+fn dry_run_without_lock_lists_nothing() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(
+        tmp.path().join("package.json"),
+        r#"{"dependencies": {"react": "^18.2.0"}}"#,
+    )
+    .unwrap();
+    fs::write(tmp.path().join("package-lock.json"), "{}\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, _) = run_with_fixture(
+        &["--dry-run", "--no-cache", "--output", "text", &path_str],
+        tmp.path(),
+    );
+
+    assert!(
+        stdout.contains("react"),
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(
+        !stdout.contains("Would regenerate lockfiles"),
+        "stdout: {stdout}"
+    );
+}
@@ -0,0 +1,99 @@
+//! `--update-lockfile-only` regenerates lockfiles without touching manifests.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+const PACKAGE_JSON: &str = r#"{ "dependencies": { "lodash": "4.17.0" } }"#;
+
+fn run(args: &[&str], cwd: &Path, path_dir: &Path) -> (String, String, i32) {
+    // An empty fixture makes any registry lookup fail, so a clean run proves
+    // no updater ran.
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, "{}").unwrap();
+    let path = format!(
+        "{}:{}",
+        path_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("PATH", path)
+        .env("UPD_CACHE_DIR", cwd.join(".cache"))
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+/// A fake `npm` that records its arguments next to the lockfile.
+#[cfg(unix)]
+fn stub_npm(dir: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+    let npm = dir.join("npm");
+    fs::write(
+        &npm,
+        "#!/bin/sh\n[ \"$1\" = --version ] && exit 0\necho \"$@\" > npm-args\n",
+    )
+    .unwrap();
+    fs::set_permissions(&npm, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn lockfile_only_runs_refresh_and_leaves_manifest_alone() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bin = tempfile::tempdir().unwrap();
+    stub_npm(bin.path());
+    let app = tmp.path().join("app");
+    fs::create_dir(&app).unwrap();
+    fs::write(app.join("package.json"), PACKAGE_JSON).unwrap();
+    fs::write(app.join("package-lock.json"), "{}").unwrap();
+    // No lockfile: nothing to regenerate here.
+    fs::write(tmp.path().join("requirements.txt"), "flask==3.0.0\n").unwrap();
+    let root = tmp.path().to_str().unwrap();
+
+    let (stdout, stderr, code) = run(
+        &["--update-lockfile-only", "--output", "text", root],
+        tmp.path(),
+        bin.path(),
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(stdout.contains("Would regenerate"), "{stdout}");
+    assert!(stdout.contains("package-lock.json"), "{stdout}");
+    assert!(!app.join("npm-args").exists(), "dry-run must not run npm");
+
+    let (stdout, stderr, code) = run(
+        &[
+            "--update-lockfile-only",
+            "--apply",
+            "--format",
+            "json",
+            root,
+        ],
+        tmp.path(),
+        bin.path(),
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    assert_eq!(report["mode"], "applied");
+    assert_eq!(report["lockfiles"].as_array().unwrap().len(), 1);
+    assert_eq!(report["lockfiles"][0]["regenerated"], true);
+    assert_eq!(
+        fs::read_to_string(app.join("npm-args")).unwrap().trim(),
+        "install --package-lock-only --ignore-scripts"
+    );
+    assert_eq!(
+        fs::read_to_string(app.join("package.json")).unwrap(),
+        PACKAGE_JSON
+    );
+}
@@ -0,0 +1,100 @@
+//! `--format markdown`: update tables for pull request descriptions.
+//! Registries resolve from `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{"pypi": {"flask": "3.0.3", "requests": "2.32.3"}, "npm": {"react": "18.3.1"}}"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[test]
+fn markdown_report_groups_updates_by_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = tmp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("requirements.txt"),
+        "flask==2.0.0\nrequests==2.32.0\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("package.json"),
+        r#"{"dependencies": {"react": "^18.2.0"}}"#,
+    )
+    .unwrap();
+    let project_str = project.to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--format",
+            "markdown",
+            &project_str,
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(
+        stdout.contains("requirements.txt`\n\n| Package | From | To | Type | Ecosystem |"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("| flask | 2.0.0 | 3.0.3 | **major** \u{26a0}\u{fe0f} | python |"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("| requests | 2.32.0 | 2.32.3 | patch | python |"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("| react | 18.2.0 | 18.3.1 | minor | node |"),
+        "{stdout}"
+    );
+    assert!(
+        stdout
+            .ends_with("**3 update(s) applied** (1 major, 1 minor, 1 patch) in 2 of 2 file(s).\n"),
+        "{stdout}"
+    );
+    // Markdown replaces the text report on stdout.
+    assert!(!stdout.contains("Summary"), "{stdout}");
+}
+
+#[test]
+fn markdown_report_without_updates() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "flask==3.0.3\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--no-cache", "--format", "markdown", &path_str],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(stdout, "**No updates available.**\n");
+}
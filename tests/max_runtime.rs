@@ -0,0 +1,144 @@
+//! Integration tests for `--max-runtime`.
+//!
+//! Once the budget passes, no further registry request is made and requests
+//! in flight are abandoned: whatever resolved in time is reported (and
+//! applied with `--apply`), the rest is listed under `timed_out`, and the run
+//! exits with the distinct "timed out, partial" status 7.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+use wiremock::matchers::{method, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_index(args: &[&str], cwd: &Path, index: &str) -> (String, String, i32) {
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UV_INDEX_URL", index)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn simple_page(package: &str, version: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html><html><body>
+<a href="{package}-{version}.tar.gz">{package}-{version}.tar.gz</a>
+</body></html>"#
+    )
+}
+
+/// An index that answers `fast` at once and stalls on `slow` far beyond the
+/// budget used by the tests.
+async fn start_index() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/simple/fast/?$"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw(simple_page("fast", "2.0.0").into_bytes(), "text/html"),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/simple/slow/?$"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw(simple_page("slow", "2.0.0").into_bytes(), "text/html")
+                .set_delay(Duration::from_secs(20)),
+        )
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn apply_writes_resolved_updates_and_exits_timed_out() {
+    let server = start_index().await;
+    let tmp = tempfile::tempdir().unwrap();
+    let req = tmp.path().join("requirements.txt");
+    fs::write(&req, "fast==1.0.0\nslow==1.0.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let started = Instant::now();
+    let (stdout, stderr, code) = run_with_index(
+        &[
+            "--apply",
+            "--no-cache",
+            "--pypi-api",
+            "simple-only",
+            "--max-runtime",
+            "2s",
+            "--format",
+            "json",
+            &path_str,
+        ],
+        tmp.path(),
+        &server.uri(),
+    );
+
+    assert!(
+        started.elapsed() < Duration::from_secs(15),
+        "the slow lookup must be abandoned at the deadline"
+    );
+    assert_eq!(code, 7, "timed out, partial; stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(&req).unwrap(),
+        "fast==2.0.0\nslow==1.0.0\n"
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    assert_eq!(report["summary"]["errors"], 0);
+    assert_eq!(report["summary"]["timed_out"], 1);
+    let file = &report["files"][0];
+    assert!(
+        file["timed_out"][0].as_str().unwrap().contains("slow"),
+        "{file}"
+    );
+}
+
+#[tokio::test]
+async fn check_reports_partial_results_with_timed_out_status() {
+    let server = start_index().await;
+    let tmp = tempfile::tempdir().unwrap();
+    let req = tmp.path().join("requirements.txt");
+    fs::write(&req, "fast==1.0.0\nslow==1.0.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_index(
+        &[
+            "--check",
+            "--no-cache",
+            "--pypi-api",
+            "simple-only",
+            "--max-runtime",
+            "2s",
+            "--format",
+            "text",
+            &path_str,
+        ],
+        tmp.path(),
+        &server.uri(),
+    );
+
+    assert_eq!(code, 7, "stdout: {stdout}\nstderr: {stderr}");
+    let combined = format!("{stdout}{stderr}");
+    assert!(combined.contains("fast"), "{combined}");
+    assert!(combined.contains("--max-runtime"), "{combined}");
+    assert_eq!(
+        fs::read_to_string(&req).unwrap(),
+        "fast==1.0.0\nslow==1.0.0\n",
+        "check mode never writes"
+    );
+}
@@ -0,0 +1,126 @@
+//! `upd merge-reports`: combine the `--format json` reports of runs over two
+//! repositories, resolved through `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+const FIXTURE: &str = r#"{
+    "pypi": { "requests": "2.32.3", "django": "5.1.0" },
+    "npm": { "lodash": "4.17.21" }
+}"#;
+
+fn run(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, FIXTURE).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .env("NPM_REGISTRY", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+/// Run `upd --format json` over `repo` and save its report as `<name>.json`.
+fn write_report(root: &Path, name: &str, files: &[(&str, &str)]) -> String {
+    let repo = root.join(name);
+    fs::create_dir_all(&repo).unwrap();
+    for (file, content) in files {
+        fs::write(repo.join(file), content).unwrap();
+    }
+    let (stdout, stderr, code) = run(
+        &["--no-cache", "--format", "json", repo.to_str().unwrap()],
+        root,
+    );
+    assert_eq!(code, 1, "pending updates; stderr: {stderr}");
+    let report = format!("{name}.json");
+    fs::write(root.join(&report), stdout).unwrap();
+    report
+}
+
+#[test]
+fn merge_reports_combines_totals_and_keeps_per_repo_attribution() {
+    let tmp = tempfile::tempdir().unwrap();
+    let api = write_report(
+        tmp.path(),
+        "api",
+        &[("requirements.txt", "requests==2.31.0\ndjango==4.2.0\n")],
+    );
+    let web = write_report(
+        tmp.path(),
+        "web",
+        &[
+            ("requirements.txt", "requests==2.30.0\n"),
+            (
+                "package.json",
+                r#"{"dependencies": {"lodash": "^4.17.20"}}"#,
+            ),
+        ],
+    );
+
+    let (stdout, stderr, code) = run(
+        &["merge-reports", &api, &web, "--output", "json"],
+        tmp.path(),
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+
+    assert_eq!(json["command"], "merge-reports");
+    assert_eq!(json["summary"]["reports"], 2);
+    assert_eq!(json["summary"]["files_scanned"], 3);
+    assert_eq!(json["summary"]["updates_total"], 4);
+    assert_eq!(json["summary"]["updates_major"], 1);
+
+    assert_eq!(json["repos"][0]["repo"], "api.json");
+    assert_eq!(json["repos"][0]["updates_total"], 2);
+    assert_eq!(json["repos"][1]["repo"], "web.json");
+    assert_eq!(json["repos"][1]["updates_total"], 2);
+
+    let requests = &json["packages"][0];
+    assert_eq!(requests["package"], "requests");
+    assert_eq!(requests["repos"], 2);
+    assert_eq!(requests["updates"][0]["repo"], "api.json");
+    assert_eq!(requests["updates"][0]["current"], "2.31.0");
+    assert_eq!(requests["updates"][1]["repo"], "web.json");
+    assert_eq!(requests["updates"][1]["current"], "2.30.0");
+
+    let (stdout, stderr, code) = run(
+        &["merge-reports", &api, &web, "--output", "text"],
+        tmp.path(),
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(
+        stdout.contains("Merged 2 report(s): 4 update(s) (1 major"),
+        "stdout: {stdout}"
+    );
+    assert!(
+        stdout.contains("requests (python) in 2 repo(s)"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn merge_reports_rejects_a_non_update_report() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(
+        tmp.path().join("stats.json"),
+        r#"{"command": "cache-stats", "entries": 0}"#,
+    )
+    .unwrap();
+
+    let (_stdout, stderr, code) = run(&["merge-reports", "stats.json"], tmp.path());
+    assert_eq!(code, 2, "stderr: {stderr}");
+    assert!(stderr.contains("`cache-stats` report"), "stderr: {stderr}");
+}
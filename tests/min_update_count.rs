@@ -0,0 +1,176 @@
+//! Integration tests for `--min-update-count`.
+//!
+//! The update plan is computed without writing first. When it contains fewer
+//! than N updates, the run is a no-op: no files are written and `upd` exits 0.
+//! At or above the threshold the plan is applied as usual.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use wiremock::matchers::{method, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_env(args: &[&str], cwd: &Path, env: &[(&str, &str)]) -> (String, String, i32) {
+    let mut cmd = Command::new(upd_bin());
+    cmd.args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap());
+    for (k, v) in env {
+        cmd.env(k, v);
+    }
+    let output = cmd.output().expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+/// Serve a PyPI simple index advertising `version` for `package`.
+async fn mount_simple(server: &MockServer, package: &str, version: &str) {
+    let html = format!(
+        r#"<!DOCTYPE html><html><body>
+<a href="{package}-{version}.tar.gz">{package}-{version}.tar.gz</a>
+</body></html>"#
+    );
+    Mock::given(method("GET"))
+        .and(path_regex(format!(r"^/simple/{package}/?$")))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(html.into_bytes(), "text/html"))
+        .mount(server)
+        .await;
+}
+
+/// Two pending updates (`requests`, `flask`) against a threshold of three:
+/// nothing is written and the run exits 0.
+#[tokio::test]
+async fn apply_is_skipped_below_threshold() {
+    let server = MockServer::start().await;
+    mount_simple(&server, "requests", "1.0.1").await;
+    mount_simple(&server, "flask", "2.0.1").await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let original = "requests==1.0.0\nflask==2.0.0\n";
+    let req = tmp.path().join("requirements.txt");
+    fs::write(&req, original).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_env(
+        &[
+            "--apply",
+            "--no-cache",
+            "--output",
+            "text",
+            "--min-update-count",
+            "3",
+            &path_str,
+        ],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 0, "below threshold must exit 0; stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(&req).unwrap(),
+        original,
+        "below threshold must not write any files"
+    );
+    assert!(
+        stdout.contains("below --min-update-count 3"),
+        "expected a note explaining the skip; stdout: {stdout}"
+    );
+}
+
+/// A dry-run below the threshold is also a clean no-op rather than the usual
+/// exit 1 for pending updates.
+#[tokio::test]
+async fn check_below_threshold_exits_zero() {
+    let server = MockServer::start().await;
+    mount_simple(&server, "requests", "1.0.1").await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==1.0.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_env(
+        &[
+            "--check",
+            "--no-cache",
+            "--min-update-count",
+            "2",
+            &path_str,
+        ],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 0, "below threshold must exit 0; stderr: {stderr}");
+}
+
+/// Exactly meeting the threshold applies the plan. The previewed plan is
+/// written as is, so each package is looked up only once.
+#[tokio::test]
+async fn apply_proceeds_at_threshold() {
+    let server = MockServer::start().await;
+    mount_simple(&server, "requests", "1.0.1").await;
+    mount_simple(&server, "flask", "2.0.1").await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let req = tmp.path().join("requirements.txt");
+    fs::write(&req, "requests==1.0.0\nflask==2.0.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_env(
+        &[
+            "--apply",
+            "--no-cache",
+            "--min-update-count",
+            "2",
+            &path_str,
+        ],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 0, "applied run must exit 0; stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(&req).unwrap(),
+        "requests==1.0.1\nflask==2.0.1\n"
+    );
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2, "one lookup per package: {requests:?}");
+}
+
+/// The threshold counts only updates that pass the bump filters.
+#[tokio::test]
+async fn threshold_counts_only_filtered_updates() {
+    let server = MockServer::start().await;
+    mount_simple(&server, "requests", "2.0.0").await;
+    mount_simple(&server, "flask", "2.0.1").await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let original = "requests==1.0.0\nflask==2.0.0\n";
+    let req = tmp.path().join("requirements.txt");
+    fs::write(&req, original).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_env(
+        &[
+            "--apply",
+            "--no-cache",
+            "--max-bump",
+            "patch",
+            "--min-update-count",
+            "2",
+            &path_str,
+        ],
+        tmp.path(),
+        &[("UV_INDEX_URL", &server.uri())],
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(fs::read_to_string(&req).unwrap(), original);
+}
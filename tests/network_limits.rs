@@ -0,0 +1,219 @@
+//! Integration tests for `--timeout`, `--retries`,
+//! `--resolve-concurrency-per-host` and `--rate-limit`.
+//!
+//! These settings reach every registry client: `--retries` bounds how often a
+//! failing request is re-sent (0 sends it exactly once), `--timeout`
+//! abandons a request that takes longer than the given number of seconds,
+//! `--resolve-concurrency-per-host` caps the requests in flight to one host,
+//! and `--rate-limit` caps the requests sent per second.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use wiremock::matchers::{method, path_regex};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_index(args: &[&str], cwd: &Path, index: &str) -> (String, String, i32) {
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UV_INDEX_URL", index)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn write_project(dir: &Path) -> String {
+    fs::write(dir.join("requirements.txt"), "requests==1.0.0\n").unwrap();
+    dir.to_str().unwrap().to_string()
+}
+
+/// Count the requests the index saw for a package that always answers 500.
+async fn attempts_with_retries(retries: &str) -> usize {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/simple/requests/?$"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+    let (_stdout, stderr, code) = run_with_index(
+        &[
+            "--no-cache",
+            "--pypi-api",
+            "simple-only",
+            "--retries",
+            retries,
+            "--format",
+            "text",
+            &project,
+        ],
+        tmp.path(),
+        &server.uri(),
+    );
+    assert_ne!(code, 0, "a failing index is an error; stderr: {stderr}");
+
+    server.received_requests().await.unwrap_or_default().len()
+}
+
+#[tokio::test]
+async fn zero_retries_sends_each_request_once() {
+    assert_eq!(attempts_with_retries("0").await, 1);
+}
+
+#[tokio::test]
+async fn retries_bound_the_number_of_attempts() {
+    assert_eq!(attempts_with_retries("3").await, 4);
+}
+
+#[tokio::test]
+async fn timeout_abandons_a_slow_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/simple/requests/?$"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(20)))
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+    let started = Instant::now();
+    let (_stdout, stderr, code) = run_with_index(
+        &[
+            "--no-cache",
+            "--pypi-api",
+            "simple-only",
+            "--timeout",
+            "1",
+            "--retries",
+            "0",
+            "--format",
+            "text",
+            &project,
+        ],
+        tmp.path(),
+        &server.uri(),
+    );
+
+    assert!(
+        started.elapsed() < Duration::from_secs(10),
+        "--timeout 1 must not wait for the 20s response"
+    );
+    assert_ne!(code, 0, "a timed-out lookup is an error; stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("requirements.txt")).unwrap(),
+        "requests==1.0.0\n"
+    );
+}
+
+/// Answers every request with a slow 404 and records when each one arrived.
+#[derive(Clone, Default)]
+struct SlowArrivals(Arc<Mutex<Vec<Instant>>>);
+
+impl Respond for SlowArrivals {
+    fn respond(&self, _request: &Request) -> ResponseTemplate {
+        self.0.lock().unwrap().push(Instant::now());
+        ResponseTemplate::new(404).set_delay(Duration::from_millis(300))
+    }
+}
+
+/// Resolve two PyPI and two npm packages against one mock server standing in
+/// for both registries, and return the sorted request arrival times.
+async fn arrival_times(extra_args: &[&str]) -> Vec<Instant> {
+    let server = MockServer::start().await;
+    let arrivals = SlowArrivals::default();
+    Mock::given(method("GET"))
+        .respond_with(arrivals.clone())
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(
+        tmp.path().join("requirements.txt"),
+        "flask==1.0.0\nclick==7.0.0\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("package.json"),
+        r#"{"dependencies": {"left-pad": "1.0.0", "lodash": "4.0.0"}}"#,
+    )
+    .unwrap();
+
+    let mut args = vec![
+        "--no-cache",
+        "--pypi-api",
+        "simple-only",
+        "--retries",
+        "0",
+        "--format",
+        "text",
+    ];
+    args.extend_from_slice(extra_args);
+    args.push(tmp.path().to_str().unwrap());
+    let output = Command::new(upd_bin())
+        .args(&args)
+        .current_dir(tmp.path())
+        .env("UPD_CACHE_DIR", tmp.path().join(".cache").to_str().unwrap())
+        .env("UV_INDEX_URL", server.uri())
+        .env("NPM_REGISTRY", server.uri())
+        .output()
+        .expect("failed to run upd");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let mut times = arrivals.0.lock().unwrap().clone();
+    assert_eq!(times.len(), 4, "one request per package; stderr: {stderr}");
+    times.sort();
+    times
+}
+
+/// The smallest gap between the request arrivals of [`arrival_times`].
+async fn smallest_arrival_gap(extra_args: &[&str]) -> Duration {
+    arrival_times(extra_args)
+        .await
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .min()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn concurrency_per_host_serializes_requests_across_ecosystems() {
+    let gap = smallest_arrival_gap(&["--resolve-concurrency-per-host", "1"]).await;
+    assert!(
+        gap >= Duration::from_millis(250),
+        "a request started before the previous one finished (gap {gap:?})"
+    );
+}
+
+#[tokio::test]
+async fn requests_to_one_host_overlap_without_a_per_host_limit() {
+    let gap = smallest_arrival_gap(&[]).await;
+    assert!(
+        gap < Duration::from_millis(250),
+        "unlimited lookups should run in parallel (gap {gap:?})"
+    );
+}
+
+#[tokio::test]
+async fn rate_limit_spaces_requests_across_ecosystems() {
+    // Two requests fit the initial burst; the other two wait 0.5s each.
+    let times = arrival_times(&["--rate-limit", "2"]).await;
+    let span = times[times.len() - 1] - times[0];
+    assert!(
+        span >= Duration::from_millis(900),
+        "four requests at 2/s must span about a second (span {span:?})"
+    );
+}
@@ -0,0 +1,118 @@
+//! Integration tests for `--newest-only`.
+//!
+//! The update plan is computed without writing first, ranked major before
+//! minor before patch, and only the top N updates are written. The rest are
+//! reported as deferred.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "npm": {
+            "express": "4.21.2",
+            "lodash": "4.17.21",
+            "react": "18.3.1"
+        } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+// One patch, one major and one minor update, in that file order.
+const PACKAGE_JSON: &str = r#"{
+  "dependencies": {
+    "lodash": "4.17.20",
+    "react": "17.0.2",
+    "express": "4.17.0"
+  }
+}
+"#;
+
+#[test]
+fn applies_only_the_most_impactful_updates() {
+    let tmp = tempfile::tempdir().unwrap();
+    let manifest = tmp.path().join("package.json");
+    fs::write(&manifest, PACKAGE_JSON).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--format",
+            "text",
+            "--newest-only",
+            "2",
+            &path_str,
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let written = fs::read_to_string(&manifest).unwrap();
+    assert!(written.contains(r#""react": "18.3.1""#), "{written}");
+    assert!(written.contains(r#""express": "4.21.2""#), "{written}");
+    assert!(written.contains(r#""lodash": "4.17.20""#), "{written}");
+    assert!(
+        stdout
+            .lines()
+            .any(|line| line.contains("Deferred") && line.contains("lodash")),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn dry_run_reports_deferred_updates_in_json() {
+    let tmp = tempfile::tempdir().unwrap();
+    let manifest = tmp.path().join("package.json");
+    fs::write(&manifest, PACKAGE_JSON).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, _) = run_with_fixture(
+        &[
+            "--no-cache",
+            "--format",
+            "json",
+            "--newest-only",
+            "1",
+            &path_str,
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(fs::read_to_string(&manifest).unwrap(), PACKAGE_JSON);
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    let file = &report["files"][0];
+    let names = |key: &str| -> Vec<String> {
+        let mut names: Vec<String> = file[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["package"].as_str().unwrap().to_string())
+            .collect();
+        names.sort();
+        names
+    };
+    assert_eq!(names("updates"), vec!["react"]);
+    assert_eq!(names("deferred"), vec!["express", "lodash"]);
+    assert_eq!(report["summary"]["deferred"], 2);
+}
@@ -0,0 +1,103 @@
+//! `--no-config`: discovered config files are skipped entirely, so their
+//! ignore/pin rules do not apply, resolved through `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+const FIXTURE: &str = r#"{
+    "pypi": { "requests": "2.32.0", "flask": "2.3.3" }
+}"#;
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, FIXTURE).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn write_project(dir: &Path) -> String {
+    fs::write(
+        dir.join("requirements.txt"),
+        "requests==2.28.0\nflask==2.0.0\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join(".updrc.toml"),
+        "ignore = [\"requests\"]\n\n[pin]\nflask = \"2.0.0\"\n",
+    )
+    .unwrap();
+    dir.to_str().unwrap().to_string()
+}
+
+#[test]
+fn config_rules_apply_by_default() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = write_project(tmp.path());
+
+    let (_, stderr, code) = run_with_fixture(&["--apply", "--no-cache", &path], tmp.path());
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let content = fs::read_to_string(tmp.path().join("requirements.txt")).unwrap();
+    assert_eq!(content, "requests==2.28.0\nflask==2.0.0\n");
+}
+
+#[test]
+fn no_config_bypasses_ignore_and_pin() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = write_project(tmp.path());
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--no-config",
+            "--verbose",
+            "--format",
+            "text",
+            &path,
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let content = fs::read_to_string(tmp.path().join("requirements.txt")).unwrap();
+    assert_eq!(content, "requests==2.32.0\nflask==2.3.3\n");
+    assert!(
+        stderr.contains("Config files bypassed (--no-config)"),
+        "stderr: {stderr}"
+    );
+    assert!(!stderr.contains("Using config from"), "stderr: {stderr}");
+    assert!(!stdout.contains("Config files"), "stdout: {stdout}");
+}
+
+#[test]
+fn no_config_skips_malformed_config() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "flask==2.0.0\n").unwrap();
+    fs::write(tmp.path().join(".updrc.toml"), "ignore = [ broken\n").unwrap();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--no-cache", "--no-config", tmp.path().to_str().unwrap()],
+        tmp.path(),
+    );
+
+    // Exit 1 (updates available) rather than 4 (parse_error).
+    assert_eq!(code, 1, "stderr: {stderr}");
+    assert!(stdout.contains("\"latest\": \"2.3.3\""), "stdout: {stdout}");
+}
@@ -0,0 +1,76 @@
+//! `--no-parallel`: files are processed one at a time, so the report lists
+//! them in discovery order on every run. Resolved through
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+const FIXTURE: &str = r#"{
+    "pypi": { "requests": "2.32.3", "flask": "3.0.3" },
+    "npm": { "lodash": "4.17.21" }
+}"#;
+
+fn run(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, FIXTURE).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .env("NPM_REGISTRY", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[test]
+fn no_parallel_reports_files_in_discovery_order_on_every_run() {
+    let tmp = tempfile::tempdir().unwrap();
+    // Given out of alphabetical order, so the report order is the order the
+    // files were named in rather than a sort.
+    let files = [
+        (
+            "web/package.json",
+            r#"{"dependencies": {"lodash": "^4.17.20"}}"#,
+        ),
+        ("api/requirements.txt", "requests==2.31.0\n"),
+        ("worker/requirements.txt", "flask==2.0.0\n"),
+        ("cli/requirements.txt", "requests==2.30.0\nflask==2.3.0\n"),
+    ];
+    for (file, content) in files {
+        let path = tmp.path().join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    let paths: Vec<&str> = files.iter().map(|(file, _)| *file).collect();
+
+    let mut args = vec!["--no-parallel", "--no-cache", "--format", "json"];
+    args.extend(&paths);
+
+    let mut reports = Vec::new();
+    for _ in 0..3 {
+        let (stdout, stderr, code) = run(&args, tmp.path());
+        assert_eq!(code, 1, "pending updates; stderr: {stderr}");
+        let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+        let order: Vec<String> = json["files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|file| file["path"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(order, paths);
+        reports.push(json["files"].clone());
+    }
+    assert!(reports.windows(2).all(|pair| pair[0] == pair[1]));
+}
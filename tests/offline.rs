@@ -0,0 +1,97 @@
+//! `--offline`: version lookups are answered from the cache only. Packages
+//! with no cached version are reported as offline-skipped, not as errors.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_offline(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        // Any registry request would fail fast against this address.
+        .env("UV_INDEX_URL", "http://127.0.0.1:9/simple")
+        .env("PIP_INDEX_URL", "http://127.0.0.1:9/simple")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+/// Seed the version cache with a week-old flask entry; `requests` is absent.
+fn write_project(dir: &Path) -> String {
+    let cache = dir.join(".cache");
+    fs::create_dir_all(&cache).unwrap();
+    let week_ago = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        - 7 * 24 * 3600;
+    fs::write(
+        cache.join("versions.json"),
+        format!(r#"{{"pypi": {{"flask": {{"version": "3.0.3", "fetched_at": {week_ago}}}}}}}"#),
+    )
+    .unwrap();
+    let project = dir.join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("requirements.txt"),
+        "flask==2.0.0\nrequests==2.31.0\n",
+    )
+    .unwrap();
+    project.to_str().unwrap().to_string()
+}
+
+#[test]
+fn offline_uses_cached_versions_and_skips_the_rest() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, stderr, code) = run_offline(
+        &["--offline", "--check", "--format", "text", &project],
+        tmp.path(),
+    );
+
+    // Pending update from the cache; the uncached package is not an error.
+    assert_eq!(code, 1, "stdout: {stdout}\nstderr: {stderr}");
+    assert!(stdout.contains("3.0.3"), "{stdout}");
+    assert!(
+        stderr.contains("offline: no cached version for requests"),
+        "{stderr}"
+    );
+    assert!(!stderr.contains("error(s) occurred"), "{stderr}");
+    assert!(
+        stdout.contains("1 package(s) with no cached version (offline)"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn offline_json_reports_skips_apart_from_errors() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, stderr, _code) =
+        run_offline(&["--offline", "--format", "json", &project], tmp.path());
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    assert_eq!(report["summary"]["errors"], 0);
+    assert_eq!(report["summary"]["offline_skipped"], 1);
+    assert_eq!(report["summary"]["updates_total"], 1);
+    let file = &report["files"][0];
+    assert_eq!(file["errors"].as_array().unwrap().len(), 0);
+    assert!(
+        file["offline_skipped"][0]
+            .as_str()
+            .unwrap()
+            .contains("requests")
+    );
+}
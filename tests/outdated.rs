@@ -0,0 +1,108 @@
+//! `upd outdated`: read-only listing of dependencies with a newer version and
+//! the number of majors each is behind, resolved through
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+// django skipped 4.x entirely; flask only has a minor update.
+const FIXTURE: &str = r#"{
+    "pypi": {
+        "django": {"latest": "5.1.0", "versions": ["2.2.0", "3.2.0", "5.0.0", "5.1.0"]},
+        "requests": {"latest": "5.0.0", "versions": ["2.31.0", "3.0.0", "4.0.0", "5.0.0"]},
+        "flask": {"latest": "3.1.0", "versions": ["3.0.0", "3.1.0"]}
+    }
+}"#;
+
+const REQUIREMENTS: &str = "django==2.2.0\nrequests==3.0.0\nflask==3.0.0\n";
+
+fn run(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, FIXTURE).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[test]
+fn outdated_major_lists_only_major_gaps() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), REQUIREMENTS).unwrap();
+
+    let (stdout, stderr, code) = run(
+        &["outdated", "--major", "--no-cache", "--output", "text", "."],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 1, "stderr: {stderr}");
+    assert!(
+        stdout.contains("django 2.2.0 → 5.1.0 (2 majors behind: 2.x → 5.x)"),
+        "stdout: {stdout}"
+    );
+    assert!(
+        stdout.contains("requests 3.0.0 → 5.0.0 (2 majors behind: 3.x → 5.x)"),
+        "stdout: {stdout}"
+    );
+    assert!(!stdout.contains("flask"), "stdout: {stdout}");
+}
+
+#[test]
+fn outdated_reports_json_and_never_writes() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), REQUIREMENTS).unwrap();
+
+    let (stdout, stderr, code) = run(
+        &["outdated", "--apply", "--no-cache", "--output", "json", "."],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 1, "stderr: {stderr}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["command"], "outdated");
+    assert_eq!(json["summary"]["outdated"], 3);
+    assert_eq!(json["summary"]["behind_a_major"], 2);
+    let flask = json["packages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["package"] == "flask")
+        .unwrap();
+    assert_eq!(flask["bump"], "minor");
+    assert_eq!(flask["majors_behind"], 0);
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("requirements.txt")).unwrap(),
+        REQUIREMENTS
+    );
+}
+
+#[test]
+fn outdated_major_exits_zero_when_no_major_is_behind() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "flask==3.0.0\n").unwrap();
+
+    let (stdout, stderr, code) = run(
+        &["outdated", "--major", "--no-cache", "--output", "text", "."],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(
+        stdout.contains("no dependencies are behind by a major version"),
+        "stdout: {stdout}"
+    );
+}
@@ -2,7 +2,7 @@
 //!
 //! Covers:
 //!  - Error lines go to stderr in text mode, not stdout
-//!  - `--quiet` suppresses progress/summary on stdout
+//!  - `--quiet` replaces progress output with a one-line summary on stdout
 //!  - `--quiet --format json` still emits JSON on stdout (JSON is unaffected)
 //!  - Normal text run (no --quiet) still prints summary to stdout
 
@@ -147,10 +147,56 @@ fn quiet_does_not_silence_errors_on_stderr() {
         stderr.to_lowercase().contains("error"),
         "--quiet must not suppress error messages on stderr; stderr: {stderr}"
     );
-    assert!(
-        stdout.trim().is_empty(),
-        "--quiet must suppress non-error stdout even on error run; stdout: {stdout:?}"
+    assert_eq!(
+        stdout, "Scanned 1 file(s), 1 error(s)\n",
+        "--quiet must print only the summary line on stdout; stdout: {stdout:?}"
+    );
+}
+
+/// `--quiet` replaces the per-file update lines with one summary line, and
+/// `--check` still exits 1 for pending updates.
+#[test]
+fn quiet_check_prints_one_summary_line_and_keeps_exit_code() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "flask==2.0.0\n").unwrap();
+    let fixture_path = tmp.path().join("fixture.json");
+    fs::write(&fixture_path, r#"{ "pypi": { "flask": "3.0.3" } }"#).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_env(
+        &["-q", "--check", "--no-cache", "--output", "text", &path_str],
+        tmp.path(),
+        &[("UPD_REGISTRY_FIXTURE", fixture_path.to_str().unwrap())],
     );
+
+    assert_eq!(code, 1, "pending updates must exit 1; stderr: {stderr}");
+    assert_eq!(stdout, "Would update 1 package(s) in 1 file(s)\n");
+    assert!(stderr.is_empty(), "stderr: {stderr}");
+}
+
+/// The `--quiet` summary counts the files that would change, not every
+/// scanned file.
+#[test]
+fn quiet_summary_counts_only_changed_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "flask==2.0.0\n").unwrap();
+    fs::write(tmp.path().join("requirements-dev.txt"), "pytest==8.3.4\n").unwrap();
+    let fixture_path = tmp.path().join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "pypi": { "flask": "3.0.3", "pytest": "8.3.4" } }"#,
+    )
+    .unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_env(
+        &["-q", "--check", "--no-cache", "--output", "text", &path_str],
+        tmp.path(),
+        &[("UPD_REGISTRY_FIXTURE", fixture_path.to_str().unwrap())],
+    );
+
+    assert_eq!(code, 1, "pending updates must exit 1; stderr: {stderr}");
+    assert_eq!(stdout, "Would update 1 package(s) in 1 file(s)\n");
 }
 
 /// Registry errors appear on stderr, not stdout, during a text-mode update run.
@@ -273,3 +319,39 @@ fn verbose_python_scan_omits_crates_io_auth_message() {
         "verbose python-only scan must not mention crates.io; combined output:\n{combined}"
     );
 }
+
+/// `--log-level` logs go to stderr; stdout carries only the normal output.
+#[test]
+fn log_level_writes_logs_to_stderr_only() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("package.json"), b"{ BROKEN JSON }").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, _code) = run(
+        &[
+            "--dry-run",
+            "--no-cache",
+            "--format",
+            "json",
+            "--log-level",
+            "debug",
+            &path_str,
+        ],
+        tmp.path(),
+    );
+
+    assert!(
+        stderr.contains("DEBUG") && stderr.contains("processing"),
+        "file-processing debug log must appear on stderr; stderr: {stderr}"
+    );
+    assert!(
+        stderr.contains("package.json"),
+        "log span must name the file; stderr: {stderr}"
+    );
+    assert!(
+        !stdout.contains("DEBUG"),
+        "logs must NOT appear on stdout; stdout: {stdout}"
+    );
+    serde_json::from_str::<serde_json::Value>(&stdout)
+        .expect("stdout must still be a single JSON document");
+}
@@ -194,3 +194,71 @@ fn cli_parses_package_mixed_comma_and_repeated() {
         vec!["foo".to_string(), "bar".to_string(), "baz".to_string()]
     );
 }
+
+#[test]
+fn cli_only_is_an_alias_that_unions_with_package() {
+    use clap::Parser;
+    let cli = upd::cli::Cli::try_parse_from([
+        "upd",
+        "--only",
+        "foo",
+        "--package",
+        "bar",
+        "--only",
+        "baz,qux",
+    ])
+    .unwrap();
+    assert_eq!(cli.packages, ["foo", "bar", "baz", "qux"]);
+}
+
+/// `--only` updates the named package in every file and counts the others
+/// as unchanged, resolved through `UPD_REGISTRY_FIXTURE`.
+#[test]
+fn only_updates_the_named_package_across_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let fixture = tmp.path().join("fixture.json");
+    fs::write(
+        &fixture,
+        r#"{"pypi": {"requests": "2.32.3", "flask": "3.1.0"}}"#,
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("requirements.txt"),
+        "requests==2.28.0\nflask==2.0.0\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("requirements-dev.txt"),
+        "requests==2.30.0\n",
+    )
+    .unwrap();
+
+    let output = Command::new(upd_bin())
+        .args([
+            "--only",
+            "requests",
+            "--apply",
+            "--no-cache",
+            "--format",
+            "json",
+            tmp.path().to_str().unwrap(),
+        ])
+        .current_dir(tmp.path())
+        .env("UPD_CACHE_DIR", tmp.path().join(".cache"))
+        .env("UPD_REGISTRY_FIXTURE", &fixture)
+        .output()
+        .expect("failed to run upd");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(0), "stdout: {stdout}");
+
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["summary"]["updates_total"], 2);
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("requirements.txt")).unwrap(),
+        "requests==2.32.3\nflask==2.0.0\n"
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("requirements-dev.txt")).unwrap(),
+        "requests==2.32.3\n"
+    );
+}
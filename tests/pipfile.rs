@@ -0,0 +1,220 @@
+//! End-to-end Pipfile runs, resolved through `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path, fixture: &str) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, fixture).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const FIXTURE: &str = r#"{
+    "pypi": { "requests": "2.32.3", "flask": "3.0.3", "pytest": "8.3.2" },
+    "npm": { "lodash": "4.17.21" }
+}"#;
+
+const PIPFILE: &str = r#"[[source]]
+url = "https://pypi.org/simple"
+verify_ssl = true
+name = "pypi"
+
+[packages]
+requests = "==2.31.0"
+flask = {version = ">=2.0", extras = ["async"]}
+anything = "*"
+mylib = {git = "https://github.com/example/mylib.git", ref = "v1.0.0"}
+
+[dev-packages]
+pytest = "==8.0.0"
+"#;
+
+fn write_project(dir: &Path) {
+    fs::write(dir.join("Pipfile"), PIPFILE).unwrap();
+    fs::write(
+        dir.join("package.json"),
+        r#"{"dependencies": {"lodash": "^4.17.20"}}"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn pipfile_is_discovered_with_lang_python() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--no-cache",
+            "--format",
+            "json",
+            "--lang",
+            "python",
+            &path_str,
+        ],
+        tmp.path(),
+        FIXTURE,
+    );
+
+    assert_eq!(code, 1, "pending updates in dry-run; stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), 1, "only the Pipfile is in scope: {stdout}");
+    assert!(files[0]["path"].as_str().unwrap().ends_with("Pipfile"));
+
+    let mut updates: Vec<(String, String, u64)> = files[0]["updates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|u| {
+            (
+                u["package"].as_str().unwrap().to_string(),
+                u["latest"].as_str().unwrap().to_string(),
+                u["line"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    updates.sort();
+    assert_eq!(
+        updates,
+        vec![
+            ("flask".to_string(), "3.0".to_string(), 8),
+            ("pytest".to_string(), "8.3.2".to_string(), 13),
+            ("requests".to_string(), "2.32.3".to_string(), 7),
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("Pipfile")).unwrap(),
+        PIPFILE
+    );
+}
+
+#[test]
+fn pipfile_apply_keeps_wildcards_extras_and_git_refs() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_fixture(
+        &["--apply", "--no-cache", "--lang", "python", &path_str],
+        tmp.path(),
+        FIXTURE,
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let content = fs::read_to_string(tmp.path().join("Pipfile")).unwrap();
+    assert!(content.contains(r#"requests = "==2.32.3""#), "{content}");
+    assert!(
+        content.contains(r#"flask = {version = ">=3.0", extras = ["async"]}"#),
+        "{content}"
+    );
+    assert!(content.contains(r#"anything = "*""#), "{content}");
+    assert!(content.contains(r#"ref = "v1.0.0""#), "{content}");
+    assert!(content.contains(r#"pytest = "==8.3.2""#), "{content}");
+    assert!(
+        fs::read_to_string(tmp.path().join("package.json"))
+            .unwrap()
+            .contains("^4.17.20"),
+        "--lang python leaves package.json alone"
+    );
+}
+
+const PRIVATE_SOURCE_PIPFILE: &str = r#"[[source]]
+url = "${PRIVATE_INDEX}/simple"
+name = "private"
+
+[packages]
+internal-lib = "==1.0.0"
+"#;
+
+fn run_private_source(args: &[&str], cwd: &Path, index: &str) -> (String, String, i32) {
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("PRIVATE_INDEX", index)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+async fn mount_internal_lib(server: &wiremock::MockServer) {
+    use wiremock::matchers::{method, path_regex};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let html = r#"<html><body>
+<a href="internal-lib-1.2.0.tar.gz">internal-lib-1.2.0.tar.gz</a>
+</body></html>"#;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/simple/internal-lib/?$"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(html, "text/html"))
+        .mount(server)
+        .await;
+}
+
+/// A `[[source]]` URL is expanded from the environment and queried.
+#[tokio::test]
+async fn pipfile_private_source_resolves_from_its_index() {
+    let server = wiremock::MockServer::start().await;
+    mount_internal_lib(&server).await;
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("Pipfile"), PRIVATE_SOURCE_PIPFILE).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_private_source(
+        &["--no-cache", "--format", "json", &path_str],
+        tmp.path(),
+        &server.uri(),
+    );
+
+    assert_eq!(code, 1, "pending update; stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(report["files"][0]["updates"][0]["latest"], "1.2.0");
+}
+
+/// `--offline` covers the source index like every other registry: nothing
+/// is requested.
+#[tokio::test]
+async fn pipfile_private_source_makes_no_request_offline() {
+    let server = wiremock::MockServer::start().await;
+    mount_internal_lib(&server).await;
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("Pipfile"), PRIVATE_SOURCE_PIPFILE).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_private_source(
+        &["--offline", "--format", "json", &path_str],
+        tmp.path(),
+        &server.uri(),
+    );
+
+    assert_eq!(
+        code, 0,
+        "an offline miss is not a failure; stderr: {stderr}"
+    );
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests.is_empty(), "offline run sent {requests:?}");
+}
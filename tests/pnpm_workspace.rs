@@ -0,0 +1,79 @@
+//! End-to-end pnpm catalog runs, resolved through `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "npm": {
+            "react": { "latest": "19.0.0", "matching": { "^18.2.0": "18.3.1" } },
+            "lodash": "4.17.21"
+        } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const PNPM_WORKSPACE: &str = "packages:
+  - 'packages/*'
+
+catalog:
+  react: ^18.2.0
+  lodash: 4.17.20  # utilities
+";
+
+#[test]
+fn catalog_versions_are_updated_and_catalog_refs_skipped() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("pnpm-workspace.yaml"), PNPM_WORKSPACE).unwrap();
+    let app = tmp.path().join("packages").join("app");
+    fs::create_dir_all(&app).unwrap();
+    fs::write(
+        app.join("package.json"),
+        r#"{ "dependencies": { "react": "catalog:", "lodash": "catalog:" } }"#,
+    )
+    .unwrap();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--verbose",
+            "--format",
+            "text",
+            tmp.path().to_str().unwrap(),
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("pnpm-workspace.yaml")).unwrap(),
+        PNPM_WORKSPACE
+            .replace("^18.2.0", "^18.3.1")
+            .replace("4.17.20", "4.17.21")
+    );
+    assert!(
+        stdout.contains("(pnpm catalog)"),
+        "catalog: references must be reported as skipped; stdout: {stdout}"
+    );
+    assert!(!stderr.contains("Error"), "stderr: {stderr}");
+}
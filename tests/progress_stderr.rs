@@ -0,0 +1,83 @@
+//! `--progress-stderr`: human-readable text goes to stderr so stdout carries
+//! only the structured output, even when both are requested. Resolved through
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, r#"{"pypi": {"requests": "2.32.3"}}"#).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[test]
+fn json_on_stdout_and_progress_on_stderr() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==2.31.0\n").unwrap();
+
+    let (stdout, stderr, code) = run(
+        &[
+            "--format",
+            "json",
+            "--progress-stderr",
+            "--no-cache",
+            "--verbose",
+            ".",
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 1, "pending updates; stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stdout);
+    assert_eq!(report["files"][0]["updates"][0]["latest"], "2.32.3");
+    assert!(stderr.contains("Processed: "), "stderr: {stderr}");
+    assert!(stderr.contains("requests"), "stderr: {stderr}");
+    assert!(stderr.contains("2.32.3"), "stderr: {stderr}");
+}
+
+#[test]
+fn text_output_moves_to_stderr() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==2.31.0\n").unwrap();
+
+    let (stdout, stderr, code) = run(
+        &["--output", "text", "--progress-stderr", "--no-cache", "."],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 1, "pending updates; stderr: {stderr}");
+    assert!(stdout.is_empty(), "stdout: {stdout}");
+    assert!(stderr.contains("2.32.3"), "stderr: {stderr}");
+}
+
+#[test]
+fn without_the_flag_json_mode_prints_no_progress() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==2.31.0\n").unwrap();
+
+    let (stdout, stderr, _code) = run(
+        &["--format", "json", "--no-cache", "--verbose", "."],
+        tmp.path(),
+    );
+
+    assert!(serde_json::from_str::<serde_json::Value>(&stdout).is_ok());
+    assert!(!stderr.contains("Processed: "), "stderr: {stderr}");
+}
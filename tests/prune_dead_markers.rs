@@ -0,0 +1,91 @@
+//! `--prune-dead-markers`: pyproject requirements whose environment markers
+//! contradict `requires-python` are reported, never removed. Registries
+//! resolve from `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{"pypi": {"tomli": "2.0.1", "requests": "2.32.3"}}"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const PYPROJECT: &str = r#"[project]
+name = "demo"
+version = "0.1.0"
+requires-python = ">=3.11"
+dependencies = [
+    "tomli>=2.0.1; python_version < \"3.11\"",
+    "requests>=2.32.3",
+]
+"#;
+
+fn write_project(dir: &Path) -> String {
+    let project = dir.join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(project.join("pyproject.toml"), PYPROJECT).unwrap();
+    project.to_str().unwrap().to_string()
+}
+
+#[test]
+fn prune_dead_markers_reports_contradicting_marker_without_deleting() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--format",
+            "text",
+            "--prune-dead-markers",
+            &project,
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let output = format!("{stdout}{stderr}");
+    assert!(
+        output.contains(
+            r#"'tomli>=2.0.1; python_version < "3.11"' is dead: marker 'python_version < "3.11"' can never match requires-python '>=3.11'"#
+        ),
+        "{output}"
+    );
+    assert!(!output.contains("'requests>=2.32.3' is dead"), "{output}");
+    let content = fs::read_to_string(tmp.path().join("project/pyproject.toml")).unwrap();
+    assert_eq!(content, PYPROJECT);
+}
+
+#[test]
+fn dead_markers_are_not_reported_without_flag() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, stderr, code) =
+        run_with_fixture(&["--no-cache", "--format", "text", &project], tmp.path());
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(!format!("{stdout}{stderr}").contains("is dead"));
+}
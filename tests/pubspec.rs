@@ -0,0 +1,108 @@
+//! End-to-end pubspec.yaml runs, resolved through `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "pub": {
+            "http": { "latest": "2.0.0", "matching": { "^1.1.0": "1.2.2" } },
+            "path": "1.9.0",
+            "lints": "4.0.0"
+        } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const PUBSPEC: &str = "name: my_app
+
+environment:
+  sdk: ^3.4.0
+
+dependencies:
+  flutter:
+    sdk: flutter
+  http: ^1.1.0
+  path: 1.8.3
+  local_lib:
+    path: ../local_lib
+
+dev_dependencies:
+  lints: ^3.0.0 # analysis rules
+";
+
+#[test]
+fn pubspec_dependencies_are_updated_in_place() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("pubspec.yaml"), PUBSPEC).unwrap();
+    fs::write(tmp.path().join(".updrc.toml"), "ignore = [\"lints\"]\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--apply", "--no-cache", "--format", "json", &path_str],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("pubspec.yaml")).unwrap(),
+        PUBSPEC
+            .replace("http: ^1.1.0", "http: ^1.2.2")
+            .replace("path: 1.8.3", "path: 1.9.0")
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let file = &report["files"][0];
+    assert_eq!(file["file_type"], "pubspec");
+    assert_eq!(file["lang"], "dart");
+    assert_eq!(file["updates"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn lang_dart_limits_the_run_to_pubspecs() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("pubspec.yaml"), PUBSPEC).unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "flask==2.0.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--lang",
+            "dart",
+            "--no-cache",
+            "--format",
+            "json",
+            &path_str,
+        ],
+        tmp.path(),
+    );
+
+    // Dry run with pending updates.
+    assert_eq!(code, 1, "stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), 1);
+    assert!(
+        files[0]["path"].as_str().unwrap().ends_with("pubspec.yaml"),
+        "{files:?}"
+    );
+}
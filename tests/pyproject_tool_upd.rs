@@ -0,0 +1,107 @@
+//! `[tool.upd]` in `pyproject.toml` configures ignore/pin rules for the same
+//! project whose dependencies it declares. Registries resolve from
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{"pypi": {"requests": "2.32.3", "flask": "3.1.0", "django": "5.1.0"}}"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const PYPROJECT: &str = r#"[project]
+name = "demo"
+version = "0.1.0"
+dependencies = [
+    "requests>=2.28.0",
+    "flask>=2.0.0",
+    "django>=4.2.0",
+]
+
+[tool.upd]
+ignore = ["django"]
+
+[tool.upd.pin]
+flask = "2.3.0"
+"#;
+
+#[test]
+fn tool_upd_pins_and_ignores_apply_to_pyproject_dependencies() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = tmp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(project.join("pyproject.toml"), PYPROJECT).unwrap();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--format",
+            "text",
+            project.to_str().unwrap(),
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stdout: {stdout}\nstderr: {stderr}");
+    let content = fs::read_to_string(project.join("pyproject.toml")).unwrap();
+    assert!(content.contains(r#""requests>=2.32.3""#), "{content}");
+    assert!(content.contains(r#""flask>=2.3.0""#), "{content}");
+    assert!(content.contains(r#""django>=4.2.0""#), "{content}");
+    assert!(
+        content
+            .ends_with("[tool.upd]\nignore = [\"django\"]\n\n[tool.upd.pin]\nflask = \"2.3.0\"\n"),
+        "{content}"
+    );
+}
+
+#[test]
+fn explicit_config_replaces_tool_upd() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = tmp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(project.join("pyproject.toml"), PYPROJECT).unwrap();
+    let explicit = tmp.path().join("explicit.toml");
+    fs::write(&explicit, "ignore = [\"requests\"]\n").unwrap();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--format",
+            "text",
+            "--config",
+            explicit.to_str().unwrap(),
+            project.to_str().unwrap(),
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stdout: {stdout}\nstderr: {stderr}");
+    let content = fs::read_to_string(project.join("pyproject.toml")).unwrap();
+    assert!(content.contains(r#""requests>=2.28.0""#), "{content}");
+    assert!(content.contains(r#""flask>=3.1.0""#), "{content}");
+    assert!(content.contains(r#""django>=5.1.0""#), "{content}");
+}
@@ -0,0 +1,72 @@
+//! A `[pin]` range such as `attrs = ">=23.1,<24"` resolves to the newest
+//! version inside it in every Python format, resolved through
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+const FIXTURE: &str = r#"{
+    "pypi": {
+        "attrs": { "latest": "24.2.0", "matching": { ">=23.1,<24": "23.2.0" } }
+    }
+}"#;
+
+/// Write `manifest` as `name` next to a range pin for `attrs`, run
+/// `upd --apply` and return the rewritten manifest.
+fn apply_with_range_pin(name: &str, manifest: &str) -> String {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    fs::write(dir.join(name), manifest).unwrap();
+    fs::write(dir.join(".updrc.toml"), "[pin]\nattrs = \">=23.1,<24\"\n").unwrap();
+    let fixture_path = dir.join("fixture.json");
+    fs::write(&fixture_path, FIXTURE).unwrap();
+
+    let output = Command::new(upd_bin())
+        .args(["--apply", "--no-cache", dir.to_str().unwrap()])
+        .current_dir(dir)
+        .env("UPD_CACHE_DIR", dir.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    let stderr = String::from_utf8(output.stderr).expect("stderr not UTF-8");
+    assert_eq!(output.status.code(), Some(0), "stderr: {stderr}");
+    fs::read_to_string(dir.join(name)).unwrap()
+}
+
+#[test]
+fn range_pin_resolves_in_requirements_txt() {
+    let content = apply_with_range_pin("requirements.txt", "attrs==23.1.0\n");
+    assert_eq!(content, "attrs==23.2.0\n");
+}
+
+#[test]
+fn range_pin_resolves_in_setup_py() {
+    let content = apply_with_range_pin(
+        "setup.py",
+        "from setuptools import setup\n\nsetup(install_requires=[\"attrs==23.1.0\"])\n",
+    );
+    assert_eq!(
+        content,
+        "from setuptools import setup\n\nsetup(install_requires=[\"attrs==23.2.0\"])\n"
+    );
+}
+
+#[test]
+fn range_pin_resolves_in_pipfile() {
+    let content = apply_with_range_pin("Pipfile", "[packages]\nattrs = \"==23.1.0\"\n");
+    assert_eq!(content, "[packages]\nattrs = \"==23.2.0\"\n");
+}
+
+#[test]
+fn range_pin_resolves_in_environment_yml() {
+    let content = apply_with_range_pin(
+        "environment.yml",
+        "dependencies:\n  - pip:\n      - attrs==23.1.0\n",
+    );
+    assert_eq!(content, "dependencies:\n  - pip:\n      - attrs==23.2.0\n");
+}
@@ -0,0 +1,188 @@
+//! Whole-run tests driven by `UPD_REGISTRY_FIXTURE`.
+//!
+//! The fixture replaces every registry with a pre-seeded in-memory map, so a
+//! full `upd` run across several ecosystems resolves deterministically
+//! without wiremock or network access.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path, fixture: &str) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, fixture).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        // Any request that escaped the fixture would fail against these.
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .env("NPM_REGISTRY", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const FIXTURE: &str = r#"{
+    "pypi": { "requests": "2.32.3", "flask": "3.0.0" },
+    "npm": { "lodash": "4.17.21" },
+    "crates.io": { "serde": "1.0.210" }
+}"#;
+
+fn write_project(dir: &Path) {
+    fs::write(
+        dir.join("requirements.txt"),
+        "requests==2.31.0\nflask==3.0.0\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("package.json"),
+        r#"{
+  "name": "demo",
+  "dependencies": {
+    "lodash": "^4.17.20"
+  }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        r#"[package]
+name = "demo"
+version = "0.1.0"
+
+[dependencies]
+serde = "1.0.200"
+"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn fixture_drives_a_full_run_across_ecosystems() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--no-cache", "--format", "json", &path_str],
+        tmp.path(),
+        FIXTURE,
+    );
+
+    assert_eq!(code, 1, "pending updates in dry-run; stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(report["summary"]["errors"], 0, "{stdout}");
+    assert_eq!(report["summary"]["updates_total"], 3, "{stdout}");
+
+    let mut updates: Vec<(String, String)> = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|f| f["updates"].as_array().unwrap().clone())
+        .map(|u| {
+            (
+                u["package"].as_str().unwrap().to_string(),
+                u["latest"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    updates.sort();
+    assert_eq!(
+        updates,
+        vec![
+            ("lodash".to_string(), "4.17.21".to_string()),
+            ("requests".to_string(), "2.32.3".to_string()),
+            ("serde".to_string(), "1.0.210".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn fixture_apply_writes_every_file_type() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) =
+        run_with_fixture(&["--apply", "--no-cache", &path_str], tmp.path(), FIXTURE);
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let read = |name: &str| fs::read_to_string(tmp.path().join(name)).unwrap();
+    assert!(read("requirements.txt").contains("requests==2.32.3"));
+    assert!(read("package.json").contains("\"lodash\": \"^4.17.21\""));
+    assert!(read("Cargo.toml").contains("serde = \"1.0.210\""));
+}
+
+#[test]
+fn package_missing_from_fixture_is_an_error() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "unknown-pkg==1.0.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_fixture(
+        &["--no-cache", "--format", "text", &path_str],
+        tmp.path(),
+        FIXTURE,
+    );
+
+    assert_eq!(code, 2, "stderr: {stderr}");
+    assert!(stderr.contains("Package not found"), "stderr: {stderr}");
+}
+
+#[test]
+fn fixture_updates_workflow_actions_and_sha_pins() {
+    let tmp = tempfile::tempdir().unwrap();
+    let workflows = tmp.path().join(".github/workflows");
+    fs::create_dir_all(&workflows).unwrap();
+    fs::write(
+        workflows.join("ci.yml"),
+        "jobs:\n  build:\n    steps:\n      \
+         - uses: actions/checkout@a5ac7e51b28d7f9f3091645916e8170a8b5cbc47 # v3.1.0\n      \
+         - uses: actions/setup-python@v4\n      \
+         - uses: ./local-action\n",
+    )
+    .unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==2.31.0\n").unwrap();
+    let fixture = r#"{
+        "pypi": { "requests": "2.32.3" },
+        "github-releases": {
+            "actions/checkout": {
+                "latest": "v4.2.2",
+                "commits": { "v4.2.2": "11bd71901bbe5b1630ceea73d27597364c9af683" }
+            },
+            "actions/setup-python": "v5.3.0"
+        }
+    }"#;
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_fixture(
+        &["--apply", "--no-cache", "--lang", "actions", &path_str],
+        tmp.path(),
+        fixture,
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(workflows.join("ci.yml")).unwrap(),
+        "jobs:\n  build:\n    steps:\n      \
+         - uses: actions/checkout@11bd71901bbe5b1630ceea73d27597364c9af683 # v4.2.2\n      \
+         - uses: actions/setup-python@v5\n      \
+         - uses: ./local-action\n"
+    );
+    // --lang actions leaves other ecosystems alone.
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("requirements.txt")).unwrap(),
+        "requests==2.31.0\n"
+    );
+}
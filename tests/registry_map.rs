@@ -0,0 +1,96 @@
+//! `--registry-map`: one flag to point ecosystems at a proxy such as
+//! Artifactory, taking precedence over per-tool environment variables.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_env(args: &[&str], cwd: &Path, env: &[(&str, &str)]) -> (String, String, i32) {
+    let mut cmd = Command::new(upd_bin());
+    cmd.args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap());
+    for (k, v) in env {
+        cmd.env(k, v);
+    }
+    let output = cmd.output().expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[tokio::test]
+async fn mapped_registries_override_environment_detection() {
+    let server = MockServer::start().await;
+    let html = r#"<!DOCTYPE html><html><body>
+<a href="requests-2.32.3.tar.gz">requests-2.32.3.tar.gz</a>
+</body></html>"#;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/pypi/simple/requests/?$"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(html.as_bytes(), "text/html"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/packagist/p2/monolog/monolog.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"packages": {"monolog/monolog": [{"version": "3.7.0"}, {"version": "2.10.0"}]}}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==2.31.0\n").unwrap();
+    fs::write(
+        tmp.path().join("composer.json"),
+        r#"{"require": {"monolog/monolog": "2.9.0"}}"#,
+    )
+    .unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+    let map = format!("python={0}/pypi/simple,php={0}/packagist", server.uri());
+
+    // The environment points PyPI at a closed port; the map must win.
+    let (stdout, stderr, code) = run_with_env(
+        &[
+            "--apply",
+            "--no-cache",
+            "--output",
+            "text",
+            "--registry-map",
+            &map,
+            &path_str,
+        ],
+        tmp.path(),
+        &[("UV_INDEX_URL", "http://127.0.0.1:9/simple")],
+    );
+
+    assert_eq!(code, 0, "stdout: {stdout}\nstderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("requirements.txt")).unwrap(),
+        "requests==2.32.3\n"
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("composer.json")).unwrap(),
+        r#"{"require": {"monolog/monolog": "3.7.0"}}"#
+    );
+}
+
+#[test]
+fn unsupported_ecosystem_is_a_parse_error() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (_, stderr, code) = run_with_env(
+        &["--registry-map", "docker=https://art.example/docker"],
+        tmp.path(),
+        &[],
+    );
+
+    assert_eq!(code, 4, "{stderr}");
+    assert!(stderr.contains("unknown ecosystem 'docker'"), "{stderr}");
+}
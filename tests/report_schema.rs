@@ -0,0 +1,50 @@
+//! `upd schema --report` describes what `--format json` actually emits.
+
+use std::fs;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+#[test]
+fn update_report_validates_against_report_schema() {
+    let output = Command::new(upd_bin())
+        .args(["schema", "--report"])
+        .output()
+        .expect("failed to run upd");
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let validator = jsonschema::draft202012::new(&schema).expect("valid Draft 2020-12 schema");
+
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(
+        tmp.path().join("requirements.txt"),
+        "requests==2.31.0\nflask==3.0.0\n",
+    )
+    .unwrap();
+    let fixture_path = tmp.path().join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "pypi": { "requests": "2.32.3", "flask": "3.0.0" } }"#,
+    )
+    .unwrap();
+
+    let output = Command::new(upd_bin())
+        .args(["--no-cache", "--format", "json"])
+        .arg(tmp.path())
+        .current_dir(tmp.path())
+        .env("UPD_CACHE_DIR", tmp.path().join(".cache"))
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(report["schema_version"], 1);
+    assert_eq!(report["files"][0]["updates"][0]["package"], "requests");
+    let errors: Vec<String> = validator
+        .iter_errors(&report)
+        .map(|e| format!("{}: {}", e.instance_path(), e))
+        .collect();
+    assert!(errors.is_empty(), "{errors:?}");
+}
@@ -0,0 +1,110 @@
+//! `-c` constraints files are read-only ceilings: requirements files that
+//! reference them (directly or through `-r`) are bumped no further than the
+//! constraint allows, and the constraints file itself is never rewritten.
+//! Registries resolve from `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{"pypi": {
+            "flask": {"latest": "3.1.0", "matching": {"<3.1": "3.0.3"}},
+            "requests": {"latest": "2.32.3", "matching": {"==2.28.0": "2.28.0"}}
+        }}"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[test]
+fn constraints_file_is_left_untouched() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = tmp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("requirements.txt"),
+        "-c requirements-constraints.txt\nrequests==2.28.0\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("requirements-constraints.txt"),
+        "requests==2.28.0\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--format",
+            "text",
+            project.to_str().unwrap(),
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stdout: {stdout}\nstderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(project.join("requirements-constraints.txt")).unwrap(),
+        "requests==2.28.0\n"
+    );
+    assert!(!stdout.contains("requirements-constraints.txt"), "{stdout}");
+}
+
+#[test]
+fn included_requirement_is_capped_by_including_files_constraints() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = tmp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("requirements.txt"),
+        "-r requirements-base.txt\n-c constraints.txt\nrequests==2.28.0\n",
+    )
+    .unwrap();
+    fs::write(project.join("requirements-base.txt"), "flask==2.3.0\n").unwrap();
+    fs::write(project.join("constraints.txt"), "flask<3.1\n").unwrap();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--format",
+            "text",
+            project.to_str().unwrap(),
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stdout: {stdout}\nstderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(project.join("requirements-base.txt")).unwrap(),
+        "flask==3.0.3\n"
+    );
+    assert_eq!(
+        fs::read_to_string(project.join("requirements.txt")).unwrap(),
+        "-r requirements-base.txt\n-c constraints.txt\nrequests==2.32.3\n"
+    );
+    assert_eq!(
+        fs::read_to_string(project.join("constraints.txt")).unwrap(),
+        "flask<3.1\n"
+    );
+}
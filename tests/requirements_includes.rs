@@ -0,0 +1,169 @@
+//! Requirements files reached through `-r` includes are updated and reported
+//! like discovered ones. They are installed together, so a package they
+//! declare at different versions is reported as a conflict on the including
+//! file, and `upd align` aligns the included files as well. Registries
+//! resolve from `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{"pypi": {"flask": "3.1.0", "requests": "2.32.3"}}"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+/// `requirements.txt` includes `base.txt` and `web.txt`, which pin `flask`
+/// at different versions; `requests` agrees.
+fn write_project(dir: &Path) -> String {
+    let project = dir.join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("requirements.txt"),
+        "-r base.txt\n-r web.txt\n",
+    )
+    .unwrap();
+    fs::write(project.join("base.txt"), "flask==2.0.0\nrequests==2.32.3\n").unwrap();
+    fs::write(project.join("web.txt"), "flask==2.3.0\nrequests==2.32.3\n").unwrap();
+    project.to_str().unwrap().to_string()
+}
+
+#[test]
+fn diverging_versions_across_includes_are_reported() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, stderr, _code) = run_with_fixture(
+        &[
+            "--no-cache",
+            "--format",
+            "json",
+            "--lang",
+            "python",
+            &project,
+        ],
+        tmp.path(),
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    let warnings = report["files"][0]["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1, "stdout: {stdout}");
+    assert_eq!(
+        warnings[0],
+        "flask is declared at different versions across the -r includes of requirements.txt: \
+         base.txt (2.0.0), web.txt (2.3.0); run `upd align` to align them"
+    );
+    assert_eq!(report["summary"]["warnings"], 1);
+}
+
+#[test]
+fn align_reaches_included_requirements_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["align", "--apply", "--output", "text", &project],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stdout: {stdout}\nstderr: {stderr}");
+    let project = Path::new(&project);
+    assert_eq!(
+        fs::read_to_string(project.join("base.txt")).unwrap(),
+        "flask==2.3.0\nrequests==2.32.3\n"
+    );
+    assert_eq!(
+        fs::read_to_string(project.join("web.txt")).unwrap(),
+        "flask==2.3.0\nrequests==2.32.3\n"
+    );
+}
+
+#[test]
+fn included_files_are_updated_and_reported_separately() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = tmp.path().join("project");
+    fs::create_dir_all(project.join("reqs")).unwrap();
+    // Two roots share reqs/common.txt, which includes the root back (a
+    // cycle); constraints.txt is only ever read.
+    fs::write(
+        project.join("requirements.txt"),
+        "-r reqs/common.txt\n-c constraints.txt\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("requirements-dev.txt"),
+        "--requirement reqs/common.txt\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("reqs/common.txt"),
+        "-r ../requirements.txt\nrequests==2.31.0\n",
+    )
+    .unwrap();
+    fs::write(project.join("constraints.txt"), "flask==2.0.0\n").unwrap();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--no-parallel",
+            "--format",
+            "json",
+            project.to_str().unwrap(),
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    let files: Vec<&str> = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| file["path"].as_str().unwrap())
+        .collect();
+    let common: Vec<&&str> = files
+        .iter()
+        .filter(|path| path.ends_with("common.txt"))
+        .collect();
+    assert_eq!(common.len(), 1, "processed once: {files:?}");
+    assert!(!files.iter().any(|path| path.ends_with("constraints.txt")));
+
+    let common_report = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["path"].as_str().unwrap().ends_with("common.txt"))
+        .unwrap();
+    assert_eq!(common_report["updates"][0]["package"], "requests");
+    assert_eq!(common_report["updates"][0]["latest"], "2.32.3");
+
+    assert_eq!(
+        fs::read_to_string(project.join("reqs/common.txt")).unwrap(),
+        "-r ../requirements.txt\nrequests==2.32.3\n"
+    );
+    assert_eq!(
+        fs::read_to_string(project.join("constraints.txt")).unwrap(),
+        "flask==2.0.0\n"
+    );
+}
@@ -0,0 +1,83 @@
+//! `--respect-msrv`: crate versions needing a newer Rust than the project's
+//! `rust-version` are skipped. Registries resolve from `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{
+            "crates.io": {
+                "clap": {
+                    "latest": "4.5.20",
+                    "versions": ["4.4.0", "4.4.18", "4.5.20"],
+                    "rust_versions": { "4.4.18": "1.70", "4.5.20": "1.74" }
+                }
+            }
+        }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn write_project(dir: &Path) -> String {
+    let project = dir.join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nrust-version = \"1.72\"\n\n[dependencies]\nclap = \"4.4.0\"\n",
+    )
+    .unwrap();
+    project.to_str().unwrap().to_string()
+}
+
+#[test]
+fn respect_msrv_applies_newest_compatible_version() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--apply", "--no-cache", "--respect-msrv", &project],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let content = fs::read_to_string(tmp.path().join("project/Cargo.toml")).unwrap();
+    assert!(content.contains(r#"clap = "4.4.18""#), "{content}");
+    let output = format!("{stdout}{stderr}");
+    assert!(
+        output.contains("clap: 4.5.20 requires Rust 1.74 but the project targets 1.72"),
+        "{output}"
+    );
+}
+
+#[test]
+fn without_respect_msrv_the_latest_version_is_applied() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(tmp.path());
+
+    let (_stdout, stderr, code) =
+        run_with_fixture(&["--apply", "--no-cache", &project], tmp.path());
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let content = fs::read_to_string(tmp.path().join("project/Cargo.toml")).unwrap();
+    assert!(content.contains(r#"clap = "4.5.20""#), "{content}");
+}
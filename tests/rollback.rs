@@ -0,0 +1,118 @@
+//! `--backup` and `upd rollback`: an applied run keeps a `.<name>.upd.bak`
+//! copy of each file it changes, and `rollback` moves the copies back.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "pypi": { "requests": { "latest": "2.32.3" }, "flask": { "latest": "3.1.0" } } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[test]
+fn rollback_restores_the_last_backup_run() {
+    let tmp = tempfile::tempdir().unwrap();
+    let requirements = tmp.path().join("requirements.txt");
+    fs::write(&requirements, "requests==2.28.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--backup",
+            "--no-cache",
+            "--output",
+            "text",
+            &path_str,
+        ],
+        tmp.path(),
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(&requirements).unwrap(),
+        "requests==2.32.3\n"
+    );
+    let backup = tmp.path().join(".requirements.txt.upd.bak");
+    assert_eq!(fs::read_to_string(&backup).unwrap(), "requests==2.28.0\n");
+
+    let (stdout, stderr, code) =
+        run_with_fixture(&["rollback", "--output", "json", &path_str], tmp.path());
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    assert_eq!(report["restored"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        fs::read_to_string(&requirements).unwrap(),
+        "requests==2.28.0\n"
+    );
+    assert!(!backup.exists(), "rollback consumes the backup");
+
+    let (stdout, stderr, code) =
+        run_with_fixture(&["rollback", "--output", "text", &path_str], tmp.path());
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(stdout.contains("Nothing to restore."), "{stdout}");
+}
+
+#[test]
+fn a_new_backup_run_replaces_older_backups() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==2.28.0\n").unwrap();
+    let nested = tmp.path().join("web");
+    fs::create_dir(&nested).unwrap();
+    fs::write(nested.join("requirements.txt"), "flask==3.1.0\n").unwrap();
+    // Left over from an earlier run that changed web/requirements.txt.
+    let stale = nested.join(".requirements.txt.upd.bak");
+    fs::write(&stale, "flask==2.0.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_, stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--backup",
+            "--no-cache",
+            "--output",
+            "text",
+            &path_str,
+        ],
+        tmp.path(),
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(!stale.exists(), "the previous run's backup is discarded");
+    assert!(tmp.path().join(".requirements.txt.upd.bak").exists());
+}
+
+#[test]
+fn dry_run_writes_no_backup() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==2.28.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_, stderr, _) = run_with_fixture(
+        &["--backup", "--no-cache", "--output", "text", &path_str],
+        tmp.path(),
+    );
+    assert!(
+        !tmp.path().join(".requirements.txt.upd.bak").exists(),
+        "{stderr}"
+    );
+}
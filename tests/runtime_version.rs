@@ -0,0 +1,68 @@
+//! End-to-end `.nvmrc` / `.python-version` runs, resolved through
+//! `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "runtime": {
+            "node": { "latest": "23.4.0", "matching": { "20": "20.18.1" } },
+            "python": { "latest": "3.13.1", "matching": { "3": "3.13.1" } }
+        } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[test]
+fn runtime_versions_stay_within_major_and_keep_formatting() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join(".nvmrc"), "v20.11.0\n").unwrap();
+    fs::write(tmp.path().join(".python-version"), "3.12\nsystem\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--apply", "--no-cache", "--format", "json", &path_str],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join(".nvmrc")).unwrap(),
+        "v20.18.1\n"
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join(".python-version")).unwrap(),
+        "3.13\nsystem\n"
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    assert_eq!(report["summary"]["errors"], 0);
+    let file_types: Vec<&str> = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| file["file_type"].as_str().unwrap())
+        .collect();
+    assert!(file_types.contains(&"nvmrc"), "{file_types:?}");
+    assert!(file_types.contains(&"python_version"), "{file_types:?}");
+}
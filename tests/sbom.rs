@@ -0,0 +1,171 @@
+//! `upd sbom` end-to-end: manifests in, CycloneDX/SPDX JSON out. No network.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+/// One manifest per ecosystem; `requests` is declared twice.
+fn write_project(dir: &Path) {
+    let project = dir.join("project");
+    fs::create_dir_all(project.join("web")).unwrap();
+    fs::write(
+        project.join("requirements.txt"),
+        "requests==2.31.0\nDjango_Filter>=23.5\n",
+    )
+    .unwrap();
+    fs::write(project.join("web/requirements.txt"), "requests==2.31.0\n").unwrap();
+    fs::write(
+        project.join("web/package.json"),
+        r#"{"dependencies": {"@angular/core": "^17.0.0", "lodash": "4.17.21"}}"#,
+    )
+    .unwrap();
+    fs::write(
+        project.join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = \"1.0.210\"\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("go.mod"),
+        "module example.com/demo\n\ngo 1.22\n\nrequire golang.org/x/net v0.30.0\n",
+    )
+    .unwrap();
+}
+
+fn purls(components: &[serde_json::Value], key: &str) -> Vec<String> {
+    components
+        .iter()
+        .map(|c| c[key].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn sbom_cyclonedx_lists_every_declared_dependency() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+
+    let (stdout, stderr, code) = run(&["sbom", "bom.json", "project"], tmp.path());
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(stdout.contains("6 component(s)"), "stdout: {stdout}");
+
+    let bom: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(tmp.path().join("bom.json")).unwrap()).unwrap();
+    assert_eq!(bom["bomFormat"], "CycloneDX");
+    assert_eq!(bom["specVersion"], "1.5");
+    let components = bom["components"].as_array().unwrap();
+    assert_eq!(components.len(), 6, "{bom:#}");
+    assert_eq!(
+        purls(components, "purl"),
+        vec![
+            "pkg:cargo/serde@1.0.210",
+            "pkg:golang/golang.org/x/net@v0.30.0",
+            "pkg:npm/%40angular/core@17.0.0",
+            "pkg:npm/lodash@4.17.21",
+            "pkg:pypi/django-filter@23.5",
+            "pkg:pypi/requests@2.31.0",
+        ]
+    );
+    assert!(components.iter().all(|c| c["type"] == "library"));
+}
+
+#[test]
+fn sbom_spdx_to_stdout_uses_purl_external_refs() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+
+    let (stdout, stderr, code) = run(
+        &["sbom", "--format", "spdx", "--lang", "node", "-", "project"],
+        tmp.path(),
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+
+    let doc: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(doc["spdxVersion"], "SPDX-2.3");
+    let locators: Vec<String> = doc["packages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| {
+            assert_eq!(p["externalRefs"][0]["referenceType"], "purl");
+            p["externalRefs"][0]["referenceLocator"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(
+        locators,
+        vec!["pkg:npm/%40angular/core@17.0.0", "pkg:npm/lodash@4.17.21"]
+    );
+}
+
+#[test]
+fn sbom_rejects_non_sbom_format() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+
+    let (_stdout, stderr, code) = run(&["sbom", "--format", "sarif", "-", "project"], tmp.path());
+    assert_ne!(code, 0);
+    assert!(stderr.contains("cyclonedx"), "stderr: {stderr}");
+}
+
+#[test]
+fn emit_purls_prints_one_purl_per_line() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+
+    let (stdout, stderr, code) = run(&["--emit-purls", "project"], tmp.path());
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec![
+            "pkg:cargo/serde@1.0.210",
+            "pkg:golang/golang.org/x/net@v0.30.0",
+            "pkg:npm/%40angular/core@17.0.0",
+            "pkg:npm/lodash@4.17.21",
+            "pkg:pypi/django-filter@23.5",
+            "pkg:pypi/requests@2.31.0",
+        ]
+    );
+}
+
+#[test]
+fn emit_purls_json_is_an_array_of_purls() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+
+    let (stdout, stderr, code) = run(
+        &[
+            "--emit-purls",
+            "--format",
+            "json",
+            "--lang",
+            "node",
+            "project",
+        ],
+        tmp.path(),
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let purls: Vec<String> = serde_json::from_str(&stdout).expect(&stdout);
+    assert_eq!(
+        purls,
+        vec!["pkg:npm/%40angular/core@17.0.0", "pkg:npm/lodash@4.17.21"]
+    );
+}
@@ -0,0 +1,99 @@
+//! End-to-end `setup.py` runs, resolved through `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{ "pypi": { "requests": "2.32.3", "flask": "3.0.3", "pytest": "8.3.2" } }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+const SETUP_PY: &str = r#"from setuptools import setup
+
+setup(
+    name="demo",
+    install_requires=[
+        "requests>=2.31.0",
+        "flask==2.0.0",
+        *read_requirements("extra.txt"),
+    ],
+    extras_require={"test": ["pytest==8.0.0"]},
+)
+"#;
+
+#[test]
+fn setup_py_literals_are_updated_and_dynamic_entries_reported() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("setup.py"), SETUP_PY).unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &["--apply", "--no-cache", "--format", "json", &path_str],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("setup.py")).unwrap(),
+        SETUP_PY
+            .replace("requests>=2.31.0", "requests>=2.32.3")
+            .replace("flask==2.0.0", "flask==3.0.3")
+            .replace("pytest==8.0.0", "pytest==8.3.2")
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stderr);
+    let file = &report["files"][0];
+    assert_eq!(file["file_type"], "setup_py", "{file}");
+    assert_eq!(report["summary"]["errors"], 0);
+    let warnings = file["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    assert!(
+        warnings[0]
+            .as_str()
+            .unwrap()
+            .contains("*read_requirements(\"extra.txt\")"),
+        "{warnings:?}"
+    );
+}
+
+#[test]
+fn align_rewrites_setup_py_requirements() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("setup.py"), SETUP_PY).unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "flask==2.3.0\n").unwrap();
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (_stdout, stderr, code) = run_with_fixture(
+        &["align", "--apply", "--format", "text", &path_str],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(
+        fs::read_to_string(tmp.path().join("setup.py"))
+            .unwrap()
+            .contains("\"flask==2.3.0\""),
+    );
+}
@@ -0,0 +1,136 @@
+//! `--stdin-format`: a manifest piped on stdin is updated and written to
+//! stdout, resolved through `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+const FIXTURE: &str = r#"{
+    "pypi": { "requests": "2.32.3", "flask": "3.1.0" },
+    "npm": { "left-pad": "1.3.0" },
+    "crates.io": { "serde": "1.0.219" },
+    "go-proxy": { "github.com/pkg/errors": "v0.9.1" }
+}"#;
+
+/// Pipe `input` into `upd <args>` and return (stdout, stderr, exit code).
+fn run_piped(args: &[&str], input: &str, cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, FIXTURE).unwrap();
+    let mut child = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run upd");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[test]
+fn piped_manifests_are_updated_on_stdout() {
+    let cases = [
+        (
+            "requirements",
+            "requests==2.28.0\nflask>=2.0.0\n",
+            "requests==2.32.3\nflask>=3.1.0\n",
+        ),
+        (
+            "pyproject",
+            "[project]\nname = \"demo\"\ndependencies = [\"requests>=2.28.0\"]\n",
+            "[project]\nname = \"demo\"\ndependencies = [\"requests>=2.32.3\"]\n",
+        ),
+        (
+            "package_json",
+            "{\n  \"dependencies\": {\n    \"left-pad\": \"^1.1.0\"\n  }\n}\n",
+            "{\n  \"dependencies\": {\n    \"left-pad\": \"^1.3.0\"\n  }\n}\n",
+        ),
+        (
+            "cargo_toml",
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = \"1.0.100\"\n",
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = \"1.0.219\"\n",
+        ),
+        (
+            "go_mod",
+            "module example.com/demo\n\ngo 1.21\n\nrequire github.com/pkg/errors v0.8.0\n",
+            "module example.com/demo\n\ngo 1.21\n\nrequire github.com/pkg/errors v0.9.1\n",
+        ),
+    ];
+
+    for (file_type, input, expected) in cases {
+        let tmp = tempfile::tempdir().unwrap();
+        let (stdout, stderr, code) = run_piped(
+            &[
+                "update",
+                "-",
+                "--stdin-format",
+                file_type,
+                "--no-cache",
+                "--format",
+                "text",
+            ],
+            input,
+            tmp.path(),
+        );
+
+        assert_eq!(code, 0, "{file_type}: stderr: {stderr}");
+        assert_eq!(stdout, expected, "{file_type}: stderr: {stderr}");
+        let entries: Vec<_> = fs::read_dir(tmp.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert!(
+            entries.iter().all(|name| name == "fixture.json"),
+            "{file_type}: nothing but the fixture may be written: {entries:?}"
+        );
+    }
+}
+
+#[test]
+fn piped_manifest_without_updates_is_echoed() {
+    let tmp = tempfile::tempdir().unwrap();
+    let input = "# pinned\nrequests==2.32.3\n";
+    let (stdout, stderr, code) = run_piped(
+        &["-", "--file-type", "requirements", "--no-cache"],
+        input,
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(stdout, input);
+}
+
+#[test]
+fn stdin_format_requires_dash_as_the_only_path() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("requirements.txt"), "requests==2.28.0\n").unwrap();
+    let path = tmp.path().to_str().unwrap().to_string();
+    let (stdout, stderr, code) = run_piped(
+        &["update", &path, "--stdin-format", "requirements"],
+        "requests==2.28.0\n",
+        tmp.path(),
+    );
+
+    assert_ne!(code, 0);
+    assert!(stdout.is_empty(), "stdout: {stdout}");
+    assert!(stderr.contains("pass `-`"), "stderr: {stderr}");
+}
@@ -0,0 +1,159 @@
+//! End-to-end `--strategy` runs, resolved through `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+const FIXTURE: &str = r#"{
+    "pypi": {
+        "requests": {
+            "latest": "3.0.0",
+            "matching": { ">=2.28.0,<3": "2.32.3" },
+            "versions": ["2.28.0", "2.32.3", "3.0.0"]
+        }
+    },
+    "npm": {
+        "left-pad": {
+            "latest": "1.3.0",
+            "matching": { "^0.4.1": "0.4.9" },
+            "versions": ["0.4.1", "0.4.9", "1.3.0"]
+        }
+    },
+    "crates.io": {
+        "serde": {
+            "latest": "2.0.0",
+            "matching": { "^1.0.100": "1.0.219" },
+            "versions": ["1.0.100", "1.0.219", "2.0.0"]
+        }
+    }
+}"#;
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(&fixture_path, FIXTURE).unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .env("UV_INDEX_URL", "http://127.0.0.1:1")
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn write_manifests(dir: &Path) {
+    fs::write(dir.join("requirements.txt"), "requests==2.28.0\n").unwrap();
+    fs::write(
+        dir.join("package.json"),
+        "{\n  \"dependencies\": {\n    \"left-pad\": \"0.4.1\"\n  }\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = \"1.0.100\"\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn compatible_strategy_stays_within_current_major() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_manifests(tmp.path());
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--format",
+            "text",
+            "--strategy",
+            "compatible",
+            &path_str,
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("requirements.txt")).unwrap(),
+        "requests==2.32.3\n"
+    );
+    assert!(
+        fs::read_to_string(tmp.path().join("package.json"))
+            .unwrap()
+            .contains("\"left-pad\": \"0.4.9\"")
+    );
+    assert!(
+        fs::read_to_string(tmp.path().join("Cargo.toml"))
+            .unwrap()
+            .contains("serde = \"1.0.219\"")
+    );
+}
+
+#[test]
+fn latest_strategy_is_the_default() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_manifests(tmp.path());
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stderr, code) = run_with_fixture(
+        &["--apply", "--no-cache", "--format", "text", &path_str],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("requirements.txt")).unwrap(),
+        "requests==3.0.0\n"
+    );
+    assert!(
+        fs::read_to_string(tmp.path().join("Cargo.toml"))
+            .unwrap()
+            .contains("serde = \"2.0.0\"")
+    );
+}
+
+#[test]
+fn n_minus_one_strategy_stays_one_release_behind_the_newest() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_manifests(tmp.path());
+    let path_str = tmp.path().to_str().unwrap().to_string();
+
+    let (stderr, code) = run_with_fixture(
+        &[
+            "--apply",
+            "--no-cache",
+            "--format",
+            "text",
+            "--strategy",
+            "n-1",
+            &path_str,
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("requirements.txt")).unwrap(),
+        "requests==2.32.3\n"
+    );
+    assert!(
+        fs::read_to_string(tmp.path().join("package.json"))
+            .unwrap()
+            .contains("\"left-pad\": \"0.4.9\"")
+    );
+    assert!(
+        fs::read_to_string(tmp.path().join("Cargo.toml"))
+            .unwrap()
+            .contains("serde = \"1.0.219\"")
+    );
+}
@@ -0,0 +1,74 @@
+//! `--summary-json`: a compact counts file is written next to the normal
+//! output. Registries resolve from `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{"pypi": {"flask": "3.0.3", "requests": "2.32.3"}, "npm": {"react": "18.3.1"}}"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+#[test]
+fn summary_json_written_alongside_text_output() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = tmp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("requirements.txt"),
+        "flask==2.0.0\nrequests==2.32.0\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("package.json"),
+        r#"{"dependencies": {"react": "^18.2.0"}}"#,
+    )
+    .unwrap();
+    let summary_path = tmp.path().join("summary.json");
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--no-cache",
+            "--format",
+            "text",
+            "--summary-json",
+            summary_path.to_str().unwrap(),
+            project.to_str().unwrap(),
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 1, "stderr: {stderr}");
+    assert!(stdout.contains("flask"), "text output expected: {stdout}");
+    let summary: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&summary_path).unwrap()).unwrap();
+    assert_eq!(summary["mode"], "dry-run");
+    assert_eq!(summary["total"]["updated"], 3);
+    assert_eq!(summary["total"]["major"], 1);
+    assert_eq!(summary["total"]["minor"], 1);
+    assert_eq!(summary["total"]["patch"], 1);
+    assert_eq!(summary["ecosystems"]["python"]["updated"], 2);
+    assert_eq!(summary["ecosystems"]["node"]["minor"], 1);
+    assert!(summary.get("files").is_none());
+}
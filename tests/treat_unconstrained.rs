@@ -0,0 +1,117 @@
+//! `--treat-unconstrained-as-error`: dependencies without a lower bound fail
+//! the run. Registries resolve from `UPD_REGISTRY_FIXTURE`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn upd_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_upd")
+}
+
+fn run_with_fixture(args: &[&str], cwd: &Path) -> (String, String, i32) {
+    let fixture_path = cwd.join("fixture.json");
+    fs::write(
+        &fixture_path,
+        r#"{
+            "pypi": { "requests": "2.32.3", "flask": "3.0.0" },
+            "npm": { "express": "4.19.2", "react": "18.3.1" }
+        }"#,
+    )
+    .unwrap();
+    let output = Command::new(upd_bin())
+        .args(args)
+        .current_dir(cwd)
+        .env("UPD_CACHE_DIR", cwd.join(".cache").to_str().unwrap())
+        .env("UPD_REGISTRY_FIXTURE", &fixture_path)
+        .output()
+        .expect("failed to run upd");
+    (
+        String::from_utf8(output.stdout).expect("stdout not UTF-8"),
+        String::from_utf8(output.stderr).expect("stderr not UTF-8"),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+fn write_project(dir: &Path, requirements: &str, package_json: &str) -> String {
+    let project = dir.join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(project.join("requirements.txt"), requirements).unwrap();
+    fs::write(project.join("package.json"), package_json).unwrap();
+    project.to_str().unwrap().to_string()
+}
+
+#[test]
+fn unconstrained_dependencies_are_reported_with_file_and_line() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(
+        tmp.path(),
+        "flask==3.0.0\nrequests\n",
+        "{\n  \"dependencies\": {\n    \"react\": \"^18.3.1\",\n    \"express\": \"*\"\n  }\n}\n",
+    );
+
+    let (stdout, stderr, code) = run_with_fixture(
+        &[
+            "--no-cache",
+            "--format",
+            "json",
+            "--treat-unconstrained-as-error",
+            &project,
+        ],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 2, "stderr: {stderr}");
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let mut errors: Vec<String> = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|f| f["errors"].as_array().unwrap().clone())
+        .map(|e| e["message"].as_str().unwrap().to_string())
+        .collect();
+    errors.sort();
+    assert_eq!(errors.len(), 2, "{errors:?}");
+    assert!(
+        errors[0].starts_with("express: declared without a lower bound at ")
+            && errors[0].ends_with("package.json:4 (\"*\")"),
+        "{errors:?}"
+    );
+    assert!(
+        errors[1].starts_with("requests: declared without a lower bound at ")
+            && errors[1].ends_with("requirements.txt:2 (no version specifier)"),
+        "{errors:?}"
+    );
+}
+
+#[test]
+fn constrained_dependencies_pass() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(
+        tmp.path(),
+        "flask==3.0.0\nrequests>=2.32\n",
+        r#"{"dependencies": {"react": "^18.3.1", "express": ">=4.19.2"}}"#,
+    );
+
+    let (_stdout, stderr, code) = run_with_fixture(
+        &["--no-cache", "--treat-unconstrained-as-error", &project],
+        tmp.path(),
+    );
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(!stderr.contains("lower bound"), "stderr: {stderr}");
+}
+
+#[test]
+fn unconstrained_dependencies_are_allowed_without_the_flag() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = write_project(
+        tmp.path(),
+        "flask==3.0.0\nrequests\n",
+        r#"{"dependencies": {"express": "*"}}"#,
+    );
+
+    let (_stdout, stderr, code) = run_with_fixture(&["--no-cache", &project], tmp.path());
+
+    assert_eq!(code, 0, "stderr: {stderr}");
+}